@@ -0,0 +1,52 @@
+//! MSM and FFT time of every `accel` backend at the sizes a `2^k`-row proof
+//! commits to, `cargo bench --bench accel`.
+
+use std::time::{Duration, Instant};
+
+use fibonacci_circuit::accel::backends;
+use halo2_proofs::{
+    pasta::{group::ff::Field, EqAffine, Fp},
+    poly::{commitment::Params, EvaluationDomain},
+};
+use rand_core::OsRng;
+
+const KS: [u32; 3] = [10, 14, 16];
+const RUNS: u32 = 5;
+
+fn time(mut f: impl FnMut()) -> Duration {
+    // The first run warms up the allocator and caches.
+    f();
+    let mut fastest = Duration::MAX;
+    for _ in 0..RUNS {
+        let start = Instant::now();
+        f();
+        fastest = fastest.min(start.elapsed());
+    }
+    fastest
+}
+
+fn main() {
+    for k in KS {
+        let bases = Params::<EqAffine>::new(k).get_g();
+        let coeffs: Vec<Fp> = (0..1 << k).map(|_| Fp::random(OsRng)).collect();
+        let omega = EvaluationDomain::<Fp>::new(1, k).get_omega();
+
+        for backend in backends() {
+            let msm = time(|| {
+                backend.msm(&coeffs, &bases);
+            });
+            let fft = time(|| {
+                let mut values = coeffs.clone();
+                backend.fft(&mut values, omega, k);
+            });
+            println!(
+                "{} at k = {}: msm {:?}, fft {:?}, fastest of {} runs",
+                backend.name(),
+                k,
+                msm,
+                fft,
+                RUNS
+            );
+        }
+    }
+}
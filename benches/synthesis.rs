@@ -0,0 +1,37 @@
+//! Synthesis time of a long three-column chain, `cargo bench --bench
+//! synthesis`. Only the layouter runs; there is no proving.
+
+use std::time::{Duration, Instant};
+
+use fibonacci_circuit::{chunked, introspect::witness::Witness};
+use halo2_proofs::pasta::Fp;
+
+/// Steps in the chain, and the smallest `k` they fit in.
+const STEPS: usize = 4000;
+const K: u32 = 12;
+const RUNS: u32 = 20;
+
+fn main() {
+    let (circuit, instances) = chunked::chunk::<STEPS>(Fp::one(), Fp::one());
+
+    // The first run warms up the allocator and caches.
+    Witness::synthesize(K, &circuit, instances.clone()).expect("the chain fits in K");
+    let mut total = Duration::ZERO;
+    let mut fastest = Duration::MAX;
+    for _ in 0..RUNS {
+        let start = Instant::now();
+        Witness::synthesize(K, &circuit, instances.clone()).unwrap();
+        let elapsed = start.elapsed();
+        total += elapsed;
+        fastest = fastest.min(elapsed);
+    }
+
+    println!(
+        "synthesize {} steps at k = {}: mean {:?}, fastest {:?} over {} runs",
+        STEPS,
+        K,
+        total / RUNS,
+        fastest,
+        RUNS
+    );
+}
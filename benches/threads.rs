@@ -0,0 +1,40 @@
+//! Proving time of a long three-column chain against the number of
+//! threads, `cargo bench --bench threads`.
+
+use std::time::{Duration, Instant};
+
+use fibonacci_circuit::{chunked, prover};
+use halo2_proofs::{pasta::Fp, poly::commitment::Params};
+
+/// Steps in the chain, and the smallest `k` they fit in.
+const STEPS: usize = 1000;
+const K: u32 = 10;
+const RUNS: u32 = 3;
+
+fn main() {
+    let params = Params::new(K);
+    let (circuit, instances) = chunked::chunk::<STEPS>(Fp::one(), Fp::one());
+    let pk = prover::keygen(&params, &circuit).expect("the chain fits in K");
+
+    let cores = rayon::current_num_threads();
+    let mut threads: Vec<usize> = [1, 2, 4, 8].into_iter().filter(|&n| n < cores).collect();
+    threads.push(cores);
+
+    for threads in threads {
+        let fastest = prover::with_threads(threads, || {
+            let mut fastest = Duration::MAX;
+            for _ in 0..RUNS {
+                let (circuit, _) = chunked::chunk::<STEPS>(Fp::one(), Fp::one());
+                let start = Instant::now();
+                prover::create_proof(&params, &pk, circuit, &instances).unwrap();
+                fastest = fastest.min(start.elapsed());
+            }
+            fastest
+        })
+        .unwrap();
+        println!(
+            "prove {} steps at k = {} on {} threads: fastest {:?} over {} runs",
+            STEPS, K, threads, fastest, RUNS
+        );
+    }
+}
@@ -0,0 +1,57 @@
+use std::{fmt, process};
+
+use clap::Parser;
+use fibonacci_circuit::registry::{CircuitKind, CircuitVisitor};
+use halo2_proofs::{
+    dev::{CircuitLayout, MockProver},
+    pasta::Fp,
+    plonk::Circuit,
+};
+use plotters::prelude::*;
+
+/// Checks a registered circuit's demo statement with the `MockProver` and
+/// renders its layout to `<circuit>-layout.png`.
+#[derive(Parser)]
+struct Args {
+    /// Circuit name, e.g. `fib-vertical`.
+    circuit: CircuitKind,
+    #[arg(long, default_value_t = 4)]
+    k: u32,
+}
+
+struct Render<'a>(&'a Args);
+
+impl CircuitVisitor for Render<'_> {
+    type Output = Result<(), String>;
+
+    fn visit<C: Circuit<Fp> + fmt::Debug>(
+        self,
+        circuit: C,
+        instances: Vec<Vec<Fp>>,
+    ) -> Self::Output {
+        let Args { circuit: kind, k } = self.0;
+        let prover = MockProver::run(*k, &circuit, instances)
+            .map_err(|e| format!("{} does not fit in k = {}: {:?}", kind, k, e))?;
+        prover
+            .verify()
+            .map_err(|failures| format!("{} is not satisfied: {:?}", kind, failures))?;
+
+        let path = format!("{}-layout.png", kind);
+        let root = BitMapBackend::new(&path, (1024, 768)).into_drawing_area();
+        root.fill(&WHITE).unwrap();
+        let root = root.titled(kind.name(), ("sans-serif", 60)).unwrap();
+        CircuitLayout::default()
+            .render(*k, &circuit, &root)
+            .unwrap();
+        println!("{} holds at k = {}; layout in {}", kind, k, path);
+        Ok(())
+    }
+}
+
+fn main() {
+    let args = Args::parse();
+    if let Err(e) = args.circuit.visit(Render(&args)) {
+        eprintln!("error: {}", e);
+        process::exit(1);
+    }
+}
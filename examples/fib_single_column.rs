@@ -0,0 +1,27 @@
+use std::process;
+
+use clap::Parser;
+use fibonacci_circuit::{example::ExampleArgs, fibonacci::single_column::FiboTermsCircuit};
+
+fn main() {
+    let args = ExampleArgs::parse();
+    let (a, b) = args.seeds();
+    let fibo_circuit = FiboTermsCircuit { a, b, n: args.n };
+
+    if let Err(e) = args.check(&fibo_circuit) {
+        eprintln!("error: {}", e);
+        process::exit(1);
+    }
+    println!("{}", args.summary());
+
+    // Plot the circuit
+    use plotters::prelude::*;
+    let root = BitMapBackend::new("fib-single-column-layout.png", (1024, 768)).into_drawing_area();
+    root.fill(&WHITE).unwrap();
+    let root = root
+        .titled("fib-single-column", ("sans-serif", 60))
+        .unwrap();
+    halo2_proofs::dev::CircuitLayout::default()
+        .render(args.k, &fibo_circuit, &root)
+        .unwrap();
+}
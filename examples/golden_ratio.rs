@@ -0,0 +1,35 @@
+use fibonacci_circuit::{
+    fibonacci::golden_ratio::GoldenRatioCircuit,
+    gadgets::fixed_point::{from_raw, to_raw},
+};
+use halo2_proofs::{dev::MockProver, pasta::Fp};
+
+/// Whether the circuit accepts `|F(N+1)/F(N) - φ| < epsilon`.
+fn holds<const N: usize>(epsilon: f64) -> bool {
+    let k = 10;
+    let instance = vec![Fp::from(to_raw(epsilon))];
+    let prover = MockProver::run(k, &GoldenRatioCircuit::<N>, vec![instance]).unwrap();
+    prover.verify().is_ok()
+}
+
+fn report<const N: usize>() {
+    let phi = (1.0 + 5f64.sqrt()) / 2.0;
+    let (mut a, mut b) = (1u64, 1u64);
+    for _ in 1..N {
+        (a, b) = (b, a + b);
+    }
+    let error = (b as f64 / a as f64 - phi).abs();
+    print!("N = {:>2}: |F(N+1)/F(N) - phi| = {:.3e};", N, error);
+    for epsilon in [1e-2, 1e-4, 1e-6, 1e-8] {
+        let mark = if holds::<N>(epsilon) { "<" } else { ">=" };
+        print!(" {} {:.0e}", mark, from_raw(to_raw(epsilon)));
+    }
+    println!();
+}
+
+fn main() {
+    report::<5>();
+    report::<10>();
+    report::<20>();
+    report::<40>();
+}
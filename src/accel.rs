@@ -0,0 +1,80 @@
+//! The two operations that dominate proving time, multi-scalar
+//! multiplication and the FFT, behind a [`Backend`] trait so another
+//! implementation can be swapped in and benchmarked against the CPU one
+//! (`cargo bench --bench accel`).
+//!
+//! Only [`Cpu`] exists, wrapping halo2's own `best_multiexp` and
+//! `best_fft`. halo2_proofs 0.2 calls those functions directly rather
+//! than through a hook, so a backend here speeds up code written against
+//! this trait, not [`crate::prover`]; routing the prover through it needs
+//! a halo2 fork that exposes one. GPU libraries such as icicle have no
+//! binding for this fork's Pasta curves, which is why there is no GPU
+//! feature: a backend for one would implement [`Backend`] behind its own
+//! feature and add itself to [`backends`].
+
+use std::{error, fmt};
+
+use halo2_proofs::{
+    arithmetic::{best_fft, best_multiexp},
+    pasta::{Eq, EqAffine, Fp},
+};
+
+pub trait Backend {
+    fn name(&self) -> &'static str;
+
+    /// `Σ coeffs[i] · bases[i]`.
+    fn msm(&self, coeffs: &[Fp], bases: &[EqAffine]) -> Eq;
+
+    /// Evaluates the polynomial with coefficients `values` over the powers
+    /// of `omega`, a primitive `2^log_n`-th root of unity, in place.
+    fn fft(&self, values: &mut [Fp], omega: Fp, log_n: u32);
+}
+
+/// halo2's multi-threaded CPU implementation.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Cpu;
+
+impl Backend for Cpu {
+    fn name(&self) -> &'static str {
+        "cpu"
+    }
+
+    fn msm(&self, coeffs: &[Fp], bases: &[EqAffine]) -> Eq {
+        assert_eq!(coeffs.len(), bases.len(), "one coefficient per base");
+        best_multiexp(coeffs, bases)
+    }
+
+    fn fft(&self, values: &mut [Fp], omega: Fp, log_n: u32) {
+        assert_eq!(values.len(), 1 << log_n, "2^log_n values");
+        best_fft(values, omega, log_n)
+    }
+}
+
+/// Every backend compiled in, [`Cpu`] first.
+pub fn backends() -> Vec<Box<dyn Backend>> {
+    vec![Box::new(Cpu)]
+}
+
+/// The backend called `name`.
+pub fn select(name: &str) -> Result<Box<dyn Backend>, UnknownBackend> {
+    backends()
+        .into_iter()
+        .find(|backend| backend.name() == name)
+        .ok_or_else(|| UnknownBackend(name.to_string()))
+}
+
+/// Returned when a backend name does not match any compiled-in backend.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownBackend(pub String);
+
+impl fmt::Display for UnknownBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown backend `{}`, expected one of:", self.0)?;
+        for backend in backends() {
+            write!(f, " {}", backend.name())?;
+        }
+        Ok(())
+    }
+}
+
+impl error::Error for UnknownBackend {}
@@ -0,0 +1,115 @@
+//! The native half of aggregating many proofs of one circuit: their
+//! instances flattened into the single public input an aggregation proof
+//! would expose, and the proofs checked together with one MSM.
+//!
+//! An IPA proof's verifier ends in a multi-scalar multiplication over the
+//! parameters' generators, which dominates its cost. [`verify_aggregate`]
+//! runs the rest of each verifier and folds each MSM into an accumulator
+//! with a random factor, so the whole batch costs one MSM.
+//!
+//! That is the accumulation step; the second layer, a circuit that runs the
+//! verifiers and emits one small proof, is not here. It needs an in-circuit
+//! verifier for the inner proofs: snark-verifier's for KZG over BN254, which
+//! halo2_proofs 0.2 does not support, or an IPA accumulation verifier over
+//! the Pasta cycle, which it does not provide.
+
+use std::io;
+
+use halo2_proofs::{
+    pasta::{group::ff::Field, EqAffine, Fp},
+    plonk::{self, Error, VerificationStrategy, VerifyingKey},
+    poly::commitment::{Guard, Params, MSM},
+    transcript::{Blake2bRead, Challenge255, EncodedChallenge},
+};
+use rand_core::OsRng;
+use rayon::prelude::*;
+
+/// A proof and the instance columns it was made against.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Proof {
+    pub bytes: Vec<u8>,
+    pub instances: Vec<Vec<Fp>>,
+}
+
+/// The instances of `proofs` as one column: the number of proofs, then for
+/// each proof in order its instance columns in order, each prefixed by its
+/// length so different splits of the same values cannot flatten alike.
+pub fn flatten_instances(proofs: &[Proof]) -> Vec<Fp> {
+    let mut flat = vec![Fp::from(proofs.len() as u64)];
+    for proof in proofs {
+        for column in &proof.instances {
+            flat.push(Fp::from(column.len() as u64));
+            flat.extend(column);
+        }
+    }
+    flat
+}
+
+/// Whether every proof in `proofs` verifies under `vk`. On `false` at least
+/// one failed; which one takes verifying them one at a time.
+///
+/// This is halo2's `BatchVerifier`, except that each transcript is read
+/// from a slice so bytes left over after a proof fail the batch, as they
+/// fail [`prover::verify_batch_proof`](crate::prover::verify_batch_proof).
+pub fn verify_aggregate(
+    params: &Params<EqAffine>,
+    vk: &VerifyingKey<EqAffine>,
+    proofs: &[Proof],
+) -> bool {
+    let msms = proofs
+        .par_iter()
+        .map(|proof| proof_msm(params, vk, proof))
+        .collect::<Result<Vec<_>, _>>();
+    match msms {
+        Ok(msms) => msms
+            .into_iter()
+            .fold(params.empty_msm(), |mut acc, msm| {
+                // A random factor, so a failing proof's MSM cannot cancel
+                // another's.
+                acc.scale(Fp::random(OsRng));
+                acc.add_msm(&msm);
+                acc
+            })
+            .eval(),
+        Err(_) => false,
+    }
+}
+
+/// Runs the verifier on `proof` up to its final MSM, which it returns.
+fn proof_msm<'params>(
+    params: &'params Params<EqAffine>,
+    vk: &VerifyingKey<EqAffine>,
+    proof: &Proof,
+) -> Result<MSM<'params, EqAffine>, Error> {
+    let columns: Vec<&[Fp]> = proof.instances.iter().map(Vec::as_slice).collect();
+    let mut rest = &proof.bytes[..];
+    let mut transcript = Blake2bRead::<_, EqAffine, Challenge255<_>>::init(&mut rest);
+    let msm = plonk::verify_proof(
+        params,
+        vk,
+        Accumulate(params.empty_msm()),
+        &[&columns],
+        &mut transcript,
+    )?;
+    if !rest.is_empty() {
+        return Err(Error::Transcript(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{} bytes after the proof", rest.len()),
+        )));
+    }
+    Ok(msm)
+}
+
+/// Returns a proof's MSM instead of evaluating it.
+struct Accumulate<'params>(MSM<'params, EqAffine>);
+
+impl<'params> VerificationStrategy<'params, EqAffine> for Accumulate<'params> {
+    type Output = MSM<'params, EqAffine>;
+
+    fn process<E: EncodedChallenge<EqAffine>>(
+        self,
+        f: impl FnOnce(MSM<'params, EqAffine>) -> Result<Guard<'params, EqAffine, E>, Error>,
+    ) -> Result<Self::Output, Error> {
+        Ok(f(self.0)?.use_challenges())
+    }
+}
@@ -0,0 +1,57 @@
+use std::{error::Error, fmt};
+
+use fibonacci_circuit::{
+    cost::CostReport,
+    registry::{CircuitKind, CircuitVisitor},
+};
+use halo2_proofs::{pasta::Fp, plonk::Circuit};
+
+use crate::stdio;
+
+#[derive(clap::Args)]
+pub struct Args {
+    /// Circuit name, e.g. `fib-three-column`.
+    circuit: CircuitKind,
+    #[arg(long, default_value_t = 4)]
+    k: u32,
+    /// Print the report as JSON.
+    #[arg(long)]
+    json: bool,
+    /// Write the report to standard output as CBOR.
+    #[arg(long, conflicts_with = "json")]
+    cbor: bool,
+}
+
+pub fn run(args: Args) -> Result<(), Box<dyn Error>> {
+    let report = args.circuit.visit(Measure {
+        kind: args.circuit,
+        k: args.k,
+    })?;
+
+    if args.cbor {
+        stdio::write_cbor(&report)?;
+    } else if args.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        println!("{}", report);
+    }
+    Ok(())
+}
+
+struct Measure {
+    kind: CircuitKind,
+    k: u32,
+}
+
+impl CircuitVisitor for Measure {
+    type Output = Result<CostReport, Box<dyn Error>>;
+
+    fn visit<C: Circuit<Fp> + fmt::Debug>(
+        self,
+        circuit: C,
+        instances: Vec<Vec<Fp>>,
+    ) -> Self::Output {
+        CostReport::try_measure(self.kind.name(), self.k, &circuit, instances)
+            .map_err(|e| format!("{} does not fit in k = {}: {:?}", self.kind, self.k, e).into())
+    }
+}
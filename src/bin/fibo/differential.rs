@@ -0,0 +1,61 @@
+use std::{error::Error, path::PathBuf};
+
+use fibonacci_circuit::differential;
+use rand_core::{OsRng, RngCore};
+
+use crate::{params, stdio, Rejected};
+
+#[derive(clap::Args)]
+pub struct Args {
+    /// Cases to compare.
+    #[arg(long, default_value_t = 100)]
+    cases: usize,
+    /// Seed of the first case, as for `fibo soak`; random if absent.
+    #[arg(long)]
+    seed: Option<u64>,
+    #[arg(long, default_value_t = 4)]
+    k: u32,
+    /// Parameters file from `fibo params gen`; generated on the fly if absent.
+    #[arg(long)]
+    params: Option<PathBuf>,
+    /// Print the report as JSON.
+    #[arg(long)]
+    json: bool,
+    /// Write the report to standard output as CBOR.
+    #[arg(long, conflicts_with = "json")]
+    cbor: bool,
+}
+
+pub fn run(args: Args) -> Result<(), Box<dyn Error>> {
+    let seed = args.seed.unwrap_or_else(|| OsRng.next_u64());
+    let params = params::load_or_generate(args.params.as_ref(), args.k)?;
+    eprintln!("comparing {} cases from seed {}", args.cases, seed);
+
+    let report = differential::differential(&params, args.k, seed, args.cases, |i, comparison| {
+        if !comparison.agrees() {
+            eprintln!("case {} (seed {}) disagreed", i, comparison.case.seed);
+        }
+        if (i + 1) % 100 == 0 {
+            eprintln!("{} of {} cases", i + 1, args.cases);
+        }
+    });
+
+    if args.cbor {
+        stdio::write_cbor(&report)?;
+    } else if args.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print!("{}", report);
+    }
+
+    if report.passed() {
+        Ok(())
+    } else {
+        Err(Rejected(format!(
+            "{} of {} cases disagreed",
+            report.disagreements.len(),
+            report.cases
+        ))
+        .into())
+    }
+}
@@ -0,0 +1,82 @@
+use std::{error::Error, fmt, ops::Range};
+
+use fibonacci_circuit::{
+    explain::Explanation,
+    introspect::witness::Witness,
+    registry::{CircuitKind, CircuitVisitor},
+};
+use halo2_proofs::{pasta::Fp, plonk::Circuit};
+
+use crate::stdio;
+
+#[derive(clap::Args)]
+pub struct Args {
+    /// Circuit name, e.g. `fib-three-column`.
+    circuit: CircuitKind,
+    #[arg(long, default_value_t = 4)]
+    k: u32,
+    /// Rows to explain, as `start..end` or a single row.
+    #[arg(long, default_value = "0..4", value_parser = parse_rows)]
+    rows: Range<usize>,
+    /// Only explain gates whose name contains this string.
+    #[arg(long)]
+    gate: Option<String>,
+    /// Print the report as JSON.
+    #[arg(long)]
+    json: bool,
+    /// Write the report to standard output as CBOR.
+    #[arg(long, conflicts_with = "json")]
+    cbor: bool,
+}
+
+pub fn run(args: Args) -> Result<(), Box<dyn Error>> {
+    let explanation = args.circuit.visit(Explain {
+        kind: args.circuit,
+        k: args.k,
+        rows: args.rows,
+        filter: args.gate.as_deref(),
+    })?;
+
+    if args.cbor {
+        stdio::write_cbor(&explanation)?;
+    } else if args.json {
+        println!("{}", serde_json::to_string_pretty(&explanation)?);
+    } else {
+        println!("{}", explanation);
+    }
+    Ok(())
+}
+
+fn parse_rows(s: &str) -> Result<Range<usize>, String> {
+    let parse = |n: &str| n.parse::<usize>().map_err(|e| format!("`{}`: {}", n, e));
+    match s.split_once("..") {
+        Some((start, end)) => Ok(parse(start)?..parse(end)?),
+        None => parse(s).map(|row| row..row + 1),
+    }
+}
+
+struct Explain<'a> {
+    kind: CircuitKind,
+    k: u32,
+    rows: Range<usize>,
+    filter: Option<&'a str>,
+}
+
+impl CircuitVisitor for Explain<'_> {
+    type Output = Result<Explanation, Box<dyn Error>>;
+
+    fn visit<C: Circuit<Fp> + fmt::Debug>(
+        self,
+        circuit: C,
+        instances: Vec<Vec<Fp>>,
+    ) -> Self::Output {
+        let witness = Witness::synthesize(self.k, &circuit, instances)
+            .map_err(|e| format!("{} does not fit in k = {}: {:?}", self.kind, self.k, e))?;
+        Ok(Explanation::of(
+            self.kind.name(),
+            &witness,
+            self.rows,
+            self.filter,
+        ))
+    }
+}
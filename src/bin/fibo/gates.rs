@@ -0,0 +1,53 @@
+use std::{error::Error, fmt};
+
+use fibonacci_circuit::{
+    gates::GateReport,
+    registry::{CircuitKind, CircuitVisitor},
+};
+use halo2_proofs::{pasta::Fp, plonk::Circuit};
+
+use crate::stdio;
+
+#[derive(clap::Args)]
+pub struct Args {
+    /// Circuit name, e.g. `fib-three-column`.
+    circuit: CircuitKind,
+    /// Only list gates whose name contains this string.
+    #[arg(long)]
+    gate: Option<String>,
+    /// Print the report as JSON.
+    #[arg(long)]
+    json: bool,
+    /// Write the report to standard output as CBOR.
+    #[arg(long, conflicts_with = "json")]
+    cbor: bool,
+}
+
+pub fn run(args: Args) -> Result<(), Box<dyn Error>> {
+    let report = args.circuit.visit(Collect {
+        kind: args.circuit,
+        filter: args.gate.as_deref(),
+    });
+
+    if args.cbor {
+        stdio::write_cbor(&report)?;
+    } else if args.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        println!("{}", report);
+    }
+    Ok(())
+}
+
+struct Collect<'a> {
+    kind: CircuitKind,
+    filter: Option<&'a str>,
+}
+
+impl CircuitVisitor for Collect<'_> {
+    type Output = GateReport;
+
+    fn visit<C: Circuit<Fp> + fmt::Debug>(self, _: C, _: Vec<Vec<Fp>>) -> Self::Output {
+        GateReport::collect::<C>(self.kind.name(), self.filter)
+    }
+}
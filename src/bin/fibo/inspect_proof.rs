@@ -0,0 +1,36 @@
+use std::{error::Error, fs, path::PathBuf};
+
+use fibonacci_circuit::{envelope::ProofEnvelope, inspect::Inspection};
+
+use crate::{stdio, Rejected};
+
+#[derive(clap::Args)]
+pub struct Args {
+    /// Proof envelope written by `prove`.
+    proof: PathBuf,
+    /// Print the report as JSON.
+    #[arg(long)]
+    json: bool,
+    /// Write the report to standard output as CBOR.
+    #[arg(long, conflicts_with = "json")]
+    cbor: bool,
+}
+
+pub fn run(args: Args) -> Result<(), Box<dyn Error>> {
+    let envelope = ProofEnvelope::parse(&fs::read(&args.proof)?)?;
+    let inspection = Inspection::of(&envelope);
+
+    if args.cbor {
+        stdio::write_cbor(&inspection)?;
+    } else if args.json {
+        println!("{}", serde_json::to_string_pretty(&inspection)?);
+    } else {
+        println!("{}", inspection);
+    }
+
+    if inspection.passed() {
+        Ok(())
+    } else {
+        Err(Rejected("envelope failed structural checks".to_string()).into())
+    }
+}
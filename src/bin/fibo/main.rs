@@ -0,0 +1,111 @@
+//! Command-line entry point for inspecting and proving the crate's circuits.
+//!
+//! `fibo` exits with 0 on success, 1 when a proof or envelope was checked
+//! and failed, and 2 on any other error, usage errors included.
+
+use std::{error::Error, fmt, process};
+
+use clap::{Parser, Subcommand};
+
+mod copies;
+mod cost;
+mod coverage;
+mod differential;
+mod explain;
+mod gates;
+mod inspect_proof;
+mod occupancy;
+mod params;
+mod prove;
+mod soak;
+mod stdio;
+mod step;
+mod term;
+mod unconstrained;
+mod verify;
+
+#[derive(Parser)]
+#[command(
+    name = "fibo",
+    about = "Inspect and prove the Fibonacci practice circuits"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Print the cost breakdown of a circuit at a given k.
+    Cost(cost::Args),
+    /// List the gates of a circuit with their degrees and queried cells.
+    Gates(gates::Args),
+    /// Show the constraints on a range of rows evaluated on the demo
+    /// witness.
+    Explain(explain::Args),
+    /// List the cells wired together by copy constraints, by region.
+    Copies(copies::Args),
+    /// Write the rows and cells each region occupies per column as CSV.
+    Occupancy(occupancy::Args),
+    /// Flag advice cells that no gate, lookup or copy constrains.
+    Unconstrained(unconstrained::Args),
+    /// Show the rows each selector is enabled on and flag likely gaps.
+    Coverage(coverage::Args),
+    /// Synthesize a circuit interactively, pausing after each region.
+    Step(step::Args),
+    /// Print F(n) as an integer and as the circuits' field element.
+    Term(term::Args),
+    /// Prove a circuit's demo statement and write a proof envelope.
+    Prove(prove::Args),
+    /// Verify a proof envelope.
+    Verify(verify::Args),
+    /// Prove and verify random cases against the reference for as long as
+    /// asked, reporting the seed of any that fail.
+    Soak(soak::Args),
+    /// Check the MockProver and the real prover agree with the reference on
+    /// random cases, and time one against the other.
+    Differential(differential::Args),
+    /// Print a proof envelope's metadata and check its structure without
+    /// verifying it.
+    InspectProof(inspect_proof::Args),
+    /// Generate, describe and check commitment parameters.
+    Params(params::Args),
+}
+
+/// A proof or envelope that was checked and failed, as opposed to an error
+/// that kept it from being checked.
+#[derive(Debug)]
+pub struct Rejected(pub String);
+
+impl fmt::Display for Rejected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Error for Rejected {}
+
+fn main() {
+    let result: Result<(), Box<dyn Error>> = match Cli::parse().command {
+        Command::Cost(args) => cost::run(args),
+        Command::Gates(args) => gates::run(args),
+        Command::Explain(args) => explain::run(args),
+        Command::Copies(args) => copies::run(args),
+        Command::Occupancy(args) => occupancy::run(args),
+        Command::Unconstrained(args) => unconstrained::run(args),
+        Command::Coverage(args) => coverage::run(args),
+        Command::Step(args) => step::run(args),
+        Command::Term(args) => term::run(args),
+        Command::Prove(args) => prove::run(args),
+        Command::Verify(args) => verify::run(args),
+        Command::Soak(args) => soak::run(args),
+        Command::Differential(args) => differential::run(args),
+        Command::InspectProof(args) => inspect_proof::run(args),
+        Command::Params(args) => params::run(args),
+    };
+
+    if let Err(e) = result {
+        eprintln!("error: {}", e);
+        process::exit(if e.is::<Rejected>() { 1 } else { 2 });
+    }
+}
@@ -0,0 +1,69 @@
+use std::{error::Error, fmt, fs, path::PathBuf};
+
+use fibonacci_circuit::{
+    introspect::witness::Witness,
+    occupancy::OccupancyReport,
+    registry::{CircuitKind, CircuitVisitor},
+};
+use halo2_proofs::{pasta::Fp, plonk::Circuit};
+
+use crate::stdio;
+
+#[derive(clap::Args)]
+pub struct Args {
+    /// Circuit name, e.g. `fib-three-column`.
+    circuit: CircuitKind,
+    #[arg(long, default_value_t = 4)]
+    k: u32,
+    /// Write the CSV here instead of to stdout.
+    #[arg(long)]
+    output: Option<PathBuf>,
+    /// Print the report as JSON instead of CSV.
+    #[arg(long, conflicts_with = "output")]
+    json: bool,
+    /// Write the report to standard output as CBOR.
+    #[arg(long, conflicts_with_all = ["json", "output"])]
+    cbor: bool,
+}
+
+pub fn run(args: Args) -> Result<(), Box<dyn Error>> {
+    let report = args.circuit.visit(Occupancy {
+        kind: args.circuit,
+        k: args.k,
+    })?;
+
+    if args.cbor {
+        stdio::write_cbor(&report)?;
+    } else if args.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else if let Some(path) = &args.output {
+        fs::write(path, report.to_csv())?;
+        println!(
+            "wrote {} ({} entries)",
+            path.display(),
+            report.entries.len()
+        );
+    } else {
+        print!("{}", report.to_csv());
+    }
+    Ok(())
+}
+
+struct Occupancy {
+    kind: CircuitKind,
+    k: u32,
+}
+
+impl CircuitVisitor for Occupancy {
+    type Output = Result<OccupancyReport, Box<dyn Error>>;
+
+    fn visit<C: Circuit<Fp> + fmt::Debug>(
+        self,
+        circuit: C,
+        instances: Vec<Vec<Fp>>,
+    ) -> Self::Output {
+        let witness = Witness::synthesize(self.k, &circuit, instances)
+            .map_err(|e| format!("{} does not fit in k = {}: {:?}", self.kind, self.k, e))?;
+        Ok(OccupancyReport::of(self.kind.name(), &witness))
+    }
+}
@@ -0,0 +1,160 @@
+use std::{error::Error, fs, path::PathBuf};
+
+use clap::Subcommand;
+use fibonacci_circuit::{
+    params::{self, ParamsInfo},
+    ptau::{self, PtauFile, PtauInfo},
+};
+use halo2_proofs::{pasta::EqAffine, poly::commitment::Params};
+
+use crate::stdio;
+
+#[derive(clap::Args)]
+pub struct Args {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Generate parameters for a given k.
+    Gen {
+        #[arg(long)]
+        k: u32,
+        #[arg(long, short)]
+        output: PathBuf,
+    },
+    /// Describe a parameters file.
+    Info {
+        path: PathBuf,
+        /// Print the description as JSON.
+        #[arg(long)]
+        json: bool,
+        /// Write the description to standard output as CBOR.
+        #[arg(long, conflicts_with = "json")]
+        cbor: bool,
+    },
+    /// Check that a parameters file decodes and matches the generated
+    /// parameters for its k.
+    Check { path: PathBuf },
+    /// Describe a Powers of Tau `.ptau` file and check its layout.
+    PtauInfo {
+        path: PathBuf,
+        /// Also check the file reaches this k.
+        #[arg(long)]
+        k: Option<u32>,
+        /// Print the description as JSON.
+        #[arg(long)]
+        json: bool,
+        /// Write the description to standard output as CBOR.
+        #[arg(long, conflicts_with = "json")]
+        cbor: bool,
+    },
+    /// Cut a `.ptau` file down to the powers a given k needs.
+    PtauTrim {
+        path: PathBuf,
+        #[arg(long)]
+        k: u32,
+        #[arg(long, short)]
+        output: PathBuf,
+    },
+}
+
+pub fn run(args: Args) -> Result<(), Box<dyn Error>> {
+    match args.command {
+        Command::Gen { k, output } => {
+            params::save(&params::generate(k)?, &output)?;
+            let info = params::info(&fs::read(&output)?)?;
+            eprintln!("wrote {}", output.display());
+            print_info(&info);
+        }
+        Command::Info { path, json, cbor } => {
+            let info = params::info(&fs::read(path)?)?;
+            if cbor {
+                stdio::write_cbor(&info)?;
+            } else if json {
+                println!("{}", serde_json::to_string_pretty(&info)?);
+            } else {
+                print_info(&info);
+            }
+        }
+        Command::Check { path } => {
+            let info = params::check(&fs::read(path)?)?;
+            print_info(&info);
+            println!("parameters are intact");
+        }
+        Command::PtauInfo {
+            path,
+            k,
+            json,
+            cbor,
+        } => {
+            let bytes = fs::read(path)?;
+            let info = ptau::info(&bytes)?;
+            if let Some(k) = k {
+                PtauFile::parse(&bytes)?.check_degree(k)?;
+            }
+            if cbor {
+                stdio::write_cbor(&info)?;
+            } else if json {
+                println!("{}", serde_json::to_string_pretty(&info)?);
+            } else {
+                print_ptau_info(&info);
+            }
+        }
+        Command::PtauTrim { path, k, output } => {
+            let bytes = fs::read(&path)?;
+            let trimmed = PtauFile::parse(&bytes)?.trim(k)?;
+            fs::write(&output, &trimmed)?;
+            eprintln!(
+                "wrote {} ({} of {} bytes)",
+                output.display(),
+                trimmed.len(),
+                bytes.len()
+            );
+            print_ptau_info(&ptau::info(&trimmed)?);
+        }
+    }
+    Ok(())
+}
+
+fn print_info(info: &ParamsInfo) {
+    println!("k: {}", info.k);
+    println!("generators: {}", info.generators);
+    println!("bytes: {}", info.bytes);
+    println!("digest: {}", info.digest);
+}
+
+fn print_ptau_info(info: &PtauInfo) {
+    println!("version: {}", info.version);
+    println!("prime: {}", info.prime);
+    println!("power: {} (ceremony {})", info.power, info.ceremony_power);
+    for section in &info.sections {
+        println!("section {}: {} bytes", section.id, section.bytes);
+    }
+    println!("bytes: {}", info.bytes);
+    println!("digest: {}", info.digest);
+}
+
+/// Loads the parameters a prove or verify run asked for, or generates them
+/// (noisily) when no file was given.
+pub fn load_or_generate(
+    path: Option<&PathBuf>,
+    k: u32,
+) -> Result<Params<EqAffine>, Box<dyn Error>> {
+    let params = match path {
+        Some(path) => params::load(path)?,
+        None => {
+            eprintln!(
+                "generating parameters for k = {} (cache them with `fibo params gen`)",
+                k
+            );
+            params::generate(k)?
+        }
+    };
+    let params_k = params.get_g().len().trailing_zeros();
+    if params_k != k {
+        return Err(format!("parameters are for k = {}, expected k = {}", params_k, k).into());
+    }
+    Ok(params)
+}
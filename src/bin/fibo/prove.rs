@@ -0,0 +1,238 @@
+use std::{
+    error::Error,
+    fmt,
+    io::{self, IsTerminal},
+    path::PathBuf,
+};
+
+use fibonacci_circuit::{
+    bundle::ProofBundle,
+    cache::{CacheKey, ProofCache},
+    encoding::Encoding,
+    envelope::{ProofEnvelope, TranscriptKind},
+    fingerprint::{self, fingerprint},
+    metrics::ProverMetrics,
+    params as params_file, precheck,
+    progress::{self, Event},
+    prover::{self, Blinding},
+    registry::{CircuitKind, CircuitVisitor},
+};
+use halo2_proofs::{
+    pasta::{EqAffine, Fp},
+    plonk::Circuit,
+    poly::commitment::Params,
+};
+
+use crate::{params, stdio};
+
+#[derive(clap::Args)]
+pub struct Args {
+    /// Circuit name, e.g. `fib-three-column`.
+    circuit: CircuitKind,
+    #[arg(long, default_value_t = 4)]
+    k: u32,
+    /// Where to write the proof envelope; `-` for standard output.
+    #[arg(long, short)]
+    output: PathBuf,
+    /// Write a bundle with the verifying key and parameters identifier
+    /// instead of a bare envelope.
+    #[arg(long)]
+    bundle: bool,
+    /// Write the envelope as a CBOR map instead of its binary layout.
+    #[arg(long, conflicts_with = "bundle")]
+    cbor: bool,
+    /// How to encode the output: `raw`, `hex` or `base64`.
+    #[arg(long, default_value_t = Encoding::Raw)]
+    encoding: Encoding,
+    /// Shorthand for `--encoding hex`, to print a proof to a terminal.
+    #[arg(long, conflicts_with = "encoding")]
+    hex: bool,
+    /// Parameters file from `fibo params gen`; generated on the fly if absent.
+    #[arg(long)]
+    params: Option<PathBuf>,
+    /// Report the phases and synthesized rows on stderr.
+    #[arg(long)]
+    progress: bool,
+    /// Threads to prove with; one per core by default.
+    #[arg(long, value_parser = clap::value_parser!(u16).range(1..))]
+    threads: Option<u16>,
+    /// Write Prometheus metrics for the run to this file, for node_exporter's
+    /// textfile collector; written whether or not proving succeeds.
+    #[arg(long)]
+    metrics: Option<PathBuf>,
+    /// Reuse a proof stored in this directory for the same circuit, key,
+    /// parameters and public inputs, and store new proofs there.
+    #[arg(long)]
+    cache: Option<PathBuf>,
+    /// Run the MockProver on the witness first, to name the constraints a
+    /// bad witness breaks; always on in debug builds.
+    #[arg(long)]
+    check: bool,
+    /// Prove without zero knowledge: the blinding comes from a fixed public
+    /// seed, so the proof is reproducible but does not hide the witness.
+    #[arg(long)]
+    no_zk: bool,
+}
+
+pub fn run(args: Args) -> Result<(), Box<dyn Error>> {
+    let encoding = stdio::encoding(args.encoding, args.hex);
+    stdio::check_output(&args.output, encoding)?;
+    let params = params::load_or_generate(args.params.as_ref(), args.k)?;
+    let cache = args.cache.as_ref().map(ProofCache::new);
+    let mut events = vec![];
+    let mut prove = || {
+        args.circuit
+            .visit(Prove {
+                kind: args.circuit,
+                k: args.k,
+                params: &params,
+                progress: args.progress,
+                events: &mut events,
+                cache: cache.as_ref(),
+                check: args.check || cfg!(debug_assertions),
+                blinding: if args.no_zk {
+                    Blinding::Off
+                } else {
+                    Blinding::ZeroKnowledge
+                },
+            })
+            // A boxed error cannot leave the pool's threads.
+            .map_err(|e| e.to_string())
+    };
+    let bundle = match args.threads {
+        Some(threads) => prover::with_threads(threads.into(), prove)?,
+        None => prove(),
+    };
+    if let Some(path) = &args.metrics {
+        let metrics = ProverMetrics::new();
+        metrics.record(args.circuit.name(), &events, bundle.is_ok());
+        std::fs::write(path, metrics.render())?;
+    }
+    let bundle = bundle?;
+    let envelope = &bundle.envelope;
+    let bytes = if args.bundle {
+        bundle.to_bytes()
+    } else if args.cbor {
+        envelope.to_cbor()
+    } else {
+        envelope.to_bytes()
+    };
+    stdio::write(&args.output, &encoding.encode(&bytes))?;
+    let output = if stdio::is_stdio(&args.output) {
+        "standard output".to_string()
+    } else {
+        args.output.display().to_string()
+    };
+    eprintln!("wrote {} ({} proof bytes)", output, envelope.proof.len());
+    Ok(())
+}
+
+struct Prove<'a> {
+    kind: CircuitKind,
+    k: u32,
+    params: &'a Params<EqAffine>,
+    progress: bool,
+    events: &'a mut Vec<Event>,
+    cache: Option<&'a ProofCache>,
+    check: bool,
+    blinding: Blinding,
+}
+
+impl CircuitVisitor for Prove<'_> {
+    type Output = Result<ProofBundle, Box<dyn Error>>;
+
+    fn visit<C: Circuit<Fp> + fmt::Debug>(
+        self,
+        circuit: C,
+        instances: Vec<Vec<Fp>>,
+    ) -> Self::Output {
+        if self.check {
+            precheck::check(self.k, &circuit, &instances)?;
+        }
+        let params = params_file::info(&params_file::to_bytes(self.params))?;
+        let cached = match self.cache {
+            Some(cache) => {
+                let vk = prover::keygen_vk(self.params, &circuit)?;
+                let key = CacheKey {
+                    fingerprint: fingerprint(&vk),
+                    params: params.digest.clone(),
+                    blinding: self.blinding,
+                    instances: instances.clone(),
+                };
+                if let Some(envelope) = cache.get(&key) {
+                    eprintln!("proof found in {}", cache.dir().display());
+                    return Ok(ProofBundle {
+                        envelope,
+                        vk: fingerprint::pinned(&vk),
+                        params,
+                    });
+                }
+                Some((cache, key))
+            }
+            None => None,
+        };
+
+        let mut reporter = Reporter {
+            terminal: io::stderr().is_terminal(),
+            redrawing: false,
+        };
+        let (pk, proof) = progress::prove_with(
+            self.params,
+            self.k,
+            circuit,
+            &instances,
+            self.blinding,
+            |event| {
+                self.events.push(event);
+                if self.progress {
+                    reporter.report(event)
+                }
+            },
+        )?;
+
+        let envelope = ProofEnvelope {
+            circuit: self.kind.name().to_string(),
+            k: self.k,
+            n: self.kind.n() as u32,
+            fingerprint: fingerprint(pk.get_vk()),
+            transcript: TranscriptKind::Blake2b,
+            instances,
+            proof,
+        };
+        if let Some((cache, key)) = cached {
+            cache.put(&key, &envelope)?;
+        }
+        Ok(ProofBundle {
+            envelope,
+            vk: fingerprint::pinned(pk.get_vk()),
+            params,
+        })
+    }
+}
+
+/// Prints progress events to stderr, redrawing the row count in place on a
+/// terminal and leaving it out elsewhere.
+struct Reporter {
+    terminal: bool,
+    /// A row count is on the current line.
+    redrawing: bool,
+}
+
+impl Reporter {
+    fn report(&mut self, event: Event) {
+        match event {
+            Event::Rows { rows, usable } if self.terminal => {
+                eprint!("\rsynthesized {} of {} rows", rows, usable);
+                self.redrawing = true;
+            }
+            Event::Rows { .. } => {}
+            Event::Phase { phase, elapsed_ms } => {
+                if self.redrawing {
+                    eprintln!();
+                    self.redrawing = false;
+                }
+                eprintln!("{} at {:.0} ms", phase.name(), elapsed_ms);
+            }
+        }
+    }
+}
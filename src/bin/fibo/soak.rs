@@ -0,0 +1,85 @@
+use std::{
+    error::Error,
+    fs::OpenOptions,
+    io::{self, Write},
+    path::PathBuf,
+};
+
+use fibonacci_circuit::soak::{self, SoakFailure};
+use rand_core::{OsRng, RngCore};
+
+use crate::{params, stdio, Rejected};
+
+#[derive(clap::Args)]
+pub struct Args {
+    /// Cases to prove and verify.
+    #[arg(long, default_value_t = 1000)]
+    cases: usize,
+    /// Seed of the first case, the next case taking the next seed; random
+    /// if absent. `--seed S --cases 1` replays the case with seed `S`.
+    #[arg(long)]
+    seed: Option<u64>,
+    #[arg(long, default_value_t = 4)]
+    k: u32,
+    /// Parameters file from `fibo params gen`; generated on the fly if absent.
+    #[arg(long)]
+    params: Option<PathBuf>,
+    /// Append each failure to this file as a line of JSON as it happens.
+    #[arg(long)]
+    failures: Option<PathBuf>,
+    /// Print the report as JSON.
+    #[arg(long)]
+    json: bool,
+    /// Write the report to standard output as CBOR.
+    #[arg(long, conflicts_with = "json")]
+    cbor: bool,
+}
+
+pub fn run(args: Args) -> Result<(), Box<dyn Error>> {
+    let seed = args.seed.unwrap_or_else(|| OsRng.next_u64());
+    let params = params::load_or_generate(args.params.as_ref(), args.k)?;
+    let mut log = match &args.failures {
+        Some(path) => Some(OpenOptions::new().create(true).append(true).open(path)?),
+        None => None,
+    };
+    eprintln!("soaking {} cases from seed {}", args.cases, seed);
+
+    let mut logged = Ok(());
+    let report = soak::soak(&params, args.k, seed, args.cases, |i, failure| {
+        if let Some(failure) = failure {
+            eprintln!("case {} failed: {}", i, failure);
+            if let (Some(log), Ok(())) = (&mut log, &logged) {
+                logged = append(log, failure);
+            }
+        }
+        if (i + 1) % 100 == 0 {
+            eprintln!("{} of {} cases", i + 1, args.cases);
+        }
+    });
+    logged?;
+
+    if args.cbor {
+        stdio::write_cbor(&report)?;
+    } else if args.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print!("{}", report);
+    }
+
+    if report.passed() {
+        Ok(())
+    } else {
+        Err(Rejected(format!(
+            "{} of {} cases failed",
+            report.failures.len(),
+            report.cases
+        ))
+        .into())
+    }
+}
+
+fn append(log: &mut impl Write, failure: &SoakFailure) -> io::Result<()> {
+    let line = serde_json::to_string(failure)?;
+    writeln!(log, "{}", line)?;
+    log.flush()
+}
@@ -0,0 +1,65 @@
+//! `-` as a path: standard input for files `fibo` reads, standard output
+//! for files it writes. The bytes pass through unchanged, so binary
+//! envelopes can be piped between processes.
+
+use std::{
+    error::Error,
+    fs,
+    io::{self, IsTerminal, Read, Write},
+    path::Path,
+};
+
+use fibonacci_circuit::{cbor, encoding::Encoding};
+use serde::Serialize;
+
+pub fn is_stdio(path: &Path) -> bool {
+    path.as_os_str() == "-"
+}
+
+/// The contents of `path`, or all of standard input for `-`.
+pub fn read(path: &Path) -> io::Result<Vec<u8>> {
+    if !is_stdio(path) {
+        return fs::read(path);
+    }
+    let mut bytes = vec![];
+    io::stdin().lock().read_to_end(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// Fails for raw bytes bound for a terminal, which would garble them, so
+/// that can be caught before the work of making them.
+pub fn check_output(path: &Path, encoding: Encoding) -> Result<(), Box<dyn Error>> {
+    if is_stdio(path) && encoding == Encoding::Raw && io::stdout().is_terminal() {
+        return Err("refusing to write a binary proof to a terminal; pass --hex or pipe it".into());
+    }
+    Ok(())
+}
+
+/// Writes `bytes` to `path`, or to standard output for `-`.
+pub fn write(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    if !is_stdio(path) {
+        return fs::write(path, bytes);
+    }
+    let mut stdout = io::stdout().lock();
+    stdout.write_all(bytes)?;
+    stdout.flush()
+}
+
+/// `--hex` as a shorthand for `--encoding hex`.
+pub fn encoding(encoding: Encoding, hex: bool) -> Encoding {
+    if hex {
+        Encoding::Hex
+    } else {
+        encoding
+    }
+}
+
+/// Writes `report` to standard output as CBOR, refusing a terminal as for
+/// raw proofs.
+pub fn write_cbor<T: Serialize>(report: &T) -> Result<(), Box<dyn Error>> {
+    if io::stdout().is_terminal() {
+        return Err("refusing to write CBOR to a terminal; pipe it or pass --json".into());
+    }
+    write(Path::new("-"), &cbor::to_vec(report)?)?;
+    Ok(())
+}
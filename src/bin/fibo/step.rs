@@ -0,0 +1,161 @@
+use std::{
+    error::Error,
+    fmt,
+    io::{self, BufRead, Write},
+    ops::Range,
+    process,
+};
+
+use fibonacci_circuit::{
+    introspect::{
+        format_field,
+        witness::{RegionInfo, Witness},
+        ColumnKind, ColumnRef,
+    },
+    registry::{CircuitKind, CircuitVisitor},
+};
+use halo2_proofs::{pasta::Fp, plonk::Circuit};
+
+#[derive(clap::Args)]
+pub struct Args {
+    /// Circuit name, e.g. `fib-three-column`.
+    circuit: CircuitKind,
+    #[arg(long, default_value_t = 4)]
+    k: u32,
+}
+
+const HELP: &str = "\
+commands:
+  next, n, <enter>     continue to the next region
+  continue, c          run to the end without pausing
+  cells                show the cells of the last region again
+  regions              list the regions assigned so far
+  column, col COLUMN [START..END]
+                       dump a column, e.g. `col A0` or `col F1 0..8`
+  quit, q              stop
+  help, h              show this message";
+
+pub fn run(args: Args) -> Result<(), Box<dyn Error>> {
+    let witness = args.circuit.visit(Step { k: args.k })?;
+    println!(
+        "synthesis done: {} regions, {} copies",
+        witness.regions.len(),
+        witness.copies.len()
+    );
+    Ok(())
+}
+
+struct Step {
+    k: u32,
+}
+
+impl CircuitVisitor for Step {
+    type Output = Result<Witness, Box<dyn Error>>;
+
+    fn visit<C: Circuit<Fp> + fmt::Debug>(
+        self,
+        circuit: C,
+        instances: Vec<Vec<Fp>>,
+    ) -> Self::Output {
+        let stdin = io::stdin();
+        let mut lines = stdin.lock().lines();
+        let mut paused = true;
+        println!("{}", HELP);
+
+        let witness = Witness::synthesize_with(self.k, &circuit, instances, |witness, region| {
+            print_region(witness.regions.len() - 1, region);
+            while paused {
+                print!("> ");
+                io::stdout().flush().unwrap();
+                // End of input runs to the end.
+                let line = match lines.next() {
+                    Some(Ok(line)) => line,
+                    _ => {
+                        paused = false;
+                        break;
+                    }
+                };
+                let words: Vec<_> = line.split_whitespace().collect();
+                match words.as_slice() {
+                    [] | ["next" | "n"] => break,
+                    ["continue" | "c"] => paused = false,
+                    ["cells"] => print_region(witness.regions.len() - 1, region),
+                    ["regions"] => {
+                        for (i, region) in witness.regions.iter().enumerate() {
+                            println!("{:>3}  {}  {}", i, rows(region), region.name);
+                        }
+                    }
+                    ["column" | "col", column, range @ ..] => {
+                        match dump_column(witness, column, range.first().copied()) {
+                            Ok(()) => {}
+                            Err(e) => println!("{}", e),
+                        }
+                    }
+                    ["quit" | "q"] => process::exit(0),
+                    ["help" | "h"] => println!("{}", HELP),
+                    _ => println!("unknown command `{}`, try `help`", line.trim()),
+                }
+            }
+        })
+        .map_err(|e| format!("synthesis failed at k = {}: {:?}", self.k, e))?;
+        Ok(witness)
+    }
+}
+
+fn print_region(index: usize, region: &RegionInfo) {
+    println!("region {} `{}`, {}", index, region.name, rows(region));
+    for (selector, row) in &region.selectors {
+        println!("  S{} enabled on row {}", selector, row);
+    }
+    for assigned in &region.cells {
+        let value = assigned.value.map_or("?".to_string(), format_field);
+        println!(
+            "  {}@{} = {}  ({})",
+            assigned.cell.column, assigned.cell.row, value, assigned.annotation
+        );
+    }
+}
+
+fn rows(region: &RegionInfo) -> String {
+    match region.rows {
+        Some((first, last)) if first == last => format!("row {}", first),
+        Some((first, last)) => format!("rows {}..={}", first, last),
+        None => "no cells".to_string(),
+    }
+}
+
+fn dump_column(witness: &Witness, column: &str, range: Option<&str>) -> Result<(), String> {
+    let column = parse_column(witness, column)?;
+    let range = match range {
+        Some(range) => parse_range(range)?,
+        None => 0..witness.usable_rows,
+    };
+    for row in range.start..range.end.min(witness.rows()) {
+        let value = witness
+            .value(column, row)
+            .map_or("?".to_string(), format_field);
+        println!("  {}@{} = {}", column, row, value);
+    }
+    Ok(())
+}
+
+fn parse_column(witness: &Witness, s: &str) -> Result<ColumnRef, String> {
+    let (kind, count) = match s.get(..1) {
+        Some("A") => (ColumnKind::Advice, witness.cs.num_advice_columns),
+        Some("F") => (ColumnKind::Fixed, witness.cs.num_fixed_columns),
+        Some("I") => (ColumnKind::Instance, witness.cs.num_instance_columns),
+        _ => return Err(format!("`{}` is not a column like A0, F1 or I0", s)),
+    };
+    match s[1..].parse() {
+        Ok(index) if index < count => Ok(ColumnRef { kind, index }),
+        _ => Err(format!("no column `{}`", s)),
+    }
+}
+
+fn parse_range(s: &str) -> Result<Range<usize>, String> {
+    let parse = |n: &str| n.parse::<usize>().map_err(|e| format!("`{}`: {}", n, e));
+    match s.split_once("..") {
+        Some((start, end)) => Ok(parse(start)?..parse(end)?),
+        None => parse(s).map(|row| row..row + 1),
+    }
+}
@@ -0,0 +1,15 @@
+use std::error::Error;
+
+use fibonacci_circuit::reference::Expected;
+use halo2_proofs::pasta::Fp;
+
+#[derive(clap::Args)]
+pub struct Args {
+    /// Index of the term, with F(0) = 0 and F(1) = 1.
+    n: usize,
+}
+
+pub fn run(args: Args) -> Result<(), Box<dyn Error>> {
+    println!("{}", Expected::<Fp>::of(args.n));
+    Ok(())
+}
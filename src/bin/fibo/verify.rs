@@ -0,0 +1,85 @@
+use std::{error::Error, path::PathBuf};
+
+use fibonacci_circuit::{
+    bundle::ProofBundle,
+    encoding::Encoding,
+    envelope::ProofEnvelope,
+    verification::{self, Status, Verification},
+};
+
+use crate::{params, stdio, Rejected};
+
+/// The largest `k` parameters are generated for when `--params` is absent.
+/// The `k` comes from the envelope, which is untrusted, and generating
+/// parameters near `params::MAX_K` takes hours and more memory than most
+/// machines have.
+const MAX_GENERATED_K: u32 = 16;
+
+#[derive(clap::Args)]
+pub struct Args {
+    /// Proof envelope written by `prove`, in either layout; `-` for
+    /// standard input.
+    #[arg(long, required_unless_present = "bundle", conflicts_with = "bundle")]
+    proof: Option<PathBuf>,
+    /// Proof bundle written by `prove --bundle`; `-` for standard input.
+    #[arg(long)]
+    bundle: Option<PathBuf>,
+    /// How the proof file is encoded: `raw`, `hex` or `base64`.
+    #[arg(long, default_value_t = Encoding::Raw)]
+    encoding: Encoding,
+    /// Shorthand for `--encoding hex`.
+    #[arg(long, conflicts_with = "encoding")]
+    hex: bool,
+    /// Parameters file from `fibo params gen`; generated on the fly if
+    /// absent, for `k` up to 16.
+    #[arg(long)]
+    params: Option<PathBuf>,
+    /// Print the result as JSON, errors included.
+    #[arg(long)]
+    json: bool,
+    /// Write the result to standard output as CBOR.
+    #[arg(long, conflicts_with = "json")]
+    cbor: bool,
+}
+
+pub fn run(args: Args) -> Result<(), Box<dyn Error>> {
+    let verification = check(&args).unwrap_or_else(Verification::error);
+    if args.cbor {
+        stdio::write_cbor(&verification)?;
+    } else if args.json {
+        println!("{}", serde_json::to_string_pretty(&verification)?);
+    } else if verification.status != Status::Error {
+        println!("{}", verification);
+    }
+
+    match (verification.status, verification.reason) {
+        (Status::Valid, _) => Ok(()),
+        (Status::Invalid, reason) => Err(Rejected(reason.unwrap_or_default()).into()),
+        (Status::Error, reason) => Err(reason.unwrap_or_default().into()),
+    }
+}
+
+fn check(args: &Args) -> Result<Verification, Box<dyn Error>> {
+    let encoding = stdio::encoding(args.encoding, args.hex);
+    let (envelope, bundle) = match (&args.proof, &args.bundle) {
+        (Some(path), _) => {
+            let bytes = encoding.decode(&stdio::read(path)?)?;
+            (ProofEnvelope::parse(&bytes)?, None)
+        }
+        (None, Some(path)) => {
+            let bytes = encoding.decode(&stdio::read(path)?)?;
+            let bundle = ProofBundle::from_bytes(&bytes)?;
+            (bundle.envelope.clone(), Some(bundle))
+        }
+        (None, None) => unreachable!("clap requires one of them"),
+    };
+    if args.params.is_none() && envelope.k > MAX_GENERATED_K {
+        return Err(format!(
+            "the proof claims k = {}; parameters are generated only up to k = {}, pass --params",
+            envelope.k, MAX_GENERATED_K
+        )
+        .into());
+    }
+    let params = params::load_or_generate(args.params.as_ref(), envelope.k)?;
+    Ok(verification::verify(&envelope, bundle.as_ref(), &params))
+}
@@ -0,0 +1,363 @@
+//! The proof bundle: one file holding a proof and every artifact around it.
+//!
+//! A bundle is a small archive of named files:
+//!
+//! ```text
+//! manifest.json    circuit, k, n, transcript, fingerprint, and the size and
+//!                  Blake2b digest of every other file
+//! proof.bin        the transcript bytes
+//! instances.json   the instance columns, each value the hex of its
+//!                  canonical little-endian encoding
+//! vk.txt           the pinned verifying key
+//! params.json      the parameters' k and digest, as `fibo params info`
+//! ```
+//!
+//! halo2_proofs 0.2 cannot serialize verifying keys, so `vk.txt` is the
+//! pinned description the [`fingerprint`](crate::fingerprint) hashes; a
+//! verifier regenerates the key from the circuit and compares.
+//!
+//! Archive layout, all integers little-endian:
+//!
+//! ```text
+//! magic        4 bytes   "FIBB"
+//! version      u8        BUNDLE_VERSION
+//! files        u32 count, then per file a u16 length and UTF-8 name,
+//!              then a u32 length and the contents
+//! ```
+//!
+//! Version 2 added `n` to the manifest, as envelope version 2 did. A
+//! version 1 bundle is still read, its `n` implied by its circuit as for
+//! an envelope; see [`envelope::implied_n`].
+
+use std::{error, fmt};
+
+use halo2_proofs::pasta::{group::ff::PrimeField, Fp};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    envelope::{self, ProofEnvelope, TranscriptKind},
+    fingerprint::{self, Fingerprint},
+    params::ParamsInfo,
+};
+
+pub const MAGIC: [u8; 4] = *b"FIBB";
+pub const BUNDLE_VERSION: u8 = 2;
+/// The oldest version still read.
+pub const OLDEST_BUNDLE_VERSION: u8 = 1;
+
+pub const MANIFEST: &str = "manifest.json";
+pub const PROOF: &str = "proof.bin";
+pub const INSTANCES: &str = "instances.json";
+pub const VERIFYING_KEY: &str = "vk.txt";
+pub const PARAMS: &str = "params.json";
+
+/// The files a manifest lists, in archive order.
+const FILES: [&str; 4] = [PROOF, INSTANCES, VERIFYING_KEY, PARAMS];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofBundle {
+    pub envelope: ProofEnvelope,
+    /// The pinned verifying key, as in `vk.txt`.
+    pub vk: String,
+    pub params: ParamsInfo,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Manifest {
+    pub circuit: String,
+    pub k: u32,
+    /// Absent from version 1 manifests.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub n: Option<u32>,
+    pub transcript: String,
+    /// The verifying-key fingerprint, hex encoded.
+    pub fingerprint: String,
+    pub files: Vec<FileEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileEntry {
+    pub name: String,
+    pub bytes: usize,
+    /// Blake2b digest of the contents, hex encoded.
+    pub digest: String,
+}
+
+#[derive(Debug)]
+pub enum BundleError {
+    /// The input ended before the bundle did.
+    Truncated,
+    BadMagic([u8; 4]),
+    UnsupportedVersion(u8),
+    InvalidFileName,
+    /// A file the manifest needs is absent.
+    MissingFile(String),
+    /// A file is present that the manifest does not list.
+    UnexpectedFile(String),
+    /// A file's size or digest differs from its manifest entry.
+    DigestMismatch(String),
+    /// A JSON file does not parse.
+    Json {
+        file: &'static str,
+        error: serde_json::Error,
+    },
+    UnknownTranscript(String),
+    /// A version 1 bundle names no known circuit, so its `n` is unknown.
+    UnknownCircuit(String),
+    InvalidFingerprint(String),
+    InvalidVerifyingKey,
+    /// An instance value is not the hex of a canonical field element.
+    InvalidInstance {
+        column: usize,
+        row: usize,
+    },
+    /// Bytes follow the end of the bundle.
+    TrailingBytes(usize),
+}
+
+impl ProofBundle {
+    pub fn manifest(&self) -> Manifest {
+        let envelope = &self.envelope;
+        Manifest {
+            circuit: envelope.circuit.clone(),
+            k: envelope.k,
+            n: Some(envelope.n),
+            transcript: envelope.transcript.name().to_string(),
+            fingerprint: fingerprint::to_hex(&envelope.fingerprint),
+            files: FILES
+                .iter()
+                .zip(self.files())
+                .map(|(name, contents)| FileEntry {
+                    name: name.to_string(),
+                    bytes: contents.len(),
+                    digest: digest(&contents),
+                })
+                .collect(),
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let manifest = serde_json::to_vec_pretty(&self.manifest()).expect("manifests serialize");
+        let files: Vec<_> = std::iter::once((MANIFEST, manifest))
+            .chain(FILES.into_iter().zip(self.files()))
+            .collect();
+
+        let mut bytes = MAGIC.to_vec();
+        bytes.push(BUNDLE_VERSION);
+        bytes.extend((files.len() as u32).to_le_bytes());
+        for (name, contents) in files {
+            bytes.extend((name.len() as u16).to_le_bytes());
+            bytes.extend(name.as_bytes());
+            bytes.extend((contents.len() as u32).to_le_bytes());
+            bytes.extend(contents);
+        }
+        bytes
+    }
+
+    /// Unpacks a bundle, checking every file against the manifest.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, BundleError> {
+        let mut reader = bytes;
+        let magic: [u8; 4] = read_array(&mut reader)?;
+        if magic != MAGIC {
+            return Err(BundleError::BadMagic(magic));
+        }
+        let [version] = read_array(&mut reader)?;
+        if !(OLDEST_BUNDLE_VERSION..=BUNDLE_VERSION).contains(&version) {
+            return Err(BundleError::UnsupportedVersion(version));
+        }
+
+        let count = u32::from_le_bytes(read_array(&mut reader)?);
+        let mut files = vec![];
+        for _ in 0..count {
+            let name_len = u16::from_le_bytes(read_array(&mut reader)?) as usize;
+            let name = String::from_utf8(read_slice(&mut reader, name_len)?.to_vec())
+                .map_err(|_| BundleError::InvalidFileName)?;
+            let len = u32::from_le_bytes(read_array(&mut reader)?) as usize;
+            files.push((name, read_slice(&mut reader, len)?));
+        }
+        if !reader.is_empty() {
+            return Err(BundleError::TrailingBytes(reader.len()));
+        }
+
+        let file = |name: &str| {
+            files
+                .iter()
+                .find(|(file, _)| file == name)
+                .map(|(_, contents)| *contents)
+                .ok_or_else(|| BundleError::MissingFile(name.to_string()))
+        };
+        let manifest: Manifest = parse_json(MANIFEST, file(MANIFEST)?)?;
+        for (name, _) in &files {
+            if name != MANIFEST && !manifest.files.iter().any(|entry| entry.name == *name) {
+                return Err(BundleError::UnexpectedFile(name.clone()));
+            }
+        }
+        for name in FILES {
+            let contents = file(name)?;
+            let entry = manifest
+                .files
+                .iter()
+                .find(|entry| entry.name == name)
+                .ok_or_else(|| BundleError::MissingFile(name.to_string()))?;
+            if entry.bytes != contents.len() || entry.digest != digest(contents) {
+                return Err(BundleError::DigestMismatch(name.to_string()));
+            }
+        }
+
+        let n = match (version, manifest.n) {
+            (1, _) => envelope::implied_n(&manifest.circuit)
+                .ok_or_else(|| BundleError::UnknownCircuit(manifest.circuit.clone()))?,
+            (_, Some(n)) => n,
+            (_, None) => {
+                return Err(BundleError::Json {
+                    file: MANIFEST,
+                    error: serde::de::Error::missing_field("n"),
+                })
+            }
+        };
+        let transcript = match manifest.transcript.as_str() {
+            "blake2b" => TranscriptKind::Blake2b,
+            other => return Err(BundleError::UnknownTranscript(other.to_string())),
+        };
+        let fingerprint = parse_fingerprint(&manifest.fingerprint)
+            .ok_or_else(|| BundleError::InvalidFingerprint(manifest.fingerprint.clone()))?;
+        let instances: Vec<Vec<String>> = parse_json(INSTANCES, file(INSTANCES)?)?;
+        let instances = instances
+            .iter()
+            .enumerate()
+            .map(|(column, values)| {
+                values
+                    .iter()
+                    .enumerate()
+                    .map(|(row, value)| {
+                        parse_field(value).ok_or(BundleError::InvalidInstance { column, row })
+                    })
+                    .collect()
+            })
+            .collect::<Result<_, _>>()?;
+        let vk = String::from_utf8(file(VERIFYING_KEY)?.to_vec())
+            .map_err(|_| BundleError::InvalidVerifyingKey)?;
+
+        Ok(ProofBundle {
+            envelope: ProofEnvelope {
+                circuit: manifest.circuit,
+                k: manifest.k,
+                n,
+                fingerprint,
+                transcript,
+                instances,
+                proof: file(PROOF)?.to_vec(),
+            },
+            vk,
+            params: parse_json(PARAMS, file(PARAMS)?)?,
+        })
+    }
+
+    /// The contents of [`FILES`], in order.
+    fn files(&self) -> [Vec<u8>; 4] {
+        let instances: Vec<Vec<String>> = self
+            .envelope
+            .instances
+            .iter()
+            .map(|column| column.iter().map(format_field).collect())
+            .collect();
+        [
+            self.envelope.proof.clone(),
+            serde_json::to_vec_pretty(&instances).expect("instances serialize"),
+            self.vk.clone().into_bytes(),
+            serde_json::to_vec_pretty(&self.params).expect("parameter info serializes"),
+        ]
+    }
+}
+
+pub fn digest(bytes: &[u8]) -> String {
+    blake2b_simd::Params::new()
+        .hash_length(32)
+        .personal(b"Fibo-Bundle")
+        .hash(bytes)
+        .to_hex()
+        .to_string()
+}
+
+/// The hex of `value`'s canonical little-endian encoding.
+fn format_field(value: &Fp) -> String {
+    value
+        .to_repr()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+fn parse_field(hex: &str) -> Option<Fp> {
+    Option::from(Fp::from_repr(parse_hex(hex)?))
+}
+
+fn parse_fingerprint(hex: &str) -> Option<Fingerprint> {
+    parse_hex(hex)
+}
+
+fn parse_hex<const N: usize>(hex: &str) -> Option<[u8; N]> {
+    if hex.len() != 2 * N || !hex.is_ascii() {
+        return None;
+    }
+    let mut bytes = [0; N];
+    for (byte, pair) in bytes.iter_mut().zip(hex.as_bytes().chunks(2)) {
+        *byte = u8::from_str_radix(std::str::from_utf8(pair).ok()?, 16).ok()?;
+    }
+    Some(bytes)
+}
+
+fn parse_json<'a, T: Deserialize<'a>>(
+    file: &'static str,
+    contents: &'a [u8],
+) -> Result<T, BundleError> {
+    serde_json::from_slice(contents).map_err(|error| BundleError::Json { file, error })
+}
+
+fn read_array<const N: usize>(reader: &mut &[u8]) -> Result<[u8; N], BundleError> {
+    Ok(read_slice(reader, N)?.try_into().unwrap())
+}
+
+fn read_slice<'a>(reader: &mut &'a [u8], len: usize) -> Result<&'a [u8], BundleError> {
+    if reader.len() < len {
+        return Err(BundleError::Truncated);
+    }
+    let (head, tail) = reader.split_at(len);
+    *reader = tail;
+    Ok(head)
+}
+
+impl fmt::Display for BundleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BundleError::Truncated => f.write_str("bundle is truncated"),
+            BundleError::BadMagic(magic) => write!(f, "not a proof bundle (magic {:?})", magic),
+            BundleError::UnsupportedVersion(v) => write!(f, "unsupported bundle version {}", v),
+            BundleError::InvalidFileName => f.write_str("file name is not UTF-8"),
+            BundleError::MissingFile(name) => write!(f, "bundle has no {}", name),
+            BundleError::UnexpectedFile(name) => {
+                write!(f, "{} is not listed in the manifest", name)
+            }
+            BundleError::DigestMismatch(name) => {
+                write!(f, "{} does not match its manifest entry", name)
+            }
+            BundleError::Json { file, error } => write!(f, "malformed {}: {}", file, error),
+            BundleError::UnknownTranscript(t) => write!(f, "unknown transcript type `{}`", t),
+            BundleError::UnknownCircuit(name) => write!(
+                f,
+                "version 1 bundle of unknown circuit `{}` does not record n",
+                name
+            ),
+            BundleError::InvalidFingerprint(hex) => write!(f, "invalid fingerprint `{}`", hex),
+            BundleError::InvalidVerifyingKey => write!(f, "{} is not UTF-8", VERIFYING_KEY),
+            BundleError::InvalidInstance { column, row } => write!(
+                f,
+                "instance column {} row {} is not a canonical field element",
+                column, row
+            ),
+            BundleError::TrailingBytes(n) => write!(f, "{} bytes after the bundle", n),
+        }
+    }
+}
+
+impl error::Error for BundleError {}
@@ -0,0 +1,99 @@
+//! A content-addressed proof cache, so proving the same inputs again reads
+//! back the stored proof instead of running the prover.
+//!
+//! An entry is addressed by the verifying-key fingerprint, the parameters'
+//! digest, the blinding and the instance columns. The fingerprint changes
+//! with the circuit and `k` and the digest with the parameters, so entries
+//! made under other keys or parameters are never found; nothing needs
+//! clearing when either changes, only pruning if the directory grows.
+//! Zero-knowledge proofs are randomized, so a hit is a valid proof of the
+//! inputs, not the bytes a fresh run would produce. A proof made with
+//! [`Blinding::Off`] does not hide the witness, so it is stored apart and
+//! never handed to a request for a zero-knowledge one.
+//!
+//! Each entry is a [`ProofEnvelope`] file named by the key's digest. A file
+//! that does not parse or does not match its key is treated as a miss.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use halo2_proofs::pasta::{group::ff::PrimeField, Fp};
+
+use crate::{envelope::ProofEnvelope, fingerprint::Fingerprint, prover::Blinding};
+
+/// What a cached proof is a proof of.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheKey {
+    pub fingerprint: Fingerprint,
+    /// The parameters' digest, as [`params::digest`](crate::params::digest).
+    pub params: String,
+    pub blinding: Blinding,
+    pub instances: Vec<Vec<Fp>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ProofCache {
+    dir: PathBuf,
+}
+
+impl CacheKey {
+    /// Blake2b of the key. Each column is prefixed by its length so
+    /// different splits of the same values address different entries.
+    pub fn digest(&self) -> String {
+        let mut state = blake2b_simd::Params::new()
+            .hash_length(32)
+            .personal(b"Fibo-ProofCache")
+            .to_state();
+        state.update(&self.fingerprint);
+        state.update(&(self.params.len() as u64).to_le_bytes());
+        state.update(self.params.as_bytes());
+        state.update(&[match self.blinding {
+            Blinding::ZeroKnowledge => 0,
+            Blinding::Off => 1,
+        }]);
+        state.update(&(self.instances.len() as u64).to_le_bytes());
+        for column in &self.instances {
+            state.update(&(column.len() as u64).to_le_bytes());
+            for value in column {
+                state.update(value.to_repr().as_ref());
+            }
+        }
+        state.finalize().to_hex().to_string()
+    }
+}
+
+impl ProofCache {
+    /// A cache in `dir`, which is created on the first [`put`](Self::put).
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn path(&self, key: &CacheKey) -> PathBuf {
+        self.dir.join(format!("{}.proof", key.digest()))
+    }
+
+    /// The stored proof for `key`, if there is one that matches it.
+    pub fn get(&self, key: &CacheKey) -> Option<ProofEnvelope> {
+        let bytes = fs::read(self.path(key)).ok()?;
+        let envelope = ProofEnvelope::from_bytes(&bytes).ok()?;
+        (envelope.fingerprint == key.fingerprint && envelope.instances == key.instances)
+            .then_some(envelope)
+    }
+
+    /// Stores `envelope` under `key`, replacing any entry there. The file
+    /// is written beside its final name and renamed into place, so a
+    /// concurrent [`get`](Self::get) sees the old entry or the new one.
+    pub fn put(&self, key: &CacheKey, envelope: &ProofEnvelope) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        let path = self.path(key);
+        let partial = path.with_extension(format!("proof.{}.partial", std::process::id()));
+        fs::write(&partial, envelope.to_bytes())?;
+        fs::rename(&partial, &path)
+    }
+}
@@ -0,0 +1,350 @@
+//! CBOR (RFC 8949) for envelopes, public inputs and reports, for
+//! consumers that want a compact binary format with a schema rather than
+//! JSON or the envelope's own layout.
+//!
+//! Only what those need is covered: integers, byte and text strings,
+//! arrays, maps, booleans, null and floats, all with definite lengths.
+//! [`encode`] writes every head in its shortest form, so equal values
+//! encode to equal bytes; [`decode`] rejects tags, indefinite lengths and
+//! anything after the first value.
+
+use std::{error, fmt};
+
+use halo2_proofs::pasta::{group::ff::PrimeField, Fp};
+use serde::Serialize;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Unsigned(u64),
+    /// The integer `-1 - n`.
+    Negative(u64),
+    Bytes(Vec<u8>),
+    Text(String),
+    Array(Vec<Value>),
+    /// Entries in the order written.
+    Map(Vec<(Value, Value)>),
+    Bool(bool),
+    Null,
+    Float(f64),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CborError {
+    /// The input ended inside a value.
+    Truncated,
+    /// A tag, an indefinite length, or a simple value other than `false`,
+    /// `true` and `null`, at its byte offset.
+    Unsupported(usize),
+    /// Bytes follow the end of the value.
+    TrailingBytes(usize),
+    /// A text string at this offset is not UTF-8.
+    InvalidUtf8(usize),
+    /// Arrays and maps nest deeper than [`MAX_DEPTH`].
+    TooDeep,
+    /// The value does not have the shape expected of it.
+    Unexpected(&'static str),
+}
+
+/// How deeply [`decode`] lets arrays and maps nest.
+pub const MAX_DEPTH: usize = 64;
+
+impl Value {
+    pub fn text(s: &str) -> Self {
+        Value::Text(s.to_string())
+    }
+
+    /// The value under the text key `key`, if this is a map with one.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Map(entries) => entries
+                .iter()
+                .find(|(k, _)| matches!(k, Value::Text(k) if k == key))
+                .map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            Value::Unsigned(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Value::Bytes(bytes) => Some(bytes),
+            _ => None,
+        }
+    }
+
+    pub fn as_text(&self) -> Option<&str> {
+        match self {
+            Value::Text(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+}
+
+pub fn encode(value: &Value) -> Vec<u8> {
+    let mut bytes = vec![];
+    write(&mut bytes, value);
+    bytes
+}
+
+fn write(bytes: &mut Vec<u8>, value: &Value) {
+    match value {
+        Value::Unsigned(n) => head(bytes, 0, *n),
+        Value::Negative(n) => head(bytes, 1, *n),
+        Value::Bytes(b) => {
+            head(bytes, 2, b.len() as u64);
+            bytes.extend(b);
+        }
+        Value::Text(s) => {
+            head(bytes, 3, s.len() as u64);
+            bytes.extend(s.as_bytes());
+        }
+        Value::Array(items) => {
+            head(bytes, 4, items.len() as u64);
+            items.iter().for_each(|item| write(bytes, item));
+        }
+        Value::Map(entries) => {
+            head(bytes, 5, entries.len() as u64);
+            for (key, value) in entries {
+                write(bytes, key);
+                write(bytes, value);
+            }
+        }
+        Value::Bool(false) => bytes.push(0xf4),
+        Value::Bool(true) => bytes.push(0xf5),
+        Value::Null => bytes.push(0xf6),
+        Value::Float(x) => {
+            bytes.push(0xfb);
+            bytes.extend(x.to_be_bytes());
+        }
+    }
+}
+
+/// A major type and its argument, in the fewest bytes that hold it.
+fn head(bytes: &mut Vec<u8>, major: u8, n: u64) {
+    let major = major << 5;
+    match n {
+        0..=23 => bytes.push(major | n as u8),
+        24..=0xff => bytes.extend([major | 24, n as u8]),
+        0x100..=0xffff => {
+            bytes.push(major | 25);
+            bytes.extend((n as u16).to_be_bytes());
+        }
+        0x1_0000..=0xffff_ffff => {
+            bytes.push(major | 26);
+            bytes.extend((n as u32).to_be_bytes());
+        }
+        _ => {
+            bytes.push(major | 27);
+            bytes.extend(n.to_be_bytes());
+        }
+    }
+}
+
+pub fn decode(bytes: &[u8]) -> Result<Value, CborError> {
+    let mut reader = Reader { bytes, offset: 0 };
+    let value = reader.value(0)?;
+    if reader.offset == bytes.len() {
+        Ok(value)
+    } else {
+        Err(CborError::TrailingBytes(bytes.len() - reader.offset))
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], CborError> {
+        let end = self
+            .offset
+            .checked_add(len)
+            .filter(|&end| end <= self.bytes.len())
+            .ok_or(CborError::Truncated)?;
+        let taken = &self.bytes[self.offset..end];
+        self.offset = end;
+        Ok(taken)
+    }
+
+    fn argument(&mut self, start: usize, info: u8) -> Result<u64, CborError> {
+        let width = match info {
+            0..=23 => return Ok(info.into()),
+            24 => 1,
+            25 => 2,
+            26 => 4,
+            27 => 8,
+            _ => return Err(CborError::Unsupported(start)),
+        };
+        let mut be = [0; 8];
+        be[8 - width..].copy_from_slice(self.take(width)?);
+        Ok(u64::from_be_bytes(be))
+    }
+
+    /// A count of items that each take at least one byte, checked against
+    /// what is left so a forged length cannot size an allocation.
+    fn count(&mut self, start: usize, info: u8) -> Result<usize, CborError> {
+        let n = self.argument(start, info)?;
+        if n > (self.bytes.len() - self.offset) as u64 {
+            return Err(CborError::Truncated);
+        }
+        Ok(n as usize)
+    }
+
+    fn value(&mut self, depth: usize) -> Result<Value, CborError> {
+        let start = self.offset;
+        let initial = self.take(1)?[0];
+        let (major, info) = (initial >> 5, initial & 0x1f);
+        Ok(match major {
+            0 => Value::Unsigned(self.argument(start, info)?),
+            1 => Value::Negative(self.argument(start, info)?),
+            2 => {
+                let len = self.count(start, info)?;
+                Value::Bytes(self.take(len)?.to_vec())
+            }
+            3 => {
+                let len = self.count(start, info)?;
+                let text = std::str::from_utf8(self.take(len)?)
+                    .map_err(|_| CborError::InvalidUtf8(start))?;
+                Value::Text(text.to_string())
+            }
+            4 | 5 if depth == MAX_DEPTH => return Err(CborError::TooDeep),
+            4 => {
+                let len = self.count(start, info)?;
+                let items = (0..len)
+                    .map(|_| self.value(depth + 1))
+                    .collect::<Result<_, _>>()?;
+                Value::Array(items)
+            }
+            5 => {
+                let len = self.count(start, info)?;
+                let mut entries = Vec::with_capacity(len);
+                for _ in 0..len {
+                    let key = self.value(depth + 1)?;
+                    entries.push((key, self.value(depth + 1)?));
+                }
+                Value::Map(entries)
+            }
+            7 => match info {
+                20 => Value::Bool(false),
+                21 => Value::Bool(true),
+                22 => Value::Null,
+                26 => {
+                    let be = self.take(4)?.try_into().expect("four bytes");
+                    Value::Float(f32::from_be_bytes(be).into())
+                }
+                27 => {
+                    let be = self.take(8)?.try_into().expect("eight bytes");
+                    Value::Float(f64::from_be_bytes(be))
+                }
+                _ => return Err(CborError::Unsupported(start)),
+            },
+            _ => return Err(CborError::Unsupported(start)),
+        })
+    }
+}
+
+/// The CBOR counterpart of a JSON value: numbers become integers where
+/// they are integers and floats otherwise, objects become maps with text
+/// keys in the same order.
+pub fn from_json(value: &serde_json::Value) -> Value {
+    match value {
+        serde_json::Value::Null => Value::Null,
+        serde_json::Value::Bool(b) => Value::Bool(*b),
+        serde_json::Value::Number(n) => match (n.as_u64(), n.as_i64()) {
+            (Some(n), _) => Value::Unsigned(n),
+            (None, Some(n)) => Value::Negative(!n as u64),
+            (None, None) => Value::Float(n.as_f64().unwrap_or(f64::NAN)),
+        },
+        serde_json::Value::String(s) => Value::text(s),
+        serde_json::Value::Array(items) => Value::Array(items.iter().map(from_json).collect()),
+        serde_json::Value::Object(entries) => Value::Map(
+            entries
+                .iter()
+                .map(|(k, v)| (Value::text(k), from_json(v)))
+                .collect(),
+        ),
+    }
+}
+
+/// Any report as CBOR, with the same fields its JSON has.
+pub fn to_vec<T: Serialize>(report: &T) -> serde_json::Result<Vec<u8>> {
+    Ok(encode(&from_json(&serde_json::to_value(report)?)))
+}
+
+/// Public inputs as an array of columns, each an array of 32-byte
+/// canonical field elements, little-endian as in the envelope.
+pub fn instances(instances: &[Vec<Fp>]) -> Value {
+    Value::Array(
+        instances
+            .iter()
+            .map(|column| {
+                Value::Array(
+                    column
+                        .iter()
+                        .map(|value| Value::Bytes(value.to_repr().to_vec()))
+                        .collect(),
+                )
+            })
+            .collect(),
+    )
+}
+
+/// Reads public inputs written by [`instances`].
+pub fn to_instances(value: &Value) -> Result<Vec<Vec<Fp>>, CborError> {
+    let columns = value
+        .as_array()
+        .ok_or(CborError::Unexpected("instances are not an array"))?;
+    columns
+        .iter()
+        .map(|column| {
+            let column = column
+                .as_array()
+                .ok_or(CborError::Unexpected("an instance column is not an array"))?;
+            column
+                .iter()
+                .map(|value| {
+                    value
+                        .as_bytes()
+                        .and_then(|bytes| bytes.try_into().ok())
+                        .and_then(|repr| Option::from(Fp::from_repr(repr)))
+                        .ok_or(CborError::Unexpected(
+                            "an instance is not a canonical 32-byte field element",
+                        ))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+impl fmt::Display for CborError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CborError::Truncated => f.write_str("CBOR input is truncated"),
+            CborError::Unsupported(offset) => {
+                write!(f, "unsupported CBOR item at byte {}", offset)
+            }
+            CborError::TrailingBytes(n) => write!(f, "{} bytes after the CBOR value", n),
+            CborError::InvalidUtf8(offset) => {
+                write!(f, "CBOR text string at byte {} is not UTF-8", offset)
+            }
+            CborError::TooDeep => write!(f, "CBOR nests deeper than {} levels", MAX_DEPTH),
+            CborError::Unexpected(what) => f.write_str(what),
+        }
+    }
+}
+
+impl error::Error for CborError {}
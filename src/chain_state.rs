@@ -0,0 +1,314 @@
+//! Checkpointing a long [`chunked`](crate::chunked) proving job, so one
+//! that is interrupted resumes at the chunk it stopped on.
+//!
+//! [`resume_chain`] saves a [`ChainState`] after every chunk it proves: the
+//! job it is running and the proofs so far, each with the pair of terms it
+//! ended on. Run again on the same file, it loads the state, checks it is
+//! the same job, and proves only the chunks still missing. At most the
+//! chunk that was being proven is lost.
+//!
+//! Layout, all integers little-endian:
+//!
+//! ```text
+//! magic        4 bytes   "FIBC"
+//! version      u8        CHAIN_STATE_VERSION
+//! steps        u32       C, the steps in each chunk
+//! chunks       u32       the chunks the job proves
+//! fingerprint  32 bytes  fingerprint of the chunk circuit's verifying key
+//! seeds        2 × 32-byte canonical field elements
+//! proven       u32 count, then per chunk its output pair as two 32-byte
+//!              field elements, and a u32 length and the proof bytes
+//! ```
+//!
+//! A chunk's input is the seeds or the previous chunk's output, so only
+//! outputs are stored. The file is written beside its final name and
+//! renamed into place, as the [`cache`](crate::cache) does, so a job killed
+//! mid-write leaves the previous state, never half of one. Loaded proofs
+//! are not verified again; [`chunked::verify_chain`] still checks the
+//! finished chain.
+//!
+//! [`chunked::verify_chain`]: crate::chunked::verify_chain
+
+use std::{
+    error, fmt, fs,
+    io::{self, Read},
+    path::Path,
+};
+
+use halo2_proofs::{
+    pasta::{group::ff::PrimeField, EqAffine, Fp},
+    plonk::{self, ProvingKey},
+    poly::commitment::Params,
+};
+
+use crate::{
+    chunked::{self, ChunkProof},
+    fingerprint::{fingerprint, Fingerprint},
+    prover,
+};
+
+pub const MAGIC: [u8; 4] = *b"FIBC";
+pub const CHAIN_STATE_VERSION: u8 = 1;
+
+/// A chunked proving job and how far it has got.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainState {
+    pub steps: u32,
+    pub chunks: u32,
+    pub fingerprint: Fingerprint,
+    pub seeds: (Fp, Fp),
+    /// The chunks proven so far, in order.
+    pub proofs: Vec<ChunkProof>,
+}
+
+#[derive(Debug)]
+pub enum ChainStateError {
+    Io(io::Error),
+    /// The input ended before the state did.
+    Truncated,
+    BadMagic([u8; 4]),
+    UnsupportedVersion(u8),
+    /// The seeds or a chunk's output are not canonical field elements.
+    NonCanonical,
+    /// Bytes follow the end of the state.
+    TrailingBytes(usize),
+    /// The state holds more proofs than its job has chunks.
+    TooManyProofs {
+        proven: u32,
+        chunks: u32,
+    },
+}
+
+#[derive(Debug)]
+pub enum ResumeError {
+    State(ChainStateError),
+    /// The job's steps per chunk or chunk count, named, does not fit the
+    /// state's `u32`.
+    TooLarge(&'static str),
+    /// The state file is of another job; names the field that differs.
+    OtherJob(&'static str),
+    Prove {
+        chunk: usize,
+        error: plonk::Error,
+    },
+}
+
+impl ChainState {
+    /// A job of `chunks` chunks of `steps` steps from `seeds`, none proven.
+    pub fn new(steps: u32, chunks: u32, fingerprint: Fingerprint, seeds: (Fp, Fp)) -> Self {
+        ChainState {
+            steps,
+            chunks,
+            fingerprint,
+            seeds,
+            proofs: vec![],
+        }
+    }
+
+    /// Where the next chunk starts.
+    pub fn next_input(&self) -> (Fp, Fp) {
+        self.proofs.last().map_or(self.seeds, ChunkProof::output)
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.proofs.len() >= self.chunks as usize
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![];
+        bytes.extend_from_slice(&MAGIC);
+        bytes.push(CHAIN_STATE_VERSION);
+        bytes.extend_from_slice(&self.steps.to_le_bytes());
+        bytes.extend_from_slice(&self.chunks.to_le_bytes());
+        bytes.extend_from_slice(&self.fingerprint);
+        bytes.extend_from_slice(&self.seeds.0.to_repr());
+        bytes.extend_from_slice(&self.seeds.1.to_repr());
+        bytes.extend_from_slice(&(self.proofs.len() as u32).to_le_bytes());
+        for proof in &self.proofs {
+            let (x, y) = proof.output();
+            bytes.extend_from_slice(&x.to_repr());
+            bytes.extend_from_slice(&y.to_repr());
+            bytes.extend_from_slice(&(proof.proof.len() as u32).to_le_bytes());
+            bytes.extend_from_slice(&proof.proof);
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ChainStateError> {
+        let mut reader = bytes;
+        let magic: [u8; 4] = read_array(&mut reader)?;
+        if magic != MAGIC {
+            return Err(ChainStateError::BadMagic(magic));
+        }
+        let [version] = read_array(&mut reader)?;
+        if version != CHAIN_STATE_VERSION {
+            return Err(ChainStateError::UnsupportedVersion(version));
+        }
+        let steps = u32::from_le_bytes(read_array(&mut reader)?);
+        let chunks = u32::from_le_bytes(read_array(&mut reader)?);
+        let fingerprint = read_array(&mut reader)?;
+        let seeds = (read_field(&mut reader)?, read_field(&mut reader)?);
+
+        let mut state = ChainState::new(steps, chunks, fingerprint, seeds);
+        let proven = u32::from_le_bytes(read_array(&mut reader)?);
+        if proven > chunks {
+            return Err(ChainStateError::TooManyProofs { proven, chunks });
+        }
+        for _ in 0..proven {
+            let (x0, x1) = state.next_input();
+            let (x, y) = (read_field(&mut reader)?, read_field(&mut reader)?);
+            let len = u32::from_le_bytes(read_array(&mut reader)?) as usize;
+            let mut proof = vec![];
+            (&mut reader).take(len as u64).read_to_end(&mut proof)?;
+            if proof.len() != len {
+                return Err(ChainStateError::Truncated);
+            }
+            state.proofs.push(ChunkProof {
+                instances: vec![vec![x0, x1, x, y]],
+                proof,
+            });
+        }
+        if reader.is_empty() {
+            Ok(state)
+        } else {
+            Err(ChainStateError::TrailingBytes(reader.len()))
+        }
+    }
+
+    /// Writes the state to `path` beside its final name and renames it
+    /// into place.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut partial = path.as_os_str().to_owned();
+        partial.push(format!(".{}.partial", std::process::id()));
+        fs::write(&partial, self.to_bytes())?;
+        fs::rename(&partial, path)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, ChainStateError> {
+        Self::from_bytes(&fs::read(path)?)
+    }
+
+    /// Whether `saved` is this job, proven or not.
+    fn check_same_job(&self, saved: &ChainState) -> Result<(), ResumeError> {
+        let differs = if saved.steps != self.steps {
+            "steps"
+        } else if saved.chunks != self.chunks {
+            "chunks"
+        } else if saved.fingerprint != self.fingerprint {
+            "fingerprint"
+        } else if saved.seeds != self.seeds {
+            "seeds"
+        } else {
+            return Ok(());
+        };
+        Err(ResumeError::OtherJob(differs))
+    }
+}
+
+/// Proves `chunks` chunks of `C` steps from `seeds` as
+/// [`chunked::prove_chain`] does, saving the state to `path` after each,
+/// and starting from the state already there, if any.
+///
+/// A state file for other seeds, another chunk count or size, or another
+/// key is an error rather than being overwritten, as is a job whose chunk
+/// count or size does not fit in a `u32`.
+pub fn resume_chain<const C: usize>(
+    params: &Params<EqAffine>,
+    pk: &ProvingKey<EqAffine>,
+    seeds: (Fp, Fp),
+    chunks: usize,
+    path: &Path,
+) -> Result<Vec<ChunkProof>, ResumeError> {
+    let steps = u32::try_from(C).map_err(|_| ResumeError::TooLarge("steps"))?;
+    let chunks = u32::try_from(chunks).map_err(|_| ResumeError::TooLarge("chunks"))?;
+    let job = ChainState::new(steps, chunks, fingerprint(pk.get_vk()), seeds);
+    let mut state = if path.exists() {
+        let state = ChainState::load(path)?;
+        job.check_same_job(&state)?;
+        state
+    } else {
+        job
+    };
+
+    while !state.is_complete() {
+        let chunk = state.proofs.len();
+        let (x0, x1) = state.next_input();
+        let (circuit, instances) = chunked::chunk::<C>(x0, x1);
+        let proof = prover::create_proof(params, pk, circuit, &instances)
+            .map_err(|error| ResumeError::Prove { chunk, error })?;
+        state.proofs.push(ChunkProof { instances, proof });
+        state.save(path).map_err(ChainStateError::Io)?;
+    }
+    Ok(state.proofs)
+}
+
+fn read_array<const N: usize>(reader: &mut &[u8]) -> Result<[u8; N], ChainStateError> {
+    let mut bytes = [0; N];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+fn read_field(reader: &mut &[u8]) -> Result<Fp, ChainStateError> {
+    Option::from(Fp::from_repr(read_array(reader)?)).ok_or(ChainStateError::NonCanonical)
+}
+
+impl From<io::Error> for ChainStateError {
+    fn from(e: io::Error) -> Self {
+        if e.kind() == io::ErrorKind::UnexpectedEof {
+            ChainStateError::Truncated
+        } else {
+            ChainStateError::Io(e)
+        }
+    }
+}
+
+impl From<ChainStateError> for ResumeError {
+    fn from(e: ChainStateError) -> Self {
+        ResumeError::State(e)
+    }
+}
+
+impl fmt::Display for ChainStateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChainStateError::Io(e) => write!(f, "I/O error: {}", e),
+            ChainStateError::Truncated => f.write_str("chain state is truncated"),
+            ChainStateError::BadMagic(magic) => {
+                write!(f, "not a chain state (magic {:?})", magic)
+            }
+            ChainStateError::UnsupportedVersion(v) => {
+                write!(f, "unsupported chain state version {}", v)
+            }
+            ChainStateError::NonCanonical => {
+                f.write_str("chain state holds a non-canonical field element")
+            }
+            ChainStateError::TrailingBytes(n) => write!(f, "{} bytes after the chain state", n),
+            ChainStateError::TooManyProofs { proven, chunks } => write!(
+                f,
+                "chain state holds {} proofs for a job of {} chunks",
+                proven, chunks
+            ),
+        }
+    }
+}
+
+impl fmt::Display for ResumeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ResumeError::State(e) => e.fmt(f),
+            ResumeError::TooLarge(field) => {
+                write!(f, "job {} do not fit in a chain state", field)
+            }
+            ResumeError::OtherJob(field) => {
+                write!(f, "saved chain state has different {}", field)
+            }
+            ResumeError::Prove { chunk, error } => {
+                write!(f, "proving chunk {} failed: {:?}", chunk, error)
+            }
+        }
+    }
+}
+
+impl error::Error for ChainStateError {}
+
+impl error::Error for ResumeError {}
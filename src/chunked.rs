@@ -0,0 +1,129 @@
+//! Proving a long sequence as a chain of [`FiboChunkCircuit`] proofs.
+//!
+//! Each proof covers `C` steps and is verified on its own; what ties them
+//! together is the verifier checking that every chunk starts where the
+//! previous one ended, and that the first chunk starts at the claimed
+//! seeds.
+
+use std::{error, fmt};
+
+use halo2_proofs::{
+    circuit::Value,
+    pasta::{EqAffine, Fp},
+    plonk::{self, ProvingKey, VerifyingKey},
+    poly::commitment::Params,
+};
+
+use crate::{fibonacci::chunk::FiboChunkCircuit, prover};
+
+/// A proof of one chunk with its public `(x0, x1, x_C, x_{C+1})`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChunkProof {
+    pub instances: Vec<Vec<Fp>>,
+    pub proof: Vec<u8>,
+}
+
+impl ChunkProof {
+    pub fn input(&self) -> (Fp, Fp) {
+        (self.instances[0][0], self.instances[0][1])
+    }
+
+    pub fn output(&self) -> (Fp, Fp) {
+        (self.instances[0][2], self.instances[0][3])
+    }
+}
+
+#[derive(Debug)]
+pub enum ChainError {
+    Empty,
+    /// A chunk does not have the four public inputs of [`FiboChunkCircuit`].
+    BadInstances {
+        chunk: usize,
+    },
+    /// The first chunk does not start at the expected seeds, or a chunk does
+    /// not start where the previous one ended.
+    Broken {
+        chunk: usize,
+    },
+    Invalid {
+        chunk: usize,
+        error: plonk::Error,
+    },
+}
+
+/// The chunk circuit for `C` steps from `(x0, x1)`, with its instances.
+pub fn chunk<const C: usize>(x0: Fp, x1: Fp) -> (FiboChunkCircuit<Fp, C>, Vec<Vec<Fp>>) {
+    let (mut a, mut b) = (x0, x1);
+    for _ in 0..C {
+        (a, b) = (b, a + b);
+    }
+    let circuit = FiboChunkCircuit {
+        x0: Value::known(x0),
+        x1: Value::known(x1),
+    };
+    (circuit, vec![vec![x0, x1, a, b]])
+}
+
+/// Proves `chunks` consecutive chunks of `C` steps starting at the seeds.
+pub fn prove_chain<const C: usize>(
+    params: &Params<EqAffine>,
+    pk: &ProvingKey<EqAffine>,
+    seeds: (Fp, Fp),
+    chunks: usize,
+) -> Result<Vec<ChunkProof>, plonk::Error> {
+    let mut state = seeds;
+    (0..chunks)
+        .map(|_| {
+            let (circuit, instances) = chunk::<C>(state.0, state.1);
+            let proof = prover::create_proof(params, pk, circuit, &instances)?;
+            let proof = ChunkProof { instances, proof };
+            state = proof.output();
+            Ok(proof)
+        })
+        .collect()
+}
+
+/// Verifies every chunk and their chaining from `seeds`, returning the
+/// final pair of terms.
+pub fn verify_chain(
+    params: &Params<EqAffine>,
+    vk: &VerifyingKey<EqAffine>,
+    seeds: (Fp, Fp),
+    proofs: &[ChunkProof],
+) -> Result<(Fp, Fp), ChainError> {
+    if proofs.is_empty() {
+        return Err(ChainError::Empty);
+    }
+    let mut state = seeds;
+    for (chunk, proof) in proofs.iter().enumerate() {
+        if proof.instances.len() != 1 || proof.instances[0].len() != 4 {
+            return Err(ChainError::BadInstances { chunk });
+        }
+        if proof.input() != state {
+            return Err(ChainError::Broken { chunk });
+        }
+        prover::verify_proof(params, vk, &proof.proof, &proof.instances)
+            .map_err(|error| ChainError::Invalid { chunk, error })?;
+        state = proof.output();
+    }
+    Ok(state)
+}
+
+impl fmt::Display for ChainError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChainError::Empty => f.write_str("no chunks to verify"),
+            ChainError::BadInstances { chunk } => {
+                write!(f, "chunk {} does not have 4 public inputs", chunk)
+            }
+            ChainError::Broken { chunk } => {
+                write!(f, "chunk {} does not start where the chain left off", chunk)
+            }
+            ChainError::Invalid { chunk, error } => {
+                write!(f, "chunk {} proof is invalid: {:?}", chunk, error)
+            }
+        }
+    }
+}
+
+impl error::Error for ChainError {}
@@ -0,0 +1,171 @@
+//! Catalan numbers through `C(n+1) = C(n) · 2(2n+1) / (n+2)`.
+//!
+//! Each step is a factor row, which derives `2(2n+1)` and `n+2` from the
+//! index `n` in a fixed column, followed by a multiplication and a division
+//! with [`ArithmeticChip`]. The division is exact over the integers, so
+//! field division gives the Catalan number itself. `C(0) = 1` is a
+//! constant of the circuit, so there is no witness; instance row `0` is
+//! the last term.
+
+use std::marker::PhantomData;
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    plonk::{Advice, Circuit, Column, ConstraintSystem, Error, Fixed, Instance, Selector},
+    poly::Rotation,
+};
+
+use crate::{
+    expr,
+    fibonacci::{ACell, NUM_TERMS},
+    gadgets::arithmetic::{ArithmeticChip, ArithmeticConfig, ArithmeticInstructions},
+};
+
+#[derive(Debug, Clone)]
+pub struct CatalanConfig {
+    /// `num, den`.
+    pub advice: [Column<Advice>; 2],
+    pub index: Column<Fixed>,
+    pub selector: Selector,
+}
+
+#[derive(Debug)]
+pub struct CatalanChip<F: FieldExt> {
+    config: CatalanConfig,
+    marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> CatalanChip<F> {
+    pub fn construct(config: CatalanConfig) -> Self {
+        Self {
+            config,
+            marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 2],
+        index: Column<Fixed>,
+    ) -> CatalanConfig {
+        for column in advice {
+            meta.enable_equality(column);
+        }
+
+        let selector = meta.selector();
+
+        meta.create_gate("catalan factors", |meta| {
+            //
+            //  num       | den   | index | selector
+            //  2(2n + 1) | n + 2 |   n   |    s
+            //
+            let [num, den] = expr::advice_row(meta, advice);
+            let n = meta.query_fixed(index, Rotation::cur());
+            let s = meta.query_selector(selector);
+
+            vec![
+                s.clone() * (num - (n.clone() * expr::constant(4) + expr::constant(2))),
+                s * (den - (n + expr::constant(2))),
+            ]
+        });
+
+        CatalanConfig {
+            advice,
+            index,
+            selector,
+        }
+    }
+
+    /// Assigns `(2(2n+1), n+2)` for the index `n`.
+    pub fn factors(
+        &self,
+        mut layouter: impl Layouter<F>,
+        n: u64,
+    ) -> Result<(ACell<F>, ACell<F>), Error> {
+        let [num, den] = self.config.advice;
+        layouter.assign_region(
+            || "catalan factors",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+                region.assign_fixed(|| "n", self.config.index, 0, || Value::known(F::from(n)))?;
+                let num = region.assign_advice(
+                    || "num",
+                    num,
+                    0,
+                    || Value::known(F::from(2 * (2 * n + 1))),
+                )?;
+                let den =
+                    region.assign_advice(|| "den", den, 0, || Value::known(F::from(n + 2)))?;
+                Ok((num, den))
+            },
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CatalanCircuitConfig {
+    pub catalan: CatalanConfig,
+    pub arithmetic: ArithmeticConfig,
+    pub instance: Column<Instance>,
+}
+
+/// Lays out `C(0)` to `C(NUM_TERMS - 1)` and exposes the last.
+#[derive(Debug, Default)]
+pub struct CatalanCircuit<F: FieldExt> {
+    marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> Circuit<F> for CatalanCircuit<F> {
+    type Config = CatalanCircuitConfig;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let advice = [(); 3].map(|_| meta.advice_column());
+        let index = meta.fixed_column();
+        let constants = meta.fixed_column();
+        meta.enable_constant(constants);
+        let instance = meta.instance_column();
+        meta.enable_equality(instance);
+
+        CatalanCircuitConfig {
+            catalan: CatalanChip::configure(meta, [advice[0], advice[1]], index),
+            arithmetic: ArithmeticChip::configure(meta, advice),
+            instance,
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let catalan = CatalanChip::construct(config.catalan);
+        let arithmetic = ArithmeticChip::construct(config.arithmetic);
+        let [column, ..] = catalan.config.advice;
+
+        let mut c = layouter.assign_region(
+            || "C(0)",
+            |mut region| region.assign_advice_from_constant(|| "C(0)", column, 0, F::one()),
+        )?;
+        for n in 0..NUM_TERMS as u64 - 1 {
+            let (num, den) = catalan.factors(layouter.namespace(|| "factors"), n)?;
+            let product = arithmetic.mul(layouter.namespace(|| "C(n) · num"), &c, &num)?;
+            c = arithmetic.div(layouter.namespace(|| "/ den"), &product, &den)?;
+        }
+
+        layouter.constrain_instance(c.cell(), config.instance, 0)
+    }
+}
+
+/// `C(n)`.
+pub fn nth_term<F: FieldExt>(n: usize) -> F {
+    (0..n as u64).fold(F::one(), |c, i| {
+        c * F::from(2 * (2 * i + 1)) * F::from(i + 2).invert().unwrap()
+    })
+}
@@ -0,0 +1,85 @@
+//! `N` steps of the recurrence `s_{i+1} = H(s_i)`, with [`hash1`] in place
+//! of the Fibonacci step.
+//!
+//! The layout follows `fibonacci::three_column`: one region per step,
+//! each starting from a copy of the previous output, and the head and
+//! tail exposed. A step is a whole [`PoseidonChip`] permutation, 66 rows
+//! against the Fibonacci step's one, and the S-boxes raise the maximum
+//! degree from 3 to 6. Instance rows: `0` the head, `1` the tail.
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    plonk::{Circuit, Column, ConstraintSystem, Error, Instance},
+};
+
+use crate::gadgets::poseidon::{hash1, PoseidonChip, PoseidonConfig};
+
+/// The state after `n` steps from `head`.
+pub fn nth_state<F: FieldExt>(head: F, n: usize) -> F {
+    (0..n).fold(head, |state, _| hash1(state))
+}
+
+#[derive(Debug, Clone)]
+pub struct HashChainConfig<F: FieldExt> {
+    poseidon: PoseidonConfig<F>,
+    instance: Column<Instance>,
+}
+
+#[derive(Debug, Clone)]
+pub struct HashChainCircuit<F: FieldExt, const N: usize> {
+    pub head: Value<F>,
+}
+
+impl<F: FieldExt, const N: usize> Default for HashChainCircuit<F, N> {
+    fn default() -> Self {
+        Self {
+            head: Value::unknown(),
+        }
+    }
+}
+
+impl<F: FieldExt, const N: usize> Circuit<F> for HashChainCircuit<F, N> {
+    type Config = HashChainConfig<F>;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let state = [(); 3].map(|_| meta.advice_column());
+        let constants = meta.fixed_column();
+        meta.enable_constant(constants);
+        let instance = meta.instance_column();
+        meta.enable_equality(instance);
+
+        HashChainConfig {
+            poseidon: PoseidonChip::configure(meta, state),
+            instance,
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let col_head = config.poseidon.state[0];
+        let chip = PoseidonChip::construct(config.poseidon);
+
+        let head = layouter.assign_region(
+            || "head",
+            |mut region| region.assign_advice(|| "head", col_head, 0, || self.head),
+        )?;
+
+        let mut state = head.clone();
+        for _ in 0..N {
+            state = chip.hash1(layouter.namespace(|| "step"), &state)?;
+        }
+
+        layouter.constrain_instance(head.cell(), config.instance, 0)?;
+        layouter.constrain_instance(state.cell(), config.instance, 1)
+    }
+}
@@ -0,0 +1,293 @@
+//! The histogram of the last digits of the sequence, checked against a
+//! claimed table through the lookup argument.
+//!
+//! A lookup only proves inclusion: every input row is somewhere in the
+//! table, however many times. To count, the digits are copied into sorted
+//! order, and each row of the copy carries its position `k` in its run of
+//! equal values:
+//!
+//! ```text
+//!   digit | value  k  end
+//!     1   |   1    1   0
+//!     1   |   1    2   0
+//!     2   |   1    3   1    (1, 3) is looked up in the histogram
+//!     3   |   2    1   1    (2, 1)
+//!     5   |   3    1   0
+//!    ...  |  ...
+//! ```
+//!
+//! `k` starts at 1 and counts up within a run, and the last row of each run
+//! (`end = 1`) looks up `(value, k)` in the claimed `(digit, count)` table.
+//! Runs must strictly increase, the gap looked up in the digit table, so
+//! no digit has two runs, and as the counts add up to the number of rows,
+//! every digit with a non-zero count has its run.
+//!
+//! halo2_proofs 0.2 only looks up into fixed columns, so the sorted copy
+//! cannot be tied to the digits by a lookup. It is a permutation of them
+//! by [`GrandProductChip`] instead, with the same caveat about the
+//! challenge as [`crate::circuits::permutation`]. The histogram is part of
+//! the circuit, fixed at keygen. Instance rows: `0` the challenge.
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    plonk::{Advice, Circuit, Column, ConstraintSystem, Error, Instance, Selector, TableColumn},
+    poly::Rotation,
+};
+
+use crate::{
+    expr,
+    fibonacci::{
+        last_digit::{nth_digit, LastDigitChip, LastDigitConfig},
+        NUM_TERMS,
+    },
+    gadgets::grand_product::{GrandProductChip, GrandProductConfig},
+};
+
+/// Counts of each digit `0..10`.
+pub type Histogram = [u64; 10];
+
+#[derive(Debug, Clone)]
+pub struct HistogramConfig {
+    digits: LastDigitConfig,
+    grand_product: GrandProductConfig,
+    /// `value, k, end` of the sorted copy.
+    sorted: [Column<Advice>; 3],
+    /// Enabled on every row of the sorted copy but the last.
+    s_next: Selector,
+    s_first: Selector,
+    s_last: Selector,
+    /// Enabled on every row of the sorted copy.
+    q_run: Selector,
+    /// Enabled with `s_next`, for the gap between runs.
+    q_gap: Selector,
+    /// `digit, count`.
+    histogram: [TableColumn; 2],
+    instance: Column<Instance>,
+}
+
+/// Proves the last digits of the first [`NUM_TERMS`] terms, seeded with
+/// the digits `a, b`, occur as often as `histogram` says.
+#[derive(Debug, Clone)]
+pub struct HistogramCircuit<F: FieldExt> {
+    pub a: Value<F>,
+    pub b: Value<F>,
+    pub histogram: Histogram,
+}
+
+impl<F: FieldExt> Circuit<F> for HistogramCircuit<F> {
+    type Config = HistogramConfig;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            a: Value::unknown(),
+            b: Value::unknown(),
+            histogram: self.histogram,
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let digit_advice = [(); 2].map(|_| meta.advice_column());
+        let product_advice = [(); 4].map(|_| meta.advice_column());
+        let sorted = [(); 3].map(|_| meta.advice_column());
+        let constants = meta.fixed_column();
+        meta.enable_constant(constants);
+        let instance = meta.instance_column();
+        meta.enable_equality(sorted[0]);
+
+        let digits = LastDigitChip::configure(meta, digit_advice, instance);
+        let grand_product = GrandProductChip::configure(meta, product_advice);
+
+        let s_next = meta.selector();
+        let s_first = meta.selector();
+        let s_last = meta.selector();
+        let q_run = meta.complex_selector();
+        let q_gap = meta.complex_selector();
+        let histogram = [(); 2].map(|_| meta.lookup_table_column());
+        let one = || expr::constant(1);
+
+        meta.create_gate("histogram run", |meta| {
+            //
+            //  value | k  | end | selector
+            //  v     | k  |  e  |    s
+            //  v'    | k' |     |
+            //
+            // e = 0: v' = v, k' = k + 1
+            // e = 1: k' = 1
+            //
+            let [v, k, e] = expr::advice_row(meta, sorted);
+            let v_next = meta.query_advice(sorted[0], Rotation::next());
+            let k_next = meta.query_advice(sorted[1], Rotation::next());
+            let s = meta.query_selector(s_next);
+
+            vec![
+                s.clone() * expr::bool_check(e.clone()),
+                s.clone() * (one() - e.clone()) * (v_next - v),
+                s.clone() * (one() - e.clone()) * (k_next.clone() - k - one()),
+                s * e * (k_next - one()),
+            ]
+        });
+
+        meta.create_gate("histogram ends", |meta| {
+            let k = meta.query_advice(sorted[1], Rotation::cur());
+            let e = meta.query_advice(sorted[2], Rotation::cur());
+            let first = meta.query_selector(s_first);
+            let last = meta.query_selector(s_last);
+
+            vec![first * (k - one()), last * (one() - e)]
+        });
+
+        meta.lookup(|meta| {
+            let [v, k, e] = expr::advice_row(meta, sorted);
+            let q = meta.query_selector(q_run);
+
+            vec![
+                (q.clone() * e.clone() * v, histogram[0]),
+                (q * e * k, histogram[1]),
+            ]
+        });
+
+        meta.lookup(|meta| {
+            let v = meta.query_advice(sorted[0], Rotation::cur());
+            let e = meta.query_advice(sorted[2], Rotation::cur());
+            let v_next = meta.query_advice(sorted[0], Rotation::next());
+            let q = meta.query_selector(q_gap);
+
+            vec![(q * e * (v_next - v - one()), digits.table)]
+        });
+
+        HistogramConfig {
+            digits,
+            grand_product,
+            sorted,
+            s_next,
+            s_first,
+            s_last,
+            q_run,
+            q_gap,
+            histogram,
+            instance,
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        assert_eq!(
+            self.histogram.iter().sum::<u64>(),
+            NUM_TERMS as u64,
+            "the counts must add up to the number of terms"
+        );
+        let digits = LastDigitChip::construct(config.digits.clone());
+        let grand_product = GrandProductChip::construct(config.grand_product.clone());
+
+        digits.load_table(layouter.namespace(|| "digit table"))?;
+        layouter.assign_table(
+            || "histogram",
+            |mut table| {
+                // The row disabled lookups land on; no run has `k = 0`.
+                let rows = std::iter::once((0, 0)).chain(self.histogram.into_iter().enumerate());
+                for (row, (digit, count)) in rows.enumerate() {
+                    let [col_digit, col_count] = config.histogram;
+                    let digit = Value::known(F::from(digit as u64));
+                    table.assign_cell(|| "digit", col_digit, row, || digit)?;
+                    table.assign_cell(
+                        || "count",
+                        col_count,
+                        row,
+                        || Value::known(F::from(count)),
+                    )?;
+                }
+                Ok(())
+            },
+        )?;
+
+        let cells =
+            digits.assign_digits(layouter.namespace(|| "digits"), self.a, self.b, NUM_TERMS)?;
+        let runs = runs(
+            &cells
+                .iter()
+                .map(|cell| cell.value().copied())
+                .collect::<Vec<_>>(),
+        );
+
+        let sorted = layouter.assign_region(
+            || "sorted digits",
+            |mut region| {
+                let [col_v, col_k, col_e] = config.sorted;
+                let mut values = vec![];
+                for (row, run) in runs.iter().enumerate() {
+                    config.q_run.enable(&mut region, row)?;
+                    if row == 0 {
+                        config.s_first.enable(&mut region, row)?;
+                    }
+                    if row + 1 < NUM_TERMS {
+                        config.s_next.enable(&mut region, row)?;
+                        config.q_gap.enable(&mut region, row)?;
+                    } else {
+                        config.s_last.enable(&mut region, row)?;
+                    }
+                    let field = |i: usize| run.map(|run: [u64; 3]| F::from(run[i]));
+                    values.push(region.assign_advice(|| "value", col_v, row, || field(0))?);
+                    region.assign_advice(|| "k", col_k, row, || field(1))?;
+                    region.assign_advice(|| "end", col_e, row, || field(2))?;
+                }
+                Ok(values)
+            },
+        )?;
+
+        let [_, _, col_gamma, _] = config.grand_product.advice;
+        let gamma = layouter.assign_region(
+            || "challenge",
+            |mut region| {
+                region.assign_advice_from_instance(|| "γ", config.instance, 0, col_gamma, 0)
+            },
+        )?;
+        grand_product.check(
+            layouter.namespace(|| "sorted copy"),
+            &gamma,
+            &cells,
+            &sorted,
+        )
+    }
+}
+
+/// The sorted copy of `digits`, `[value, k, end]` per row.
+fn runs<F: FieldExt>(digits: &[Value<F>]) -> Vec<Value<[u64; 3]>> {
+    let sorted = digits
+        .iter()
+        .fold(Value::known(Vec::<u64>::new()), |acc, digit| {
+            acc.zip(*digit).map(|(mut acc, digit)| {
+                acc.push(digit.get_lower_128() as u64);
+                acc
+            })
+        });
+    let runs = sorted.map(|mut sorted| {
+        sorted.sort();
+        let mut rows: Vec<[u64; 3]> = vec![];
+        for (i, &value) in sorted.iter().enumerate() {
+            let k = match rows.last() {
+                Some(&[prev, k, _]) if prev == value => k + 1,
+                _ => 1,
+            };
+            let end = sorted.get(i + 1) != Some(&value);
+            rows.push([value, k, end as u64]);
+        }
+        rows
+    });
+    runs.transpose_vec(digits.len())
+}
+
+/// The histogram of the last digits of the first `n` terms seeded with
+/// `a, b`.
+pub fn histogram(a: u64, b: u64, n: usize) -> Histogram {
+    let mut histogram = [0; 10];
+    for i in 1..=n {
+        histogram[nth_digit(a, b, i) as usize] += 1;
+    }
+    histogram
+}
@@ -0,0 +1,72 @@
+//! A fixed polynomial evaluated at a public point with [`HornerChip`].
+//!
+//! Instance rows: `0` the point `x`, `1` the evaluation `p(x)`. The
+//! coefficients are fixed at keygen, so proofs for different polynomials
+//! need different keys.
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Layouter, SimpleFloorPlanner},
+    plonk::{Circuit, Column, ConstraintSystem, Error, Instance},
+};
+
+use crate::gadgets::horner::{HornerChip, HornerConfig};
+
+#[derive(Debug, Clone)]
+pub struct HornerCircuitConfig {
+    pub horner: HornerConfig,
+    pub instance: Column<Instance>,
+}
+
+/// Evaluates the polynomial with `coeffs`, highest degree first.
+#[derive(Debug, Clone)]
+pub struct HornerCircuit<F: FieldExt> {
+    pub coeffs: Vec<F>,
+}
+
+impl<F: FieldExt> Circuit<F> for HornerCircuit<F> {
+    type Config = HornerCircuitConfig;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        self.clone()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let acc = meta.advice_column();
+        let x = meta.advice_column();
+        let coeff = meta.fixed_column();
+        let instance = meta.instance_column();
+        meta.enable_equality(instance);
+
+        HornerCircuitConfig {
+            horner: HornerChip::configure(meta, acc, x, coeff),
+            instance,
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let x_column = config.horner.x;
+        let chip = HornerChip::construct(config.horner);
+
+        let x = layouter.assign_region(
+            || "load x",
+            |mut region| {
+                region.assign_advice_from_instance(|| "x", config.instance, 0, x_column, 0)
+            },
+        )?;
+        let y = chip.evaluate(layouter.namespace(|| "evaluate"), &self.coeffs, &x)?;
+
+        layouter.constrain_instance(y.cell(), config.instance, 1)
+    }
+}
+
+/// `p(x)` for `coeffs`, highest degree first.
+pub fn evaluate<F: FieldExt>(coeffs: &[F], x: F) -> F {
+    coeffs.iter().fold(F::zero(), |acc, coeff| acc * x + coeff)
+}
@@ -0,0 +1,70 @@
+//! `⟨a, b⟩ = c` for witnessed vectors of length `N`, with
+//! [`InnerProductChip`].
+//!
+//! Instance rows: `0` the inner product.
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    plonk::{Circuit, Column, ConstraintSystem, Error, Instance},
+};
+
+use crate::gadgets::inner_product::{InnerProductChip, InnerProductConfig};
+
+#[derive(Debug, Clone)]
+pub struct InnerProductCircuitConfig {
+    pub inner_product: InnerProductConfig,
+    pub instance: Column<Instance>,
+}
+
+#[derive(Debug, Clone)]
+pub struct InnerProductCircuit<F: FieldExt, const N: usize> {
+    pub a: [Value<F>; N],
+    pub b: [Value<F>; N],
+}
+
+impl<F: FieldExt, const N: usize> Default for InnerProductCircuit<F, N> {
+    fn default() -> Self {
+        Self {
+            a: [Value::unknown(); N],
+            b: [Value::unknown(); N],
+        }
+    }
+}
+
+impl<F: FieldExt, const N: usize> Circuit<F> for InnerProductCircuit<F, N> {
+    type Config = InnerProductCircuitConfig;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let advice = [(); 3].map(|_| meta.advice_column());
+        let instance = meta.instance_column();
+        meta.enable_equality(instance);
+
+        InnerProductCircuitConfig {
+            inner_product: InnerProductChip::configure(meta, advice),
+            instance,
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let chip = InnerProductChip::construct(config.inner_product);
+
+        let product = chip.assign(layouter.namespace(|| "inner product"), &self.a, &self.b)?;
+
+        layouter.constrain_instance(product.result.cell(), config.instance, 0)
+    }
+}
+
+pub fn inner_product<F: FieldExt>(a: &[F], b: &[F]) -> F {
+    a.iter().zip(b).fold(F::zero(), |acc, (a, b)| acc + *a * b)
+}
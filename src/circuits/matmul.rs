@@ -0,0 +1,70 @@
+//! `A · B = C` for witnessed `N×N` matrices, with [`MatrixChip`].
+//!
+//! Instance rows: the entries of `C`, row by row.
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    plonk::{Circuit, Column, ConstraintSystem, Error, Instance},
+};
+
+use crate::gadgets::matrix::{MatrixChip, MatrixConfig};
+
+#[derive(Debug, Clone)]
+pub struct MatMulConfig {
+    matrix: MatrixConfig,
+    instance: Column<Instance>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MatMulCircuit<F: FieldExt, const N: usize> {
+    pub a: [[Value<F>; N]; N],
+    pub b: [[Value<F>; N]; N],
+}
+
+impl<F: FieldExt, const N: usize> Default for MatMulCircuit<F, N> {
+    fn default() -> Self {
+        Self {
+            a: [[Value::unknown(); N]; N],
+            b: [[Value::unknown(); N]; N],
+        }
+    }
+}
+
+impl<F: FieldExt, const N: usize> Circuit<F> for MatMulCircuit<F, N> {
+    type Config = MatMulConfig;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let advice = [(); 3].map(|_| meta.advice_column());
+        let instance = meta.instance_column();
+        meta.enable_equality(instance);
+
+        MatMulConfig {
+            matrix: MatrixChip::configure(meta, advice),
+            instance,
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let chip = MatrixChip::construct(config.matrix);
+
+        let a = chip.load(layouter.namespace(|| "A"), &self.a.map(Vec::from))?;
+        let b = chip.load(layouter.namespace(|| "B"), &self.b.map(Vec::from))?;
+        let c = chip.mul(layouter.namespace(|| "A · B"), &a, &b)?;
+
+        for (row, entry) in c.rows.iter().flatten().enumerate() {
+            layouter.constrain_instance(entry.cell(), config.instance, row)?;
+        }
+        Ok(())
+    }
+}
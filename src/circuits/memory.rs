@@ -0,0 +1,281 @@
+//! A read/write memory checked through an address-sorted copy of its
+//! trace.
+//!
+//! The trace lists accesses in execution order, `time` being the row. A
+//! second group of columns holds the same accesses sorted by `(addr,
+//! time)`, tied to the trace by copy constraints. In sorted order each
+//! address's accesses are contiguous, so memory consistency becomes a
+//! local check between neighbouring rows:
+//!
+//! ```text
+//!   trace                  | sorted
+//!   addr time write value  | addr time write value fresh
+//!    1    0    1     7     |  0    1    1     5      1
+//!    0    1    1     5     |  0    2    0     5      0    read sees the write
+//!    0    2    0     5     |  1    0    1     7      1
+//! ```
+//!
+//! - `fresh` marks the first access to an address: either the address
+//!   grows (`addr - addr_prev - 1` is a byte) or it stays and time grows
+//!   (`time - time_prev - 1` is a byte), which one is looked up in a
+//!   [`ByteTable`].
+//! - A read of a fresh address returns `0`; any other read returns the
+//!   previous value.
+//!
+//! Both gaps have to fit in a byte: consecutive addresses in use at most
+//! 256 apart, and at most 256 steps between two accesses to one address.
+//! A trace touching addresses 0 and 1000 and nothing between, or one that
+//! leaves an address alone for longer, is correct but cannot be proven.
+//!
+//! halo2_proofs 0.2 has neither lookups into advice columns nor verifier
+//! challenges, so the permutation cannot be a multiset check over witnessed
+//! accesses. It is the copy-constraint permutation instead, fixed at keygen
+//! from the access pattern: addresses and the read/write flags are part of
+//! the circuit, and only the values are witnessed. Instance rows: the
+//! values of the reads, in execution order.
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    plonk::{
+        Advice, Circuit, Column, ConstraintSystem, Error, Fixed, Instance, Selector, VirtualCells,
+    },
+    poly::Rotation,
+};
+
+use crate::{expr, gadgets::bytes::ByteTable};
+
+/// One memory access.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Access {
+    Read(u64),
+    Write(u64),
+}
+
+impl Access {
+    pub fn addr(&self) -> u64 {
+        match self {
+            Access::Read(addr) | Access::Write(addr) => *addr,
+        }
+    }
+
+    pub fn is_write(&self) -> bool {
+        matches!(self, Access::Write(_))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MemoryConfig {
+    /// `addr, time, write` of the trace.
+    pub trace: [Column<Fixed>; 3],
+    pub value: Column<Advice>,
+    /// `addr, time, write, value, fresh` in sorted order.
+    pub sorted: [Column<Advice>; 5],
+    pub s_first: Selector,
+    pub q_next: Selector,
+    pub table: ByteTable,
+    pub instance: Column<Instance>,
+}
+
+/// Runs `accesses` and proves every read returns the last value written
+/// to its address, or `0`.
+#[derive(Debug, Clone)]
+pub struct MemoryCircuit<F: FieldExt> {
+    pub accesses: Vec<Access>,
+    /// The value of every access: written, or read back.
+    pub values: Vec<Value<F>>,
+}
+
+impl<F: FieldExt> MemoryCircuit<F> {
+    /// The circuit for `accesses`, witnessing the values of a run where
+    /// the writes store `writes` in order.
+    pub fn new(accesses: Vec<Access>, writes: &[F]) -> Self {
+        let values = run(&accesses, writes)
+            .into_iter()
+            .map(Value::known)
+            .collect();
+        Self { accesses, values }
+    }
+}
+
+impl<F: FieldExt> Circuit<F> for MemoryCircuit<F> {
+    type Config = MemoryConfig;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            accesses: self.accesses.clone(),
+            values: vec![Value::unknown(); self.values.len()],
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let trace = [(); 3].map(|_| meta.fixed_column());
+        let value = meta.advice_column();
+        let sorted = [(); 5].map(|_| meta.advice_column());
+        let table = ByteTable::configure(meta);
+        let instance = meta.instance_column();
+        for column in trace {
+            meta.enable_equality(column);
+        }
+        for column in [value].iter().chain(&sorted) {
+            meta.enable_equality(*column);
+        }
+        meta.enable_equality(instance);
+
+        let s_first = meta.selector();
+        let q_next = meta.complex_selector();
+        let one = || expr::constant(1);
+
+        meta.create_gate("memory first access", |meta| {
+            let write = meta.query_advice(sorted[2], Rotation::cur());
+            let value = meta.query_advice(sorted[3], Rotation::cur());
+            let fresh = meta.query_advice(sorted[4], Rotation::cur());
+            let s = meta.query_selector(s_first);
+
+            vec![s.clone() * (one() - fresh), s * (one() - write) * value]
+        });
+
+        let steps = |meta: &mut VirtualCells<'_, F>| {
+            let [addr, time, write, value, fresh] = expr::advice_row(meta, sorted);
+            let [addr_prev, time_prev, _, value_prev, _] =
+                sorted.map(|column| meta.query_advice(column, Rotation::prev()));
+            let addr_gap = addr - addr_prev - one();
+            let time_gap = time - time_prev - one();
+            (addr_gap, time_gap, write, value, value_prev, fresh)
+        };
+
+        meta.create_gate("memory consistency", |meta| {
+            let (addr_gap, _, write, value, value_prev, fresh) = steps(meta);
+            let q = meta.query_selector(q_next);
+
+            vec![
+                // `fresh` is boolean, and without it the address stays.
+                q.clone() * fresh.clone() * (one() - fresh.clone()),
+                q.clone() * (one() - fresh.clone()) * (addr_gap + one()),
+                // A fresh read sees 0, any other read the previous value.
+                q.clone() * fresh.clone() * (one() - write.clone()) * value.clone(),
+                q * (one() - fresh) * (one() - write) * (value - value_prev),
+            ]
+        });
+
+        meta.lookup(|meta| {
+            let (addr_gap, time_gap, _, _, _, fresh) = steps(meta);
+            let q = meta.query_selector(q_next);
+
+            let gap = fresh.clone() * addr_gap + (one() - fresh) * time_gap;
+            vec![(q * gap, table.column)]
+        });
+
+        MemoryConfig {
+            trace,
+            value,
+            sorted,
+            s_first,
+            q_next,
+            table,
+            instance,
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        assert_eq!(self.accesses.len(), self.values.len());
+        assert!(!self.accesses.is_empty(), "no accesses");
+        config.table.load(layouter.namespace(|| "byte table"))?;
+
+        let mut order: Vec<usize> = (0..self.accesses.len()).collect();
+        order.sort_by_key(|&time| (self.accesses[time].addr(), time));
+
+        let reads = layouter.assign_region(
+            || "memory",
+            |mut region| {
+                let mut trace = vec![];
+                for (time, (access, value)) in self.accesses.iter().zip(&self.values).enumerate() {
+                    let fields = [access.addr(), time as u64, access.is_write() as u64];
+                    let mut cells = vec![];
+                    for (column, field) in config.trace.iter().zip(fields) {
+                        let field = Value::known(F::from(field));
+                        cells.push(region.assign_fixed(|| "trace", *column, time, || field)?);
+                    }
+                    let value = region.assign_advice(|| "value", config.value, time, || *value)?;
+                    trace.push((cells, value));
+                }
+
+                let mut reads = vec![];
+                for (row, &time) in order.iter().enumerate() {
+                    if row == 0 {
+                        config.s_first.enable(&mut region, row)?;
+                    } else {
+                        config.q_next.enable(&mut region, row)?;
+                    }
+                    let (cells, value) = &trace[time];
+                    let access = self.accesses[time];
+                    // Fixed cells carry no value outside keygen, so the
+                    // copies are witnessed from the access itself.
+                    let fields = [access.addr(), time as u64, access.is_write() as u64];
+                    for ((cell, column), field) in cells.iter().zip(&config.sorted).zip(fields) {
+                        let field = Value::known(F::from(field));
+                        let copy = region.assign_advice(|| "sorted", *column, row, || field)?;
+                        region.constrain_equal(cell.cell(), copy.cell())?;
+                    }
+                    value.copy_advice(|| "sorted value", &mut region, config.sorted[3], row)?;
+
+                    let fresh = row == 0 || self.accesses[order[row - 1]].addr() != access.addr();
+                    region.assign_advice(
+                        || "fresh",
+                        config.sorted[4],
+                        row,
+                        || Value::known(F::from(fresh as u64)),
+                    )?;
+                }
+
+                for (access, (_, value)) in self.accesses.iter().zip(&trace) {
+                    if !access.is_write() {
+                        reads.push(value.clone());
+                    }
+                }
+                Ok(reads)
+            },
+        )?;
+
+        for (row, read) in reads.iter().enumerate() {
+            layouter.constrain_instance(read.cell(), config.instance, row)?;
+        }
+        Ok(())
+    }
+}
+
+/// The value of every access when the writes store `writes` in order, on
+/// a memory that starts out zero.
+///
+/// Panics if there are fewer `writes` than write accesses.
+pub fn run<F: FieldExt>(accesses: &[Access], writes: &[F]) -> Vec<F> {
+    let mut memory = std::collections::BTreeMap::new();
+    let mut writes = writes.iter();
+    accesses
+        .iter()
+        .map(|access| match access {
+            Access::Write(addr) => {
+                let value = *writes.next().expect("a value for every write");
+                memory.insert(*addr, value);
+                value
+            }
+            Access::Read(addr) => memory.get(addr).copied().unwrap_or(F::zero()),
+        })
+        .collect()
+}
+
+/// The instance column: the values of the reads, in execution order.
+pub fn public_inputs<F: FieldExt>(accesses: &[Access], values: &[F]) -> Vec<F> {
+    accesses
+        .iter()
+        .zip(values)
+        .filter(|(access, _)| !access.is_write())
+        .map(|(_, value)| *value)
+        .collect()
+}
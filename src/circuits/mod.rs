@@ -0,0 +1,16 @@
+//! Circuits for other sequences and statements, laid out like the
+//! Fibonacci chips in [`crate::fibonacci`].
+
+pub mod catalan;
+pub mod hash_chain;
+pub mod histogram;
+pub mod horner;
+pub mod inner_product;
+pub mod matmul;
+pub mod memory;
+pub mod padovan;
+pub mod permutation;
+pub mod small_range;
+pub mod sorting_network;
+pub mod sparse_merkle;
+pub mod vm;
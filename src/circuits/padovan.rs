@@ -0,0 +1,167 @@
+//! The Padovan sequence, `P(n) = P(n-2) + P(n-3)`, in a single column.
+//!
+//! Like `fibonacci::single_column`, the whole table is one region and the
+//! gate reaches back with rotations, here two and three rows instead of
+//! one and two. Instance rows: `0` the last term.
+
+use std::marker::PhantomData;
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    plonk::{Advice, Circuit, Column, ConstraintSystem, Error, Instance, Selector},
+    poly::Rotation,
+};
+
+use crate::{
+    fibonacci::{ACell, NUM_TERMS},
+    rows::RowCursor,
+};
+
+#[derive(Debug, Clone)]
+pub struct PadovanConfig {
+    pub advice: Column<Advice>,
+    pub selector: Selector,
+    pub instance: Column<Instance>,
+}
+
+#[derive(Debug)]
+pub struct PadovanChip<F: FieldExt> {
+    config: PadovanConfig,
+    marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> PadovanChip<F> {
+    pub fn construct(config: PadovanConfig) -> Self {
+        Self {
+            config,
+            marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: Column<Advice>,
+        instance: Column<Instance>,
+    ) -> PadovanConfig {
+        meta.enable_equality(advice);
+        meta.enable_equality(instance);
+
+        let selector = meta.selector();
+
+        meta.create_gate("padovan", |meta| {
+            //
+            // advice | selector
+            //   a    |
+            //   b    |
+            //   c    |
+            //   d    |    s
+            //
+            // d = a + b
+            //
+            let a = meta.query_advice(advice, Rotation(-3));
+            let b = meta.query_advice(advice, Rotation(-2));
+            let d = meta.query_advice(advice, Rotation::cur());
+
+            let s = meta.query_selector(selector);
+
+            vec![s * (a + b - d)]
+        });
+
+        PadovanConfig {
+            advice,
+            selector,
+            instance,
+        }
+    }
+
+    /// Lays out `nrows` terms starting from `seeds` and returns the last.
+    pub fn assign(
+        &self,
+        mut layouter: impl Layouter<F>,
+        seeds: [Value<F>; 3],
+        nrows: usize,
+    ) -> Result<ACell<F>, Error> {
+        assert!(nrows >= 3, "the table starts with three seeds");
+        layouter.assign_region(
+            || "entire padovan table",
+            |mut region| {
+                let mut rows = RowCursor::new(&mut region);
+                let mut cells = vec![];
+                for seed in seeds {
+                    cells.push(rows.assign_advice("seed", self.config.advice, seed)?);
+                    rows.advance();
+                }
+
+                for _ in 3..nrows {
+                    rows.enable(self.config.selector)?;
+
+                    let row = rows.row();
+                    let value = cells[row - 3].value().copied() + cells[row - 2].value();
+                    cells.push(rows.assign_advice("term", self.config.advice, value)?);
+                    rows.advance();
+                }
+
+                Ok(cells.pop().unwrap())
+            },
+        )
+    }
+
+    pub fn expose_public(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cell: ACell<F>,
+        row: usize,
+    ) -> Result<(), Error> {
+        layouter.constrain_instance(cell.cell(), self.config.instance, row)
+    }
+}
+
+/// Lays out [`NUM_TERMS`] terms from the seeds `P(0), P(1), P(2)` and
+/// exposes the last one.
+#[derive(Debug, Default)]
+pub struct PadovanCircuit<F: FieldExt> {
+    pub seeds: [Value<F>; 3],
+}
+
+impl<F: FieldExt> Circuit<F> for PadovanCircuit<F> {
+    type Config = PadovanConfig;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let advice = meta.advice_column();
+        let instance = meta.instance_column();
+
+        PadovanChip::configure(meta, advice, instance)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let cs = PadovanChip::construct(config);
+
+        let last_cell = cs.assign(
+            layouter.namespace(|| "assign entire table"),
+            self.seeds,
+            NUM_TERMS,
+        )?;
+
+        cs.expose_public(layouter.namespace(|| "expose public"), last_cell, 0)
+    }
+}
+
+/// The `n`th term (counting from 1) of the sequence starting with `seeds`.
+pub fn nth_term<F: FieldExt>(seeds: [F; 3], n: usize) -> F {
+    let [mut a, mut b, mut c] = seeds;
+    for _ in 1..n {
+        (a, b, c) = (b, c, a + b);
+    }
+    a
+}
@@ -0,0 +1,100 @@
+//! A witnessed list proven to be a permutation of a public one with
+//! [`GrandProductChip`], independent of halo2's copy constraints.
+//!
+//! halo2_proofs 0.2 has no verifier challenges, so the challenge is a
+//! public input: a verifier who wants soundness must choose it only after
+//! the witnessed list can no longer change. The demo fixes it to
+//! [`PERMUTATION_CHALLENGE`]. Instance rows: `0` the challenge, `1..=N`
+//! the public list.
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    plonk::{Circuit, Column, ConstraintSystem, Error, Instance},
+};
+
+use crate::gadgets::grand_product::{GrandProductChip, GrandProductConfig};
+
+/// The challenge of the demo: fixed, so only good for showing the layout.
+pub const PERMUTATION_CHALLENGE: u64 = 0x9e37_79b9_7f4a_7c15;
+
+#[derive(Debug, Clone)]
+pub struct PermutationConfig {
+    grand_product: GrandProductConfig,
+    instance: Column<Instance>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PermutationCircuit<F: FieldExt, const N: usize> {
+    /// The permutation of the public list.
+    pub permuted: [Value<F>; N],
+}
+
+impl<F: FieldExt, const N: usize> Default for PermutationCircuit<F, N> {
+    fn default() -> Self {
+        Self {
+            permuted: [Value::unknown(); N],
+        }
+    }
+}
+
+impl<F: FieldExt, const N: usize> Circuit<F> for PermutationCircuit<F, N> {
+    type Config = PermutationConfig;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let advice = [(); 4].map(|_| meta.advice_column());
+        let constants = meta.fixed_column();
+        meta.enable_constant(constants);
+        let instance = meta.instance_column();
+        meta.enable_equality(instance);
+
+        PermutationConfig {
+            grand_product: GrandProductChip::configure(meta, advice),
+            instance,
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let chip = GrandProductChip::construct(config.grand_product.clone());
+        let [col_a, col_b, col_gamma, _] = config.grand_product.advice;
+
+        let (gamma, a, b) = layouter.assign_region(
+            || "load",
+            |mut region| {
+                let instance = config.instance;
+                let gamma =
+                    region.assign_advice_from_instance(|| "γ", instance, 0, col_gamma, 0)?;
+                let mut a = vec![];
+                let mut b = vec![];
+                for (row, permuted) in self.permuted.iter().enumerate() {
+                    a.push(region.assign_advice_from_instance(
+                        || "a",
+                        instance,
+                        row + 1,
+                        col_a,
+                        row,
+                    )?);
+                    b.push(region.assign_advice(|| "b", col_b, row, || *permuted)?);
+                }
+                Ok((gamma, a, b))
+            },
+        )?;
+
+        chip.check(layouter.namespace(|| "permutation"), &gamma, &a, &b)
+    }
+}
+
+/// The instance column of a [`PermutationCircuit`] over `list`.
+pub fn instances<F: FieldExt>(gamma: F, list: &[F]) -> Vec<Vec<F>> {
+    vec![std::iter::once(gamma).chain(list.iter().copied()).collect()]
+}
@@ -0,0 +1,97 @@
+//! Witnessed values each proven below `RANGE`, the `small_range` gadget's
+//! product gate in one piece or split into chunks.
+//!
+//! [`ProductRangeCircuit`] checks `x < 8` with one degree-9 gate and
+//! [`SplitRangeCircuit`] with chunks of two factors and three partial
+//! products, degree 4. The cost report has the same minimum rows for
+//! both, so the split does not lower `k`; what halves is the extended
+//! domain, `2^(k+3)` points against `2^(k+2)`, which sets the size of the
+//! prover's largest FFTs and of the quotient it commits to, 9 pieces
+//! against 4. The three partial product columns cost a commitment and an
+//! evaluation each, so at k = 4 the split proof is 1120 bytes against
+//! 1088. No instance columns.
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    plonk::{Advice, Circuit, Column, ConstraintSystem, Error},
+};
+
+use crate::gadgets::small_range::{SmallRangeChip, SmallRangeConfig};
+
+/// The first ten Fibonacci terms mod 8, all below the range.
+pub const DEMO_VALUES: [u64; 10] = [1, 1, 2, 3, 5, 0, 5, 5, 2, 7];
+
+#[derive(Debug, Clone)]
+pub struct SmallRangeCircuitConfig<const RANGE: usize, const CHUNK: usize> {
+    values: Column<Advice>,
+    range: SmallRangeConfig<RANGE, CHUNK>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SmallRangeCircuit<F: FieldExt, const RANGE: usize, const CHUNK: usize> {
+    pub values: Vec<Value<F>>,
+}
+
+/// `x < 8` as one gate.
+pub type ProductRangeCircuit<F> = SmallRangeCircuit<F, 8, 8>;
+/// `x < 8` in chunks of two factors.
+pub type SplitRangeCircuit<F> = SmallRangeCircuit<F, 8, 2>;
+
+impl<F: FieldExt, const RANGE: usize, const CHUNK: usize> SmallRangeCircuit<F, RANGE, CHUNK> {
+    pub fn new(values: &[u64]) -> Self {
+        Self {
+            values: values.iter().map(|&v| Value::known(F::from(v))).collect(),
+        }
+    }
+}
+
+impl<F: FieldExt, const RANGE: usize, const CHUNK: usize> Circuit<F>
+    for SmallRangeCircuit<F, RANGE, CHUNK>
+{
+    type Config = SmallRangeCircuitConfig<RANGE, CHUNK>;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            values: vec![Value::unknown(); self.values.len()],
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let values = meta.advice_column();
+        meta.enable_equality(values);
+        let x = meta.advice_column();
+
+        SmallRangeCircuitConfig {
+            values,
+            range: SmallRangeChip::configure(meta, x),
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let chip = SmallRangeChip::construct(config.range);
+
+        let cells = layouter.assign_region(
+            || "values",
+            |mut region| {
+                self.values
+                    .iter()
+                    .enumerate()
+                    .map(|(row, &value)| {
+                        region.assign_advice(|| "value", config.values, row, || value)
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            },
+        )?;
+        for cell in &cells {
+            chip.range_check(layouter.namespace(|| "range check"), cell)?;
+        }
+        Ok(())
+    }
+}
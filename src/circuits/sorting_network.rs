@@ -0,0 +1,128 @@
+//! A witnessed array proven to be the public array in sorted order, through
+//! Batcher's odd-even merge sort.
+//!
+//! The network is fixed by `N` alone, so the comparators, and the copies
+//! wiring them, are part of the circuit; only each comparator's swap is
+//! witnessed, by [`CompareSwapChip`]. The network's outputs are copied to
+//! the witnessed `sorted` cells. The inputs are read from the instance
+//! column and must be below `2^64`. Instance rows: `0..N` the array.
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    plonk::{Advice, Circuit, Column, ConstraintSystem, Error, Instance},
+};
+
+use crate::gadgets::compare_swap::{CompareSwapChip, CompareSwapConfig};
+
+#[derive(Debug, Clone)]
+pub struct SortingNetworkConfig {
+    compare_swap: CompareSwapConfig,
+    /// Where the inputs and the witnessed sorted array are laid out.
+    array: Column<Advice>,
+    instance: Column<Instance>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SortingNetworkCircuit<F: FieldExt, const N: usize> {
+    pub sorted: [Value<F>; N],
+}
+
+impl<F: FieldExt, const N: usize> Default for SortingNetworkCircuit<F, N> {
+    fn default() -> Self {
+        Self {
+            sorted: [Value::unknown(); N],
+        }
+    }
+}
+
+impl<F: FieldExt, const N: usize> Circuit<F> for SortingNetworkCircuit<F, N> {
+    type Config = SortingNetworkConfig;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let advice = [(); 6].map(|_| meta.advice_column());
+        let range = meta.advice_column();
+        let array = meta.advice_column();
+        let instance = meta.instance_column();
+        meta.enable_equality(array);
+        meta.enable_equality(instance);
+
+        SortingNetworkConfig {
+            compare_swap: CompareSwapChip::configure(meta, advice, range),
+            array,
+            instance,
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let chip = CompareSwapChip::construct(config.compare_swap);
+
+        let mut wires = layouter.assign_region(
+            || "input",
+            |mut region| {
+                (0..N)
+                    .map(|row| {
+                        region.assign_advice_from_instance(
+                            || "input",
+                            config.instance,
+                            row,
+                            config.array,
+                            row,
+                        )
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            },
+        )?;
+
+        for (i, j) in comparators(N) {
+            let (lo, hi) =
+                chip.compare_swap(layouter.namespace(|| "comparator"), &wires[i], &wires[j])?;
+            (wires[i], wires[j]) = (lo, hi);
+        }
+
+        layouter.assign_region(
+            || "sorted",
+            |mut region| {
+                for (row, (value, wire)) in self.sorted.iter().zip(&wires).enumerate() {
+                    let cell = region.assign_advice(|| "sorted", config.array, row, || *value)?;
+                    region.constrain_equal(cell.cell(), wire.cell())?;
+                }
+                Ok(())
+            },
+        )
+    }
+}
+
+/// The comparators of Batcher's odd-even merge sort on `n` wires, in
+/// order; each `(i, j)` has `i < j` and leaves the smaller value on `i`.
+/// `n` must be a power of two.
+pub fn comparators(n: usize) -> Vec<(usize, usize)> {
+    assert!(n.is_power_of_two(), "{} wires is not a power of two", n);
+    let mut comparators = vec![];
+    let mut p = 1;
+    while p < n {
+        let mut k = p;
+        while k >= 1 {
+            for j in (k % p..n - k).step_by(2 * k) {
+                for i in 0..k.min(n - j - k) {
+                    if (i + j) / (2 * p) == (i + j + k) / (2 * p) {
+                        comparators.push((i + j, i + j + k));
+                    }
+                }
+            }
+            k /= 2;
+        }
+        p *= 2;
+    }
+    comparators
+}
@@ -0,0 +1,96 @@
+//! A key-value statement against a public sparse Merkle root: the leaf at
+//! `key` holds `value`, or, for `value = 0`, `key` has no leaf.
+//!
+//! The path is witnessed and hashed up by [`SparseMerkleChip`]. Instance
+//! rows: `0` the root, `1` the key, `2` the value.
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    plonk::{Circuit, Column, ConstraintSystem, Error, Instance},
+};
+
+use crate::gadgets::sparse_merkle::{SparseMerkleChip, SparseMerkleConfig, SparseMerkleTree};
+
+#[derive(Debug, Clone)]
+pub struct SparseMerkleCircuitConfig<F: FieldExt> {
+    sparse_merkle: SparseMerkleConfig<F>,
+    instance: Column<Instance>,
+}
+
+/// Proves a statement about the leaf at the public key of a tree of depth
+/// `D`.
+#[derive(Debug, Clone)]
+pub struct SparseMerkleCircuit<F: FieldExt, const D: usize> {
+    /// The siblings from the leaf up, `None` for an empty subtree.
+    pub path: [Value<Option<F>>; D],
+}
+
+impl<F: FieldExt, const D: usize> SparseMerkleCircuit<F, D> {
+    /// The circuit for the leaf at `key`, with its instance column.
+    pub fn new(tree: &SparseMerkleTree<F>, key: u64) -> (Self, Vec<Vec<F>>) {
+        let path = tree.path(key);
+        assert_eq!(path.len(), D, "the tree does not have depth {}", D);
+        let circuit = Self {
+            path: std::array::from_fn(|level| Value::known(path[level])),
+        };
+        (
+            circuit,
+            vec![vec![tree.root(), F::from(key), tree.get(key)]],
+        )
+    }
+}
+
+impl<F: FieldExt, const D: usize> Default for SparseMerkleCircuit<F, D> {
+    fn default() -> Self {
+        Self {
+            path: [Value::unknown(); D],
+        }
+    }
+}
+
+impl<F: FieldExt, const D: usize> Circuit<F> for SparseMerkleCircuit<F, D> {
+    type Config = SparseMerkleCircuitConfig<F>;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let advice = [(); 6].map(|_| meta.advice_column());
+        let constants = meta.fixed_column();
+        meta.enable_constant(constants);
+        let instance = meta.instance_column();
+        meta.enable_equality(instance);
+
+        SparseMerkleCircuitConfig {
+            sparse_merkle: SparseMerkleChip::configure(meta, advice),
+            instance,
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let [col_key, col_value, ..] = config.sparse_merkle.advice;
+        let chip = SparseMerkleChip::construct(config.sparse_merkle);
+
+        let (key, value) = layouter.assign_region(
+            || "load",
+            |mut region| {
+                let instance = config.instance;
+                let key = region.assign_advice_from_instance(|| "key", instance, 1, col_key, 0)?;
+                let value =
+                    region.assign_advice_from_instance(|| "value", instance, 2, col_value, 0)?;
+                Ok((key, value))
+            },
+        )?;
+
+        let root = chip.root(layouter.namespace(|| "path"), &key, &value, &self.path)?;
+        layouter.constrain_instance(root.cell(), config.instance, 0)
+    }
+}
@@ -0,0 +1,186 @@
+//! The instruction set of the VM and a native interpreter for it.
+
+use std::{error, fmt};
+
+use halo2_proofs::arithmetic::FieldExt;
+
+/// Registers `r0` to `r3`, all starting at `0`.
+pub const REGISTERS: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Opcode {
+    /// `r[dst] = r[src1] + r[src2]`.
+    Add,
+    /// `r[dst] = imm`.
+    Mov,
+    /// Jump to `imm` if `r[src1] ≠ 0`.
+    Jnz,
+    /// Stop; the registers hold the result.
+    Halt,
+}
+
+impl Opcode {
+    pub const ALL: [Opcode; 4] = [Opcode::Add, Opcode::Mov, Opcode::Jnz, Opcode::Halt];
+
+    /// The opcode's number in the circuit. Numbering starts at `1`, so an
+    /// all-zero row never decodes to an instruction.
+    pub fn code(self) -> u64 {
+        match self {
+            Opcode::Add => 1,
+            Opcode::Mov => 2,
+            Opcode::Jnz => 3,
+            Opcode::Halt => 4,
+        }
+    }
+}
+
+/// One instruction. Operands an opcode does not use are zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Instruction {
+    pub opcode: Opcode,
+    pub dst: usize,
+    pub src1: usize,
+    pub src2: usize,
+    pub imm: i64,
+}
+
+impl Instruction {
+    pub fn add(dst: usize, src1: usize, src2: usize) -> Self {
+        Self::new(Opcode::Add, dst, src1, src2, 0)
+    }
+
+    pub fn mov(dst: usize, imm: i64) -> Self {
+        Self::new(Opcode::Mov, dst, 0, 0, imm)
+    }
+
+    pub fn jnz(src: usize, target: usize) -> Self {
+        Self::new(Opcode::Jnz, 0, src, 0, target as i64)
+    }
+
+    pub fn halt() -> Self {
+        Self::new(Opcode::Halt, 0, 0, 0, 0)
+    }
+
+    fn new(opcode: Opcode, dst: usize, src1: usize, src2: usize, imm: i64) -> Self {
+        assert!(
+            dst < REGISTERS && src1 < REGISTERS && src2 < REGISTERS,
+            "no such register"
+        );
+        Instruction {
+            opcode,
+            dst,
+            src1,
+            src2,
+            imm,
+        }
+    }
+
+    /// `opcode, dst, src1, src2, imm` as they sit in the circuit.
+    pub fn fields<F: FieldExt>(&self) -> [F; 5] {
+        [
+            F::from(self.opcode.code()),
+            F::from(self.dst as u64),
+            F::from(self.src1 as u64),
+            F::from(self.src2 as u64),
+            self.imm(),
+        ]
+    }
+
+    /// `imm` as a field element.
+    pub fn imm<F: FieldExt>(&self) -> F {
+        let magnitude = F::from(self.imm.unsigned_abs());
+        if self.imm < 0 {
+            -magnitude
+        } else {
+            magnitude
+        }
+    }
+}
+
+/// The machine state before an instruction executes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step<F: FieldExt> {
+    pub pc: usize,
+    pub registers: [F; REGISTERS],
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VmError {
+    /// The program did not halt within the step limit.
+    StepLimit(usize),
+    /// `pc` left the program.
+    PcOutOfRange(usize),
+}
+
+/// Runs `program` from `pc = 0` for at most `max_steps` instructions and
+/// returns the state before each one, ending with the `HALT`.
+pub fn execute<F: FieldExt>(
+    program: &[Instruction],
+    max_steps: usize,
+) -> Result<Vec<Step<F>>, VmError> {
+    let mut state = Step {
+        pc: 0,
+        registers: [F::zero(); REGISTERS],
+    };
+    let mut trace = vec![];
+    while trace.len() < max_steps {
+        let instruction = program
+            .get(state.pc)
+            .ok_or(VmError::PcOutOfRange(state.pc))?;
+        trace.push(state);
+
+        let r = &mut state.registers;
+        match instruction.opcode {
+            Opcode::Add => r[instruction.dst] = r[instruction.src1] + r[instruction.src2],
+            Opcode::Mov => r[instruction.dst] = instruction.imm(),
+            Opcode::Jnz if r[instruction.src1] != F::zero() => {
+                state.pc = instruction.imm as usize;
+                continue;
+            }
+            Opcode::Jnz => {}
+            Opcode::Halt => return Ok(trace),
+        }
+        state.pc += 1;
+    }
+    Err(VmError::StepLimit(max_steps))
+}
+
+/// A program leaving `F(2n)` in `r0` and `F(2n+1)` in `r1`, two terms per
+/// loop iteration, with `r2` counting the iterations down.
+pub fn fibonacci_program(n: u64) -> Vec<Instruction> {
+    assert!(n > 0, "the loop runs at least once");
+    vec![
+        Instruction::mov(0, 0),
+        Instruction::mov(1, 1),
+        Instruction::mov(2, n as i64),
+        Instruction::mov(3, -1),
+        // loop:
+        Instruction::add(0, 0, 1),
+        Instruction::add(1, 0, 1),
+        Instruction::add(2, 2, 3),
+        Instruction::jnz(2, 4),
+        Instruction::halt(),
+    ]
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.opcode {
+            Opcode::Add => write!(f, "ADD r{}, r{}, r{}", self.dst, self.src1, self.src2),
+            Opcode::Mov => write!(f, "MOV r{}, {}", self.dst, self.imm),
+            Opcode::Jnz => write!(f, "JNZ r{}, {}", self.src1, self.imm),
+            Opcode::Halt => write!(f, "HALT"),
+        }
+    }
+}
+
+impl fmt::Display for VmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VmError::StepLimit(steps) => write!(f, "no HALT within {} steps", steps),
+            VmError::PcOutOfRange(pc) => write!(f, "pc {} is outside the program", pc),
+        }
+    }
+}
+
+impl error::Error for VmError {}
@@ -0,0 +1,461 @@
+//! A tiny register machine executing a fixed program, one instruction per
+//! row.
+//!
+//! Each row holds the registers before its instruction, the instruction
+//! itself, and the instruction decoded into one-hot flags: one group for
+//! the opcode and one per register operand, each constrained to be boolean,
+//! to sum to one, and to recompose the encoded field. From the flags the
+//! row computes its operands and result,
+//!
+//! ```text
+//! lhs = r[src1]        rhs = r[src2]
+//! out = add·(lhs + rhs) + mov·imm
+//! ```
+//!
+//! and the transition to the next row writes `out` to `r[dst]` for `ADD`
+//! and `MOV` and leaves every other register alone. The condition flag
+//! `cond` is constrained boolean and equal to `lhs ≠ 0`, which comes from
+//! [`IsZeroChip`], and the `pc` moves on by
+//!
+//! ```text
+//! pc_next = pc + (1 - halt) + jnz·cond·(imm - pc - 1)
+//! ```
+//!
+//! so `JNZ` lands on `imm` when `cond` is set and every other instruction
+//! but `HALT` falls through to `pc + 1`. The first row starts at `pc = 0`
+//! with zero registers.
+//!
+//! `HALT` keeps the `pc` and the registers, so the rows after it repeat it
+//! up to a fixed number of rows, and the last row has to be a `HALT`: any
+//! program halting within that many steps fits the same layout. The
+//! instruction of every row is witnessed and fetched from the program in a
+//! [`ProgramTable`] by a lookup on `(pc, opcode, dst, src1, src2, imm)`, so
+//! the program is fixed at keygen. It has no inputs; only the execution is
+//! witnessed. Instance rows: `0` the final `r1`.
+
+use std::marker::PhantomData;
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Layouter, Region, SimpleFloorPlanner, Value},
+    plonk::{
+        Advice, Circuit, Column, ConstraintSystem, Error, Expression, Instance, Selector,
+        VirtualCells,
+    },
+    poly::Rotation,
+};
+
+use crate::{
+    expr,
+    fibonacci::ACell,
+    gadgets::is_zero::{IsZeroChip, IsZeroConfig},
+};
+use isa::{Instruction, Opcode, Step, REGISTERS};
+use rom::ProgramTable;
+
+pub mod isa;
+pub mod rom;
+
+/// One-hot flags for a field taking `N` values.
+#[derive(Debug, Clone, Copy)]
+struct Decoded<const N: usize> {
+    flags: [Column<Advice>; N],
+}
+
+impl<const N: usize> Decoded<N> {
+    fn configure<F: FieldExt>(meta: &mut ConstraintSystem<F>) -> Self {
+        Decoded {
+            flags: [(); N].map(|_| meta.advice_column()),
+        }
+    }
+
+    fn query<F: FieldExt>(&self, meta: &mut VirtualCells<'_, F>) -> [Expression<F>; N] {
+        expr::advice_row(meta, self.flags)
+    }
+
+    /// The flags are boolean, exactly one is set, and `field` is the
+    /// `code` of the set one.
+    fn constraints<F: FieldExt>(
+        &self,
+        meta: &mut VirtualCells<'_, F>,
+        field: Expression<F>,
+        code: impl Fn(usize) -> u64,
+    ) -> Vec<Expression<F>> {
+        let flags = self.query(meta);
+        let mut constraints: Vec<_> = flags.iter().cloned().map(expr::bool_check).collect();
+        constraints.push(expr::constant(1) - expr::sum(flags.iter().cloned()));
+        let recomposed = expr::sum(
+            flags
+                .iter()
+                .enumerate()
+                .map(|(i, flag)| flag.clone() * expr::constant(code(i))),
+        );
+        constraints.push(field - recomposed);
+        constraints
+    }
+
+    fn assign<F: FieldExt>(
+        &self,
+        region: &mut Region<'_, F>,
+        row: usize,
+        set: usize,
+    ) -> Result<(), Error> {
+        for (i, column) in self.flags.iter().enumerate() {
+            let flag = Value::known(F::from((i == set) as u64));
+            region.assign_advice(|| "flag", *column, row, || flag)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct VmConfig<F: FieldExt> {
+    pc: Column<Advice>,
+    /// `opcode, dst, src1, src2, imm` of the row's instruction.
+    instruction: [Column<Advice>; 5],
+    /// `r[src1] ≠ 0`, the condition of `JNZ`.
+    cond: Column<Advice>,
+    registers: [Column<Advice>; REGISTERS],
+    opcode: Decoded<4>,
+    dst: Decoded<REGISTERS>,
+    src1: Decoded<REGISTERS>,
+    src2: Decoded<REGISTERS>,
+    /// `lhs, rhs, out`.
+    operands: [Column<Advice>; 3],
+    is_zero: IsZeroConfig<F>,
+    q_exec: Selector,
+    q_step: Selector,
+    s_first: Selector,
+    s_last: Selector,
+    program: ProgramTable,
+    instance: Column<Instance>,
+}
+
+#[derive(Debug)]
+pub struct VmChip<F: FieldExt> {
+    config: VmConfig<F>,
+    is_zero: IsZeroChip<F>,
+    marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> VmChip<F> {
+    pub fn construct(config: VmConfig<F>) -> Self {
+        Self {
+            is_zero: IsZeroChip::construct(config.is_zero.clone()),
+            config,
+            marker: PhantomData,
+        }
+    }
+
+    pub fn configure(meta: &mut ConstraintSystem<F>, instance: Column<Instance>) -> VmConfig<F> {
+        let pc = meta.advice_column();
+        let instruction = [(); 5].map(|_| meta.advice_column());
+        let cond = meta.advice_column();
+        let registers = [(); REGISTERS].map(|_| meta.advice_column());
+        let opcode = Decoded::configure(meta);
+        let dst = Decoded::configure(meta);
+        let src1 = Decoded::configure(meta);
+        let src2 = Decoded::configure(meta);
+        let operands = [(); 3].map(|_| meta.advice_column());
+        let value_inv = meta.advice_column();
+        meta.enable_equality(registers[1]);
+        meta.enable_equality(instance);
+
+        let program = ProgramTable::configure(meta);
+
+        let q_exec = meta.complex_selector();
+        let q_step = meta.selector();
+        let s_first = meta.selector();
+        let s_last = meta.selector();
+
+        let one = || expr::constant(1);
+        let select = |meta: &mut VirtualCells<'_, F>, flags: &Decoded<REGISTERS>| {
+            let flags = flags.query(meta);
+            let registers = expr::advice_row(meta, registers);
+            expr::sum(flags.into_iter().zip(registers).map(|(flag, r)| flag * r))
+        };
+
+        meta.create_gate("vm decode", |meta| {
+            let [op, dst_field, src1_field, src2_field, _] = expr::advice_row(meta, instruction);
+            let q = meta.query_selector(q_exec);
+
+            let mut constraints = opcode.constraints(meta, op, |i| Opcode::ALL[i].code());
+            constraints.extend(dst.constraints(meta, dst_field, |i| i as u64));
+            constraints.extend(src1.constraints(meta, src1_field, |i| i as u64));
+            constraints.extend(src2.constraints(meta, src2_field, |i| i as u64));
+            constraints
+                .into_iter()
+                .map(|constraint| q.clone() * constraint)
+                .collect::<Vec<_>>()
+        });
+
+        meta.create_gate("vm operands", |meta| {
+            let [lhs, rhs, out] = expr::advice_row(meta, operands);
+            let imm = meta.query_advice(instruction[4], Rotation::cur());
+            let [add, mov, _, _] = opcode.query(meta);
+            let r_src1 = select(meta, &src1);
+            let r_src2 = select(meta, &src2);
+            let q = meta.query_selector(q_exec);
+
+            vec![
+                q.clone() * (lhs.clone() - r_src1),
+                q.clone() * (rhs.clone() - r_src2),
+                q * (out - (add * (lhs + rhs) + mov * imm)),
+            ]
+        });
+
+        meta.lookup(|meta| {
+            let pc = meta.query_advice(pc, Rotation::cur());
+            let fields = expr::advice_row(meta, instruction);
+            let q = meta.query_selector(q_exec);
+
+            std::iter::once((q.clone() * pc, program.pc))
+                .chain(
+                    fields
+                        .into_iter()
+                        .zip(program.fields)
+                        .map(|(field, column)| (q.clone() * field, column)),
+                )
+                .collect()
+        });
+
+        let is_zero = IsZeroChip::configure(
+            meta,
+            |meta| meta.query_selector(q_exec),
+            |meta| meta.query_advice(operands[0], Rotation::cur()),
+            value_inv,
+        );
+
+        meta.create_gate("vm condition", |meta| {
+            let cond = meta.query_advice(cond, Rotation::cur());
+            let q = meta.query_selector(q_exec);
+
+            vec![
+                q.clone() * expr::bool_check(cond.clone()),
+                q * (one() - is_zero.is_zero_expr.clone() - cond),
+            ]
+        });
+
+        meta.create_gate("vm pc", |meta| {
+            let [_, _, jnz, halt] = opcode.query(meta);
+            let cur = meta.query_advice(pc, Rotation::cur());
+            let next = meta.query_advice(pc, Rotation::next());
+            let cond = meta.query_advice(cond, Rotation::cur());
+            let imm = meta.query_advice(instruction[4], Rotation::cur());
+            let q = meta.query_selector(q_step);
+
+            let jump = jnz * cond * (imm - cur.clone() - one());
+            vec![q * (next - cur - (one() - halt) - jump)]
+        });
+
+        meta.create_gate("vm transition", |meta| {
+            let [add, mov, _, _] = opcode.query(meta);
+            let dst = dst.query(meta);
+            let out = meta.query_advice(operands[2], Rotation::cur());
+            let q = meta.query_selector(q_step);
+
+            registers
+                .iter()
+                .zip(dst)
+                .map(|(column, dst)| {
+                    let cur = meta.query_advice(*column, Rotation::cur());
+                    let next = meta.query_advice(*column, Rotation::next());
+                    let written = dst * (add.clone() + mov.clone());
+                    q.clone() * (next - cur.clone() - written * (out.clone() - cur))
+                })
+                .collect::<Vec<_>>()
+        });
+
+        meta.create_gate("vm start", |meta| {
+            let s = meta.query_selector(s_first);
+            std::iter::once(pc)
+                .chain(registers)
+                .map(|column| s.clone() * meta.query_advice(column, Rotation::cur()))
+                .collect::<Vec<_>>()
+        });
+
+        meta.create_gate("vm end", |meta| {
+            let [_, _, _, halt] = opcode.query(meta);
+            let s = meta.query_selector(s_last);
+
+            vec![s * (one() - halt)]
+        });
+
+        VmConfig {
+            pc,
+            instruction,
+            cond,
+            registers,
+            opcode,
+            dst,
+            src1,
+            src2,
+            operands,
+            is_zero,
+            q_exec,
+            q_step,
+            s_first,
+            s_last,
+            program,
+            instance,
+        }
+    }
+
+    /// Loads `program` into the program table. Call it once per circuit.
+    pub fn load_program(
+        &self,
+        layouter: impl Layouter<F>,
+        program: &[Instruction],
+    ) -> Result<(), Error> {
+        self.config.program.load(layouter, program)
+    }
+
+    /// Lays out the execution `trace` of `program`, repeating its final
+    /// `HALT` up to `rows` rows, and returns the final `r1`.
+    pub fn assign(
+        &self,
+        mut layouter: impl Layouter<F>,
+        program: &[Instruction],
+        trace: &[Step<F>],
+        rows: usize,
+    ) -> Result<ACell<F>, Error> {
+        assert!(trace.len() <= rows, "the execution takes more rows");
+        let config = &self.config;
+        let last = *trace.last().expect("a trace ends with its HALT");
+        layouter.assign_region(
+            || "execution",
+            |mut region| {
+                let mut r1 = None;
+                for row in 0..rows {
+                    let step = trace.get(row).copied().unwrap_or(last);
+                    let instruction = program[step.pc];
+                    config.q_exec.enable(&mut region, row)?;
+                    if row == 0 {
+                        config.s_first.enable(&mut region, row)?;
+                    }
+                    if row + 1 < rows {
+                        config.q_step.enable(&mut region, row)?;
+                    } else {
+                        config.s_last.enable(&mut region, row)?;
+                    }
+
+                    region.assign_advice(
+                        || "pc",
+                        config.pc,
+                        row,
+                        || Value::known(F::from(step.pc as u64)),
+                    )?;
+                    for (column, field) in config.instruction.iter().zip(instruction.fields::<F>())
+                    {
+                        region.assign_advice(
+                            || "instruction",
+                            *column,
+                            row,
+                            || Value::known(field),
+                        )?;
+                    }
+
+                    let opcode = Opcode::ALL
+                        .iter()
+                        .position(|opcode| *opcode == instruction.opcode)
+                        .unwrap();
+                    config.opcode.assign(&mut region, row, opcode)?;
+                    config.dst.assign(&mut region, row, instruction.dst)?;
+                    config.src1.assign(&mut region, row, instruction.src1)?;
+                    config.src2.assign(&mut region, row, instruction.src2)?;
+
+                    for (i, column) in config.registers.iter().enumerate() {
+                        let value = Value::known(step.registers[i]);
+                        let cell = region.assign_advice(|| "register", *column, row, || value)?;
+                        if i == 1 {
+                            r1 = Some(cell);
+                        }
+                    }
+
+                    let lhs = step.registers[instruction.src1];
+                    let rhs = step.registers[instruction.src2];
+                    let out = match instruction.opcode {
+                        Opcode::Add => lhs + rhs,
+                        Opcode::Mov => instruction.imm(),
+                        Opcode::Jnz | Opcode::Halt => F::zero(),
+                    };
+                    for (column, value) in config.operands.iter().zip([lhs, rhs, out]) {
+                        region.assign_advice(|| "operand", *column, row, || Value::known(value))?;
+                    }
+                    self.is_zero.assign(&mut region, row, Value::known(lhs))?;
+                    region.assign_advice(
+                        || "cond",
+                        config.cond,
+                        row,
+                        || Value::known(F::from((lhs != F::zero()) as u64)),
+                    )?;
+                }
+                Ok(r1.unwrap())
+            },
+        )
+    }
+
+    pub fn expose_public(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cell: ACell<F>,
+        row: usize,
+    ) -> Result<(), Error> {
+        layouter.constrain_instance(cell.cell(), self.config.instance, row)
+    }
+}
+
+/// Runs `program` and proves its execution, exposing the final `r1`.
+#[derive(Debug, Clone)]
+pub struct VmCircuit<F: FieldExt> {
+    pub program: Vec<Instruction>,
+    /// Execution rows, `HALT` included; the program has to halt within
+    /// them.
+    pub rows: usize,
+    marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> VmCircuit<F> {
+    pub fn new(program: Vec<Instruction>, rows: usize) -> Self {
+        Self {
+            program,
+            rows,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<F: FieldExt> Circuit<F> for VmCircuit<F> {
+    type Config = VmConfig<F>;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        self.clone()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let instance = meta.instance_column();
+
+        VmChip::configure(meta, instance)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let trace = isa::execute(&self.program, self.rows).map_err(|_| Error::Synthesis)?;
+        let chip = VmChip::construct(config);
+        chip.load_program(layouter.namespace(|| "program"), &self.program)?;
+
+        let r1 = chip.assign(
+            layouter.namespace(|| "execute"),
+            &self.program,
+            &trace,
+            self.rows,
+        )?;
+
+        chip.expose_public(layouter.namespace(|| "expose r1"), r1, 0)
+    }
+}
@@ -0,0 +1,55 @@
+//! The program as a lookup table, one instruction per row keyed by its
+//! `pc`.
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Layouter, Value},
+    plonk::{ConstraintSystem, Error, TableColumn},
+};
+
+use super::isa::Instruction;
+
+/// `pc, opcode, dst, src1, src2, imm` of every instruction.
+///
+/// Row `0` is all zeros, which is what a lookup sees on rows where its
+/// selector is off; no instruction encodes to it since opcodes count from
+/// `1`. The program follows from row `1`.
+#[derive(Debug, Clone, Copy)]
+pub struct ProgramTable {
+    pub pc: TableColumn,
+    pub fields: [TableColumn; 5],
+}
+
+impl ProgramTable {
+    pub fn configure<F: FieldExt>(meta: &mut ConstraintSystem<F>) -> Self {
+        ProgramTable {
+            pc: meta.lookup_table_column(),
+            fields: [(); 5].map(|_| meta.lookup_table_column()),
+        }
+    }
+
+    pub fn load<F: FieldExt>(
+        &self,
+        mut layouter: impl Layouter<F>,
+        program: &[Instruction],
+    ) -> Result<(), Error> {
+        layouter.assign_table(
+            || "program table",
+            |mut table| {
+                let rows = std::iter::once((F::zero(), [F::zero(); 5])).chain(
+                    program
+                        .iter()
+                        .enumerate()
+                        .map(|(pc, instruction)| (F::from(pc as u64), instruction.fields())),
+                );
+                for (offset, (pc, fields)) in rows.enumerate() {
+                    table.assign_cell(|| "pc", self.pc, offset, || Value::known(pc))?;
+                    for (column, field) in self.fields.iter().zip(fields) {
+                        table.assign_cell(|| "field", *column, offset, || Value::known(field))?;
+                    }
+                }
+                Ok(())
+            },
+        )
+    }
+}
@@ -0,0 +1,189 @@
+//! The copy constraints of a synthesized circuit, as the cycles of cells
+//! they wire together.
+//!
+//! Each cycle is listed under the earliest region with a cell in it, its
+//! cells in region order, so a chain of copies from one step to the next
+//! reads top to bottom,
+//!
+//! ```text
+//! region 0 `first row`, row 0
+//!   A2@0 c = 2  ==  A1@1 b = 2 [region 1]  ==  A0@2 a = 2 [region 2]
+//! ```
+//!
+//! Cells assigned outside any region, such as the constants the floor
+//! planner places, are grouped at the end, and instance cells are marked
+//! as such. A cycle whose known values differ is one the witness fails.
+
+use std::{collections::HashMap, fmt};
+
+use serde::Serialize;
+
+use crate::introspect::{
+    format_field,
+    witness::{CellRef, Witness},
+    ColumnKind,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CopyReport {
+    pub circuit: String,
+    pub k: u32,
+    pub groups: Vec<RegionCycles>,
+}
+
+/// The cycles first reached from one region.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RegionCycles {
+    /// Index into the witness's regions, `None` for cells outside any.
+    pub region: Option<usize>,
+    pub name: String,
+    pub rows: Option<(usize, usize)>,
+    pub cycles: Vec<CopyCycle>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CopyCycle {
+    pub cells: Vec<CycleCell>,
+    /// Whether every known value in the cycle is the same.
+    pub consistent: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CycleCell {
+    /// The cell as `A0@3`.
+    pub cell: String,
+    pub region: Option<usize>,
+    pub instance: bool,
+    pub annotation: String,
+    /// `?` when unassigned or unknown.
+    pub value: String,
+}
+
+impl CopyReport {
+    /// Groups the copy cycles of `witness` by region, keeping only regions
+    /// whose name contains `filter` when one is given.
+    pub fn of(name: &str, witness: &Witness, filter: Option<&str>) -> Self {
+        let mut assigned = HashMap::new();
+        for (index, region) in witness.regions.iter().enumerate() {
+            for cell in &region.cells {
+                assigned.insert(cell.cell, (index, cell.annotation.as_str()));
+            }
+        }
+        let region_of = |cell: &CellRef| assigned.get(cell).map(|(index, _)| *index);
+
+        let mut groups: Vec<RegionCycles> = vec![];
+        for mut cells in witness.copy_cycles() {
+            // Cells outside any region sort last.
+            cells.sort_by_key(|cell| (region_of(cell).is_none(), region_of(cell), cell.row));
+            let region = region_of(&cells[0]);
+            let values: Vec<_> = cells
+                .iter()
+                .map(|cell| witness.value(cell.column, cell.row))
+                .collect();
+            let known: Vec<_> = values.iter().flatten().collect();
+            let cycle = CopyCycle {
+                consistent: known.windows(2).all(|pair| pair[0] == pair[1]),
+                cells: cells
+                    .iter()
+                    .zip(values)
+                    .map(|(cell, value)| CycleCell {
+                        cell: format!("{}@{}", cell.column, cell.row),
+                        region: region_of(cell),
+                        instance: cell.column.kind == ColumnKind::Instance,
+                        annotation: assigned
+                            .get(cell)
+                            .map_or("", |(_, annotation)| annotation)
+                            .to_string(),
+                        value: value.map_or("?".to_string(), format_field),
+                    })
+                    .collect(),
+            };
+
+            match groups.iter_mut().find(|group| group.region == region) {
+                Some(group) => group.cycles.push(cycle),
+                None => groups.push(RegionCycles {
+                    region,
+                    name: region.map_or("outside regions".to_string(), |index| {
+                        witness.regions[index].name.clone()
+                    }),
+                    rows: region.and_then(|index| witness.regions[index].rows),
+                    cycles: vec![cycle],
+                }),
+            }
+        }
+        groups.sort_by_key(|group| (group.region.is_none(), group.region));
+        if let Some(filter) = filter {
+            groups.retain(|group| group.name.contains(filter));
+        }
+
+        CopyReport {
+            circuit: name.to_string(),
+            k: witness.k,
+            groups,
+        }
+    }
+
+    pub fn consistent(&self) -> bool {
+        self.cycles().all(|cycle| cycle.consistent)
+    }
+
+    fn cycles(&self) -> impl Iterator<Item = &CopyCycle> {
+        self.groups.iter().flat_map(|group| &group.cycles)
+    }
+}
+
+impl fmt::Display for CopyReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "circuit: {} (k = {})", self.circuit, self.k)?;
+        for group in &self.groups {
+            match (group.region, group.rows) {
+                (Some(index), Some((first, last))) if first == last => {
+                    writeln!(f, "region {} `{}`, row {}", index, group.name, first)?
+                }
+                (Some(index), Some((first, last))) => writeln!(
+                    f,
+                    "region {} `{}`, rows {}..={}",
+                    index, group.name, first, last
+                )?,
+                (Some(index), None) => writeln!(f, "region {} `{}`", index, group.name)?,
+                (None, _) => writeln!(f, "{}", group.name)?,
+            }
+            for cycle in &group.cycles {
+                let cells: Vec<_> = cycle
+                    .cells
+                    .iter()
+                    .map(|cell| {
+                        let mut text = cell.cell.clone();
+                        if !cell.annotation.is_empty() {
+                            text += &format!(" {}", cell.annotation);
+                        }
+                        text += &format!(" = {}", cell.value);
+                        if cell.instance {
+                            text += " [instance]";
+                        } else if cell.region != group.region {
+                            match cell.region {
+                                Some(index) => text += &format!(" [region {}]", index),
+                                None => text += " [outside regions]",
+                            }
+                        }
+                        text
+                    })
+                    .collect();
+                writeln!(
+                    f,
+                    "  {}{}",
+                    cells.join("  ==  "),
+                    if cycle.consistent { "" } else { "  MISMATCH" }
+                )?;
+            }
+        }
+        let cycles = self.cycles().count();
+        let cells: usize = self.cycles().map(|cycle| cycle.cells.len()).sum();
+        let mismatched = self.cycles().filter(|cycle| !cycle.consistent).count();
+        write!(
+            f,
+            "{} cycles over {} cells, {} mismatched",
+            cycles, cells, mismatched
+        )
+    }
+}
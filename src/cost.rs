@@ -0,0 +1,330 @@
+//! Cost breakdown of a circuit at a given `k`.
+//!
+//! This is a thin, serializable view over [`CircuitCost`]. halo2_proofs 0.2
+//! keeps the measured fields private and only exposes them through `Debug`,
+//! so the numbers are read back out of that representation with
+//! [`Repr`](crate::introspect::debug_repr::Repr). The copy constraints
+//! are counted from a [`Witness`] of the circuit.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+};
+
+use halo2_proofs::{
+    dev::CircuitCost,
+    pasta::{Eq, Fp},
+    plonk::{Circuit, ConstraintSystem, Error},
+};
+use serde::Serialize;
+
+use crate::introspect::{debug_repr::Repr, witness::Witness, ConstraintSystemInfo};
+
+/// Size of a compressed vesta point in a proof.
+const POINT_BYTES: usize = 32;
+/// Size of an `Fp` scalar in a proof.
+const SCALAR_BYTES: usize = 32;
+
+/// The parts of a proof, in the order halo2 lays them out.
+const PROOF_PARTS: [&str; 8] = [
+    "instance",
+    "advice",
+    "fixed",
+    "lookups",
+    "equality",
+    "vanishing",
+    "multiopen",
+    "polycomm",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CostReport {
+    pub circuit: String,
+    pub k: u32,
+    pub max_degree: usize,
+    /// The prover evaluates the quotient on `2^extended_k` points.
+    pub extended_k: u32,
+    /// Rows at the end of every advice column filled with random values,
+    /// whether or not the proof is blinded with them.
+    pub blinding_factors: usize,
+    /// Rows left for the circuit once the blinding rows and the row before
+    /// them, where the permutation argument closes, are reserved.
+    pub usable_rows: usize,
+    pub minimum_rows: usize,
+    pub advice_columns: usize,
+    pub advice_queries: usize,
+    pub instance_queries: usize,
+    pub fixed_queries: usize,
+    pub lookups: usize,
+    pub permutation_columns: usize,
+    pub point_sets: usize,
+    pub copies: CopyCount,
+    pub proof: ProofCost,
+}
+
+/// The equality constraints a synthesis adds, copies to instance cells
+/// included.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CopyCount {
+    pub total: usize,
+    /// Added outside any region, by `constrain_instance` and by the floor
+    /// planner placing constants.
+    pub outside_regions: usize,
+    /// By region name, in order of first appearance.
+    pub regions: Vec<RegionCopies>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RegionCopies {
+    pub name: String,
+    /// Regions with this name.
+    pub regions: usize,
+    /// Copies added by all of them.
+    pub copies: usize,
+}
+
+impl CopyCount {
+    pub fn of(witness: &Witness) -> Self {
+        let mut regions: Vec<RegionCopies> = vec![];
+        for region in &witness.regions {
+            match regions.iter_mut().find(|r| r.name == region.name) {
+                Some(r) => {
+                    r.regions += 1;
+                    r.copies += region.copies;
+                }
+                None => regions.push(RegionCopies {
+                    name: region.name.clone(),
+                    regions: 1,
+                    copies: region.copies,
+                }),
+            }
+        }
+        let total = witness.copies.len();
+        let outside_regions = total - regions.iter().map(|r| r.copies).sum::<usize>();
+        CopyCount {
+            total,
+            outside_regions,
+            regions,
+        }
+    }
+}
+
+/// Estimated size of a proof for a single instance of the circuit.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ProofCost {
+    /// Group elements in the proof.
+    pub points: usize,
+    /// Field elements in the proof.
+    pub scalars: usize,
+    pub bytes: usize,
+    pub parts: Vec<ProofPart>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ProofPart {
+    pub name: &'static str,
+    pub points: usize,
+    pub scalars: usize,
+}
+
+impl CostReport {
+    /// Measures `circuit` at `k`, first synthesizing it against `instances`,
+    /// which fails like the `MockProver` does when the circuit does not
+    /// fit.
+    pub fn try_measure<C: Circuit<Fp> + fmt::Debug>(
+        name: &str,
+        k: u32,
+        circuit: &C,
+        instances: Vec<Vec<Fp>>,
+    ) -> Result<Self, Error> {
+        let witness = Witness::synthesize(k, circuit, instances)?;
+        Ok(Self::measure(name, k, circuit, &witness))
+    }
+
+    /// Measures `circuit` at `k`, with `witness` a synthesis of it at `k`.
+    ///
+    /// Panics if `k` is not large enough for the circuit, like
+    /// [`CircuitCost::measure`].
+    pub fn measure<C: Circuit<Fp> + fmt::Debug>(
+        name: &str,
+        k: u32,
+        circuit: &C,
+        witness: &Witness,
+    ) -> Self {
+        let mut cs = ConstraintSystem::default();
+        C::configure(&mut cs);
+
+        let cost = CircuitCost::<Eq, C>::measure(k as usize, circuit);
+        let cost_repr = Repr::parse(&format!("{:?}", cost)).expect("CircuitCost Debug output");
+        let field = |name| cost_repr.field(name).as_usize();
+
+        let max_degree = field("max_deg");
+        let point_sets = point_sets(
+            &ConstraintSystemInfo::from_cs(&cs),
+            max_degree,
+            cs.blinding_factors(),
+        );
+
+        let size_repr =
+            Repr::parse(&format!("{:?}", cost.proof_size(1))).expect("ProofSize Debug output");
+        let mut parts: Vec<_> = PROOF_PARTS
+            .into_iter()
+            .map(|name| {
+                let contribution = size_repr.field(name);
+                ProofPart {
+                    name,
+                    points: contribution.field("commitments").as_usize(),
+                    scalars: contribution.field("evaluations").as_usize(),
+                }
+            })
+            .collect();
+        // One evaluation per point set; see `point_sets`.
+        parts
+            .iter_mut()
+            .find(|part| part.name == "multiopen")
+            .unwrap()
+            .scalars = point_sets;
+
+        let points = parts.iter().map(|part| part.points).sum();
+        let scalars = parts.iter().map(|part| part.scalars).sum();
+
+        CostReport {
+            circuit: name.to_string(),
+            k,
+            max_degree,
+            extended_k: extended_k(k, max_degree),
+            blinding_factors: cs.blinding_factors(),
+            usable_rows: (1usize << k).saturating_sub(cs.blinding_factors() + 1),
+            minimum_rows: cs.minimum_rows(),
+            advice_columns: field("advice_columns"),
+            advice_queries: field("advice_queries"),
+            instance_queries: field("instance_queries"),
+            fixed_queries: field("fixed_queries"),
+            lookups: field("lookups"),
+            permutation_columns: field("permutation_cols"),
+            point_sets,
+            copies: CopyCount::of(witness),
+            proof: ProofCost {
+                points,
+                scalars,
+                bytes: points * POINT_BYTES + scalars * SCALAR_BYTES,
+                parts,
+            },
+        }
+    }
+}
+
+/// The `k` of the extended domain, as `EvaluationDomain::new` picks it:
+/// large enough for the quotient, of degree `max_degree - 1` times `2^k`.
+fn extended_k(k: u32, max_degree: usize) -> u32 {
+    let quotient = max_degree.saturating_sub(1) as u64;
+    let mut extended_k = k;
+    while (1u64 << extended_k) < (1u64 << k) * quotient {
+        extended_k += 1;
+    }
+    extended_k
+}
+
+/// Counts the distinct sets of rotations the multiopen argument opens
+/// polynomials at.
+///
+/// `CircuitCost` always counts the sets a lookup argument would add, which
+/// overestimates lookup-free circuits by one scalar; this follows what the
+/// prover actually writes.
+fn point_sets(cs: &ConstraintSystemInfo, max_degree: usize, blinding_factors: usize) -> usize {
+    let mut column_queries: BTreeMap<_, BTreeSet<i32>> = BTreeMap::new();
+    for query in cs
+        .advice_queries
+        .iter()
+        .chain(&cs.instance_queries)
+        .chain(&cs.fixed_queries)
+    {
+        column_queries
+            .entry(query.column)
+            .or_default()
+            .insert(query.rotation);
+    }
+    for column in &cs.permutation_columns {
+        column_queries.entry(*column).or_default().insert(0);
+    }
+
+    let mut sets: BTreeSet<Vec<i32>> = column_queries
+        .into_values()
+        .map(|rotations| rotations.into_iter().collect())
+        .collect();
+    // Selectors are compressed into fixed columns queried at the current row.
+    if cs.num_selectors > 0 {
+        sets.insert(vec![0]);
+    }
+    if !cs.lookups.is_empty() {
+        sets.insert(vec![0, 1]);
+        sets.insert(vec![-1, 0]);
+        sets.insert(vec![0]);
+    }
+    if !cs.permutation_columns.is_empty() {
+        sets.insert(vec![0, 1]);
+        // Chained permutation products are also opened at the last usable row.
+        if cs.permutation_columns.len() > max_degree - 2 {
+            let last = -(blinding_factors as i32 + 1);
+            sets.insert(vec![last, 0, 1]);
+        }
+    }
+    sets.len()
+}
+
+impl fmt::Display for CostReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "circuit: {} (k = {})", self.circuit, self.k)?;
+        writeln!(
+            f,
+            "max degree: {} (extended domain 2^{})",
+            self.max_degree, self.extended_k
+        )?;
+        writeln!(
+            f,
+            "rows: {} total, {} usable, {} blinding, {} minimum",
+            1u64 << self.k,
+            self.usable_rows,
+            self.blinding_factors,
+            self.minimum_rows
+        )?;
+        writeln!(f, "advice columns: {}", self.advice_columns)?;
+        writeln!(
+            f,
+            "queries: {} advice, {} instance, {} fixed",
+            self.advice_queries, self.instance_queries, self.fixed_queries
+        )?;
+        writeln!(f, "lookups: {}", self.lookups)?;
+        writeln!(f, "permutation columns: {}", self.permutation_columns)?;
+        writeln!(f, "point sets: {}", self.point_sets)?;
+        let copies = &self.copies;
+        writeln!(
+            f,
+            "copy constraints: {} ({} outside regions)",
+            copies.total, copies.outside_regions
+        )?;
+        for region in copies.regions.iter().filter(|region| region.copies > 0) {
+            writeln!(
+                f,
+                "- {}: {} in {} region{}",
+                region.name,
+                region.copies,
+                region.regions,
+                if region.regions == 1 { "" } else { "s" }
+            )?;
+        }
+        writeln!(f, "proof:")?;
+        for part in &self.proof.parts {
+            writeln!(
+                f,
+                "- {:<10} {:>3} points, {:>3} scalars",
+                part.name, part.points, part.scalars
+            )?;
+        }
+        write!(
+            f,
+            "total: {} points, {} scalars, {} bytes",
+            self.proof.points, self.proof.scalars, self.proof.bytes
+        )
+    }
+}
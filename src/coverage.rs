@@ -0,0 +1,317 @@
+//! Which rows each selector is enabled on, and the rows it should have
+//! been.
+//!
+//! halo2 cannot tell a selector left off by mistake from one left off on
+//! purpose, so this looks for three symptoms of the former:
+//!
+//! - a gate active on no row at all, whose constraints are dead for the
+//!   circuit's parameters,
+//! - a row with advice cells, in a region that enables selectors, that no
+//!   active gate constraint or lookup queries, say the last row of a loop
+//!   in one region never enabled, and
+//! - a region that enables different selectors, relative to its first
+//!   row, than most regions of the same name, which are usually the steps
+//!   of one loop.
+//!
+//! Activity follows [`explain`](crate::explain).
+
+use std::{
+    collections::{BTreeMap, BTreeSet, HashSet},
+    fmt,
+};
+
+use serde::Serialize;
+
+use crate::introspect::{witness::Witness, ColumnKind};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CoverageReport {
+    pub circuit: String,
+    pub k: u32,
+    pub selectors: Vec<SelectorCoverage>,
+    pub gates: Vec<GateCoverage>,
+    pub issues: Vec<CoverageIssue>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SelectorCoverage {
+    pub selector: usize,
+    /// Maximal runs of enabled rows, as inclusive `(first, last)`.
+    pub rows: Vec<(usize, usize)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GateCoverage {
+    pub gate: usize,
+    pub name: String,
+    /// Rows on which at least one of the gate's constraints is active.
+    pub rows: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum CoverageIssue {
+    /// A gate none of whose constraints is active on any row.
+    DeadGate { gate: usize, name: String },
+    /// A row with advice cells that no active constraint reaches, in a
+    /// region that enables selectors on others.
+    UncoveredRow {
+        region: usize,
+        name: String,
+        row: usize,
+    },
+    /// A region whose `(selector, offset)` enables differ from those of
+    /// most regions with its name.
+    UnlikeSiblings {
+        region: usize,
+        name: String,
+        enabled: Vec<(usize, usize)>,
+        expected: Vec<(usize, usize)>,
+    },
+}
+
+impl CoverageReport {
+    pub fn of(name: &str, witness: &Witness) -> Self {
+        let selectors = witness
+            .selectors
+            .iter()
+            .enumerate()
+            .map(|(selector, rows)| SelectorCoverage {
+                selector,
+                rows: runs(rows),
+            })
+            .collect();
+
+        let gates: Vec<_> = witness
+            .cs
+            .gates
+            .iter()
+            .enumerate()
+            .map(|(gate, info)| {
+                let selectors: Vec<_> = info
+                    .constraints
+                    .iter()
+                    .map(|constraint| constraint.poly.selectors())
+                    .collect();
+                GateCoverage {
+                    gate,
+                    name: info.name.clone(),
+                    rows: (0..witness.rows())
+                        .filter(|row| selectors.iter().any(|s| active(witness, s, *row)))
+                        .count(),
+                }
+            })
+            .collect();
+
+        let mut issues: Vec<_> = gates
+            .iter()
+            .filter(|gate| gate.rows == 0)
+            .map(|gate| CoverageIssue::DeadGate {
+                gate: gate.gate,
+                name: gate.name.clone(),
+            })
+            .collect();
+        let reached = reached_rows(witness);
+        // Regions that enable nothing only load cells for copies.
+        for (index, region) in witness.regions.iter().enumerate() {
+            if region.selectors.is_empty() {
+                continue;
+            }
+            let rows: BTreeSet<_> = region
+                .cells
+                .iter()
+                .filter(|cell| cell.cell.column.kind == ColumnKind::Advice)
+                .map(|cell| cell.cell.row)
+                .collect();
+            for row in rows {
+                if !reached.contains(&row) {
+                    issues.push(CoverageIssue::UncoveredRow {
+                        region: index,
+                        name: region.name.clone(),
+                        row,
+                    });
+                }
+            }
+        }
+
+        let mut siblings: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+        for (index, region) in witness.regions.iter().enumerate() {
+            siblings.entry(&region.name).or_default().push(index);
+        }
+        for indices in siblings.values().filter(|indices| indices.len() > 2) {
+            let patterns: Vec<_> = indices
+                .iter()
+                .map(|index| pattern(witness, *index))
+                .collect();
+            let mut counts: BTreeMap<&Vec<(usize, usize)>, usize> = BTreeMap::new();
+            for pattern in &patterns {
+                *counts.entry(pattern).or_default() += 1;
+            }
+            let (expected, count) = counts
+                .iter()
+                .max_by_key(|(_, count)| **count)
+                .expect("regions");
+            // Without a clear majority there is no expected pattern.
+            if 2 * count <= indices.len() {
+                continue;
+            }
+            for (index, enabled) in indices.iter().zip(&patterns) {
+                if enabled != *expected {
+                    issues.push(CoverageIssue::UnlikeSiblings {
+                        region: *index,
+                        name: witness.regions[*index].name.clone(),
+                        enabled: enabled.clone(),
+                        expected: (*expected).clone(),
+                    });
+                }
+            }
+        }
+
+        CoverageReport {
+            circuit: name.to_string(),
+            k: witness.k,
+            selectors,
+            gates,
+            issues,
+        }
+    }
+
+    pub fn passed(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// The rows holding an advice cell that some active constraint queries.
+fn reached_rows(witness: &Witness) -> HashSet<usize> {
+    let polys = witness
+        .cs
+        .gates
+        .iter()
+        .flat_map(|gate| gate.constraints.iter().map(|constraint| &constraint.poly))
+        .chain(witness.cs.lookups.iter().flat_map(|lookup| &lookup.inputs));
+    let mut rows = HashSet::new();
+    for poly in polys {
+        let selectors = poly.selectors();
+        let rotations: BTreeSet<_> = poly
+            .queries()
+            .iter()
+            .filter(|query| query.column.kind == ColumnKind::Advice)
+            .map(|query| query.rotation)
+            .collect();
+        for row in 0..witness.rows() {
+            if active(witness, &selectors, row) {
+                rows.extend(rotations.iter().map(|r| witness.rotate(row, *r)));
+            }
+        }
+    }
+    rows
+}
+
+/// Whether a constraint gated by `selectors` is active on `row`: one of
+/// them is enabled there, or there are none.
+fn active(witness: &Witness, selectors: &BTreeSet<usize>, row: usize) -> bool {
+    selectors.is_empty() || selectors.iter().any(|s| witness.selectors[*s][row])
+}
+
+/// A region's enabled selectors as `(selector, offset from its first row)`.
+fn pattern(witness: &Witness, index: usize) -> Vec<(usize, usize)> {
+    let region = &witness.regions[index];
+    let first = region.rows.map_or(0, |(first, _)| first);
+    let mut enabled: Vec<_> = region
+        .selectors
+        .iter()
+        .map(|(selector, row)| (*selector, row.saturating_sub(first)))
+        .collect();
+    enabled.sort();
+    enabled.dedup();
+    enabled
+}
+
+fn runs(rows: &[bool]) -> Vec<(usize, usize)> {
+    let mut runs: Vec<(usize, usize)> = vec![];
+    for (row, _) in rows.iter().enumerate().filter(|(_, enabled)| **enabled) {
+        match runs.last_mut() {
+            Some((_, last)) if *last + 1 == row => *last = row,
+            _ => runs.push((row, row)),
+        }
+    }
+    runs
+}
+
+fn format_enabled(enabled: &[(usize, usize)]) -> String {
+    if enabled.is_empty() {
+        return "no selectors".to_string();
+    }
+    enabled
+        .iter()
+        .map(|(selector, offset)| format!("S{}@+{}", selector, offset))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl fmt::Display for CoverageReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "circuit: {} (k = {})", self.circuit, self.k)?;
+        for selector in &self.selectors {
+            let total: usize = selector
+                .rows
+                .iter()
+                .map(|(first, last)| last - first + 1)
+                .sum();
+            let runs: Vec<_> = selector
+                .rows
+                .iter()
+                .map(|(first, last)| match first == last {
+                    true => first.to_string(),
+                    false => format!("{}..={}", first, last),
+                })
+                .collect();
+            match total {
+                0 => writeln!(f, "S{}: never enabled", selector.selector)?,
+                _ => writeln!(
+                    f,
+                    "S{}: {} rows: {}",
+                    selector.selector,
+                    total,
+                    runs.join(", ")
+                )?,
+            }
+        }
+        for gate in &self.gates {
+            writeln!(
+                f,
+                "gate {} `{}`: active on {} rows",
+                gate.gate, gate.name, gate.rows
+            )?;
+        }
+        for issue in &self.issues {
+            match issue {
+                CoverageIssue::DeadGate { gate, name } => {
+                    writeln!(f, "gate {} `{}` is active on no row", gate, name)?
+                }
+                CoverageIssue::UncoveredRow { region, name, row } => writeln!(
+                    f,
+                    "row {} of region {} `{}` is reached by no active constraint",
+                    row, region, name
+                )?,
+                CoverageIssue::UnlikeSiblings {
+                    region,
+                    name,
+                    enabled,
+                    expected,
+                } => writeln!(
+                    f,
+                    "region {} `{}` enables {} where most `{}` regions enable {}",
+                    region,
+                    name,
+                    format_enabled(enabled),
+                    name,
+                    format_enabled(expected)
+                )?,
+            }
+        }
+        match self.issues.len() {
+            0 => write!(f, "no coverage issues"),
+            n => write!(f, "{} coverage issues", n),
+        }
+    }
+}
@@ -0,0 +1,228 @@
+//! The reference, the `MockProver` and the real prover on the same random
+//! cases, which must agree.
+//!
+//! Each case comes from [`SoakCase::generate`]. The `MockProver` and the
+//! real prover and verifier are given the term [`reference`] computes and
+//! the next field element, and must both accept the first and reject the
+//! second. An input only one of them accepts points at the integration
+//! between the two paths rather than at the witness. Each case also records how
+//! long the real prover took against the `MockProver`, as a baseline for
+//! noticing either one slow down.
+//!
+//! [`reference`]: crate::reference
+
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
+
+use halo2_proofs::{
+    circuit::Value,
+    dev::MockProver,
+    pasta::{EqAffine, Fp},
+    plonk::Circuit,
+    poly::commitment::Params,
+};
+use serde::Serialize;
+
+use crate::{
+    fibonacci::{single_column, three_column},
+    prover,
+    soak::{SoakCase, SoakFailure},
+    spec::Layout,
+};
+
+/// Whether each path accepts a public input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Verdicts {
+    pub mock: bool,
+    pub real: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct Comparison {
+    pub case: SoakCase,
+    /// The verdicts on the reference term.
+    pub right: Verdicts,
+    /// The verdicts on the reference term plus one.
+    pub wrong: Verdicts,
+    pub mock_time: Duration,
+    /// Keygen, proving and verifying.
+    pub real_time: Duration,
+}
+
+impl Comparison {
+    /// Both paths accept the reference term and reject the wrong one.
+    pub fn agrees(&self) -> bool {
+        let accepted = |v: Verdicts| v.mock && v.real;
+        let rejected = |v: Verdicts| !v.mock && !v.real;
+        accepted(self.right) && rejected(self.wrong)
+    }
+
+    /// How many times longer the real prover took than the `MockProver`.
+    pub fn ratio(&self) -> f64 {
+        self.real_time.as_secs_f64() / self.mock_time.as_secs_f64().max(f64::EPSILON)
+    }
+}
+
+/// Runs one case through both paths with `params`, which must be for `k`.
+pub fn compare(params: &Params<EqAffine>, k: u32, case: &SoakCase) -> Comparison {
+    let (a, b) = (Value::known(case.a), Value::known(case.b));
+    match case.layout {
+        Layout::ThreeColumn => {
+            let circuit = three_column::FiboTermsCircuit { a, b, n: case.n };
+            run(params, k, case, circuit)
+        }
+        Layout::SingleColumn => {
+            let circuit = single_column::FiboTermsCircuit { a, b, n: case.n };
+            run(params, k, case, circuit)
+        }
+    }
+}
+
+fn run<C: Circuit<Fp>>(
+    params: &Params<EqAffine>,
+    k: u32,
+    case: &SoakCase,
+    circuit: C,
+) -> Comparison {
+    let right = vec![vec![case.expected()]];
+    let wrong = vec![vec![case.expected() + Fp::one()]];
+
+    let start = Instant::now();
+    let mock = |instances: &Vec<Vec<Fp>>| {
+        MockProver::run(k, &circuit, instances.clone())
+            .map(|prover| prover.verify().is_ok())
+            .unwrap_or(false)
+    };
+    let mock = (mock(&right), mock(&wrong));
+    let mock_time = start.elapsed();
+
+    // A proof cannot be made for a wrong public input: the prover does not
+    // check the instance, so this proves the right one and verifies the
+    // proof against both.
+    let start = Instant::now();
+    let real = match prover::keygen(params, &circuit) {
+        Ok(pk) => match prover::create_proof(params, &pk, circuit, &right) {
+            Ok(proof) => {
+                let verifies = |instances: &Vec<Vec<Fp>>| {
+                    prover::verify_proof(params, pk.get_vk(), &proof, instances).is_ok()
+                };
+                (verifies(&right), verifies(&wrong))
+            }
+            Err(_) => (false, false),
+        },
+        Err(_) => (false, false),
+    };
+    let real_time = start.elapsed();
+
+    Comparison {
+        case: case.clone(),
+        right: Verdicts {
+            mock: mock.0,
+            real: real.0,
+        },
+        wrong: Verdicts {
+            mock: mock.1,
+            real: real.1,
+        },
+        mock_time,
+        real_time,
+    }
+}
+
+/// The outcome of a differential run.
+#[derive(Debug, Clone, Serialize)]
+pub struct DifferentialReport {
+    pub seed: u64,
+    pub k: u32,
+    pub cases: usize,
+    /// The cases the paths disagreed on.
+    pub disagreements: Vec<SoakFailure>,
+    /// The real prover's time over the `MockProver`'s, `(min, median,
+    /// max)` across the cases, if there were any.
+    pub ratio: Option<(f64, f64, f64)>,
+}
+
+impl DifferentialReport {
+    pub fn passed(&self) -> bool {
+        self.disagreements.is_empty()
+    }
+}
+
+/// Compares `cases` cases from `seed` at `k`, calling `on_case` after each
+/// with its index and comparison. Case `i` takes seed `seed + i`, as in a
+/// soak run.
+pub fn differential(
+    params: &Params<EqAffine>,
+    k: u32,
+    seed: u64,
+    cases: usize,
+    mut on_case: impl FnMut(usize, &Comparison),
+) -> DifferentialReport {
+    let mut disagreements = vec![];
+    let mut ratios = vec![];
+    for i in 0..cases {
+        let case = SoakCase::generate(seed.wrapping_add(i as u64), k);
+        let comparison = compare(params, k, &case);
+        on_case(i, &comparison);
+        ratios.push(comparison.ratio());
+        if !comparison.agrees() {
+            disagreements.push(SoakFailure::new(&case, k, &Disagreement(&comparison)));
+        }
+    }
+    ratios.sort_by(f64::total_cmp);
+    let ratio = match (ratios.first(), ratios.last()) {
+        (Some(min), Some(max)) => Some((*min, ratios[ratios.len() / 2], *max)),
+        _ => None,
+    };
+    DifferentialReport {
+        seed,
+        k,
+        cases,
+        disagreements,
+        ratio,
+    }
+}
+
+/// What each path said, for a case they disagreed on.
+struct Disagreement<'a>(&'a Comparison);
+
+impl fmt::Display for Disagreement<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let verdict = |accepts: bool| if accepts { "accepts" } else { "rejects" };
+        let Comparison { right, wrong, .. } = self.0;
+        write!(
+            f,
+            "the MockProver {} the reference term and {} the wrong one, the verifier {} and {}",
+            verdict(right.mock),
+            verdict(wrong.mock),
+            verdict(right.real),
+            verdict(wrong.real)
+        )
+    }
+}
+
+impl fmt::Display for DifferentialReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} cases from seed {} at k = {}: {} disagreed",
+            self.cases,
+            self.seed,
+            self.k,
+            self.disagreements.len()
+        )?;
+        if let Some((min, median, max)) = self.ratio {
+            writeln!(
+                f,
+                "the real prover took {:.1}x the MockProver's time (min {:.1}x, max {:.1}x)",
+                median, min, max
+            )?;
+        }
+        for disagreement in &self.disagreements {
+            writeln!(f, "  {}", disagreement)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,157 @@
+//! Text encodings for proof files, so envelopes and bundles can travel
+//! through channels that are not binary safe.
+//!
+//! Encoded text ends with a newline, and decoding ignores whitespace
+//! anywhere in it, so wrapped or pasted text decodes as well.
+
+use std::{error, fmt, str::FromStr};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Encoding {
+    /// The bytes as they are.
+    #[default]
+    Raw,
+    /// Lowercase hex, two digits per byte.
+    Hex,
+    /// Standard Base64 with padding (RFC 4648).
+    Base64,
+}
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EncodingError {
+    /// A character outside the encoding's alphabet, at its offset in the
+    /// input.
+    InvalidCharacter(usize),
+    /// The input is not a whole number of hex pairs or Base64 quads.
+    InvalidLength(usize),
+}
+
+impl Encoding {
+    pub const ALL: [Encoding; 3] = [Encoding::Raw, Encoding::Hex, Encoding::Base64];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Encoding::Raw => "raw",
+            Encoding::Hex => "hex",
+            Encoding::Base64 => "base64",
+        }
+    }
+
+    pub fn encode(&self, bytes: &[u8]) -> Vec<u8> {
+        let mut text = match self {
+            Encoding::Raw => return bytes.to_vec(),
+            Encoding::Hex => bytes.iter().map(|b| format!("{:02x}", b)).collect(),
+            Encoding::Base64 => encode_base64(bytes),
+        };
+        text.push('\n');
+        text.into_bytes()
+    }
+
+    pub fn decode(&self, input: &[u8]) -> Result<Vec<u8>, EncodingError> {
+        if *self == Encoding::Raw {
+            return Ok(input.to_vec());
+        }
+        let text: Vec<(usize, u8)> = input
+            .iter()
+            .copied()
+            .enumerate()
+            .filter(|(_, c)| !c.is_ascii_whitespace())
+            .collect();
+        match self {
+            Encoding::Raw => unreachable!(),
+            Encoding::Hex => decode_hex(&text),
+            Encoding::Base64 => decode_base64(&text),
+        }
+    }
+}
+
+fn encode_base64(bytes: &[u8]) -> String {
+    let mut text = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let mut group = [0; 3];
+        group[..chunk.len()].copy_from_slice(chunk);
+        let n = u32::from_be_bytes([0, group[0], group[1], group[2]]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                text.push(BASE64[(n >> (18 - 6 * i)) as usize & 63] as char);
+            } else {
+                text.push('=');
+            }
+        }
+    }
+    text
+}
+
+fn decode_hex(text: &[(usize, u8)]) -> Result<Vec<u8>, EncodingError> {
+    if !text.len().is_multiple_of(2) {
+        return Err(EncodingError::InvalidLength(text.len()));
+    }
+    let digit = |&(offset, c): &(usize, u8)| {
+        (c as char)
+            .to_digit(16)
+            .ok_or(EncodingError::InvalidCharacter(offset))
+    };
+    text.chunks(2)
+        .map(|pair| Ok((digit(&pair[0])? << 4 | digit(&pair[1])?) as u8))
+        .collect()
+}
+
+fn decode_base64(text: &[(usize, u8)]) -> Result<Vec<u8>, EncodingError> {
+    if !text.len().is_multiple_of(4) {
+        return Err(EncodingError::InvalidLength(text.len()));
+    }
+    let mut bytes = Vec::with_capacity(text.len() / 4 * 3);
+    for (i, quad) in text.chunks(4).enumerate() {
+        let last = i + 1 == text.len() / 4;
+        let padding = quad.iter().rev().take_while(|(_, c)| *c == b'=').count();
+        if padding > 2 || (padding > 0 && !last) {
+            let (offset, _) = quad[4 - padding];
+            return Err(EncodingError::InvalidCharacter(offset));
+        }
+        let mut n = 0u32;
+        for &(offset, c) in &quad[..4 - padding] {
+            let value = BASE64
+                .iter()
+                .position(|&symbol| symbol == c)
+                .ok_or(EncodingError::InvalidCharacter(offset))?;
+            n = n << 6 | value as u32;
+        }
+        n <<= 6 * padding;
+        bytes.extend(&n.to_be_bytes()[1..4 - padding]);
+    }
+    Ok(bytes)
+}
+
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Encoding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Encoding::ALL
+            .into_iter()
+            .find(|encoding| encoding.name() == s)
+            .ok_or_else(|| format!("unknown encoding `{}`, expected raw, hex or base64", s))
+    }
+}
+
+impl fmt::Display for EncodingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EncodingError::InvalidCharacter(offset) => {
+                write!(f, "invalid character at byte {}", offset)
+            }
+            EncodingError::InvalidLength(len) => {
+                write!(f, "{} characters do not decode to whole bytes", len)
+            }
+        }
+    }
+}
+
+impl error::Error for EncodingError {}
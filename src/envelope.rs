@@ -0,0 +1,365 @@
+//! The proof envelope: a proof plus everything needed to check it.
+//!
+//! Layout, all integers little-endian:
+//!
+//! ```text
+//! magic        4 bytes   "FIBP"
+//! version      u8        ENVELOPE_VERSION
+//! transcript   u8        TranscriptKind
+//! k            u32
+//! n            u32       the statement's size, as [`CircuitKind::n`]
+//! circuit      u16 length, then UTF-8 name
+//! fingerprint  32 bytes  verifying-key fingerprint
+//! instances    u32 column count, then per column a u32 length and that
+//!              many 32-byte canonical field elements
+//! proof        u32 length, then the transcript bytes
+//! ```
+//!
+//! [`ProofEnvelope::to_cbor`] writes the same fields as a CBOR map instead:
+//! `version`, `circuit`, `k`, `n`, `transcript` by name, `fingerprint` and
+//! `proof` as byte strings, and `instances` as in [`cbor::instances`].
+//! [`ProofEnvelope::parse`] reads either.
+//!
+//! Version 2 added `n`. The circuit name, `k` and `n` together are the
+//! statement the proof claims; a verifier rebuilds the circuit they name
+//! and rejects the envelope if its size or verifying key differ, so a
+//! proof about ten terms cannot be passed off as one about a hundred.
+//! Version 1 envelopes are still read: every circuit proves one fixed
+//! size, so their `n` is the one [`implied_n`] gives for the circuit they
+//! name. They are written back as version 2.
+//!
+//! [`CircuitKind::n`]: crate::registry::CircuitKind::n
+
+use std::{
+    error, fmt,
+    io::{self, Read},
+};
+
+use halo2_proofs::pasta::{group::ff::PrimeField, Fp};
+
+use crate::{
+    cbor::{self, CborError},
+    fingerprint::Fingerprint,
+    registry::CircuitKind,
+};
+
+pub const MAGIC: [u8; 4] = *b"FIBP";
+pub const ENVELOPE_VERSION: u8 = 2;
+/// The oldest version still read.
+pub const OLDEST_ENVELOPE_VERSION: u8 = 1;
+
+/// The Fiat-Shamir transcript the proof bytes were written with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TranscriptKind {
+    Blake2b,
+}
+
+impl TranscriptKind {
+    fn to_byte(self) -> u8 {
+        match self {
+            TranscriptKind::Blake2b => 0,
+        }
+    }
+
+    fn from_byte(byte: u8) -> Result<Self, EnvelopeError> {
+        match byte {
+            0 => Ok(TranscriptKind::Blake2b),
+            other => Err(EnvelopeError::UnknownTranscript(other)),
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "blake2b" => Some(TranscriptKind::Blake2b),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            TranscriptKind::Blake2b => "blake2b",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofEnvelope {
+    pub circuit: String,
+    pub k: u32,
+    pub n: u32,
+    pub fingerprint: Fingerprint,
+    pub transcript: TranscriptKind,
+    pub instances: Vec<Vec<Fp>>,
+    pub proof: Vec<u8>,
+}
+
+#[derive(Debug)]
+pub enum EnvelopeError {
+    Io(io::Error),
+    /// The input ended before the envelope did.
+    Truncated,
+    BadMagic([u8; 4]),
+    UnsupportedVersion(u8),
+    UnknownTranscript(u8),
+    InvalidCircuitName,
+    /// A version 1 envelope names no known circuit, so its `n` is unknown.
+    UnknownCircuit(String),
+    /// An instance value is not the canonical encoding of a field element.
+    NonCanonicalInstance {
+        column: usize,
+        row: usize,
+    },
+    /// Bytes follow the end of the envelope.
+    TrailingBytes(usize),
+    /// A CBOR envelope is malformed or lacks a field.
+    Cbor(CborError),
+}
+
+impl ProofEnvelope {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![];
+        self.write(&mut bytes)
+            .expect("writing to a Vec cannot fail");
+        bytes
+    }
+
+    pub fn write<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(&MAGIC)?;
+        writer.write_all(&[ENVELOPE_VERSION, self.transcript.to_byte()])?;
+        writer.write_all(&self.k.to_le_bytes())?;
+        writer.write_all(&self.n.to_le_bytes())?;
+        writer.write_all(&(self.circuit.len() as u16).to_le_bytes())?;
+        writer.write_all(self.circuit.as_bytes())?;
+        writer.write_all(&self.fingerprint)?;
+        writer.write_all(&(self.instances.len() as u32).to_le_bytes())?;
+        for column in &self.instances {
+            writer.write_all(&(column.len() as u32).to_le_bytes())?;
+            for value in column {
+                writer.write_all(&value.to_repr())?;
+            }
+        }
+        writer.write_all(&(self.proof.len() as u32).to_le_bytes())?;
+        writer.write_all(&self.proof)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, EnvelopeError> {
+        let mut reader = bytes;
+        let envelope = Self::read(&mut reader)?;
+        if reader.is_empty() {
+            Ok(envelope)
+        } else {
+            Err(EnvelopeError::TrailingBytes(reader.len()))
+        }
+    }
+
+    /// The envelope as a CBOR map; see the [module docs](self).
+    pub fn to_cbor(&self) -> Vec<u8> {
+        let fields = [
+            ("version", cbor::Value::Unsigned(ENVELOPE_VERSION.into())),
+            ("circuit", cbor::Value::text(&self.circuit)),
+            ("k", cbor::Value::Unsigned(self.k.into())),
+            ("n", cbor::Value::Unsigned(self.n.into())),
+            ("transcript", cbor::Value::text(self.transcript.name())),
+            ("fingerprint", cbor::Value::Bytes(self.fingerprint.to_vec())),
+            ("instances", cbor::instances(&self.instances)),
+            ("proof", cbor::Value::Bytes(self.proof.clone())),
+        ];
+        cbor::encode(&cbor::Value::Map(
+            fields
+                .into_iter()
+                .map(|(key, value)| (cbor::Value::text(key), value))
+                .collect(),
+        ))
+    }
+
+    pub fn from_cbor(bytes: &[u8]) -> Result<Self, EnvelopeError> {
+        let map = cbor::decode(bytes)?;
+        let field = |key| {
+            map.get(key)
+                .ok_or(CborError::Unexpected("envelope field missing"))
+        };
+        let wrong = |what| EnvelopeError::Cbor(CborError::Unexpected(what));
+
+        let version = field("version")?
+            .as_u64()
+            .ok_or(wrong("version is not an integer"))?;
+        if !(u64::from(OLDEST_ENVELOPE_VERSION)..=u64::from(ENVELOPE_VERSION)).contains(&version) {
+            return Err(EnvelopeError::UnsupportedVersion(
+                version.try_into().unwrap_or(u8::MAX),
+            ));
+        }
+        let transcript = field("transcript")?
+            .as_text()
+            .and_then(TranscriptKind::from_name)
+            .ok_or(wrong("transcript is not a known name"))?;
+        let circuit = field("circuit")?
+            .as_text()
+            .ok_or(EnvelopeError::InvalidCircuitName)?
+            .to_string();
+        let k = field("k")?
+            .as_u64()
+            .and_then(|k| k.try_into().ok())
+            .ok_or(wrong("k is not a u32"))?;
+        let n = match version {
+            1 => {
+                implied_n(&circuit).ok_or_else(|| EnvelopeError::UnknownCircuit(circuit.clone()))?
+            }
+            _ => field("n")?
+                .as_u64()
+                .and_then(|n| n.try_into().ok())
+                .ok_or(wrong("n is not a u32"))?,
+        };
+        let fingerprint = field("fingerprint")?
+            .as_bytes()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or(wrong("fingerprint is not 32 bytes"))?;
+        let instances = cbor::to_instances(field("instances")?)?;
+        let proof = field("proof")?
+            .as_bytes()
+            .ok_or(wrong("proof is not a byte string"))?
+            .to_vec();
+
+        Ok(ProofEnvelope {
+            circuit,
+            k,
+            n,
+            fingerprint,
+            transcript,
+            instances,
+            proof,
+        })
+    }
+
+    /// Reads an envelope in either layout, telling them apart by the
+    /// binary layout's magic.
+    pub fn parse(bytes: &[u8]) -> Result<Self, EnvelopeError> {
+        if bytes.starts_with(&MAGIC) {
+            Self::from_bytes(bytes)
+        } else {
+            Self::from_cbor(bytes)
+        }
+    }
+
+    pub fn read<R: io::Read>(reader: &mut R) -> Result<Self, EnvelopeError> {
+        let magic: [u8; 4] = read_array(reader)?;
+        if magic != MAGIC {
+            return Err(EnvelopeError::BadMagic(magic));
+        }
+        let [version, transcript] = read_array(reader)?;
+        if !(OLDEST_ENVELOPE_VERSION..=ENVELOPE_VERSION).contains(&version) {
+            return Err(EnvelopeError::UnsupportedVersion(version));
+        }
+        let transcript = TranscriptKind::from_byte(transcript)?;
+        let k = u32::from_le_bytes(read_array(reader)?);
+        let n = match version {
+            1 => None,
+            _ => Some(u32::from_le_bytes(read_array(reader)?)),
+        };
+
+        let name_len = u16::from_le_bytes(read_array(reader)?) as usize;
+        let circuit = String::from_utf8(read_vec(reader, name_len)?)
+            .map_err(|_| EnvelopeError::InvalidCircuitName)?;
+        let n = match n {
+            Some(n) => n,
+            None => {
+                implied_n(&circuit).ok_or_else(|| EnvelopeError::UnknownCircuit(circuit.clone()))?
+            }
+        };
+        let fingerprint = read_array(reader)?;
+
+        let columns = u32::from_le_bytes(read_array(reader)?) as usize;
+        let mut instances = vec![];
+        for column in 0..columns {
+            let len = u32::from_le_bytes(read_array(reader)?) as usize;
+            let mut values = vec![];
+            for row in 0..len {
+                let value = Option::from(Fp::from_repr(read_array(reader)?))
+                    .ok_or(EnvelopeError::NonCanonicalInstance { column, row })?;
+                values.push(value);
+            }
+            instances.push(values);
+        }
+
+        let proof_len = u32::from_le_bytes(read_array(reader)?) as usize;
+        let proof = read_vec(reader, proof_len)?;
+
+        Ok(ProofEnvelope {
+            circuit,
+            k,
+            n,
+            fingerprint,
+            transcript,
+            instances,
+            proof,
+        })
+    }
+}
+
+/// The `n` of `circuit`, for an envelope written before `n` was recorded.
+/// `None` if no circuit has that name.
+pub fn implied_n(circuit: &str) -> Option<u32> {
+    let kind: CircuitKind = circuit.parse().ok()?;
+    Some(kind.n() as u32)
+}
+
+fn read_array<R: io::Read, const N: usize>(reader: &mut R) -> Result<[u8; N], EnvelopeError> {
+    let mut bytes = [0; N];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// Reads `len` bytes without trusting `len` for the allocation size.
+fn read_vec<R: io::Read>(reader: &mut R, len: usize) -> Result<Vec<u8>, EnvelopeError> {
+    let mut bytes = vec![];
+    reader.by_ref().take(len as u64).read_to_end(&mut bytes)?;
+    if bytes.len() == len {
+        Ok(bytes)
+    } else {
+        Err(EnvelopeError::Truncated)
+    }
+}
+
+impl From<io::Error> for EnvelopeError {
+    fn from(e: io::Error) -> Self {
+        if e.kind() == io::ErrorKind::UnexpectedEof {
+            EnvelopeError::Truncated
+        } else {
+            EnvelopeError::Io(e)
+        }
+    }
+}
+
+impl From<CborError> for EnvelopeError {
+    fn from(e: CborError) -> Self {
+        EnvelopeError::Cbor(e)
+    }
+}
+
+impl fmt::Display for EnvelopeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EnvelopeError::Io(e) => write!(f, "I/O error: {}", e),
+            EnvelopeError::Truncated => f.write_str("envelope is truncated"),
+            EnvelopeError::BadMagic(magic) => write!(f, "not a proof envelope (magic {:?})", magic),
+            EnvelopeError::UnsupportedVersion(v) => {
+                write!(f, "unsupported envelope version {}", v)
+            }
+            EnvelopeError::UnknownTranscript(t) => write!(f, "unknown transcript type {}", t),
+            EnvelopeError::InvalidCircuitName => f.write_str("circuit name is not UTF-8"),
+            EnvelopeError::UnknownCircuit(name) => write!(
+                f,
+                "version 1 envelope of unknown circuit `{}` does not record n",
+                name
+            ),
+            EnvelopeError::NonCanonicalInstance { column, row } => write!(
+                f,
+                "instance column {} row {} is not a canonical field element",
+                column, row
+            ),
+            EnvelopeError::TrailingBytes(n) => write!(f, "{} bytes after the envelope", n),
+            EnvelopeError::Cbor(e) => write!(f, "CBOR envelope: {}", e),
+        }
+    }
+}
+
+impl error::Error for EnvelopeError {}
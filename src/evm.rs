@@ -0,0 +1,90 @@
+//! EVM calldata for proofs and public inputs.
+//!
+//! The Solidity verifiers generated by snark-verifier and
+//! halo2-solidity-verifier take their calldata as the public inputs, each a
+//! big-endian `uint256`, column after column, followed by the raw proof
+//! bytes. There is no length prefix: the verifier contract hard-codes the
+//! instance count.
+//!
+//! Only the layout lives here. halo2_proofs 0.2 proves with IPA over the
+//! pasta curves, which the EVM has no precompiles for, so the proofs this
+//! crate produces today cannot be checked on chain; the encoding is what a
+//! KZG/BN254 backend would hand to the contract.
+
+use std::{error, fmt};
+
+use halo2_proofs::pasta::{group::ff::PrimeField, Fp};
+
+/// Size of one `uint256` calldata word.
+pub const WORD_BYTES: usize = 32;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CalldataError {
+    /// The calldata is shorter than the public inputs it should contain.
+    Truncated { expected: usize, actual: usize },
+    /// A public input word is not below the field modulus.
+    NonCanonicalInstance { column: usize, row: usize },
+}
+
+/// Encodes public inputs and proof bytes the way the verifier contract
+/// reads them.
+pub fn encode_calldata(instances: &[Vec<Fp>], proof: &[u8]) -> Vec<u8> {
+    let words: usize = instances.iter().map(Vec::len).sum();
+    let mut calldata = Vec::with_capacity(words * WORD_BYTES + proof.len());
+    for value in instances.iter().flatten() {
+        calldata.extend(value.to_repr().iter().rev());
+    }
+    calldata.extend_from_slice(proof);
+    calldata
+}
+
+/// Splits calldata back into public inputs and proof bytes. `column_lens`
+/// gives the number of public inputs in each instance column, since the
+/// calldata itself does not record it.
+pub fn decode_calldata(
+    calldata: &[u8],
+    column_lens: &[usize],
+) -> Result<(Vec<Vec<Fp>>, Vec<u8>), CalldataError> {
+    let expected = column_lens.iter().sum::<usize>() * WORD_BYTES;
+    if calldata.len() < expected {
+        return Err(CalldataError::Truncated {
+            expected,
+            actual: calldata.len(),
+        });
+    }
+
+    let mut words = calldata.chunks_exact(WORD_BYTES);
+    let mut instances = vec![];
+    for (column, &len) in column_lens.iter().enumerate() {
+        let mut values = vec![];
+        for row in 0..len {
+            let mut repr = [0u8; WORD_BYTES];
+            repr.copy_from_slice(words.next().unwrap());
+            repr.reverse();
+            let value = Option::from(Fp::from_repr(repr))
+                .ok_or(CalldataError::NonCanonicalInstance { column, row })?;
+            values.push(value);
+        }
+        instances.push(values);
+    }
+    Ok((instances, calldata[expected..].to_vec()))
+}
+
+impl fmt::Display for CalldataError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CalldataError::Truncated { expected, actual } => write!(
+                f,
+                "calldata is {} bytes, the public inputs alone need {}",
+                actual, expected
+            ),
+            CalldataError::NonCanonicalInstance { column, row } => write!(
+                f,
+                "public input column {} row {} is not a canonical field element",
+                column, row
+            ),
+        }
+    }
+}
+
+impl error::Error for CalldataError {}
@@ -0,0 +1,188 @@
+//! Command-line flags of the `fib_three_column` and `fib_single_column`
+//! examples, so the seeds, the length of the sequence and the circuit size
+//! can be tried without editing the source.
+//!
+//! [`ExampleArgs::check`] runs the `MockProver` on the circuit the flags
+//! describe and turns the usual ways to get them wrong into an
+//! [`ExampleError`] that says what to change.
+
+use std::{error, fmt};
+
+use halo2_proofs::{
+    circuit::Value,
+    dev::{MockProver, VerifyFailure},
+    pasta::{group::ff::PrimeField, Fp},
+    plonk::{Circuit, Error},
+};
+use num_bigint::BigUint;
+
+use crate::{fibonacci::NUM_TERMS, field, reference::fib_field};
+
+/// The `MockProver` holds every cell in memory, so the examples stop here.
+pub const MAX_EXAMPLE_K: u32 = 20;
+
+#[derive(Debug, Clone, clap::Parser)]
+pub struct ExampleArgs {
+    /// The first seed, in decimal or `0x` hex.
+    #[arg(long, default_value = "1", value_parser = field::parse::<Fp>)]
+    pub a: Fp,
+    /// The second seed, in decimal or `0x` hex.
+    #[arg(long, default_value = "1", value_parser = field::parse::<Fp>)]
+    pub b: Fp,
+    /// Terms to lay out, seeds included.
+    #[arg(long, default_value_t = NUM_TERMS)]
+    pub n: usize,
+    /// The circuit has 2^k rows.
+    #[arg(long, default_value_t = 4)]
+    pub k: u32,
+    /// The public output to check, in decimal or `0x` hex; the `n`th term
+    /// by default.
+    #[arg(long, value_parser = field::parse::<Fp>)]
+    pub public: Option<Fp>,
+}
+
+#[derive(Debug)]
+pub enum ExampleError {
+    TooFewTerms(usize),
+    InvalidK(u32),
+    /// `n` terms do not fit in `2^k` rows; `needed` is the smallest `k`
+    /// they fit in, if any up to [`MAX_EXAMPLE_K`] does.
+    TooSmall {
+        n: usize,
+        k: u32,
+        needed: Option<u32>,
+    },
+    /// `--public` is not the `n`th term.
+    WrongPublic {
+        n: usize,
+        public: Fp,
+        expected: Fp,
+    },
+    Synthesis(Error),
+    Unsatisfied(Vec<VerifyFailure>),
+}
+
+impl ExampleArgs {
+    pub fn seeds(&self) -> (Value<Fp>, Value<Fp>) {
+        (Value::known(self.a), Value::known(self.b))
+    }
+
+    /// The `n`th term of the sequence seeded with `a, b`.
+    pub fn expected(&self) -> Fp {
+        fib_field(self.a, self.b, self.n)
+    }
+
+    /// The run in a line, for the examples to print once it checks.
+    pub fn summary(&self) -> String {
+        format!(
+            "{} terms from {}, {} at k = {}: term {} is {}",
+            self.n,
+            decimal(&self.a),
+            decimal(&self.b),
+            self.k,
+            self.n,
+            decimal(&self.expected())
+        )
+    }
+
+    /// `--public`, or the `n`th term without it.
+    pub fn public_input(&self) -> Fp {
+        self.public.unwrap_or_else(|| self.expected())
+    }
+
+    /// The flags on their own, before there is a circuit.
+    pub fn validate(&self) -> Result<(), ExampleError> {
+        if self.n < 3 {
+            return Err(ExampleError::TooFewTerms(self.n));
+        }
+        if self.k > MAX_EXAMPLE_K {
+            return Err(ExampleError::InvalidK(self.k));
+        }
+        Ok(())
+    }
+
+    /// Validates the flags and runs the `MockProver` on `circuit`, laid
+    /// out from them, against [`ExampleArgs::public_input`].
+    pub fn check<C: Circuit<Fp>>(&self, circuit: &C) -> Result<(), ExampleError> {
+        self.validate()?;
+
+        let instances = vec![vec![self.public_input()]];
+        let prover = match MockProver::run(self.k, circuit, instances.clone()) {
+            Err(Error::NotEnoughRowsAvailable { .. }) => {
+                let needed = (self.k + 1..=MAX_EXAMPLE_K).find(|&k| {
+                    !matches!(
+                        MockProver::run(k, circuit, instances.clone()),
+                        Err(Error::NotEnoughRowsAvailable { .. })
+                    )
+                });
+                return Err(ExampleError::TooSmall {
+                    n: self.n,
+                    k: self.k,
+                    needed,
+                });
+            }
+            result => result.map_err(ExampleError::Synthesis)?,
+        };
+
+        if let Some(public) = self.public {
+            if public != self.expected() {
+                return Err(ExampleError::WrongPublic {
+                    n: self.n,
+                    public,
+                    expected: self.expected(),
+                });
+            }
+        }
+        prover.verify().map_err(ExampleError::Unsatisfied)
+    }
+}
+
+fn decimal(x: &Fp) -> BigUint {
+    BigUint::from_bytes_le(x.to_repr().as_ref())
+}
+
+impl fmt::Display for ExampleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExampleError::TooFewTerms(n) => {
+                write!(f, "--n {} is too short: the gate relates 3 terms", n)
+            }
+            ExampleError::InvalidK(k) => {
+                write!(f, "--k {} is larger than {}", k, MAX_EXAMPLE_K)
+            }
+            ExampleError::TooSmall {
+                n,
+                k,
+                needed: Some(needed),
+            } => write!(
+                f,
+                "{} terms do not fit in 2^{} rows; try --k {}",
+                n, k, needed
+            ),
+            ExampleError::TooSmall {
+                n, needed: None, ..
+            } => write!(f, "{} terms do not fit in 2^{} rows", n, MAX_EXAMPLE_K),
+            ExampleError::WrongPublic {
+                n,
+                public,
+                expected,
+            } => write!(
+                f,
+                "--public {} is not term {} of the sequence, which is {}",
+                decimal(public),
+                n,
+                decimal(expected)
+            ),
+            ExampleError::Synthesis(e) => write!(f, "synthesis failed: {}", e),
+            ExampleError::Unsatisfied(failures) => {
+                write!(f, "the circuit is not satisfied:")?;
+                for failure in failures {
+                    write!(f, "\n  {}", failure)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl error::Error for ExampleError {}
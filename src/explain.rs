@@ -0,0 +1,128 @@
+//! Gate constraints with the witness substituted, row by row.
+//!
+//! For each row in a range, every active constraint is printed three
+//! times over: as written, with each selector and cell replaced by its
+//! value, and evaluated,
+//!
+//! ```text
+//! row 2  fibonacci: S0 * (A0@0 + A1@0 - A2@0) = 1 * (2 + 3 - 5) = 0
+//! ```
+//!
+//! A constraint is active on a row when one of its selectors is enabled
+//! there, or always if it has none. Unassigned cells print as `?` and
+//! count as zero, so a nonzero result marks a constraint the witness
+//! fails. Lookups and copy constraints are not shown.
+
+use std::{fmt, ops::Range};
+
+use halo2_proofs::pasta::Fp;
+use serde::Serialize;
+
+use crate::introspect::{format_field, witness::Witness, Expr};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Explanation {
+    pub circuit: String,
+    pub k: u32,
+    pub constraints: Vec<ExplainedConstraint>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ExplainedConstraint {
+    pub row: usize,
+    pub gate: String,
+    /// Empty when the gate did not name the constraint.
+    pub name: String,
+    pub expression: String,
+    pub substituted: String,
+    pub value: String,
+    pub satisfied: bool,
+}
+
+impl Explanation {
+    /// Explains the constraints active on `rows` of `witness`, keeping only
+    /// gates whose name contains `filter` when one is given.
+    pub fn of(name: &str, witness: &Witness, rows: Range<usize>, filter: Option<&str>) -> Self {
+        let mut constraints = vec![];
+        for row in rows.start..rows.end.min(witness.rows()) {
+            for gate in &witness.cs.gates {
+                if filter.is_some_and(|filter| !gate.name.contains(filter)) {
+                    continue;
+                }
+                for constraint in &gate.constraints {
+                    let poly = &constraint.poly;
+                    let selectors = poly.selectors();
+                    if !selectors.is_empty()
+                        && !selectors.iter().any(|s| witness.selectors[*s][row])
+                    {
+                        continue;
+                    }
+                    let value = witness.evaluate(poly, row);
+                    constraints.push(ExplainedConstraint {
+                        row,
+                        gate: gate.name.clone(),
+                        name: constraint.name.clone(),
+                        expression: poly.to_string(),
+                        substituted: substitute(witness, poly, row),
+                        value: format_field(value),
+                        satisfied: value == Fp::zero(),
+                    });
+                }
+            }
+        }
+
+        Explanation {
+            circuit: name.to_string(),
+            k: witness.k,
+            constraints,
+        }
+    }
+
+    pub fn satisfied(&self) -> bool {
+        self.constraints
+            .iter()
+            .all(|constraint| constraint.satisfied)
+    }
+}
+
+/// `poly` with its selectors and cells at `row` replaced by their values.
+fn substitute(witness: &Witness, poly: &Expr, row: usize) -> String {
+    poly.render_with(&|leaf| match leaf {
+        Expr::Selector(s) => Some((witness.selectors[*s][row] as u8).to_string()),
+        Expr::Query(query) => Some(
+            witness
+                .value(query.column, witness.rotate(row, query.rotation))
+                .map_or("?".to_string(), format_field),
+        ),
+        _ => None,
+    })
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "circuit: {} (k = {})", self.circuit, self.k)?;
+        for c in &self.constraints {
+            let label = match c.name.is_empty() {
+                true => c.gate.clone(),
+                false => format!("{} / {}", c.gate, c.name),
+            };
+            writeln!(
+                f,
+                "row {:<3} {}: {} = {} = {}{}",
+                c.row,
+                label,
+                c.expression,
+                c.substituted,
+                c.value,
+                if c.satisfied { "" } else { "  FAILS" }
+            )?;
+        }
+        let failing = self.constraints.iter().filter(|c| !c.satisfied).count();
+        write!(
+            f,
+            "{} constraints, {} failing",
+            self.constraints.len(),
+            failing
+        )
+    }
+}
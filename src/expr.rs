@@ -0,0 +1,89 @@
+//! Helpers for writing gate expressions.
+//!
+//! `Expression` already overloads `+`, `-`, `*` and `-x` between
+//! expressions, and `*` by a field element, so the gates use those
+//! directly. What they kept redoing by hand is here: constants from
+//! integers, queries of many columns at once, sums and products that
+//! start from the first term rather than a `0` or `1` constant, limbs
+//! composed in a base, and the boolean constraint.
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    plonk::{Advice, Column, Expression, Fixed, Instance, VirtualCells},
+    poly::Rotation,
+};
+
+/// `column` at `rotation` rows from the current one.
+pub fn advice<F: FieldExt>(
+    meta: &mut VirtualCells<'_, F>,
+    column: Column<Advice>,
+    rotation: i32,
+) -> Expression<F> {
+    meta.query_advice(column, Rotation(rotation))
+}
+
+pub fn fixed<F: FieldExt>(
+    meta: &mut VirtualCells<'_, F>,
+    column: Column<Fixed>,
+    rotation: i32,
+) -> Expression<F> {
+    meta.query_fixed(column, Rotation(rotation))
+}
+
+pub fn instance<F: FieldExt>(
+    meta: &mut VirtualCells<'_, F>,
+    column: Column<Instance>,
+    rotation: i32,
+) -> Expression<F> {
+    meta.query_instance(column, Rotation(rotation))
+}
+
+/// Each of `columns` at the current row, in order.
+pub fn advice_row<F: FieldExt, const N: usize>(
+    meta: &mut VirtualCells<'_, F>,
+    columns: [Column<Advice>; N],
+) -> [Expression<F>; N] {
+    columns.map(|column| meta.query_advice(column, Rotation::cur()))
+}
+
+pub fn constant<F: FieldExt>(value: u64) -> Expression<F> {
+    Expression::Constant(F::from(value))
+}
+
+pub fn constant_u128<F: FieldExt>(value: u128) -> Expression<F> {
+    Expression::Constant(F::from_u128(value))
+}
+
+/// The sum of `terms`, or `0` if there are none.
+pub fn sum<F: FieldExt>(terms: impl IntoIterator<Item = Expression<F>>) -> Expression<F> {
+    terms
+        .into_iter()
+        .reduce(|acc, term| acc + term)
+        .unwrap_or_else(|| constant(0))
+}
+
+/// The product of `factors`, or `1` if there are none.
+pub fn product<F: FieldExt>(factors: impl IntoIterator<Item = Expression<F>>) -> Expression<F> {
+    factors
+        .into_iter()
+        .reduce(|acc, factor| acc * factor)
+        .unwrap_or_else(|| constant(1))
+}
+
+/// `Σ limbs[i] · base^i`, least significant limb first.
+pub fn compose<F: FieldExt>(
+    limbs: impl IntoIterator<Item = Expression<F>>,
+    base: F,
+) -> Expression<F> {
+    let mut power = F::one();
+    sum(limbs.into_iter().enumerate().map(|(i, limb)| {
+        let term = if i == 0 { limb } else { limb * power };
+        power *= base;
+        term
+    }))
+}
+
+/// `x · (1 - x)`, zero exactly when `x` is `0` or `1`.
+pub fn bool_check<F: FieldExt>(x: Expression<F>) -> Expression<F> {
+    x.clone() * (constant(1) - x)
+}
@@ -0,0 +1,98 @@
+//! `M` independent sequences side by side in one circuit.
+//!
+//! Each sequence gets its own group of three advice columns and its own
+//! [`three_column::FiboChip`], so the rows of all groups are laid out in
+//! parallel and the proof costs `M` times the columns but no extra rows.
+//! The outputs share one instance column: sequence `i` is exposed at row
+//! `i`.
+
+use std::fmt;
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    plonk::{Circuit, ConstraintSystem, Error},
+};
+
+use super::{three_column, NUM_TERMS};
+
+#[derive(Debug, Clone)]
+pub struct FiboBatchConfig<const M: usize> {
+    groups: [three_column::FiboConfig; M],
+}
+
+/// One line per group, e.g. `group 0: advice A0 A1 A2, ...`.
+impl<const M: usize> fmt::Display for FiboBatchConfig<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, group) in self.groups.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "group {}: {}", i, group)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct FiboBatchCircuit<F: FieldExt, const M: usize> {
+    /// Seeds `(a, b)` of each sequence.
+    pub seeds: [(Value<F>, Value<F>); M],
+}
+
+impl<F: FieldExt, const M: usize> Default for FiboBatchCircuit<F, M> {
+    fn default() -> Self {
+        Self {
+            seeds: [(Value::unknown(), Value::unknown()); M],
+        }
+    }
+}
+
+impl<F: FieldExt, const M: usize> Circuit<F> for FiboBatchCircuit<F, M> {
+    type Config = FiboBatchConfig<M>;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let instance = meta.instance_column();
+        let groups = [(); M].map(|_| {
+            let advice = [(); 3].map(|_| meta.advice_column());
+            three_column::FiboChip::configure(meta, advice, instance)
+        });
+
+        FiboBatchConfig { groups }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        for (i, (group, &(a, b))) in config.groups.into_iter().zip(&self.seeds).enumerate() {
+            let cs = three_column::FiboChip::construct(group);
+
+            let (_, mut prev_b, mut prev_c) =
+                cs.assign_first_row(layouter.namespace(|| format!("first row {}", i)), a, b)?;
+
+            for _ in 3..NUM_TERMS {
+                (prev_b, prev_c) = cs.assign_row(
+                    layouter.namespace(|| format!("next row {}", i)),
+                    &prev_b,
+                    &prev_c,
+                )?;
+            }
+
+            cs.expose_public(
+                layouter.namespace(|| format!("expose public {}", i)),
+                &prev_c,
+                i,
+            )?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,115 @@
+//! The recurrence over the integers rather than the field.
+//!
+//! Terms are [`AssignedBigInt`]s of `L` limbs, so the circuit proves the
+//! actual `n`th Fibonacci number for as long as it fits in `64·L` bits,
+//! well past the point where field-valued terms wrap around the modulus.
+//! Each step is one [`BigIntChip::add`], whose range checks make it the
+//! costliest layout per step.
+//!
+//! Instance rows: `0..L` the limbs of the `N`th term, least significant
+//! first.
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    plonk::{Circuit, Column, ConstraintSystem, Error, Instance},
+};
+use num_bigint::BigUint;
+
+use crate::gadgets::{
+    bigint::{self, AssignedBigInt, BigIntChip, BigIntConfig},
+    bytes::{ByteTable, BytesChip},
+};
+
+#[derive(Debug, Clone)]
+pub struct FiboBigIntConfig {
+    pub bigint: BigIntConfig,
+    pub instance: Column<Instance>,
+}
+
+/// Proves the `N`th term (counting from 1) of the sequence seeded with
+/// `a, b`, with terms of `L` limbs.
+#[derive(Debug, Clone, Default)]
+pub struct FiboBigIntCircuit<const L: usize, const N: usize> {
+    pub a: Value<BigUint>,
+    pub b: Value<BigUint>,
+}
+
+impl<F: FieldExt, const L: usize, const N: usize> Circuit<F> for FiboBigIntCircuit<L, N> {
+    type Config = FiboBigIntConfig;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let table = ByteTable::configure(meta);
+        let (z, byte) = (meta.advice_column(), meta.advice_column());
+        let bytes = BytesChip::configure(meta, z, byte, table);
+        let instance = meta.instance_column();
+        meta.enable_equality(instance);
+
+        FiboBigIntConfig {
+            bigint: BigIntChip::configure(meta, L, bytes),
+            instance,
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        assert!(N > 0, "terms are counted from 1");
+        config
+            .bigint
+            .bytes
+            .table
+            .load(layouter.namespace(|| "byte table"))?;
+        let chip = BigIntChip::construct(config.bigint);
+
+        let mut a = chip.load(layouter.namespace(|| "a"), self.a.clone())?;
+        let mut b = chip.load(layouter.namespace(|| "b"), self.b.clone())?;
+        if N == 1 {
+            return expose(&mut layouter, &a, config.instance);
+        }
+        for _ in 2..N {
+            let c = chip.add(layouter.namespace(|| "next term"), &a, &b)?;
+            (a, b) = (b, c);
+        }
+
+        expose(&mut layouter, &b, config.instance)
+    }
+}
+
+fn expose<F: FieldExt>(
+    layouter: &mut impl Layouter<F>,
+    term: &AssignedBigInt<F>,
+    instance: Column<Instance>,
+) -> Result<(), Error> {
+    for (row, limb) in term.limbs.iter().enumerate() {
+        layouter.constrain_instance(limb.cell(), instance, row)?;
+    }
+    Ok(())
+}
+
+/// The `n`th term (counting from 1) of the sequence seeded with `a, b`,
+/// over the integers.
+pub fn nth_term(a: &BigUint, b: &BigUint, n: usize) -> BigUint {
+    let (mut a, mut b) = (a.clone(), b.clone());
+    for _ in 1..n {
+        let c = &a + &b;
+        (a, b) = (b, c);
+    }
+    a
+}
+
+/// The instance column for a circuit with `L = limbs` exposing `term`.
+pub fn public_inputs<F: FieldExt>(term: &BigUint, limbs: usize) -> Vec<F> {
+    bigint::to_limbs(term, limbs)
+        .into_iter()
+        .map(F::from)
+        .collect()
+}
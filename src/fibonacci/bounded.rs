@@ -0,0 +1,66 @@
+//! The three-column table with its output proven below `2^B`.
+//!
+//! A verifier of [`three_column::FiboCircuit`](super::three_column) learns
+//! a field element, which for large enough seeds or long enough sequences
+//! is the term reduced modulo `p`. Here the last term is also run through
+//! [`U64Chip::range_check_bits`] before it is exposed, so the public value
+//! is an integer below `2^B` and a consumer can use it as one. `B` is at
+//! most 64 and costs `B + 1` rows. Instance rows: `0` the last term.
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    plonk::{Circuit, ConstraintSystem, Error},
+};
+
+use super::three_column::{FiboChip, FiboConfig, FiboWitness};
+use crate::gadgets::u64::{U64Chip, U64Config, BITS};
+
+#[derive(Debug, Clone)]
+pub struct FiboBoundedConfig {
+    fibo: FiboConfig,
+    range: U64Config,
+}
+
+#[derive(Debug, Default)]
+pub struct FiboBoundedCircuit<F: FieldExt, const B: usize> {
+    pub a: Value<F>,
+    pub b: Value<F>,
+}
+
+impl<F: FieldExt, const B: usize> Circuit<F> for FiboBoundedCircuit<F, B> {
+    type Config = FiboBoundedConfig;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        assert!(B <= BITS, "at most {} bits", BITS);
+        let advice = [(); 3].map(|_| meta.advice_column());
+        let z = meta.advice_column();
+        let instance = meta.instance_column();
+
+        FiboBoundedConfig {
+            fibo: FiboChip::configure(meta, advice, instance),
+            range: U64Chip::configure(meta, z),
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let fibo = FiboChip::construct(config.fibo);
+        let range = U64Chip::construct(config.range);
+
+        let witness = FiboWitness::generate(self.a, self.b);
+        let last = fibo.assign_witness(layouter.namespace(|| "table"), &witness)?;
+        let bounded = range.range_check_bits(layouter.namespace(|| "bound"), &last, B)?;
+
+        fibo.expose_public(layouter.namespace(|| "expose public"), &bounded, 0)
+    }
+}
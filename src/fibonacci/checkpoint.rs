@@ -0,0 +1,93 @@
+//! The three-column table with chosen terms public, not just the last.
+//!
+//! A verifier who only sees the last term learns nothing about the ones
+//! before it. Marking terms as checkpoints constrains each to its own
+//! instance row, in the order given, so the interior of the sequence can
+//! be spot-checked. [`instances`] builds the matching instance column.
+//!
+//! [`FiboCheckpointCircuit::final_pair`] exposes the last two terms at rows
+//! 0 and 1, the pair a following proof would continue from.
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    plonk::{Circuit, ConstraintSystem, Error},
+};
+
+use super::{
+    nth_term,
+    three_column::{FiboChip, FiboConfig, FiboWitness},
+    NUM_TERMS,
+};
+
+#[derive(Debug, Clone, Default)]
+pub struct FiboCheckpointCircuit<F: FieldExt> {
+    pub a: Value<F>,
+    pub b: Value<F>,
+    /// Terms to expose, counting from 1 like [`nth_term`]; checkpoint `i`
+    /// goes to instance row `i`.
+    pub checkpoints: Vec<usize>,
+}
+
+/// The last two terms, the pair the sequence continues from.
+pub const FINAL_PAIR: [usize; 2] = [NUM_TERMS - 1, NUM_TERMS];
+
+impl<F: FieldExt> FiboCheckpointCircuit<F> {
+    /// Exposes [`FINAL_PAIR`] at instance rows 0 and 1.
+    pub fn final_pair(a: Value<F>, b: Value<F>) -> Self {
+        FiboCheckpointCircuit {
+            a,
+            b,
+            checkpoints: FINAL_PAIR.to_vec(),
+        }
+    }
+}
+
+/// The instance column of a [`FiboCheckpointCircuit`] with seeds `a, b`.
+pub fn instances<F: FieldExt>(a: F, b: F, checkpoints: &[usize]) -> Vec<Vec<F>> {
+    vec![checkpoints.iter().map(|&n| nth_term(a, b, n)).collect()]
+}
+
+impl<F: FieldExt> Circuit<F> for FiboCheckpointCircuit<F> {
+    type Config = FiboConfig;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            checkpoints: self.checkpoints.clone(),
+            ..Self::default()
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let advice = [(); 3].map(|_| meta.advice_column());
+        let instance = meta.instance_column();
+
+        FiboChip::configure(meta, advice, instance)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        if self.checkpoints.iter().any(|&n| n == 0 || n > NUM_TERMS) {
+            return Err(Error::Synthesis);
+        }
+        let cs = FiboChip::construct(config);
+
+        let witness = FiboWitness::generate(self.a, self.b);
+        let terms = cs.assign_terms(layouter.namespace(|| "table"), &witness)?;
+
+        for (row, &n) in self.checkpoints.iter().enumerate() {
+            cs.expose_public(
+                layouter.namespace(|| format!("checkpoint {}", n)),
+                &terms[n - 1],
+                row,
+            )?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,63 @@
+//! One chunk of a longer sequence, with both ends public.
+//!
+//! The circuit takes `(x0, x1)` as public inputs and exposes where `C`
+//! steps of the recurrence lead, `(x_C, x_{C+1})`. Proofs of consecutive
+//! chunks chain when one chunk's outputs are the next chunk's inputs, which
+//! [`crate::chunked`] checks; the sequence can grow without growing `k`.
+//!
+//! Instance rows: `0, 1` the inputs, `2, 3` the outputs.
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    plonk::{Circuit, ConstraintSystem, Error},
+};
+
+use super::three_column::{FiboChip, FiboConfig};
+
+#[derive(Debug, Default)]
+pub struct FiboChunkCircuit<F: FieldExt, const C: usize> {
+    pub x0: Value<F>,
+    pub x1: Value<F>,
+}
+
+impl<F: FieldExt, const C: usize> Circuit<F> for FiboChunkCircuit<F, C> {
+    type Config = FiboConfig;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let advice = [(); 3].map(|_| meta.advice_column());
+        let instance = meta.instance_column();
+
+        FiboChip::configure(meta, advice, instance)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        assert!(C > 0, "a chunk takes at least one step");
+        let cs = FiboChip::construct(config);
+
+        let (x0, mut prev_b, mut prev_c) =
+            cs.assign_first_row(layouter.namespace(|| "first row"), self.x0, self.x1)?;
+        cs.expose_public(layouter.namespace(|| "expose x0"), &x0, 0)?;
+        cs.expose_public(layouter.namespace(|| "expose x1"), &prev_b, 1)?;
+
+        for _ in 1..C {
+            (prev_b, prev_c) =
+                cs.assign_row(layouter.namespace(|| "next row"), &prev_b, &prev_c)?;
+        }
+
+        cs.expose_public(layouter.namespace(|| "expose x_C"), &prev_b, 2)?;
+        cs.expose_public(layouter.namespace(|| "expose x_C+1"), &prev_c, 3)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,253 @@
+//! Checking that a public pair `(x, y)` is two consecutive terms by
+//! running the sequence backwards.
+//!
+//! Each row undoes one step, `(a, b) -> (b - a, a)`, and the last row must
+//! be `(1, 1)`. The step is a bijection, so only the pairs `(F(n), F(n+1))`
+//! get there. In the field, though, `b - a` happily goes negative, and a
+//! long enough descent through wrapped values reaches `(1, 1)` from pairs
+//! that are only Fibonacci terms modulo `p`. Every `a` is therefore
+//! decomposed into 8 bytes with [`BytesChip`], which pins the descent to
+//! the integers below `2^64`.
+//!
+//! The circuit has room for `N` steps. A boolean `step` per row chooses
+//! between undoing a step and copying the pair down, so pairs needing
+//! fewer than `N` steps idle on `(1, 1)`. Instance rows: `0` is `x`, `1`
+//! is `y`.
+
+use std::marker::PhantomData;
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    plonk::{Advice, Circuit, Column, ConstraintSystem, Error, Instance, Selector},
+};
+
+use super::ACell;
+use crate::{
+    expr,
+    gadgets::bytes::{ByteTable, BytesChip, BytesConfig},
+};
+
+/// Bytes each `a` of the descent is decomposed into.
+pub const DESCENT_BYTES: usize = 8;
+
+/// The `(a, b)` cells of one row.
+pub type Pair<F> = (ACell<F>, ACell<F>);
+
+#[derive(Debug, Clone)]
+pub struct DescentConfig {
+    /// `a, b, step`.
+    advice: [Column<Advice>; 3],
+    selector: Selector,
+    bytes: BytesConfig,
+    instance: Column<Instance>,
+}
+
+#[derive(Debug)]
+pub struct DescentChip<F: FieldExt> {
+    config: DescentConfig,
+    marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> DescentChip<F> {
+    pub fn construct(config: DescentConfig) -> Self {
+        Self {
+            config,
+            marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        bytes: BytesConfig,
+        instance: Column<Instance>,
+    ) -> DescentConfig {
+        meta.enable_equality(advice[0]);
+        meta.enable_equality(advice[1]);
+        meta.enable_equality(instance);
+
+        let selector = meta.selector();
+
+        meta.create_gate("descent", |meta| {
+            //
+            //  a  | b  | step | selector
+            //  a  | b  | t    |    s
+            //  a' | b' |      |
+            //
+            // t = 1: (a', b') = (b - a, a)
+            // t = 0: (a', b') = (a, b)
+            //
+            let [a, b, t] = expr::advice_row(meta, advice);
+            let a_next = expr::advice(meta, advice[0], 1);
+            let b_next = expr::advice(meta, advice[1], 1);
+            let s = meta.query_selector(selector);
+
+            vec![
+                s.clone() * expr::bool_check(t.clone()),
+                s.clone() * (a_next - a.clone() - t.clone() * (b.clone() - a.clone() - a.clone())),
+                s * (b_next - b.clone() - t * (a - b)),
+            ]
+        });
+
+        DescentConfig {
+            advice,
+            selector,
+            bytes,
+            instance,
+        }
+    }
+
+    /// Lays out `n` rows of descent from `(x, y)`, stepping while the pair
+    /// is not `(1, 1)`, and returns the pair of every row, the final row
+    /// included. The final row is constrained to `(1, 1)`.
+    pub fn descend(
+        &self,
+        mut layouter: impl Layouter<F>,
+        x: Value<F>,
+        y: Value<F>,
+        n: usize,
+    ) -> Result<Vec<Pair<F>>, Error> {
+        let [a_column, b_column, step_column] = self.config.advice;
+        let pairs = descent(x, y, n);
+
+        layouter.assign_region(
+            || "descent",
+            |mut region| {
+                let mut cells = vec![];
+                for (row, &(a, b)) in pairs.iter().enumerate() {
+                    let a = region.assign_advice(|| "a", a_column, row, || a)?;
+                    let b = region.assign_advice(|| "b", b_column, row, || b)?;
+                    if row < n {
+                        self.config.selector.enable(&mut region, row)?;
+                        let step = a
+                            .value()
+                            .zip(b.value())
+                            .map(|(a, b)| F::from(((*a, *b) != (F::one(), F::one())) as u64));
+                        region.assign_advice(|| "step", step_column, row, || step)?;
+                    } else {
+                        region.constrain_constant(a.cell(), F::one())?;
+                        region.constrain_constant(b.cell(), F::one())?;
+                    }
+                    cells.push((a, b));
+                }
+                Ok(cells)
+            },
+        )
+    }
+
+    /// Checks `a` fits in [`DESCENT_BYTES`] bytes.
+    pub fn range_check(&self, mut layouter: impl Layouter<F>, a: &ACell<F>) -> Result<(), Error> {
+        let bytes = BytesChip::construct(self.config.bytes.clone());
+        bytes.decompose(layouter.namespace(|| "range check"), a, DESCENT_BYTES)?;
+        Ok(())
+    }
+
+    /// Constrains the starting pair to instance rows 0 and 1.
+    pub fn expose_public(
+        &self,
+        mut layouter: impl Layouter<F>,
+        (x, y): &Pair<F>,
+    ) -> Result<(), Error> {
+        layouter.constrain_instance(x.cell(), self.config.instance, 0)?;
+        layouter.constrain_instance(y.cell(), self.config.instance, 1)
+    }
+}
+
+/// The pairs of an `n`-step descent from `(x, y)`, `n + 1` of them,
+/// stepping until the pair is `(1, 1)` and staying there.
+fn descent<F: FieldExt>(x: Value<F>, y: Value<F>, n: usize) -> Vec<(Value<F>, Value<F>)> {
+    let pairs = x.zip(y).map(|(x, y)| {
+        let mut pairs = vec![(x, y)];
+        for _ in 0..n {
+            let (a, b) = *pairs.last().unwrap();
+            pairs.push(if (a, b) == (F::one(), F::one()) {
+                (a, b)
+            } else {
+                (b - a, a)
+            });
+        }
+        pairs
+    });
+    pairs
+        .transpose_vec(n + 1)
+        .into_iter()
+        .map(|pair| pair.unzip())
+        .collect()
+}
+
+/// Steps from `(x, y)` down to `(1, 1)` over the integers, or `None` if
+/// the descent goes negative first, i.e. `(x, y)` is not `(F(n), F(n+1))`
+/// for any `n`.
+pub fn steps(x: u64, y: u64) -> Option<usize> {
+    let (mut a, mut b) = (x, y);
+    let mut steps = 0;
+    while (a, b) != (1, 1) {
+        (a, b) = (b.checked_sub(a)?, a);
+        steps += 1;
+    }
+    Some(steps)
+}
+
+#[derive(Debug, Clone)]
+pub struct DescentCircuitConfig {
+    descent: DescentConfig,
+    table: ByteTable,
+}
+
+/// Proves the public `(x, y)` descends to `(1, 1)` in at most `N` steps.
+#[derive(Debug, Clone)]
+pub struct DescentCircuit<F: FieldExt, const N: usize> {
+    pub x: Value<F>,
+    pub y: Value<F>,
+}
+
+impl<F: FieldExt, const N: usize> Default for DescentCircuit<F, N> {
+    fn default() -> Self {
+        Self {
+            x: Value::unknown(),
+            y: Value::unknown(),
+        }
+    }
+}
+
+impl<F: FieldExt, const N: usize> Circuit<F> for DescentCircuit<F, N> {
+    type Config = DescentCircuitConfig;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let advice = [(); 3].map(|_| meta.advice_column());
+        let [z, byte] = [(); 2].map(|_| meta.advice_column());
+        let constants = meta.fixed_column();
+        meta.enable_constant(constants);
+        let table = ByteTable::configure(meta);
+        let instance = meta.instance_column();
+
+        let bytes = BytesChip::configure(meta, z, byte, table);
+        DescentCircuitConfig {
+            descent: DescentChip::configure(meta, advice, bytes, instance),
+            table,
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        config.table.load(layouter.namespace(|| "byte table"))?;
+        let chip = DescentChip::construct(config.descent);
+
+        let pairs = chip.descend(layouter.namespace(|| "descent"), self.x, self.y, N)?;
+        for (a, _) in &pairs {
+            chip.range_check(layouter.namespace(|| "a"), a)?;
+        }
+
+        chip.expose_public(layouter.namespace(|| "expose public"), &pairs[0])
+    }
+}
@@ -0,0 +1,218 @@
+//! The three-column table with its gate switched on by a fixed column `q`
+//! instead of a [`Selector`](halo2_proofs::plonk::Selector).
+//!
+//! A selector is a fixed column with rules: its values are 0 or 1, it may
+//! not appear in lookups, and keygen is free to rewrite it. halo2 0.2
+//! compresses the selectors into as few fixed columns as their degrees and
+//! overlaps allow, replacing each `s` in a gate with a polynomial in the
+//! column that is 1 on its rows and 0 elsewhere. With one selector there is
+//! nothing to combine, so [`FiboFixedCircuit`] compiles to what
+//! `fib-three-column` does: the same gate over one fixed column with the
+//! same commitment, one fixed query, degree 3 and the same proof size. The
+//! verifying keys differ only in the selector count they pin. What changes
+//! is who owns the column: `q` is assigned in every region like an advice
+//! cell, keygen commits to it as written, and any value can go in it, so a
+//! layout can scale a constraint per row or reuse `q` in a lookup. A
+//! circuit with several mutually exclusive selectors is where compression
+//! pays and explicit columns cost one fixed column each.
+
+use std::{fmt, marker::PhantomData};
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    plonk::{Advice, Any, Circuit, Column, ConstraintSystem, Error, Fixed, Instance},
+    poly::Rotation,
+};
+
+use super::{columns, ACell, NUM_TERMS};
+
+#[derive(Clone)]
+pub struct FiboFixedConfig {
+    advice: [Column<Advice>; 3],
+    /// 1 on the rows the gate applies to and 0 elsewhere.
+    q: Column<Fixed>,
+    instance: Column<Instance>,
+}
+
+impl FiboFixedConfig {
+    /// The columns [`FiboFixedChip::configure`] enables equality on, in
+    /// order.
+    pub fn equality_columns(&self) -> Vec<Column<Any>> {
+        let mut columns: Vec<Column<Any>> = self.advice.map(Into::into).to_vec();
+        columns.push(self.instance.into());
+        columns
+    }
+}
+
+#[derive(Debug)]
+pub struct FiboFixedChip<F: FieldExt> {
+    config: FiboFixedConfig,
+    marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> FiboFixedChip<F> {
+    pub fn construct(config: FiboFixedConfig) -> Self {
+        Self {
+            config,
+            marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        instance: Column<Instance>,
+    ) -> FiboFixedConfig {
+        let [col_a, col_b, col_c] = advice;
+
+        let q = meta.fixed_column();
+
+        meta.create_gate("fibonacci", |meta| {
+            //
+            //  col_a | col_b | col_c | q
+            //    a   |   b   |   c   | q
+            //
+            let a = meta.query_advice(col_a, Rotation::cur());
+            let b = meta.query_advice(col_b, Rotation::cur());
+            let c = meta.query_advice(col_c, Rotation::cur());
+
+            let q = meta.query_fixed(q, Rotation::cur());
+
+            vec![q * (a + b - c)]
+        });
+
+        let config = FiboFixedConfig {
+            advice,
+            q,
+            instance,
+        };
+        for column in config.equality_columns() {
+            meta.enable_equality(column);
+        }
+        config
+    }
+
+    pub fn assign_first_row(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: Value<F>,
+        b: Value<F>,
+    ) -> Result<(ACell<F>, ACell<F>), Error> {
+        layouter.assign_region(
+            || "first row",
+            |mut region| {
+                region.assign_fixed(|| "q", self.config.q, 0, || Value::known(F::one()))?;
+                region.assign_advice(|| "a", self.config.advice[0], 0, || a)?;
+                let b_cell = region.assign_advice(|| "b", self.config.advice[1], 0, || b)?;
+                let c_cell = region.assign_advice(|| "c", self.config.advice[2], 0, || a + b)?;
+
+                Ok((b_cell, c_cell))
+            },
+        )
+    }
+
+    /// Assigns the row after the one holding `prev_b` and `prev_c`,
+    /// returning its `b` and `c`.
+    pub fn assign_row(
+        &self,
+        mut layouter: impl Layouter<F>,
+        prev_b: &ACell<F>,
+        prev_c: &ACell<F>,
+    ) -> Result<(ACell<F>, ACell<F>), Error> {
+        layouter.assign_region(
+            || "next row",
+            |mut region| {
+                region.assign_fixed(|| "q", self.config.q, 0, || Value::known(F::one()))?;
+                prev_b.copy_advice(|| "a", &mut region, self.config.advice[0], 0)?;
+                let b_cell = prev_c.copy_advice(|| "b", &mut region, self.config.advice[1], 0)?;
+
+                let c = prev_b.value().copied() + prev_c.value().copied();
+                let c_cell = region.assign_advice(|| "c", self.config.advice[2], 0, || c)?;
+
+                Ok((b_cell, c_cell))
+            },
+        )
+    }
+
+    pub fn expose_public(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cell: &ACell<F>,
+        row: usize,
+    ) -> Result<(), Error> {
+        layouter.constrain_instance(cell.cell(), self.config.instance, row)
+    }
+}
+
+/// Prints the columns, e.g. `advice A0 A1 A2, fixed F0, instance I0,
+/// equality on A0 A1 A2 I0`.
+impl fmt::Display for FiboFixedConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "advice {}, fixed {}, instance {}, equality on {}",
+            columns(&self.advice),
+            columns(&[self.q]),
+            columns(&[self.instance]),
+            columns(&self.equality_columns())
+        )
+    }
+}
+
+impl fmt::Debug for FiboFixedConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FiboFixedConfig")
+            .field("advice", &format_args!("[{}]", columns(&self.advice)))
+            .field("q", &format_args!("{}", columns(&[self.q])))
+            .field("instance", &format_args!("{}", columns(&[self.instance])))
+            .finish()
+    }
+}
+
+impl<F: FieldExt> fmt::Display for FiboFixedChip<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "fixed-selector chip: {}", self.config)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct FiboFixedCircuit<F: FieldExt> {
+    pub a: Value<F>,
+    pub b: Value<F>,
+}
+
+impl<F: FieldExt> Circuit<F> for FiboFixedCircuit<F> {
+    type Config = FiboFixedConfig;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let advice = [(); 3].map(|_| meta.advice_column());
+        let instance = meta.instance_column();
+
+        FiboFixedChip::configure(meta, advice, instance)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let cs = FiboFixedChip::construct(config);
+
+        let (mut prev_b, mut prev_c) =
+            cs.assign_first_row(layouter.namespace(|| "first row"), self.a, self.b)?;
+
+        for _ in 3..NUM_TERMS {
+            (prev_b, prev_c) =
+                cs.assign_row(layouter.namespace(|| "next row"), &prev_b, &prev_c)?;
+        }
+
+        cs.expose_public(layouter.namespace(|| "expose public"), &prev_c, 0)
+    }
+}
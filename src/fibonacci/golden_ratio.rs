@@ -0,0 +1,96 @@
+//! The ratio of consecutive terms approaches the golden ratio.
+//!
+//! With the seeds fixed to `1, 1`, the circuit computes `F(N)` and
+//! `F(N+1)` as fixed-point values and proves
+//!
+//! ```text
+//! |F(N+1) / F(N) - φ| < ε
+//! ```
+//!
+//! for the public `ε`. Both the quotient (rounded down) and the constant
+//! [`PHI`] are within `2^-32` of the real numbers, so the statement is only
+//! meaningful for `ε` well above that. `F(N+1)` has to stay below `2^32`,
+//! so `N <= 46`.
+//!
+//! Instance rows: `0` the raw fixed-point `ε`.
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Layouter, SimpleFloorPlanner},
+    plonk::{Circuit, Column, ConstraintSystem, Error, Instance},
+};
+
+use crate::gadgets::{
+    bytes::{ByteTable, BytesChip},
+    fixed_point::{FixedPointChip, FixedPointConfig, ONE},
+};
+
+/// `φ = (1 + √5) / 2` as a raw fixed-point value, rounded to nearest.
+pub const PHI: u64 = 6_949_403_065;
+
+#[derive(Debug, Clone)]
+pub struct GoldenRatioConfig {
+    pub fixed_point: FixedPointConfig,
+    pub instance: Column<Instance>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GoldenRatioCircuit<const N: usize>;
+
+impl<F: FieldExt, const N: usize> Circuit<F> for GoldenRatioCircuit<N> {
+    type Config = GoldenRatioConfig;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let table = ByteTable::configure(meta);
+        let (z, byte) = (meta.advice_column(), meta.advice_column());
+        let bytes = BytesChip::configure(meta, z, byte, table);
+        let advice = [(); 4].map(|_| meta.advice_column());
+        let constant = meta.fixed_column();
+        let instance = meta.instance_column();
+        meta.enable_equality(instance);
+
+        GoldenRatioConfig {
+            fixed_point: FixedPointChip::configure(meta, advice, constant, bytes),
+            instance,
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        assert!(N > 0, "terms are counted from 1");
+        config
+            .fixed_point
+            .bytes
+            .table
+            .load(layouter.namespace(|| "byte table"))?;
+        let chip = FixedPointChip::construct(config.fixed_point);
+
+        let mut a = chip.constant(layouter.namespace(|| "F(1)"), ONE)?;
+        let mut b = chip.constant(layouter.namespace(|| "F(2)"), ONE)?;
+        for _ in 1..N {
+            let c = chip.add(layouter.namespace(|| "next term"), &a, &b)?;
+            (a, b) = (b, c);
+        }
+        let ratio = chip.div(layouter.namespace(|| "F(N+1) / F(N)"), &b, &a)?;
+
+        let phi = chip.constant(layouter.namespace(|| "phi"), PHI)?;
+        let epsilon = chip.load_instance(layouter.namespace(|| "epsilon"), config.instance, 0)?;
+        let upper = chip.add(layouter.namespace(|| "phi + epsilon"), &phi, &epsilon)?;
+        let lower = chip.add(layouter.namespace(|| "ratio + epsilon"), &ratio, &epsilon)?;
+        chip.assert_less_than(
+            layouter.namespace(|| "ratio < phi + epsilon"),
+            &ratio,
+            &upper,
+        )?;
+        chip.assert_less_than(layouter.namespace(|| "phi < ratio + epsilon"), &phi, &lower)
+    }
+}
@@ -0,0 +1,226 @@
+//! The last decimal digit of each term, `F(n) mod 10`, in a single column.
+//!
+//! The table holds only residues. Two digits sum to at most 18, so each
+//! step needs a quotient of `0` or `1`: row `i` is
+//! `d_i = d_{i-2} + d_{i-1} - 10·q_i` with `q_i` boolean, and every digit,
+//! seeds included, is looked up in a fixed `0..10` table. Both halves
+//! matter: without the lookup `d_i` could be any field element, and with a
+//! free quotient `q_i = (d_{i-2} + d_{i-1} - d_i) / 10` exists for any
+//! digit `d_i`. Instance rows: `0` the last digit.
+
+use std::marker::PhantomData;
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    plonk::{Advice, Circuit, Column, ConstraintSystem, Error, Instance, Selector, TableColumn},
+    poly::Rotation,
+};
+
+use super::{ACell, NUM_TERMS};
+use crate::{expr, rows::RowCursor};
+
+#[derive(Debug, Clone)]
+pub struct LastDigitConfig {
+    /// `digit, quotient`.
+    advice: [Column<Advice>; 2],
+    /// Enabled on rows `2..`, where a digit follows from the two before.
+    s_step: Selector,
+    /// Enabled on every row holding a digit.
+    q_digit: Selector,
+    pub(crate) table: TableColumn,
+    instance: Column<Instance>,
+}
+
+#[derive(Debug)]
+pub struct LastDigitChip<F: FieldExt> {
+    config: LastDigitConfig,
+    marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> LastDigitChip<F> {
+    pub fn construct(config: LastDigitConfig) -> Self {
+        Self {
+            config,
+            marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 2],
+        instance: Column<Instance>,
+    ) -> LastDigitConfig {
+        meta.enable_equality(advice[0]);
+        meta.enable_equality(instance);
+
+        let s_step = meta.selector();
+        let q_digit = meta.complex_selector();
+        let table = meta.lookup_table_column();
+
+        meta.create_gate("last digit", |meta| {
+            //
+            //  digit | quotient | selector
+            //  d_i-2 |          |
+            //  d_i-1 |          |
+            //  d_i   |   q_i    |    s
+            //
+            // d_i = d_i-2 + d_i-1 - 10·q_i
+            //
+            let a = expr::advice(meta, advice[0], -2);
+            let b = expr::advice(meta, advice[0], -1);
+            let c = expr::advice(meta, advice[0], 0);
+            let q = expr::advice(meta, advice[1], 0);
+            let s = meta.query_selector(s_step);
+
+            vec![
+                s.clone() * (a + b - c - q.clone() * F::from(10)),
+                s * expr::bool_check(q),
+            ]
+        });
+
+        meta.lookup(|meta| {
+            let digit = meta.query_advice(advice[0], Rotation::cur());
+            let q = meta.query_selector(q_digit);
+
+            vec![(q * digit, table)]
+        });
+
+        LastDigitConfig {
+            advice,
+            s_step,
+            q_digit,
+            table,
+            instance,
+        }
+    }
+
+    pub fn load_table(&self, mut layouter: impl Layouter<F>) -> Result<(), Error> {
+        layouter.assign_table(
+            || "digit table",
+            |mut table| {
+                for digit in 0..10 {
+                    table.assign_cell(
+                        || "digit",
+                        self.config.table,
+                        digit,
+                        || Value::known(F::from(digit as u64)),
+                    )?;
+                }
+                Ok(())
+            },
+        )
+    }
+
+    /// Lays out the last digits of `nrows` terms seeded with the digits
+    /// `a, b`, returning the cell of the last.
+    pub fn assign(
+        &self,
+        layouter: impl Layouter<F>,
+        a: Value<F>,
+        b: Value<F>,
+        nrows: usize,
+    ) -> Result<ACell<F>, Error> {
+        Ok(self
+            .assign_digits(layouter, a, b, nrows)?
+            .pop()
+            .expect("digits"))
+    }
+
+    /// Like [`LastDigitChip::assign`], returning a cell for every digit in
+    /// order.
+    pub fn assign_digits(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: Value<F>,
+        b: Value<F>,
+        nrows: usize,
+    ) -> Result<Vec<ACell<F>>, Error> {
+        let [digit, quotient] = self.config.advice;
+        let seeds = a
+            .zip(b)
+            .map(|(a, b)| (a.get_lower_128() as u64, b.get_lower_128() as u64));
+
+        layouter.assign_region(
+            || "last digits",
+            |mut region| {
+                let (mut prev, mut cur) = seeds.unzip();
+                let mut rows = RowCursor::new(&mut region);
+                let mut digits = vec![];
+                for seed in [a, b] {
+                    rows.enable(self.config.q_digit)?;
+                    digits.push(rows.assign_advice("seed", digit, seed)?);
+                    rows.advance();
+                }
+
+                for _ in 2..nrows {
+                    rows.enable(self.config.s_step)?;
+                    rows.enable(self.config.q_digit)?;
+                    let sum = prev + cur;
+                    let q = sum.map(|sum| F::from(sum / 10));
+                    rows.assign_advice("quotient", quotient, q)?;
+                    (prev, cur) = (cur, sum.map(|sum| sum % 10));
+                    digits.push(rows.assign_advice("digit", digit, cur.map(F::from))?);
+                    rows.advance();
+                }
+
+                Ok(digits)
+            },
+        )
+    }
+
+    pub fn expose_public(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cell: &ACell<F>,
+        row: usize,
+    ) -> Result<(), Error> {
+        layouter.constrain_instance(cell.cell(), self.config.instance, row)
+    }
+}
+
+/// Exposes the last digit of the [`NUM_TERMS`]th term of the sequence
+/// seeded with the digits `a, b`.
+#[derive(Debug, Default)]
+pub struct LastDigitCircuit<F: FieldExt> {
+    pub a: Value<F>,
+    pub b: Value<F>,
+}
+
+impl<F: FieldExt> Circuit<F> for LastDigitCircuit<F> {
+    type Config = LastDigitConfig;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let advice = [(); 2].map(|_| meta.advice_column());
+        let instance = meta.instance_column();
+
+        LastDigitChip::configure(meta, advice, instance)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let cs = LastDigitChip::construct(config);
+        cs.load_table(layouter.namespace(|| "digit table"))?;
+
+        let last = cs.assign(layouter.namespace(|| "table"), self.a, self.b, NUM_TERMS)?;
+        cs.expose_public(layouter.namespace(|| "expose public"), &last, 0)
+    }
+}
+
+/// The last digit of the `n`th term (counting from 1) seeded with `a, b`.
+pub fn nth_digit(a: u64, b: u64, n: usize) -> u64 {
+    let (mut a, mut b) = (a % 10, b % 10);
+    for _ in 1..n {
+        (a, b) = (b, (a + b) % 10);
+    }
+    a
+}
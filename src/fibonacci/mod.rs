@@ -0,0 +1,59 @@
+//! Fibonacci chips and circuits, one module per layout.
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::AssignedCell,
+    plonk::{Column, ColumnType, Selector},
+};
+
+use crate::introspect::{selector_index, ColumnRef};
+
+pub mod batch;
+pub mod bigint;
+pub mod bounded;
+pub mod checkpoint;
+pub mod chunk;
+pub mod descent;
+pub mod fixed_selector;
+pub mod golden_ratio;
+pub mod last_digit;
+pub mod negative_rotation;
+pub mod non_member;
+pub mod parity;
+pub mod public_inputs;
+pub mod single_column;
+pub mod square_test;
+pub mod stride;
+pub mod table;
+pub mod three_column;
+pub mod vertical;
+
+/// An assigned cell whose value is a field element of the circuit field.
+pub type ACell<F> = AssignedCell<F, F>;
+
+/// Number of sequence terms the circuits lay out, seeds included.
+pub const NUM_TERMS: usize = 10;
+
+/// The `n`th term (counting from 1) of the sequence seeded with `a, b`,
+/// i.e. the value the circuits expose for those seeds when `n` is
+/// [`NUM_TERMS`].
+pub fn nth_term<F: FieldExt>(a: F, b: F, n: usize) -> F {
+    let (mut a, mut b) = (a, b);
+    for _ in 1..n {
+        (a, b) = (b, a + b);
+    }
+    a
+}
+
+/// `columns` as the introspection tools print them, e.g. `A0 A1 A2`.
+pub(crate) fn columns<C: ColumnType>(columns: &[Column<C>]) -> String {
+    columns
+        .iter()
+        .map(|column| ColumnRef::from(*column).to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+pub(crate) fn selector(selector: &Selector) -> String {
+    format!("S{}", selector_index(selector))
+}
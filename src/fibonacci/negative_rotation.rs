@@ -0,0 +1,189 @@
+//! The single-column table with its gate looking back instead of ahead.
+//!
+//! `fib-single-column` enables its gate on the middle of three rows and
+//! queries `prev`, `cur` and `next`. Here the gate is enabled on the row it
+//! constrains, and reads the two terms before it:
+//!
+//! ```text
+//!  row | advice | selector
+//!   0  |  F(1)  |
+//!   1  |  F(2)  |
+//!   2  |  F(3)  |    s        F(1) + F(2) = F(3)
+//!   3  |  F(4)  |    s        F(2) + F(3) = F(4)
+//! ```
+//!
+//! Negative rotations are relative to the row the selector is on, and
+//! halo2 evaluates them modulo `2^k`, not within the region: a gate
+//! enabled on row 0 would read `Rotation(-2)` from row `2^k - 2`, among the
+//! blinding rows, and fail on random values. So the seeds on rows 0 and 1
+//! carry no selector, and every other row does, the last included, where
+//! the forward-looking layout stops one row short. The constraints are the
+//! same and so is the area; only where the selector sits changes.
+
+use std::{fmt, marker::PhantomData};
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    plonk::{Advice, Any, Circuit, Column, ConstraintSystem, Error, Instance, Selector},
+    poly::Rotation,
+};
+
+use super::{columns, selector, ACell, NUM_TERMS};
+use crate::rows::RowCursor;
+
+#[derive(Clone)]
+pub struct FiboConfig {
+    advice: Column<Advice>,
+    selector: Selector,
+    instance: Column<Instance>,
+}
+
+impl FiboConfig {
+    /// The columns [`FiboChip::configure`] enables equality on, in order.
+    pub fn equality_columns(&self) -> Vec<Column<Any>> {
+        vec![self.advice.into(), self.instance.into()]
+    }
+}
+
+#[derive(Debug)]
+pub struct FiboChip<F: FieldExt> {
+    config: FiboConfig,
+    marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> FiboChip<F> {
+    pub fn construct(config: FiboConfig) -> Self {
+        Self {
+            config,
+            marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: Column<Advice>,
+        instance: Column<Instance>,
+    ) -> FiboConfig {
+        let selector = meta.selector();
+
+        meta.create_gate("fibonacci from the rows before", |meta| {
+            let a = meta.query_advice(advice, Rotation(-2));
+            let b = meta.query_advice(advice, Rotation::prev());
+            let c = meta.query_advice(advice, Rotation::cur());
+            let s = meta.query_selector(selector);
+            vec![s * (a + b - c)]
+        });
+
+        let config = FiboConfig {
+            advice,
+            selector,
+            instance,
+        };
+        for column in config.equality_columns() {
+            meta.enable_equality(column);
+        }
+        config
+    }
+
+    /// Lays out `n` terms from `a, b` in one region, returning the last.
+    pub fn assign(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: Value<F>,
+        b: Value<F>,
+        n: usize,
+    ) -> Result<ACell<F>, Error> {
+        let advice = self.config.advice;
+        layouter.assign_region(
+            || "fibonacci table",
+            |mut region| {
+                let mut rows = RowCursor::new(&mut region);
+                let mut a_cell = rows.assign_advice("a", advice, a)?;
+                rows.advance();
+                let mut b_cell = rows.assign_advice("b", advice, b)?;
+
+                for _ in 2..n {
+                    rows.advance();
+                    rows.enable(self.config.selector)?;
+                    let c = a_cell.value().copied() + b_cell.value().copied();
+                    let c_cell = rows.assign_advice("c", advice, c)?;
+                    (a_cell, b_cell) = (b_cell, c_cell);
+                }
+                Ok(b_cell)
+            },
+        )
+    }
+
+    pub fn expose_public(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cell: ACell<F>,
+        row: usize,
+    ) -> Result<(), Error> {
+        layouter.constrain_instance(cell.cell(), self.config.instance, row)
+    }
+}
+
+/// Prints the columns, e.g. `advice A0, selector S0, instance I0,
+/// equality on A0 I0`.
+impl fmt::Display for FiboConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "advice {}, selector {}, instance {}, equality on {}",
+            columns(&[self.advice]),
+            selector(&self.selector),
+            columns(&[self.instance]),
+            columns(&self.equality_columns())
+        )
+    }
+}
+
+impl fmt::Debug for FiboConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FiboConfig")
+            .field("advice", &format_args!("{}", columns(&[self.advice])))
+            .field("selector", &format_args!("{}", selector(&self.selector)))
+            .field("instance", &format_args!("{}", columns(&[self.instance])))
+            .finish()
+    }
+}
+
+impl<F: FieldExt> fmt::Display for FiboChip<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "negative-rotation chip: {}", self.config)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct FiboCircuit<F: FieldExt> {
+    pub a: Value<F>,
+    pub b: Value<F>,
+}
+
+impl<F: FieldExt> Circuit<F> for FiboCircuit<F> {
+    type Config = FiboConfig;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let advice = meta.advice_column();
+        let instance = meta.instance_column();
+        FiboChip::configure(meta, advice, instance)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let chip = FiboChip::construct(config);
+        let last = chip.assign(layouter.namespace(|| "terms"), self.a, self.b, NUM_TERMS)?;
+        chip.expose_public(layouter.namespace(|| "expose last"), last, 0)
+    }
+}
@@ -0,0 +1,243 @@
+//! Proving that a public `v` is not a Fibonacci number below `F(N)`.
+//!
+//! Membership needs one lookup; non-membership needs a witness that `v`
+//! falls strictly between two neighbours. A fixed table lists the
+//! adjacent pairs `(F(i), F(i+1))` for `i` in `0..N`, sorted because the
+//! sequence is, and the prover looks up the pair `(lo, hi)` around `v` in
+//! it and shows `lo < v < hi` by range-checking `above = v - lo - 1` and
+//! `below = hi - v - 1` to the bits of `F(N)`. Those checks mean
+//! something over the integers because every value involved is far below
+//! `p`: a `v` at or below `lo`, or at or above `hi`, would wrap one of the
+//! differences round to nearly `p`. With no term strictly between
+//! neighbours, `v` is no term at all, and the last pair bounds it below
+//! `F(N)`.
+//!
+//! Row `0` of the table is `(0, 0)`, the pair the lookup sees on rows
+//! without the selector; like `(1, 1)` it has nothing between, so neither
+//! can vouch for any `v`. Instance rows: `0` is `v`.
+
+use std::marker::PhantomData;
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    plonk::{Advice, Circuit, Column, ConstraintSystem, Error, Instance, Selector, TableColumn},
+};
+
+use super::ACell;
+use crate::{
+    expr,
+    gadgets::u64::{to_u64, U64Chip, U64Config},
+};
+
+/// The largest `N`: `F(93)` is the last term below `2^64`.
+pub const MAX_TERMS: usize = 93;
+
+/// `F(0), ..., F(n)` as integers.
+pub fn terms(n: usize) -> Vec<u64> {
+    let mut terms = vec![0, 1];
+    while terms.len() <= n {
+        terms.push(terms[terms.len() - 2] + terms[terms.len() - 1]);
+    }
+    terms.truncate(n + 1);
+    terms
+}
+
+/// Bits of `F(n)`, enough to hold both gaps around any `v` below it.
+pub fn gap_bits(n: usize) -> usize {
+    let bound = terms(n)[n];
+    (u64::BITS - bound.leading_zeros()) as usize
+}
+
+#[derive(Debug, Clone)]
+pub struct NonMemberConfig {
+    /// `v, lo, hi, above, below`.
+    advice: [Column<Advice>; 5],
+    /// Enabled on the row holding `v` and its neighbours.
+    q_gap: Selector,
+    /// `lo, hi` of every adjacent pair.
+    table: [TableColumn; 2],
+    range: U64Config,
+    instance: Column<Instance>,
+}
+
+#[derive(Debug)]
+pub struct NonMemberChip<F: FieldExt> {
+    config: NonMemberConfig,
+    marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> NonMemberChip<F> {
+    pub fn construct(config: NonMemberConfig) -> Self {
+        Self {
+            config,
+            marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 5],
+        instance: Column<Instance>,
+    ) -> NonMemberConfig {
+        for column in advice {
+            meta.enable_equality(column);
+        }
+        meta.enable_equality(instance);
+
+        let q_gap = meta.complex_selector();
+        let table = [(); 2].map(|_| meta.lookup_table_column());
+
+        meta.create_gate("gap", |meta| {
+            //
+            //  v | lo | hi | above | below | selector
+            //
+            // above = v - lo - 1, below = hi - v - 1
+            //
+            let [v, lo, hi, above, below] = expr::advice_row(meta, advice);
+            let s = meta.query_selector(q_gap);
+            let one = expr::constant(1);
+
+            vec![
+                s.clone() * (above - v.clone() + lo + one.clone()),
+                s * (below - hi + v + one),
+            ]
+        });
+
+        meta.lookup(|meta| {
+            let [_, lo, hi, _, _] = expr::advice_row(meta, advice);
+            let q = meta.query_selector(q_gap);
+
+            vec![(q.clone() * lo, table[0]), (q * hi, table[1])]
+        });
+
+        NonMemberConfig {
+            advice,
+            q_gap,
+            table,
+            range: U64Chip::configure(meta, advice[0]),
+            instance,
+        }
+    }
+
+    /// Fills the table with `(0, 0)` and then `(F(i), F(i+1))` for `i` in
+    /// `0..n`.
+    pub fn load_table(&self, mut layouter: impl Layouter<F>, n: usize) -> Result<(), Error> {
+        let [lo, hi] = self.config.table;
+        let terms = terms(n);
+        layouter.assign_table(
+            || "neighbour table",
+            |mut table| {
+                let pairs = [(0, 0)]
+                    .into_iter()
+                    .chain(terms.windows(2).map(|w| (w[0], w[1])));
+                for (row, (a, b)) in pairs.enumerate() {
+                    table.assign_cell(|| "lo", lo, row, || Value::known(F::from(a)))?;
+                    table.assign_cell(|| "hi", hi, row, || Value::known(F::from(b)))?;
+                }
+                Ok(())
+            },
+        )
+    }
+
+    /// Lays out `v` between the neighbours of the first `n + 1` terms
+    /// around it, and range-checks both gaps. Returns the cell of `v`.
+    pub fn assign(
+        &self,
+        mut layouter: impl Layouter<F>,
+        v: Value<F>,
+        n: usize,
+    ) -> Result<ACell<F>, Error> {
+        let terms = terms(n);
+        // The last pair starting at or below `v`, so a member or a value
+        // past `F(n)` leaves a gap that fails its range check.
+        let neighbours = v.map(|v| {
+            let v = to_u64(v).unwrap_or(u64::MAX);
+            let i = terms[..n].iter().rposition(|&t| t <= v).unwrap_or(0);
+            (F::from(terms[i]), F::from(terms[i + 1]))
+        });
+        let lo = neighbours.map(|(lo, _)| lo);
+        let hi = neighbours.map(|(_, hi)| hi);
+        let [v_col, lo_col, hi_col, above_col, below_col] = self.config.advice;
+
+        let (v, above, below) = layouter.assign_region(
+            || "gap",
+            |mut region| {
+                self.config.q_gap.enable(&mut region, 0)?;
+                let v = region.assign_advice(|| "v", v_col, 0, || v)?;
+                region.assign_advice(|| "lo", lo_col, 0, || lo)?;
+                region.assign_advice(|| "hi", hi_col, 0, || hi)?;
+                let one = F::one();
+                let above = region.assign_advice(
+                    || "above",
+                    above_col,
+                    0,
+                    || v.value().copied() - lo - Value::known(one),
+                )?;
+                let below = region.assign_advice(
+                    || "below",
+                    below_col,
+                    0,
+                    || hi - v.value().copied() - Value::known(one),
+                )?;
+                Ok((v, above, below))
+            },
+        )?;
+
+        let range = U64Chip::construct(self.config.range.clone());
+        let bits = gap_bits(n);
+        range.range_check_bits(layouter.namespace(|| "above"), &above, bits)?;
+        range.range_check_bits(layouter.namespace(|| "below"), &below, bits)?;
+        Ok(v)
+    }
+
+    pub fn expose_public(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cell: &ACell<F>,
+        row: usize,
+    ) -> Result<(), Error> {
+        layouter.constrain_instance(cell.cell(), self.config.instance, row)
+    }
+}
+
+/// Proves that the public `v` is below `F(N)` and none of `F(0..N)`, so no
+/// Fibonacci number at all, for `N` up to [`MAX_TERMS`].
+#[derive(Debug, Default)]
+pub struct NonMemberCircuit<F: FieldExt, const N: usize> {
+    pub v: Value<F>,
+}
+
+impl<F: FieldExt, const N: usize> Circuit<F> for NonMemberCircuit<F, N> {
+    type Config = NonMemberConfig;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        assert!(
+            (1..=MAX_TERMS).contains(&N),
+            "N must be in 1..={}",
+            MAX_TERMS
+        );
+        let advice = [(); 5].map(|_| meta.advice_column());
+        let instance = meta.instance_column();
+
+        NonMemberChip::configure(meta, advice, instance)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let chip = NonMemberChip::construct(config);
+        chip.load_table(layouter.namespace(|| "neighbour table"), N)?;
+
+        let v = chip.assign(layouter.namespace(|| "gap"), self.v, N)?;
+        chip.expose_public(layouter.namespace(|| "expose public"), &v, 0)
+    }
+}
@@ -0,0 +1,66 @@
+//! The three-column table with only the parity of the last term public.
+//!
+//! The term itself stays private; [`ParityChip`] splits it into `2·h + bit`
+//! with `h` range-checked to 64 bits, and `bit` goes to instance row `0`.
+//! The range check on `h` costs 65 rows against the table's 8, all to pin
+//! down a single bit. The split only holds for terms below `2^65`, so the
+//! seeds have to be small enough for the last term to stay under it.
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    plonk::{Circuit, Column, ConstraintSystem, Error, Instance},
+};
+
+use super::three_column::{FiboChip, FiboConfig, FiboWitness};
+use crate::gadgets::parity::{ParityChip, ParityConfig};
+
+#[derive(Debug, Clone)]
+pub struct FiboParityConfig {
+    fibo: FiboConfig,
+    parity: ParityConfig,
+    instance: Column<Instance>,
+}
+
+#[derive(Debug, Default)]
+pub struct FiboParityCircuit<F: FieldExt> {
+    pub a: Value<F>,
+    pub b: Value<F>,
+}
+
+impl<F: FieldExt> Circuit<F> for FiboParityCircuit<F> {
+    type Config = FiboParityConfig;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let advice = [(); 3].map(|_| meta.advice_column());
+        let range = meta.advice_column();
+        let instance = meta.instance_column();
+
+        FiboParityConfig {
+            fibo: FiboChip::configure(meta, advice, instance),
+            parity: ParityChip::configure(meta, advice, range),
+            instance,
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let fibo = FiboChip::construct(config.fibo);
+        let parity = ParityChip::construct(config.parity);
+
+        let witness = FiboWitness::generate(self.a, self.b);
+        let last = fibo.assign_witness(layouter.namespace(|| "table"), &witness)?;
+        let bit = parity.parity(layouter.namespace(|| "parity"), &last)?;
+
+        layouter.constrain_instance(bit.cell().cell(), config.instance, 0)
+    }
+}
@@ -0,0 +1,40 @@
+//! The instance column each Fibonacci circuit expects, built in one place.
+//!
+//! A proof checked against instances in the wrong order, or with a value
+//! missing, fails as a bare `ConstraintSystemFailure` that does not say
+//! which row was wrong. [`public_inputs_for`] builds the column for a
+//! circuit's [`InstanceMode`] from the seeds and term count, so callers do
+//! not lay it out by hand.
+
+use halo2_proofs::arithmetic::FieldExt;
+
+use super::{checkpoint, nth_term};
+
+/// What a circuit exposes, in instance row order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InstanceMode {
+    /// `F(n)` alone: `three_column`, `single_column` and the layouts built
+    /// on them.
+    Final,
+    /// `a, b, F(n - 1), F(n)`: `chunk`, whose `C` steps span `C + 2`
+    /// terms.
+    SeedsAndFinal,
+    /// The listed terms, counting from 1, in the order given:
+    /// `checkpoint`.
+    Checkpoints(Vec<usize>),
+    /// `n, F(n)`: `table`.
+    IndexAndTerm,
+}
+
+/// The instance columns of a circuit in `mode` over the `n` terms seeded
+/// with `a, b`.
+pub fn public_inputs_for<F: FieldExt>(a: F, b: F, n: usize, mode: &InstanceMode) -> Vec<Vec<F>> {
+    match mode {
+        InstanceMode::Final => vec![vec![nth_term(a, b, n)]],
+        InstanceMode::SeedsAndFinal => {
+            vec![vec![a, b, nth_term(a, b, n - 1), nth_term(a, b, n)]]
+        }
+        InstanceMode::Checkpoints(checkpoints) => checkpoint::instances(a, b, checkpoints),
+        InstanceMode::IndexAndTerm => vec![vec![F::from(n as u64), nth_term(a, b, n)]],
+    }
+}
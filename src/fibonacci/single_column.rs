@@ -0,0 +1,250 @@
+//! The whole table in one region of a single advice column, one term per
+//! row.
+//!
+//! The gate spans three consecutive rows, each overlapping the next
+//! gate's by two, and is enabled on the middle one:
+//!
+//! ```text
+//!  row | advice | selector
+//!   0  |  F(1)  |
+//!   1  |  F(2)  |    s        F(1) + F(2) = F(3)
+//!   2  |  F(3)  |    s        F(2) + F(3) = F(4)
+//!  ... |        |
+//!  n-1 |  F(n)  |
+//! ```
+//!
+//! The first and last rows carry no selector, since the gate there would
+//! read a row outside the region, which halo2 takes from the other end of
+//! the column. Ten terms fill ten cells, where `three_column` fills
+//! three cells on each of eight rows, 24 in all, and needs no copy
+//! constraints between rows, the gate reaching the earlier terms through
+//! its rotations.
+
+use std::{fmt, marker::PhantomData};
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{AssignedCell, Layouter, SimpleFloorPlanner, Value},
+    plonk::{Advice, Any, Circuit, Column, ConstraintSystem, Error, Instance, Selector},
+    poly::Rotation,
+};
+
+use super::{columns, selector, NUM_TERMS};
+use crate::rows::RowCursor;
+
+#[derive(Clone)]
+pub struct FiboConfig {
+    advice: Column<Advice>,
+    selector: Selector,
+    instance: Column<Instance>,
+}
+
+impl FiboConfig {
+    /// The columns [`FiboChip::configure`] enables equality on, in order.
+    pub fn equality_columns(&self) -> Vec<Column<Any>> {
+        vec![self.advice.into(), self.instance.into()]
+    }
+}
+
+#[derive(Debug)]
+pub struct FiboChip<F: FieldExt> {
+    config: FiboConfig,
+    marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> FiboChip<F> {
+    pub fn construct(config: FiboConfig) -> Self {
+        Self {
+            config,
+            marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: Column<Advice>,
+        instance: Column<Instance>,
+    ) -> FiboConfig {
+        let selector = meta.selector();
+
+        meta.create_gate("fibonacci", |meta| {
+            //
+            // advice | selector
+            //   a    |
+            //   b    |    s
+            //   c    |
+            //
+
+            let a = meta.query_advice(advice, Rotation::prev());
+            let b = meta.query_advice(advice, Rotation::cur());
+            let c = meta.query_advice(advice, Rotation::next());
+
+            let s = meta.query_selector(selector);
+
+            vec![s * (a + b - c)]
+        });
+
+        let config = FiboConfig {
+            advice,
+            selector,
+            instance,
+        };
+        for column in config.equality_columns() {
+            meta.enable_equality(column);
+        }
+        config
+    }
+
+    pub fn assign(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: Value<F>,
+        b: Value<F>,
+        nrows: usize,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        layouter.assign_region(
+            || "entire fibonacci table",
+            |mut region| {
+                let mut rows = RowCursor::new(&mut region);
+                let mut a_cell = rows.assign_advice("a", self.config.advice, a)?;
+                rows.advance();
+                rows.enable(self.config.selector)?;
+                let mut b_cell = rows.assign_advice("b", self.config.advice, b)?;
+
+                for _ in 2..nrows {
+                    if rows.advance() < nrows - 1 {
+                        rows.enable(self.config.selector)?;
+                    }
+
+                    let c_val = a_cell.value().copied() + b_cell.value().copied();
+                    let c_cell = rows.assign_advice("c", self.config.advice, c_val)?;
+
+                    a_cell = b_cell;
+                    b_cell = c_cell;
+                }
+
+                Ok(b_cell)
+            },
+        )
+    }
+
+    pub fn expose_public(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cell: AssignedCell<F, F>,
+        row: usize,
+    ) -> Result<(), Error> {
+        layouter.constrain_instance(cell.cell(), self.config.instance, row)
+    }
+}
+
+/// Prints the columns, e.g. `advice A0, selector S0, instance I0,
+/// equality on A0 I0`.
+impl fmt::Display for FiboConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "advice {}, selector {}, instance {}, equality on {}",
+            columns(&[self.advice]),
+            selector(&self.selector),
+            columns(&[self.instance]),
+            columns(&self.equality_columns())
+        )
+    }
+}
+
+impl fmt::Debug for FiboConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FiboConfig")
+            .field("advice", &format_args!("{}", columns(&[self.advice])))
+            .field("selector", &format_args!("{}", selector(&self.selector)))
+            .field("instance", &format_args!("{}", columns(&[self.instance])))
+            .finish()
+    }
+}
+
+impl<F: FieldExt> fmt::Display for FiboChip<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "single-column chip: {}", self.config)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct FiboCircuit<F: FieldExt> {
+    pub a: Value<F>,
+    pub b: Value<F>,
+}
+
+impl<F: FieldExt> Circuit<F> for FiboCircuit<F> {
+    type Config = FiboConfig;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        FiboTermsCircuit::<F>::configure(meta)
+    }
+
+    fn synthesize(&self, config: Self::Config, layouter: impl Layouter<F>) -> Result<(), Error> {
+        let circuit = FiboTermsCircuit {
+            a: self.a,
+            b: self.b,
+            n: NUM_TERMS,
+        };
+        circuit.synthesize(config, layouter)
+    }
+}
+
+/// [`FiboCircuit`] with the number of terms chosen at run time. `n` shapes
+/// the circuit, so every `n` has keys of its own.
+#[derive(Debug, Clone)]
+pub struct FiboTermsCircuit<F: FieldExt> {
+    pub a: Value<F>,
+    pub b: Value<F>,
+    /// Terms, seeds included; at least 3.
+    pub n: usize,
+}
+
+impl<F: FieldExt> Circuit<F> for FiboTermsCircuit<F> {
+    type Config = FiboConfig;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            a: Value::unknown(),
+            b: Value::unknown(),
+            n: self.n,
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let advice = meta.advice_column();
+        let instance = meta.instance_column();
+
+        FiboChip::configure(meta, advice, instance)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        assert!(self.n >= 3, "the gate spans three rows");
+        let cs = FiboChip::construct(config);
+
+        let last_cell = cs.assign(
+            layouter.namespace(|| "assign entire table"),
+            self.a,
+            self.b,
+            self.n,
+        )?;
+
+        cs.expose_public(layouter.namespace(|| "expose public"), last_cell, 0)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,155 @@
+//! Checking that a public pair `(x, y)` is two consecutive terms without
+//! running the sequence at all.
+//!
+//! A non-negative integer `x` is a Fibonacci number exactly when `5x² + 4`
+//! or `5x² - 4` is a perfect square, and the term after it is then
+//! `y = (x + √(5x² ± 4)) / 2`. Equivalently `y² - xy - x² = ±1`, whose
+//! non-negative solutions with `x ≤ y` are exactly the pairs
+//! `(F(n), F(n+1))`. The circuit witnesses the sign `s = ±1`, computes
+//! `t = 5x² + 4s`, takes `√t` with [`SqrtChip`], which must not flag a
+//! non-residue, and requires the root to be `±(2y - x)`.
+//!
+//! All of that holds for far more pairs in the field than in the integers,
+//! since most field elements have square roots. So `x`, `y` and `y - x`
+//! are each range-checked below `2^64` with [`U64Chip`]: then
+//! `y² - xy - x²` is below `2^129` in absolute value, so it is `±1` in the
+//! integers and not just modulo `p`, and `y - x < 2^64` is the order check,
+//! as `y < x` would wrap it round to nearly `p`. The order check leaves
+//! out `(1, 0)`, `F(-1)` and `F(0)`, the one other solution. Instance rows:
+//! `0` is `x`, `1` is `y`.
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    plonk::{Advice, Circuit, Column, ConstraintSystem, Error, Instance},
+};
+
+use crate::{
+    expr,
+    gadgets::{
+        simple::Operand,
+        sqrt::{SqrtChip, SqrtConfig},
+        u64::{U64Chip, U64Config},
+    },
+    simple_chip,
+};
+
+simple_chip! {
+    /// `s = ±1` and `t = 5x² + 4s`.
+    pub struct FiveSquareChip(FiveSquareConfig);
+    gate "five x squared"(x, sign, t) => [
+        (sign.clone() - expr::constant(1)) * (sign.clone() + expr::constant(1)),
+        t - expr::constant(5) * x.clone() * x - expr::constant(4) * sign,
+    ];
+}
+
+simple_chip! {
+    /// `root = ±(2y - x)` for a root that is not of a non-residue, and
+    /// `diff = y - x`.
+    pub struct ConsecutiveChip(ConsecutiveConfig);
+    gate "consecutive"(x, y, root, non_residue, diff) => [
+        (y.clone() * expr::constant(2) - x.clone() - root.clone())
+            * (y.clone() * expr::constant(2) - x.clone() + root),
+        non_residue,
+        diff - y + x,
+    ];
+}
+
+#[derive(Debug, Clone)]
+pub struct SquareTestConfig {
+    five: FiveSquareConfig,
+    consecutive: ConsecutiveConfig,
+    sqrt: SqrtConfig,
+    range: U64Config,
+    instance: Column<Instance>,
+}
+
+impl SquareTestConfig {
+    /// The gadgets share `advice`, each in regions of its own.
+    pub fn configure<F: FieldExt>(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 5],
+        instance: Column<Instance>,
+    ) -> Self {
+        meta.enable_equality(instance);
+        let [a0, a1, a2, a3, a4] = advice;
+
+        Self {
+            five: FiveSquareChip::configure(meta, a0, a1, a2),
+            consecutive: ConsecutiveChip::configure(meta, a0, a1, a2, a3, a4),
+            sqrt: SqrtChip::configure(meta, [a1, a2, a3, a4]),
+            range: U64Chip::configure(meta, a0),
+            instance,
+        }
+    }
+}
+
+/// `s = y² - xy - x²` and `t = 5x² + 4s`: the sign and the value whose
+/// root the circuit takes, for an honest pair.
+pub fn witness<F: FieldExt>(x: F, y: F) -> (F, F) {
+    let sign = y.square() - x * y - x.square();
+    (sign, x.square() * F::from(5) + sign * F::from(4))
+}
+
+/// Proves that the public `x, y` are `F(n), F(n+1)` for some `n`, with
+/// both below `2^64`.
+#[derive(Debug, Default)]
+pub struct SquareTestCircuit<F: FieldExt> {
+    pub x: Value<F>,
+    pub y: Value<F>,
+}
+
+impl<F: FieldExt> Circuit<F> for SquareTestCircuit<F> {
+    type Config = SquareTestConfig;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let advice = [(); 5].map(|_| meta.advice_column());
+        let instance = meta.instance_column();
+
+        SquareTestConfig::configure(meta, advice, instance)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let five = FiveSquareChip::construct(config.five);
+        let consecutive = ConsecutiveChip::construct(config.consecutive);
+        let sqrt = SqrtChip::construct(config.sqrt);
+        let range = U64Chip::construct(config.range);
+
+        let witness = self.x.zip(self.y).map(|(x, y)| witness(x, y));
+        let [x, _, t] = five.assign_row(
+            layouter.namespace(|| "five x squared"),
+            [
+                Operand::Witness(self.x),
+                Operand::Witness(witness.map(|(sign, _)| sign)),
+                Operand::Witness(witness.map(|(_, t)| t)),
+            ],
+        )?;
+        let root = sqrt.sqrt(layouter.namespace(|| "sqrt"), &t)?;
+        let [x, y, _, _, diff] = consecutive.assign_row(
+            layouter.namespace(|| "consecutive"),
+            [
+                Operand::Cell(&x),
+                Operand::Witness(self.y),
+                Operand::Cell(&root.root),
+                Operand::Cell(root.non_residue.cell()),
+                Operand::Witness(self.y - self.x),
+            ],
+        )?;
+
+        for (name, cell) in [("x", &x), ("y", &y), ("y - x", &diff)] {
+            range.range_check(layouter.namespace(|| name), cell)?;
+        }
+        layouter.constrain_instance(x.cell(), config.instance, 0)?;
+        layouter.constrain_instance(y.cell(), config.instance, 1)
+    }
+}
@@ -0,0 +1,236 @@
+//! `K` steps of the recurrence per row.
+//!
+//! A row holds a consecutive pair `(x_i, x_{i+1})` and the next row holds
+//! `(x_{i+K}, x_{i+K+1})`. The two are related by the `K`th power of the
+//! step matrix,
+//!
+//! ```text
+//! [1 1]^K   [F(K+1) F(K)  ]
+//! [1 0]   = [F(K)   F(K-1)]
+//! ```
+//!
+//! whose three distinct entries sit in fixed columns, so one degree-3 gate
+//! replaces `K` rows of the stride-1 chips.
+
+use std::{fmt, marker::PhantomData};
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{AssignedCell, Layouter, SimpleFloorPlanner, Value},
+    plonk::{Advice, Any, Circuit, Column, ConstraintSystem, Error, Fixed, Instance, Selector},
+    poly::Rotation,
+};
+
+use super::{columns, nth_term, selector, NUM_TERMS};
+use crate::{expr, rows::RowCursor};
+
+#[derive(Clone)]
+pub struct FiboStrideConfig {
+    /// The pair `(x_i, x_{i+1})`.
+    advice: [Column<Advice>; 2],
+    /// `F(K-1), F(K), F(K+1)`.
+    coefficients: [Column<Fixed>; 3],
+    selector: Selector,
+    instance: Column<Instance>,
+}
+
+impl FiboStrideConfig {
+    /// The columns [`FiboStrideChip::configure`] enables equality on, in
+    /// order: only the second of each pair is copied or exposed.
+    pub fn equality_columns(&self) -> Vec<Column<Any>> {
+        vec![self.advice[1].into(), self.instance.into()]
+    }
+}
+
+#[derive(Debug)]
+pub struct FiboStrideChip<F: FieldExt, const K: usize> {
+    config: FiboStrideConfig,
+    marker: PhantomData<F>,
+}
+
+impl<F: FieldExt, const K: usize> FiboStrideChip<F, K> {
+    pub fn construct(config: FiboStrideConfig) -> Self {
+        Self {
+            config,
+            marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 2],
+        coefficients: [Column<Fixed>; 3],
+        instance: Column<Instance>,
+    ) -> FiboStrideConfig {
+        let selector = meta.selector();
+
+        meta.create_gate("fibonacci stride", |meta| {
+            //
+            //  x     | y      | f0     | f1   | f2     | selector
+            //  x_i   | x_i+1  | F(K-1) | F(K) | F(K+1) |    s
+            //  x_i+K | x_i+K+1|
+            //
+            let [x, y] = expr::advice_row(meta, advice);
+            let [x_next, y_next] = advice.map(|column| meta.query_advice(column, Rotation::next()));
+            let [f0, f1, f2] = coefficients.map(|column| meta.query_fixed(column, Rotation::cur()));
+
+            let s = meta.query_selector(selector);
+
+            vec![
+                s.clone() * (f0 * x.clone() + f1.clone() * y.clone() - x_next),
+                s * (f1 * x + f2 * y - y_next),
+            ]
+        });
+
+        let config = FiboStrideConfig {
+            advice,
+            coefficients,
+            selector,
+            instance,
+        };
+        for column in config.equality_columns() {
+            meta.enable_equality(column);
+        }
+        config
+    }
+
+    /// Lays out `strides` transitions from the pair `(a, b)` and returns the
+    /// cell holding the second term of the last pair.
+    pub fn assign(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: Value<F>,
+        b: Value<F>,
+        strides: usize,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        let coefficients = [K - 1, K, K + 1].map(fib::<F>);
+
+        layouter.assign_region(
+            || "strided fibonacci table",
+            |mut region| {
+                let [col_x, col_y] = self.config.advice;
+                let (mut x, mut y) = (a, b);
+                let mut rows = RowCursor::new(&mut region);
+                let mut y_cell = rows.assign_advice("y", col_y, y)?;
+                rows.assign_advice("x", col_x, x)?;
+
+                for _ in 0..strides {
+                    rows.enable(self.config.selector)?;
+                    for (column, value) in self.config.coefficients.iter().zip(coefficients) {
+                        rows.assign_fixed("coefficient", *column, value)?;
+                    }
+
+                    let [f0, f1, f2] = coefficients;
+                    (x, y) = (
+                        x.zip(y).map(|(x, y)| f0 * x + f1 * y),
+                        x.zip(y).map(|(x, y)| f1 * x + f2 * y),
+                    );
+                    rows.advance();
+                    rows.assign_advice("x", col_x, x)?;
+                    y_cell = rows.assign_advice("y", col_y, y)?;
+                }
+
+                Ok(y_cell)
+            },
+        )
+    }
+
+    pub fn expose_public(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cell: AssignedCell<F, F>,
+        row: usize,
+    ) -> Result<(), Error> {
+        layouter.constrain_instance(cell.cell(), self.config.instance, row)
+    }
+}
+
+/// `F(n)` of the standard sequence, `F(0) = 0`.
+fn fib<F: FieldExt>(n: usize) -> F {
+    nth_term(F::zero(), F::one(), n + 1)
+}
+
+/// Prints the columns, e.g. `advice A0 A1, coefficients F0 F1 F2,
+/// selector S0, instance I0, equality on A1 I0`.
+impl fmt::Display for FiboStrideConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "advice {}, coefficients {}, selector {}, instance {}, equality on {}",
+            columns(&self.advice),
+            columns(&self.coefficients),
+            selector(&self.selector),
+            columns(&[self.instance]),
+            columns(&self.equality_columns())
+        )
+    }
+}
+
+impl fmt::Debug for FiboStrideConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FiboStrideConfig")
+            .field("advice", &format_args!("[{}]", columns(&self.advice)))
+            .field(
+                "coefficients",
+                &format_args!("[{}]", columns(&self.coefficients)),
+            )
+            .field("selector", &format_args!("{}", selector(&self.selector)))
+            .field("instance", &format_args!("{}", columns(&[self.instance])))
+            .finish()
+    }
+}
+
+impl<F: FieldExt, const K: usize> fmt::Display for FiboStrideChip<F, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "stride-{} chip: {}", K, self.config)
+    }
+}
+
+/// The strided table seeded with `a, b`, exposing the same term as the
+/// stride-1 circuits. `K` must divide `NUM_TERMS - 2`.
+#[derive(Debug, Default)]
+pub struct FiboStrideCircuit<F: FieldExt, const K: usize> {
+    pub a: Value<F>,
+    pub b: Value<F>,
+}
+
+impl<F: FieldExt, const K: usize> Circuit<F> for FiboStrideCircuit<F, K> {
+    type Config = FiboStrideConfig;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let advice = [(); 2].map(|_| meta.advice_column());
+        let coefficients = [(); 3].map(|_| meta.fixed_column());
+        let instance = meta.instance_column();
+
+        FiboStrideChip::<F, K>::configure(meta, advice, coefficients, instance)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        assert!(
+            (NUM_TERMS - 2).is_multiple_of(K),
+            "stride must divide the number of steps"
+        );
+        let cs = FiboStrideChip::<F, K>::construct(config);
+
+        let last_cell = cs.assign(
+            layouter.namespace(|| "assign strided table"),
+            self.a,
+            self.b,
+            (NUM_TERMS - 2) / K,
+        )?;
+
+        cs.expose_public(layouter.namespace(|| "expose public"), last_cell, 0)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,168 @@
+//! Membership of a public `(i, F(i))` pair in a table of the sequence.
+//!
+//! Where the other layouts compute the recurrence in gates, this one
+//! commits to its terms: two lookup table columns hold `i` and `F(i)`,
+//! counting `F(0) = 0` and `F(1) = F(2) = 1`, for as many rows as the
+//! circuit has at its `k`, and the statement is a single lookup of the public pair
+//! into them. The table is fixed, so it is part of the verifying key and
+//! changing any term changes the key; there is no gate at all, and the
+//! prover's work no longer grows with the index, only with `k`. The first
+//! entry `(0, 0)` is also what the lookup sees on rows without the
+//! selector. Instance rows: `0` the index, `1` the term.
+
+use std::marker::PhantomData;
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    plonk::{Advice, Circuit, Column, ConstraintSystem, Error, Instance, Selector, TableColumn},
+};
+
+use crate::expr;
+
+#[derive(Debug, Clone)]
+pub struct FibTableConfig {
+    /// `index, term`.
+    advice: [Column<Advice>; 2],
+    /// Enabled on the row holding the public pair.
+    q_member: Selector,
+    /// `index, term` of every entry.
+    table: [TableColumn; 2],
+    instance: Column<Instance>,
+}
+
+#[derive(Debug)]
+pub struct FibTableChip<F: FieldExt> {
+    config: FibTableConfig,
+    marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> FibTableChip<F> {
+    pub fn construct(config: FibTableConfig) -> Self {
+        Self {
+            config,
+            marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 2],
+        instance: Column<Instance>,
+    ) -> FibTableConfig {
+        for column in advice {
+            meta.enable_equality(column);
+        }
+        meta.enable_equality(instance);
+
+        let q_member = meta.complex_selector();
+        let table = [(); 2].map(|_| meta.lookup_table_column());
+
+        meta.lookup(|meta| {
+            let [index, term] = expr::advice_row(meta, advice);
+            let q = meta.query_selector(q_member);
+
+            vec![(q.clone() * index, table[0]), (q * term, table[1])]
+        });
+
+        FibTableConfig {
+            advice,
+            q_member,
+            table,
+            instance,
+        }
+    }
+
+    /// Fills the table with `(i, F(i))` for `i` in `0..len`.
+    pub fn load_table(&self, mut layouter: impl Layouter<F>, len: usize) -> Result<(), Error> {
+        let [index, term] = self.config.table;
+        layouter.assign_table(
+            || "term table",
+            |mut table| {
+                let (mut a, mut b) = (F::zero(), F::one());
+                for i in 0..len {
+                    table.assign_cell(|| "index", index, i, || Value::known(F::from(i as u64)))?;
+                    table.assign_cell(|| "term", term, i, || Value::known(a))?;
+                    (a, b) = (b, a + b);
+                }
+                Ok(())
+            },
+        )
+    }
+
+    /// Copies the public pair into a row and looks it up.
+    pub fn assign(&self, mut layouter: impl Layouter<F>) -> Result<(), Error> {
+        layouter.assign_region(
+            || "member",
+            |mut region| {
+                self.config.q_member.enable(&mut region, 0)?;
+                for (row, (annotation, column)) in ["index", "term"]
+                    .into_iter()
+                    .zip(self.config.advice)
+                    .enumerate()
+                {
+                    region.assign_advice_from_instance(
+                        || annotation,
+                        self.config.instance,
+                        row,
+                        column,
+                        0,
+                    )?;
+                }
+                Ok(())
+            },
+        )
+    }
+}
+
+/// Entries of the table at `k`: the rows the blinding factors leave
+/// usable, less the one the floor planner pads the table from.
+pub fn capacity<F: FieldExt>(k: u32) -> usize {
+    let mut cs = ConstraintSystem::<F>::default();
+    FibTableCircuit::<F>::configure(&mut cs);
+    (1usize << k).saturating_sub(cs.blinding_factors() + 2)
+}
+
+/// Proves that the public `(i, v)` is `(i, F(i))` for some `i` below
+/// [`capacity`]`(k)`. There is no witness: the table depends only on `k`.
+#[derive(Debug)]
+pub struct FibTableCircuit<F: FieldExt> {
+    pub k: u32,
+    marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> FibTableCircuit<F> {
+    pub fn new(k: u32) -> Self {
+        Self {
+            k,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<F: FieldExt> Circuit<F> for FibTableCircuit<F> {
+    type Config = FibTableConfig;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::new(self.k)
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let advice = [(); 2].map(|_| meta.advice_column());
+        let instance = meta.instance_column();
+
+        FibTableChip::configure(meta, advice, instance)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let chip = FibTableChip::construct(config);
+        chip.load_table(layouter.namespace(|| "term table"), capacity::<F>(self.k))?;
+        chip.assign(layouter.namespace(|| "member"))
+    }
+}
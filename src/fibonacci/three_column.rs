@@ -0,0 +1,323 @@
+use std::{fmt, marker::PhantomData};
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    plonk::{Advice, Any, Circuit, Column, ConstraintSystem, Error, Instance, Selector},
+    poly::Rotation,
+};
+
+use super::{columns, selector, ACell, NUM_TERMS};
+
+/// The terms the three-column layout assigns, computed before synthesis
+/// so that the regions only place them.
+#[derive(Debug, Clone)]
+pub struct FiboWitness<F: FieldExt> {
+    /// All [`NUM_TERMS`] terms, seeds first.
+    pub terms: Vec<Value<F>>,
+}
+
+impl<F: FieldExt> FiboWitness<F> {
+    pub fn generate(a: Value<F>, b: Value<F>) -> Self {
+        let terms = a.zip(b).map(|(a, b)| {
+            let mut terms = vec![a, b];
+            for i in 2..NUM_TERMS {
+                terms.push(terms[i - 2] + terms[i - 1]);
+            }
+            terms
+        });
+        FiboWitness {
+            terms: terms.transpose_vec(NUM_TERMS),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct FiboConfig {
+    advice: [Column<Advice>; 3],
+    selector: Selector,
+    instance: Column<Instance>,
+}
+
+impl FiboConfig {
+    /// The columns [`FiboChip::configure`] enables equality on, in order.
+    pub fn equality_columns(&self) -> Vec<Column<Any>> {
+        let mut columns: Vec<Column<Any>> = self.advice.map(Into::into).to_vec();
+        columns.push(self.instance.into());
+        columns
+    }
+}
+
+#[derive(Debug)]
+pub struct FiboChip<F: FieldExt> {
+    config: FiboConfig,
+    marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> FiboChip<F> {
+    pub fn construct(config: FiboConfig) -> Self {
+        Self {
+            config,
+            marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        instance: Column<Instance>,
+    ) -> FiboConfig {
+        let [col_a, col_b, col_c] = advice;
+
+        let selector = meta.selector();
+
+        meta.create_gate("fibonacci", |meta| {
+            //
+            //  col_a | col_b | col_c | selector
+            //    a   |   b   |   c   |    s
+            //
+            //
+            let a = meta.query_advice(col_a, Rotation::cur());
+            let b = meta.query_advice(col_b, Rotation::cur());
+            let c = meta.query_advice(col_c, Rotation::cur());
+
+            let s = meta.query_selector(selector);
+
+            vec![s * (a + b - c)]
+        });
+
+        let config = FiboConfig {
+            advice: [col_a, col_b, col_c],
+            selector,
+            instance,
+        };
+        for column in config.equality_columns() {
+            meta.enable_equality(column);
+        }
+        config
+    }
+
+    #[allow(clippy::type_complexity)]
+    pub fn assign_first_row(
+        &self,
+        layouter: impl Layouter<F>,
+        a: Value<F>,
+        b: Value<F>,
+    ) -> Result<(ACell<F>, ACell<F>, ACell<F>), Error> {
+        self.assign_first(layouter, a, b, a + b)
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn assign_first(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: Value<F>,
+        b: Value<F>,
+        c: Value<F>,
+    ) -> Result<(ACell<F>, ACell<F>, ACell<F>), Error> {
+        layouter.assign_region(
+            || "first row",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+                let a_cell = region.assign_advice(|| "a", self.config.advice[0], 0, || a)?;
+                let b_cell = region.assign_advice(|| "b", self.config.advice[1], 0, || b)?;
+                let c_cell = region.assign_advice(|| "c", self.config.advice[2], 0, || c)?;
+
+                Ok((a_cell, b_cell, c_cell))
+            },
+        )
+    }
+
+    /// Assigns the row after the one holding `prev_b` and `prev_c`,
+    /// returning its `b` and `c`.
+    pub fn assign_row(
+        &self,
+        layouter: impl Layouter<F>,
+        prev_b: &ACell<F>,
+        prev_c: &ACell<F>,
+    ) -> Result<(ACell<F>, ACell<F>), Error> {
+        let c = prev_b.value().copied() + prev_c.value().copied();
+        self.assign_next(layouter, prev_b, prev_c, c)
+    }
+
+    fn assign_next(
+        &self,
+        mut layouter: impl Layouter<F>,
+        prev_b: &ACell<F>,
+        prev_c: &ACell<F>,
+        c: Value<F>,
+    ) -> Result<(ACell<F>, ACell<F>), Error> {
+        layouter.assign_region(
+            || "next row",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+                let _a_cell = prev_b.copy_advice(|| "a", &mut region, self.config.advice[0], 0)?;
+                let b_cell = prev_c.copy_advice(|| "b", &mut region, self.config.advice[1], 0)?;
+
+                let c_cell = region.assign_advice(|| "c", self.config.advice[2], 0, || c)?;
+
+                Ok((b_cell, c_cell))
+            },
+        )
+    }
+
+    /// Lays out the whole table from a precomputed `witness`, returning the
+    /// cell of the last term.
+    pub fn assign_witness(
+        &self,
+        layouter: impl Layouter<F>,
+        witness: &FiboWitness<F>,
+    ) -> Result<ACell<F>, Error> {
+        Ok(self.assign_terms(layouter, witness)?.pop().expect("terms"))
+    }
+
+    /// Like [`FiboChip::assign_witness`], returning a cell for every term
+    /// in order.
+    pub fn assign_terms(
+        &self,
+        mut layouter: impl Layouter<F>,
+        witness: &FiboWitness<F>,
+    ) -> Result<Vec<ACell<F>>, Error> {
+        let terms = &witness.terms;
+        let (a, b, c) = self.assign_first(
+            layouter.namespace(|| "first row"),
+            terms[0],
+            terms[1],
+            terms[2],
+        )?;
+        let (mut prev_b, mut prev_c) = (b.clone(), c.clone());
+        let mut cells = vec![a, b, c];
+        for &c in &terms[3..] {
+            (prev_b, prev_c) =
+                self.assign_next(layouter.namespace(|| "next row"), &prev_b, &prev_c, c)?;
+            cells.push(prev_c.clone());
+        }
+        Ok(cells)
+    }
+
+    pub fn expose_public(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cell: &ACell<F>,
+        row: usize,
+    ) -> Result<(), Error> {
+        layouter.constrain_instance(cell.cell(), self.config.instance, row)
+    }
+}
+
+/// Prints the columns, e.g. `advice A0 A1 A2, selector S0, instance I0,
+/// equality on A0 A1 A2 I0`.
+impl fmt::Display for FiboConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "advice {}, selector {}, instance {}, equality on {}",
+            columns(&self.advice),
+            selector(&self.selector),
+            columns(&[self.instance]),
+            columns(&self.equality_columns())
+        )
+    }
+}
+
+impl fmt::Debug for FiboConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FiboConfig")
+            .field("advice", &format_args!("[{}]", columns(&self.advice)))
+            .field("selector", &format_args!("{}", selector(&self.selector)))
+            .field("instance", &format_args!("{}", columns(&[self.instance])))
+            .finish()
+    }
+}
+
+impl<F: FieldExt> fmt::Display for FiboChip<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "three-column chip: {}", self.config)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct FiboCircuit<F: FieldExt> {
+    pub a: Value<F>,
+    pub b: Value<F>,
+}
+
+impl<F: FieldExt> Circuit<F> for FiboCircuit<F> {
+    type Config = FiboConfig;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        FiboTermsCircuit::<F>::configure(meta)
+    }
+
+    fn synthesize(&self, config: Self::Config, layouter: impl Layouter<F>) -> Result<(), Error> {
+        let circuit = FiboTermsCircuit {
+            a: self.a,
+            b: self.b,
+            n: NUM_TERMS,
+        };
+        circuit.synthesize(config, layouter)
+    }
+}
+
+/// [`FiboCircuit`] with the number of terms chosen at run time. `n` shapes
+/// the circuit, so every `n` has keys of its own.
+#[derive(Debug, Clone)]
+pub struct FiboTermsCircuit<F: FieldExt> {
+    pub a: Value<F>,
+    pub b: Value<F>,
+    /// Terms, seeds included; at least 3.
+    pub n: usize,
+}
+
+impl<F: FieldExt> Circuit<F> for FiboTermsCircuit<F> {
+    type Config = FiboConfig;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            a: Value::unknown(),
+            b: Value::unknown(),
+            n: self.n,
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let col_a = meta.advice_column();
+        let col_b = meta.advice_column();
+        let col_c = meta.advice_column();
+
+        let advice = [col_a, col_b, col_c];
+
+        let instance = meta.instance_column();
+
+        FiboChip::configure(meta, advice, instance)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        assert!(self.n >= 3, "the first row holds three terms");
+        let cs = FiboChip::construct(config);
+
+        let (_, mut prev_b, mut prev_c) =
+            cs.assign_first_row(layouter.namespace(|| "first row"), self.a, self.b)?;
+
+        for _ in 3..self.n {
+            (prev_b, prev_c) =
+                cs.assign_row(layouter.namespace(|| "next row"), &prev_b, &prev_c)?;
+        }
+
+        cs.expose_public(layouter.namespace(|| "expose public"), &prev_c, 0)?;
+
+        Ok(())
+    }
+}
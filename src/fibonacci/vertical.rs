@@ -0,0 +1,98 @@
+//! The sequence on [`FlexGateChip`], one universal gate down one advice
+//! column, where the other layouts write a custom gate.
+//!
+//! Each term is an addition `a + b·1 = d` whose `a` is the term before,
+//! so after the first operation every term takes three rows, the previous
+//! term and the constant `1` being copied in:
+//!
+//! ```text
+//!  row | advice
+//!   0  | F(1)      s
+//!   1  | F(2)
+//!   2  | 1
+//!   3  | F(3)      s
+//!   4  | F(2)
+//!   5  | 1
+//!   6  | F(4)      ...
+//! ```
+//!
+//! Ten terms take 25 rows where `fib-single-column` takes 10, so the demo
+//! needs k = 5, with two copy constraints a term against none in
+//! `fib-single-column`.
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    plonk::{Circuit, Column, ConstraintSystem, Error, Instance},
+};
+
+use super::NUM_TERMS;
+use crate::gadgets::flex_gate::{FlexGateChip, FlexGateConfig, GateBuilder, QuantumCell};
+
+#[derive(Debug, Clone)]
+pub struct FiboVerticalConfig {
+    gate: FlexGateConfig,
+    instance: Column<Instance>,
+}
+
+/// Records the additions taking `a, b` to the `n`th term on `builder`,
+/// returning the offset of that term.
+pub fn fibonacci<F: FieldExt>(
+    builder: &mut GateBuilder<F>,
+    a: Value<F>,
+    b: Value<F>,
+    n: usize,
+) -> usize {
+    assert!(n >= 3, "the first addition makes the third term");
+    let c = builder.add(QuantumCell::Witness(a), QuantumCell::Witness(b));
+    // `b` is the gate's second cell.
+    let (mut prev, mut last) = (c - 2, c);
+    for _ in 3..n {
+        (prev, last) = (
+            last,
+            builder.add(QuantumCell::Offset(last), QuantumCell::Offset(prev)),
+        );
+    }
+    last
+}
+
+#[derive(Debug, Default)]
+pub struct FiboVerticalCircuit<F: FieldExt> {
+    pub a: Value<F>,
+    pub b: Value<F>,
+}
+
+impl<F: FieldExt> Circuit<F> for FiboVerticalCircuit<F> {
+    type Config = FiboVerticalConfig;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let advice = meta.advice_column();
+        let constants = meta.fixed_column();
+        let instance = meta.instance_column();
+        meta.enable_equality(instance);
+
+        FiboVerticalConfig {
+            gate: FlexGateChip::configure(meta, advice, constants),
+            instance,
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let chip = FlexGateChip::construct(config.gate);
+        let mut builder = GateBuilder::new();
+        let last = fibonacci(&mut builder, self.a, self.b, NUM_TERMS);
+
+        let cells = chip.assign(layouter.namespace(|| "terms"), &builder)?;
+        layouter.constrain_instance(cells[last].cell(), config.instance, 0)
+    }
+}
@@ -0,0 +1,100 @@
+//! Field elements from strings, in decimal or in hex after `0x`.
+//!
+//! `Fp::from(u64)` reaches only the first 2^64 of the field's 2^254-odd
+//! elements, and reducing a larger integer modulo `p` silently turns a typo
+//! into a different element. [`parse`] takes either base and accepts only
+//! canonical values, below the modulus; `p - 1` is written as such, not as
+//! `-1`.
+
+use std::{error, fmt};
+
+use halo2_proofs::arithmetic::FieldExt;
+use num_bigint::BigUint;
+
+use crate::reference::{modulus, to_field};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseFieldError {
+    /// Nothing after the optional `0x`.
+    Empty,
+    /// A leading `-`: field elements have no sign.
+    Negative,
+    /// `found`, at byte offset `position`, is not a digit of the base.
+    InvalidDigit {
+        position: usize,
+        found: char,
+        hex: bool,
+    },
+    /// The value is at least the modulus, so it is no canonical element.
+    OutOfRange {
+        value: BigUint,
+        modulus: BigUint,
+        hex: bool,
+    },
+}
+
+/// `s` as an element of `F`: decimal digits, or hex digits after `0x` or
+/// `0X` in either case, with leading zeros allowed.
+pub fn parse<F: FieldExt>(s: &str) -> Result<F, ParseFieldError> {
+    if s.starts_with('-') {
+        return Err(ParseFieldError::Negative);
+    }
+    let (digits, radix, offset) = match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        Some(digits) => (digits, 16, 2),
+        None => (s, 10, 0),
+    };
+    if digits.is_empty() {
+        return Err(ParseFieldError::Empty);
+    }
+    let hex = radix == 16;
+    if let Some((position, found)) = digits.char_indices().find(|(_, c)| !c.is_digit(radix)) {
+        return Err(ParseFieldError::InvalidDigit {
+            position: offset + position,
+            found,
+            hex,
+        });
+    }
+
+    let value = BigUint::parse_bytes(digits.as_bytes(), radix).expect("digits were checked");
+    let modulus = modulus::<F>();
+    if value >= modulus {
+        return Err(ParseFieldError::OutOfRange {
+            value,
+            modulus,
+            hex,
+        });
+    }
+    Ok(to_field(&value))
+}
+
+impl fmt::Display for ParseFieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseFieldError::Empty => write!(f, "no digits"),
+            ParseFieldError::Negative => {
+                write!(f, "field elements have no sign; write p - x for -x")
+            }
+            ParseFieldError::InvalidDigit {
+                position,
+                found,
+                hex,
+            } => write!(
+                f,
+                "{:?} at position {} is not a {} digit",
+                found,
+                position,
+                if *hex { "hex" } else { "decimal" }
+            ),
+            ParseFieldError::OutOfRange {
+                value,
+                modulus,
+                hex: true,
+            } => write!(f, "{:#x} is not below the modulus {:#x}", value, modulus),
+            ParseFieldError::OutOfRange { value, modulus, .. } => {
+                write!(f, "{} is not below the modulus {}", value, modulus)
+            }
+        }
+    }
+}
+
+impl error::Error for ParseFieldError {}
@@ -0,0 +1,34 @@
+//! Stable identifiers for verifying keys.
+
+use halo2_proofs::{pasta::EqAffine, plonk::VerifyingKey};
+
+/// A 32-byte digest identifying a verifying key.
+pub type Fingerprint = [u8; 32];
+
+/// Hashes the pinned representation of `vk`, the same data halo2 itself
+/// hashes into the transcript, so two keys share a fingerprint exactly when
+/// they describe the same circuit at the same `k`.
+pub fn fingerprint(vk: &VerifyingKey<EqAffine>) -> Fingerprint {
+    let pinned = pinned(vk);
+    let hash = blake2b_simd::Params::new()
+        .hash_length(32)
+        .personal(b"Fibo-Fingerprint")
+        .to_state()
+        .update(&(pinned.len() as u64).to_le_bytes())
+        .update(pinned.as_bytes())
+        .finalize();
+
+    let mut fingerprint = [0; 32];
+    fingerprint.copy_from_slice(hash.as_bytes());
+    fingerprint
+}
+
+/// The pinned representation of `vk`: its domain, constraint system and
+/// fixed and permutation commitments.
+pub fn pinned(vk: &VerifyingKey<EqAffine>) -> String {
+    format!("{:?}", vk.pinned())
+}
+
+pub fn to_hex(fingerprint: &Fingerprint) -> String {
+    fingerprint.iter().map(|b| format!("{:02x}", b)).collect()
+}
@@ -0,0 +1,231 @@
+//! Experimental: Nova-style folding of the Fibonacci step.
+//!
+//! One step maps `(x0, x1)` to `(y0, y1) = (x1, x0 + x1)`. Written as a
+//! relaxed R1CS over `z = (x0, x1, y0, y1)` with slack `u` and error vector
+//! `E`, each step satisfies
+//!
+//! ```text
+//! (A z) ∘ (B z) = u · (C z) + E,   A z = (x1, x0 + x1), B z = (u, u), C z = (y0, y1)
+//! ```
+//!
+//! A fresh step has `u = 1, E = 0`. Two instances fold into one with a
+//! random `r`: `u = u1 + r·u2`, `z = z1 + r·z2`, `E = E1 + r·T + r²·E2`,
+//! where `T` is the cross term, and the result is satisfied iff both inputs
+//! were (with overwhelming probability over `r`). Folding a whole trace
+//! leaves one accumulator, and [`FoldedStepCircuit`] proves it with a single
+//! halo2 proof. The step is linear, so folding satisfied instances never
+//! introduces error terms (`T` vanishes); a broken step shows up as a
+//! nonzero residual that survives every later fold.
+//!
+//! This is a prototype of the algebra, not a secure scheme: nothing is
+//! committed to, `r` is derived from the plain instances, and the chaining
+//! of step outputs into inputs is checked natively by [`fold_trace`] rather
+//! than by an augmented circuit. A real IVC needs commitments to `z` and `E`
+//! and an in-circuit folding verifier.
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    pasta::{group::ff::PrimeField, Fp},
+    plonk::{Advice, Circuit, Column, ConstraintSystem, Error, Instance, Selector},
+};
+
+use crate::expr;
+
+/// A relaxed instance of the step relation, witness included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StepInstance {
+    pub u: Fp,
+    /// `(x0, x1, y0, y1)`.
+    pub z: [Fp; 4],
+    pub e: [Fp; 2],
+}
+
+impl StepInstance {
+    /// The strict instance for one step from `(x0, x1)`.
+    pub fn step(x0: Fp, x1: Fp) -> Self {
+        StepInstance {
+            u: Fp::one(),
+            z: [x0, x1, x1, x0 + x1],
+            e: [Fp::zero(); 2],
+        }
+    }
+
+    pub fn input(&self) -> (Fp, Fp) {
+        (self.z[0], self.z[1])
+    }
+
+    pub fn output(&self) -> (Fp, Fp) {
+        (self.z[2], self.z[3])
+    }
+
+    /// `(A z) ∘ (B z) - u · (C z) - E`, zero when the instance is satisfied.
+    pub fn residual(&self) -> [Fp; 2] {
+        let (az, bz, cz) = (self.az(), self.bz(), self.cz());
+        [0, 1].map(|i| az[i] * bz[i] - self.u * cz[i] - self.e[i])
+    }
+
+    pub fn is_satisfied(&self) -> bool {
+        self.residual() == [Fp::zero(); 2]
+    }
+
+    fn az(&self) -> [Fp; 2] {
+        [self.z[1], self.z[0] + self.z[1]]
+    }
+
+    fn bz(&self) -> [Fp; 2] {
+        [self.u, self.u]
+    }
+
+    fn cz(&self) -> [Fp; 2] {
+        [self.z[2], self.z[3]]
+    }
+}
+
+/// The cross term `T` of folding `a` with `b`.
+pub fn cross_term(a: &StepInstance, b: &StepInstance) -> [Fp; 2] {
+    let (az1, bz1, cz1) = (a.az(), a.bz(), a.cz());
+    let (az2, bz2, cz2) = (b.az(), b.bz(), b.cz());
+    [0, 1].map(|i| az1[i] * bz2[i] + az2[i] * bz1[i] - a.u * cz2[i] - b.u * cz1[i])
+}
+
+/// Fiat-Shamir folding challenge over both instances and the cross term.
+pub fn challenge(a: &StepInstance, b: &StepInstance, t: &[Fp; 2]) -> Fp {
+    let mut state = blake2b_simd::Params::new()
+        .hash_length(64)
+        .personal(b"Fibo-Folding")
+        .to_state();
+    for instance in [a, b] {
+        state.update(&instance.u.to_repr());
+        for value in instance.z.iter().chain(&instance.e) {
+            state.update(&value.to_repr());
+        }
+    }
+    for value in t {
+        state.update(&value.to_repr());
+    }
+    Fp::from_bytes_wide(state.finalize().as_array())
+}
+
+/// Folds `b` into `a`.
+pub fn fold(a: &StepInstance, b: &StepInstance) -> StepInstance {
+    let t = cross_term(a, b);
+    let r = challenge(a, b, &t);
+    StepInstance {
+        u: a.u + r * b.u,
+        z: [0, 1, 2, 3].map(|i| a.z[i] + r * b.z[i]),
+        e: [0, 1].map(|i| a.e[i] + r * t[i] + r.square() * b.e[i]),
+    }
+}
+
+/// The Fibonacci trace seeded with `a, b`, one strict instance per step.
+pub fn trace(a: Fp, b: Fp, steps: usize) -> Vec<StepInstance> {
+    let mut state = (a, b);
+    (0..steps)
+        .map(|_| {
+            let step = StepInstance::step(state.0, state.1);
+            state = step.output();
+            step
+        })
+        .collect()
+}
+
+/// Checks that each step starts where the previous one ended and folds the
+/// whole trace into one accumulator. `None` if the trace is empty or broken.
+pub fn fold_trace(steps: &[StepInstance]) -> Option<StepInstance> {
+    let chained = steps
+        .windows(2)
+        .all(|pair| pair[0].output() == pair[1].input());
+    if !chained {
+        return None;
+    }
+    steps.iter().copied().reduce(|acc, step| fold(&acc, &step))
+}
+
+#[derive(Debug, Clone)]
+pub struct FoldedStepConfig {
+    /// `u, x0, x1, y0, y1, e0, e1`, all on one row.
+    advice: [Column<Advice>; 7],
+    selector: Selector,
+    instance: Column<Instance>,
+}
+
+/// Proves knowledge of a `z` satisfying the relaxed step relation for
+/// public `u` and `E`, which sit in instance rows `0, 1, 2`.
+#[derive(Debug, Default)]
+pub struct FoldedStepCircuit {
+    pub accumulator: Value<StepInstance>,
+}
+
+impl Circuit<Fp> for FoldedStepCircuit {
+    type Config = FoldedStepConfig;
+
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+        let advice = [(); 7].map(|_| meta.advice_column());
+        let instance = meta.instance_column();
+        for column in [advice[0], advice[5], advice[6]] {
+            meta.enable_equality(column);
+        }
+        meta.enable_equality(instance);
+
+        let selector = meta.selector();
+        meta.create_gate("relaxed step", |meta| {
+            let [u, x0, x1, y0, y1, e0, e1] = expr::advice_row(meta, advice);
+            let s = meta.query_selector(selector);
+
+            vec![
+                s.clone() * (x1.clone() * u.clone() - u.clone() * y0 - e0),
+                s * ((x0 + x1) * u.clone() - u * y1 - e1),
+            ]
+        });
+
+        FoldedStepConfig {
+            advice,
+            selector,
+            instance,
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<Fp>,
+    ) -> Result<(), Error> {
+        let public = layouter.assign_region(
+            || "accumulator",
+            |mut region| {
+                config.selector.enable(&mut region, 0)?;
+                let value = |i: usize| {
+                    self.accumulator.map(|acc| match i {
+                        0 => acc.u,
+                        1..=4 => acc.z[i - 1],
+                        _ => acc.e[i - 5],
+                    })
+                };
+                let cells = (0..7)
+                    .map(|i| {
+                        region.assign_advice(|| "accumulator", config.advice[i], 0, || value(i))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok([cells[0].clone(), cells[5].clone(), cells[6].clone()])
+            },
+        )?;
+
+        for (row, cell) in public.iter().enumerate() {
+            layouter.constrain_instance(cell.cell(), config.instance, row)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Instance column for [`FoldedStepCircuit`]: `u, e0, e1`.
+pub fn public_inputs(accumulator: &StepInstance) -> Vec<Vec<Fp>> {
+    vec![vec![accumulator.u, accumulator.e[0], accumulator.e[1]]]
+}
@@ -0,0 +1,142 @@
+//! 64-bit addition with an explicit carry.
+//!
+//! One row `a | b | c | carry` with `a + b = c + carry·2^64` and `carry`
+//! boolean. The operands are [`AssignedU64`]s, so they are range-checked
+//! already; the sum is range-checked by the embedded [`U64Chip`], which,
+//! together with the boolean carry, pins `c` to `(a + b) mod 2^64`. In
+//! [`Overflow::Checked`] mode the carry must also be zero.
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::Layouter,
+    plonk::{Advice, Column, ConstraintSystem, Error, Selector},
+    poly::Rotation,
+};
+
+use super::{
+    u64::{AssignedU64, U64Chip, U64Config},
+    AssignedBit,
+};
+use crate::expr;
+
+/// What to do when the sum does not fit in 64 bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Overflow {
+    /// Keep the low 64 bits and report the carry, like `u64::overflowing_add`.
+    Wrapping,
+    /// Make the circuit unsatisfiable, like `u64::checked_add(..).unwrap()`.
+    Checked,
+}
+
+#[derive(Debug, Clone)]
+pub struct Add64Config {
+    /// `a, b, c, carry`.
+    pub advice: [Column<Advice>; 4],
+    pub selector: Selector,
+    /// Additionally forces the carry to zero.
+    pub s_checked: Selector,
+    pub range: U64Config,
+}
+
+#[derive(Debug)]
+pub struct Sum64<F: FieldExt> {
+    pub sum: AssignedU64<F>,
+    pub carry: AssignedBit<F>,
+}
+
+#[derive(Debug)]
+pub struct Add64Chip<F: FieldExt> {
+    config: Add64Config,
+    range: U64Chip<F>,
+}
+
+impl<F: FieldExt> Add64Chip<F> {
+    pub fn construct(config: Add64Config) -> Self {
+        Self {
+            range: U64Chip::construct(config.range.clone()),
+            config,
+        }
+    }
+
+    /// `range` is the running-sum column of the sum's range check.
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 4],
+        range: Column<Advice>,
+    ) -> Add64Config {
+        for column in advice {
+            meta.enable_equality(column);
+        }
+
+        let selector = meta.selector();
+        let s_checked = meta.selector();
+
+        meta.create_gate("add64", |meta| {
+            //
+            //  a | b | c | carry | selector
+            //  a | b | c |   k   |    s
+            //
+            let [a, b, c, carry] = expr::advice_row(meta, advice);
+            let s = meta.query_selector(selector);
+            let two_pow_64 = expr::constant_u128(1 << 64);
+
+            vec![
+                s.clone() * (a + b - c - carry.clone() * two_pow_64),
+                s * expr::bool_check(carry),
+            ]
+        });
+
+        meta.create_gate("add64 checked", |meta| {
+            let carry = meta.query_advice(advice[3], Rotation::cur());
+            let s = meta.query_selector(s_checked);
+
+            vec![s * carry]
+        });
+
+        Add64Config {
+            advice,
+            selector,
+            s_checked,
+            range: U64Chip::configure(meta, range),
+        }
+    }
+
+    pub fn add(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &AssignedU64<F>,
+        b: &AssignedU64<F>,
+        mode: Overflow,
+    ) -> Result<Sum64<F>, Error> {
+        let [col_a, col_b, col_c, col_carry] = self.config.advice;
+        let sum = a.value().zip(b.value()).map(|(a, b)| a.overflowing_add(b));
+
+        let (c, carry) = layouter.assign_region(
+            || "add64",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+                if mode == Overflow::Checked {
+                    self.config.s_checked.enable(&mut region, 0)?;
+                }
+                a.cell().copy_advice(|| "a", &mut region, col_a, 0)?;
+                b.cell().copy_advice(|| "b", &mut region, col_b, 0)?;
+                let c = region.assign_advice(|| "c", col_c, 0, || sum.map(|(c, _)| F::from(c)))?;
+                let carry = region.assign_advice(
+                    || "carry",
+                    col_carry,
+                    0,
+                    || sum.map(|(_, carry)| F::from(carry as u64)),
+                )?;
+                Ok((c, carry))
+            },
+        )?;
+
+        let sum = self
+            .range
+            .range_check(layouter.namespace(|| "sum range"), &c)?;
+        Ok(Sum64 {
+            sum,
+            carry: AssignedBit::new(carry),
+        })
+    }
+}
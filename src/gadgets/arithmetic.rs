@@ -0,0 +1,148 @@
+//! Field arithmetic on assigned cells: addition, multiplication and
+//! division.
+//!
+//! Every operation is one row of `lhs | rhs | out`. Division witnesses the
+//! quotient `q`, lays out the multiplication row `q · b = a` with `a` copied
+//! into the output, and checks `b ≠ 0` with [`NonZeroChip`], since otherwise
+//! `a = 0, b = 0` would accept any `q`.
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::Layouter,
+    plonk::{Advice, Column, ConstraintSystem, Error},
+};
+
+use super::{
+    nonzero::{NonZeroChip, NonZeroConfig},
+    simple::Operand,
+};
+use crate::fibonacci::ACell;
+
+/// The operations [`ArithmeticChip`] provides.
+pub trait ArithmeticInstructions<F: FieldExt> {
+    fn add(
+        &self,
+        layouter: impl Layouter<F>,
+        a: &ACell<F>,
+        b: &ACell<F>,
+    ) -> Result<ACell<F>, Error>;
+
+    fn mul(
+        &self,
+        layouter: impl Layouter<F>,
+        a: &ACell<F>,
+        b: &ACell<F>,
+    ) -> Result<ACell<F>, Error>;
+
+    /// `a / b`; unsatisfiable when `b` is zero.
+    fn div(
+        &self,
+        layouter: impl Layouter<F>,
+        a: &ACell<F>,
+        b: &ACell<F>,
+    ) -> Result<ACell<F>, Error>;
+}
+
+crate::simple_chip! {
+    pub struct AddChip(AddConfig);
+    //
+    //  lhs | rhs | out   | selector
+    //   a  |  b  | a + b |    s
+    //
+    gate "add"(lhs, rhs, out) => [lhs + rhs - out];
+}
+
+crate::simple_chip! {
+    pub struct MulChip(MulConfig);
+    //
+    //  lhs | rhs | out   | selector
+    //   a  |  b  | a * b |    s
+    //
+    gate "mul"(lhs, rhs, out) => [lhs * rhs - out];
+}
+
+#[derive(Debug, Clone)]
+pub struct ArithmeticConfig {
+    pub add: AddConfig,
+    pub mul: MulConfig,
+    /// Checks the divisor of [`div`](ArithmeticInstructions::div).
+    pub nonzero: NonZeroConfig,
+}
+
+#[derive(Debug)]
+pub struct ArithmeticChip<F: FieldExt> {
+    add: AddChip<F>,
+    mul: MulChip<F>,
+    nonzero: NonZeroChip<F>,
+}
+
+impl<F: FieldExt> ArithmeticChip<F> {
+    pub fn construct(config: ArithmeticConfig) -> Self {
+        Self {
+            add: AddChip::construct(config.add),
+            mul: MulChip::construct(config.mul),
+            nonzero: NonZeroChip::construct(config.nonzero),
+        }
+    }
+
+    /// `advice` is `lhs, rhs, out`, shared by every operation.
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+    ) -> ArithmeticConfig {
+        let [lhs, rhs, out] = advice;
+        ArithmeticConfig {
+            add: AddChip::configure(meta, lhs, rhs, out),
+            mul: MulChip::configure(meta, lhs, rhs, out),
+            nonzero: NonZeroChip::configure(meta, lhs, rhs),
+        }
+    }
+}
+
+impl<F: FieldExt> ArithmeticInstructions<F> for ArithmeticChip<F> {
+    fn add(
+        &self,
+        layouter: impl Layouter<F>,
+        a: &ACell<F>,
+        b: &ACell<F>,
+    ) -> Result<ACell<F>, Error> {
+        let out = a.value().copied() + b.value().copied();
+        let [_, _, out] = self.add.assign_row(
+            layouter,
+            [Operand::Cell(a), Operand::Cell(b), Operand::Witness(out)],
+        )?;
+        Ok(out)
+    }
+
+    fn mul(
+        &self,
+        layouter: impl Layouter<F>,
+        a: &ACell<F>,
+        b: &ACell<F>,
+    ) -> Result<ACell<F>, Error> {
+        let out = a.value().copied() * b.value().copied();
+        let [_, _, out] = self.mul.assign_row(
+            layouter,
+            [Operand::Cell(a), Operand::Cell(b), Operand::Witness(out)],
+        )?;
+        Ok(out)
+    }
+
+    fn div(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &ACell<F>,
+        b: &ACell<F>,
+    ) -> Result<ACell<F>, Error> {
+        let b_inv = self
+            .nonzero
+            .assert_nonzero(layouter.namespace(|| "divisor is nonzero"), b)?;
+        let q = a.value().copied() * b_inv.value().copied();
+
+        let [q, _, _] = self.mul.assign_row(
+            layouter.namespace(|| "div"),
+            [Operand::Witness(q), Operand::Cell(b), Operand::Cell(a)],
+        )?;
+        Ok(q)
+    }
+}
@@ -0,0 +1,486 @@
+//! Unsigned integers wider than the field, as vectors of 64-bit limbs.
+//!
+//! A [`BigIntChip`] is configured for a fixed limb count `n`, so it holds
+//! integers below `2^(64n)`; every limb is range-checked with
+//! [`BytesChip`]. Unlike field elements the values never wrap: an
+//! operation whose result does not fit in `n` limbs is unsatisfiable.
+//!
+//! Each operation is one wide row, with carries propagated column by
+//! column in base `2^64`:
+//!
+//! ```text
+//! x0..x(n-1) | y0..y(n-1) | z0..z(n-1) | c0..
+//! ```
+//!
+//! - add: `x_k + y_k + c_{k-1} = z_k + c_k·2^64`, boolean carries, no carry
+//!   out of the top limb.
+//! - mul: the `k`th column of `x·y` plus `c_{k-1}` is `z_k + c_k·2^64`,
+//!   where `z_k` is zero above the top limb and the last column carries
+//!   nothing out. Carries are range-checked to 72 bits, which holds for up
+//!   to [`MAX_LIMBS`] limbs.
+//! - compare: `x_k - y_k - w_{k-1} + w_k·2^64 = z_k` with boolean borrows
+//!   `w_k`; the last borrow is set exactly when `x < y`.
+//!
+//! Every term stays below `2^137`, so the identities hold over the
+//! integers. Reduction modulo `m` witnesses `q` and `r` and reuses the
+//! rows above: `q·m + r = a` and `r < m`.
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Layouter, Region, Value},
+    plonk::{Advice, Column, ConstraintSystem, Error, Expression, Selector, VirtualCells},
+};
+use num_bigint::BigUint;
+
+use super::{
+    bytes::{BytesChip, BytesConfig},
+    AssignedBit,
+};
+use crate::{expr, fibonacci::ACell};
+
+pub const LIMB_BITS: usize = 64;
+
+/// Largest limb count whose multiplication carries fit in [`CARRY_BYTES`].
+pub const MAX_LIMBS: usize = 128;
+
+/// Bytes in a multiplication carry's range check.
+const CARRY_BYTES: usize = 9;
+
+/// The result limbs and carry cells of an operation row.
+type Row<F> = (Vec<ACell<F>>, Vec<ACell<F>>);
+
+/// An integer below `2^(64n)`, as `n` range-checked limbs, least
+/// significant first.
+#[derive(Debug, Clone)]
+pub struct AssignedBigInt<F: FieldExt> {
+    pub limbs: Vec<ACell<F>>,
+}
+
+impl<F: FieldExt> AssignedBigInt<F> {
+    pub fn value(&self) -> Value<BigUint> {
+        let limbs = self.limbs.iter().fold(Value::known(vec![]), |acc, limb| {
+            acc.zip(limb.value()).map(|(mut acc, limb)| {
+                acc.push(limb.get_lower_128() as u64);
+                acc
+            })
+        });
+        limbs.map(|limbs| from_limbs(&limbs))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BigIntConfig {
+    pub x: Vec<Column<Advice>>,
+    pub y: Vec<Column<Advice>>,
+    pub z: Vec<Column<Advice>>,
+    /// `max(2n - 2, n)` columns: carries for add and mul, borrows for
+    /// compare.
+    pub carries: Vec<Column<Advice>>,
+    pub s_add: Selector,
+    pub s_mul: Selector,
+    pub s_compare: Selector,
+    /// Enabled together with `s_compare` to require `x < y`.
+    pub s_less: Selector,
+    pub bytes: BytesConfig,
+}
+
+#[derive(Debug)]
+pub struct BigIntChip<F: FieldExt> {
+    config: BigIntConfig,
+    bytes: BytesChip<F>,
+}
+
+impl<F: FieldExt> BigIntChip<F> {
+    pub fn construct(config: BigIntConfig) -> Self {
+        Self {
+            bytes: BytesChip::construct(config.bytes.clone()),
+            config,
+        }
+    }
+
+    /// Allocates the columns of an operation row for `limbs`-limb integers.
+    /// `bytes` does the range checks; its table has to be loaded by the
+    /// circuit.
+    ///
+    /// Panics if `limbs` is zero or above [`MAX_LIMBS`].
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        limbs: usize,
+        bytes: BytesConfig,
+    ) -> BigIntConfig {
+        assert!(
+            (1..=MAX_LIMBS).contains(&limbs),
+            "limb count {} is not in 1..={}",
+            limbs,
+            MAX_LIMBS
+        );
+        let mut columns = |n| {
+            (0..n)
+                .map(|_| {
+                    let column = meta.advice_column();
+                    meta.enable_equality(column);
+                    column
+                })
+                .collect::<Vec<_>>()
+        };
+        let (x, y, z) = (columns(limbs), columns(limbs), columns(limbs));
+        let carries = columns((2 * limbs - 2).max(limbs));
+
+        let s_add = meta.selector();
+        let s_mul = meta.selector();
+        let s_compare = meta.selector();
+        let s_less = meta.selector();
+
+        let base = expr::constant_u128(1 << LIMB_BITS);
+
+        meta.create_gate("bigint add", |meta| {
+            let [x, y, z, c] = [&x, &y, &z, &carries].map(|columns| query(meta, columns));
+            let s = meta.query_selector(s_add);
+
+            let mut constraints = vec![];
+            for k in 0..limbs {
+                let mut t = x[k].clone() + y[k].clone() - z[k].clone();
+                if k > 0 {
+                    t = t + c[k - 1].clone();
+                }
+                if k < limbs - 1 {
+                    t = t - c[k].clone() * base.clone();
+                    constraints.push(s.clone() * expr::bool_check(c[k].clone()));
+                }
+                constraints.push(s.clone() * t);
+            }
+            constraints
+        });
+
+        meta.create_gate("bigint mul", |meta| {
+            let [x, y, z, c] = [&x, &y, &z, &carries].map(|columns| query(meta, columns));
+            let s = meta.query_selector(s_mul);
+
+            let mut constraints = vec![];
+            for k in 0..2 * limbs - 1 {
+                let mut t = expr::sum(
+                    (0..limbs)
+                        .filter(|&i| k >= i && k - i < limbs)
+                        .map(|i| x[i].clone() * y[k - i].clone()),
+                );
+                if k < limbs {
+                    t = t - z[k].clone();
+                }
+                if k > 0 {
+                    t = t + c[k - 1].clone();
+                }
+                if k < 2 * limbs - 2 {
+                    t = t - c[k].clone() * base.clone();
+                }
+                constraints.push(s.clone() * t);
+            }
+            constraints
+        });
+
+        meta.create_gate("bigint compare", |meta| {
+            let [x, y, z, w] = [&x, &y, &z, &carries].map(|columns| query(meta, columns));
+            let s = meta.query_selector(s_compare);
+            let s_less = meta.query_selector(s_less);
+
+            let mut constraints = vec![];
+            for k in 0..limbs {
+                let mut t = x[k].clone() - y[k].clone() + w[k].clone() * base.clone();
+                if k > 0 {
+                    t = t - w[k - 1].clone();
+                }
+                constraints.push(s.clone() * (t - z[k].clone()));
+                constraints.push(s.clone() * expr::bool_check(w[k].clone()));
+            }
+            constraints.push(s_less * (expr::constant(1) - w[limbs - 1].clone()));
+            constraints
+        });
+
+        BigIntConfig {
+            x,
+            y,
+            z,
+            carries,
+            s_add,
+            s_mul,
+            s_compare,
+            s_less,
+            bytes,
+        }
+    }
+
+    pub fn limbs(&self) -> usize {
+        self.config.x.len()
+    }
+
+    /// Assigns `value` and range-checks its limbs. A known value that does
+    /// not fit in the configured limbs is a synthesis error.
+    pub fn load(
+        &self,
+        mut layouter: impl Layouter<F>,
+        value: Value<BigUint>,
+    ) -> Result<AssignedBigInt<F>, Error> {
+        let n = self.limbs();
+        value.error_if_known_and(|value| value.bits() > (n * LIMB_BITS) as u64)?;
+        let limbs = value.map(|value| to_limbs(&value, n));
+        let limbs = layouter.assign_region(
+            || "load bigint",
+            |mut region| {
+                (0..n)
+                    .map(|i| {
+                        let limb = limbs.as_ref().map(|limbs| F::from(limbs[i]));
+                        region.assign_advice(|| "limb", self.config.z[i], 0, || limb)
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            },
+        )?;
+        self.range_check(layouter.namespace(|| "range"), &limbs)?;
+        Ok(AssignedBigInt { limbs })
+    }
+
+    /// `a + b`, unsatisfiable if the sum needs more limbs.
+    pub fn add(
+        &self,
+        layouter: impl Layouter<F>,
+        a: &AssignedBigInt<F>,
+        b: &AssignedBigInt<F>,
+    ) -> Result<AssignedBigInt<F>, Error> {
+        let n = self.limbs();
+        let witness = a.value().zip(b.value()).map(|(a, b)| {
+            let (a, b) = (to_limbs(&a, n), to_limbs(&b, n));
+            let mut sum = vec![0; n];
+            let mut carries = vec![0; n - 1];
+            let mut carry = false;
+            for k in 0..n {
+                let (s, c1) = a[k].overflowing_add(b[k]);
+                let (s, c2) = s.overflowing_add(carry as u64);
+                sum[k] = s;
+                carry = c1 || c2;
+                if k < n - 1 {
+                    carries[k] = carry as u64;
+                }
+            }
+            (sum, carries)
+        });
+        let (z, _) = self.operation(layouter, "bigint add", self.config.s_add, a, b, witness)?;
+        Ok(z)
+    }
+
+    /// `a · b`, unsatisfiable if the product needs more limbs.
+    pub fn mul(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &AssignedBigInt<F>,
+        b: &AssignedBigInt<F>,
+    ) -> Result<AssignedBigInt<F>, Error> {
+        let n = self.limbs();
+        let witness = a.value().zip(b.value()).map(|(a, b)| {
+            let (x, y) = (to_limbs(&a, n), to_limbs(&b, n));
+            let mut columns = vec![BigUint::from(0u8); 2 * n - 1];
+            for i in 0..n {
+                for j in 0..n {
+                    columns[i + j] += x[i] as u128 * y[j] as u128;
+                }
+            }
+            let product = to_limbs(&(a * b), n);
+            let mut carries = vec![0u128; 2 * n - 2];
+            let mut carry = BigUint::from(0u8);
+            for (k, stored) in carries.iter_mut().enumerate() {
+                let low = if k < n { product[k] } else { 0 };
+                carry = (&columns[k] + &carry - low) >> LIMB_BITS;
+                *stored = carry
+                    .iter_u64_digits()
+                    .rev()
+                    .fold(0, |acc, digit| (acc << 64) | digit as u128);
+            }
+            (product, carries)
+        });
+        let (z, carries) = self.operation(
+            layouter.namespace(|| "row"),
+            "bigint mul",
+            self.config.s_mul,
+            a,
+            b,
+            witness,
+        )?;
+        for carry in &carries {
+            self.bytes
+                .decompose(layouter.namespace(|| "carry range"), carry, CARRY_BYTES)?;
+        }
+        Ok(z)
+    }
+
+    /// Whether `a < b`.
+    pub fn is_less_than(
+        &self,
+        layouter: impl Layouter<F>,
+        a: &AssignedBigInt<F>,
+        b: &AssignedBigInt<F>,
+    ) -> Result<AssignedBit<F>, Error> {
+        let borrows = self.compare(layouter, a, b, false)?;
+        Ok(AssignedBit::new(borrows[self.limbs() - 1].clone()))
+    }
+
+    /// Constrains `a < b`.
+    pub fn assert_less_than(
+        &self,
+        layouter: impl Layouter<F>,
+        a: &AssignedBigInt<F>,
+        b: &AssignedBigInt<F>,
+    ) -> Result<(), Error> {
+        self.compare(layouter, a, b, true)?;
+        Ok(())
+    }
+
+    /// `a mod m`, unsatisfiable if `m` is zero.
+    pub fn mod_reduce(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &AssignedBigInt<F>,
+        m: &AssignedBigInt<F>,
+    ) -> Result<AssignedBigInt<F>, Error> {
+        let (q, r) = a
+            .value()
+            .zip(m.value())
+            .map(|(a, m)| {
+                if m == BigUint::from(0u8) {
+                    (BigUint::from(0u8), a)
+                } else {
+                    (&a / &m, &a % &m)
+                }
+            })
+            .unzip();
+        let q = self.load(layouter.namespace(|| "quotient"), q)?;
+        let r = self.load(layouter.namespace(|| "remainder"), r)?;
+        let qm = self.mul(layouter.namespace(|| "q·m"), &q, m)?;
+        let sum = self.add(layouter.namespace(|| "q·m + r"), &qm, &r)?;
+        layouter.assign_region(
+            || "q·m + r = a",
+            |mut region| {
+                for (i, (lhs, rhs)) in sum.limbs.iter().zip(&a.limbs).enumerate() {
+                    let lhs = lhs.copy_advice(|| "q·m + r", &mut region, self.config.x[i], 0)?;
+                    region.constrain_equal(lhs.cell(), rhs.cell())?;
+                }
+                Ok(())
+            },
+        )?;
+        self.assert_less_than(layouter.namespace(|| "r < m"), &r, m)?;
+        Ok(r)
+    }
+
+    /// Lays out `a - b` with borrows and returns the borrow cells.
+    fn compare(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &AssignedBigInt<F>,
+        b: &AssignedBigInt<F>,
+        assert_less: bool,
+    ) -> Result<Vec<ACell<F>>, Error> {
+        let n = self.limbs();
+        let witness = a.value().zip(b.value()).map(|(a, b)| {
+            let (a, b) = (to_limbs(&a, n), to_limbs(&b, n));
+            let mut diff = vec![0; n];
+            let mut borrows = vec![0; n];
+            let mut borrow = false;
+            for k in 0..n {
+                let (d, b1) = a[k].overflowing_sub(b[k]);
+                let (d, b2) = d.overflowing_sub(borrow as u64);
+                diff[k] = d;
+                borrow = b1 || b2;
+                borrows[k] = borrow as u64;
+            }
+            (diff, borrows)
+        });
+        let (diff, borrows) = layouter.assign_region(
+            || "bigint compare",
+            |mut region| {
+                self.config.s_compare.enable(&mut region, 0)?;
+                if assert_less {
+                    self.config.s_less.enable(&mut region, 0)?;
+                }
+                self.assign_row(&mut region, a, b, &witness)
+            },
+        )?;
+        self.range_check(layouter.namespace(|| "difference range"), &diff)?;
+        Ok(borrows)
+    }
+
+    /// Lays out an add or mul row and range-checks the result limbs.
+    fn operation<C: Copy + Into<u128>>(
+        &self,
+        mut layouter: impl Layouter<F>,
+        name: &'static str,
+        selector: Selector,
+        a: &AssignedBigInt<F>,
+        b: &AssignedBigInt<F>,
+        witness: Value<(Vec<u64>, Vec<C>)>,
+    ) -> Result<(AssignedBigInt<F>, Vec<ACell<F>>), Error> {
+        let (z, carries) = layouter.assign_region(
+            || name,
+            |mut region| {
+                selector.enable(&mut region, 0)?;
+                self.assign_row(&mut region, a, b, &witness)
+            },
+        )?;
+        self.range_check(layouter.namespace(|| "result range"), &z)?;
+        Ok((AssignedBigInt { limbs: z }, carries))
+    }
+
+    fn assign_row<C: Copy + Into<u128>>(
+        &self,
+        region: &mut Region<'_, F>,
+        a: &AssignedBigInt<F>,
+        b: &AssignedBigInt<F>,
+        witness: &Value<(Vec<u64>, Vec<C>)>,
+    ) -> Result<Row<F>, Error> {
+        for (i, (a, b)) in a.limbs.iter().zip(&b.limbs).enumerate() {
+            a.copy_advice(|| "x", region, self.config.x[i], 0)?;
+            b.copy_advice(|| "y", region, self.config.y[i], 0)?;
+        }
+        let z = (0..self.limbs())
+            .map(|i| {
+                let z_i = witness.as_ref().map(|(z, _)| F::from(z[i]));
+                region.assign_advice(|| "z", self.config.z[i], 0, || z_i)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut carries = vec![];
+        for (i, column) in self.config.carries.iter().enumerate() {
+            let c_i = witness
+                .as_ref()
+                .map(|(_, c)| c.get(i).map_or(F::zero(), |c| F::from_u128((*c).into())));
+            carries.push(region.assign_advice(|| "carry", *column, 0, || c_i)?);
+        }
+        Ok((z, carries))
+    }
+
+    fn range_check(&self, mut layouter: impl Layouter<F>, limbs: &[ACell<F>]) -> Result<(), Error> {
+        for limb in limbs {
+            self.bytes
+                .decompose(layouter.namespace(|| "limb range"), limb, 8)?;
+        }
+        Ok(())
+    }
+}
+
+fn query<F: FieldExt>(
+    meta: &mut VirtualCells<'_, F>,
+    columns: &[Column<Advice>],
+) -> Vec<Expression<F>> {
+    columns
+        .iter()
+        .map(|&column| expr::advice(meta, column, 0))
+        .collect()
+}
+
+/// The low `n` limbs of `x`, least significant first.
+pub fn to_limbs(x: &BigUint, n: usize) -> Vec<u64> {
+    let mut limbs = x.to_u64_digits();
+    limbs.resize(n, 0);
+    limbs
+}
+
+pub fn from_limbs(limbs: &[u64]) -> BigUint {
+    limbs
+        .iter()
+        .rev()
+        .fold(BigUint::from(0u8), |acc, limb| (acc << LIMB_BITS) + limb)
+}
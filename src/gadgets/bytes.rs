@@ -0,0 +1,168 @@
+//! Decomposing a cell into little-endian bytes.
+//!
+//! Each byte is looked up in a shared 256-row [`ByteTable`], and a running
+//! sum ties the bytes to the value: row `i` holds `z_i = x >> 8i` and byte
+//! `b_i`, with `z_i = b_i + 256·z_{i+1}` and `z_n = 0`. Any number of
+//! [`BytesChip`]s can share one table; load it once per circuit.
+
+use std::marker::PhantomData;
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Layouter, Value},
+    plonk::{Advice, Column, ConstraintSystem, Error, Selector, TableColumn},
+    poly::Rotation,
+};
+
+use crate::fibonacci::ACell;
+
+/// Bytes in the largest decomposition, keeping `256^n` below the modulus so
+/// the running sum cannot wrap.
+pub const MAX_BYTES: usize = 31;
+
+/// The values `0..256` in a lookup table column.
+#[derive(Debug, Clone, Copy)]
+pub struct ByteTable {
+    pub column: TableColumn,
+}
+
+impl ByteTable {
+    pub fn configure<F: FieldExt>(meta: &mut ConstraintSystem<F>) -> Self {
+        ByteTable {
+            column: meta.lookup_table_column(),
+        }
+    }
+
+    pub fn load<F: FieldExt>(&self, mut layouter: impl Layouter<F>) -> Result<(), Error> {
+        layouter.assign_table(
+            || "byte table",
+            |mut table| {
+                for byte in 0..256 {
+                    table.assign_cell(
+                        || "byte",
+                        self.column,
+                        byte,
+                        || Value::known(F::from(byte as u64)),
+                    )?;
+                }
+                Ok(())
+            },
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BytesConfig {
+    /// The running sum `z_i`.
+    pub z: Column<Advice>,
+    pub byte: Column<Advice>,
+    /// Enabled on rows `0..n`.
+    pub q_byte: Selector,
+    /// Enabled on row `n`.
+    pub s_end: Selector,
+    pub table: ByteTable,
+}
+
+#[derive(Debug)]
+pub struct BytesChip<F: FieldExt> {
+    config: BytesConfig,
+    marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> BytesChip<F> {
+    pub fn construct(config: BytesConfig) -> Self {
+        Self {
+            config,
+            marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        z: Column<Advice>,
+        byte: Column<Advice>,
+        table: ByteTable,
+    ) -> BytesConfig {
+        meta.enable_equality(z);
+        meta.enable_equality(byte);
+
+        let q_byte = meta.complex_selector();
+        let s_end = meta.selector();
+
+        meta.create_gate("byte decomposition", |meta| {
+            //
+            //  z     | byte | q_byte
+            //  z_i   | b_i  |   q
+            //  z_i+1 |      |
+            //
+            let z_cur = meta.query_advice(z, Rotation::cur());
+            let z_next = meta.query_advice(z, Rotation::next());
+            let byte = meta.query_advice(byte, Rotation::cur());
+            let q = meta.query_selector(q_byte);
+
+            vec![q * (z_cur - byte - z_next * F::from(256))]
+        });
+
+        meta.lookup(|meta| {
+            let byte = meta.query_advice(byte, Rotation::cur());
+            let q = meta.query_selector(q_byte);
+
+            vec![(q * byte, table.column)]
+        });
+
+        meta.create_gate("byte decomposition end", |meta| {
+            let z = meta.query_advice(z, Rotation::cur());
+            let s = meta.query_selector(s_end);
+
+            vec![s * z]
+        });
+
+        BytesConfig {
+            z,
+            byte,
+            q_byte,
+            s_end,
+            table,
+        }
+    }
+
+    /// Decomposes `x` into `n` bytes, least significant first. The circuit
+    /// is unsatisfiable if `x` does not fit in `n` bytes.
+    pub fn decompose(
+        &self,
+        mut layouter: impl Layouter<F>,
+        x: &ACell<F>,
+        n: usize,
+    ) -> Result<Vec<ACell<F>>, Error> {
+        assert!(n <= MAX_BYTES, "at most {} bytes", MAX_BYTES);
+        let repr = x.value().map(|x| x.to_repr());
+
+        layouter.assign_region(
+            || "byte decomposition",
+            |mut region| {
+                x.copy_advice(|| "z_0", &mut region, self.config.z, 0)?;
+                let mut bytes = vec![];
+                for row in 0..n {
+                    self.config.q_byte.enable(&mut region, row)?;
+                    let byte = repr.as_ref().map(|repr| F::from(repr.as_ref()[row] as u64));
+                    bytes.push(region.assign_advice(|| "byte", self.config.byte, row, || byte)?);
+                    if row > 0 {
+                        let z = repr.as_ref().map(|repr| high_part::<F>(repr.as_ref(), row));
+                        region.assign_advice(|| "z", self.config.z, row, || z)?;
+                    }
+                }
+                self.config.s_end.enable(&mut region, n)?;
+                region.assign_advice(|| "z_n", self.config.z, n, || Value::known(F::zero()))?;
+
+                Ok(bytes)
+            },
+        )
+    }
+}
+
+/// `x >> 8·skip` from the little-endian bytes of `x`.
+fn high_part<F: FieldExt>(bytes: &[u8], skip: usize) -> F {
+    bytes[skip..].iter().rev().fold(F::zero(), |acc, byte| {
+        acc * F::from(256) + F::from(*byte as u64)
+    })
+}
@@ -0,0 +1,113 @@
+//! A comparator for sorting networks: `(a, b) -> (min, max)`.
+//!
+//! One row `a | b | swap | lo | hi | diff` combines a two-way mux and a
+//! comparison. With `swap` boolean, `lo = a + swap·(b - a)` picks one of
+//! the inputs and `hi = a + b - lo` the other, and the embedded
+//! [`U64Chip`] range-checks `diff = hi - lo` to 64 bits, so `lo <= hi` as
+//! integers. For inputs below `2^64` only the ordered pair satisfies both:
+//! the wrong `swap` makes `diff` negative, which wraps far above `2^64`.
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::Layouter,
+    plonk::{Advice, Column, ConstraintSystem, Error, Selector},
+};
+
+use super::u64::{U64Chip, U64Config};
+use crate::{expr, fibonacci::ACell};
+
+#[derive(Debug, Clone)]
+pub struct CompareSwapConfig {
+    /// `a, b, swap, lo, hi, diff`.
+    pub advice: [Column<Advice>; 6],
+    pub selector: Selector,
+    pub range: U64Config,
+}
+
+#[derive(Debug)]
+pub struct CompareSwapChip<F: FieldExt> {
+    config: CompareSwapConfig,
+    range: U64Chip<F>,
+}
+
+impl<F: FieldExt> CompareSwapChip<F> {
+    pub fn construct(config: CompareSwapConfig) -> Self {
+        Self {
+            range: U64Chip::construct(config.range.clone()),
+            config,
+        }
+    }
+
+    /// `range` is the running-sum column of the range check on `diff`.
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 6],
+        range: Column<Advice>,
+    ) -> CompareSwapConfig {
+        for column in advice {
+            meta.enable_equality(column);
+        }
+
+        let selector = meta.selector();
+
+        meta.create_gate("compare swap", |meta| {
+            //
+            //  a | b | swap | lo | hi | diff | selector
+            //  a | b |  t   | lo | hi |  d   |    s
+            //
+            let [a, b, t, lo, hi, diff] = expr::advice_row(meta, advice);
+            let s = meta.query_selector(selector);
+            vec![
+                s.clone() * expr::bool_check(t.clone()),
+                s.clone() * (lo.clone() - a.clone() - t * (b.clone() - a.clone())),
+                s.clone() * (hi.clone() - a - b + lo.clone()),
+                s * (diff - hi + lo),
+            ]
+        });
+
+        CompareSwapConfig {
+            advice,
+            selector,
+            range: U64Chip::configure(meta, range),
+        }
+    }
+
+    /// `(min(a, b), max(a, b))`. The circuit is unsatisfiable if the two
+    /// differ by `2^64` or more.
+    pub fn compare_swap(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &ACell<F>,
+        b: &ACell<F>,
+    ) -> Result<(ACell<F>, ACell<F>), Error> {
+        let [col_a, col_b, col_swap, col_lo, col_hi, col_diff] = self.config.advice;
+        let pair = a.value().zip(b.value()).map(|(a, b)| {
+            let swap = a.get_lower_128() > b.get_lower_128();
+            let (lo, hi) = if swap { (*b, *a) } else { (*a, *b) };
+            (F::from(swap as u64), lo, hi)
+        });
+
+        let (lo, hi, diff) = layouter.assign_region(
+            || "compare swap",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+                a.copy_advice(|| "a", &mut region, col_a, 0)?;
+                b.copy_advice(|| "b", &mut region, col_b, 0)?;
+                region.assign_advice(|| "swap", col_swap, 0, || pair.map(|(t, _, _)| t))?;
+                let lo = region.assign_advice(|| "lo", col_lo, 0, || pair.map(|(_, lo, _)| lo))?;
+                let hi = region.assign_advice(|| "hi", col_hi, 0, || pair.map(|(_, _, hi)| hi))?;
+                let diff = region.assign_advice(
+                    || "diff",
+                    col_diff,
+                    0,
+                    || pair.map(|(_, lo, hi)| hi - lo),
+                )?;
+                Ok((lo, hi, diff))
+            },
+        )?;
+        self.range
+            .range_check(layouter.namespace(|| "hi - lo"), &diff)?;
+
+        Ok((lo, hi))
+    }
+}
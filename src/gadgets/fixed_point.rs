@@ -0,0 +1,342 @@
+//! Unsigned fixed-point numbers: a value `x` is the integer `x·2^32` in a
+//! cell, range-checked to 64 bits with [`BytesChip`], so `0 <= x < 2^32`
+//! with a resolution of `2^-32`.
+//!
+//! One row per operation, with the range checks in their own regions:
+//!
+//! ```text
+//!   a | b | c | r | selector
+//!   x | y | z | r |    s
+//! ```
+//!
+//! - add: `x + y = z`
+//! - mul: `x·y + 2^31 = z·2^32 + r`, `r < 2^32`, so `z` is the product
+//!   rounded to the nearest representable value.
+//! - div: `x·2^32 = z·y + r`, `r < y`, so `z` is the quotient rounded
+//!   down; unsatisfiable for `y = 0`.
+//! - less: `y - x - 1 = z` with `z` range-checked, so `x < y`.
+//!
+//! Every product stays below `2^128`, so the identities hold over the
+//! integers.
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Layouter, Value},
+    plonk::{Advice, Column, ConstraintSystem, Error, Fixed, Instance, Selector},
+};
+
+use super::bytes::{BytesChip, BytesConfig};
+use crate::{expr, fibonacci::ACell};
+
+/// Fractional bits.
+pub const SCALE_BITS: u32 = 32;
+/// The raw representation of `1`.
+pub const ONE: u64 = 1 << SCALE_BITS;
+
+/// Bytes in a value's range check.
+const VALUE_BYTES: usize = 8;
+/// Bytes in a rounding remainder's range check.
+const REMAINDER_BYTES: usize = SCALE_BITS as usize / 8;
+
+/// A range-checked fixed-point value.
+#[derive(Debug, Clone)]
+pub struct AssignedFixed<F: FieldExt>(ACell<F>);
+
+impl<F: FieldExt> AssignedFixed<F> {
+    pub fn cell(&self) -> &ACell<F> {
+        &self.0
+    }
+
+    /// The raw value `x·2^32`.
+    pub fn raw(&self) -> Value<u64> {
+        self.0.value().map(|x| x.get_lower_128() as u64)
+    }
+
+    pub fn value(&self) -> Value<f64> {
+        self.raw().map(from_raw)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FixedPointConfig {
+    pub a: Column<Advice>,
+    pub b: Column<Advice>,
+    pub c: Column<Advice>,
+    pub r: Column<Advice>,
+    /// Holds the constants of [`FixedPointChip::constant`].
+    pub constant: Column<Fixed>,
+    pub s_add: Selector,
+    pub s_mul: Selector,
+    pub s_div: Selector,
+    pub s_less: Selector,
+    pub bytes: BytesConfig,
+}
+
+#[derive(Debug)]
+pub struct FixedPointChip<F: FieldExt> {
+    config: FixedPointConfig,
+    bytes: BytesChip<F>,
+}
+
+impl<F: FieldExt> FixedPointChip<F> {
+    pub fn construct(config: FixedPointConfig) -> Self {
+        Self {
+            bytes: BytesChip::construct(config.bytes.clone()),
+            config,
+        }
+    }
+
+    /// `bytes` does the range checks; its table has to be loaded by the
+    /// circuit.
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 4],
+        constant: Column<Fixed>,
+        bytes: BytesConfig,
+    ) -> FixedPointConfig {
+        let [a, b, c, r] = advice;
+        for column in advice {
+            meta.enable_equality(column);
+        }
+        meta.enable_constant(constant);
+
+        let s_add = meta.selector();
+        let s_mul = meta.selector();
+        let s_div = meta.selector();
+        let s_less = meta.selector();
+
+        let one = expr::constant(ONE);
+        let half = expr::constant(ONE / 2);
+
+        meta.create_gate("fixed add", |meta| {
+            let [x, y, z] = expr::advice_row(meta, [a, b, c]);
+            let s = meta.query_selector(s_add);
+
+            vec![s * (x + y - z)]
+        });
+
+        meta.create_gate("fixed mul", |meta| {
+            let [x, y, z, r] = expr::advice_row(meta, advice);
+            let s = meta.query_selector(s_mul);
+
+            vec![s * (x * y + half - z * one.clone() - r)]
+        });
+
+        meta.create_gate("fixed div", |meta| {
+            let [x, y, z, r] = expr::advice_row(meta, advice);
+            let s = meta.query_selector(s_div);
+
+            vec![s * (x * one.clone() - z * y - r)]
+        });
+
+        meta.create_gate("fixed less", |meta| {
+            let [x, y, z] = expr::advice_row(meta, [a, b, c]);
+            let s = meta.query_selector(s_less);
+
+            vec![s * (y - x - expr::constant(1) - z)]
+        });
+
+        FixedPointConfig {
+            a,
+            b,
+            c,
+            r,
+            constant,
+            s_add,
+            s_mul,
+            s_div,
+            s_less,
+            bytes,
+        }
+    }
+
+    /// Assigns the raw value `raw` and range-checks it.
+    pub fn load(
+        &self,
+        mut layouter: impl Layouter<F>,
+        raw: Value<u64>,
+    ) -> Result<AssignedFixed<F>, Error> {
+        let x = layouter.assign_region(
+            || "load fixed",
+            |mut region| region.assign_advice(|| "x", self.config.c, 0, || raw.map(F::from)),
+        )?;
+        self.range_check(layouter.namespace(|| "range"), &x, VALUE_BYTES)?;
+        Ok(AssignedFixed(x))
+    }
+
+    /// Copies the raw value in `row` of `instance`, which needs equality
+    /// enabled, and range-checks it.
+    pub fn load_instance(
+        &self,
+        mut layouter: impl Layouter<F>,
+        instance: Column<Instance>,
+        row: usize,
+    ) -> Result<AssignedFixed<F>, Error> {
+        let x = layouter.assign_region(
+            || "load fixed instance",
+            |mut region| {
+                region.assign_advice_from_instance(|| "x", instance, row, self.config.c, 0)
+            },
+        )?;
+        self.range_check(layouter.namespace(|| "range"), &x, VALUE_BYTES)?;
+        Ok(AssignedFixed(x))
+    }
+
+    /// Assigns the raw value `raw` as a constant of the circuit.
+    pub fn constant(
+        &self,
+        mut layouter: impl Layouter<F>,
+        raw: u64,
+    ) -> Result<AssignedFixed<F>, Error> {
+        layouter
+            .assign_region(
+                || "fixed constant",
+                |mut region| {
+                    region.assign_advice_from_constant(|| "x", self.config.c, 0, F::from(raw))
+                },
+            )
+            .map(AssignedFixed)
+    }
+
+    /// `x + y`, unsatisfiable if the sum reaches `2^32`.
+    pub fn add(
+        &self,
+        layouter: impl Layouter<F>,
+        x: &AssignedFixed<F>,
+        y: &AssignedFixed<F>,
+    ) -> Result<AssignedFixed<F>, Error> {
+        let z = x.raw().zip(y.raw()).map(|(x, y)| x.wrapping_add(y));
+        let (z, _) = self.operation(layouter, "fixed add", self.config.s_add, x, y, z, None)?;
+        Ok(AssignedFixed(z))
+    }
+
+    /// `x · y`, rounded to the nearest value.
+    pub fn mul(
+        &self,
+        mut layouter: impl Layouter<F>,
+        x: &AssignedFixed<F>,
+        y: &AssignedFixed<F>,
+    ) -> Result<AssignedFixed<F>, Error> {
+        let witness = x.raw().zip(y.raw()).map(|(x, y)| {
+            let product = x as u128 * y as u128 + (ONE / 2) as u128;
+            (
+                (product >> SCALE_BITS) as u64,
+                (product % ONE as u128) as u64,
+            )
+        });
+        let (z, r) = witness.unzip();
+        let (z, r) = self.operation(
+            layouter.namespace(|| "row"),
+            "fixed mul",
+            self.config.s_mul,
+            x,
+            y,
+            z,
+            Some(r),
+        )?;
+        self.range_check(
+            layouter.namespace(|| "remainder range"),
+            &r.unwrap(),
+            REMAINDER_BYTES,
+        )?;
+        Ok(AssignedFixed(z))
+    }
+
+    /// `x / y`, rounded down.
+    pub fn div(
+        &self,
+        mut layouter: impl Layouter<F>,
+        x: &AssignedFixed<F>,
+        y: &AssignedFixed<F>,
+    ) -> Result<AssignedFixed<F>, Error> {
+        let witness = x.raw().zip(y.raw()).map(|(x, y)| {
+            let scaled = (x as u128) << SCALE_BITS;
+            match y {
+                0 => (0, 0),
+                y => ((scaled / y as u128) as u64, (scaled % y as u128) as u64),
+            }
+        });
+        let (z, r) = witness.unzip();
+        let (z, r) = self.operation(
+            layouter.namespace(|| "row"),
+            "fixed div",
+            self.config.s_div,
+            x,
+            y,
+            z,
+            Some(r),
+        )?;
+        let r = AssignedFixed(r.unwrap());
+        self.range_check(
+            layouter.namespace(|| "remainder range"),
+            r.cell(),
+            VALUE_BYTES,
+        )?;
+        self.assert_less_than(layouter.namespace(|| "remainder < y"), &r, y)?;
+        Ok(AssignedFixed(z))
+    }
+
+    /// Constrains `x < y`.
+    pub fn assert_less_than(
+        &self,
+        layouter: impl Layouter<F>,
+        x: &AssignedFixed<F>,
+        y: &AssignedFixed<F>,
+    ) -> Result<(), Error> {
+        let d = x
+            .raw()
+            .zip(y.raw())
+            .map(|(x, y)| y.wrapping_sub(x).wrapping_sub(1));
+        self.operation(layouter, "fixed less", self.config.s_less, x, y, d, None)?;
+        Ok(())
+    }
+
+    /// Lays out one operation row and range-checks `z`. The remainder cell
+    /// is assigned when `r` is given, and left to the caller to check.
+    #[allow(clippy::too_many_arguments)]
+    fn operation(
+        &self,
+        mut layouter: impl Layouter<F>,
+        name: &'static str,
+        selector: Selector,
+        x: &AssignedFixed<F>,
+        y: &AssignedFixed<F>,
+        z: Value<u64>,
+        r: Option<Value<u64>>,
+    ) -> Result<(ACell<F>, Option<ACell<F>>), Error> {
+        let (z, r) = layouter.assign_region(
+            || name,
+            |mut region| {
+                selector.enable(&mut region, 0)?;
+                x.0.copy_advice(|| "x", &mut region, self.config.a, 0)?;
+                y.0.copy_advice(|| "y", &mut region, self.config.b, 0)?;
+                let z = region.assign_advice(|| "z", self.config.c, 0, || z.map(F::from))?;
+                let r = r
+                    .map(|r| region.assign_advice(|| "r", self.config.r, 0, || r.map(F::from)))
+                    .transpose()?;
+                Ok((z, r))
+            },
+        )?;
+        self.range_check(layouter.namespace(|| "result range"), &z, VALUE_BYTES)?;
+        Ok((z, r))
+    }
+
+    fn range_check(
+        &self,
+        layouter: impl Layouter<F>,
+        x: &ACell<F>,
+        bytes: usize,
+    ) -> Result<(), Error> {
+        self.bytes.decompose(layouter, x, bytes)?;
+        Ok(())
+    }
+}
+
+/// The raw value nearest to `x`, saturating outside `0..2^32`.
+pub fn to_raw(x: f64) -> u64 {
+    (x * ONE as f64).round() as u64
+}
+
+pub fn from_raw(raw: u64) -> f64 {
+    raw as f64 / ONE as f64
+}
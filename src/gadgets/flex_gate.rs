@@ -0,0 +1,245 @@
+//! A single universal gate over one advice column, in the style of
+//! halo2-base's `FlexGateConfig`.
+//!
+//! Where the other chips write one custom gate per operation, this one has
+//! a single gate, enabled on the first of four consecutive cells:
+//!
+//! ```text
+//!  advice | selector
+//!    a    |    s
+//!    b    |
+//!    c    |
+//!    d    |          s · (a + b·c - d) = 0
+//! ```
+//!
+//! Additions are `a + b·1` and multiplications `0 + b·c`, with the
+//! constants copied in from a fixed column. Operations are recorded on a
+//! [`GateBuilder`] and laid out together by [`FlexGateChip::assign`] in one
+//! region. When an operation's `a` is the result of the one before it, the
+//! gate starts on that cell rather than on a copy of it, so a chain costs
+//! three rows per operation. A circuit pays for the generality in rows and
+//! copy constraints, and in return has one gate of degree 3 whatever it
+//! computes.
+
+use std::{fmt, marker::PhantomData};
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Layouter, Value},
+    plonk::{Advice, Column, ConstraintSystem, Error, Fixed, Selector},
+    poly::Rotation,
+};
+
+use crate::{
+    fibonacci::{columns, selector, ACell},
+    rows::RowCursor,
+};
+
+/// An input to an operation on a [`GateBuilder`].
+#[derive(Debug, Clone)]
+pub enum QuantumCell<F: FieldExt> {
+    /// The cell at this offset of the builder, copied.
+    Offset(usize),
+    /// A cell assigned in another region, copied.
+    Existing(ACell<F>),
+    Witness(Value<F>),
+    /// Copied from the constants column.
+    Constant(F),
+}
+
+#[derive(Debug, Clone)]
+struct Entry<F: FieldExt> {
+    cell: QuantumCell<F>,
+    value: Value<F>,
+    /// Whether the gate starts on this cell.
+    gate: bool,
+}
+
+/// The cells of a run of operations, in the order they go down the column.
+#[derive(Debug, Clone)]
+pub struct GateBuilder<F: FieldExt> {
+    entries: Vec<Entry<F>>,
+}
+
+impl<F: FieldExt> Default for GateBuilder<F> {
+    fn default() -> Self {
+        Self { entries: vec![] }
+    }
+}
+
+impl<F: FieldExt> GateBuilder<F> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rows the cells take.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The value of the cell at `offset`.
+    pub fn value(&self, offset: usize) -> Value<F> {
+        self.entries[offset].value
+    }
+
+    fn value_of(&self, cell: &QuantumCell<F>) -> Value<F> {
+        match cell {
+            QuantumCell::Offset(offset) => self.value(*offset),
+            QuantumCell::Existing(cell) => cell.value().copied(),
+            QuantumCell::Witness(value) => *value,
+            QuantumCell::Constant(value) => Value::known(*value),
+        }
+    }
+
+    /// Appends `cell` outside any gate and returns its offset.
+    pub fn push(&mut self, cell: QuantumCell<F>) -> usize {
+        let value = self.value_of(&cell);
+        self.entries.push(Entry {
+            cell,
+            value,
+            gate: false,
+        });
+        self.entries.len() - 1
+    }
+
+    /// `a + b·c`, returning the offset of the result.
+    pub fn mul_add(&mut self, a: QuantumCell<F>, b: QuantumCell<F>, c: QuantumCell<F>) -> usize {
+        let d = self.value_of(&a) + self.value_of(&b) * self.value_of(&c);
+        let start = match a {
+            QuantumCell::Offset(offset) if offset + 1 == self.len() => offset,
+            a => self.push(a),
+        };
+        self.entries[start].gate = true;
+        self.push(b);
+        self.push(c);
+        self.push(QuantumCell::Witness(d))
+    }
+
+    pub fn add(&mut self, a: QuantumCell<F>, b: QuantumCell<F>) -> usize {
+        self.mul_add(a, b, QuantumCell::Constant(F::one()))
+    }
+
+    pub fn mul(&mut self, a: QuantumCell<F>, b: QuantumCell<F>) -> usize {
+        self.mul_add(QuantumCell::Constant(F::zero()), a, b)
+    }
+}
+
+#[derive(Clone)]
+pub struct FlexGateConfig {
+    advice: Column<Advice>,
+    selector: Selector,
+    constants: Column<Fixed>,
+}
+
+#[derive(Debug)]
+pub struct FlexGateChip<F: FieldExt> {
+    config: FlexGateConfig,
+    marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> FlexGateChip<F> {
+    pub fn construct(config: FlexGateConfig) -> Self {
+        Self {
+            config,
+            marker: PhantomData,
+        }
+    }
+
+    /// Enables equality on `advice` and takes `constants` as a constants
+    /// column.
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: Column<Advice>,
+        constants: Column<Fixed>,
+    ) -> FlexGateConfig {
+        let selector = meta.selector();
+        meta.enable_equality(advice);
+        meta.enable_constant(constants);
+
+        meta.create_gate("a + b·c = d", |meta| {
+            let [a, b, c, d] = [0, 1, 2, 3].map(|i| meta.query_advice(advice, Rotation(i)));
+            let s = meta.query_selector(selector);
+            vec![s * (a + b * c - d)]
+        });
+
+        FlexGateConfig {
+            advice,
+            selector,
+            constants,
+        }
+    }
+
+    /// Lays out `builder`'s cells in one region, enabling the gate where
+    /// its operations start, and returns them by offset.
+    pub fn assign(
+        &self,
+        mut layouter: impl Layouter<F>,
+        builder: &GateBuilder<F>,
+    ) -> Result<Vec<ACell<F>>, Error> {
+        let advice = self.config.advice;
+        layouter.assign_region(
+            || "flex gate",
+            |mut region| {
+                let mut rows = RowCursor::new(&mut region);
+                let mut cells: Vec<ACell<F>> = Vec::with_capacity(builder.len());
+                for (offset, entry) in builder.entries.iter().enumerate() {
+                    if offset > 0 {
+                        rows.advance();
+                    }
+                    if entry.gate {
+                        rows.enable(self.config.selector)?;
+                    }
+                    let cell = match &entry.cell {
+                        QuantumCell::Offset(offset) => {
+                            rows.copy_advice("copy", &cells[*offset], advice)?
+                        }
+                        QuantumCell::Existing(cell) => {
+                            rows.copy_advice("existing", cell, advice)?
+                        }
+                        QuantumCell::Witness(value) => {
+                            rows.assign_advice("witness", advice, *value)?
+                        }
+                        QuantumCell::Constant(value) => {
+                            let row = rows.row();
+                            rows.region().assign_advice_from_constant(
+                                || "constant",
+                                advice,
+                                row,
+                                *value,
+                            )?
+                        }
+                    };
+                    cells.push(cell);
+                }
+                Ok(cells)
+            },
+        )
+    }
+}
+
+/// Prints the columns, e.g. `advice A0, selector S0, constants F0`.
+impl fmt::Display for FlexGateConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "advice {}, selector {}, constants {}",
+            columns(&[self.advice]),
+            selector(&self.selector),
+            columns(&[self.constants])
+        )
+    }
+}
+
+impl fmt::Debug for FlexGateConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FlexGateConfig")
+            .field("advice", &format_args!("{}", columns(&[self.advice])))
+            .field("selector", &format_args!("{}", selector(&self.selector)))
+            .field("constants", &format_args!("{}", columns(&[self.constants])))
+            .finish()
+    }
+}
@@ -0,0 +1,109 @@
+//! `b` is a permutation of `a`, by a running product over a challenge.
+//!
+//! For a challenge `γ`, `∏ (a_i + γ) = ∏ (b_i + γ)` holds as polynomials in
+//! `γ` exactly when the two lists are the same multiset, and otherwise at
+//! no more than `n` values of `γ`. Row `i` holds `a_i | b_i | γ | z_i` with
+//! `z_{i+1}·(b_i + γ) = z_i·(a_i + γ)`, and `z_0 = z_n = 1` are constants.
+//!
+//! The argument is only sound if `γ` is unpredictable when the lists are
+//! fixed. halo2_proofs 0.2 has no verifier challenges to draw it from
+//! inside the proof, so here it is an input cell, and the caller decides
+//! where it comes from, such as a public input the verifier picks after
+//! seeing a commitment to the lists. halo2's own copy constraints are a
+//! permutation argument of this kind, over the cells instead of values.
+
+use std::marker::PhantomData;
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Layouter, Value},
+    plonk::{Advice, Column, ConstraintSystem, Error, Selector},
+    poly::Rotation,
+};
+
+use crate::{expr, fibonacci::ACell};
+
+#[derive(Debug, Clone)]
+pub struct GrandProductConfig {
+    /// `a, b, γ, z`.
+    pub advice: [Column<Advice>; 4],
+    pub selector: Selector,
+}
+
+#[derive(Debug)]
+pub struct GrandProductChip<F: FieldExt> {
+    config: GrandProductConfig,
+    marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> GrandProductChip<F> {
+    pub fn construct(config: GrandProductConfig) -> Self {
+        Self {
+            config,
+            marker: PhantomData,
+        }
+    }
+
+    /// Needs a fixed column enabled for constants.
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 4],
+    ) -> GrandProductConfig {
+        for column in advice {
+            meta.enable_equality(column);
+        }
+
+        let selector = meta.selector();
+
+        meta.create_gate("grand product", |meta| {
+            //
+            //  a   | b   | γ | z     | selector
+            //  a_i | b_i | γ | z_i   |    s
+            //      |     |   | z_i+1 |
+            //
+            let [a, b, gamma, z] = expr::advice_row(meta, advice);
+            let z_next = meta.query_advice(advice[3], Rotation::next());
+            let s = meta.query_selector(selector);
+
+            vec![s * (z_next * (b + gamma.clone()) - z * (a + gamma))]
+        });
+
+        GrandProductConfig { advice, selector }
+    }
+
+    /// Constrains `b` to be a permutation of `a` for the challenge `gamma`.
+    pub fn check(
+        &self,
+        mut layouter: impl Layouter<F>,
+        gamma: &ACell<F>,
+        a: &[ACell<F>],
+        b: &[ACell<F>],
+    ) -> Result<(), Error> {
+        assert_eq!(a.len(), b.len(), "lists of different lengths");
+        let [col_a, col_b, col_gamma, col_z] = self.config.advice;
+
+        layouter.assign_region(
+            || "grand product",
+            |mut region| {
+                let mut z = region.assign_advice_from_constant(|| "z_0", col_z, 0, F::one())?;
+                for (row, (a, b)) in a.iter().zip(b).enumerate() {
+                    self.config.selector.enable(&mut region, row)?;
+                    a.copy_advice(|| "a", &mut region, col_a, row)?;
+                    b.copy_advice(|| "b", &mut region, col_b, row)?;
+                    gamma.copy_advice(|| "γ", &mut region, col_gamma, row)?;
+
+                    let ratio =
+                        a.value()
+                            .zip(b.value())
+                            .zip(gamma.value())
+                            .map(|((a, b), gamma)| {
+                                (*a + gamma) * (*b + gamma).invert().unwrap_or(F::zero())
+                            });
+                    let next: Value<F> = z.value().copied() * ratio;
+                    z = region.assign_advice(|| "z", col_z, row + 1, || next)?;
+                }
+                region.constrain_constant(z.cell(), F::one())
+            },
+        )
+    }
+}
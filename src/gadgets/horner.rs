@@ -0,0 +1,127 @@
+//! Polynomial evaluation by Horner's rule, one step per row.
+//!
+//! The coefficients are in a fixed column, highest degree first, so the
+//! polynomial is part of the circuit rather than the witness. Row `i`
+//! holds the accumulator after `i + 1` coefficients:
+//!
+//! ```text
+//!   acc                   | x | coeff | s_first | s_step
+//!   c_0                   | x |  c_0  |    1    |
+//!   c_0·x + c_1           | x |  c_1  |         |   1
+//!   (c_0·x + c_1)·x + c_2 | x |  c_2  |         |   1
+//! ```
+//!
+//! and every row's `x` is copied from the evaluation point.
+
+use std::marker::PhantomData;
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Layouter, Value},
+    plonk::{Advice, Column, ConstraintSystem, Error, Fixed, Selector},
+    poly::Rotation,
+};
+
+use crate::fibonacci::ACell;
+
+#[derive(Debug, Clone)]
+pub struct HornerConfig {
+    pub acc: Column<Advice>,
+    pub x: Column<Advice>,
+    pub coeff: Column<Fixed>,
+    pub s_first: Selector,
+    pub s_step: Selector,
+}
+
+#[derive(Debug)]
+pub struct HornerChip<F: FieldExt> {
+    config: HornerConfig,
+    marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> HornerChip<F> {
+    pub fn construct(config: HornerConfig) -> Self {
+        Self {
+            config,
+            marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        acc: Column<Advice>,
+        x: Column<Advice>,
+        coeff: Column<Fixed>,
+    ) -> HornerConfig {
+        meta.enable_equality(acc);
+        meta.enable_equality(x);
+
+        let s_first = meta.selector();
+        let s_step = meta.selector();
+
+        meta.create_gate("horner first", |meta| {
+            let acc = meta.query_advice(acc, Rotation::cur());
+            let coeff = meta.query_fixed(coeff, Rotation::cur());
+            let s = meta.query_selector(s_first);
+
+            vec![s * (acc - coeff)]
+        });
+
+        meta.create_gate("horner step", |meta| {
+            let prev = meta.query_advice(acc, Rotation::prev());
+            let x = meta.query_advice(x, Rotation::prev());
+            let acc = meta.query_advice(acc, Rotation::cur());
+            let coeff = meta.query_fixed(coeff, Rotation::cur());
+            let s = meta.query_selector(s_step);
+
+            vec![s * (acc - (prev * x + coeff))]
+        });
+
+        HornerConfig {
+            acc,
+            x,
+            coeff,
+            s_first,
+            s_step,
+        }
+    }
+
+    /// Evaluates the polynomial with `coeffs`, highest degree first, at
+    /// `x`.
+    pub fn evaluate(
+        &self,
+        mut layouter: impl Layouter<F>,
+        coeffs: &[F],
+        x: &ACell<F>,
+    ) -> Result<ACell<F>, Error> {
+        assert!(
+            !coeffs.is_empty(),
+            "a polynomial has at least one coefficient"
+        );
+        layouter.assign_region(
+            || "horner",
+            |mut region| {
+                let mut acc = Value::known(F::zero());
+                let mut cell = None;
+                for (row, coeff) in coeffs.iter().enumerate() {
+                    if row == 0 {
+                        self.config.s_first.enable(&mut region, row)?;
+                    } else {
+                        self.config.s_step.enable(&mut region, row)?;
+                    }
+                    region.assign_fixed(
+                        || "coeff",
+                        self.config.coeff,
+                        row,
+                        || Value::known(*coeff),
+                    )?;
+                    x.copy_advice(|| "x", &mut region, self.config.x, row)?;
+
+                    acc = acc * x.value() + Value::known(*coeff);
+                    cell = Some(region.assign_advice(|| "acc", self.config.acc, row, || acc)?);
+                }
+                Ok(cell.unwrap())
+            },
+        )
+    }
+}
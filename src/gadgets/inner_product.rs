@@ -0,0 +1,153 @@
+//! `⟨a, b⟩` with a running accumulator, one product per row.
+//!
+//! ```text
+//!   a   | b   | acc                 | s_first | s_step
+//!   a_0 | b_0 | a_0·b_0             |    1    |
+//!   a_1 | b_1 | a_0·b_0 + a_1·b_1   |         |   1
+//!   ...
+//! ```
+//!
+//! The last accumulator is the inner product.
+
+use std::marker::PhantomData;
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Layouter, Value},
+    plonk::{Advice, Column, ConstraintSystem, Error, Selector},
+    poly::Rotation,
+};
+
+use super::simple::Operand;
+use crate::{expr, fibonacci::ACell};
+
+#[derive(Debug, Clone)]
+pub struct InnerProductConfig {
+    pub a: Column<Advice>,
+    pub b: Column<Advice>,
+    pub acc: Column<Advice>,
+    pub s_first: Selector,
+    pub s_step: Selector,
+}
+
+#[derive(Debug)]
+pub struct InnerProductChip<F: FieldExt> {
+    config: InnerProductConfig,
+    marker: PhantomData<F>,
+}
+
+/// The cells of a laid out inner product.
+#[derive(Debug, Clone)]
+pub struct InnerProduct<F: FieldExt> {
+    pub a: Vec<ACell<F>>,
+    pub b: Vec<ACell<F>>,
+    pub result: ACell<F>,
+}
+
+impl<F: FieldExt> InnerProductChip<F> {
+    pub fn construct(config: InnerProductConfig) -> Self {
+        Self {
+            config,
+            marker: PhantomData,
+        }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+    ) -> InnerProductConfig {
+        let [a, b, acc] = advice;
+        for column in advice {
+            meta.enable_equality(column);
+        }
+
+        let s_first = meta.selector();
+        let s_step = meta.selector();
+
+        meta.create_gate("inner product first", |meta| {
+            let [a, b, acc] = expr::advice_row(meta, advice);
+            let s = meta.query_selector(s_first);
+
+            vec![s * (a * b - acc)]
+        });
+
+        meta.create_gate("inner product step", |meta| {
+            let prev = meta.query_advice(acc, Rotation::prev());
+            let [a, b, acc] = expr::advice_row(meta, advice);
+            let s = meta.query_selector(s_step);
+
+            vec![s * (prev + a * b - acc)]
+        });
+
+        InnerProductConfig {
+            a,
+            b,
+            acc,
+            s_first,
+            s_step,
+        }
+    }
+
+    /// Witnesses `a` and `b` and lays out `⟨a, b⟩`.
+    pub fn assign(
+        &self,
+        layouter: impl Layouter<F>,
+        a: &[Value<F>],
+        b: &[Value<F>],
+    ) -> Result<InnerProduct<F>, Error> {
+        let a = a.iter().map(|a| Operand::Witness(*a)).collect();
+        let b = b.iter().map(|b| Operand::Witness(*b)).collect();
+        self.layout(layouter, a, b)
+    }
+
+    /// `⟨a, b⟩` of already assigned cells.
+    pub fn inner_product(
+        &self,
+        layouter: impl Layouter<F>,
+        a: &[ACell<F>],
+        b: &[ACell<F>],
+    ) -> Result<ACell<F>, Error> {
+        let a = a.iter().map(Operand::Cell).collect();
+        let b = b.iter().map(Operand::Cell).collect();
+        self.layout(layouter, a, b).map(|product| product.result)
+    }
+
+    fn layout(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: Vec<Operand<'_, F>>,
+        b: Vec<Operand<'_, F>>,
+    ) -> Result<InnerProduct<F>, Error> {
+        assert_eq!(a.len(), b.len(), "vectors of different lengths");
+        assert!(!a.is_empty(), "empty inner product");
+        layouter.assign_region(
+            || "inner product",
+            |mut region| {
+                let mut a_cells = vec![];
+                let mut b_cells = vec![];
+                for (row, (a, b)) in a.iter().zip(&b).enumerate() {
+                    a_cells.push(a.assign(&mut region, "a", self.config.a, row)?);
+                    b_cells.push(b.assign(&mut region, "b", self.config.b, row)?);
+                }
+
+                let mut acc = Value::known(F::zero());
+                let mut acc_cell = None;
+                for (row, (a, b)) in a_cells.iter().zip(&b_cells).enumerate() {
+                    if row == 0 {
+                        self.config.s_first.enable(&mut region, row)?;
+                    } else {
+                        self.config.s_step.enable(&mut region, row)?;
+                    }
+                    acc = acc + a.value().copied() * b.value();
+                    acc_cell =
+                        Some(region.assign_advice(|| "acc", self.config.acc, row, || acc)?);
+                }
+                Ok(InnerProduct {
+                    a: a_cells,
+                    b: b_cells,
+                    result: acc_cell.unwrap(),
+                })
+            },
+        )
+    }
+}
@@ -0,0 +1,108 @@
+//! `a == b` as a constrained bit, via [`IsZeroChip`] applied to `a - b`.
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::Layouter,
+    plonk::{Advice, Column, ConstraintSystem, Error, Selector},
+    poly::Rotation,
+};
+
+use super::{
+    is_zero::{IsZeroChip, IsZeroConfig},
+    AssignedBit,
+};
+use crate::fibonacci::ACell;
+
+#[derive(Debug, Clone)]
+pub struct IsEqualConfig<F: FieldExt> {
+    pub a: Column<Advice>,
+    pub b: Column<Advice>,
+    pub out: Column<Advice>,
+    pub selector: Selector,
+    pub is_zero: IsZeroConfig<F>,
+}
+
+#[derive(Debug)]
+pub struct IsEqualChip<F: FieldExt> {
+    config: IsEqualConfig<F>,
+}
+
+impl<F: FieldExt> IsEqualChip<F> {
+    pub fn construct(config: IsEqualConfig<F>) -> Self {
+        Self { config }
+    }
+
+    /// `diff_inv` holds the witnessed inverse of `a - b`.
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        a: Column<Advice>,
+        b: Column<Advice>,
+        diff_inv: Column<Advice>,
+        out: Column<Advice>,
+    ) -> IsEqualConfig<F> {
+        meta.enable_equality(a);
+        meta.enable_equality(b);
+        meta.enable_equality(out);
+
+        let selector = meta.selector();
+
+        let is_zero = IsZeroChip::configure(
+            meta,
+            |meta| meta.query_selector(selector),
+            |meta| meta.query_advice(a, Rotation::cur()) - meta.query_advice(b, Rotation::cur()),
+            diff_inv,
+        );
+
+        meta.create_gate("is equal", |meta| {
+            //
+            //  a | b | diff_inv | out | selector
+            //  a | b | 1/(a-b)  | eq  |    s
+            //
+            let out = meta.query_advice(out, Rotation::cur());
+            let s = meta.query_selector(selector);
+
+            vec![s * (out - is_zero.is_zero_expr.clone())]
+        });
+
+        IsEqualConfig {
+            a,
+            b,
+            out,
+            selector,
+            is_zero,
+        }
+    }
+
+    /// Returns a bit that is `1` iff `a` and `b` hold the same value.
+    pub fn is_equal(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &ACell<F>,
+        b: &ACell<F>,
+    ) -> Result<AssignedBit<F>, Error> {
+        let is_zero = IsZeroChip::construct(self.config.is_zero.clone());
+
+        layouter.assign_region(
+            || "is equal",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+                a.copy_advice(|| "a", &mut region, self.config.a, 0)?;
+                b.copy_advice(|| "b", &mut region, self.config.b, 0)?;
+
+                let diff = a.value().copied() - b.value().copied();
+                is_zero.assign(&mut region, 0, diff)?;
+
+                let out = diff.map(|diff| {
+                    if diff == F::zero() {
+                        F::one()
+                    } else {
+                        F::zero()
+                    }
+                });
+                let out = region.assign_advice(|| "out", self.config.out, 0, || out)?;
+
+                Ok(AssignedBit::new(out))
+            },
+        )
+    }
+}
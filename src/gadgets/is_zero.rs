@@ -0,0 +1,74 @@
+//! `x == 0` as an expression, for use inside other gates.
+//!
+//! With `x_inv` witnessed as `x⁻¹` (or anything when `x = 0`),
+//! `is_zero = 1 - x·x_inv`, and the gate `q · x · is_zero = 0` forces
+//! `is_zero` to be `1` exactly when `x = 0` and `0` otherwise.
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Region, Value},
+    plonk::{Advice, Column, ConstraintSystem, Error, Expression, VirtualCells},
+    poly::Rotation,
+};
+
+use crate::expr;
+
+#[derive(Debug, Clone)]
+pub struct IsZeroConfig<F: FieldExt> {
+    pub value_inv: Column<Advice>,
+    /// `1` when the value is zero, `0` otherwise; valid on rows where
+    /// `q_enable` is nonzero.
+    pub is_zero_expr: Expression<F>,
+}
+
+#[derive(Debug)]
+pub struct IsZeroChip<F: FieldExt> {
+    config: IsZeroConfig<F>,
+}
+
+impl<F: FieldExt> IsZeroChip<F> {
+    pub fn construct(config: IsZeroConfig<F>) -> Self {
+        Self { config }
+    }
+
+    /// Constrains `value_inv` against the expression `value`, on rows where
+    /// `q_enable` is nonzero.
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        q_enable: impl FnOnce(&mut VirtualCells<'_, F>) -> Expression<F>,
+        value: impl FnOnce(&mut VirtualCells<'_, F>) -> Expression<F>,
+        value_inv: Column<Advice>,
+    ) -> IsZeroConfig<F> {
+        let mut is_zero_expr = expr::constant(0);
+
+        meta.create_gate("is zero", |meta| {
+            //
+            // value | value_inv | q_enable
+            //   x   |   x_inv   |    q
+            //
+            let value = value(meta);
+            let q_enable = q_enable(meta);
+            let value_inv = meta.query_advice(value_inv, Rotation::cur());
+
+            is_zero_expr = expr::constant(1) - value.clone() * value_inv;
+            vec![q_enable * value * is_zero_expr.clone()]
+        });
+
+        IsZeroConfig {
+            value_inv,
+            is_zero_expr,
+        }
+    }
+
+    /// Witnesses the inverse of `value` at `offset`.
+    pub fn assign(
+        &self,
+        region: &mut Region<'_, F>,
+        offset: usize,
+        value: Value<F>,
+    ) -> Result<(), Error> {
+        let value_inv = value.map(|value| value.invert().unwrap_or(F::zero()));
+        region.assign_advice(|| "value inv", self.config.value_inv, offset, || value_inv)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,138 @@
+//! Matrix products, one [`InnerProductChip`] layout per output entry.
+//!
+//! An `m×k` by `k×n` product lays out `m·n` inner products of length `k`,
+//! copying the operands in from wherever the matrices were assigned.
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Layouter, Value},
+    plonk::{Advice, Column, ConstraintSystem, Error},
+};
+
+use super::inner_product::{InnerProductChip, InnerProductConfig};
+use crate::fibonacci::ACell;
+
+/// A matrix of assigned cells, row by row.
+#[derive(Debug, Clone)]
+pub struct AssignedMatrix<F: FieldExt> {
+    pub rows: Vec<Vec<ACell<F>>>,
+}
+
+impl<F: FieldExt> AssignedMatrix<F> {
+    pub fn dims(&self) -> (usize, usize) {
+        (self.rows.len(), self.rows.first().map_or(0, Vec::len))
+    }
+
+    pub fn column(&self, j: usize) -> Vec<ACell<F>> {
+        self.rows.iter().map(|row| row[j].clone()).collect()
+    }
+
+    pub fn values(&self) -> Value<Vec<Vec<F>>> {
+        self.rows
+            .iter()
+            .map(|row| row.iter().map(|cell| cell.value().copied()).collect())
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MatrixConfig {
+    pub inner_product: InnerProductConfig,
+}
+
+#[derive(Debug)]
+pub struct MatrixChip<F: FieldExt> {
+    config: MatrixConfig,
+    inner_product: InnerProductChip<F>,
+}
+
+impl<F: FieldExt> MatrixChip<F> {
+    pub fn construct(config: MatrixConfig) -> Self {
+        Self {
+            inner_product: InnerProductChip::construct(config.inner_product.clone()),
+            config,
+        }
+    }
+
+    pub fn configure(meta: &mut ConstraintSystem<F>, advice: [Column<Advice>; 3]) -> MatrixConfig {
+        MatrixConfig {
+            inner_product: InnerProductChip::configure(meta, advice),
+        }
+    }
+
+    /// Witnesses a matrix given row by row.
+    pub fn load(
+        &self,
+        mut layouter: impl Layouter<F>,
+        rows: &[Vec<Value<F>>],
+    ) -> Result<AssignedMatrix<F>, Error> {
+        let width = rows.first().map_or(0, Vec::len);
+        assert!(
+            rows.iter().all(|row| row.len() == width),
+            "rows of different lengths"
+        );
+        let column = self.config.inner_product.a;
+        let rows = layouter.assign_region(
+            || "load matrix",
+            |mut region| {
+                rows.iter()
+                    .enumerate()
+                    .map(|(i, row)| {
+                        row.iter()
+                            .enumerate()
+                            .map(|(j, value)| {
+                                region.assign_advice(|| "entry", column, i * width + j, || *value)
+                            })
+                            .collect::<Result<Vec<_>, _>>()
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            },
+        )?;
+        Ok(AssignedMatrix { rows })
+    }
+
+    /// `a · b`. Panics if the inner dimensions differ.
+    pub fn mul(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &AssignedMatrix<F>,
+        b: &AssignedMatrix<F>,
+    ) -> Result<AssignedMatrix<F>, Error> {
+        let ((m, k), (k_b, n)) = (a.dims(), b.dims());
+        assert_eq!(k, k_b, "cannot multiply {}×{} by {}×{}", m, k, k_b, n);
+        let columns: Vec<_> = (0..n).map(|j| b.column(j)).collect();
+        let rows = a
+            .rows
+            .iter()
+            .map(|row| {
+                columns
+                    .iter()
+                    .map(|column| {
+                        self.inner_product.inner_product(
+                            layouter.namespace(|| "entry"),
+                            row,
+                            column,
+                        )
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(AssignedMatrix { rows })
+    }
+}
+
+/// `a · b` of matrices given row by row.
+pub fn mul<F: FieldExt>(a: &[Vec<F>], b: &[Vec<F>]) -> Vec<Vec<F>> {
+    let n = b.first().map_or(0, Vec::len);
+    a.iter()
+        .map(|row| {
+            (0..n)
+                .map(|j| {
+                    row.iter()
+                        .zip(b)
+                        .fold(F::zero(), |acc, (a, b_row)| acc + *a * b_row[j])
+                })
+                .collect()
+        })
+        .collect()
+}
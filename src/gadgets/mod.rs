@@ -0,0 +1,58 @@
+//! Small reusable chips for circuits beyond the plain recurrence.
+//!
+//! Each gadget follows the layout of the Fibonacci chips: a `Config` built
+//! by `configure`, a `Chip` built from it by `construct`, and methods that
+//! take a layouter and assigned cells and return assigned cells. For a
+//! chip that is one gate over one row, [`simple_chip!`](crate::simple_chip)
+//! writes all of that but the methods.
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::Value};
+
+use crate::fibonacci::ACell;
+
+pub mod add64;
+pub mod arithmetic;
+pub mod bigint;
+pub mod bytes;
+pub mod compare_swap;
+pub mod fixed_point;
+pub mod flex_gate;
+pub mod grand_product;
+pub mod horner;
+pub mod inner_product;
+pub mod is_equal;
+pub mod is_zero;
+pub mod matrix;
+pub mod mul64;
+pub mod nonnative;
+pub mod nonzero;
+pub mod parity;
+pub mod poseidon;
+pub mod simple;
+pub mod small_range;
+pub mod sparse_merkle;
+pub mod sqrt;
+pub mod u64;
+pub mod version;
+
+/// A cell constrained to hold `0` or `1`.
+///
+/// Only gadgets that constrain booleanity hand these out, so a circuit
+/// taking an `AssignedBit` can rely on it without re-checking.
+#[derive(Debug, Clone)]
+pub struct AssignedBit<F: FieldExt>(ACell<F>);
+
+impl<F: FieldExt> AssignedBit<F> {
+    /// Wraps a cell the caller has already constrained to be boolean.
+    pub(crate) fn new(cell: ACell<F>) -> Self {
+        AssignedBit(cell)
+    }
+
+    pub fn cell(&self) -> &ACell<F> {
+        &self.0
+    }
+
+    pub fn value(&self) -> Value<bool> {
+        self.0.value().map(|v| *v == F::one())
+    }
+}
@@ -0,0 +1,117 @@
+//! 64 × 64 → 128-bit multiplication, split into two 64-bit limbs.
+//!
+//! One row `a | b | lo | hi` with `a·b = lo + hi·2^64`. The product of two
+//! [`AssignedU64`]s is below `2^128`, far below the modulus, so the equation
+//! holds over the integers, and range-checking both limbs makes the split
+//! unique.
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::Layouter,
+    plonk::{Advice, Column, ConstraintSystem, Error, Selector},
+};
+
+use super::u64::{AssignedU64, U64Chip, U64Config};
+use crate::expr;
+
+#[derive(Debug, Clone)]
+pub struct Mul64Config {
+    /// `a, b, lo, hi`.
+    pub advice: [Column<Advice>; 4],
+    pub selector: Selector,
+    pub range: U64Config,
+}
+
+/// `a·b = lo + hi·2^64`.
+#[derive(Debug)]
+pub struct Product128<F: FieldExt> {
+    pub lo: AssignedU64<F>,
+    pub hi: AssignedU64<F>,
+}
+
+#[derive(Debug)]
+pub struct Mul64Chip<F: FieldExt> {
+    config: Mul64Config,
+    range: U64Chip<F>,
+}
+
+impl<F: FieldExt> Mul64Chip<F> {
+    pub fn construct(config: Mul64Config) -> Self {
+        Self {
+            range: U64Chip::construct(config.range.clone()),
+            config,
+        }
+    }
+
+    /// `range` is the running-sum column of the limbs' range checks.
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 4],
+        range: Column<Advice>,
+    ) -> Mul64Config {
+        for column in advice {
+            meta.enable_equality(column);
+        }
+
+        let selector = meta.selector();
+
+        meta.create_gate("mul64", |meta| {
+            //
+            //  a | b | lo | hi | selector
+            //  a | b | lo | hi |    s
+            //
+            let [a, b, lo, hi] = expr::advice_row(meta, advice);
+            let s = meta.query_selector(selector);
+            let two_pow_64 = expr::constant_u128(1 << 64);
+
+            vec![s * (a * b - lo - hi * two_pow_64)]
+        });
+
+        Mul64Config {
+            advice,
+            selector,
+            range: U64Chip::configure(meta, range),
+        }
+    }
+
+    pub fn mul(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &AssignedU64<F>,
+        b: &AssignedU64<F>,
+    ) -> Result<Product128<F>, Error> {
+        let [col_a, col_b, col_lo, col_hi] = self.config.advice;
+        let product = a.value().zip(b.value()).map(|(a, b)| a as u128 * b as u128);
+
+        let (lo, hi) = layouter.assign_region(
+            || "mul64",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+                a.cell().copy_advice(|| "a", &mut region, col_a, 0)?;
+                b.cell().copy_advice(|| "b", &mut region, col_b, 0)?;
+                let lo = region.assign_advice(
+                    || "lo",
+                    col_lo,
+                    0,
+                    || product.map(|p| F::from(p as u64)),
+                )?;
+                let hi = region.assign_advice(
+                    || "hi",
+                    col_hi,
+                    0,
+                    || product.map(|p| F::from((p >> 64) as u64)),
+                )?;
+                Ok((lo, hi))
+            },
+        )?;
+
+        Ok(Product128 {
+            lo: self
+                .range
+                .range_check(layouter.namespace(|| "lo range"), &lo)?,
+            hi: self
+                .range
+                .range_check(layouter.namespace(|| "hi range"), &hi)?,
+        })
+    }
+}
@@ -0,0 +1,459 @@
+//! Arithmetic modulo a foreign prime `p`, e.g. secp256k1's base field.
+//!
+//! A foreign element is four 64-bit limbs, least significant first, each
+//! range-checked with [`BytesChip`]. An operation witnesses a quotient `q`
+//! and remainder `r` and checks the integer identity
+//!
+//! ```text
+//! a·b = q·p + r        (mul)
+//! a + b = q·p + r      (add, q ∈ {0, 1})
+//! ```
+//!
+//! column by column in base `2^64`: with `t_k` the `k`th column of
+//! `a·b - q·p - r`, each `t_k + c_{k-1} = c_k·2^64` for a carry `c_k`, and
+//! the last carry is zero. Carries can be negative, so they are stored with
+//! an offset of [`CARRY_OFFSET`] and range-checked to 72 bits. Every term is
+//! below `2^140`, far below the native modulus, so the identity holds over
+//! the integers. Finally `r < p` is checked by computing `p - 1 - r` with
+//! borrows, which makes every result canonical.
+//!
+//! The layout is one wide row per operation:
+//!
+//! ```text
+//! a0..a3 | b0..b3 | q0..q3 | r0..r3 | c0..c5
+//! ```
+//!
+//! and one row per canonicity check, reusing the same columns:
+//!
+//! ```text
+//! d0..d3 | - | - | x0..x3 | borrow0..borrow2
+//! ```
+
+use std::marker::PhantomData;
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Layouter, Value},
+    plonk::{Advice, Column, ConstraintSystem, Error, Selector},
+};
+use num_bigint::{BigInt, BigUint};
+
+use super::bytes::{BytesChip, BytesConfig};
+use crate::{expr, fibonacci::ACell};
+
+pub const LIMBS: usize = 4;
+pub const LIMB_BITS: usize = 64;
+
+/// Carries are stored as `c + CARRY_OFFSET`, which keeps them nonnegative.
+pub const CARRY_OFFSET: u128 = 1 << 70;
+/// Bytes in a stored carry's range check.
+const CARRY_BYTES: usize = 9;
+
+/// A prime modulus of at most 256 bits.
+pub trait ForeignModulus: Clone + std::fmt::Debug {
+    /// `p` in 64-bit limbs, least significant first.
+    const LIMBS: [u64; LIMBS];
+
+    fn modulus() -> BigUint {
+        from_limbs(Self::LIMBS)
+    }
+}
+
+/// The base field of secp256k1, `2^256 - 2^32 - 977`.
+#[derive(Debug, Clone)]
+pub struct Secp256k1Base;
+
+impl ForeignModulus for Secp256k1Base {
+    const LIMBS: [u64; LIMBS] = [
+        0xffff_fffe_ffff_fc2f,
+        0xffff_ffff_ffff_ffff,
+        0xffff_ffff_ffff_ffff,
+        0xffff_ffff_ffff_ffff,
+    ];
+}
+
+/// A canonical foreign element: range-checked limbs of a value below `p`.
+#[derive(Debug, Clone)]
+pub struct AssignedForeign<F: FieldExt> {
+    pub limbs: [ACell<F>; LIMBS],
+}
+
+impl<F: FieldExt> AssignedForeign<F> {
+    pub fn value(&self) -> Value<BigUint> {
+        let limbs = self.limbs.iter().fold(Value::known(vec![]), |acc, limb| {
+            acc.zip(limb.value()).map(|(mut acc, limb)| {
+                acc.push(limb.get_lower_128() as u64);
+                acc
+            })
+        });
+        limbs.map(|limbs| from_limbs(limbs.try_into().unwrap()))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct NonNativeConfig {
+    pub a: [Column<Advice>; LIMBS],
+    pub b: [Column<Advice>; LIMBS],
+    pub q: [Column<Advice>; LIMBS],
+    pub r: [Column<Advice>; LIMBS],
+    pub carries: [Column<Advice>; 2 * LIMBS - 2],
+    pub s_add: Selector,
+    pub s_mul: Selector,
+    pub s_canonical: Selector,
+    pub bytes: BytesConfig,
+}
+
+#[derive(Debug)]
+pub struct NonNativeChip<F: FieldExt, M: ForeignModulus> {
+    config: NonNativeConfig,
+    bytes: BytesChip<F>,
+    marker: PhantomData<M>,
+}
+
+impl<F: FieldExt, M: ForeignModulus> NonNativeChip<F, M> {
+    pub fn construct(config: NonNativeConfig) -> Self {
+        Self {
+            bytes: BytesChip::construct(config.bytes.clone()),
+            config,
+            marker: PhantomData,
+        }
+    }
+
+    /// Allocates the 22 advice columns of the operation row. `bytes` does
+    /// the range checks; its table has to be loaded by the circuit.
+    pub fn configure(meta: &mut ConstraintSystem<F>, bytes: BytesConfig) -> NonNativeConfig {
+        let mut limbs = || {
+            [(); LIMBS].map(|_| {
+                let column = meta.advice_column();
+                meta.enable_equality(column);
+                column
+            })
+        };
+        let (a, b, q, r) = (limbs(), limbs(), limbs(), limbs());
+        let carries = [(); 2 * LIMBS - 2].map(|_| meta.advice_column());
+        for column in carries {
+            meta.enable_equality(column);
+        }
+
+        let s_add = meta.selector();
+        let s_mul = meta.selector();
+        let s_canonical = meta.selector();
+
+        let p = M::LIMBS.map(expr::constant);
+        let base = expr::constant_u128(1 << LIMB_BITS);
+        let offset = expr::constant_u128(CARRY_OFFSET);
+
+        meta.create_gate("nonnative mul", |meta| {
+            let [a, b, q, r] = [a, b, q, r].map(|columns| expr::advice_row(meta, columns));
+            let c = expr::advice_row(meta, carries).map(|c| c - offset.clone());
+            let s = meta.query_selector(s_mul);
+
+            let mut constraints = vec![];
+            for k in 0..2 * LIMBS - 1 {
+                let mut t = expr::sum((0..LIMBS).filter(|&i| k >= i && k - i < LIMBS).map(|i| {
+                    let j = k - i;
+                    a[i].clone() * b[j].clone() - q[i].clone() * p[j].clone()
+                }));
+                if k < LIMBS {
+                    t = t - r[k].clone();
+                }
+                if k > 0 {
+                    t = t + c[k - 1].clone();
+                }
+                if k < 2 * LIMBS - 2 {
+                    t = t - c[k].clone() * base.clone();
+                }
+                constraints.push(s.clone() * t);
+            }
+            constraints
+        });
+
+        meta.create_gate("nonnative add", |meta| {
+            let [a, b, q, r] = [a, b, q, r].map(|columns| expr::advice_row(meta, columns));
+            let c = expr::advice_row(meta, carries).map(|c| c - offset.clone());
+            let s = meta.query_selector(s_add);
+
+            let mut constraints = vec![s.clone() * expr::bool_check(q[0].clone())];
+            for q in &q[1..] {
+                constraints.push(s.clone() * q.clone());
+            }
+            for k in 0..LIMBS {
+                let mut t =
+                    a[k].clone() + b[k].clone() - q[0].clone() * p[k].clone() - r[k].clone();
+                if k > 0 {
+                    t = t + c[k - 1].clone();
+                }
+                if k < LIMBS - 1 {
+                    t = t - c[k].clone() * base.clone();
+                }
+                constraints.push(s.clone() * t);
+            }
+            constraints
+        });
+
+        meta.create_gate("nonnative canonical", |meta| {
+            // d = (p - 1) - x, limb by limb with borrows; no final borrow.
+            let d = expr::advice_row(meta, a);
+            let x = expr::advice_row(meta, r);
+            let borrow = expr::advice_row(meta, [carries[0], carries[1], carries[2]]);
+            let s = meta.query_selector(s_canonical);
+
+            let mut p_minus_one = M::LIMBS;
+            p_minus_one[0] -= 1;
+
+            let mut constraints = vec![];
+            for k in 0..LIMBS {
+                let mut expected = expr::constant(p_minus_one[k]) - x[k].clone();
+                if k < LIMBS - 1 {
+                    expected = expected + borrow[k].clone() * base.clone();
+                }
+                if k > 0 {
+                    expected = expected - borrow[k - 1].clone();
+                }
+                constraints.push(s.clone() * (d[k].clone() - expected));
+            }
+            for borrow in borrow {
+                constraints.push(s.clone() * expr::bool_check(borrow));
+            }
+            constraints
+        });
+
+        NonNativeConfig {
+            a,
+            b,
+            q,
+            r,
+            carries,
+            s_add,
+            s_mul,
+            s_canonical,
+            bytes,
+        }
+    }
+
+    /// Assigns `value`, range-checks its limbs and checks it is below `p`.
+    pub fn load(
+        &self,
+        mut layouter: impl Layouter<F>,
+        value: Value<BigUint>,
+    ) -> Result<AssignedForeign<F>, Error> {
+        let limbs = value.map(|value| to_limbs(&value));
+        let limbs = layouter.assign_region(
+            || "load foreign",
+            |mut region| {
+                let mut cells = vec![];
+                for i in 0..LIMBS {
+                    let limb = limbs.map(|limbs| F::from(limbs[i]));
+                    cells.push(region.assign_advice(|| "limb", self.config.r[i], 0, || limb)?);
+                }
+                Ok(cells)
+            },
+        )?;
+        for limb in &limbs {
+            self.bytes
+                .decompose(layouter.namespace(|| "limb range"), limb, 8)?;
+        }
+        let x = AssignedForeign {
+            limbs: limbs.try_into().unwrap(),
+        };
+        self.assert_canonical(layouter.namespace(|| "canonical"), &x)?;
+        Ok(x)
+    }
+
+    /// `(a + b) mod p`.
+    pub fn add(
+        &self,
+        layouter: impl Layouter<F>,
+        a: &AssignedForeign<F>,
+        b: &AssignedForeign<F>,
+    ) -> Result<AssignedForeign<F>, Error> {
+        let witness = a.value().zip(b.value()).map(|(a, b)| {
+            let p = M::modulus();
+            let sum = a + b;
+            let (q, r) = (&sum / &p, &sum % &p);
+            (to_limbs(&q), to_limbs(&r))
+        });
+        self.operation(layouter, self.config.s_add, a, b, witness, false)
+    }
+
+    /// `(a · b) mod p`.
+    pub fn mul(
+        &self,
+        layouter: impl Layouter<F>,
+        a: &AssignedForeign<F>,
+        b: &AssignedForeign<F>,
+    ) -> Result<AssignedForeign<F>, Error> {
+        let witness = a.value().zip(b.value()).map(|(a, b)| {
+            let p = M::modulus();
+            let product = a * b;
+            (to_limbs(&(&product / &p)), to_limbs(&(&product % &p)))
+        });
+        self.operation(layouter, self.config.s_mul, a, b, witness, true)
+    }
+
+    fn operation(
+        &self,
+        mut layouter: impl Layouter<F>,
+        selector: Selector,
+        a: &AssignedForeign<F>,
+        b: &AssignedForeign<F>,
+        witness: Value<([u64; LIMBS], [u64; LIMBS])>,
+        is_mul: bool,
+    ) -> Result<AssignedForeign<F>, Error> {
+        let a_limbs = a.value().map(|a| to_limbs(&a));
+        let b_limbs = b.value().map(|b| to_limbs(&b));
+        let carries = a_limbs
+            .zip(b_limbs)
+            .zip(witness)
+            .map(|((a, b), (q, r))| carries::<F, M>(a, b, q, r, is_mul));
+
+        let (q, r, c) = layouter.assign_region(
+            || {
+                if is_mul {
+                    "nonnative mul"
+                } else {
+                    "nonnative add"
+                }
+            },
+            |mut region| {
+                selector.enable(&mut region, 0)?;
+                for i in 0..LIMBS {
+                    a.limbs[i].copy_advice(|| "a", &mut region, self.config.a[i], 0)?;
+                    b.limbs[i].copy_advice(|| "b", &mut region, self.config.b[i], 0)?;
+                }
+                let mut q = vec![];
+                let mut r = vec![];
+                for i in 0..LIMBS {
+                    let q_i = witness.map(|(q, _)| F::from(q[i]));
+                    let r_i = witness.map(|(_, r)| F::from(r[i]));
+                    q.push(region.assign_advice(|| "q", self.config.q[i], 0, || q_i)?);
+                    r.push(region.assign_advice(|| "r", self.config.r[i], 0, || r_i)?);
+                }
+                let mut c = vec![];
+                for (i, column) in self.config.carries.iter().enumerate() {
+                    let c_i = carries.map(|carries| carries[i]);
+                    c.push(region.assign_advice(|| "carry", *column, 0, || c_i)?);
+                }
+                Ok((q, r, c))
+            },
+        )?;
+
+        for limb in q.iter().chain(&r) {
+            self.bytes
+                .decompose(layouter.namespace(|| "limb range"), limb, 8)?;
+        }
+        for carry in &c {
+            self.bytes
+                .decompose(layouter.namespace(|| "carry range"), carry, CARRY_BYTES)?;
+        }
+
+        let r = AssignedForeign {
+            limbs: r.try_into().unwrap(),
+        };
+        self.assert_canonical(layouter.namespace(|| "canonical"), &r)?;
+        Ok(r)
+    }
+
+    /// Checks that `x`, whose limbs are already range-checked, is below `p`.
+    fn assert_canonical(
+        &self,
+        mut layouter: impl Layouter<F>,
+        x: &AssignedForeign<F>,
+    ) -> Result<(), Error> {
+        let mut p_minus_one = M::LIMBS;
+        p_minus_one[0] -= 1;
+        let witness = x.value().map(|x| {
+            let x = to_limbs(&x);
+            let mut d = [0u64; LIMBS];
+            let mut borrow = [false; LIMBS];
+            for k in 0..LIMBS {
+                let (diff, b1) = p_minus_one[k].overflowing_sub(x[k]);
+                let (diff, b2) =
+                    diff.overflowing_sub(k.checked_sub(1).map_or(0, |i| borrow[i] as u64));
+                d[k] = diff;
+                borrow[k] = b1 || b2;
+            }
+            (d, borrow)
+        });
+
+        let d = layouter.assign_region(
+            || "nonnative canonical",
+            |mut region| {
+                self.config.s_canonical.enable(&mut region, 0)?;
+                let mut d = vec![];
+                for i in 0..LIMBS {
+                    x.limbs[i].copy_advice(|| "x", &mut region, self.config.r[i], 0)?;
+                    let d_i = witness.map(|(d, _)| F::from(d[i]));
+                    d.push(region.assign_advice(|| "d", self.config.a[i], 0, || d_i)?);
+                }
+                for i in 0..LIMBS - 1 {
+                    let borrow = witness.map(|(_, borrow)| F::from(borrow[i] as u64));
+                    region.assign_advice(|| "borrow", self.config.carries[i], 0, || borrow)?;
+                }
+                Ok(d)
+            },
+        )?;
+
+        for limb in &d {
+            self.bytes
+                .decompose(layouter.namespace(|| "d range"), limb, 8)?;
+        }
+        Ok(())
+    }
+}
+
+pub fn to_limbs(x: &BigUint) -> [u64; LIMBS] {
+    let mut limbs = [0; LIMBS];
+    for (limb, digit) in limbs.iter_mut().zip(x.to_u64_digits()) {
+        *limb = digit;
+    }
+    limbs
+}
+
+pub fn from_limbs(limbs: [u64; LIMBS]) -> BigUint {
+    limbs
+        .iter()
+        .rev()
+        .fold(BigUint::from(0u8), |acc, limb| (acc << LIMB_BITS) + limb)
+}
+
+/// The stored carries `c_k + CARRY_OFFSET` of `a·b - q·p - r` (or
+/// `a + b - q·p - r`).
+fn carries<F: FieldExt, M: ForeignModulus>(
+    a: [u64; LIMBS],
+    b: [u64; LIMBS],
+    q: [u64; LIMBS],
+    r: [u64; LIMBS],
+    is_mul: bool,
+) -> [F; 2 * LIMBS - 2] {
+    let int = |x: u64| BigInt::from(x);
+    let mut columns = vec![BigInt::from(0); 2 * LIMBS - 1];
+    for k in 0..LIMBS {
+        columns[k] -= int(r[k]);
+    }
+    if is_mul {
+        for i in 0..LIMBS {
+            for j in 0..LIMBS {
+                columns[i + j] += int(a[i]) * int(b[j]) - int(q[i]) * int(M::LIMBS[j]);
+            }
+        }
+    } else {
+        for k in 0..LIMBS {
+            columns[k] += int(a[k]) + int(b[k]) - int(q[0]) * int(M::LIMBS[k]);
+        }
+    }
+
+    let mut stored = [F::zero(); 2 * LIMBS - 2];
+    let mut carry = BigInt::from(0);
+    for (k, stored) in stored.iter_mut().enumerate() {
+        carry = (&columns[k] + &carry) >> LIMB_BITS;
+        let shifted: BigInt = &carry + BigInt::from(CARRY_OFFSET);
+        let (_, digits) = shifted.to_u64_digits();
+        let value = digits
+            .iter()
+            .rev()
+            .fold(0u128, |acc, digit| (acc << 64) | *digit as u128);
+        *stored = F::from_u128(value);
+    }
+    stored
+}
@@ -0,0 +1,34 @@
+//! `x ≠ 0`, enforced by witnessing `x⁻¹` and constraining `x · x_inv = 1`.
+//!
+//! The inverse cell is returned so callers that need `x⁻¹` anyway (see
+//! division) do not witness it twice.
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::Layouter, plonk::Error};
+
+use super::simple::Operand;
+use crate::{expr, fibonacci::ACell};
+
+crate::simple_chip! {
+    pub struct NonZeroChip(NonZeroConfig);
+    //
+    //  x | x_inv | selector
+    //  x |  1/x  |    s
+    //
+    gate "nonzero"(x, x_inv) => [x * x_inv - expr::constant(1)];
+}
+
+impl<F: FieldExt> NonZeroChip<F> {
+    /// Constrains `x` to be nonzero and returns the cell holding `x⁻¹`.
+    ///
+    /// When `x` is zero the inverse is witnessed as zero and the circuit is
+    /// unsatisfiable.
+    pub fn assert_nonzero(
+        &self,
+        layouter: impl Layouter<F>,
+        x: &ACell<F>,
+    ) -> Result<ACell<F>, Error> {
+        let x_inv = x.value().map(|x| x.invert().unwrap_or(F::zero()));
+        let [_, x_inv] = self.assign_row(layouter, [Operand::Cell(x), Operand::Witness(x_inv)])?;
+        Ok(x_inv)
+    }
+}
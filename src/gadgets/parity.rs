@@ -0,0 +1,106 @@
+//! The lowest bit of a field element.
+//!
+//! "Even" is not a property of a field element: `p` is odd, so every `x`
+//! is `2·h + bit` for both values of `bit`, with `h = (x - bit) / 2`. The
+//! bit only means anything once `h` is bounded. One row `x | h | bit`
+//! constrains `x = 2·h + bit` with `bit` boolean, and an embedded
+//! [`U64Chip`] checks `h < 2^64`. Then `2·h + bit` cannot wrap, so for any
+//! `x` below `2^65` exactly one `bit` is satisfiable, its integer parity.
+//! Larger `x` have no valid witness at all.
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::Layouter,
+    plonk::{Advice, Column, ConstraintSystem, Error, Selector},
+};
+
+use super::{
+    u64::{U64Chip, U64Config},
+    AssignedBit,
+};
+use crate::{expr, fibonacci::ACell};
+
+#[derive(Debug, Clone)]
+pub struct ParityConfig {
+    /// `x, h, bit`.
+    pub advice: [Column<Advice>; 3],
+    pub selector: Selector,
+    pub range: U64Config,
+}
+
+#[derive(Debug)]
+pub struct ParityChip<F: FieldExt> {
+    config: ParityConfig,
+    range: U64Chip<F>,
+}
+
+impl<F: FieldExt> ParityChip<F> {
+    pub fn construct(config: ParityConfig) -> Self {
+        Self {
+            range: U64Chip::construct(config.range.clone()),
+            config,
+        }
+    }
+
+    /// `range` is the running-sum column of the range check on `h`.
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        range: Column<Advice>,
+    ) -> ParityConfig {
+        for column in advice {
+            meta.enable_equality(column);
+        }
+
+        let selector = meta.selector();
+
+        meta.create_gate("parity", |meta| {
+            //
+            //  x | h | bit | selector
+            //  x | h |  b  |    s
+            //
+            let [x, h, bit] = expr::advice_row(meta, advice);
+            let s = meta.query_selector(selector);
+            vec![
+                s.clone() * (x - h * F::from(2) - bit.clone()),
+                s * expr::bool_check(bit),
+            ]
+        });
+
+        ParityConfig {
+            advice,
+            selector,
+            range: U64Chip::configure(meta, range),
+        }
+    }
+
+    /// The lowest bit of `x`. The circuit is unsatisfiable if `x` is not
+    /// below `2^65`.
+    pub fn parity(
+        &self,
+        mut layouter: impl Layouter<F>,
+        x: &ACell<F>,
+    ) -> Result<AssignedBit<F>, Error> {
+        let [col_x, col_h, col_bit] = self.config.advice;
+        let split = x.value().map(|x| {
+            let bit = F::from((x.to_repr().as_ref()[0] & 1) as u64);
+            let h = (*x - bit) * F::from(2).invert().unwrap();
+            (h, bit)
+        });
+
+        let (h, bit) = layouter.assign_region(
+            || "parity",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+                x.copy_advice(|| "x", &mut region, col_x, 0)?;
+                let h = region.assign_advice(|| "h", col_h, 0, || split.map(|(h, _)| h))?;
+                let bit =
+                    region.assign_advice(|| "bit", col_bit, 0, || split.map(|(_, bit)| bit))?;
+                Ok((h, bit))
+            },
+        )?;
+        self.range.range_check(layouter.namespace(|| "h"), &h)?;
+
+        Ok(AssignedBit::new(bit))
+    }
+}
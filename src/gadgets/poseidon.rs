@@ -0,0 +1,293 @@
+//! A Poseidon permutation of width 3, and the hashes of one and two
+//! elements built on it.
+//!
+//! The structure is Poseidon's: `x^5` S-boxes, 4 full rounds, 56 partial
+//! rounds with one S-box, and 4 full rounds again, each followed by a
+//! 3×3 MDS matrix. The constants are not the reference ones. The round
+//! constants are Blake2b outputs reduced into the field and the matrix is
+//! the Cauchy matrix `1 / (i + 3 + j)`, so hashes do not match other
+//! Poseidon implementations, and nobody has analysed this instance.
+//!
+//! Each round is one row: the state in three advice columns, the round's
+//! constants in three fixed columns, and a gate for full or partial rounds
+//! relating the row to the next. A permutation takes 65 rows, the last
+//! holding the output.
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Layouter, Value},
+    plonk::{Advice, Column, ConstraintSystem, Error, Expression, Fixed, Selector},
+    poly::Rotation,
+};
+
+use crate::fibonacci::ACell;
+
+/// State width.
+pub const WIDTH: usize = 3;
+
+pub const FULL_ROUNDS: usize = 8;
+
+pub const PARTIAL_ROUNDS: usize = 56;
+
+pub const ROUNDS: usize = FULL_ROUNDS + PARTIAL_ROUNDS;
+
+/// The capacity element of [`hash1`], telling its inputs apart from other
+/// uses of the permutation.
+const HASH1_DOMAIN: u64 = 1 << 32;
+
+/// The capacity element of [`hash2`].
+const HASH2_DOMAIN: u64 = 2 << 32;
+
+/// The constants of the permutation.
+#[derive(Debug, Clone)]
+pub struct Spec<F: FieldExt> {
+    pub round_constants: Vec<[F; WIDTH]>,
+    pub mds: [[F; WIDTH]; WIDTH],
+}
+
+impl<F: FieldExt> Spec<F> {
+    pub fn new() -> Self {
+        let round_constants = (0..ROUNDS)
+            .map(|round| {
+                [0, 1, 2].map(|lane| {
+                    let hash = blake2b_simd::Params::new()
+                        .hash_length(64)
+                        .personal(b"Fibo-Poseidon")
+                        .to_state()
+                        .update(&(round as u64).to_le_bytes())
+                        .update(&(lane as u64).to_le_bytes())
+                        .finalize();
+                    F::from_bytes_wide(hash.as_array())
+                })
+            })
+            .collect();
+        let mds =
+            [0, 1, 2].map(|i| [0, 1, 2].map(|j| F::from((i + WIDTH + j) as u64).invert().unwrap()));
+        Spec {
+            round_constants,
+            mds,
+        }
+    }
+
+    fn is_full(round: usize) -> bool {
+        !(FULL_ROUNDS / 2..FULL_ROUNDS / 2 + PARTIAL_ROUNDS).contains(&round)
+    }
+
+    /// One round on a native state.
+    fn round(&self, round: usize, state: [F; WIDTH]) -> [F; WIDTH] {
+        let mut added = [0, 1, 2].map(|i| state[i] + self.round_constants[round][i]);
+        for (i, x) in added.iter_mut().enumerate() {
+            if i == 0 || Self::is_full(round) {
+                *x = x.pow(&[5, 0, 0, 0]);
+            }
+        }
+        self.mds.map(|row| {
+            row.iter()
+                .zip(&added)
+                .fold(F::zero(), |acc, (m, x)| acc + *m * x)
+        })
+    }
+
+    pub fn permute(&self, state: [F; WIDTH]) -> [F; WIDTH] {
+        (0..ROUNDS).fold(state, |state, round| self.round(round, state))
+    }
+}
+
+impl<F: FieldExt> Default for Spec<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `H(x)`, the first state element after permuting `[x, 0, domain]`.
+pub fn hash1<F: FieldExt>(x: F) -> F {
+    Spec::new().permute([x, F::zero(), F::from(HASH1_DOMAIN)])[0]
+}
+
+/// `H(l, r)`, the first state element after permuting `[l, r, domain]`.
+pub fn hash2<F: FieldExt>(l: F, r: F) -> F {
+    Spec::new().permute([l, r, F::from(HASH2_DOMAIN)])[0]
+}
+
+#[derive(Debug, Clone)]
+pub struct PoseidonConfig<F: FieldExt> {
+    pub state: [Column<Advice>; WIDTH],
+    pub round_constants: [Column<Fixed>; WIDTH],
+    pub s_full: Selector,
+    pub s_partial: Selector,
+    spec: Spec<F>,
+}
+
+#[derive(Debug)]
+pub struct PoseidonChip<F: FieldExt> {
+    config: PoseidonConfig<F>,
+}
+
+impl<F: FieldExt> PoseidonChip<F> {
+    pub fn construct(config: PoseidonConfig<F>) -> Self {
+        Self { config }
+    }
+
+    /// [`PoseidonChip::hash1`] and [`PoseidonChip::hash2`] also need a
+    /// fixed column enabled for constants.
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        state: [Column<Advice>; WIDTH],
+    ) -> PoseidonConfig<F> {
+        for column in state {
+            meta.enable_equality(column);
+        }
+        let round_constants = [(); WIDTH].map(|_| meta.fixed_column());
+        let s_full = meta.selector();
+        let s_partial = meta.selector();
+        let spec = Spec::new();
+
+        let pow5 = |x: Expression<F>| {
+            let x2 = x.clone() * x.clone();
+            x2.clone() * x2 * x
+        };
+        let mds = spec.mds;
+        let mix = move |added: [Expression<F>; WIDTH], next: [Expression<F>; WIDTH]| {
+            (0..WIDTH)
+                .map(|i| {
+                    let mixed = (0..WIDTH)
+                        .map(|j| added[j].clone() * mds[i][j])
+                        .reduce(|a, b| a + b)
+                        .unwrap();
+                    next[i].clone() - mixed
+                })
+                .collect::<Vec<_>>()
+        };
+
+        meta.create_gate("poseidon full round", |meta| {
+            //
+            //  state       | round constants | selector
+            //  s0  s1  s2  |   c0  c1  c2    |    s
+            //  s0' s1' s2' |                 |
+            //
+            // s' = M · (s + c)^5
+            //
+            let s = meta.query_selector(s_full);
+            let added = [0, 1, 2].map(|i| {
+                pow5(
+                    meta.query_advice(state[i], Rotation::cur())
+                        + meta.query_fixed(round_constants[i], Rotation::cur()),
+                )
+            });
+            let next = state.map(|column| meta.query_advice(column, Rotation::next()));
+            mix(added, next)
+                .into_iter()
+                .map(|c| s.clone() * c)
+                .collect::<Vec<_>>()
+        });
+
+        meta.create_gate("poseidon partial round", |meta| {
+            // As a full round, with the S-box on s0 only.
+            let s = meta.query_selector(s_partial);
+            let added = [0, 1, 2].map(|i| {
+                let x = meta.query_advice(state[i], Rotation::cur())
+                    + meta.query_fixed(round_constants[i], Rotation::cur());
+                if i == 0 {
+                    pow5(x)
+                } else {
+                    x
+                }
+            });
+            let next = state.map(|column| meta.query_advice(column, Rotation::next()));
+            mix(added, next)
+                .into_iter()
+                .map(|c| s.clone() * c)
+                .collect::<Vec<_>>()
+        });
+
+        PoseidonConfig {
+            state,
+            round_constants,
+            s_full,
+            s_partial,
+            spec,
+        }
+    }
+
+    /// Permutes `input`, returning the output state.
+    pub fn permute(
+        &self,
+        mut layouter: impl Layouter<F>,
+        input: &[ACell<F>; WIDTH],
+    ) -> Result<[ACell<F>; WIDTH], Error> {
+        let config = &self.config;
+        let spec = &config.spec;
+
+        layouter.assign_region(
+            || "poseidon",
+            |mut region| {
+                let mut cells = Vec::with_capacity(WIDTH);
+                for (i, cell) in input.iter().enumerate() {
+                    cells.push(cell.copy_advice(|| "input", &mut region, config.state[i], 0)?);
+                }
+                let mut state = cells[0]
+                    .value()
+                    .zip(cells[1].value())
+                    .zip(cells[2].value())
+                    .map(|((s0, s1), s2)| [*s0, *s1, *s2]);
+
+                for round in 0..ROUNDS {
+                    if Spec::<F>::is_full(round) {
+                        config.s_full.enable(&mut region, round)?;
+                    } else {
+                        config.s_partial.enable(&mut region, round)?;
+                    }
+                    for (i, column) in config.round_constants.iter().enumerate() {
+                        let c = Value::known(spec.round_constants[round][i]);
+                        region.assign_fixed(|| "round constant", *column, round, || c)?;
+                    }
+
+                    state = state.map(|state| spec.round(round, state));
+                    cells = (0..WIDTH)
+                        .map(|i| {
+                            let v = state.map(|state| state[i]);
+                            region.assign_advice(|| "state", config.state[i], round + 1, || v)
+                        })
+                        .collect::<Result<_, _>>()?;
+                }
+
+                Ok(cells.try_into().expect("width"))
+            },
+        )
+    }
+
+    /// [`hash2`] of `l` and `r`.
+    pub fn hash2(
+        &self,
+        mut layouter: impl Layouter<F>,
+        l: &ACell<F>,
+        r: &ACell<F>,
+    ) -> Result<ACell<F>, Error> {
+        let domain = layouter.assign_region(
+            || "poseidon domain",
+            |mut region| {
+                let domain = F::from(HASH2_DOMAIN);
+                region.assign_advice_from_constant(|| "domain", self.config.state[2], 0, domain)
+            },
+        )?;
+        let [out, _, _] = self.permute(layouter, &[l.clone(), r.clone(), domain])?;
+        Ok(out)
+    }
+
+    /// [`hash1`] of `x`.
+    pub fn hash1(&self, mut layouter: impl Layouter<F>, x: &ACell<F>) -> Result<ACell<F>, Error> {
+        let (zero, domain) = layouter.assign_region(
+            || "poseidon domain",
+            |mut region| {
+                let [_, col_zero, col_domain] = self.config.state;
+                let zero = region.assign_advice_from_constant(|| "0", col_zero, 0, F::zero())?;
+                let domain = F::from(HASH1_DOMAIN);
+                let domain =
+                    region.assign_advice_from_constant(|| "domain", col_domain, 0, domain)?;
+                Ok((zero, domain))
+            },
+        )?;
+        let [out, _, _] = self.permute(layouter, &[x.clone(), zero, domain])?;
+        Ok(out)
+    }
+}
@@ -0,0 +1,145 @@
+//! [`simple_chip!`](crate::simple_chip) for chips that are one gate over
+//! one row of advice cells.
+//!
+//! Most small gadgets are the same forty lines around one expression: a
+//! config of named advice columns and a selector, a `configure` that
+//! enables equality on the columns and creates the gate, and a method that
+//! lays out one row in a region of its own. The macro writes those from
+//! the gate's name, its columns and its constraints, each of which is
+//! multiplied by the selector:
+//!
+//! ```
+//! use fibonacci_circuit::{expr, simple_chip};
+//!
+//! simple_chip! {
+//!     /// `x · x_inv = 1`.
+//!     pub struct NonZeroChip(NonZeroConfig);
+//!     gate "nonzero"(x, x_inv) => [x * x_inv - expr::constant(1)];
+//! }
+//! ```
+//!
+//! expands to a `NonZeroConfig { x, x_inv, selector }`, a `NonZeroChip<F>`
+//! with `construct` and `configure(meta, x, x_inv)`, and
+//! `assign_row(layouter, [x, x_inv])` taking an [`Operand`] per column and
+//! returning the row's cells. In the constraints each column name is its
+//! cell on the current row. The chip's own operations go in an `impl`
+//! block of their own beside it. Gates that reach other rows, or chips
+//! that embed other chips, are written out by hand.
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Region, Value},
+    plonk::{Advice, Column, Error},
+};
+
+use crate::fibonacci::ACell;
+
+/// A cell of a row being laid out: witnessed here, or copied from a cell
+/// assigned elsewhere.
+#[derive(Debug, Clone, Copy)]
+pub enum Operand<'a, F: FieldExt> {
+    Witness(Value<F>),
+    Cell(&'a ACell<F>),
+}
+
+impl<F: FieldExt> Operand<'_, F> {
+    pub fn assign(
+        self,
+        region: &mut Region<'_, F>,
+        annotation: &str,
+        column: Column<Advice>,
+        offset: usize,
+    ) -> Result<ACell<F>, Error> {
+        match self {
+            Operand::Witness(value) => {
+                region.assign_advice(|| annotation, column, offset, || value)
+            }
+            Operand::Cell(cell) => cell.copy_advice(|| annotation, region, column, offset),
+        }
+    }
+}
+
+/// Defines a chip whose single gate constrains one row of advice cells.
+/// See [`gadgets::simple`](crate::gadgets::simple).
+#[macro_export]
+macro_rules! simple_chip {
+    (@count) => { 0 };
+    (@count $head:ident $($tail:ident)*) => { 1 + $crate::simple_chip!(@count $($tail)*) };
+
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $chip:ident($config:ident);
+        gate $name:literal($($column:ident),+ $(,)?) => [$($constraint:expr),+ $(,)?];
+    ) => {
+        #[doc = concat!("The columns and selector of [`", stringify!($chip), "`].")]
+        #[derive(Debug, Clone)]
+        $vis struct $config {
+            $(pub $column: ::halo2_proofs::plonk::Column<::halo2_proofs::plonk::Advice>,)+
+            pub selector: ::halo2_proofs::plonk::Selector,
+        }
+
+        $(#[$attr])*
+        #[derive(Debug)]
+        $vis struct $chip<F: ::halo2_proofs::arithmetic::FieldExt> {
+            config: $config,
+            marker: ::std::marker::PhantomData<F>,
+        }
+
+        impl<F: ::halo2_proofs::arithmetic::FieldExt> $chip<F> {
+            pub fn construct(config: $config) -> Self {
+                Self {
+                    config,
+                    marker: ::std::marker::PhantomData,
+                }
+            }
+
+            /// Enables equality on every column.
+            pub fn configure(
+                meta: &mut ::halo2_proofs::plonk::ConstraintSystem<F>,
+                $($column: ::halo2_proofs::plonk::Column<::halo2_proofs::plonk::Advice>,)+
+            ) -> $config {
+                $(meta.enable_equality($column);)+
+
+                let selector = meta.selector();
+
+                meta.create_gate($name, |meta| {
+                    let s = meta.query_selector(selector);
+                    $(let $column = $crate::expr::advice(meta, $column, 0);)+
+
+                    vec![$(s.clone() * ($constraint)),+]
+                });
+
+                $config {
+                    $($column,)+
+                    selector,
+                }
+            }
+
+            /// Lays out one row of the gate in a region of its own, with an
+            /// operand per column in order, and returns its cells.
+            pub fn assign_row(
+                &self,
+                mut layouter: impl ::halo2_proofs::circuit::Layouter<F>,
+                operands: [$crate::gadgets::simple::Operand<'_, F>;
+                    $crate::simple_chip!(@count $($column)+)],
+            ) -> ::std::result::Result<
+                [$crate::fibonacci::ACell<F>; $crate::simple_chip!(@count $($column)+)],
+                ::halo2_proofs::plonk::Error,
+            > {
+                layouter.assign_region(
+                    || $name,
+                    |mut region| {
+                        self.config.selector.enable(&mut region, 0)?;
+                        let [$($column),+] = operands;
+                        Ok([$($column.assign(
+                            &mut region,
+                            stringify!($column),
+                            self.config.$column,
+                            0,
+                        )?),+])
+                    },
+                )
+            }
+        }
+    };
+}
@@ -0,0 +1,144 @@
+//! `x < RANGE` for a small `RANGE`, as `∏ (x - i) = 0` over `i < RANGE`,
+//! with the product split into chunks of `CHUNK` factors.
+//!
+//! With `CHUNK = RANGE` the whole product is one gate of degree
+//! `RANGE + 1`, the selector included. The prover's quotient has degree
+//! one less than the circuit's largest gate, so one such gate sets the
+//! extended domain (`2^k` times the next power of two at or above
+//! `RANGE`) and the number of quotient pieces in every proof, however
+//! cheap the rest of the circuit is. A smaller `CHUNK` carries the running
+//! product through `RANGE / CHUNK - 1` advice cells on the same row, each
+//! constrained to the previous one times the next chunk, so no gate
+//! exceeds degree `CHUNK + 2`, at the price of those cells.
+
+use std::marker::PhantomData;
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::Layouter,
+    plonk::{Advice, Column, ConstraintSystem, Error, Selector},
+};
+
+use crate::{expr, fibonacci::ACell};
+
+#[derive(Debug, Clone)]
+pub struct SmallRangeConfig<const RANGE: usize, const CHUNK: usize> {
+    pub x: Column<Advice>,
+    /// The running products after each chunk but the last.
+    pub partial: Vec<Column<Advice>>,
+    pub selector: Selector,
+}
+
+#[derive(Debug)]
+pub struct SmallRangeChip<F: FieldExt, const RANGE: usize, const CHUNK: usize> {
+    config: SmallRangeConfig<RANGE, CHUNK>,
+    marker: PhantomData<F>,
+}
+
+/// `∏ (x - i)` over the `i` of chunk `chunk`.
+fn chunk_product<T, const CHUNK: usize>(
+    x: &T,
+    chunk: usize,
+    mut sub: impl FnMut(&T, u64) -> T,
+    mul: impl Fn(T, T) -> T,
+) -> T {
+    let start = chunk * CHUNK;
+    (start + 1..start + CHUNK).fold(sub(x, start as u64), |acc, i| mul(acc, sub(x, i as u64)))
+}
+
+impl<F: FieldExt, const RANGE: usize, const CHUNK: usize> SmallRangeChip<F, RANGE, CHUNK> {
+    const CHUNKS: usize = RANGE / CHUNK;
+
+    pub fn construct(config: SmallRangeConfig<RANGE, CHUNK>) -> Self {
+        Self {
+            config,
+            marker: PhantomData,
+        }
+    }
+
+    /// Allocates the partial product columns next to `x`.
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        x: Column<Advice>,
+    ) -> SmallRangeConfig<RANGE, CHUNK> {
+        assert!(
+            CHUNK > 0 && RANGE.is_multiple_of(CHUNK),
+            "chunks of {} do not split a range of {}",
+            CHUNK,
+            RANGE
+        );
+        meta.enable_equality(x);
+        let partial: Vec<_> = (1..Self::CHUNKS).map(|_| meta.advice_column()).collect();
+        let selector = meta.selector();
+
+        meta.create_gate("small range", |meta| {
+            //
+            //  x | partial[0] .. partial[m-2] | selector
+            //  x |    p_0     ..    p_{m-2}   |    s
+            //
+            let s = meta.query_selector(selector);
+            let x = expr::advice(meta, x, 0);
+            let partial: Vec<_> = partial
+                .iter()
+                .map(|&column| expr::advice(meta, column, 0))
+                .collect();
+            let product = |chunk| {
+                chunk_product::<_, CHUNK>(
+                    &x,
+                    chunk,
+                    |x, i| x.clone() - expr::constant(i),
+                    |a, b| a * b,
+                )
+            };
+
+            let mut constraints = vec![];
+            let mut running = None;
+            for (chunk, p) in partial.iter().enumerate() {
+                let next = match running {
+                    None => product(chunk),
+                    Some(prev) => prev * product(chunk),
+                };
+                constraints.push(s.clone() * (p.clone() - next));
+                running = Some(p.clone());
+            }
+            let last = product(Self::CHUNKS - 1);
+            constraints.push(match running {
+                None => s * last,
+                Some(prev) => s * prev * last,
+            });
+            constraints
+        });
+
+        SmallRangeConfig {
+            x,
+            partial,
+            selector,
+        }
+    }
+
+    /// Constrains `x` to be below `RANGE`; unsatisfiable otherwise.
+    pub fn range_check(&self, mut layouter: impl Layouter<F>, x: &ACell<F>) -> Result<(), Error> {
+        layouter.assign_region(
+            || "small range",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+                x.copy_advice(|| "x", &mut region, self.config.x, 0)?;
+
+                let value = x.value().copied();
+                let mut running = None;
+                for (chunk, &column) in self.config.partial.iter().enumerate() {
+                    let product = value.map(|x| {
+                        chunk_product::<_, CHUNK>(&x, chunk, |x, i| *x - F::from(i), |a, b| a * b)
+                    });
+                    let p = match running {
+                        None => product,
+                        Some(prev) => prev * product,
+                    };
+                    region.assign_advice(|| "partial product", column, 0, || p)?;
+                    running = Some(p);
+                }
+                Ok(())
+            },
+        )
+    }
+}
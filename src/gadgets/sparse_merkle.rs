@@ -0,0 +1,282 @@
+//! Membership and non-membership in a sparse Merkle tree.
+//!
+//! A tree of depth `D` has a leaf for every key below `2^D`, almost all of
+//! them empty. A leaf holds its value, `0` meaning empty, and a node is
+//! [`hash2`] of its children. The root of an empty subtree depends only on
+//! its level, `default_0 = 0` and `default_{i+1} = H(default_i,
+//! default_i)`, so those are precomputed into a fixed column and a path
+//! only witnesses the siblings that are not empty. One proof covers both
+//! statements: the leaf at `key` is `value`, and `value = 0` says `key` is
+//! absent.
+//!
+//! The key is split into `D` bits by a running sum `z_i = key >> i` that
+//! must end at `0`, so keys of `2^D` or more have no proof. Each level is
+//! one row
+//!
+//! ```text
+//!   node | sibling | bit | is_default | left | right | default
+//! ```
+//!
+//! with the sibling taken from the fixed column when `is_default` is set,
+//! and `(left, right)` the node and sibling swapped when the key bit is 1,
+//! followed by a [`PoseidonChip`] permutation for the parent.
+
+use std::collections::BTreeMap;
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Layouter, Value},
+    plonk::{Advice, Column, ConstraintSystem, Error, Fixed, Selector},
+    poly::Rotation,
+};
+
+use super::poseidon::{hash2, PoseidonChip, PoseidonConfig};
+use crate::{expr, fibonacci::ACell};
+
+/// The roots of empty subtrees of heights `0..=depth`.
+pub fn defaults<F: FieldExt>(depth: usize) -> Vec<F> {
+    let mut defaults = vec![F::zero()];
+    for level in 0..depth {
+        defaults.push(hash2(defaults[level], defaults[level]));
+    }
+    defaults
+}
+
+/// A sparse Merkle tree computed natively, for building witnesses.
+#[derive(Debug, Clone)]
+pub struct SparseMerkleTree<F: FieldExt> {
+    depth: usize,
+    leaves: BTreeMap<u64, F>,
+    defaults: Vec<F>,
+}
+
+impl<F: FieldExt> SparseMerkleTree<F> {
+    pub fn new(depth: usize) -> Self {
+        assert!(depth < 64, "depth {} leaves no room for the keys", depth);
+        SparseMerkleTree {
+            depth,
+            leaves: BTreeMap::new(),
+            defaults: defaults(depth),
+        }
+    }
+
+    /// Sets the leaf at `key`; a `value` of 0 empties it.
+    pub fn insert(&mut self, key: u64, value: F) {
+        assert!(key >> self.depth == 0, "key {} is out of range", key);
+        if value == F::zero() {
+            self.leaves.remove(&key);
+        } else {
+            self.leaves.insert(key, value);
+        }
+    }
+
+    pub fn get(&self, key: u64) -> F {
+        self.leaves.get(&key).copied().unwrap_or(F::zero())
+    }
+
+    /// The non-empty nodes of each level, leaves first.
+    fn levels(&self) -> Vec<BTreeMap<u64, F>> {
+        let mut levels = vec![self.leaves.clone()];
+        for level in 0..self.depth {
+            let nodes = &levels[level];
+            let default = self.defaults[level];
+            let parents = nodes
+                .keys()
+                .map(|index| index >> 1)
+                .collect::<std::collections::BTreeSet<_>>()
+                .into_iter()
+                .map(|parent| {
+                    let child = |index| nodes.get(&index).copied().unwrap_or(default);
+                    (parent, hash2(child(2 * parent), child(2 * parent + 1)))
+                })
+                .collect();
+            levels.push(parents);
+        }
+        levels
+    }
+
+    pub fn root(&self) -> F {
+        let levels = self.levels();
+        levels[self.depth]
+            .get(&0)
+            .copied()
+            .unwrap_or(self.defaults[self.depth])
+    }
+
+    /// The siblings from the leaf at `key` up, `None` for an empty subtree.
+    pub fn path(&self, key: u64) -> Vec<Option<F>> {
+        let levels = self.levels();
+        (0..self.depth)
+            .map(|level| levels[level].get(&((key >> level) ^ 1)).copied())
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SparseMerkleConfig<F: FieldExt> {
+    /// `node, sibling, bit, is_default, left, right`; the key's running sum
+    /// reuses `node` and `bit`.
+    pub advice: [Column<Advice>; 6],
+    /// `default_i` on the row of level `i`.
+    pub defaults: Column<Fixed>,
+    pub s_level: Selector,
+    pub s_key: Selector,
+    pub poseidon: PoseidonConfig<F>,
+}
+
+#[derive(Debug)]
+pub struct SparseMerkleChip<F: FieldExt> {
+    config: SparseMerkleConfig<F>,
+    poseidon: PoseidonChip<F>,
+}
+
+impl<F: FieldExt> SparseMerkleChip<F> {
+    pub fn construct(config: SparseMerkleConfig<F>) -> Self {
+        Self {
+            poseidon: PoseidonChip::construct(config.poseidon.clone()),
+            config,
+        }
+    }
+
+    /// Needs a fixed column enabled for constants.
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 6],
+    ) -> SparseMerkleConfig<F> {
+        for column in advice {
+            meta.enable_equality(column);
+        }
+        let defaults = meta.fixed_column();
+        let s_level = meta.selector();
+        let s_key = meta.selector();
+        let one = || expr::constant(1);
+
+        meta.create_gate("sparse merkle level", |meta| {
+            let [node, sibling, bit, is_default, left, right] = expr::advice_row(meta, advice);
+            let default = meta.query_fixed(defaults, Rotation::cur());
+            let s = meta.query_selector(s_level);
+
+            let sibling = is_default.clone() * default + (one() - is_default.clone()) * sibling;
+            vec![
+                s.clone() * expr::bool_check(is_default),
+                s.clone() * (left.clone() - node.clone() - bit * (sibling.clone() - node.clone())),
+                s * (right - node - sibling + left),
+            ]
+        });
+
+        meta.create_gate("sparse merkle key", |meta| {
+            //
+            //  node  | bit
+            //  z_i   | b_i
+            //  z_i+1 |
+            //
+            let z = meta.query_advice(advice[0], Rotation::cur());
+            let z_next = meta.query_advice(advice[0], Rotation::next());
+            let bit = meta.query_advice(advice[2], Rotation::cur());
+            let s = meta.query_selector(s_key);
+
+            vec![
+                s.clone() * (z - z_next * F::from(2) - bit.clone()),
+                s * bit.clone() * (one() - bit),
+            ]
+        });
+
+        SparseMerkleConfig {
+            advice,
+            defaults,
+            s_level,
+            s_key,
+            poseidon: PoseidonChip::configure(meta, [advice[0], advice[1], advice[2]]),
+        }
+    }
+
+    /// Splits `key` into `depth` bits, least significant first.
+    fn key_bits(
+        &self,
+        mut layouter: impl Layouter<F>,
+        key: &ACell<F>,
+        depth: usize,
+    ) -> Result<Vec<ACell<F>>, Error> {
+        let [col_z, _, col_bit, ..] = self.config.advice;
+        let low = key.value().map(|key| key.get_lower_128());
+
+        layouter.assign_region(
+            || "sparse merkle key",
+            |mut region| {
+                key.copy_advice(|| "z_0", &mut region, col_z, 0)?;
+                let mut bits = vec![];
+                for row in 0..depth {
+                    self.config.s_key.enable(&mut region, row)?;
+                    let bit = low.map(|low| F::from((low >> row & 1) as u64));
+                    bits.push(region.assign_advice(|| "bit", col_bit, row, || bit)?);
+                    let z = low.map(|low| F::from_u128(low >> (row + 1)));
+                    let z = region.assign_advice(|| "z", col_z, row + 1, || z)?;
+                    if row + 1 == depth {
+                        region.constrain_constant(z.cell(), F::zero())?;
+                    }
+                }
+                Ok(bits)
+            },
+        )
+    }
+
+    /// The root of the tree whose leaf at `key` is `leaf`, with `path` the
+    /// siblings from the leaf up as [`SparseMerkleTree::path`] returns them.
+    pub fn root(
+        &self,
+        mut layouter: impl Layouter<F>,
+        key: &ACell<F>,
+        leaf: &ACell<F>,
+        path: &[Value<Option<F>>],
+    ) -> Result<ACell<F>, Error> {
+        let [col_node, col_sibling, col_bit, col_default, col_left, col_right] = self.config.advice;
+        let defaults = defaults::<F>(path.len());
+        let bits = self.key_bits(layouter.namespace(|| "key"), key, path.len())?;
+
+        let mut node = leaf.clone();
+        for (level, (sibling, bit)) in path.iter().zip(&bits).enumerate() {
+            let default = defaults[level];
+            let (left, right) = layouter.assign_region(
+                || "sparse merkle level",
+                |mut region| {
+                    self.config.s_level.enable(&mut region, 0)?;
+                    region.assign_fixed(
+                        || "default",
+                        self.config.defaults,
+                        0,
+                        || Value::known(default),
+                    )?;
+                    node.copy_advice(|| "node", &mut region, col_node, 0)?;
+                    bit.copy_advice(|| "bit", &mut region, col_bit, 0)?;
+                    let value = sibling.map(|sibling| sibling.unwrap_or(default));
+                    let witnessed = sibling.map(|sibling| sibling.unwrap_or(F::zero()));
+                    region.assign_advice(|| "sibling", col_sibling, 0, || witnessed)?;
+                    let is_default = sibling.map(|sibling| F::from(sibling.is_none() as u64));
+                    region.assign_advice(|| "is_default", col_default, 0, || is_default)?;
+
+                    let pair =
+                        node.value()
+                            .zip(bit.value())
+                            .zip(value)
+                            .map(|((node, bit), sibling)| {
+                                if *bit == F::one() {
+                                    (sibling, *node)
+                                } else {
+                                    (*node, sibling)
+                                }
+                            });
+                    let left =
+                        region.assign_advice(|| "left", col_left, 0, || pair.map(|(l, _)| l))?;
+                    let right =
+                        region.assign_advice(|| "right", col_right, 0, || pair.map(|(_, r)| r))?;
+                    Ok((left, right))
+                },
+            )?;
+            node = self
+                .poseidon
+                .hash2(layouter.namespace(|| "parent"), &left, &right)?;
+        }
+
+        Ok(node)
+    }
+}
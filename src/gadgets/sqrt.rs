@@ -0,0 +1,131 @@
+//! Square roots, with a flag for non-residues.
+//!
+//! For `x` a square the gadget witnesses `r` with `r² = x`. Otherwise it
+//! sets `non_residue` and witnesses `r` with `r² = g·x` instead, where `g`
+//! is the field's multiplicative generator, itself a non-residue, so `g·x`
+//! is a square exactly when `x` is not. The flag cannot be set for `x = 0`
+//! (`r` must then be invertible), so the flag is determined by `x`. The root
+//! is either of `±r`; the gadget does not pick a sign.
+
+use std::marker::PhantomData;
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::Layouter,
+    plonk::{Advice, Column, ConstraintSystem, Error, Expression, Selector},
+};
+
+use super::AssignedBit;
+use crate::{expr, fibonacci::ACell};
+
+#[derive(Debug, Clone)]
+pub struct SqrtConfig {
+    pub x: Column<Advice>,
+    pub root: Column<Advice>,
+    pub non_residue: Column<Advice>,
+    /// Inverse of the root, only needed when `non_residue` is set.
+    pub root_inv: Column<Advice>,
+    pub selector: Selector,
+}
+
+#[derive(Debug)]
+pub struct SquareRoot<F: FieldExt> {
+    /// `√x`, or `√(g·x)` when `non_residue` is set.
+    pub root: ACell<F>,
+    pub non_residue: AssignedBit<F>,
+}
+
+#[derive(Debug)]
+pub struct SqrtChip<F: FieldExt> {
+    config: SqrtConfig,
+    marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> SqrtChip<F> {
+    pub fn construct(config: SqrtConfig) -> Self {
+        Self {
+            config,
+            marker: PhantomData,
+        }
+    }
+
+    pub fn configure(meta: &mut ConstraintSystem<F>, advice: [Column<Advice>; 4]) -> SqrtConfig {
+        let [x, root, non_residue, root_inv] = advice;
+        meta.enable_equality(x);
+        meta.enable_equality(root);
+        meta.enable_equality(non_residue);
+
+        let selector = meta.selector();
+
+        meta.create_gate("sqrt", |meta| {
+            //
+            //  x | root | non_residue | root_inv | selector
+            //  x |  r   |      n      |   1/r    |    s
+            //
+            let [x, r, n, r_inv] = expr::advice_row(meta, advice);
+            let s = meta.query_selector(selector);
+            let one = expr::constant(1);
+            let g_minus_one = Expression::Constant(F::multiplicative_generator() - F::one());
+
+            vec![
+                s.clone() * expr::bool_check(n.clone()),
+                s.clone() * (r.clone() * r.clone() - x * (one.clone() + n.clone() * g_minus_one)),
+                s * n * (r * r_inv - one),
+            ]
+        });
+
+        SqrtConfig {
+            x,
+            root,
+            non_residue,
+            root_inv,
+            selector,
+        }
+    }
+
+    pub fn sqrt(
+        &self,
+        mut layouter: impl Layouter<F>,
+        x: &ACell<F>,
+    ) -> Result<SquareRoot<F>, Error> {
+        let witness = x.value().map(|x| witness(*x));
+
+        layouter.assign_region(
+            || "sqrt",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+                x.copy_advice(|| "x", &mut region, self.config.x, 0)?;
+
+                let root = witness.map(|(root, _)| root);
+                let non_residue = witness.map(|(_, n)| if n { F::one() } else { F::zero() });
+                let root_inv = root.map(|root| root.invert().unwrap_or(F::zero()));
+
+                let root = region.assign_advice(|| "root", self.config.root, 0, || root)?;
+                let non_residue = region.assign_advice(
+                    || "non residue",
+                    self.config.non_residue,
+                    0,
+                    || non_residue,
+                )?;
+                region.assign_advice(|| "root inv", self.config.root_inv, 0, || root_inv)?;
+
+                Ok(SquareRoot {
+                    root,
+                    non_residue: AssignedBit::new(non_residue),
+                })
+            },
+        )
+    }
+}
+
+/// The root the gadget witnesses for `x`, and whether `x` is a non-residue.
+pub fn witness<F: FieldExt>(x: F) -> (F, bool) {
+    match Option::from(x.sqrt()) {
+        Some(root) => (root, false),
+        None => {
+            // g·x is a square when x is not.
+            let root = (x * F::multiplicative_generator()).sqrt().unwrap();
+            (root, true)
+        }
+    }
+}
@@ -0,0 +1,141 @@
+//! Field elements checked to fit in 64 bits.
+//!
+//! The range check is a running sum over one advice column: row `i` holds
+//! `z_i = x >> i`, so `z_0 = x`, each `z_i - 2·z_{i+1}` must be a bit and
+//! `z_64` must be zero. That costs 65 rows and no lookup table.
+//! [`U64Chip::range_check_bits`] stops the running sum early for a tighter
+//! bound, `bits + 1` rows for `x < 2^bits`.
+
+use std::marker::PhantomData;
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Layouter, Value},
+    plonk::{Advice, Column, ConstraintSystem, Error, Selector},
+    poly::Rotation,
+};
+
+use crate::{expr, fibonacci::ACell};
+
+pub const BITS: usize = 64;
+
+/// A cell constrained to hold a value below `2^64`.
+#[derive(Debug, Clone)]
+pub struct AssignedU64<F: FieldExt>(ACell<F>);
+
+impl<F: FieldExt> AssignedU64<F> {
+    pub fn cell(&self) -> &ACell<F> {
+        &self.0
+    }
+
+    /// The low 64 bits of the witness, which is the whole value whenever the
+    /// circuit is satisfied.
+    pub fn value(&self) -> Value<u64> {
+        self.0.value().map(|v| v.get_lower_128() as u64)
+    }
+}
+
+/// The integer value of a field element, if it is below `2^64`.
+pub fn to_u64<F: FieldExt>(value: F) -> Option<u64> {
+    let lower = value.get_lower_128();
+    (F::from_u128(lower) == value)
+        .then_some(lower)
+        .and_then(|v| u64::try_from(v).ok())
+}
+
+#[derive(Debug, Clone)]
+pub struct U64Config {
+    /// The running sum `z_i`.
+    pub z: Column<Advice>,
+    /// Enabled on rows `0..64`, or `0..bits` for a tighter bound.
+    pub s_bit: Selector,
+    /// Enabled on row `64`, or `bits`.
+    pub s_end: Selector,
+}
+
+#[derive(Debug)]
+pub struct U64Chip<F: FieldExt> {
+    config: U64Config,
+    marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> U64Chip<F> {
+    pub fn construct(config: U64Config) -> Self {
+        Self {
+            config,
+            marker: PhantomData,
+        }
+    }
+
+    pub fn configure(meta: &mut ConstraintSystem<F>, z: Column<Advice>) -> U64Config {
+        meta.enable_equality(z);
+
+        let s_bit = meta.selector();
+        let s_end = meta.selector();
+
+        meta.create_gate("u64 bit", |meta| {
+            //
+            //  z     | s_bit
+            //  z_i   |   s
+            //  z_i+1 |
+            //
+            let z_cur = expr::advice(meta, z, 0);
+            let z_next = expr::advice(meta, z, 1);
+            let s = meta.query_selector(s_bit);
+
+            vec![s * expr::bool_check(z_cur - z_next * F::from(2))]
+        });
+
+        meta.create_gate("u64 end", |meta| {
+            let z = meta.query_advice(z, Rotation::cur());
+            let s = meta.query_selector(s_end);
+
+            vec![s * z]
+        });
+
+        U64Config { z, s_bit, s_end }
+    }
+
+    /// Constrains `x` to be below `2^64`. The circuit is unsatisfiable if it
+    /// is not.
+    pub fn range_check(
+        &self,
+        layouter: impl Layouter<F>,
+        x: &ACell<F>,
+    ) -> Result<AssignedU64<F>, Error> {
+        self.range_check_bits(layouter, x, BITS).map(AssignedU64)
+    }
+
+    /// Constrains `x` to be below `2^bits`, for `bits` up to 64, returning
+    /// the copy of `x` in the running-sum column.
+    pub fn range_check_bits(
+        &self,
+        mut layouter: impl Layouter<F>,
+        x: &ACell<F>,
+        bits: usize,
+    ) -> Result<ACell<F>, Error> {
+        assert!(bits <= BITS, "at most {} bits", BITS);
+        // Only the low 64 bits are decomposed; any higher bits leave row 0
+        // inconsistent with row 1.
+        let low = x.value().map(|x| x.get_lower_128() as u64);
+
+        layouter.assign_region(
+            || "u64 range check",
+            |mut region| {
+                let z_0 = x.copy_advice(|| "z_0", &mut region, self.config.z, 0)?;
+                for row in 0..bits {
+                    self.config.s_bit.enable(&mut region, row)?;
+                    if row > 0 {
+                        let z = low.map(|low| F::from(low >> row));
+                        region.assign_advice(|| "z", self.config.z, row, || z)?;
+                    }
+                }
+                self.config.s_end.enable(&mut region, bits)?;
+                let zero = || Value::known(F::zero());
+                region.assign_advice(|| format!("z_{}", bits), self.config.z, bits, zero)?;
+
+                Ok(z_0)
+            },
+        )
+    }
+}
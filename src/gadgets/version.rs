@@ -0,0 +1,125 @@
+//! A circuit version fixed into the verifying key and checked against the
+//! verifier's expectation.
+//!
+//! The version is assigned to a fixed column, so it is part of the key:
+//! circuits of different versions have different fingerprints even when
+//! their gates agree. The fixed cell is also copied to row 0 of an
+//! instance column of its own, so a verifier states the version it
+//! expects, and a proof made for any other is rejected with the public
+//! inputs rather than by comparing keys out of band.
+//!
+//! [`Versioned`] adds both columns to any circuit, after the circuit's
+//! own, so its other instance columns keep their rows.
+
+use std::marker::PhantomData;
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Layouter, Value},
+    plonk::{Circuit, Column, ConstraintSystem, Error, Fixed, Instance},
+};
+
+/// The version of the circuits in this crate that carry one.
+pub const CIRCUIT_VERSION: u64 = 1;
+
+#[derive(Debug, Clone)]
+pub struct VersionConfig {
+    fixed: Column<Fixed>,
+    instance: Column<Instance>,
+}
+
+#[derive(Debug)]
+pub struct VersionChip<F: FieldExt> {
+    config: VersionConfig,
+    marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> VersionChip<F> {
+    pub fn construct(config: VersionConfig) -> Self {
+        Self {
+            config,
+            marker: PhantomData,
+        }
+    }
+
+    /// Enables equality on both columns.
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        fixed: Column<Fixed>,
+        instance: Column<Instance>,
+    ) -> VersionConfig {
+        meta.enable_equality(fixed);
+        meta.enable_equality(instance);
+        VersionConfig { fixed, instance }
+    }
+
+    /// Fixes `version` and constrains it to instance row 0.
+    pub fn assign(&self, mut layouter: impl Layouter<F>, version: u64) -> Result<(), Error> {
+        let cell = layouter.assign_region(
+            || "circuit version",
+            |mut region| {
+                region.assign_fixed(
+                    || "version",
+                    self.config.fixed,
+                    0,
+                    || Value::known(F::from(version)),
+                )
+            },
+        )?;
+        layouter.constrain_instance(cell.cell(), self.config.instance, 0)
+    }
+}
+
+/// `circuit` with `version` fixed into it.
+#[derive(Debug, Clone, Default)]
+pub struct Versioned<C> {
+    pub circuit: C,
+    pub version: u64,
+}
+
+impl<C> Versioned<C> {
+    /// `circuit` at [`CIRCUIT_VERSION`].
+    pub fn new(circuit: C) -> Self {
+        Versioned {
+            circuit,
+            version: CIRCUIT_VERSION,
+        }
+    }
+}
+
+/// The instance columns of a [`Versioned`] circuit: the circuit's own,
+/// then the version.
+pub fn instances<F: FieldExt>(mut instances: Vec<Vec<F>>, version: u64) -> Vec<Vec<F>> {
+    instances.push(vec![F::from(version)]);
+    instances
+}
+
+impl<F: FieldExt, C: Circuit<F>> Circuit<F> for Versioned<C> {
+    type Config = (C::Config, VersionConfig);
+
+    type FloorPlanner = C::FloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Versioned {
+            circuit: self.circuit.without_witnesses(),
+            version: self.version,
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let config = C::configure(meta);
+        let fixed = meta.fixed_column();
+        let instance = meta.instance_column();
+        (config, VersionChip::configure(meta, fixed, instance))
+    }
+
+    fn synthesize(
+        &self,
+        (config, version): Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        self.circuit
+            .synthesize(config, layouter.namespace(|| "circuit"))?;
+        VersionChip::construct(version).assign(layouter.namespace(|| "version"), self.version)
+    }
+}
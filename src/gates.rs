@@ -0,0 +1,90 @@
+//! Per-gate listing of a circuit's custom constraints.
+
+use std::fmt;
+
+use halo2_proofs::{pasta::Fp, plonk::Circuit};
+use serde::Serialize;
+
+use crate::introspect::{ConstraintSystemInfo, Query};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GateReport {
+    pub circuit: String,
+    pub gates: Vec<GateSummary>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GateSummary {
+    pub name: String,
+    pub constraints: Vec<ConstraintSummary>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ConstraintSummary {
+    pub name: String,
+    pub expression: String,
+    pub degree: usize,
+    pub selectors: Vec<usize>,
+    pub queries: Vec<Query>,
+}
+
+impl GateReport {
+    /// Lists the gates of `C`, keeping only those whose name contains
+    /// `filter` when one is given.
+    pub fn collect<C: Circuit<Fp>>(name: &str, filter: Option<&str>) -> Self {
+        let cs = ConstraintSystemInfo::of::<C>();
+        let gates = cs
+            .gates
+            .into_iter()
+            .filter(|gate| filter.is_none_or(|filter| gate.name.contains(filter)))
+            .map(|gate| GateSummary {
+                name: gate.name,
+                constraints: gate
+                    .constraints
+                    .into_iter()
+                    .map(|constraint| ConstraintSummary {
+                        name: constraint.name,
+                        expression: constraint.poly.to_string(),
+                        degree: constraint.poly.degree(),
+                        selectors: constraint.poly.selectors().into_iter().collect(),
+                        queries: constraint.poly.queries().into_iter().collect(),
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        GateReport {
+            circuit: name.to_string(),
+            gates,
+        }
+    }
+}
+
+impl fmt::Display for GateReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "circuit: {}", self.circuit)?;
+        for gate in &self.gates {
+            writeln!(f, "{}:", gate.name)?;
+            for constraint in &gate.constraints {
+                if constraint.name.is_empty() {
+                    writeln!(f, "- {}", constraint.expression)?;
+                } else {
+                    writeln!(f, "- {}: {}", constraint.name, constraint.expression)?;
+                }
+                let queried: Vec<_> = constraint
+                    .selectors
+                    .iter()
+                    .map(|s| format!("S{}", s))
+                    .chain(constraint.queries.iter().map(Query::to_string))
+                    .collect();
+                writeln!(
+                    f,
+                    "  degree {}, queries {}",
+                    constraint.degree,
+                    queried.join(", ")
+                )?;
+            }
+        }
+        write!(f, "total gates: {}", self.gates.len())
+    }
+}
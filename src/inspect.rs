@@ -0,0 +1,190 @@
+//! Structural checks on a proof envelope that stop short of verifying it.
+
+use std::fmt;
+
+use halo2_proofs::{pasta::Fp, plonk::Circuit};
+use serde::Serialize;
+
+use crate::{
+    cost::CostReport,
+    envelope::ProofEnvelope,
+    fingerprint,
+    introspect::{format_field, ConstraintSystemInfo},
+    registry::{CircuitKind, CircuitVisitor},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Inspection {
+    pub circuit: String,
+    pub k: u32,
+    pub n: u32,
+    pub fingerprint: String,
+    pub transcript: &'static str,
+    pub instances: Vec<Vec<String>>,
+    pub proof_bytes: usize,
+    pub envelope_bytes: usize,
+    pub checks: Vec<Check>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Check {
+    pub name: &'static str,
+    pub passed: bool,
+    pub detail: String,
+}
+
+impl Inspection {
+    /// Describes `envelope` and checks it against what its circuit expects:
+    /// the circuit is known, is about `n`, and fits in `k`, the instance
+    /// columns have the right shape, and the proof has the length the cost
+    /// model predicts.
+    pub fn of(envelope: &ProofEnvelope) -> Self {
+        let envelope_bytes = envelope.to_bytes().len();
+        let mut checks = vec![];
+
+        match envelope.circuit.parse::<CircuitKind>() {
+            Err(e) => checks.push(Check::new("circuit", false, e.to_string())),
+            Ok(kind) => {
+                checks.push(Check::new("circuit", true, kind.to_string()));
+                checks.push(statement(kind, envelope));
+                checks.extend(kind.visit(Expectations { envelope }));
+            }
+        }
+
+        Inspection {
+            circuit: envelope.circuit.clone(),
+            k: envelope.k,
+            n: envelope.n,
+            fingerprint: fingerprint::to_hex(&envelope.fingerprint),
+            transcript: envelope.transcript.name(),
+            instances: envelope
+                .instances
+                .iter()
+                .map(|column| column.iter().copied().map(format_field).collect())
+                .collect(),
+            proof_bytes: envelope.proof.len(),
+            envelope_bytes,
+            checks,
+        }
+    }
+
+    pub fn passed(&self) -> bool {
+        self.checks.iter().all(|check| check.passed)
+    }
+}
+
+impl Check {
+    pub(crate) fn new(name: &'static str, passed: bool, detail: String) -> Self {
+        Check {
+            name,
+            passed,
+            detail,
+        }
+    }
+}
+
+/// Whether `envelope` claims the `n` that `kind` proves. `k` needs no
+/// check of its own: the verifying key, and so the fingerprint, depends on
+/// it.
+pub(crate) fn statement(kind: CircuitKind, envelope: &ProofEnvelope) -> Check {
+    let n = kind.n();
+    if envelope.n as usize == n {
+        Check::new(
+            "statement",
+            true,
+            format!("n = {} at k = {}", n, envelope.k),
+        )
+    } else {
+        Check::new(
+            "statement",
+            false,
+            format!(
+                "envelope claims n = {}, {} proves n = {}",
+                envelope.n, kind, n
+            ),
+        )
+    }
+}
+
+struct Expectations<'a> {
+    envelope: &'a ProofEnvelope,
+}
+
+impl CircuitVisitor for Expectations<'_> {
+    type Output = Vec<Check>;
+
+    fn visit<C: Circuit<Fp> + fmt::Debug>(self, circuit: C, instances: Vec<Vec<Fp>>) -> Vec<Check> {
+        let envelope = self.envelope;
+        let cs = ConstraintSystemInfo::of::<C>();
+        let mut checks = vec![];
+
+        let columns = envelope.instances.len();
+        checks.push(Check::new(
+            "instance columns",
+            columns == cs.num_instance_columns,
+            format!("{} present, {} expected", columns, cs.num_instance_columns),
+        ));
+
+        match CostReport::try_measure(&envelope.circuit, envelope.k, &circuit, instances) {
+            Err(e) => checks.push(Check::new(
+                "k",
+                false,
+                format!("circuit does not fit: {:?}", e),
+            )),
+            Ok(cost) => {
+                checks.push(Check::new(
+                    "k",
+                    true,
+                    format!("{} rows", 1u64 << envelope.k),
+                ));
+
+                let usable = (1usize << envelope.k) - (cost.blinding_factors + 1);
+                let longest = envelope.instances.iter().map(Vec::len).max().unwrap_or(0);
+                checks.push(Check::new(
+                    "instance rows",
+                    longest <= usable,
+                    format!("longest column {}, {} usable rows", longest, usable),
+                ));
+
+                checks.push(Check::new(
+                    "proof length",
+                    envelope.proof.len() == cost.proof.bytes,
+                    format!(
+                        "{} bytes, {} expected",
+                        envelope.proof.len(),
+                        cost.proof.bytes
+                    ),
+                ));
+            }
+        }
+
+        checks
+    }
+}
+
+impl fmt::Display for Inspection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "circuit: {}", self.circuit)?;
+        writeln!(f, "k: {}", self.k)?;
+        writeln!(f, "n: {}", self.n)?;
+        writeln!(f, "fingerprint: {}", self.fingerprint)?;
+        writeln!(f, "transcript: {}", self.transcript)?;
+        for (i, column) in self.instances.iter().enumerate() {
+            writeln!(f, "instance column {}: [{}]", i, column.join(", "))?;
+        }
+        writeln!(
+            f,
+            "size: {} proof bytes, {} envelope bytes",
+            self.proof_bytes, self.envelope_bytes
+        )?;
+        writeln!(f, "checks:")?;
+        for (i, check) in self.checks.iter().enumerate() {
+            let status = if check.passed { "ok" } else { "FAILED" };
+            write!(f, "- {:<16} {:<6} {}", check.name, status, check.detail)?;
+            if i + 1 < self.checks.len() {
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
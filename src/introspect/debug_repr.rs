@@ -0,0 +1,250 @@
+//! A parser for the output of `#[derive(Debug)]`.
+//!
+//! Rust's derived `Debug` output is regular enough to read back: structs,
+//! tuple structs, unit variants, lists, tuples, strings and numbers. That is
+//! all halo2_proofs' types ever print, so this is how the crate reaches
+//! fields that halo2_proofs 0.2 keeps `pub(crate)`.
+
+use std::{iter::Peekable, str::Chars};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Repr {
+    /// `Name { field: value, .. }`.
+    Struct {
+        name: String,
+        fields: Vec<(String, Repr)>,
+    },
+    /// `Name(value, ..)`, or a plain tuple when `name` is empty.
+    Tuple { name: String, items: Vec<Repr> },
+    /// `[value, ..]`.
+    List(Vec<Repr>),
+    /// A string literal, unescaped.
+    Str(String),
+    /// Anything else: numbers, booleans, unit variants, `PhantomData<..>`.
+    Atom(String),
+}
+
+impl Repr {
+    pub(crate) fn parse(s: &str) -> Result<Repr, String> {
+        let mut parser = Parser {
+            chars: s.chars().peekable(),
+        };
+        let repr = parser.value()?;
+        parser.skip_whitespace();
+        match parser.chars.next() {
+            None => Ok(repr),
+            Some(c) => Err(format!("trailing input at `{}`", c)),
+        }
+    }
+
+    /// The struct, tuple struct or unit variant name.
+    pub(crate) fn name(&self) -> &str {
+        match self {
+            Repr::Struct { name, .. } | Repr::Tuple { name, .. } | Repr::Atom(name) => name,
+            Repr::List(_) | Repr::Str(_) => "",
+        }
+    }
+
+    pub(crate) fn field(&self, name: &str) -> &Repr {
+        match self {
+            Repr::Struct { fields, .. } => fields
+                .iter()
+                .find(|(field, _)| field == name)
+                .map(|(_, value)| value)
+                .unwrap_or_else(|| panic!("no field `{}` in {:?}", name, self)),
+            _ => panic!("expected a struct with field `{}`, got {:?}", name, self),
+        }
+    }
+
+    /// The elements of a list or tuple.
+    pub(crate) fn items(&self) -> &[Repr] {
+        match self {
+            Repr::Tuple { items, .. } | Repr::List(items) => items,
+            _ => panic!("expected a list or tuple, got {:?}", self),
+        }
+    }
+
+    pub(crate) fn item(&self, index: usize) -> &Repr {
+        &self.items()[index]
+    }
+
+    pub(crate) fn as_str(&self) -> &str {
+        match self {
+            Repr::Str(s) | Repr::Atom(s) => s,
+            _ => panic!("expected a string, got {:?}", self),
+        }
+    }
+
+    pub(crate) fn as_usize(&self) -> usize {
+        self.as_str()
+            .parse()
+            .unwrap_or_else(|_| panic!("expected an unsigned integer, got {:?}", self))
+    }
+
+    pub(crate) fn as_i32(&self) -> i32 {
+        self.as_str()
+            .parse()
+            .unwrap_or_else(|_| panic!("expected an integer, got {:?}", self))
+    }
+}
+
+struct Parser<'a> {
+    chars: Peekable<Chars<'a>>,
+}
+
+impl Parser<'_> {
+    fn skip_whitespace(&mut self) {
+        while self.chars.peek().is_some_and(|c| c.is_whitespace()) {
+            self.chars.next();
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        self.skip_whitespace();
+        match self.chars.next() {
+            Some(c) if c == expected => Ok(()),
+            Some(c) => Err(format!("expected `{}`, found `{}`", expected, c)),
+            None => Err(format!("expected `{}`, found end of input", expected)),
+        }
+    }
+
+    /// Consumes `close` if it is next.
+    fn at_close(&mut self, close: char) -> bool {
+        self.skip_whitespace();
+        if self.chars.peek() == Some(&close) {
+            self.chars.next();
+            return true;
+        }
+        false
+    }
+
+    /// Consumes the `,` after an element, returning whether the sequence
+    /// ended with `close`.
+    fn separator(&mut self, close: char) -> Result<bool, String> {
+        self.skip_whitespace();
+        match self.chars.next() {
+            Some(',') => Ok(self.at_close(close)),
+            Some(c) if c == close => Ok(true),
+            Some(c) => Err(format!("expected `,` or `{}`, found `{}`", close, c)),
+            None => Err(format!("expected `{}`, found end of input", close)),
+        }
+    }
+
+    fn sequence(&mut self, close: char) -> Result<Vec<Repr>, String> {
+        let mut items = vec![];
+        if self.at_close(close) {
+            return Ok(items);
+        }
+        loop {
+            items.push(self.value()?);
+            if self.separator(close)? {
+                return Ok(items);
+            }
+        }
+    }
+
+    fn value(&mut self) -> Result<Repr, String> {
+        self.skip_whitespace();
+        match self.chars.peek() {
+            None => Err("unexpected end of input".to_string()),
+            Some('"') => self.string(),
+            Some('[') => {
+                self.chars.next();
+                Ok(Repr::List(self.sequence(']')?))
+            }
+            Some('(') => {
+                self.chars.next();
+                Ok(Repr::Tuple {
+                    name: String::new(),
+                    items: self.sequence(')')?,
+                })
+            }
+            Some(_) => {
+                let name = self.token()?;
+                match self.chars.peek() {
+                    Some('(') => {
+                        self.chars.next();
+                        Ok(Repr::Tuple {
+                            name,
+                            items: self.sequence(')')?,
+                        })
+                    }
+                    Some(' ') if name.chars().all(|c| c.is_alphanumeric() || c == '_') => {
+                        // `Name { .. }`; any other space ends the value.
+                        let mut lookahead = self.chars.clone();
+                        lookahead.next();
+                        if lookahead.peek() == Some(&'{') {
+                            self.chars.next();
+                            self.chars.next();
+                            Ok(Repr::Struct {
+                                name,
+                                fields: self.fields()?,
+                            })
+                        } else {
+                            Ok(Repr::Atom(name))
+                        }
+                    }
+                    _ => Ok(Repr::Atom(name)),
+                }
+            }
+        }
+    }
+
+    fn fields(&mut self) -> Result<Vec<(String, Repr)>, String> {
+        let mut fields = vec![];
+        if self.at_close('}') {
+            return Ok(fields);
+        }
+        loop {
+            self.skip_whitespace();
+            let name = self.token()?;
+            self.expect(':')?;
+            fields.push((name, self.value()?));
+            if self.separator('}')? {
+                return Ok(fields);
+            }
+        }
+    }
+
+    /// Reads an identifier or literal. Generic arguments are kept whole, so
+    /// `PhantomData<(A, B)>` is one token.
+    fn token(&mut self) -> Result<String, String> {
+        let mut token = String::new();
+        let mut depth = 0usize;
+        while let Some(&c) = self.chars.peek() {
+            match c {
+                '<' => depth += 1,
+                '>' if depth > 0 => depth -= 1,
+                c if depth == 0 && (c.is_whitespace() || ",:()[]{}\"".contains(c)) => break,
+                _ => {}
+            }
+            token.push(c);
+            self.chars.next();
+        }
+        if token.is_empty() {
+            Err(format!("unexpected `{}`", self.chars.peek().unwrap()))
+        } else {
+            Ok(token)
+        }
+    }
+
+    fn string(&mut self) -> Result<Repr, String> {
+        self.chars.next();
+        let mut s = String::new();
+        loop {
+            match self.chars.next() {
+                None => return Err("unterminated string".to_string()),
+                Some('"') => return Ok(Repr::Str(s)),
+                Some('\\') => match self.chars.next() {
+                    Some('n') => s.push('\n'),
+                    Some('t') => s.push('\t'),
+                    Some('r') => s.push('\r'),
+                    Some('0') => s.push('\0'),
+                    Some(c) => s.push(c),
+                    None => return Err("unterminated string".to_string()),
+                },
+                Some(c) => s.push(c),
+            }
+        }
+    }
+}
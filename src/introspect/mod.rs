@@ -0,0 +1,365 @@
+//! A read-only view of a circuit's constraint system.
+//!
+//! halo2_proofs 0.2 does not expose the gates, queries or permutation
+//! columns of a [`ConstraintSystem`]; everything here is rebuilt from its
+//! `Debug` output by [`debug_repr`].
+
+use std::{collections::BTreeSet, fmt};
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    pasta::{group::ff::PrimeField, Fp},
+    plonk::{Circuit, Column, ColumnType, ConstraintSystem, Selector},
+};
+use serde::Serialize;
+
+pub(crate) mod debug_repr;
+pub mod witness;
+
+use debug_repr::Repr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub enum ColumnKind {
+    Advice,
+    Fixed,
+    Instance,
+}
+
+/// A column, identified the way halo2 numbers them: by kind and index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub struct ColumnRef {
+    pub kind: ColumnKind,
+    pub index: usize,
+}
+
+/// A column queried at a rotation relative to the current row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub struct Query {
+    pub column: ColumnRef,
+    pub rotation: i32,
+}
+
+/// Mirror of `halo2_proofs::plonk::Expression` for `Fp`, with selectors
+/// reduced to their index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expr {
+    Constant(Fp),
+    Selector(usize),
+    Query(Query),
+    Negated(Box<Expr>),
+    Sum(Box<Expr>, Box<Expr>),
+    Product(Box<Expr>, Box<Expr>),
+    Scaled(Box<Expr>, Fp),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Constraint {
+    /// Empty when the gate did not name the constraint.
+    pub name: String,
+    pub poly: Expr,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Gate {
+    pub name: String,
+    pub constraints: Vec<Constraint>,
+}
+
+/// A lookup argument: the `inputs` tuple must appear in the `table` tuple.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lookup {
+    pub inputs: Vec<Expr>,
+    pub table: Vec<Expr>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConstraintSystemInfo {
+    pub num_fixed_columns: usize,
+    pub num_advice_columns: usize,
+    pub num_instance_columns: usize,
+    pub num_selectors: usize,
+    pub gates: Vec<Gate>,
+    pub advice_queries: Vec<Query>,
+    pub instance_queries: Vec<Query>,
+    pub fixed_queries: Vec<Query>,
+    /// Columns with equality enabled, in permutation order.
+    pub permutation_columns: Vec<ColumnRef>,
+    pub lookups: Vec<Lookup>,
+    /// Fixed columns enabled for global constants.
+    pub constants: Vec<ColumnRef>,
+}
+
+impl ConstraintSystemInfo {
+    /// Configures `C` and describes the resulting constraint system.
+    pub fn of<C: Circuit<Fp>>() -> Self {
+        let mut cs = ConstraintSystem::default();
+        C::configure(&mut cs);
+        Self::from_cs(&cs)
+    }
+
+    pub fn from_cs(cs: &ConstraintSystem<Fp>) -> Self {
+        let repr = Repr::parse(&format!("{:?}", cs)).expect("ConstraintSystem Debug output");
+
+        let queries = |name| -> Vec<Query> {
+            repr.field(name)
+                .items()
+                .iter()
+                .map(|query| Query {
+                    column: column_ref(query.item(0)),
+                    rotation: rotation(query.item(1)),
+                })
+                .collect()
+        };
+        let columns =
+            |repr: &Repr| -> Vec<ColumnRef> { repr.items().iter().map(column_ref).collect() };
+        let exprs =
+            |repr: &Repr| -> Vec<Expr> { repr.items().iter().map(Expr::from_repr).collect() };
+
+        ConstraintSystemInfo {
+            num_fixed_columns: repr.field("num_fixed_columns").as_usize(),
+            num_advice_columns: repr.field("num_advice_columns").as_usize(),
+            num_instance_columns: repr.field("num_instance_columns").as_usize(),
+            num_selectors: repr.field("num_selectors").as_usize(),
+            gates: repr
+                .field("gates")
+                .items()
+                .iter()
+                .map(|gate| Gate {
+                    name: gate.field("name").as_str().to_string(),
+                    constraints: gate
+                        .field("constraint_names")
+                        .items()
+                        .iter()
+                        .zip(gate.field("polys").items())
+                        .map(|(name, poly)| Constraint {
+                            name: name.as_str().to_string(),
+                            poly: Expr::from_repr(poly),
+                        })
+                        .collect(),
+                })
+                .collect(),
+            advice_queries: queries("advice_queries"),
+            instance_queries: queries("instance_queries"),
+            fixed_queries: queries("fixed_queries"),
+            permutation_columns: columns(repr.field("permutation").field("columns")),
+            lookups: repr
+                .field("lookups")
+                .items()
+                .iter()
+                .map(|lookup| Lookup {
+                    inputs: exprs(lookup.field("input_expressions")),
+                    table: exprs(lookup.field("table_expressions")),
+                })
+                .collect(),
+            constants: columns(repr.field("constants")),
+        }
+    }
+}
+
+impl Expr {
+    fn from_repr(repr: &Repr) -> Self {
+        let boxed = |index| Box::new(Expr::from_repr(repr.item(index)));
+        match repr.name() {
+            "Constant" => Expr::Constant(field_element(repr.item(0))),
+            "Selector" => Expr::Selector(repr.item(0).item(0).as_usize()),
+            kind @ ("Advice" | "Fixed" | "Instance") => Expr::Query(Query {
+                column: ColumnRef {
+                    kind: column_kind(kind),
+                    index: repr.field("column_index").as_usize(),
+                },
+                rotation: rotation(repr.field("rotation")),
+            }),
+            "Negated" => Expr::Negated(boxed(0)),
+            "Sum" => Expr::Sum(boxed(0), boxed(1)),
+            "Product" => Expr::Product(boxed(0), boxed(1)),
+            "Scaled" => Expr::Scaled(boxed(0), field_element(repr.item(1))),
+            other => panic!("unknown expression `{}`", other),
+        }
+    }
+
+    /// Degree of the expression, counting selectors as degree one like
+    /// halo2 does.
+    pub fn degree(&self) -> usize {
+        match self {
+            Expr::Constant(_) => 0,
+            Expr::Selector(_) | Expr::Query(_) => 1,
+            Expr::Negated(e) | Expr::Scaled(e, _) => e.degree(),
+            Expr::Sum(a, b) => a.degree().max(b.degree()),
+            Expr::Product(a, b) => a.degree() + b.degree(),
+        }
+    }
+
+    /// Every column query in the expression.
+    pub fn queries(&self) -> BTreeSet<Query> {
+        let mut queries = BTreeSet::new();
+        self.visit(&mut |e| {
+            if let Expr::Query(query) = e {
+                queries.insert(*query);
+            }
+        });
+        queries
+    }
+
+    /// Every selector index in the expression.
+    pub fn selectors(&self) -> BTreeSet<usize> {
+        let mut selectors = BTreeSet::new();
+        self.visit(&mut |e| {
+            if let Expr::Selector(selector) = e {
+                selectors.insert(*selector);
+            }
+        });
+        selectors
+    }
+
+    fn visit(&self, f: &mut impl FnMut(&Expr)) {
+        f(self);
+        match self {
+            Expr::Constant(_) | Expr::Selector(_) | Expr::Query(_) => {}
+            Expr::Negated(e) | Expr::Scaled(e, _) => e.visit(f),
+            Expr::Sum(a, b) | Expr::Product(a, b) => {
+                a.visit(f);
+                b.visit(f);
+            }
+        }
+    }
+}
+
+/// Formats a field element as a small signed integer where possible, and
+/// as hex otherwise.
+pub fn format_field(value: Fp) -> String {
+    let small = |v: Fp| {
+        let repr = v.to_repr();
+        repr[8..]
+            .iter()
+            .all(|b| *b == 0)
+            .then(|| v.get_lower_128() as u64)
+    };
+    if let Some(v) = small(value) {
+        v.to_string()
+    } else if let Some(v) = small(-value) {
+        format!("-{}", v)
+    } else {
+        let hex: String = value
+            .to_repr()
+            .iter()
+            .rev()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        format!("0x{}", hex.trim_start_matches('0'))
+    }
+}
+
+fn column_kind(name: &str) -> ColumnKind {
+    match name {
+        "Advice" => ColumnKind::Advice,
+        "Fixed" => ColumnKind::Fixed,
+        "Instance" => ColumnKind::Instance,
+        other => panic!("unknown column type `{}`", other),
+    }
+}
+
+/// `Column` keeps its index private; its `Debug` output has it.
+impl<C: ColumnType> From<Column<C>> for ColumnRef {
+    fn from(column: Column<C>) -> Self {
+        column_ref(&Repr::parse(&format!("{:?}", column)).expect("Column Debug output"))
+    }
+}
+
+/// The index of `selector`, which prints as `Selector(index, simple)`.
+pub fn selector_index(selector: &Selector) -> usize {
+    Repr::parse(&format!("{:?}", selector))
+        .expect("Selector Debug output")
+        .item(0)
+        .as_usize()
+}
+
+fn column_ref(repr: &Repr) -> ColumnRef {
+    ColumnRef {
+        kind: column_kind(repr.field("column_type").name()),
+        index: repr.field("index").as_usize(),
+    }
+}
+
+fn rotation(repr: &Repr) -> i32 {
+    repr.item(0).as_i32()
+}
+
+/// Parses the `0x`-prefixed big-endian hex that `Fp` prints as `Debug`.
+fn field_element(repr: &Repr) -> Fp {
+    let hex = repr.as_str().trim_start_matches("0x");
+    let mut bytes = [0u8; 32];
+    for (i, byte) in bytes.iter_mut().rev().enumerate() {
+        *byte = u8::from_str_radix(&hex[2 * i..2 * i + 2], 16).unwrap();
+    }
+    Fp::from_repr(bytes).unwrap()
+}
+
+impl fmt::Display for ColumnKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ColumnKind::Advice => "A",
+            ColumnKind::Fixed => "F",
+            ColumnKind::Instance => "I",
+        })
+    }
+}
+
+impl fmt::Display for ColumnRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.kind, self.index)
+    }
+}
+
+impl fmt::Display for Query {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}@{}", self.column, self.rotation)
+    }
+}
+
+/// Prints in the notation of `halo2_proofs::dev::CircuitGates`, e.g.
+/// `S0 * (A0@0 + A1@0 - A2@0)`.
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.render())
+    }
+}
+
+impl Expr {
+    fn render(&self) -> String {
+        self.render_with(&|_| None)
+    }
+
+    /// Renders like `Display`, except that leaves for which `leaf` returns
+    /// text are printed as that text.
+    pub fn render_with(&self, leaf: &dyn Fn(&Expr) -> Option<String>) -> String {
+        if let Some(text) = leaf(self) {
+            return text;
+        }
+        let group = |s: String| {
+            if s.contains(' ') {
+                format!("({})", s)
+            } else {
+                s
+            }
+        };
+        match self {
+            Expr::Constant(v) => format_field(*v),
+            Expr::Selector(s) => format!("S{}", s),
+            Expr::Query(query) => query.to_string(),
+            Expr::Negated(e) => format!("-{}", group(e.render_with(leaf))),
+            Expr::Sum(a, b) => {
+                let b = b.render_with(leaf);
+                match b.strip_prefix('-') {
+                    Some(b) => format!("{} - {}", a.render_with(leaf), b),
+                    None => format!("{} + {}", a.render_with(leaf), b),
+                }
+            }
+            Expr::Product(a, b) => format!(
+                "{} * {}",
+                group(a.render_with(leaf)),
+                group(b.render_with(leaf))
+            ),
+            Expr::Scaled(e, v) => format!("{} * {}", group(e.render_with(leaf)), format_field(*v)),
+        }
+    }
+}
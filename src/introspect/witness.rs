@@ -0,0 +1,473 @@
+//! Synthesizing a circuit into a recording of everything it assigned.
+//!
+//! [`Witness::synthesize`] runs the circuit's floor planner against an
+//! [`Assignment`] that keeps every cell value, selector, copy and region,
+//! the same information the [`MockProver`](halo2_proofs::dev::MockProver)
+//! keeps privately.
+
+use std::collections::BTreeMap;
+
+use halo2_proofs::{
+    circuit::Value,
+    pasta::Fp,
+    plonk::{
+        Advice, Any, Assigned, Assignment, Circuit, Column, ConstraintSystem, Error, Fixed,
+        FloorPlanner, Instance, Selector,
+    },
+};
+
+use super::{selector_index, ColumnKind, ColumnRef, ConstraintSystemInfo, Expr};
+
+/// One cell, by column and absolute row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CellRef {
+    pub column: ColumnRef,
+    pub row: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssignedCellInfo {
+    pub cell: CellRef,
+    pub annotation: String,
+    /// `None` when the circuit had no value for the cell.
+    pub value: Option<Fp>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegionInfo {
+    pub name: String,
+    /// First and last row the region assigned, if it assigned any.
+    pub rows: Option<(usize, usize)>,
+    /// Cells in assignment order.
+    pub cells: Vec<AssignedCellInfo>,
+    /// `(selector, row)` for every enabled selector.
+    pub selectors: Vec<(usize, usize)>,
+    /// Equality constraints added while the region was being assigned.
+    pub copies: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Witness {
+    pub k: u32,
+    pub cs: ConstraintSystemInfo,
+    /// Rows `0..usable_rows` may be assigned; the rest hold blinding
+    /// factors.
+    pub usable_rows: usize,
+    /// `advice[column][row]`, `None` where unassigned or unknown.
+    pub advice: Vec<Vec<Option<Fp>>>,
+    pub fixed: Vec<Vec<Option<Fp>>>,
+    pub instance: Vec<Vec<Fp>>,
+    /// `selectors[selector][row]`.
+    pub selectors: Vec<Vec<bool>>,
+    pub regions: Vec<RegionInfo>,
+    /// Equality constraints in the order they were added, instance links
+    /// included.
+    pub copies: Vec<(CellRef, CellRef)>,
+}
+
+impl Witness {
+    /// Synthesizes `circuit` at `k` against `instances`, failing like the
+    /// `MockProver` does when it does not fit.
+    pub fn synthesize<C: Circuit<Fp>>(
+        k: u32,
+        circuit: &C,
+        instances: Vec<Vec<Fp>>,
+    ) -> Result<Self, Error> {
+        Self::synthesize_with(k, circuit, instances, |_, _| {})
+    }
+
+    /// Like [`Witness::synthesize`], calling `on_region` with the witness so
+    /// far each time a region has been assigned.
+    pub fn synthesize_with<C: Circuit<Fp>>(
+        k: u32,
+        circuit: &C,
+        instances: Vec<Vec<Fp>>,
+        mut on_region: impl FnMut(&Witness, &RegionInfo),
+    ) -> Result<Self, Error> {
+        let n = 1usize << k;
+        let mut meta = ConstraintSystem::default();
+        let config = C::configure(&mut meta);
+        let cs = ConstraintSystemInfo::from_cs(&meta);
+        if n < meta.minimum_rows() {
+            return Err(Error::NotEnoughRowsAvailable { current_k: k });
+        }
+        if instances.len() != cs.num_instance_columns {
+            return Err(Error::InvalidInstances);
+        }
+        let usable_rows = n - (meta.blinding_factors() + 1);
+        let instance = instances
+            .into_iter()
+            .map(|mut column| {
+                if column.len() > usable_rows {
+                    return Err(Error::InstanceTooLarge);
+                }
+                column.resize(n, Fp::zero());
+                Ok(column)
+            })
+            .collect::<Result<_, _>>()?;
+
+        let mut witness = Witness {
+            k,
+            usable_rows,
+            advice: vec![vec![None; n]; cs.num_advice_columns],
+            fixed: vec![vec![None; n]; cs.num_fixed_columns],
+            instance,
+            selectors: vec![vec![false; n]; cs.num_selectors],
+            regions: vec![],
+            copies: vec![],
+            cs,
+        };
+        let constants = fixed_columns(&witness.cs.constants);
+        let mut recorder = Recorder {
+            witness: &mut witness,
+            region: None,
+            on_region: &mut on_region,
+        };
+        C::FloorPlanner::synthesize(&mut recorder, circuit, config, constants)?;
+        Ok(witness)
+    }
+
+    /// The first way `other` differs from `self`, as a sentence, checking
+    /// regions (names, rows, cells and selectors in order) and then every
+    /// cell, selector and copy. Two syntheses of one circuit should never
+    /// differ; if they do, keys and proofs made from it are not
+    /// reproducible.
+    pub fn difference(&self, other: &Witness) -> Option<String> {
+        if self.k != other.k || self.cs != other.cs {
+            return Some("the constraint systems differ".to_string());
+        }
+        if self.regions.len() != other.regions.len() {
+            return Some(format!(
+                "{} regions against {}",
+                self.regions.len(),
+                other.regions.len()
+            ));
+        }
+        for (index, (a, b)) in self.regions.iter().zip(&other.regions).enumerate() {
+            if a.name != b.name || a.rows != b.rows {
+                return Some(format!(
+                    "region {} is `{}` at {:?} against `{}` at {:?}",
+                    index, a.name, a.rows, b.name, b.rows
+                ));
+            }
+            if a.selectors != b.selectors {
+                return Some(format!(
+                    "region {} `{}` enables {:?} against {:?}",
+                    index, a.name, a.selectors, b.selectors
+                ));
+            }
+            let cells = |region: &RegionInfo| {
+                region
+                    .cells
+                    .iter()
+                    .map(|cell| (cell.cell, cell.value))
+                    .collect::<Vec<_>>()
+            };
+            if let Some((x, y)) = cells(a).into_iter().zip(cells(b)).find(|(x, y)| x != y) {
+                return Some(format!(
+                    "region {} `{}` assigns {}@{} = {:?} against {}@{} = {:?}",
+                    index, a.name, x.0.column, x.0.row, x.1, y.0.column, y.0.row, y.1
+                ));
+            }
+            if a.cells.len() != b.cells.len() {
+                return Some(format!(
+                    "region {} `{}` assigns {} cells against {}",
+                    index,
+                    a.name,
+                    a.cells.len(),
+                    b.cells.len()
+                ));
+            }
+        }
+        let columns = [
+            (ColumnKind::Advice, &self.advice, &other.advice),
+            (ColumnKind::Fixed, &self.fixed, &other.fixed),
+        ];
+        for (kind, a, b) in columns {
+            for (index, (a, b)) in a.iter().zip(b).enumerate() {
+                if let Some(row) = (0..a.len()).find(|row| a[*row] != b[*row]) {
+                    let column = ColumnRef { kind, index };
+                    return Some(format!(
+                        "{}@{} is {:?} against {:?}",
+                        column, row, a[row], b[row]
+                    ));
+                }
+            }
+        }
+        if self.selectors != other.selectors {
+            return Some("the enabled selectors differ".to_string());
+        }
+        if self.copies != other.copies {
+            return Some("the copy constraints differ".to_string());
+        }
+        None
+    }
+
+    pub fn rows(&self) -> usize {
+        1 << self.k
+    }
+
+    /// The value of `column` at `row`, `None` where unassigned.
+    pub fn value(&self, column: ColumnRef, row: usize) -> Option<Fp> {
+        match column.kind {
+            ColumnKind::Advice => self.advice[column.index][row],
+            ColumnKind::Fixed => self.fixed[column.index][row],
+            ColumnKind::Instance => Some(self.instance[column.index][row]),
+        }
+    }
+
+    /// Evaluates `expr` at `row`, rotations wrapping around like the
+    /// polynomials do. Unassigned cells count as zero.
+    pub fn evaluate(&self, expr: &Expr, row: usize) -> Fp {
+        match expr {
+            Expr::Constant(v) => *v,
+            Expr::Selector(s) => Fp::from(self.selectors[*s][row] as u64),
+            Expr::Query(query) => self
+                .value(query.column, self.rotate(row, query.rotation))
+                .unwrap_or(Fp::zero()),
+            Expr::Negated(e) => -self.evaluate(e, row),
+            Expr::Sum(a, b) => self.evaluate(a, row) + self.evaluate(b, row),
+            Expr::Product(a, b) => self.evaluate(a, row) * self.evaluate(b, row),
+            Expr::Scaled(e, v) => self.evaluate(e, row) * v,
+        }
+    }
+
+    /// The row `rotation` away from `row`.
+    pub fn rotate(&self, row: usize, rotation: i32) -> usize {
+        (row as i64 + rotation as i64).rem_euclid(self.rows() as i64) as usize
+    }
+
+    /// The cells wired together by copy constraints, one set per cycle of
+    /// the permutation with more than one cell. Cells and cycles are in
+    /// column-then-row order.
+    pub fn copy_cycles(&self) -> Vec<Vec<CellRef>> {
+        let mut parent: BTreeMap<CellRef, CellRef> = BTreeMap::new();
+        fn root(parent: &mut BTreeMap<CellRef, CellRef>, cell: CellRef) -> CellRef {
+            let next = *parent.entry(cell).or_insert(cell);
+            if next == cell {
+                return cell;
+            }
+            let root = root(parent, next);
+            parent.insert(cell, root);
+            root
+        }
+        for (left, right) in &self.copies {
+            let (left, right) = (root(&mut parent, *left), root(&mut parent, *right));
+            parent.insert(left.max(right), left.min(right));
+        }
+
+        let mut cycles: BTreeMap<CellRef, Vec<CellRef>> = BTreeMap::new();
+        for cell in parent.keys().copied().collect::<Vec<_>>() {
+            cycles
+                .entry(root(&mut parent, cell))
+                .or_default()
+                .push(cell);
+        }
+        cycles
+            .into_values()
+            .filter(|cells| cells.len() > 1)
+            .collect()
+    }
+
+    /// The region that assigned `cell`, if any did.
+    pub fn region_of(&self, cell: CellRef) -> Option<&RegionInfo> {
+        self.regions
+            .iter()
+            .find(|region| region.cells.iter().any(|assigned| assigned.cell == cell))
+    }
+}
+
+struct Recorder<'a> {
+    witness: &'a mut Witness,
+    region: Option<RegionInfo>,
+    on_region: &'a mut dyn FnMut(&Witness, &RegionInfo),
+}
+
+impl Recorder<'_> {
+    fn check_row(&self, row: usize) -> Result<(), Error> {
+        if row < self.witness.usable_rows {
+            Ok(())
+        } else {
+            Err(Error::NotEnoughRowsAvailable {
+                current_k: self.witness.k,
+            })
+        }
+    }
+
+    fn assign(
+        &mut self,
+        column: ColumnRef,
+        row: usize,
+        annotation: String,
+        value: Value<Assigned<Fp>>,
+    ) -> Result<(), Error> {
+        self.check_row(row)?;
+        let mut known = None;
+        value.map(|v| known = Some(v.evaluate()));
+        let cells = match column.kind {
+            ColumnKind::Advice => &mut self.witness.advice,
+            ColumnKind::Fixed => &mut self.witness.fixed,
+            ColumnKind::Instance => unreachable!("instance cells are not assigned"),
+        };
+        *cells
+            .get_mut(column.index)
+            .and_then(|column| column.get_mut(row))
+            .ok_or(Error::BoundsFailure)? = known;
+
+        if let Some(region) = self.region.as_mut() {
+            region.rows = Some(match region.rows {
+                None => (row, row),
+                Some((first, last)) => (first.min(row), last.max(row)),
+            });
+            region.cells.push(AssignedCellInfo {
+                cell: CellRef { column, row },
+                annotation,
+                value: known,
+            });
+        }
+        Ok(())
+    }
+}
+
+impl Assignment<Fp> for Recorder<'_> {
+    fn enter_region<NR, N>(&mut self, name_fn: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+        assert!(self.region.is_none(), "regions do not nest");
+        self.region = Some(RegionInfo {
+            name: name_fn().into(),
+            rows: None,
+            cells: vec![],
+            selectors: vec![],
+            copies: 0,
+        });
+    }
+
+    fn exit_region(&mut self) {
+        let region = self.region.take().expect("inside a region");
+        self.witness.regions.push(region);
+        (self.on_region)(self.witness, self.witness.regions.last().unwrap());
+    }
+
+    fn enable_selector<A, AR>(&mut self, _: A, selector: &Selector, row: usize) -> Result<(), Error>
+    where
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        self.check_row(row)?;
+        let index = selector_index(selector);
+        self.witness.selectors[index][row] = true;
+        if let Some(region) = self.region.as_mut() {
+            region.selectors.push((index, row));
+        }
+        Ok(())
+    }
+
+    fn query_instance(&self, column: Column<Instance>, row: usize) -> Result<Value<Fp>, Error> {
+        self.check_row(row)?;
+        self.witness
+            .instance
+            .get(ColumnRef::from(column).index)
+            .and_then(|column| column.get(row))
+            .map(|v| Value::known(*v))
+            .ok_or(Error::BoundsFailure)
+    }
+
+    fn assign_advice<V, VR, A, AR>(
+        &mut self,
+        annotation: A,
+        column: Column<Advice>,
+        row: usize,
+        to: V,
+    ) -> Result<(), Error>
+    where
+        V: FnOnce() -> Value<VR>,
+        VR: Into<Assigned<Fp>>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        self.assign(
+            ColumnRef::from(column),
+            row,
+            annotation().into(),
+            to().map(Into::into),
+        )
+    }
+
+    fn assign_fixed<V, VR, A, AR>(
+        &mut self,
+        annotation: A,
+        column: Column<Fixed>,
+        row: usize,
+        to: V,
+    ) -> Result<(), Error>
+    where
+        V: FnOnce() -> Value<VR>,
+        VR: Into<Assigned<Fp>>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        self.assign(
+            ColumnRef::from(column),
+            row,
+            annotation().into(),
+            to().map(Into::into),
+        )
+    }
+
+    fn copy(
+        &mut self,
+        left_column: Column<Any>,
+        left_row: usize,
+        right_column: Column<Any>,
+        right_row: usize,
+    ) -> Result<(), Error> {
+        self.check_row(left_row)?;
+        self.check_row(right_row)?;
+        let cell = |column: Column<Any>, row| CellRef {
+            column: ColumnRef::from(column),
+            row,
+        };
+        self.witness
+            .copies
+            .push((cell(left_column, left_row), cell(right_column, right_row)));
+        if let Some(region) = self.region.as_mut() {
+            region.copies += 1;
+        }
+        Ok(())
+    }
+
+    fn fill_from_row(
+        &mut self,
+        column: Column<Fixed>,
+        row: usize,
+        to: Value<Assigned<Fp>>,
+    ) -> Result<(), Error> {
+        self.check_row(row)?;
+        for row in row..self.witness.usable_rows {
+            self.assign_fixed(|| "", column, row, || to)?;
+        }
+        Ok(())
+    }
+
+    fn push_namespace<NR, N>(&mut self, _: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+    }
+
+    fn pop_namespace(&mut self, _: Option<String>) {}
+}
+
+/// Fixed column handles for `columns`. Columns cannot be built from an
+/// index directly, but a fresh constraint system hands them out in index
+/// order.
+fn fixed_columns(columns: &[ColumnRef]) -> Vec<Column<Fixed>> {
+    let mut meta = ConstraintSystem::<Fp>::default();
+    let count = columns.iter().map(|c| c.index + 1).max().unwrap_or(0);
+    let all: Vec<_> = (0..count).map(|_| meta.fixed_column()).collect();
+    columns.iter().map(|column| all[column.index]).collect()
+}
@@ -0,0 +1,71 @@
+//! Halo2 practice circuits for the Fibonacci sequence.
+//!
+//! The chips live under [`fibonacci`], [`registry`] names them so tools can
+//! select a circuit by string, and [`cost`] and [`gates`] report what each one
+//! costs and constrains, with [`explain`] showing the constraints evaluated on
+//! the witness, [`copies`] the cells its copy constraints wire together,
+//! [`occupancy`] the rows each region fills in each column as CSV, and
+//! [`unconstrained`] and [`coverage`] the cells and rows nothing
+//! constrains. [`prover`] runs the real IPA prover, after [`precheck`] if
+//! asked, reporting on the way through [`progress`] and counted in
+//! [`metrics`], with proofs of repeated inputs reused from a [`cache`], and
+//! its output is shipped in a [`envelope::ProofEnvelope`], in its own
+//! layout or as [`cbor`], or, with the verifying key and parameters it was
+//! made with, a [`bundle::ProofBundle`],
+//! from parameters managed by [`params`]. [`verification`] reports on the verifier,
+//! a [`session`] verifies many proofs against keys generated once,
+//! [`aggregation`] checks many proofs at once, [`accel`] is the extension
+//! point for faster MSM and FFT backends, and [`ptau`] reads KZG ceremony
+//! files the prover cannot use yet. The circuits' outputs are tested against
+//! [`reference`], and [`soak`] proves random cases against it, which
+//! [`differential`] also runs through the `MockProver`. Reusable building
+//! blocks for other circuits are in [`gadgets`], with [`expr`] for writing
+//! their gates and [`rows`] for filling long regions, circuits for other sequences in [`circuits`], and
+//! the flags of the examples under `examples/` in [`example`], with [`field`] parsing the
+//! field elements among them and [`spec`] checking a run's inputs before
+//! any of it starts. Long [`chunked`] proving jobs checkpoint through
+//! [`chain_state`] and resume where they stopped.
+
+pub mod accel;
+pub mod aggregation;
+pub mod bundle;
+pub mod cache;
+pub mod cbor;
+pub mod chain_state;
+pub mod chunked;
+pub mod circuits;
+pub mod copies;
+pub mod cost;
+pub mod coverage;
+pub mod differential;
+pub mod encoding;
+pub mod envelope;
+#[cfg(feature = "evm")]
+pub mod evm;
+pub mod example;
+pub mod explain;
+pub mod expr;
+pub mod fibonacci;
+pub mod field;
+pub mod fingerprint;
+pub mod folding;
+pub mod gadgets;
+pub mod gates;
+pub mod inspect;
+pub mod introspect;
+pub mod metrics;
+pub mod occupancy;
+pub mod params;
+pub mod precheck;
+pub mod progress;
+pub mod prover;
+pub mod ptau;
+pub mod reference;
+pub mod registry;
+pub mod rng;
+pub mod rows;
+pub mod session;
+pub mod soak;
+pub mod spec;
+pub mod unconstrained;
+pub mod verification;
@@ -0,0 +1,150 @@
+//! Prover metrics in the Prometheus text exposition format: proofs by
+//! circuit and outcome, the time spent in each [`Phase`], and the depth of
+//! a queue of pending requests.
+//!
+//! [`ProverMetrics`] is shared behind `&self` so a serving thread can
+//! render it while workers record runs. The crate has no HTTP front end to
+//! mount a `/metrics` endpoint on; `fibo prove --metrics` writes
+//! [`ProverMetrics::render`] to a file for node_exporter's textfile
+//! collector instead.
+
+use std::{collections::BTreeMap, fmt::Write, sync::Mutex};
+
+use crate::progress::{Event, Phase};
+
+/// Upper bounds of the phase duration buckets, in seconds.
+pub const BUCKETS: [f64; 10] = [0.01, 0.05, 0.1, 0.5, 1.0, 5.0, 10.0, 30.0, 60.0, 300.0];
+
+#[derive(Debug, Default)]
+pub struct ProverMetrics {
+    inner: Mutex<Inner>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    /// `(circuit, succeeded)` to count.
+    proofs: BTreeMap<(String, bool), u64>,
+    phases: BTreeMap<&'static str, Histogram>,
+    queue_depth: i64,
+}
+
+#[derive(Debug, Default, Clone)]
+struct Histogram {
+    /// Observations at or below each of [`BUCKETS`].
+    buckets: [u64; BUCKETS.len()],
+    sum: f64,
+    count: u64,
+}
+
+impl ProverMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a finished run of `circuit` from its progress events. Each
+    /// phase lasts until the next one starts, so the phase a failed run
+    /// stopped in is counted in the outcome but has no duration.
+    pub fn record(&self, circuit: &str, events: &[Event], succeeded: bool) {
+        let phases: Vec<(Phase, f64)> = events
+            .iter()
+            .filter_map(|event| match *event {
+                Event::Phase { phase, elapsed_ms } => Some((phase, elapsed_ms)),
+                Event::Rows { .. } => None,
+            })
+            .collect();
+
+        let mut inner = self.inner.lock().unwrap();
+        *inner
+            .proofs
+            .entry((circuit.to_string(), succeeded))
+            .or_default() += 1;
+        for pair in phases.windows(2) {
+            let ((phase, start), (_, end)) = (pair[0], pair[1]);
+            inner
+                .phases
+                .entry(phase.name())
+                .or_default()
+                .observe((end - start) / 1000.0);
+        }
+    }
+
+    pub fn set_queue_depth(&self, depth: i64) {
+        self.inner.lock().unwrap().queue_depth = depth;
+    }
+
+    /// Every metric in the text exposition format, version 0.0.4.
+    pub fn render(&self) -> String {
+        let inner = self.inner.lock().unwrap();
+        let mut out = String::new();
+
+        out.push_str("# HELP fibo_proofs_total Proving runs by circuit and outcome.\n");
+        out.push_str("# TYPE fibo_proofs_total counter\n");
+        for ((circuit, succeeded), count) in &inner.proofs {
+            let outcome = if *succeeded { "success" } else { "failure" };
+            writeln!(
+                out,
+                "fibo_proofs_total{{circuit=\"{}\",outcome=\"{}\"}} {}",
+                escape(circuit),
+                outcome,
+                count
+            )
+            .unwrap();
+        }
+
+        out.push_str("# HELP fibo_phase_duration_seconds Time spent in each proving phase.\n");
+        out.push_str("# TYPE fibo_phase_duration_seconds histogram\n");
+        for (phase, histogram) in &inner.phases {
+            for (le, count) in BUCKETS.iter().zip(histogram.buckets) {
+                writeln!(
+                    out,
+                    "fibo_phase_duration_seconds_bucket{{phase=\"{}\",le=\"{}\"}} {}",
+                    phase, le, count
+                )
+                .unwrap();
+            }
+            writeln!(
+                out,
+                "fibo_phase_duration_seconds_bucket{{phase=\"{}\",le=\"+Inf\"}} {}",
+                phase, histogram.count
+            )
+            .unwrap();
+            writeln!(
+                out,
+                "fibo_phase_duration_seconds_sum{{phase=\"{}\"}} {}",
+                phase, histogram.sum
+            )
+            .unwrap();
+            writeln!(
+                out,
+                "fibo_phase_duration_seconds_count{{phase=\"{}\"}} {}",
+                phase, histogram.count
+            )
+            .unwrap();
+        }
+
+        out.push_str("# HELP fibo_queue_depth Proving requests waiting to start.\n");
+        out.push_str("# TYPE fibo_queue_depth gauge\n");
+        writeln!(out, "fibo_queue_depth {}", inner.queue_depth).unwrap();
+        out
+    }
+}
+
+impl Histogram {
+    fn observe(&mut self, seconds: f64) {
+        for (bucket, le) in self.buckets.iter_mut().zip(BUCKETS) {
+            if seconds <= le {
+                *bucket += 1;
+            }
+        }
+        self.sum += seconds;
+        self.count += 1;
+    }
+}
+
+/// A label value with `\`, `"` and newlines escaped.
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
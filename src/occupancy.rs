@@ -0,0 +1,106 @@
+//! Where each region sits in each column, as data to analyse a layout
+//! with other tools.
+//!
+//! There is one entry per region and column the region assigned cells in,
+//! written as CSV by [`OccupancyReport::to_csv`]:
+//!
+//! ```text
+//! region,name,column,start,length,cells
+//! 0,first row,A0,0,1,1
+//! ```
+//!
+//! `start` and `length` span the region's first to last row in the
+//! column, and `cells` counts the distinct cells it assigned there, so
+//! `cells / length` is how densely the region fills its part of the
+//! column. Cells assigned outside any region are not listed.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::Serialize;
+
+use crate::introspect::{witness::Witness, ColumnRef};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OccupancyReport {
+    pub circuit: String,
+    pub k: u32,
+    pub usable_rows: usize,
+    pub entries: Vec<Occupancy>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Occupancy {
+    /// Index into the witness's regions.
+    pub region: usize,
+    pub name: String,
+    /// The column as `A0`.
+    pub column: String,
+    pub start: usize,
+    pub length: usize,
+    pub cells: usize,
+}
+
+impl OccupancyReport {
+    /// The entries of `witness`, by region and then column.
+    pub fn of(name: &str, witness: &Witness) -> Self {
+        let mut entries = vec![];
+        for (index, region) in witness.regions.iter().enumerate() {
+            let mut rows: BTreeMap<ColumnRef, BTreeSet<usize>> = BTreeMap::new();
+            for cell in &region.cells {
+                rows.entry(cell.cell.column)
+                    .or_default()
+                    .insert(cell.cell.row);
+            }
+            for (column, rows) in rows {
+                let (first, last) = (rows.first().unwrap(), rows.last().unwrap());
+                entries.push(Occupancy {
+                    region: index,
+                    name: region.name.clone(),
+                    column: column.to_string(),
+                    start: *first,
+                    length: last - first + 1,
+                    cells: rows.len(),
+                });
+            }
+        }
+
+        OccupancyReport {
+            circuit: name.to_string(),
+            k: witness.k,
+            usable_rows: witness.usable_rows,
+            entries,
+        }
+    }
+
+    /// Cells assigned in regions, across every column: the layout's
+    /// witness area.
+    pub fn cells(&self) -> usize {
+        self.entries.iter().map(|entry| entry.cells).sum()
+    }
+
+    /// The entries as CSV with a header line, quoting names as RFC 4180
+    /// does where they need it.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("region,name,column,start,length,cells\n");
+        for entry in &self.entries {
+            csv.push_str(&format!(
+                "{},{},{},{},{},{}\n",
+                entry.region,
+                csv_field(&entry.name),
+                entry.column,
+                entry.start,
+                entry.length,
+                entry.cells
+            ));
+        }
+        csv
+    }
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
@@ -0,0 +1,172 @@
+//! Serialized commitment parameters.
+//!
+//! IPA parameters are transparent: `Params::new(k)` derives every generator
+//! by hashing to the curve, so a parameters file can always be checked by
+//! regenerating it. The file format is halo2's own `Params::write` output.
+
+use std::{error, fmt, fs, io, path::Path};
+
+use halo2_proofs::{pasta::EqAffine, poly::commitment::Params};
+use serde::{Deserialize, Serialize};
+
+/// `Params::new` refuses `k >= 32`.
+pub const MAX_K: u32 = 31;
+
+/// Size of a compressed vesta point in the parameters file.
+const POINT_BYTES: usize = 32;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParamsInfo {
+    pub k: u32,
+    /// Number of `g` generators, `2^k`.
+    pub generators: u64,
+    pub bytes: usize,
+    /// Blake2b digest of the serialized parameters, hex encoded.
+    pub digest: String,
+}
+
+#[derive(Debug)]
+pub enum ParamsError {
+    Io(io::Error),
+    InvalidK(u32),
+    /// The file is not the size parameters for its `k` have to be.
+    Length {
+        k: u32,
+        expected: usize,
+        actual: usize,
+    },
+    /// A generator does not decode to a curve point.
+    Malformed(io::Error),
+    /// The file decodes but differs from `Params::new(k)`.
+    Mismatch {
+        expected: String,
+        actual: String,
+    },
+}
+
+pub fn generate(k: u32) -> Result<Params<EqAffine>, ParamsError> {
+    if k > MAX_K {
+        return Err(ParamsError::InvalidK(k));
+    }
+    Ok(Params::new(k))
+}
+
+pub fn to_bytes(params: &Params<EqAffine>) -> Vec<u8> {
+    let mut bytes = vec![];
+    params
+        .write(&mut bytes)
+        .expect("writing to a Vec cannot fail");
+    bytes
+}
+
+/// Decodes parameters, checking the length against the encoded `k` before
+/// allocating anything.
+pub fn from_bytes(bytes: &[u8]) -> Result<Params<EqAffine>, ParamsError> {
+    let k = encoded_k(bytes)?;
+    let expected = serialized_len(k);
+    if bytes.len() != expected {
+        return Err(ParamsError::Length {
+            k,
+            expected,
+            actual: bytes.len(),
+        });
+    }
+    Params::read(&mut &bytes[..]).map_err(ParamsError::Malformed)
+}
+
+pub fn save(params: &Params<EqAffine>, path: &Path) -> Result<(), ParamsError> {
+    fs::write(path, to_bytes(params))?;
+    Ok(())
+}
+
+pub fn load(path: &Path) -> Result<Params<EqAffine>, ParamsError> {
+    from_bytes(&fs::read(path)?)
+}
+
+/// Describes serialized parameters without decoding the generators.
+pub fn info(bytes: &[u8]) -> Result<ParamsInfo, ParamsError> {
+    let k = encoded_k(bytes)?;
+    Ok(ParamsInfo {
+        k,
+        generators: 1 << k,
+        bytes: bytes.len(),
+        digest: digest(bytes),
+    })
+}
+
+/// Decodes serialized parameters and checks they are exactly
+/// `Params::new(k)` for their `k`.
+pub fn check(bytes: &[u8]) -> Result<ParamsInfo, ParamsError> {
+    from_bytes(bytes)?;
+    let info = info(bytes)?;
+    let expected = digest(&to_bytes(&generate(info.k)?));
+    if expected != info.digest {
+        return Err(ParamsError::Mismatch {
+            expected,
+            actual: info.digest,
+        });
+    }
+    Ok(info)
+}
+
+/// Length of `Params::write` output: `k`, then `g` and `g_lagrange`, then
+/// `w` and `u`.
+pub fn serialized_len(k: u32) -> usize {
+    4 + (2 * (1usize << k) + 2) * POINT_BYTES
+}
+
+pub fn digest(bytes: &[u8]) -> String {
+    blake2b_simd::Params::new()
+        .hash_length(32)
+        .personal(b"Fibo-Params")
+        .hash(bytes)
+        .to_hex()
+        .to_string()
+}
+
+fn encoded_k(bytes: &[u8]) -> Result<u32, ParamsError> {
+    let k = bytes
+        .get(..4)
+        .map(|k| u32::from_le_bytes(k.try_into().unwrap()))
+        .ok_or(ParamsError::Length {
+            k: 0,
+            expected: 4,
+            actual: bytes.len(),
+        })?;
+    if k > MAX_K {
+        return Err(ParamsError::InvalidK(k));
+    }
+    Ok(k)
+}
+
+impl From<io::Error> for ParamsError {
+    fn from(e: io::Error) -> Self {
+        ParamsError::Io(e)
+    }
+}
+
+impl fmt::Display for ParamsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParamsError::Io(e) => write!(f, "I/O error: {}", e),
+            ParamsError::InvalidK(k) => write!(f, "k = {} is larger than {}", k, MAX_K),
+            ParamsError::Length {
+                k,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "parameters for k = {} are {} bytes, file has {}",
+                k, expected, actual
+            ),
+            ParamsError::Malformed(e) => write!(f, "malformed parameters: {}", e),
+            ParamsError::Mismatch { expected, actual } => write!(
+                f,
+                "parameters differ from the generated ones (digest {}, expected {})",
+                actual, expected
+            ),
+        }
+    }
+}
+
+impl error::Error for ParamsError {}
@@ -0,0 +1,53 @@
+//! The `MockProver` run on a witness before the real prover is.
+//!
+//! The real prover does not say which constraint a bad witness breaks: it
+//! fails with `ConstraintSystemFailure` or, on a wrong public input, makes
+//! a proof the verifier rejects. The `MockProver` evaluates every gate,
+//! lookup and copy on the witness and names the failing ones with their
+//! regions and rows, at the cost of holding every cell in memory, so
+//! [`check`] is worth running first while a circuit is being developed.
+
+use std::{error, fmt};
+
+use halo2_proofs::{
+    dev::{MockProver, VerifyFailure},
+    pasta::Fp,
+    plonk::{Circuit, Error},
+};
+
+#[derive(Debug)]
+pub enum PrecheckError {
+    /// Synthesis failed before any constraint was checked.
+    Synthesis(Error),
+    Unsatisfied(Vec<VerifyFailure>),
+}
+
+/// Runs the `MockProver` on `circuit` at `k` against `instances`, the
+/// inputs the real prover would be given.
+pub fn check<C: Circuit<Fp>>(
+    k: u32,
+    circuit: &C,
+    instances: &[Vec<Fp>],
+) -> Result<(), PrecheckError> {
+    MockProver::run(k, circuit, instances.to_vec())
+        .map_err(PrecheckError::Synthesis)?
+        .verify()
+        .map_err(PrecheckError::Unsatisfied)
+}
+
+impl fmt::Display for PrecheckError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PrecheckError::Synthesis(e) => write!(f, "synthesis failed: {}", e),
+            PrecheckError::Unsatisfied(failures) => {
+                write!(f, "the MockProver rejects the witness:")?;
+                for failure in failures {
+                    write!(f, "\n  {}", failure)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl error::Error for PrecheckError {}
@@ -0,0 +1,111 @@
+//! Progress events from a proving run, so a long one can be watched.
+//!
+//! halo2_proofs 0.2 reports nothing from inside keygen or the prover, so
+//! [`prove`] reports the phases around them, and synthesizes the witness
+//! once more beforehand to report assigned rows as regions close. That
+//! pass costs a synthesis, small next to proving at large `k`, and fails
+//! early with the same error the prover would on a witness that does not
+//! fit. The events are plain data, so a caller can print them, forward
+//! them over a channel or serialize them for a client.
+
+use std::time::Instant;
+
+use halo2_proofs::{
+    pasta::{EqAffine, Fp},
+    plonk::{Circuit, Error, ProvingKey},
+    poly::commitment::Params,
+};
+use serde::Serialize;
+
+use crate::{
+    introspect::witness::Witness,
+    prover::{self, Blinding},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Phase {
+    Synthesis,
+    Keygen,
+    Proving,
+    Done,
+}
+
+impl Phase {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Phase::Synthesis => "synthesis",
+            Phase::Keygen => "keygen",
+            Phase::Proving => "proving",
+            Phase::Done => "done",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(tag = "event", rename_all = "lowercase")]
+pub enum Event {
+    /// `phase` started, `elapsed_ms` after the run did.
+    Phase { phase: Phase, elapsed_ms: f64 },
+    /// A region closed during synthesis with rows up to `rows` assigned,
+    /// out of `usable`.
+    Rows { rows: usize, usable: usize },
+}
+
+/// Generates the proving key for `circuit` and proves it at `k`, calling
+/// `on_event` as the run goes.
+pub fn prove<C: Circuit<Fp>>(
+    params: &Params<EqAffine>,
+    k: u32,
+    circuit: C,
+    instances: &[Vec<Fp>],
+    on_event: impl FnMut(Event),
+) -> Result<(ProvingKey<EqAffine>, Vec<u8>), Error> {
+    prove_with(
+        params,
+        k,
+        circuit,
+        instances,
+        Blinding::ZeroKnowledge,
+        on_event,
+    )
+}
+
+/// [`prove`] with the blinding chosen.
+pub fn prove_with<C: Circuit<Fp>>(
+    params: &Params<EqAffine>,
+    k: u32,
+    circuit: C,
+    instances: &[Vec<Fp>],
+    blinding: Blinding,
+    mut on_event: impl FnMut(Event),
+) -> Result<(ProvingKey<EqAffine>, Vec<u8>), Error> {
+    let start = Instant::now();
+    let phase = |phase, on_event: &mut dyn FnMut(Event)| {
+        on_event(Event::Phase {
+            phase,
+            elapsed_ms: start.elapsed().as_secs_f64() * 1000.0,
+        })
+    };
+
+    phase(Phase::Synthesis, &mut on_event);
+    let mut rows = 0;
+    Witness::synthesize_with(k, &circuit, instances.to_vec(), |witness, region| {
+        if let Some((_, last)) = region.rows {
+            rows = rows.max(last + 1);
+        }
+        on_event(Event::Rows {
+            rows,
+            usable: witness.usable_rows,
+        });
+    })?;
+
+    phase(Phase::Keygen, &mut on_event);
+    let pk = prover::keygen(params, &circuit)?;
+
+    phase(Phase::Proving, &mut on_event);
+    let proof = prover::create_proof_with(params, &pk, circuit, instances, blinding)?;
+
+    phase(Phase::Done, &mut on_event);
+    Ok((pk, proof))
+}
@@ -0,0 +1,241 @@
+//! The real prover and verifier: IPA commitments over the Pasta curves with a
+//! Blake2b transcript.
+//!
+//! The `_to` and `_from` variants stream the transcript through an
+//! [`io::Write`] or [`io::Read`] instead of a byte vector. halo2 writes each
+//! commitment and evaluation as the prover produces it and reads them back
+//! one at a time, so a proof going to a file or socket is never held in
+//! memory whole beyond what the writer buffers.
+
+use std::io;
+
+use halo2_proofs::{
+    pasta::{EqAffine, Fp},
+    plonk::{self, Circuit, Error, ProvingKey, SingleVerifier, VerifyingKey},
+    poly::commitment::Params,
+    transcript::{Blake2bRead, Blake2bWrite, Challenge255},
+};
+use rand_core::OsRng;
+
+use crate::rng::SplitMix64;
+
+/// Generates the proving key for `circuit` (its witness is not used).
+pub fn keygen<C: Circuit<Fp>>(
+    params: &Params<EqAffine>,
+    circuit: &C,
+) -> Result<ProvingKey<EqAffine>, Error> {
+    let empty = circuit.without_witnesses();
+    let vk = plonk::keygen_vk(params, &empty)?;
+    plonk::keygen_pk(params, vk, &empty)
+}
+
+/// Generates only the verifying key for `circuit`.
+pub fn keygen_vk<C: Circuit<Fp>>(
+    params: &Params<EqAffine>,
+    circuit: &C,
+) -> Result<VerifyingKey<EqAffine>, Error> {
+    plonk::keygen_vk(params, &circuit.without_witnesses())
+}
+
+/// Runs `f` on a pool of `threads` threads instead of rayon's global pool,
+/// which has one per core. halo2 parallelizes through rayon, so proving and
+/// verifying inside `f` use at most `threads` threads.
+pub fn with_threads<R: Send>(
+    threads: usize,
+    f: impl FnOnce() -> R + Send,
+) -> Result<R, rayon::ThreadPoolBuildError> {
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build()?;
+    Ok(pool.install(f))
+}
+
+/// Where the prover's blinding values come from.
+///
+/// halo2_proofs 0.2 always reserves the blinding rows and commits with a
+/// blinding scalar, so turning zero knowledge off saves no rows or time;
+/// it makes the proof a function of the witness alone, which is what
+/// debugging and reproducible benchmarks want. The values cannot simply be
+/// zero: the transcript rejects the commitments to the all-zero random
+/// polynomials the prover would then make. The proof verifies the same
+/// either way.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Blinding {
+    /// Fresh randomness for every blinding value, so the proof reveals
+    /// nothing about the witness beyond the statement.
+    #[default]
+    ZeroKnowledge,
+    /// Every blinding value comes from [`UNBLINDED_SEED`]. The proof is
+    /// deterministic, and no longer hides the witness since anyone can
+    /// recompute the blinding and take it off.
+    Off,
+}
+
+/// The public seed of the blinding values of [`Blinding::Off`].
+pub const UNBLINDED_SEED: u64 = 0;
+
+/// Proves `circuit` against its instance columns and returns the proof bytes.
+pub fn create_proof<C: Circuit<Fp>>(
+    params: &Params<EqAffine>,
+    pk: &ProvingKey<EqAffine>,
+    circuit: C,
+    instances: &[Vec<Fp>],
+) -> Result<Vec<u8>, Error> {
+    create_proof_with(params, pk, circuit, instances, Blinding::ZeroKnowledge)
+}
+
+/// [`create_proof`] with the blinding chosen.
+pub fn create_proof_with<C: Circuit<Fp>>(
+    params: &Params<EqAffine>,
+    pk: &ProvingKey<EqAffine>,
+    circuit: C,
+    instances: &[Vec<Fp>],
+    blinding: Blinding,
+) -> Result<Vec<u8>, Error> {
+    create_batch_proof_with(params, pk, vec![circuit], &[instances.to_vec()], blinding)
+}
+
+/// [`create_proof_with`], writing the transcript to `writer` and handing
+/// it back.
+pub fn create_proof_to<C: Circuit<Fp>, W: io::Write>(
+    params: &Params<EqAffine>,
+    pk: &ProvingKey<EqAffine>,
+    circuit: C,
+    instances: &[Vec<Fp>],
+    blinding: Blinding,
+    writer: W,
+) -> Result<W, Error> {
+    create_batch_proof_to(
+        params,
+        pk,
+        vec![circuit],
+        &[instances.to_vec()],
+        blinding,
+        writer,
+    )
+}
+
+pub fn verify_proof(
+    params: &Params<EqAffine>,
+    vk: &VerifyingKey<EqAffine>,
+    proof: &[u8],
+    instances: &[Vec<Fp>],
+) -> Result<(), Error> {
+    verify_batch_proof(params, vk, proof, &[instances.to_vec()])
+}
+
+/// [`verify_proof`] reading the transcript from `reader`. Bytes after the
+/// proof are left unread.
+pub fn verify_proof_from<R: io::Read>(
+    params: &Params<EqAffine>,
+    vk: &VerifyingKey<EqAffine>,
+    reader: R,
+    instances: &[Vec<Fp>],
+) -> Result<(), Error> {
+    verify_batch_proof_from(params, vk, reader, &[instances.to_vec()])
+}
+
+/// Proves several instances of one circuit in a single transcript.
+///
+/// `instances[i]` holds the instance columns of `circuits[i]`. All circuits
+/// share `pk`, so they must agree on everything the key fixes (columns,
+/// gates, selector and fixed assignments) and differ only in their witness
+/// and public inputs.
+pub fn create_batch_proof<C: Circuit<Fp>>(
+    params: &Params<EqAffine>,
+    pk: &ProvingKey<EqAffine>,
+    circuits: Vec<C>,
+    instances: &[Vec<Vec<Fp>>],
+) -> Result<Vec<u8>, Error> {
+    create_batch_proof_with(params, pk, circuits, instances, Blinding::ZeroKnowledge)
+}
+
+/// [`create_batch_proof`] with the blinding chosen.
+pub fn create_batch_proof_with<C: Circuit<Fp>>(
+    params: &Params<EqAffine>,
+    pk: &ProvingKey<EqAffine>,
+    circuits: Vec<C>,
+    instances: &[Vec<Vec<Fp>>],
+    blinding: Blinding,
+) -> Result<Vec<u8>, Error> {
+    create_batch_proof_to(params, pk, circuits, instances, blinding, vec![])
+}
+
+/// [`create_batch_proof_with`], writing the transcript to `writer` and
+/// handing it back. A failed write fails the proof with
+/// [`Error::Transcript`], leaving part of a proof behind in `writer`.
+pub fn create_batch_proof_to<C: Circuit<Fp>, W: io::Write>(
+    params: &Params<EqAffine>,
+    pk: &ProvingKey<EqAffine>,
+    circuits: Vec<C>,
+    instances: &[Vec<Vec<Fp>>],
+    blinding: Blinding,
+    writer: W,
+) -> Result<W, Error> {
+    if circuits.len() != instances.len() {
+        return Err(Error::InvalidInstances);
+    }
+    let columns = instance_slices(instances);
+    let instances: Vec<&[&[Fp]]> = columns.iter().map(Vec::as_slice).collect();
+    let mut transcript = Blake2bWrite::<_, EqAffine, Challenge255<_>>::init(writer);
+    match blinding {
+        Blinding::ZeroKnowledge => {
+            plonk::create_proof(params, pk, &circuits, &instances, OsRng, &mut transcript)?
+        }
+        Blinding::Off => {
+            let rng = SplitMix64::new(UNBLINDED_SEED);
+            plonk::create_proof(params, pk, &circuits, &instances, rng, &mut transcript)?
+        }
+    }
+    Ok(transcript.finalize())
+}
+
+/// Verifies a proof from [`create_batch_proof`]. `instances` must list the
+/// circuits' instance columns in the order they were proven.
+///
+/// The transcript ends where the verifier stops reading, so `proof` with
+/// anything appended would also pass; bytes left over are an error, so each
+/// statement has one accepted encoding per proof.
+pub fn verify_batch_proof(
+    params: &Params<EqAffine>,
+    vk: &VerifyingKey<EqAffine>,
+    proof: &[u8],
+    instances: &[Vec<Vec<Fp>>],
+) -> Result<(), Error> {
+    let mut rest = proof;
+    verify_batch_proof_from(params, vk, &mut rest, instances)?;
+    if !rest.is_empty() {
+        return Err(Error::Transcript(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{} bytes after the proof", rest.len()),
+        )));
+    }
+    Ok(())
+}
+
+/// [`verify_batch_proof`] reading the transcript from `reader`.
+pub fn verify_batch_proof_from<R: io::Read>(
+    params: &Params<EqAffine>,
+    vk: &VerifyingKey<EqAffine>,
+    reader: R,
+    instances: &[Vec<Vec<Fp>>],
+) -> Result<(), Error> {
+    let columns = instance_slices(instances);
+    let instances: Vec<&[&[Fp]]> = columns.iter().map(Vec::as_slice).collect();
+    let mut transcript = Blake2bRead::<_, EqAffine, Challenge255<_>>::init(reader);
+    plonk::verify_proof(
+        params,
+        vk,
+        SingleVerifier::new(params),
+        &instances,
+        &mut transcript,
+    )
+}
+
+/// Borrows per-circuit instance columns in the nesting `plonk` expects.
+fn instance_slices(instances: &[Vec<Vec<Fp>>]) -> Vec<Vec<&[Fp]>> {
+    instances
+        .iter()
+        .map(|columns| columns.iter().map(Vec::as_slice).collect())
+        .collect()
+}
@@ -0,0 +1,312 @@
+//! Reading Perpetual Powers of Tau `.ptau` files, the snarkjs container
+//! for a BN254 KZG setup.
+//!
+//! A file is the magic `ptau`, a version and a section count, then
+//! sections of a `u32` id and `u64` length, all little-endian. Section 1
+//! is the header (`n8`, the base field modulus in `n8` bytes, the file's
+//! `power` and the ceremony's), and sections 2 to 6 hold the points:
+//! `2^(power+1) - 1` powers of τ in G1, `2^power` in G2, `2^power` each of
+//! `α·τ^i` and `β·τ^i` in G1, and `β` in G2, uncompressed at `2·n8` bytes
+//! per G1 point and `4·n8` per G2 point. The rest (contributions, the
+//! Lagrange-basis sections of a prepared file) are carried along
+//! unchecked.
+//!
+//! The crate proves with IPA over the Pasta curves, and halo2_proofs 0.2
+//! has no KZG backend, so there is no params type for the powers to load
+//! into and no BN254 arithmetic to check them with. [`PtauFile`] checks
+//! the container: the layout, the section lengths the header implies, and
+//! whether the file reaches a given `k`, with a digest to pin the download,
+//! and [`PtauFile::trim`] cuts a large file down to the degree a `k`
+//! needs.
+
+use std::{error, fmt};
+
+use num_bigint::BigUint;
+use serde::Serialize;
+
+use crate::params;
+
+pub const MAGIC: &[u8; 4] = b"ptau";
+
+pub const HEADER: u32 = 1;
+pub const TAU_G1: u32 = 2;
+pub const TAU_G2: u32 = 3;
+pub const ALPHA_TAU_G1: u32 = 4;
+pub const BETA_TAU_G1: u32 = 5;
+pub const BETA_G2: u32 = 6;
+pub const CONTRIBUTIONS: u32 = 7;
+/// The Lagrange-basis sections a prepared (`_final`) file adds.
+pub const LAGRANGE: [u32; 4] = [12, 13, 14, 15];
+
+/// The largest `power` a file can describe and still be addressed.
+const MAX_POWER: u32 = 30;
+
+/// A `.ptau` file split into its sections, borrowing the bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PtauFile<'a> {
+    pub version: u32,
+    /// Bytes per base field element.
+    pub n8: u32,
+    pub prime: BigUint,
+    /// The file holds powers of τ up to `2^power`.
+    pub power: u32,
+    /// The power the ceremony ran to, which a trimmed file keeps.
+    pub ceremony_power: u32,
+    /// Every section in file order, the header included.
+    pub sections: Vec<(u32, &'a [u8])>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PtauInfo {
+    pub version: u32,
+    pub n8: u32,
+    /// The base field modulus, in decimal.
+    pub prime: String,
+    pub power: u32,
+    pub ceremony_power: u32,
+    pub sections: Vec<SectionInfo>,
+    pub bytes: usize,
+    /// Blake2b digest of the file, as [`params::digest`] hashes.
+    pub digest: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SectionInfo {
+    pub id: u32,
+    pub bytes: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PtauError {
+    /// The file does not start with [`MAGIC`].
+    Magic,
+    /// The file ends inside the structure at this offset.
+    Truncated(usize),
+    MissingSection(u32),
+    DuplicateSection(u32),
+    /// A section is not the length the header implies.
+    SectionLength {
+        id: u32,
+        expected: usize,
+        actual: usize,
+    },
+    InvalidPower(u32),
+    /// The file's powers do not reach `2^k`.
+    TooSmall {
+        power: u32,
+        k: u32,
+    },
+}
+
+impl<'a> PtauFile<'a> {
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, PtauError> {
+        let mut reader = Reader { bytes, at: 0 };
+        if reader.take(4)? != MAGIC {
+            return Err(PtauError::Magic);
+        }
+        let version = reader.u32()?;
+        let count = reader.u32()?;
+
+        let mut sections: Vec<(u32, &[u8])> = vec![];
+        for _ in 0..count {
+            let id = reader.u32()?;
+            let len = reader.u64()?;
+            let len = usize::try_from(len).map_err(|_| PtauError::Truncated(reader.at))?;
+            if sections.iter().any(|(seen, _)| *seen == id) {
+                return Err(PtauError::DuplicateSection(id));
+            }
+            sections.push((id, reader.take(len)?));
+        }
+
+        let header = section(&sections, HEADER)?;
+        let mut reader = Reader {
+            bytes: header,
+            at: 0,
+        };
+        let n8 = reader.u32()?;
+        let prime = BigUint::from_bytes_le(reader.take(n8 as usize)?);
+        let power = reader.u32()?;
+        let ceremony_power = reader.u32()?;
+        if power > MAX_POWER {
+            return Err(PtauError::InvalidPower(power));
+        }
+
+        let file = PtauFile {
+            version,
+            n8,
+            prime,
+            power,
+            ceremony_power,
+            sections,
+        };
+        for id in [TAU_G1, TAU_G2, ALPHA_TAU_G1, BETA_TAU_G1, BETA_G2] {
+            let actual = section(&file.sections, id)?.len();
+            let expected = file.section_len(id, power);
+            if actual != expected {
+                return Err(PtauError::SectionLength {
+                    id,
+                    expected,
+                    actual,
+                });
+            }
+        }
+        Ok(file)
+    }
+
+    /// The length sections 2 to 6 have in a file of `power`.
+    pub fn section_len(&self, id: u32, power: u32) -> usize {
+        let g1 = 2 * self.n8 as usize;
+        let g2 = 4 * self.n8 as usize;
+        let n = 1usize << power;
+        match id {
+            TAU_G1 => (2 * n - 1) * g1,
+            TAU_G2 => n * g2,
+            ALPHA_TAU_G1 | BETA_TAU_G1 => n * g1,
+            BETA_G2 => g2,
+            _ => panic!("section {} has no fixed length", id),
+        }
+    }
+
+    pub fn section(&self, id: u32) -> Option<&'a [u8]> {
+        section(&self.sections, id).ok()
+    }
+
+    /// Fails unless the file commits to polynomials of a `2^k`-row circuit,
+    /// which takes `2^k` powers of τ in G1.
+    pub fn check_degree(&self, k: u32) -> Result<(), PtauError> {
+        if k > self.power {
+            return Err(PtauError::TooSmall {
+                power: self.power,
+                k,
+            });
+        }
+        Ok(())
+    }
+
+    /// The file cut down to `power = k`: the header with the new power,
+    /// the same ceremony power, and sections 2 to 5 truncated to their
+    /// first powers of τ, which are the powers a smaller ceremony would
+    /// have produced. `β` in G2 and the contributions are kept. The
+    /// Lagrange-basis sections of a prepared file are dropped, since they
+    /// are evaluations over the full domain and recomputing them takes
+    /// BN254 arithmetic.
+    pub fn trim(&self, k: u32) -> Result<Vec<u8>, PtauError> {
+        self.check_degree(k)?;
+
+        let mut header = self.n8.to_le_bytes().to_vec();
+        let mut prime = self.prime.to_bytes_le();
+        prime.resize(self.n8 as usize, 0);
+        header.extend(prime);
+        header.extend(k.to_le_bytes());
+        header.extend(self.ceremony_power.to_le_bytes());
+
+        let sections: Vec<(u32, &[u8])> = self
+            .sections
+            .iter()
+            .filter(|(id, _)| !LAGRANGE.contains(id))
+            .map(|&(id, data)| match id {
+                HEADER => (id, &header[..]),
+                TAU_G1 | TAU_G2 | ALPHA_TAU_G1 | BETA_TAU_G1 => {
+                    (id, &data[..self.section_len(id, k)])
+                }
+                _ => (id, data),
+            })
+            .collect();
+
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend(self.version.to_le_bytes());
+        bytes.extend((sections.len() as u32).to_le_bytes());
+        for (id, data) in sections {
+            bytes.extend(id.to_le_bytes());
+            bytes.extend((data.len() as u64).to_le_bytes());
+            bytes.extend(data);
+        }
+        Ok(bytes)
+    }
+}
+
+/// Parses and describes a `.ptau` file.
+pub fn info(bytes: &[u8]) -> Result<PtauInfo, PtauError> {
+    let file = PtauFile::parse(bytes)?;
+    Ok(PtauInfo {
+        version: file.version,
+        n8: file.n8,
+        prime: file.prime.to_string(),
+        power: file.power,
+        ceremony_power: file.ceremony_power,
+        sections: file
+            .sections
+            .iter()
+            .map(|(id, data)| SectionInfo {
+                id: *id,
+                bytes: data.len(),
+            })
+            .collect(),
+        bytes: bytes.len(),
+        digest: params::digest(bytes),
+    })
+}
+
+fn section<'a>(sections: &[(u32, &'a [u8])], id: u32) -> Result<&'a [u8], PtauError> {
+    sections
+        .iter()
+        .find(|(seen, _)| *seen == id)
+        .map(|(_, data)| *data)
+        .ok_or(PtauError::MissingSection(id))
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    at: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], PtauError> {
+        let end = self
+            .at
+            .checked_add(len)
+            .filter(|&end| end <= self.bytes.len())
+            .ok_or(PtauError::Truncated(self.at))?;
+        let taken = &self.bytes[self.at..end];
+        self.at = end;
+        Ok(taken)
+    }
+
+    fn u32(&mut self) -> Result<u32, PtauError> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> Result<u64, PtauError> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+}
+
+impl fmt::Display for PtauError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PtauError::Magic => write!(f, "not a .ptau file"),
+            PtauError::Truncated(at) => write!(f, "file is truncated at byte {}", at),
+            PtauError::MissingSection(id) => write!(f, "section {} is missing", id),
+            PtauError::DuplicateSection(id) => write!(f, "section {} appears twice", id),
+            PtauError::SectionLength {
+                id,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "section {} is {} bytes, the header implies {}",
+                id, actual, expected
+            ),
+            PtauError::InvalidPower(power) => {
+                write!(f, "power {} is larger than {}", power, MAX_POWER)
+            }
+            PtauError::TooSmall { power, k } => write!(
+                f,
+                "the file has powers up to 2^{}, k = {} needs 2^{}",
+                power, k, k
+            ),
+        }
+    }
+}
+
+impl error::Error for PtauError {}
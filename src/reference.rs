@@ -0,0 +1,123 @@
+//! Native Fibonacci numbers to test the circuits against.
+//!
+//! These are computed differently from the witness helpers the circuits
+//! use ([`fibonacci::nth_term`](crate::fibonacci::nth_term) steps through
+//! the recurrence): [`fib_biguint`] by fast doubling over the integers, and
+//! [`fib_field`] from it through the identity `x_n = a·F(n-2) + b·F(n-1)`.
+//! A circuit whose public output matches them computes the right thing,
+//! not just the same thing its witness generator does.
+//!
+//! The circuits compute modulo the field's order, so past
+//! [`first_wraparound`] what they expose is no longer the integer
+//! Fibonacci number; [`Expected`] puts the two side by side.
+
+use std::fmt;
+
+use halo2_proofs::arithmetic::FieldExt;
+use num_bigint::BigUint;
+
+/// `F(n)` over the integers and as the circuits see it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expected<F: FieldExt> {
+    pub n: usize,
+    pub integer: BigUint,
+    /// `integer` reduced modulo the field's order.
+    pub field: F,
+    /// How many times the order fits in `integer`, zero if it is below it.
+    pub wraps: BigUint,
+}
+
+impl<F: FieldExt> Expected<F> {
+    pub fn of(n: usize) -> Self {
+        let integer = fib_biguint(n);
+        Expected {
+            n,
+            field: to_field(&integer),
+            wraps: &integer / modulus::<F>(),
+            integer,
+        }
+    }
+
+    /// Whether the field value differs from the integer.
+    pub fn wrapped(&self) -> bool {
+        self.wraps != BigUint::from(0u8)
+    }
+}
+
+/// The `n`th Fibonacci number, with `F(0) = 0` and `F(1) = 1`.
+pub fn fib_biguint(n: usize) -> BigUint {
+    fib_pair(n).0
+}
+
+/// `(F(n), F(n+1))` by fast doubling:
+/// `F(2k) = F(k)·(2F(k+1) - F(k))` and `F(2k+1) = F(k)² + F(k+1)²`.
+fn fib_pair(n: usize) -> (BigUint, BigUint) {
+    if n == 0 {
+        return (BigUint::from(0u8), BigUint::from(1u8));
+    }
+    let (a, b) = fib_pair(n / 2);
+    let even = &a * (&b * 2u8 - &a);
+    let odd = &a * &a + &b * &b;
+    if n.is_multiple_of(2) {
+        (even, odd)
+    } else {
+        let next = &even + &odd;
+        (odd, next)
+    }
+}
+
+/// The `n`th term (counting from 1) of the sequence seeded with `a, b`, as
+/// the circuits expose it: `a` for `n = 1`, `b` for `n = 2`.
+pub fn fib_field<F: FieldExt>(a: F, b: F, n: usize) -> F {
+    assert!(n >= 1, "terms are counted from 1");
+    if n == 1 {
+        return a;
+    }
+    a * to_field::<F>(&fib_biguint(n - 2)) + b * to_field::<F>(&fib_biguint(n - 1))
+}
+
+/// `x` reduced into `F`.
+pub fn to_field<F: FieldExt>(x: &BigUint) -> F {
+    x.to_bytes_be().iter().fold(F::zero(), |acc, byte| {
+        acc * F::from(256) + F::from(*byte as u64)
+    })
+}
+
+/// The order of `F`.
+pub fn modulus<F: FieldExt>() -> BigUint {
+    BigUint::from_bytes_le((-F::one()).to_repr().as_ref()) + 1u8
+}
+
+/// The smallest `n` with `F(n)` at least the order of `F`, and so the first
+/// term a circuit over `F` cannot expose as an integer.
+pub fn first_wraparound<F: FieldExt>() -> usize {
+    let modulus = modulus::<F>();
+    let (mut n, (mut a, mut b)) = (0, (BigUint::from(0u8), BigUint::from(1u8)));
+    while a < modulus {
+        (a, b) = (b.clone(), a + b);
+        n += 1;
+    }
+    n
+}
+
+impl<F: FieldExt> fmt::Display for Expected<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "F({}) = {}", self.n, self.integer)?;
+        if self.wrapped() {
+            let field = BigUint::from_bytes_le(self.field.to_repr().as_ref());
+            write!(
+                f,
+                "in the field: {} (the integer less {} times the modulus {})",
+                field,
+                self.wraps,
+                modulus::<F>()
+            )
+        } else {
+            write!(
+                f,
+                "in the field: the same; the first term past the modulus is F({})",
+                first_wraparound::<F>()
+            )
+        }
+    }
+}
@@ -0,0 +1,539 @@
+//! Named circuits, so tools can pick a circuit by string.
+
+use std::{error, fmt, str::FromStr};
+
+use halo2_proofs::{arithmetic::FieldExt, circuit::Value, pasta::Fp, plonk::Circuit};
+
+use crate::{
+    chunked,
+    circuits::{
+        catalan, hash_chain, histogram, horner, inner_product, matmul,
+        memory::{self, Access},
+        padovan, permutation, sorting_network, sparse_merkle,
+        vm::{self, isa},
+    },
+    fibonacci::{
+        self, batch, bounded, checkpoint, descent, fixed_selector, last_digit, negative_rotation,
+        non_member, parity,
+        public_inputs::{public_inputs_for, InstanceMode},
+        single_column, square_test, stride, table, three_column, vertical,
+    },
+    gadgets::{
+        matrix,
+        sparse_merkle::SparseMerkleTree,
+        version::{self, Versioned, CIRCUIT_VERSION},
+    },
+};
+
+/// Every circuit the crate knows how to build by name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CircuitKind {
+    /// `fibonacci::three_column`, one step per row with copies between rows.
+    FibThreeColumn,
+    /// `fibonacci::single_column`, the whole table in one region.
+    FibSingleColumn,
+    /// `fibonacci::batch` with [`BATCH_SEEDS`]`.len()` sequences.
+    FibBatch,
+    /// `fibonacci::chunk` covering [`CHUNK_STEPS`] steps.
+    FibChunk,
+    /// `fibonacci::stride` advancing [`STRIDE`] steps per row.
+    FibStride,
+    /// `fibonacci::checkpoint` exposing the terms in [`CHECKPOINTS`].
+    FibCheckpoint,
+    /// `fibonacci::descent` from `F(NUM_TERMS), F(NUM_TERMS + 1)`, with room
+    /// for [`DESCENT_STEPS`] steps.
+    FibDescent,
+    /// `fibonacci::parity`, exposing only the parity of the last term.
+    FibParity,
+    /// `fibonacci::last_digit`, the terms reduced mod 10.
+    FibLastDigit,
+    /// `fibonacci::bounded`, the output proven below `2^`[`BOUND_BITS`].
+    FibBounded,
+    /// `fibonacci::fixed_selector`, the three-column table gated by a fixed
+    /// column instead of a selector.
+    FibFixedSelector,
+    /// `fibonacci::table`, looking up `(NUM_TERMS, F(NUM_TERMS))` in the
+    /// terms up to the capacity of [`TABLE_K`].
+    FibTable,
+    /// `fibonacci::square_test` on `F(NUM_TERMS), F(NUM_TERMS + 1)`.
+    FibSquareTest,
+    /// `fibonacci::non_member` showing [`NON_MEMBER_VALUE`] is none of the
+    /// first `NUM_TERMS` terms.
+    FibNonMember,
+    /// `fibonacci::vertical`, additions on the universal gate of
+    /// `gadgets::flex_gate`.
+    FibVertical,
+    /// `fibonacci::negative_rotation`, the single-column table with a gate
+    /// reading the two rows before the one it constrains.
+    FibNegativeRotation,
+    /// `fibonacci::three_column` wrapped in `gadgets::version::Versioned`,
+    /// binding its key and proofs to [`CIRCUIT_VERSION`].
+    FibVersioned,
+    /// `circuits::padovan`, seeded with `1, 1, 1`.
+    Padovan,
+    /// `circuits::catalan`, which has no witness.
+    Catalan,
+    /// `circuits::hash_chain` from `1`, [`HASH_CHAIN_STEPS`] hashes long.
+    HashChain,
+    /// `circuits::histogram` of the last digits of the first `NUM_TERMS`
+    /// terms.
+    Histogram,
+    /// `circuits::horner` evaluating [`HORNER_COEFFS`] at [`HORNER_POINT`].
+    Horner,
+    /// `circuits::inner_product` of [`INNER_PRODUCT_VECTORS`].
+    InnerProduct,
+    /// `circuits::matmul` of the 4×4 [`MATMUL_LHS`] and [`MATMUL_RHS`].
+    MatMul,
+    /// `circuits::memory` running [`MEMORY_ACCESSES`].
+    Memory,
+    /// `circuits::permutation` of [`PERMUTATION_LIST`] into
+    /// [`PERMUTATION_WITNESS`].
+    Permutation,
+    /// `circuits::sorting_network` sorting [`SORTING_INPUT`].
+    SortingNetwork,
+    /// `circuits::sparse_merkle` looking up the leaf at `NUM_TERMS` in
+    /// [`sparse_merkle_demo`].
+    SparseMerkle,
+    /// `circuits::vm` running the Fibonacci program for [`VM_ITERATIONS`]
+    /// loop iterations.
+    Vm,
+}
+
+/// Seeds of the sequences in the [`CircuitKind::FibBatch`] demo.
+pub const BATCH_SEEDS: [(u64, u64); 3] = [(1, 1), (2, 3), (5, 8)];
+
+/// Steps per proof in the [`CircuitKind::FibChunk`] demo.
+pub const CHUNK_STEPS: usize = 8;
+
+/// Steps per row in the [`CircuitKind::FibStride`] demo.
+pub const STRIDE: usize = 4;
+
+/// Terms made public in the [`CircuitKind::FibCheckpoint`] demo.
+pub const CHECKPOINTS: [usize; 3] = [1, 5, fibonacci::NUM_TERMS];
+
+/// Steps the [`CircuitKind::FibDescent`] demo has room for.
+pub const DESCENT_STEPS: usize = 12;
+
+/// Bits the [`CircuitKind::FibBounded`] demo bounds its output to.
+pub const BOUND_BITS: usize = 16;
+
+/// Steps of the [`CircuitKind::HashChain`] demo, as many as the Fibonacci
+/// demos take to reach their last term.
+pub const HASH_CHAIN_STEPS: usize = fibonacci::NUM_TERMS - 2;
+
+/// The `k` whose capacity the [`CircuitKind::FibTable`] demo's table
+/// fills.
+pub const TABLE_K: u32 = 5;
+
+/// The value the [`CircuitKind::FibNonMember`] demo proves is not a term.
+pub const NON_MEMBER_VALUE: u64 = 4;
+
+/// Coefficients, highest degree first, of the [`CircuitKind::Horner`] demo.
+pub const HORNER_COEFFS: [u64; 4] = [1, 2, 3, 4];
+
+/// Evaluation point of the [`CircuitKind::Horner`] demo.
+pub const HORNER_POINT: u64 = 5;
+
+/// The vectors of the [`CircuitKind::InnerProduct`] demo.
+pub const INNER_PRODUCT_VECTORS: ([u64; 4], [u64; 4]) = ([1, 2, 3, 4], [5, 6, 7, 8]);
+
+/// The left operand of the [`CircuitKind::MatMul`] demo.
+pub const MATMUL_LHS: [[u64; 4]; 4] = [
+    [1, 2, 3, 4],
+    [5, 6, 7, 8],
+    [9, 10, 11, 12],
+    [13, 14, 15, 16],
+];
+
+/// The right operand of the [`CircuitKind::MatMul`] demo.
+pub const MATMUL_RHS: [[u64; 4]; 4] = [[0, 1, 0, 0], [1, 1, 0, 0], [0, 0, 2, 0], [0, 0, 0, 3]];
+
+/// The accesses of the [`CircuitKind::Memory`] demo, whose writes store
+/// [`MEMORY_WRITES`].
+pub const MEMORY_ACCESSES: [Access; 7] = [
+    Access::Write(0),
+    Access::Write(1),
+    Access::Read(0),
+    Access::Write(0),
+    Access::Read(1),
+    Access::Read(0),
+    Access::Read(2),
+];
+
+/// The values written in the [`CircuitKind::Memory`] demo.
+pub const MEMORY_WRITES: [u64; 3] = [5, 7, 9];
+
+/// The public list of the [`CircuitKind::Permutation`] demo.
+pub const PERMUTATION_LIST: [u64; 6] = [3, 1, 4, 1, 5, 9];
+
+/// The witnessed permutation of [`PERMUTATION_LIST`].
+pub const PERMUTATION_WITNESS: [u64; 6] = [1, 9, 4, 3, 5, 1];
+
+/// The public array of the [`CircuitKind::SortingNetwork`] demo.
+pub const SORTING_INPUT: [u64; 8] = [5, 3, 8, 1, 9, 2, 7, 3];
+
+/// Depth of the tree in the [`CircuitKind::SparseMerkle`] demo.
+pub const SPARSE_MERKLE_DEPTH: usize = 8;
+
+/// The tree of the [`CircuitKind::SparseMerkle`] demo: key `i` holds
+/// `F(i)` for `i` in `1..=NUM_TERMS`, and every other leaf is empty.
+pub fn sparse_merkle_demo() -> SparseMerkleTree<Fp> {
+    let mut tree = SparseMerkleTree::new(SPARSE_MERKLE_DEPTH);
+    for i in 1..=fibonacci::NUM_TERMS {
+        tree.insert(i as u64, fibonacci::nth_term(Fp::from(1), Fp::from(1), i));
+    }
+    tree
+}
+
+/// Loop iterations of the program in the [`CircuitKind::Vm`] demo.
+pub const VM_ITERATIONS: u64 = 4;
+
+/// Execution rows of the [`CircuitKind::Vm`] demo, enough for up to five
+/// iterations.
+pub const VM_ROWS: usize = 25;
+
+impl CircuitKind {
+    pub const ALL: [CircuitKind; 29] = [
+        CircuitKind::FibThreeColumn,
+        CircuitKind::FibSingleColumn,
+        CircuitKind::FibBatch,
+        CircuitKind::FibChunk,
+        CircuitKind::FibStride,
+        CircuitKind::FibCheckpoint,
+        CircuitKind::FibDescent,
+        CircuitKind::FibParity,
+        CircuitKind::FibLastDigit,
+        CircuitKind::FibBounded,
+        CircuitKind::FibFixedSelector,
+        CircuitKind::FibTable,
+        CircuitKind::FibSquareTest,
+        CircuitKind::FibNonMember,
+        CircuitKind::FibVertical,
+        CircuitKind::FibNegativeRotation,
+        CircuitKind::FibVersioned,
+        CircuitKind::Padovan,
+        CircuitKind::Catalan,
+        CircuitKind::HashChain,
+        CircuitKind::Histogram,
+        CircuitKind::Horner,
+        CircuitKind::InnerProduct,
+        CircuitKind::MatMul,
+        CircuitKind::Memory,
+        CircuitKind::Permutation,
+        CircuitKind::SortingNetwork,
+        CircuitKind::SparseMerkle,
+        CircuitKind::Vm,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            CircuitKind::FibThreeColumn => "fib-three-column",
+            CircuitKind::FibSingleColumn => "fib-single-column",
+            CircuitKind::FibBatch => "fib-batch",
+            CircuitKind::FibChunk => "fib-chunk",
+            CircuitKind::FibStride => "fib-stride",
+            CircuitKind::FibCheckpoint => "fib-checkpoint",
+            CircuitKind::FibDescent => "fib-descent",
+            CircuitKind::FibParity => "fib-parity",
+            CircuitKind::FibLastDigit => "fib-last-digit",
+            CircuitKind::FibBounded => "fib-bounded",
+            CircuitKind::FibFixedSelector => "fib-fixed-selector",
+            CircuitKind::FibTable => "fib-table",
+            CircuitKind::FibSquareTest => "fib-square-test",
+            CircuitKind::FibNonMember => "fib-non-member",
+            CircuitKind::FibVertical => "fib-vertical",
+            CircuitKind::FibNegativeRotation => "fib-negative-rotation",
+            CircuitKind::FibVersioned => "fib-versioned",
+            CircuitKind::Padovan => "padovan",
+            CircuitKind::Catalan => "catalan",
+            CircuitKind::HashChain => "hash-chain",
+            CircuitKind::Histogram => "histogram",
+            CircuitKind::Horner => "horner",
+            CircuitKind::InnerProduct => "inner-product",
+            CircuitKind::MatMul => "matmul",
+            CircuitKind::Memory => "memory",
+            CircuitKind::Permutation => "permutation",
+            CircuitKind::SortingNetwork => "sorting-network",
+            CircuitKind::SparseMerkle => "sparse-merkle",
+            CircuitKind::Vm => "vm",
+        }
+    }
+
+    /// The size of the demo statement: the terms of a sequence, seeds
+    /// included, or the steps, entries or accesses the other circuits
+    /// prove something about. Proof envelopes record it beside `k`.
+    pub fn n(&self) -> usize {
+        match self {
+            CircuitKind::FibChunk => CHUNK_STEPS,
+            CircuitKind::FibDescent => DESCENT_STEPS,
+            CircuitKind::HashChain => HASH_CHAIN_STEPS,
+            CircuitKind::Horner => HORNER_COEFFS.len(),
+            CircuitKind::InnerProduct => INNER_PRODUCT_VECTORS.0.len(),
+            CircuitKind::MatMul => MATMUL_LHS.len(),
+            CircuitKind::Memory => MEMORY_ACCESSES.len(),
+            CircuitKind::Permutation => PERMUTATION_LIST.len(),
+            CircuitKind::SortingNetwork => SORTING_INPUT.len(),
+            CircuitKind::Vm => VM_ITERATIONS as usize,
+            CircuitKind::FibThreeColumn
+            | CircuitKind::FibSingleColumn
+            | CircuitKind::FibBatch
+            | CircuitKind::FibStride
+            | CircuitKind::FibCheckpoint
+            | CircuitKind::FibParity
+            | CircuitKind::FibLastDigit
+            | CircuitKind::FibBounded
+            | CircuitKind::FibFixedSelector
+            | CircuitKind::FibTable
+            | CircuitKind::FibSquareTest
+            | CircuitKind::FibNonMember
+            | CircuitKind::FibVertical
+            | CircuitKind::FibNegativeRotation
+            | CircuitKind::FibVersioned
+            | CircuitKind::Padovan
+            | CircuitKind::Catalan
+            | CircuitKind::Histogram
+            | CircuitKind::SparseMerkle => fibonacci::NUM_TERMS,
+        }
+    }
+
+    /// Builds the demo witness for this circuit (seeds `1, 1`, or
+    /// [`BATCH_SEEDS`] for the batch circuit and `1, 1, 1` for Padovan) and
+    /// hands it, together with its
+    /// instance columns, to `visitor`.
+    pub fn visit<V: CircuitVisitor>(&self, visitor: V) -> V::Output {
+        let (a, b) = (Fp::from(1), Fp::from(1));
+        let instances = public_inputs_for(a, b, fibonacci::NUM_TERMS, &InstanceMode::Final);
+        let (a, b) = (Value::known(a), Value::known(b));
+
+        match self {
+            CircuitKind::FibThreeColumn => {
+                visitor.visit(three_column::FiboCircuit { a, b }, instances)
+            }
+            CircuitKind::FibSingleColumn => {
+                visitor.visit(single_column::FiboCircuit { a, b }, instances)
+            }
+            CircuitKind::FibBatch => {
+                let seeds = BATCH_SEEDS.map(|(a, b)| (Fp::from(a), Fp::from(b)));
+                let outputs = seeds
+                    .iter()
+                    .map(|&(a, b)| fibonacci::nth_term(a, b, fibonacci::NUM_TERMS))
+                    .collect();
+                let seeds = seeds.map(|(a, b)| (Value::known(a), Value::known(b)));
+                visitor.visit(batch::FiboBatchCircuit { seeds }, vec![outputs])
+            }
+            CircuitKind::FibChunk => {
+                let (circuit, instances) = chunked::chunk::<CHUNK_STEPS>(Fp::from(1), Fp::from(1));
+                visitor.visit(circuit, instances)
+            }
+            CircuitKind::FibStride => {
+                visitor.visit(stride::FiboStrideCircuit::<_, STRIDE> { a, b }, instances)
+            }
+            CircuitKind::FibCheckpoint => {
+                let checkpoints = CHECKPOINTS.to_vec();
+                let mode = InstanceMode::Checkpoints(checkpoints.clone());
+                let instances =
+                    public_inputs_for(Fp::from(1), Fp::from(1), fibonacci::NUM_TERMS, &mode);
+                let circuit = checkpoint::FiboCheckpointCircuit { a, b, checkpoints };
+                visitor.visit(circuit, instances)
+            }
+            CircuitKind::FibDescent => {
+                let x = fibonacci::nth_term(Fp::from(1), Fp::from(1), fibonacci::NUM_TERMS);
+                let y = fibonacci::nth_term(Fp::from(1), Fp::from(1), fibonacci::NUM_TERMS + 1);
+                let circuit = descent::DescentCircuit::<_, DESCENT_STEPS> {
+                    x: Value::known(x),
+                    y: Value::known(y),
+                };
+                visitor.visit(circuit, vec![vec![x, y]])
+            }
+            CircuitKind::FibParity => {
+                let last = fibonacci::nth_term(Fp::from(1), Fp::from(1), fibonacci::NUM_TERMS);
+                let bit = Fp::from(last.get_lower_128() as u64 & 1);
+                visitor.visit(parity::FiboParityCircuit { a, b }, vec![vec![bit]])
+            }
+            CircuitKind::FibLastDigit => {
+                let digit = Fp::from(last_digit::nth_digit(1, 1, fibonacci::NUM_TERMS));
+                visitor.visit(last_digit::LastDigitCircuit { a, b }, vec![vec![digit]])
+            }
+            CircuitKind::FibBounded => visitor.visit(
+                bounded::FiboBoundedCircuit::<_, BOUND_BITS> { a, b },
+                instances,
+            ),
+            CircuitKind::FibFixedSelector => {
+                visitor.visit(fixed_selector::FiboFixedCircuit { a, b }, instances)
+            }
+            CircuitKind::FibTable => {
+                let instances = public_inputs_for(
+                    Fp::from(1),
+                    Fp::from(1),
+                    fibonacci::NUM_TERMS,
+                    &InstanceMode::IndexAndTerm,
+                );
+                visitor.visit(table::FibTableCircuit::new(TABLE_K), instances)
+            }
+            CircuitKind::FibSquareTest => {
+                let x = fibonacci::nth_term(Fp::from(1), Fp::from(1), fibonacci::NUM_TERMS);
+                let y = fibonacci::nth_term(Fp::from(1), Fp::from(1), fibonacci::NUM_TERMS + 1);
+                let circuit = square_test::SquareTestCircuit {
+                    x: Value::known(x),
+                    y: Value::known(y),
+                };
+                visitor.visit(circuit, vec![vec![x, y]])
+            }
+            CircuitKind::FibNonMember => {
+                let v = Fp::from(NON_MEMBER_VALUE);
+                let circuit = non_member::NonMemberCircuit::<_, { fibonacci::NUM_TERMS }> {
+                    v: Value::known(v),
+                };
+                visitor.visit(circuit, vec![vec![v]])
+            }
+            CircuitKind::FibVertical => {
+                visitor.visit(vertical::FiboVerticalCircuit { a, b }, instances)
+            }
+            CircuitKind::FibNegativeRotation => {
+                visitor.visit(negative_rotation::FiboCircuit { a, b }, instances)
+            }
+            CircuitKind::FibVersioned => visitor.visit(
+                Versioned::new(three_column::FiboCircuit { a, b }),
+                version::instances(instances, CIRCUIT_VERSION),
+            ),
+            CircuitKind::Padovan => {
+                let seeds = [Fp::from(1); 3];
+                let output = padovan::nth_term(seeds, fibonacci::NUM_TERMS);
+                let seeds = seeds.map(Value::known);
+                visitor.visit(padovan::PadovanCircuit { seeds }, vec![vec![output]])
+            }
+            CircuitKind::Catalan => {
+                let output = catalan::nth_term(fibonacci::NUM_TERMS - 1);
+                visitor.visit(catalan::CatalanCircuit::default(), vec![vec![output]])
+            }
+            CircuitKind::HashChain => {
+                let head = Fp::from(1);
+                let tail = hash_chain::nth_state(head, HASH_CHAIN_STEPS);
+                let circuit = hash_chain::HashChainCircuit::<_, HASH_CHAIN_STEPS> {
+                    head: Value::known(head),
+                };
+                visitor.visit(circuit, vec![vec![head, tail]])
+            }
+            CircuitKind::Histogram => {
+                let circuit = histogram::HistogramCircuit {
+                    a,
+                    b,
+                    histogram: histogram::histogram(1, 1, fibonacci::NUM_TERMS),
+                };
+                let gamma = Fp::from(permutation::PERMUTATION_CHALLENGE);
+                visitor.visit(circuit, vec![vec![gamma]])
+            }
+            CircuitKind::Horner => {
+                let coeffs = HORNER_COEFFS.map(Fp::from).to_vec();
+                let x = Fp::from(HORNER_POINT);
+                let y = horner::evaluate(&coeffs, x);
+                visitor.visit(horner::HornerCircuit { coeffs }, vec![vec![x, y]])
+            }
+            CircuitKind::InnerProduct => {
+                let (a, b) = INNER_PRODUCT_VECTORS;
+                let (a, b) = (a.map(Fp::from), b.map(Fp::from));
+                let c = inner_product::inner_product(&a, &b);
+                let (a, b) = (a.map(Value::known), b.map(Value::known));
+                visitor.visit(inner_product::InnerProductCircuit { a, b }, vec![vec![c]])
+            }
+            CircuitKind::MatMul => {
+                let (a, b) = (
+                    MATMUL_LHS.map(|row| row.map(Fp::from)),
+                    MATMUL_RHS.map(|row| row.map(Fp::from)),
+                );
+                let c = matrix::mul(&a.map(Vec::from), &b.map(Vec::from));
+                let (a, b) = (
+                    a.map(|row| row.map(Value::known)),
+                    b.map(|row| row.map(Value::known)),
+                );
+                visitor.visit(matmul::MatMulCircuit { a, b }, vec![c.concat()])
+            }
+            CircuitKind::Memory => {
+                let writes = MEMORY_WRITES.map(Fp::from);
+                let values = memory::run(&MEMORY_ACCESSES, &writes);
+                let reads = memory::public_inputs(&MEMORY_ACCESSES, &values);
+                let circuit = memory::MemoryCircuit::new(MEMORY_ACCESSES.to_vec(), &writes);
+                visitor.visit(circuit, vec![reads])
+            }
+            CircuitKind::Permutation => {
+                let gamma = Fp::from(permutation::PERMUTATION_CHALLENGE);
+                let list = PERMUTATION_LIST.map(Fp::from);
+                let circuit = permutation::PermutationCircuit {
+                    permuted: PERMUTATION_WITNESS.map(|x| Value::known(Fp::from(x))),
+                };
+                visitor.visit(circuit, permutation::instances(gamma, &list))
+            }
+            CircuitKind::SortingNetwork => {
+                let mut sorted = SORTING_INPUT;
+                sorted.sort();
+                let circuit = sorting_network::SortingNetworkCircuit {
+                    sorted: sorted.map(|x| Value::known(Fp::from(x))),
+                };
+                visitor.visit(circuit, vec![SORTING_INPUT.map(Fp::from).to_vec()])
+            }
+            CircuitKind::SparseMerkle => {
+                let (circuit, instances) =
+                    sparse_merkle::SparseMerkleCircuit::<_, SPARSE_MERKLE_DEPTH>::new(
+                        &sparse_merkle_demo(),
+                        fibonacci::NUM_TERMS as u64,
+                    );
+                visitor.visit(circuit, instances)
+            }
+            CircuitKind::Vm => {
+                let program = isa::fibonacci_program(VM_ITERATIONS);
+                let trace = isa::execute::<Fp>(&program, VM_ROWS).expect("the demo halts");
+                let output = trace.last().unwrap().registers[1];
+                visitor.visit(vm::VmCircuit::new(program, VM_ROWS), vec![vec![output]])
+            }
+        }
+    }
+}
+
+/// Something that can be run against any concrete circuit type.
+///
+/// `Circuit` is not object safe, so dispatching on a [`CircuitKind`] goes
+/// through this trait instead of a `Box<dyn Circuit>`. Every registered
+/// circuit is `Send + Sync`, which the bound checks at compile time, so a
+/// visitor may hand the circuit to other threads.
+pub trait CircuitVisitor {
+    type Output;
+
+    fn visit<C: Circuit<Fp> + fmt::Debug + Send + Sync>(
+        self,
+        circuit: C,
+        instances: Vec<Vec<Fp>>,
+    ) -> Self::Output;
+}
+
+impl fmt::Display for CircuitKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for CircuitKind {
+    type Err = UnknownCircuit;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        CircuitKind::ALL
+            .into_iter()
+            .find(|kind| kind.name() == s)
+            .ok_or_else(|| UnknownCircuit(s.to_string()))
+    }
+}
+
+/// Returned when a circuit name does not match any [`CircuitKind`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownCircuit(pub String);
+
+impl fmt::Display for UnknownCircuit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown circuit `{}`, expected one of:", self.0)?;
+        for kind in CircuitKind::ALL {
+            write!(f, " {}", kind)?;
+        }
+        Ok(())
+    }
+}
+
+impl error::Error for UnknownCircuit {}
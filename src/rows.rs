@@ -0,0 +1,104 @@
+//! A cursor over the rows of one region, for layouts that fill a long
+//! region row by row.
+//!
+//! The single-region layouts used to count offsets by hand, with the
+//! selector, the advice and the fixed cells of a step each taking the same
+//! `row` or `row + 1`. [`RowCursor`] holds the offset: cells are assigned
+//! and selectors enabled on the current row, [`advance`](RowCursor::advance)
+//! moves to the one below, and [`checkpoint`](RowCursor::checkpoint) names
+//! the current row so the layout, or a test, can find it again.
+
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Region, Value},
+    plonk::{Advice, Column, Error, Fixed, Selector},
+};
+
+use crate::fibonacci::ACell;
+
+#[derive(Debug)]
+pub struct RowCursor<'r, 'a, F: FieldExt> {
+    region: &'r mut Region<'a, F>,
+    row: usize,
+    checkpoints: Vec<(&'static str, usize)>,
+}
+
+impl<'r, 'a, F: FieldExt> RowCursor<'r, 'a, F> {
+    /// A cursor on the first row of `region`.
+    pub fn new(region: &'r mut Region<'a, F>) -> Self {
+        Self {
+            region,
+            row: 0,
+            checkpoints: vec![],
+        }
+    }
+
+    /// The current row's offset in the region.
+    pub fn row(&self) -> usize {
+        self.row
+    }
+
+    /// Moves to the next row and returns its offset.
+    pub fn advance(&mut self) -> usize {
+        self.row += 1;
+        self.row
+    }
+
+    pub fn enable(&mut self, selector: Selector) -> Result<(), Error> {
+        selector.enable(self.region, self.row)
+    }
+
+    pub fn assign_advice(
+        &mut self,
+        annotation: &str,
+        column: Column<Advice>,
+        value: Value<F>,
+    ) -> Result<ACell<F>, Error> {
+        self.region
+            .assign_advice(|| annotation, column, self.row, || value)
+    }
+
+    pub fn assign_fixed(
+        &mut self,
+        annotation: &str,
+        column: Column<Fixed>,
+        value: F,
+    ) -> Result<ACell<F>, Error> {
+        self.region
+            .assign_fixed(|| annotation, column, self.row, || Value::known(value))
+    }
+
+    /// Copies `cell` into `column` on the current row.
+    pub fn copy_advice(
+        &mut self,
+        annotation: &str,
+        cell: &ACell<F>,
+        column: Column<Advice>,
+    ) -> Result<ACell<F>, Error> {
+        cell.copy_advice(|| annotation, self.region, column, self.row)
+    }
+
+    /// Names the current row. A name may be recorded more than once, and
+    /// [`checkpoint_row`](Self::checkpoint_row) finds the latest.
+    pub fn checkpoint(&mut self, name: &'static str) {
+        self.checkpoints.push((name, self.row));
+    }
+
+    pub fn checkpoint_row(&self, name: &str) -> Option<usize> {
+        self.checkpoints
+            .iter()
+            .rev()
+            .find(|(checkpoint, _)| *checkpoint == name)
+            .map(|&(_, row)| row)
+    }
+
+    /// Every checkpoint in the order recorded.
+    pub fn checkpoints(&self) -> &[(&'static str, usize)] {
+        &self.checkpoints
+    }
+
+    /// The region itself, for what the cursor does not cover.
+    pub fn region(&mut self) -> &mut Region<'a, F> {
+        self.region
+    }
+}
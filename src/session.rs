@@ -0,0 +1,105 @@
+//! Verifying many proofs of one circuit with its key generated once.
+//!
+//! [`verification::verify`] regenerates the verifying key for every
+//! envelope, and keygen costs more than checking the proof. A
+//! [`VerifierSession`] holds the parameters and the key of one circuit, so
+//! a service or batch job pays for them once and then only runs the
+//! verifier. A session is `Send + Sync`, so one can serve many threads.
+//!
+//! [`verification::verify`]: crate::verification::verify
+
+use std::fmt;
+
+use halo2_proofs::{
+    pasta::{EqAffine, Fp},
+    plonk::{Circuit, Error, VerifyingKey},
+    poly::commitment::Params,
+};
+
+use crate::{
+    envelope::ProofEnvelope,
+    fingerprint::{fingerprint, Fingerprint},
+    inspect::{self, Check},
+    prover,
+    registry::{CircuitKind, CircuitVisitor},
+    verification::{self, Verification},
+};
+
+#[derive(Debug)]
+pub struct VerifierSession {
+    kind: CircuitKind,
+    params: Params<EqAffine>,
+    vk: VerifyingKey<EqAffine>,
+    fingerprint: Fingerprint,
+}
+
+impl VerifierSession {
+    /// Generates the verifying key of `kind` at the size of `params`.
+    pub fn new(kind: CircuitKind, params: Params<EqAffine>) -> Result<Self, Error> {
+        let vk = kind.visit(Keygen(&params))?;
+        Ok(VerifierSession {
+            kind,
+            fingerprint: fingerprint(&vk),
+            params,
+            vk,
+        })
+    }
+
+    pub fn kind(&self) -> CircuitKind {
+        self.kind
+    }
+
+    pub fn params(&self) -> &Params<EqAffine> {
+        &self.params
+    }
+
+    pub fn vk(&self) -> &VerifyingKey<EqAffine> {
+        &self.vk
+    }
+
+    pub fn fingerprint(&self) -> &Fingerprint {
+        &self.fingerprint
+    }
+
+    /// Runs the verifier alone on `proof`.
+    pub fn verify(&self, proof: &[u8], instances: &[Vec<Fp>]) -> Result<(), Error> {
+        prover::verify_proof(&self.params, &self.vk, proof, instances)
+    }
+
+    /// Checks `envelope` as [`verification::verify`] does an envelope out
+    /// of a bundle, after first checking it names this session's circuit.
+    /// No keygen runs, so `keygen_ms` stays 0.
+    pub fn verify_envelope(&self, envelope: &ProofEnvelope) -> Verification {
+        let mut verification = Verification::of(envelope);
+        let named = envelope.circuit == self.kind.name();
+        verification.check(Check::new(
+            "circuit",
+            named,
+            if named {
+                self.kind.to_string()
+            } else {
+                format!(
+                    "envelope is for {}, the session verifies {}",
+                    envelope.circuit, self.kind
+                )
+            },
+        ));
+        if verification.valid {
+            verification.check(inspect::statement(self.kind, envelope));
+        }
+        if verification.valid {
+            verification::check_key(&mut verification, envelope, None, &self.params, &self.vk);
+        }
+        verification
+    }
+}
+
+struct Keygen<'a>(&'a Params<EqAffine>);
+
+impl CircuitVisitor for Keygen<'_> {
+    type Output = Result<VerifyingKey<EqAffine>, Error>;
+
+    fn visit<C: Circuit<Fp> + fmt::Debug>(self, circuit: C, _: Vec<Vec<Fp>>) -> Self::Output {
+        prover::keygen_vk(self.0, &circuit)
+    }
+}
@@ -0,0 +1,236 @@
+//! Random cases through the real prover, for the bugs only some inputs
+//! reach.
+//!
+//! Each case is drawn from its own 64-bit seed: a layout, seeds `a, b`
+//! that are small integers half the time and any field element otherwise,
+//! and a number of terms that fits `k`. [`run_case`] generates the keys
+//! twice and requires the same verifying key, proves against the term
+//! [`reference`] computes, verifies, and checks the proof does not also
+//! verify against the next field element. A run with seed `s` takes case
+//! `i` from seed `s + i`, so a failing case replays on its own as a run of
+//! one from its seed.
+
+use std::fmt;
+
+use halo2_proofs::{
+    arithmetic::Field,
+    circuit::Value,
+    pasta::{group::ff::PrimeField, EqAffine, Fp},
+    plonk::Circuit,
+    poly::commitment::Params,
+};
+use num_bigint::BigUint;
+use rand_core::RngCore;
+use serde::Serialize;
+
+use crate::{
+    fibonacci::{single_column, three_column},
+    fingerprint::fingerprint,
+    prover, reference,
+    rng::SplitMix64,
+    spec::Layout,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SoakCase {
+    pub seed: u64,
+    pub layout: Layout,
+    pub a: Fp,
+    pub b: Fp,
+    /// Terms, seeds included.
+    pub n: usize,
+}
+
+impl SoakCase {
+    /// The case `seed` names, with `n` drawn from what fits `k`. `k` must
+    /// fit at least three terms in both layouts.
+    pub fn generate(seed: u64, k: u32) -> Self {
+        let mut rng = SplitMix64::new(seed);
+        let layout = Layout::ALL[rng.next_u64() as usize % Layout::ALL.len()];
+        let seed_value = |rng: &mut SplitMix64| {
+            if rng.next_u64() & 1 == 0 {
+                Fp::from(rng.next_u64() % 1000)
+            } else {
+                Fp::random(&mut *rng)
+            }
+        };
+        let (a, b) = (seed_value(&mut rng), seed_value(&mut rng));
+        let usable = layout.usable_rows(k);
+        let max = (3..).take_while(|&n| layout.rows(n) <= usable).last();
+        let max = max.expect("k fits three terms");
+        let n = 3 + rng.next_u64() as usize % (max - 2);
+        Self {
+            seed,
+            layout,
+            a,
+            b,
+            n,
+        }
+    }
+
+    /// The `n`th term from the reference, not the witness generator.
+    pub fn expected(&self) -> Fp {
+        reference::fib_field(self.a, self.b, self.n)
+    }
+}
+
+/// What went wrong with a case.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mismatch {
+    /// Keygen, proving or synthesis failed outright.
+    Error(String),
+    /// Generating the verifying key twice gave different keys.
+    Keygen,
+    /// The proof of the reference term did not verify.
+    Rejected(String),
+    /// The proof also verified against a term it was not made for.
+    AcceptedWrongTerm,
+}
+
+/// A failed case, with the seeds in decimal as the example binaries read
+/// them.
+#[derive(Debug, Clone, Serialize)]
+pub struct SoakFailure {
+    pub seed: u64,
+    pub layout: &'static str,
+    pub a: String,
+    pub b: String,
+    pub n: usize,
+    pub k: u32,
+    pub mismatch: String,
+}
+
+impl SoakFailure {
+    pub fn new(case: &SoakCase, k: u32, mismatch: &impl fmt::Display) -> Self {
+        let decimal = |x: Fp| BigUint::from_bytes_le(x.to_repr().as_ref()).to_string();
+        Self {
+            seed: case.seed,
+            layout: case.layout.name(),
+            a: decimal(case.a),
+            b: decimal(case.b),
+            n: case.n,
+            k,
+            mismatch: mismatch.to_string(),
+        }
+    }
+}
+
+/// The outcome of a soak run.
+#[derive(Debug, Clone, Serialize)]
+pub struct SoakReport {
+    pub seed: u64,
+    pub k: u32,
+    pub cases: usize,
+    pub failures: Vec<SoakFailure>,
+}
+
+impl SoakReport {
+    pub fn passed(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+/// Proves and verifies one case with `params`, which must be for `k`.
+pub fn run_case(params: &Params<EqAffine>, case: &SoakCase) -> Result<(), Mismatch> {
+    let (a, b) = (Value::known(case.a), Value::known(case.b));
+    match case.layout {
+        Layout::ThreeColumn => {
+            let circuit = three_column::FiboTermsCircuit { a, b, n: case.n };
+            prove_and_verify(params, circuit, case.expected())
+        }
+        Layout::SingleColumn => {
+            let circuit = single_column::FiboTermsCircuit { a, b, n: case.n };
+            prove_and_verify(params, circuit, case.expected())
+        }
+    }
+}
+
+fn prove_and_verify<C: Circuit<Fp>>(
+    params: &Params<EqAffine>,
+    circuit: C,
+    expected: Fp,
+) -> Result<(), Mismatch> {
+    let error = |e| Mismatch::Error(format!("{:?}", e));
+    let pk = prover::keygen(params, &circuit).map_err(error)?;
+    let vk = prover::keygen_vk(params, &circuit).map_err(error)?;
+    if fingerprint(pk.get_vk()) != fingerprint(&vk) {
+        return Err(Mismatch::Keygen);
+    }
+
+    let instances = vec![vec![expected]];
+    let proof = prover::create_proof(params, &pk, circuit, &instances).map_err(error)?;
+    prover::verify_proof(params, &vk, &proof, &instances)
+        .map_err(|e| Mismatch::Rejected(format!("{:?}", e)))?;
+    let wrong = vec![vec![expected + Fp::one()]];
+    if prover::verify_proof(params, &vk, &proof, &wrong).is_ok() {
+        return Err(Mismatch::AcceptedWrongTerm);
+    }
+    Ok(())
+}
+
+/// Runs `cases` cases from `seed` at `k`, calling `on_case` after each
+/// with its index and failure, if any.
+pub fn soak(
+    params: &Params<EqAffine>,
+    k: u32,
+    seed: u64,
+    cases: usize,
+    mut on_case: impl FnMut(usize, Option<&SoakFailure>),
+) -> SoakReport {
+    let mut failures = vec![];
+    for i in 0..cases {
+        let case = SoakCase::generate(seed.wrapping_add(i as u64), k);
+        match run_case(params, &case) {
+            Ok(()) => on_case(i, None),
+            Err(mismatch) => {
+                let failure = SoakFailure::new(&case, k, &mismatch);
+                on_case(i, Some(&failure));
+                failures.push(failure);
+            }
+        }
+    }
+    SoakReport {
+        seed,
+        k,
+        cases,
+        failures,
+    }
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Mismatch::Error(e) => write!(f, "failed to prove: {}", e),
+            Mismatch::Keygen => f.write_str("keygen is not deterministic"),
+            Mismatch::Rejected(e) => write!(f, "proof of the reference term rejected: {}", e),
+            Mismatch::AcceptedWrongTerm => f.write_str("proof verified against the wrong term"),
+        }
+    }
+}
+
+impl fmt::Display for SoakFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "seed {}: {} with a = {}, b = {}, n = {} at k = {}: {}",
+            self.seed, self.layout, self.a, self.b, self.n, self.k, self.mismatch
+        )
+    }
+}
+
+impl fmt::Display for SoakReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} cases from seed {} at k = {}: {} failed",
+            self.cases,
+            self.seed,
+            self.k,
+            self.failures.len()
+        )?;
+        for failure in &self.failures {
+            writeln!(f, "  {}", failure)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,223 @@
+//! Everything about a Fibonacci proving run that can be checked before
+//! synthesis, keygen or proving start.
+//!
+//! halo2 reports a bad input one error at a time, and late: a circuit too
+//! big for `k` as `NotEnoughRowsAvailable` from synthesis, a wrong number
+//! of public inputs as a failed proof, parameters of another size as a key
+//! that does not match. [`CircuitSpec::validate`] works out each from the
+//! spec alone, configuring the circuit but assigning nothing, and returns
+//! every problem at once.
+
+use std::{error, fmt};
+
+use halo2_proofs::{
+    pasta::Fp,
+    plonk::{Circuit, ConstraintSystem},
+};
+
+use crate::{
+    fibonacci::{single_column, three_column},
+    field::{self, ParseFieldError},
+    params::MAX_K,
+};
+
+/// Which `FiboTermsCircuit` the spec lays out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Layout {
+    /// `fibonacci::three_column`, one row per term after the first three.
+    ThreeColumn,
+    /// `fibonacci::single_column`, one row per term.
+    SingleColumn,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CircuitSpec {
+    pub layout: Layout,
+    /// The seeds as given, in decimal or `0x` hex.
+    pub a: String,
+    pub b: String,
+    /// Terms, seeds included.
+    pub n: usize,
+    pub k: u32,
+    /// The instance columns the prover will be given.
+    pub instances: Vec<Vec<Fp>>,
+    /// The `k` of the parameters to prove with, if already chosen.
+    pub params_k: Option<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpecProblem {
+    /// A seed is not a field element, as [`field::parse`] reads them.
+    InvalidSeed {
+        name: &'static str,
+        value: String,
+        reason: ParseFieldError,
+    },
+    /// The gate relates three terms, so there must be at least three.
+    TooFewTerms(usize),
+    InvalidK(u32),
+    /// The layout needs `rows` rows, more than `2^k` leaves usable;
+    /// `needed` is the smallest `k` that fits them, if any does.
+    TooManyRows {
+        rows: usize,
+        usable: usize,
+        needed: Option<u32>,
+    },
+    /// The circuit exposes one cell in one instance column.
+    InstanceShape {
+        columns: usize,
+        cells: usize,
+    },
+    /// The parameters are for another circuit size.
+    ParamsK {
+        params: u32,
+        k: u32,
+    },
+}
+
+/// The circuits have one instance column and expose the last term in it.
+const EXPOSED: [usize; 1] = [1];
+
+impl Layout {
+    pub const ALL: [Layout; 2] = [Layout::ThreeColumn, Layout::SingleColumn];
+
+    /// The layout's name in reports, its module name in kebab case.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Layout::ThreeColumn => "three-column",
+            Layout::SingleColumn => "single-column",
+        }
+    }
+
+    /// Rows the layout assigns for `n` terms.
+    pub fn rows(&self, n: usize) -> usize {
+        match self {
+            Layout::ThreeColumn => n.saturating_sub(2),
+            Layout::SingleColumn => n,
+        }
+    }
+
+    /// Rows of a `2^k`-row circuit left once the blinding rows are
+    /// reserved.
+    pub fn usable_rows(&self, k: u32) -> usize {
+        let mut cs = ConstraintSystem::<Fp>::default();
+        match self {
+            Layout::ThreeColumn => {
+                three_column::FiboTermsCircuit::<Fp>::configure(&mut cs);
+            }
+            Layout::SingleColumn => {
+                single_column::FiboTermsCircuit::<Fp>::configure(&mut cs);
+            }
+        }
+        (1usize << k).saturating_sub(cs.blinding_factors() + 1)
+    }
+}
+
+impl CircuitSpec {
+    /// The seeds as field elements, if both parse.
+    pub fn seeds(&self) -> Option<(Fp, Fp)> {
+        Some((field::parse(&self.a).ok()?, field::parse(&self.b).ok()?))
+    }
+
+    /// Every problem with the spec, in the order the fields are declared.
+    pub fn validate(&self) -> Result<(), Vec<SpecProblem>> {
+        let mut problems = vec![];
+        for (name, value) in [("a", &self.a), ("b", &self.b)] {
+            if let Err(reason) = field::parse::<Fp>(value) {
+                problems.push(SpecProblem::InvalidSeed {
+                    name,
+                    value: value.clone(),
+                    reason,
+                });
+            }
+        }
+
+        if self.n < 3 {
+            problems.push(SpecProblem::TooFewTerms(self.n));
+        }
+        if self.k > MAX_K {
+            problems.push(SpecProblem::InvalidK(self.k));
+        } else {
+            let rows = self.layout.rows(self.n);
+            let usable = self.layout.usable_rows(self.k);
+            if rows > usable {
+                let needed = (self.k + 1..=MAX_K).find(|&k| rows <= self.layout.usable_rows(k));
+                problems.push(SpecProblem::TooManyRows {
+                    rows,
+                    usable,
+                    needed,
+                });
+            }
+        }
+
+        let shape: Vec<usize> = self.instances.iter().map(Vec::len).collect();
+        if shape != EXPOSED {
+            problems.push(SpecProblem::InstanceShape {
+                columns: shape.len(),
+                cells: shape.iter().sum(),
+            });
+        }
+
+        if let Some(params) = self.params_k {
+            if params != self.k {
+                problems.push(SpecProblem::ParamsK { params, k: self.k });
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(problems)
+        }
+    }
+}
+
+impl fmt::Display for SpecProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpecProblem::InvalidSeed {
+                name,
+                value,
+                reason,
+            } => write!(
+                f,
+                "seed {} = {:?} is not a field element: {}",
+                name, value, reason
+            ),
+            SpecProblem::TooFewTerms(n) => {
+                write!(f, "n = {} is too short: the gate relates 3 terms", n)
+            }
+            SpecProblem::InvalidK(k) => write!(f, "k = {} is larger than {}", k, MAX_K),
+            SpecProblem::TooManyRows {
+                rows,
+                usable,
+                needed: Some(needed),
+            } => write!(
+                f,
+                "the layout needs {} rows and k leaves {} usable; try k = {}",
+                rows, usable, needed
+            ),
+            SpecProblem::TooManyRows {
+                rows, needed: None, ..
+            } => write!(
+                f,
+                "the layout needs {} rows, more than k = {} allows",
+                rows, MAX_K
+            ),
+            SpecProblem::InstanceShape { columns, cells } => write!(
+                f,
+                "the circuit exposes 1 cell in 1 instance column, got {} cells in {} columns",
+                cells, columns
+            ),
+            SpecProblem::ParamsK { params, k } => {
+                write!(
+                    f,
+                    "the parameters are for k = {}, the circuit is k = {}",
+                    params, k
+                )
+            }
+        }
+    }
+}
+
+impl error::Error for SpecProblem {}
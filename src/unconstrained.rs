@@ -0,0 +1,119 @@
+//! A heuristic search for advice cells that are witnessed but never
+//! constrained.
+//!
+//! A cell counts as constrained when a gate constraint or lookup input
+//! queries it from a row where that constraint is active, or when a copy
+//! constraint wires it to another cell, instance cells included. Activity
+//! follows [`explain`](crate::explain): a constraint is active where one of
+//! its selectors is enabled, or everywhere if it has none. A constraint
+//! can still reduce to nothing on a row, say through a zero fixed
+//! coefficient, so an empty report is not a proof of soundness; a cell
+//! it does list is one the prover can set freely.
+
+use std::{collections::HashSet, fmt};
+
+use serde::Serialize;
+
+use crate::introspect::{
+    format_field,
+    witness::{CellRef, Witness},
+    ColumnKind,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UnconstrainedReport {
+    pub circuit: String,
+    pub k: u32,
+    pub cells: Vec<UnconstrainedCell>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UnconstrainedCell {
+    /// The cell as `A0@3`.
+    pub cell: String,
+    pub region: usize,
+    pub region_name: String,
+    pub annotation: String,
+    /// `?` when the value is unknown.
+    pub value: String,
+}
+
+impl UnconstrainedReport {
+    pub fn of(name: &str, witness: &Witness) -> Self {
+        let constrained = constrained_cells(witness);
+        let mut cells = vec![];
+        for (index, region) in witness.regions.iter().enumerate() {
+            for assigned in &region.cells {
+                if assigned.cell.column.kind != ColumnKind::Advice
+                    || constrained.contains(&assigned.cell)
+                {
+                    continue;
+                }
+                cells.push(UnconstrainedCell {
+                    cell: format!("{}@{}", assigned.cell.column, assigned.cell.row),
+                    region: index,
+                    region_name: region.name.clone(),
+                    annotation: assigned.annotation.clone(),
+                    value: assigned.value.map_or("?".to_string(), format_field),
+                });
+            }
+        }
+
+        UnconstrainedReport {
+            circuit: name.to_string(),
+            k: witness.k,
+            cells,
+        }
+    }
+
+    pub fn passed(&self) -> bool {
+        self.cells.is_empty()
+    }
+}
+
+/// Every cell some active constraint or copy touches.
+fn constrained_cells(witness: &Witness) -> HashSet<CellRef> {
+    let mut cells: HashSet<CellRef> = witness
+        .copies
+        .iter()
+        .flat_map(|(left, right)| [*left, *right])
+        .collect();
+    let polys = witness
+        .cs
+        .gates
+        .iter()
+        .flat_map(|gate| gate.constraints.iter().map(|constraint| &constraint.poly))
+        .chain(witness.cs.lookups.iter().flat_map(|lookup| &lookup.inputs));
+    for poly in polys {
+        let selectors = poly.selectors();
+        let queries = poly.queries();
+        for row in 0..witness.rows() {
+            if selectors.is_empty() || selectors.iter().any(|s| witness.selectors[*s][row]) {
+                for query in &queries {
+                    cells.insert(CellRef {
+                        column: query.column,
+                        row: witness.rotate(row, query.rotation),
+                    });
+                }
+            }
+        }
+    }
+    cells
+}
+
+impl fmt::Display for UnconstrainedReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "circuit: {} (k = {})", self.circuit, self.k)?;
+        for cell in &self.cells {
+            writeln!(
+                f,
+                "{} {} = {} in region {} `{}`",
+                cell.cell, cell.annotation, cell.value, cell.region, cell.region_name
+            )?;
+        }
+        match self.cells.len() {
+            0 => write!(f, "every assigned advice cell is constrained"),
+            n => write!(f, "{} unconstrained advice cells", n),
+        }
+    }
+}
@@ -0,0 +1,257 @@
+//! The outcome of verifying an envelope or bundle as data, so tools can
+//! tell a rejected proof from a failure to check it at all.
+//!
+//! [`verify`] runs the checks in order and stops at the first that fails:
+//! for a bundle, that the parameters are the ones it was made with, then
+//! that the envelope claims the `n` its circuit proves, that the bundled
+//! verifying key and the envelope's fingerprint are the circuit's at `k`,
+//! and last the verifier itself. A failed check makes the proof
+//! [`Status::Invalid`]; [`Verification::error`] records the
+//! [`Status::Error`] of an input that could not be read or checked.
+
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
+
+use halo2_proofs::{
+    pasta::{EqAffine, Fp},
+    plonk::{Circuit, VerifyingKey},
+    poly::commitment::Params,
+};
+use serde::Serialize;
+
+use crate::{
+    bundle::ProofBundle,
+    envelope::ProofEnvelope,
+    fingerprint::{self, fingerprint},
+    inspect::{self, Check},
+    params, prover,
+    registry::{CircuitKind, CircuitVisitor},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    Valid,
+    /// A check failed, the verifier's included.
+    Invalid,
+    /// The proof could not be checked.
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Verification {
+    pub status: Status,
+    pub valid: bool,
+    pub circuit: Option<String>,
+    pub k: Option<u32>,
+    pub n: Option<u32>,
+    /// The envelope's fingerprint, hex encoded.
+    pub fingerprint: Option<String>,
+    /// The detail of the failed check, or the error.
+    pub reason: Option<String>,
+    pub checks: Vec<Check>,
+    pub timings: Timings,
+}
+
+/// Wall-clock milliseconds.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct Timings {
+    pub keygen_ms: f64,
+    pub verify_ms: f64,
+}
+
+impl Verification {
+    /// The verification that could not run because of `reason`.
+    pub fn error(reason: impl fmt::Display) -> Self {
+        Verification {
+            status: Status::Error,
+            valid: false,
+            circuit: None,
+            k: None,
+            n: None,
+            fingerprint: None,
+            reason: Some(reason.to_string()),
+            checks: vec![],
+            timings: Timings::default(),
+        }
+    }
+
+    /// The verification of `envelope` before any check has run.
+    pub(crate) fn of(envelope: &ProofEnvelope) -> Self {
+        Verification {
+            status: Status::Valid,
+            valid: true,
+            circuit: Some(envelope.circuit.clone()),
+            k: Some(envelope.k),
+            n: Some(envelope.n),
+            fingerprint: Some(fingerprint::to_hex(&envelope.fingerprint)),
+            reason: None,
+            checks: vec![],
+            timings: Timings::default(),
+        }
+    }
+
+    /// Records `check`, and the proof as invalid if it failed.
+    pub(crate) fn check(&mut self, check: Check) {
+        if !check.passed {
+            self.status = Status::Invalid;
+            self.valid = false;
+            self.reason = Some(format!("{}: {}", check.name, check.detail));
+        }
+        self.checks.push(check);
+    }
+}
+
+/// Verifies `envelope` against `params`, and against the key and parameters
+/// recorded in `bundle` if it came in one.
+pub fn verify(
+    envelope: &ProofEnvelope,
+    bundle: Option<&ProofBundle>,
+    params: &Params<EqAffine>,
+) -> Verification {
+    let kind: CircuitKind = match envelope.circuit.parse() {
+        Ok(kind) => kind,
+        Err(e) => return Verification::error(e),
+    };
+    let mut verification = Verification::of(envelope);
+
+    if let Some(bundle) = bundle {
+        let digest = params::digest(&params::to_bytes(params));
+        let matches = bundle.params.k == envelope.k && bundle.params.digest == digest;
+        verification.check(Check::new(
+            "parameters",
+            matches,
+            if matches {
+                format!("digest {}", digest)
+            } else {
+                format!(
+                    "bundle was made with k = {} digest {}, these are digest {}",
+                    bundle.params.k, bundle.params.digest, digest
+                )
+            },
+        ));
+    }
+    if verification.valid {
+        verification.check(inspect::statement(kind, envelope));
+    }
+    if verification.valid {
+        kind.visit(Checks {
+            envelope,
+            vk: bundle.map(|bundle| bundle.vk.as_str()),
+            params,
+            verification: &mut verification,
+        });
+    }
+    verification
+}
+
+struct Checks<'a> {
+    envelope: &'a ProofEnvelope,
+    /// The pinned verifying key shipped with the proof, if any.
+    vk: Option<&'a str>,
+    params: &'a Params<EqAffine>,
+    verification: &'a mut Verification,
+}
+
+impl CircuitVisitor for Checks<'_> {
+    type Output = ();
+
+    fn visit<C: Circuit<Fp> + fmt::Debug>(self, circuit: C, _: Vec<Vec<Fp>>) {
+        let Checks {
+            envelope,
+            vk: pinned,
+            params,
+            verification,
+        } = self;
+
+        let start = Instant::now();
+        let vk = prover::keygen_vk(params, &circuit);
+        verification.timings.keygen_ms = millis(start.elapsed());
+        let vk = match vk {
+            Ok(vk) => vk,
+            Err(e) => {
+                verification.status = Status::Error;
+                verification.valid = false;
+                verification.reason = Some(format!("keygen failed: {:?}", e));
+                return;
+            }
+        };
+
+        check_key(verification, envelope, pinned, params, &vk);
+    }
+}
+
+/// The checks that need the circuit's verifying key `vk`: that a bundled
+/// key is it, that the envelope's fingerprint is its, and the verifier.
+pub(crate) fn check_key(
+    verification: &mut Verification,
+    envelope: &ProofEnvelope,
+    pinned: Option<&str>,
+    params: &Params<EqAffine>,
+    vk: &VerifyingKey<EqAffine>,
+) {
+    if let Some(pinned) = pinned {
+        verification.check(Check::new(
+            "bundled key",
+            pinned == fingerprint::pinned(vk),
+            if pinned == fingerprint::pinned(vk) {
+                "the circuit's".to_string()
+            } else {
+                "the bundled key is not the circuit's".to_string()
+            },
+        ));
+        if !verification.valid {
+            return;
+        }
+    }
+
+    let expected = fingerprint(vk);
+    verification.check(Check::new(
+        "fingerprint",
+        expected == envelope.fingerprint,
+        if expected == envelope.fingerprint {
+            fingerprint::to_hex(&expected)
+        } else {
+            format!(
+                "envelope has {}, circuit has {}",
+                fingerprint::to_hex(&envelope.fingerprint),
+                fingerprint::to_hex(&expected)
+            )
+        },
+    ));
+    if !verification.valid {
+        return;
+    }
+
+    let start = Instant::now();
+    let result = prover::verify_proof(params, vk, &envelope.proof, &envelope.instances);
+    verification.timings.verify_ms = millis(start.elapsed());
+    verification.check(Check::new(
+        "proof",
+        result.is_ok(),
+        match result {
+            Ok(()) => "accepted by the verifier".to_string(),
+            Err(e) => format!("rejected by the verifier: {:?}", e),
+        },
+    ));
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+impl fmt::Display for Verification {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for check in &self.checks {
+            let status = if check.passed { "ok" } else { "FAILED" };
+            writeln!(f, "- {:<16} {:<6} {}", check.name, status, check.detail)?;
+        }
+        match self.status {
+            Status::Valid => write!(f, "proof is valid"),
+            Status::Invalid => write!(f, "proof is invalid"),
+            Status::Error => write!(f, "could not verify"),
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use fibonacci_circuit::accel::{backends, select, Backend, Cpu, UnknownBackend};
+use halo2_proofs::{
+    arithmetic::eval_polynomial,
+    pasta::{
+        group::{Curve, Group},
+        Eq, EqAffine, Fp,
+    },
+    poly::{commitment::Params, EvaluationDomain},
+};
+
+const K: u32 = 4;
+
+#[test]
+fn msm_matches_the_naive_sum() {
+    let bases = Params::<EqAffine>::new(K).get_g();
+    let coeffs: Vec<Fp> = (0..1 << K).map(|i| Fp::from(i * i + 3)).collect();
+    let naive = coeffs
+        .iter()
+        .zip(&bases)
+        .fold(Eq::identity(), |acc, (c, b)| acc + b * c);
+    assert_eq!(Cpu.msm(&coeffs, &bases).to_affine(), naive.to_affine());
+}
+
+#[test]
+fn fft_evaluates_over_the_roots_of_unity() {
+    let coeffs: Vec<Fp> = (0..1 << K).map(|i| Fp::from(2 * i + 1)).collect();
+    let omega = EvaluationDomain::<Fp>::new(1, K).get_omega();
+    let mut values = coeffs.clone();
+    Cpu.fft(&mut values, omega, K);
+
+    let mut point = Fp::one();
+    for value in values {
+        assert_eq!(value, eval_polynomial(&coeffs, point));
+        point *= omega;
+    }
+}
+
+#[test]
+fn selects_backends_by_name() {
+    assert_eq!(backends()[0].name(), "cpu");
+    assert_eq!(select("cpu").unwrap().name(), "cpu");
+}
+
+#[test]
+fn unknown_backend_is_an_error() {
+    let err = select("icicle").err().unwrap();
+    assert_eq!(err, UnknownBackend("icicle".to_string()));
+    assert_eq!(
+        err.to_string(),
+        "unknown backend `icicle`, expected one of: cpu"
+    );
+}
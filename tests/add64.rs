@@ -0,0 +1,272 @@
+use fibonacci_circuit::gadgets::{
+    add64::{Add64Chip, Add64Config, Overflow},
+    u64::U64Chip,
+};
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    dev::MockProver,
+    pasta::Fp,
+    plonk::{Advice, Circuit, Column, ConstraintSystem, Error, Instance},
+};
+
+#[derive(Debug, Clone)]
+struct Config {
+    input: Column<Advice>,
+    instance: Column<Instance>,
+    add: Add64Config,
+}
+
+fn configure(meta: &mut ConstraintSystem<Fp>) -> Config {
+    let input = meta.advice_column();
+    meta.enable_equality(input);
+    let instance = meta.instance_column();
+    meta.enable_equality(instance);
+    let advice = [(); 4].map(|_| meta.advice_column());
+    let range = meta.advice_column();
+    Config {
+        input,
+        instance,
+        add: Add64Chip::configure(meta, advice, range),
+    }
+}
+
+/// Adds `a` and `b`, exposing the sum and carry at instance rows 0 and 1.
+#[derive(Debug)]
+struct Add64Circuit {
+    a: u64,
+    b: u64,
+    mode: Overflow,
+}
+
+impl Circuit<Fp> for Add64Circuit {
+    type Config = Config;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            a: 0,
+            b: 0,
+            mode: self.mode,
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>) -> Config {
+        configure(meta)
+    }
+
+    fn synthesize(&self, config: Config, mut layouter: impl Layouter<Fp>) -> Result<(), Error> {
+        let (a, b) = layouter.assign_region(
+            || "inputs",
+            |mut region| {
+                let a = region.assign_advice(
+                    || "a",
+                    config.input,
+                    0,
+                    || Value::known(Fp::from(self.a)),
+                )?;
+                let b = region.assign_advice(
+                    || "b",
+                    config.input,
+                    1,
+                    || Value::known(Fp::from(self.b)),
+                )?;
+                Ok((a, b))
+            },
+        )?;
+        let range = U64Chip::construct(config.add.range.clone());
+        let a = range.range_check(layouter.namespace(|| "a range"), &a)?;
+        let b = range.range_check(layouter.namespace(|| "b range"), &b)?;
+
+        let chip = Add64Chip::construct(config.add);
+        let sum = chip.add(layouter.namespace(|| "add"), &a, &b, self.mode)?;
+        layouter.constrain_instance(sum.sum.cell().cell(), config.instance, 0)?;
+        layouter.constrain_instance(sum.carry.cell().cell(), config.instance, 1)
+    }
+}
+
+/// Lays out the addition row with an arbitrary sum and carry,
+/// range-checking the operands and sum like the chip does.
+#[derive(Debug, Default)]
+struct ForgedCircuit {
+    a: Fp,
+    b: Fp,
+    c: Fp,
+    carry: Fp,
+    checked: bool,
+}
+
+impl Circuit<Fp> for ForgedCircuit {
+    type Config = Config;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            checked: self.checked,
+            ..Self::default()
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>) -> Config {
+        configure(meta)
+    }
+
+    fn synthesize(&self, config: Config, mut layouter: impl Layouter<Fp>) -> Result<(), Error> {
+        let cfg = config.add;
+        let cells = layouter.assign_region(
+            || "forged add64",
+            |mut region| {
+                cfg.selector.enable(&mut region, 0)?;
+                if self.checked {
+                    cfg.s_checked.enable(&mut region, 0)?;
+                }
+                cfg.advice
+                    .iter()
+                    .zip([self.a, self.b, self.c, self.carry])
+                    .map(|(column, value)| {
+                        region.assign_advice(|| "forged", *column, 0, || Value::known(value))
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            },
+        )?;
+        let range = U64Chip::construct(cfg.range);
+        for cell in &cells[..3] {
+            range.range_check(layouter.namespace(|| "range"), cell)?;
+        }
+        Ok(())
+    }
+}
+
+const K: u32 = 9;
+
+const EDGES: [u64; 7] = [0, 1, 2, 1 << 32, (1 << 32) - 1, 1 << 63, u64::MAX];
+
+fn run(circuit: &impl Circuit<Fp>, sum: u64, carry: bool) -> MockProver<Fp> {
+    let instances = vec![vec![Fp::from(sum), Fp::from(carry as u64)]];
+    MockProver::run(K, circuit, instances).unwrap()
+}
+
+#[test]
+fn wraps_at_the_boundary() {
+    let cases = [
+        (u64::MAX, 0, u64::MAX, false),
+        (u64::MAX, 1, 0, true),
+        (u64::MAX, u64::MAX, u64::MAX - 1, true),
+        (1 << 63, 1 << 63, 0, true),
+    ];
+    for (a, b, sum, carry) in cases {
+        let circuit = Add64Circuit {
+            a,
+            b,
+            mode: Overflow::Wrapping,
+        };
+        assert_eq!(run(&circuit, sum, carry).verify(), Ok(()), "{} + {}", a, b);
+    }
+}
+
+#[test]
+fn carry_out_matches_overflowing_add() {
+    for a in EDGES {
+        for b in EDGES {
+            let (sum, carry) = a.overflowing_add(b);
+            let circuit = Add64Circuit {
+                a,
+                b,
+                mode: Overflow::Wrapping,
+            };
+            assert_eq!(run(&circuit, sum, carry).verify(), Ok(()), "{} + {}", a, b);
+            assert!(
+                run(&circuit, sum, !carry).verify().is_err(),
+                "{} + {} with the carry flipped",
+                a,
+                b
+            );
+        }
+    }
+}
+
+#[test]
+fn forged_sums_and_carries_are_rejected() {
+    let two_pow_64 = Fp::from_u128(1 << 64);
+    let (a, b) = (Fp::from(u64::MAX), Fp::from(u64::MAX));
+    // (2^64 - 1) + (2^64 - 1) = 2^64 + (2^64 - 2)
+    let (c, carry) = (Fp::from(u64::MAX - 1), Fp::one());
+
+    let honest = ForgedCircuit {
+        a,
+        b,
+        c,
+        carry,
+        checked: false,
+    };
+    assert_eq!(
+        MockProver::run(K, &honest, vec![vec![]]).unwrap().verify(),
+        Ok(())
+    );
+
+    let forgeries = [
+        // The carry dropped and the sum left unreduced.
+        ForgedCircuit {
+            c: c + two_pow_64,
+            carry: Fp::zero(),
+            ..honest
+        },
+        // A carry of 2 with the sum wrapping below zero.
+        ForgedCircuit {
+            c: c - two_pow_64,
+            carry: Fp::from(2),
+            ..honest
+        },
+        // A sum that does not add up.
+        ForgedCircuit {
+            c: c + Fp::one(),
+            ..honest
+        },
+        // A carry where there is none.
+        ForgedCircuit {
+            a: Fp::one(),
+            b: Fp::one(),
+            c: Fp::from(2),
+            carry: Fp::one(),
+            checked: false,
+        },
+    ];
+    for circuit in forgeries {
+        let prover = MockProver::run(K, &circuit, vec![vec![]]).unwrap();
+        assert!(prover.verify().is_err(), "accepted {:?}", circuit);
+    }
+}
+
+#[test]
+fn checked_mode_rejects_overflow() {
+    let fits = Add64Circuit {
+        a: u64::MAX - 1,
+        b: 1,
+        mode: Overflow::Checked,
+    };
+    assert_eq!(run(&fits, u64::MAX, false).verify(), Ok(()));
+
+    let overflows = Add64Circuit {
+        a: u64::MAX,
+        b: 1,
+        mode: Overflow::Checked,
+    };
+    assert!(run(&overflows, 0, true).verify().is_err());
+
+    // The same carry is fine when wrapping.
+    let wraps = Add64Circuit {
+        mode: Overflow::Wrapping,
+        ..overflows
+    };
+    assert_eq!(run(&wraps, 0, true).verify(), Ok(()));
+
+    let forged = ForgedCircuit {
+        a: Fp::from(u64::MAX),
+        b: Fp::one(),
+        c: Fp::zero(),
+        carry: Fp::one(),
+        checked: true,
+    };
+    let prover = MockProver::run(K, &forged, vec![vec![]]).unwrap();
+    assert!(prover.verify().is_err());
+}
@@ -0,0 +1,88 @@
+use fibonacci_circuit::{
+    aggregation::{flatten_instances, verify_aggregate, Proof},
+    fibonacci::{three_column::FiboCircuit, NUM_TERMS},
+    prover,
+    reference::fib_field,
+};
+use halo2_proofs::{
+    circuit::Value,
+    pasta::{EqAffine, Fp},
+    plonk::ProvingKey,
+    poly::commitment::Params,
+};
+
+const K: u32 = 4;
+
+const SEEDS: [(u64, u64); 3] = [(1, 1), (2, 3), (5, 0)];
+
+fn proofs(params: &Params<EqAffine>) -> (ProvingKey<EqAffine>, Vec<Proof>) {
+    let circuit = |(a, b): (u64, u64)| FiboCircuit {
+        a: Value::known(Fp::from(a)),
+        b: Value::known(Fp::from(b)),
+    };
+    let pk = prover::keygen(params, &circuit(SEEDS[0])).unwrap();
+    let proofs = SEEDS
+        .iter()
+        .map(|&seeds| {
+            let instances = vec![vec![fib_field(
+                Fp::from(seeds.0),
+                Fp::from(seeds.1),
+                NUM_TERMS,
+            )]];
+            Proof {
+                bytes: prover::create_proof(params, &pk, circuit(seeds), &instances).unwrap(),
+                instances,
+            }
+        })
+        .collect();
+    (pk, proofs)
+}
+
+#[test]
+fn batch_of_valid_proofs_verifies() {
+    let params = Params::new(K);
+    let (pk, proofs) = proofs(&params);
+    assert!(verify_aggregate(&params, pk.get_vk(), &proofs));
+    assert!(verify_aggregate(&params, pk.get_vk(), &proofs[..1]));
+}
+
+#[test]
+fn one_bad_proof_fails_the_batch() {
+    let params = Params::new(K);
+    let (pk, mut proofs) = proofs(&params);
+
+    let mut wrong_input = proofs.clone();
+    wrong_input[1].instances[0][0] += Fp::one();
+    assert!(!verify_aggregate(&params, pk.get_vk(), &wrong_input));
+
+    // Swapped instances: each proof is valid, but not for the other's output.
+    let (first, second) = (proofs[0].instances.clone(), proofs[1].instances.clone());
+    proofs[0].instances = second;
+    proofs[1].instances = first;
+    assert!(!verify_aggregate(&params, pk.get_vk(), &proofs));
+}
+
+#[test]
+fn flattened_instances_keep_the_layout() {
+    let params = Params::new(K);
+    let (_, proofs) = proofs(&params);
+    let flat = flatten_instances(&proofs);
+
+    assert_eq!(flat.len(), 1 + 3 * 2);
+    assert_eq!(flat[0], Fp::from(3));
+    assert_eq!(&flat[1..3], &[Fp::one(), Fp::from(55)]);
+    assert_eq!(flat[4], fib_field(Fp::from(2), Fp::from(3), NUM_TERMS));
+
+    // Moving a value between columns changes the flattening.
+    let split = |columns: Vec<Vec<u64>>| Proof {
+        bytes: vec![],
+        instances: columns
+            .into_iter()
+            .map(|column| column.into_iter().map(Fp::from).collect())
+            .collect(),
+    };
+    assert_ne!(
+        flatten_instances(&[split(vec![vec![1, 2], vec![3]])]),
+        flatten_instances(&[split(vec![vec![1], vec![2, 3]])])
+    );
+}
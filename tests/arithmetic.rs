@@ -0,0 +1,147 @@
+use fibonacci_circuit::gadgets::{
+    arithmetic::{ArithmeticChip, ArithmeticConfig, ArithmeticInstructions, MulChip},
+    nonzero::NonZeroChip,
+    simple::Operand,
+};
+use halo2_proofs::{
+    arithmetic::Field,
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    dev::MockProver,
+    pasta::Fp,
+    plonk::{Advice, Circuit, Column, ConstraintSystem, Error, Instance},
+};
+
+const K: u32 = 4;
+
+#[derive(Debug, Clone)]
+struct Config {
+    input: Column<Advice>,
+    instance: Column<Instance>,
+    arithmetic: ArithmeticConfig,
+}
+
+fn configure(meta: &mut ConstraintSystem<Fp>) -> Config {
+    let input = meta.advice_column();
+    meta.enable_equality(input);
+    let instance = meta.instance_column();
+    meta.enable_equality(instance);
+    let advice = [(); 3].map(|_| meta.advice_column());
+    Config {
+        input,
+        instance,
+        arithmetic: ArithmeticChip::configure(meta, advice),
+    }
+}
+
+/// Divides `a` by `b` through [`ArithmeticChip::div`], exposing the
+/// quotient at instance row 0, or, with `forged` set, lays out the same
+/// rows with that quotient and divisor inverse instead of the honest ones.
+#[derive(Debug, Default)]
+struct DivCircuit {
+    a: Fp,
+    b: Fp,
+    forged: Option<(Fp, Fp)>,
+}
+
+impl Circuit<Fp> for DivCircuit {
+    type Config = Config;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>) -> Config {
+        configure(meta)
+    }
+
+    fn synthesize(&self, config: Config, mut layouter: impl Layouter<Fp>) -> Result<(), Error> {
+        let (a, b) = layouter.assign_region(
+            || "inputs",
+            |mut region| {
+                let a = region.assign_advice(|| "a", config.input, 0, || Value::known(self.a))?;
+                let b = region.assign_advice(|| "b", config.input, 1, || Value::known(self.b))?;
+                Ok((a, b))
+            },
+        )?;
+        let q = match self.forged {
+            None => ArithmeticChip::construct(config.arithmetic).div(
+                layouter.namespace(|| "div"),
+                &a,
+                &b,
+            )?,
+            Some((q, b_inv)) => {
+                let nonzero = NonZeroChip::construct(config.arithmetic.nonzero);
+                nonzero.assign_row(
+                    layouter.namespace(|| "forged inverse"),
+                    [Operand::Cell(&b), Operand::Witness(Value::known(b_inv))],
+                )?;
+                let [q, _, _] = MulChip::construct(config.arithmetic.mul).assign_row(
+                    layouter.namespace(|| "forged div"),
+                    [
+                        Operand::Witness(Value::known(q)),
+                        Operand::Cell(&b),
+                        Operand::Cell(&a),
+                    ],
+                )?;
+                q
+            }
+        };
+        layouter.constrain_instance(q.cell(), config.instance, 0)
+    }
+}
+
+fn run(circuit: &DivCircuit, q: Fp) -> MockProver<Fp> {
+    MockProver::run(K, circuit, vec![vec![q]]).unwrap()
+}
+
+#[test]
+fn correct_quotient_is_accepted() {
+    for (a, b) in [(91, 7), (0, 5), (1, 3), (7, 1)] {
+        let (a, b) = (Fp::from(a), Fp::from(b));
+        let q = a * b.invert().unwrap();
+        let circuit = DivCircuit { a, b, forged: None };
+        assert_eq!(run(&circuit, q).verify(), Ok(()), "{:?} / {:?}", a, b);
+        assert!(run(&circuit, q + Fp::one()).verify().is_err());
+    }
+}
+
+#[test]
+fn forged_quotient_is_rejected() {
+    let (a, b) = (Fp::from(91), Fp::from(7));
+    let b_inv = b.invert().unwrap();
+    let honest = DivCircuit {
+        a,
+        b,
+        forged: Some((Fp::from(13), b_inv)),
+    };
+    assert_eq!(run(&honest, Fp::from(13)).verify(), Ok(()));
+
+    // q·b ≠ a, with the instance agreeing with the forged q.
+    let forged = DivCircuit {
+        forged: Some((Fp::from(14), b_inv)),
+        ..honest
+    };
+    assert!(run(&forged, Fp::from(14)).verify().is_err());
+}
+
+#[test]
+fn division_by_zero_is_unsatisfiable() {
+    let zero = Fp::zero();
+    let circuit = DivCircuit {
+        a: zero,
+        b: zero,
+        forged: None,
+    };
+    assert!(run(&circuit, zero).verify().is_err());
+
+    // 0 · b = 0 holds for any q; only the inverse of b stops it.
+    for (q, b_inv) in [(zero, zero), (Fp::from(5), zero), (Fp::from(5), Fp::one())] {
+        let circuit = DivCircuit {
+            a: zero,
+            b: zero,
+            forged: Some((q, b_inv)),
+        };
+        assert!(run(&circuit, q).verify().is_err(), "q = {:?}", q);
+    }
+}
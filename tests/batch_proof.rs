@@ -0,0 +1,65 @@
+use fibonacci_circuit::{
+    fibonacci::{nth_term, three_column::FiboCircuit, NUM_TERMS},
+    prover,
+    reference::fib_field,
+};
+use halo2_proofs::{circuit::Value, pasta::Fp, plonk::Error, poly::commitment::Params};
+
+const K: u32 = 4;
+
+/// Seeds of the statements proven together.
+const SEEDS: [(u64, u64); 3] = [(1, 1), (2, 3), (5, 0)];
+
+fn circuits() -> Vec<FiboCircuit<Fp>> {
+    SEEDS
+        .iter()
+        .map(|&(a, b)| FiboCircuit {
+            a: Value::known(Fp::from(a)),
+            b: Value::known(Fp::from(b)),
+        })
+        .collect()
+}
+
+fn instances() -> Vec<Vec<Vec<Fp>>> {
+    SEEDS
+        .iter()
+        .map(|&(a, b)| vec![vec![nth_term(Fp::from(a), Fp::from(b), NUM_TERMS)]])
+        .collect()
+}
+
+#[test]
+fn batch_proof_verifies() {
+    let params = Params::new(K);
+    let circuits = circuits();
+    let pk = prover::keygen(&params, &circuits[0]).unwrap();
+    let proof = prover::create_batch_proof(&params, &pk, circuits, &instances()).unwrap();
+
+    assert_eq!(
+        instances()[0],
+        vec![vec![fib_field(Fp::one(), Fp::one(), NUM_TERMS)]]
+    );
+    prover::verify_batch_proof(&params, pk.get_vk(), &proof, &instances()).unwrap();
+}
+
+#[test]
+fn batch_proof_binds_instance_order() {
+    let params = Params::new(K);
+    let circuits = circuits();
+    let pk = prover::keygen(&params, &circuits[0]).unwrap();
+    let proof = prover::create_batch_proof(&params, &pk, circuits, &instances()).unwrap();
+
+    let mut swapped = instances();
+    swapped.swap(0, 1);
+    assert!(prover::verify_batch_proof(&params, pk.get_vk(), &proof, &swapped).is_err());
+    assert!(prover::verify_batch_proof(&params, pk.get_vk(), &proof, &instances()[..2]).is_err());
+}
+
+#[test]
+fn batch_proof_rejects_mismatched_instances() {
+    let params = Params::new(K);
+    let circuits = circuits();
+    let pk = prover::keygen(&params, &circuits[0]).unwrap();
+
+    let result = prover::create_batch_proof(&params, &pk, circuits, &instances()[..2]);
+    assert!(matches!(result, Err(Error::InvalidInstances)));
+}
@@ -0,0 +1,193 @@
+use fibonacci_circuit::{
+    fibonacci::bigint::{nth_term, public_inputs, FiboBigIntCircuit},
+    gadgets::{
+        bigint::{BigIntChip, BigIntConfig},
+        bytes::{ByteTable, BytesChip},
+    },
+};
+use halo2_proofs::{
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    dev::MockProver,
+    pasta::Fp,
+    plonk::{Circuit, Column, ConstraintSystem, Error, Instance},
+};
+use num_bigint::BigUint;
+
+const LIMBS: usize = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Add,
+    Mul,
+    Less,
+    Mod,
+}
+
+#[derive(Debug, Clone)]
+struct Config {
+    bigint: BigIntConfig,
+    instance: Column<Instance>,
+}
+
+/// Loads `a` and `b`, applies `op` and exposes the result: its limbs, or
+/// the comparison bit.
+#[derive(Debug, Clone)]
+struct OpCircuit {
+    a: Value<BigUint>,
+    b: Value<BigUint>,
+    op: Op,
+}
+
+impl Circuit<Fp> for OpCircuit {
+    type Config = Config;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        OpCircuit {
+            a: Value::unknown(),
+            b: Value::unknown(),
+            op: self.op,
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>) -> Config {
+        let table = ByteTable::configure(meta);
+        let (z, byte) = (meta.advice_column(), meta.advice_column());
+        let bytes = BytesChip::configure(meta, z, byte, table);
+        let instance = meta.instance_column();
+        meta.enable_equality(instance);
+        Config {
+            bigint: BigIntChip::configure(meta, LIMBS, bytes),
+            instance,
+        }
+    }
+
+    fn synthesize(&self, config: Config, mut layouter: impl Layouter<Fp>) -> Result<(), Error> {
+        config
+            .bigint
+            .bytes
+            .table
+            .load(layouter.namespace(|| "bytes"))?;
+        let chip = BigIntChip::construct(config.bigint);
+        let a = chip.load(layouter.namespace(|| "a"), self.a.clone())?;
+        let b = chip.load(layouter.namespace(|| "b"), self.b.clone())?;
+        let c = match self.op {
+            Op::Add => chip.add(layouter.namespace(|| "add"), &a, &b)?,
+            Op::Mul => chip.mul(layouter.namespace(|| "mul"), &a, &b)?,
+            Op::Mod => chip.mod_reduce(layouter.namespace(|| "mod"), &a, &b)?,
+            Op::Less => {
+                let bit = chip.is_less_than(layouter.namespace(|| "less"), &a, &b)?;
+                return layouter.constrain_instance(bit.cell().cell(), config.instance, 0);
+            }
+        };
+        for (row, limb) in c.limbs.iter().enumerate() {
+            layouter.constrain_instance(limb.cell(), config.instance, row)?;
+        }
+        Ok(())
+    }
+}
+
+const K: u32 = 9;
+
+fn big(x: u128) -> BigUint {
+    BigUint::from(x)
+}
+
+fn run(a: &BigUint, b: &BigUint, op: Op, instance: Vec<Fp>) -> Result<(), String> {
+    let circuit = OpCircuit {
+        a: Value::known(a.clone()),
+        b: Value::known(b.clone()),
+        op,
+    };
+    MockProver::run(K, &circuit, vec![instance])
+        .map_err(|e| format!("{:?}", e))?
+        .verify()
+        .map_err(|e| format!("{} failures", e.len()))
+}
+
+fn run_op(a: u128, b: u128, op: Op, claimed: &BigUint) -> Result<(), String> {
+    run(&big(a), &big(b), op, public_inputs(claimed, LIMBS))
+}
+
+#[test]
+fn operations() {
+    let cases = [
+        (0, 0),
+        (1, u64::MAX as u128),
+        (u64::MAX as u128, u64::MAX as u128),
+        (1 << 100, (1 << 100) + 7),
+        (u128::MAX / 3, 3),
+        (12345678901234567890123, 98765432109876543),
+    ];
+    for (a, b) in cases {
+        if let Some(sum) = a.checked_add(b) {
+            assert_eq!(run_op(a, b, Op::Add, &big(sum)), Ok(()), "{} + {}", a, b);
+        }
+        if let Some(product) = a.checked_mul(b) {
+            assert_eq!(
+                run_op(a, b, Op::Mul, &big(product)),
+                Ok(()),
+                "{} * {}",
+                a,
+                b
+            );
+        }
+        if b != 0 {
+            assert_eq!(run_op(a, b, Op::Mod, &big(a % b)), Ok(()), "{} % {}", a, b);
+        }
+        let less = Fp::from((a < b) as u64);
+        assert_eq!(run(&big(a), &big(b), Op::Less, vec![less]), Ok(()));
+        let less = Fp::from((b < a) as u64);
+        assert_eq!(run(&big(b), &big(a), Op::Less, vec![less]), Ok(()));
+    }
+}
+
+#[test]
+fn overflow_is_rejected() {
+    let max = u128::MAX;
+    let wrapped = big(max.wrapping_add(1));
+    assert!(run_op(max, 1, Op::Add, &wrapped).is_err());
+    let wrapped = big((1u128 << 64).wrapping_mul(1 << 64));
+    assert!(run_op(1 << 64, 1 << 64, Op::Mul, &wrapped).is_err());
+}
+
+#[test]
+fn wrong_results_are_rejected() {
+    assert!(run_op(5, 7, Op::Add, &big(13)).is_err());
+    assert!(run_op(5, 7, Op::Mul, &big(36)).is_err());
+    assert!(run_op(17, 5, Op::Mod, &big(7)).is_err());
+    assert!(run(&big(5), &big(7), Op::Less, vec![Fp::zero()]).is_err());
+    assert!(run(&big(7), &big(7), Op::Less, vec![Fp::one()]).is_err());
+}
+
+#[test]
+fn zero_modulus_is_rejected() {
+    assert!(run_op(17, 0, Op::Mod, &big(17)).is_err());
+}
+
+#[test]
+fn oversized_inputs_fail_synthesis() {
+    let too_big = BigUint::from(1u8) << 128;
+    assert!(run(&too_big, &big(1), Op::Add, public_inputs(&too_big, LIMBS)).is_err());
+}
+
+#[test]
+fn fibonacci_past_the_modulus() {
+    // F(370) is about 2^255, above the pasta modulus, so field-valued terms
+    // would have wrapped.
+    const N: usize = 370;
+    let one = BigUint::from(1u8);
+    let term = nth_term(&one, &one, N);
+    assert!(term.bits() > 254);
+
+    let circuit = FiboBigIntCircuit::<4, N> {
+        a: Value::known(one.clone()),
+        b: Value::known(one),
+    };
+    let prover = MockProver::run(15, &circuit, vec![public_inputs::<Fp>(&term, 4)]).unwrap();
+    assert_eq!(prover.verify(), Ok(()));
+
+    let wrong = public_inputs::<Fp>(&(term - 1u8), 4);
+    let prover = MockProver::run(15, &circuit, vec![wrong]).unwrap();
+    assert!(prover.verify().is_err());
+}
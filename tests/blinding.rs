@@ -0,0 +1,50 @@
+use fibonacci_circuit::{
+    cost::CostReport,
+    fibonacci::{three_column::FiboCircuit, NUM_TERMS},
+    prover::{self, Blinding},
+    reference::fib_field,
+};
+use halo2_proofs::{circuit::Value, pasta::Fp, poly::commitment::Params};
+
+const K: u32 = 4;
+
+fn circuit() -> FiboCircuit<Fp> {
+    FiboCircuit {
+        a: Value::known(Fp::one()),
+        b: Value::known(Fp::one()),
+    }
+}
+
+fn instances() -> Vec<Vec<Fp>> {
+    vec![vec![fib_field(Fp::one(), Fp::one(), NUM_TERMS)]]
+}
+
+#[test]
+fn unblinded_proofs_verify_and_repeat() {
+    let params = Params::new(K);
+    let pk = prover::keygen(&params, &circuit()).unwrap();
+    let prove = |blinding| {
+        prover::create_proof_with(&params, &pk, circuit(), &instances(), blinding).unwrap()
+    };
+
+    let first = prove(Blinding::Off);
+    prover::verify_proof(&params, pk.get_vk(), &first, &instances()).unwrap();
+    assert_eq!(first, prove(Blinding::Off));
+    let wrong = vec![vec![Fp::from(54)]];
+    assert!(prover::verify_proof(&params, pk.get_vk(), &first, &wrong).is_err());
+
+    // Blinded proofs are the same size but never the same bytes.
+    let blinded = prove(Blinding::ZeroKnowledge);
+    assert_eq!(blinded.len(), first.len());
+    assert_ne!(blinded, prove(Blinding::ZeroKnowledge));
+    assert_ne!(blinded, first);
+}
+
+#[test]
+fn cost_reports_the_rows_blinding_takes() {
+    let report = CostReport::try_measure("fib", K, &circuit(), instances()).unwrap();
+    assert_eq!(report.usable_rows, 16 - report.blinding_factors - 1);
+    assert!(report
+        .to_string()
+        .contains(&format!("rows: 16 total, {} usable, ", report.usable_rows)));
+}
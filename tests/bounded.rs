@@ -0,0 +1,41 @@
+use fibonacci_circuit::fibonacci::{
+    bounded::FiboBoundedCircuit, nth_term, three_column, NUM_TERMS,
+};
+use halo2_proofs::{circuit::Value, dev::MockProver, pasta::Fp, plonk::Circuit};
+
+const K: u32 = 7;
+
+fn run<C: Circuit<Fp>>(circuit: C, a: u64, b: u64) -> bool {
+    let output = nth_term(Fp::from(a), Fp::from(b), NUM_TERMS);
+    let prover = MockProver::run(K, &circuit, vec![vec![output]]).unwrap();
+    prover.verify().is_ok()
+}
+
+fn bounded<const B: usize>(a: u64, b: u64) -> FiboBoundedCircuit<Fp, B> {
+    FiboBoundedCircuit {
+        a: Value::known(Fp::from(a)),
+        b: Value::known(Fp::from(b)),
+    }
+}
+
+#[test]
+fn accepts_outputs_below_the_bound() {
+    // F(10) = 55 takes 6 bits.
+    assert!(run(bounded::<6>(1, 1), 1, 1));
+    assert!(run(bounded::<16>(1, 1), 1, 1));
+    assert!(run(bounded::<64>(1, 1), 1, 1));
+}
+
+#[test]
+fn rejects_outputs_at_or_above_the_bound() {
+    assert!(!run(bounded::<5>(1, 1), 1, 1));
+    // 21·2^59 + 34·2^59 = 55·2^59 does not fit in 64 bits, and the
+    // three-column circuit alone would prove it as a field element.
+    let seed = 1 << 59;
+    assert!(!run(bounded::<64>(seed, seed), seed, seed));
+    let unbounded = three_column::FiboCircuit {
+        a: Value::known(Fp::from(seed)),
+        b: Value::known(Fp::from(seed)),
+    };
+    assert!(run(unbounded, seed, seed));
+}
@@ -0,0 +1,90 @@
+use fibonacci_circuit::{
+    bundle::{self, BundleError, ProofBundle},
+    envelope::{ProofEnvelope, TranscriptKind},
+    params::ParamsInfo,
+};
+use halo2_proofs::pasta::Fp;
+
+fn sample() -> ProofBundle {
+    ProofBundle {
+        envelope: ProofEnvelope {
+            circuit: "fib-three-column".to_string(),
+            k: 4,
+            n: 10,
+            fingerprint: [7; 32],
+            transcript: TranscriptKind::Blake2b,
+            instances: vec![vec![Fp::from(55), -Fp::one()]],
+            proof: vec![1, 2, 3, 4],
+        },
+        vk: "PinnedVerificationKey { .. }".to_string(),
+        params: ParamsInfo {
+            k: 4,
+            generators: 16,
+            bytes: 1092,
+            digest: "00".repeat(32),
+        },
+    }
+}
+
+#[test]
+fn round_trip() {
+    let bundle = sample();
+    assert_eq!(ProofBundle::from_bytes(&bundle.to_bytes()).unwrap(), bundle);
+}
+
+#[test]
+fn manifest_lists_every_file() {
+    let manifest = sample().manifest();
+    let names: Vec<_> = manifest
+        .files
+        .iter()
+        .map(|file| file.name.as_str())
+        .collect();
+
+    assert_eq!(
+        names,
+        [
+            bundle::PROOF,
+            bundle::INSTANCES,
+            bundle::VERIFYING_KEY,
+            bundle::PARAMS
+        ]
+    );
+    assert_eq!(manifest.files[0].bytes, 4);
+}
+
+#[test]
+fn tampered_file_is_rejected() {
+    let mut bytes = sample().to_bytes();
+    // Flip a bit of the proof bytes, which only appear in `proof.bin`.
+    let at = bytes
+        .windows(4)
+        .position(|window| window == [1, 2, 3, 4])
+        .unwrap();
+    bytes[at] ^= 1;
+
+    assert!(matches!(
+        ProofBundle::from_bytes(&bytes),
+        Err(BundleError::DigestMismatch(name)) if name == bundle::PROOF
+    ));
+}
+
+#[test]
+fn truncated_bundle_is_rejected() {
+    let bytes = sample().to_bytes();
+
+    assert!(matches!(
+        ProofBundle::from_bytes(&bytes[..bytes.len() - 1]),
+        Err(BundleError::Truncated)
+    ));
+}
+
+#[test]
+fn envelope_is_not_a_bundle() {
+    let bytes = sample().envelope.to_bytes();
+
+    assert!(matches!(
+        ProofBundle::from_bytes(&bytes),
+        Err(BundleError::BadMagic(_))
+    ));
+}
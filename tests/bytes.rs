@@ -0,0 +1,258 @@
+use fibonacci_circuit::gadgets::bytes::{ByteTable, BytesChip, BytesConfig, MAX_BYTES};
+use halo2_proofs::{
+    arithmetic::{Field, FieldExt},
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    dev::{MockProver, VerifyFailure},
+    pasta::{group::ff::PrimeField, Fp},
+    plonk::{Advice, Circuit, Column, ConstraintSystem, Error, Instance},
+};
+
+#[derive(Debug, Clone)]
+struct Config {
+    input: Column<Advice>,
+    /// Two chips on their own columns, sharing one table.
+    chips: [BytesConfig; 2],
+    instance: Column<Instance>,
+}
+
+fn configure(meta: &mut ConstraintSystem<Fp>) -> Config {
+    let input = meta.advice_column();
+    meta.enable_equality(input);
+    let instance = meta.instance_column();
+    meta.enable_equality(instance);
+    let table = ByteTable::configure(meta);
+    let chips = [(); 2].map(|_| {
+        let (z, byte) = (meta.advice_column(), meta.advice_column());
+        BytesChip::configure(meta, z, byte, table)
+    });
+    Config {
+        input,
+        chips,
+        instance,
+    }
+}
+
+/// Decomposes each `(x, n)` into `n` bytes, alternating between the two
+/// chips, and exposes all the bytes in order.
+#[derive(Debug, Default, Clone)]
+struct DecomposeCircuit {
+    inputs: Vec<(Fp, usize)>,
+}
+
+impl Circuit<Fp> for DecomposeCircuit {
+    type Config = Config;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            inputs: self.inputs.iter().map(|&(_, n)| (Fp::zero(), n)).collect(),
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>) -> Config {
+        configure(meta)
+    }
+
+    fn synthesize(&self, config: Config, mut layouter: impl Layouter<Fp>) -> Result<(), Error> {
+        config.chips[0]
+            .table
+            .load(layouter.namespace(|| "byte table"))?;
+        let chips = config.chips.map(BytesChip::construct);
+        let mut bytes = vec![];
+        for (i, &(x, n)) in self.inputs.iter().enumerate() {
+            let x = layouter.assign_region(
+                || "input",
+                |mut region| region.assign_advice(|| "x", config.input, 0, || Value::known(x)),
+            )?;
+            bytes.extend(chips[i % 2].decompose(layouter.namespace(|| "decompose"), &x, n)?);
+        }
+        for (row, byte) in bytes.iter().enumerate() {
+            layouter.constrain_instance(byte.cell(), config.instance, row)?;
+        }
+        Ok(())
+    }
+}
+
+/// Lays out one decomposition with arbitrary `z_0..=z_n` and bytes.
+#[derive(Debug, Default, Clone)]
+struct ForgedCircuit {
+    z: Vec<Fp>,
+    bytes: Vec<Fp>,
+}
+
+impl ForgedCircuit {
+    /// The rows the chip would lay out for `x` in `n` bytes.
+    fn honest(x: u64, n: usize) -> Self {
+        ForgedCircuit {
+            z: (0..=n)
+                .map(|i| Fp::from_u128(x as u128 >> (8 * i)))
+                .collect(),
+            bytes: (0..n)
+                .map(|i| Fp::from_u128(x as u128 >> (8 * i) & 0xff))
+                .collect(),
+        }
+    }
+}
+
+impl Circuit<Fp> for ForgedCircuit {
+    type Config = Config;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        self.clone()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>) -> Config {
+        configure(meta)
+    }
+
+    fn synthesize(&self, config: Config, mut layouter: impl Layouter<Fp>) -> Result<(), Error> {
+        let cfg = &config.chips[0];
+        cfg.table.load(layouter.namespace(|| "byte table"))?;
+        layouter.assign_region(
+            || "forged decomposition",
+            |mut region| {
+                for (row, z) in self.z.iter().enumerate() {
+                    if row < self.bytes.len() {
+                        cfg.q_byte.enable(&mut region, row)?;
+                        let byte = Value::known(self.bytes[row]);
+                        region.assign_advice(|| "byte", cfg.byte, row, || byte)?;
+                    } else {
+                        cfg.s_end.enable(&mut region, row)?;
+                    }
+                    region.assign_advice(|| "z", cfg.z, row, || Value::known(*z))?;
+                }
+                Ok(())
+            },
+        )
+    }
+}
+
+const K: u32 = 9;
+
+/// The gates and lookups that fail, by name.
+fn failures(circuit: &impl Circuit<Fp>, instance: Vec<Fp>) -> Vec<String> {
+    let prover = MockProver::run(K, circuit, vec![instance]).unwrap();
+    let mut names: Vec<_> = prover
+        .verify()
+        .err()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|failure| match failure {
+            VerifyFailure::ConstraintNotSatisfied { constraint, .. } => {
+                // "Constraint i ('name') in gate j ('gate')"
+                let constraint = constraint.to_string();
+                constraint.rsplit('\'').nth(1).unwrap().to_string()
+            }
+            VerifyFailure::Lookup { .. } => "lookup".to_string(),
+            other => other.to_string(),
+        })
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// The low `n` bytes of `x`.
+fn bytes_of(x: Fp, n: usize) -> Vec<Fp> {
+    x.to_repr().as_ref()[..n]
+        .iter()
+        .map(|&byte| Fp::from(byte as u64))
+        .collect()
+}
+
+fn decompose(inputs: &[(Fp, usize)]) -> Vec<String> {
+    let instance = inputs.iter().flat_map(|&(x, n)| bytes_of(x, n)).collect();
+    let circuit = DecomposeCircuit {
+        inputs: inputs.to_vec(),
+    };
+    failures(&circuit, instance)
+}
+
+fn forged(circuit: &ForgedCircuit) -> Vec<String> {
+    failures(circuit, vec![])
+}
+
+#[test]
+fn decompose_accepts_exactly_the_values_below_256_pow_n() {
+    for n in [0, 1, 2, 8, 16, MAX_BYTES] {
+        let bound = Fp::from(256).pow(&[n as u64, 0, 0, 0]);
+        assert_eq!(decompose(&[(bound - Fp::one(), n)]), Vec::<String>::new());
+        assert_eq!(decompose(&[(Fp::zero(), n)]), Vec::<String>::new());
+        assert!(!decompose(&[(bound, n)]).is_empty(), "256^{}", n);
+        assert!(!decompose(&[(-Fp::one(), n)]).is_empty(), "-1 in {}", n);
+    }
+}
+
+#[test]
+fn decompose_returns_the_bytes_least_significant_first() {
+    let x = Fp::from(0x0102_0304);
+    assert_eq!(bytes_of(x, 4), [4, 3, 2, 1].map(Fp::from));
+    let circuit = DecomposeCircuit {
+        inputs: vec![(x, 4)],
+    };
+    assert_eq!(failures(&circuit, bytes_of(x, 4)), Vec::<String>::new());
+    let reversed = [1, 2, 3, 4].map(Fp::from).to_vec();
+    assert!(!failures(&circuit, reversed).is_empty());
+}
+
+#[test]
+fn two_chips_share_one_table() {
+    let inputs = [
+        (Fp::from(u64::MAX), 8),
+        (Fp::from(0xbeef), 2),
+        (Fp::from(255), 1),
+        (Fp::from_u128(u128::MAX), 16),
+    ];
+    assert_eq!(decompose(&inputs), Vec::<String>::new());
+
+    // Out of range on the second chip, with the table loaded by the first.
+    let mut inputs = inputs;
+    inputs[1].0 = Fp::from(0x1_0000);
+    assert!(!decompose(&inputs).is_empty());
+}
+
+#[test]
+fn forged_bytes_are_rejected() {
+    assert_eq!(
+        forged(&ForgedCircuit::honest(0x1234, 2)),
+        Vec::<String>::new()
+    );
+
+    // A byte off by one.
+    let mut circuit = ForgedCircuit::honest(0x1234, 2);
+    circuit.bytes[0] += Fp::one();
+    assert_eq!(forged(&circuit), ["byte decomposition"]);
+
+    // 256 = 256 + 256·0 satisfies the gate but not the lookup.
+    let mut circuit = ForgedCircuit::honest(256, 2);
+    circuit.bytes = vec![Fp::from(256), Fp::zero()];
+    circuit.z[1] = Fp::zero();
+    assert_eq!(forged(&circuit), ["lookup"]);
+
+    // 255 = -1 + 256·1, with the high byte honest.
+    let mut circuit = ForgedCircuit::honest(255, 2);
+    circuit.bytes[0] = -Fp::one();
+    circuit.z[1] = Fp::one();
+    circuit.bytes[1] = Fp::one();
+    assert_eq!(forged(&circuit), ["lookup"]);
+
+    // 256 in one byte: a byte of 0 leaves 1 for the end.
+    let circuit = ForgedCircuit {
+        z: vec![Fp::from(256), Fp::one()],
+        bytes: vec![Fp::zero()],
+    };
+    assert_eq!(forged(&circuit), ["byte decomposition end"]);
+
+    // Dividing by 256 in the field, every byte 0, never reaches 0.
+    let inverse = Fp::from(256).invert().unwrap();
+    let mut z = vec![Fp::from(256 * 256 * 256)];
+    for i in 0..2 {
+        z.push(z[i] * inverse);
+    }
+    let circuit = ForgedCircuit {
+        z,
+        bytes: vec![Fp::zero(); 2],
+    };
+    assert_eq!(forged(&circuit), ["byte decomposition end"]);
+}
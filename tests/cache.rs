@@ -0,0 +1,147 @@
+use fibonacci_circuit::{
+    cache::{CacheKey, ProofCache},
+    envelope::{ProofEnvelope, TranscriptKind},
+    fibonacci::{three_column::FiboCircuit, NUM_TERMS},
+    fingerprint::fingerprint,
+    params,
+    prover::{self, Blinding},
+    reference::fib_field,
+};
+use halo2_proofs::{circuit::Value, pasta::Fp, poly::commitment::Params};
+
+const K: u32 = 4;
+
+fn cache_dir(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("fibo-cache-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    dir
+}
+
+fn circuit() -> FiboCircuit<Fp> {
+    FiboCircuit {
+        a: Value::known(Fp::one()),
+        b: Value::known(Fp::one()),
+    }
+}
+
+fn key() -> CacheKey {
+    CacheKey {
+        fingerprint: [7; 32],
+        params: "digest".to_string(),
+        blinding: Blinding::ZeroKnowledge,
+        instances: vec![vec![Fp::from(55)]],
+    }
+}
+
+fn envelope(key: &CacheKey) -> ProofEnvelope {
+    ProofEnvelope {
+        circuit: "fib-three-column".to_string(),
+        k: K,
+        n: NUM_TERMS as u32,
+        fingerprint: key.fingerprint,
+        transcript: TranscriptKind::Blake2b,
+        instances: key.instances.clone(),
+        proof: vec![1, 2, 3],
+    }
+}
+
+#[test]
+fn returns_what_was_stored() {
+    let cache = ProofCache::new(cache_dir("roundtrip"));
+    let key = key();
+    assert_eq!(cache.get(&key), None);
+    cache.put(&key, &envelope(&key)).unwrap();
+    assert_eq!(cache.get(&key), Some(envelope(&key)));
+    std::fs::remove_dir_all(cache.dir()).unwrap();
+}
+
+#[test]
+fn other_keys_params_or_inputs_miss() {
+    let cache = ProofCache::new(cache_dir("miss"));
+    let key = key();
+    cache.put(&key, &envelope(&key)).unwrap();
+
+    let mut other = key.clone();
+    other.fingerprint[0] ^= 1;
+    assert_eq!(cache.get(&other), None);
+
+    let mut other = key.clone();
+    other.params = "other digest".to_string();
+    assert_eq!(cache.get(&other), None);
+
+    let mut other = key.clone();
+    other.instances = vec![vec![Fp::from(56)]];
+    assert_eq!(cache.get(&other), None);
+
+    // The same values split across columns differently.
+    let mut other = key.clone();
+    other.instances = vec![vec![], vec![Fp::from(55)]];
+    assert_ne!(other.digest(), key.digest());
+    std::fs::remove_dir_all(cache.dir()).unwrap();
+}
+
+#[test]
+fn an_unblinded_proof_is_not_a_hit_for_a_zero_knowledge_one() {
+    let cache = ProofCache::new(cache_dir("blinding"));
+    let unblinded = CacheKey {
+        blinding: Blinding::Off,
+        ..key()
+    };
+    cache.put(&unblinded, &envelope(&unblinded)).unwrap();
+    assert_eq!(cache.get(&key()), None);
+    assert_eq!(cache.get(&unblinded), Some(envelope(&unblinded)));
+
+    cache.put(&key(), &envelope(&key())).unwrap();
+    assert_ne!(cache.path(&key()), cache.path(&unblinded));
+    std::fs::remove_dir_all(cache.dir()).unwrap();
+}
+
+#[test]
+fn a_corrupt_or_mismatched_entry_is_a_miss() {
+    let cache = ProofCache::new(cache_dir("corrupt"));
+    let key = key();
+    cache.put(&key, &envelope(&key)).unwrap();
+    std::fs::write(cache.path(&key), b"not an envelope").unwrap();
+    assert_eq!(cache.get(&key), None);
+
+    let mut wrong = envelope(&key);
+    wrong.instances = vec![vec![Fp::from(56)]];
+    cache.put(&key, &wrong).unwrap();
+    assert_eq!(cache.get(&key), None);
+    std::fs::remove_dir_all(cache.dir()).unwrap();
+}
+
+#[test]
+fn a_cached_proof_verifies() {
+    let params: Params<_> = Params::new(K);
+    let instances = vec![vec![fib_field(Fp::one(), Fp::one(), NUM_TERMS)]];
+    let pk = prover::keygen(&params, &circuit()).unwrap();
+    let proof = prover::create_proof(&params, &pk, circuit(), &instances).unwrap();
+
+    let cache = ProofCache::new(cache_dir("verify"));
+    let key = CacheKey {
+        fingerprint: fingerprint(pk.get_vk()),
+        params: params::digest(&params::to_bytes(&params)),
+        blinding: Blinding::ZeroKnowledge,
+        instances: instances.clone(),
+    };
+    let stored = ProofEnvelope {
+        circuit: "fib-three-column".to_string(),
+        k: K,
+        n: NUM_TERMS as u32,
+        fingerprint: key.fingerprint,
+        transcript: TranscriptKind::Blake2b,
+        instances: instances.clone(),
+        proof,
+    };
+    cache.put(&key, &stored).unwrap();
+
+    let vk = prover::keygen_vk(&params, &circuit()).unwrap();
+    let lookup = CacheKey {
+        fingerprint: fingerprint(&vk),
+        ..key
+    };
+    let found = cache.get(&lookup).unwrap();
+    prover::verify_proof(&params, &vk, &found.proof, &instances).unwrap();
+    std::fs::remove_dir_all(cache.dir()).unwrap();
+}
@@ -0,0 +1,127 @@
+use fibonacci_circuit::{
+    circuits::catalan::{self, CatalanCircuit, CatalanCircuitConfig},
+    fibonacci::NUM_TERMS,
+    gadgets::arithmetic::{ArithmeticChip, ArithmeticInstructions},
+};
+use halo2_proofs::{
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    dev::{MockProver, VerifyFailure},
+    pasta::Fp,
+    plonk::{Circuit, ConstraintSystem, Error},
+};
+
+const K: u32 = 6;
+const STEPS: usize = NUM_TERMS - 1;
+
+/// Lays out the Catalan circuit with each step's factors `2(2n+1)` and
+/// `n+2` multiplied by `scales[n]`, the multiplication and division done
+/// honestly on whatever factors result.
+#[derive(Debug, Clone)]
+struct ForgedCircuit {
+    scales: Vec<(Fp, Fp)>,
+}
+
+impl ForgedCircuit {
+    fn honest() -> Self {
+        ForgedCircuit {
+            scales: vec![(Fp::one(), Fp::one()); STEPS],
+        }
+    }
+}
+
+impl Circuit<Fp> for ForgedCircuit {
+    type Config = CatalanCircuitConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        self.clone()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>) -> CatalanCircuitConfig {
+        CatalanCircuit::<Fp>::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: CatalanCircuitConfig,
+        mut layouter: impl Layouter<Fp>,
+    ) -> Result<(), Error> {
+        let cfg = config.catalan;
+        let arithmetic = ArithmeticChip::construct(config.arithmetic);
+        let mut c = layouter.assign_region(
+            || "C(0)",
+            |mut region| region.assign_advice_from_constant(|| "C(0)", cfg.advice[0], 0, Fp::one()),
+        )?;
+        for (n, &(num_scale, den_scale)) in self.scales.iter().enumerate() {
+            let n = n as u64;
+            let (num, den) = layouter.assign_region(
+                || "forged factors",
+                |mut region| {
+                    cfg.selector.enable(&mut region, 0)?;
+                    region.assign_fixed(|| "n", cfg.index, 0, || Value::known(Fp::from(n)))?;
+                    let num = Fp::from(2 * (2 * n + 1)) * num_scale;
+                    let den = Fp::from(n + 2) * den_scale;
+                    let num =
+                        region.assign_advice(|| "num", cfg.advice[0], 0, || Value::known(num))?;
+                    let den =
+                        region.assign_advice(|| "den", cfg.advice[1], 0, || Value::known(den))?;
+                    Ok((num, den))
+                },
+            )?;
+            let product = arithmetic.mul(layouter.namespace(|| "C(n) · num"), &c, &num)?;
+            c = arithmetic.div(layouter.namespace(|| "/ den"), &product, &den)?;
+        }
+        layouter.constrain_instance(c.cell(), config.instance, 0)
+    }
+}
+
+/// The gates that fail, by name, with the honest last term public.
+fn failures(circuit: &impl Circuit<Fp>) -> Vec<String> {
+    let last = catalan::nth_term(STEPS);
+    let prover = MockProver::run(K, circuit, vec![vec![last]]).unwrap();
+    let mut names: Vec<_> = prover
+        .verify()
+        .err()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|failure| match failure {
+            VerifyFailure::ConstraintNotSatisfied { constraint, .. } => {
+                // "Constraint i ('name') in gate j ('gate')"
+                let constraint = constraint.to_string();
+                constraint.rsplit('\'').nth(1).unwrap().to_string()
+            }
+            VerifyFailure::Permutation { .. } => "permutation".to_string(),
+            other => other.to_string(),
+        })
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+#[test]
+fn the_honest_circuit_verifies() {
+    assert_eq!(catalan::nth_term::<Fp>(STEPS), Fp::from(4862));
+    assert_eq!(failures(&CatalanCircuit::default()), Vec::<String>::new());
+    assert_eq!(failures(&ForgedCircuit::honest()), Vec::<String>::new());
+}
+
+#[test]
+fn forged_intermediate_terms_are_rejected() {
+    // C(3) doubled by a doubled numerator, then halved back by a doubled
+    // denominator, so C(4) on is honest.
+    let mut forged = ForgedCircuit::honest();
+    forged.scales[2].0 = Fp::from(2);
+    forged.scales[3].1 = Fp::from(2);
+    assert_eq!(failures(&forged), ["catalan factors"]);
+
+    // Both factors tripled: every term is honest, only the factors are not.
+    let mut forged = ForgedCircuit::honest();
+    forged.scales[4] = (Fp::from(3), Fp::from(3));
+    assert_eq!(failures(&forged), ["catalan factors"]);
+
+    // Left uncompensated, the last term is wrong too.
+    let mut forged = ForgedCircuit::honest();
+    forged.scales[2].0 = Fp::from(2);
+    assert_eq!(failures(&forged), ["catalan factors", "permutation"]);
+}
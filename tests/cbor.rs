@@ -0,0 +1,189 @@
+use std::{fs, path::PathBuf};
+
+use fibonacci_circuit::{
+    cbor::{self, CborError, Value},
+    envelope::{EnvelopeError, ProofEnvelope, ENVELOPE_VERSION},
+    inspect::Inspection,
+};
+use halo2_proofs::pasta::Fp;
+
+fn hex(s: &str) -> Vec<u8> {
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+fn golden_envelope() -> ProofEnvelope {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join(format!("tests/golden/envelope-v{}.proof", ENVELOPE_VERSION));
+    ProofEnvelope::from_bytes(&fs::read(path).unwrap()).unwrap()
+}
+
+#[test]
+fn encodes_rfc_8949_examples() {
+    let examples = [
+        (Value::Unsigned(0), "00"),
+        (Value::Unsigned(23), "17"),
+        (Value::Unsigned(24), "1818"),
+        (Value::Unsigned(1000), "1903e8"),
+        (Value::Unsigned(1_000_000), "1a000f4240"),
+        (Value::Unsigned(u64::MAX), "1bffffffffffffffff"),
+        (Value::Negative(0), "20"),
+        (Value::Negative(999), "3903e7"),
+        (Value::Float(1.1), "fb3ff199999999999a"),
+        (Value::Bool(false), "f4"),
+        (Value::Null, "f6"),
+        (Value::Bytes(vec![1, 2, 3, 4]), "4401020304"),
+        (Value::text("IETF"), "6449455446"),
+        (Value::text("\u{00fc}"), "62c3bc"),
+        (
+            Value::Array(vec![
+                Value::Unsigned(1),
+                Value::Array(vec![Value::Unsigned(2), Value::Unsigned(3)]),
+            ]),
+            "8201820203",
+        ),
+        (
+            Value::Map(vec![
+                (Value::text("a"), Value::Unsigned(1)),
+                (
+                    Value::text("b"),
+                    Value::Array(vec![Value::Unsigned(2), Value::Unsigned(3)]),
+                ),
+            ]),
+            "a26161016162820203",
+        ),
+    ];
+    for (value, bytes) in examples {
+        assert_eq!(cbor::encode(&value), hex(bytes), "{:?}", value);
+        assert_eq!(cbor::decode(&hex(bytes)), Ok(value));
+    }
+}
+
+#[test]
+fn json_numbers_keep_their_kind() {
+    let json = serde_json::json!({"n": 7, "neg": -3, "x": 0.5, "ok": true});
+    let value = cbor::from_json(&json);
+    assert_eq!(value.get("n"), Some(&Value::Unsigned(7)));
+    assert_eq!(value.get("neg"), Some(&Value::Negative(2)));
+    assert_eq!(value.get("x"), Some(&Value::Float(0.5)));
+    assert_eq!(value.get("ok"), Some(&Value::Bool(true)));
+}
+
+#[test]
+fn reports_have_the_fields_of_their_json() {
+    let report = Inspection::of(&golden_envelope());
+    let value = cbor::decode(&cbor::to_vec(&report).unwrap()).unwrap();
+    assert_eq!(
+        value,
+        cbor::from_json(&serde_json::to_value(&report).unwrap())
+    );
+    assert_eq!(value.get("k"), Some(&Value::Unsigned(4)));
+}
+
+#[test]
+fn rejects_what_it_does_not_read() {
+    assert_eq!(cbor::decode(&[]), Err(CborError::Truncated));
+    assert_eq!(cbor::decode(&hex("1903")), Err(CborError::Truncated));
+    // A byte string claiming more bytes than there are.
+    assert_eq!(
+        cbor::decode(&hex("5bffffffffffffffff00")),
+        Err(CborError::Truncated)
+    );
+    // Indefinite-length array, and a tag.
+    assert_eq!(cbor::decode(&hex("9fff")), Err(CborError::Unsupported(0)));
+    assert_eq!(
+        cbor::decode(&hex("c11a514b67b0")),
+        Err(CborError::Unsupported(0))
+    );
+    assert_eq!(cbor::decode(&hex("0000")), Err(CborError::TrailingBytes(1)));
+    assert_eq!(cbor::decode(&hex("62c328")), Err(CborError::InvalidUtf8(0)));
+    assert_eq!(
+        cbor::decode(&[0x81; cbor::MAX_DEPTH + 1]),
+        Err(CborError::TooDeep)
+    );
+}
+
+#[test]
+fn envelope_round_trips_through_cbor() {
+    let envelope = golden_envelope();
+    let bytes = envelope.to_cbor();
+    assert_eq!(ProofEnvelope::from_cbor(&bytes).unwrap(), envelope);
+    assert_eq!(ProofEnvelope::parse(&bytes).unwrap(), envelope);
+    assert_eq!(
+        ProofEnvelope::parse(&envelope.to_bytes()).unwrap(),
+        envelope
+    );
+    assert!(bytes.len() < envelope.to_bytes().len() + 64);
+}
+
+#[test]
+fn cbor_envelope_rejects_malformed_fields() {
+    let envelope = golden_envelope();
+    let value = cbor::decode(&envelope.to_cbor()).unwrap();
+    let Value::Map(entries) = value else {
+        panic!("envelope is not a map")
+    };
+
+    let without_proof: Vec<_> = entries
+        .iter()
+        .filter(|(key, _)| key.as_text() != Some("proof"))
+        .cloned()
+        .collect();
+    assert!(matches!(
+        ProofEnvelope::from_cbor(&cbor::encode(&Value::Map(without_proof))),
+        Err(EnvelopeError::Cbor(CborError::Unexpected(_)))
+    ));
+
+    // The modulus is not a canonical field element.
+    let modulus = hex("01000000ed302d991bf94c09fc98462200000000000000000000000000000040");
+    let bad_instances: Vec<_> = entries
+        .iter()
+        .map(|(key, value)| match key.as_text() {
+            Some("instances") => (
+                key.clone(),
+                Value::Array(vec![Value::Array(vec![Value::Bytes(modulus.clone())])]),
+            ),
+            _ => (key.clone(), value.clone()),
+        })
+        .collect();
+    assert!(matches!(
+        ProofEnvelope::from_cbor(&cbor::encode(&Value::Map(bad_instances))),
+        Err(EnvelopeError::Cbor(CborError::Unexpected(_)))
+    ));
+}
+
+#[test]
+fn version_1_cbor_envelopes_take_n_from_the_circuit() {
+    let envelope = golden_envelope();
+    let Value::Map(entries) = cbor::decode(&envelope.to_cbor()).unwrap() else {
+        panic!("envelope is not a map")
+    };
+    // Version 1 had no `n`.
+    let version_1 = |circuit: &str| {
+        let entries: Vec<_> = entries
+            .iter()
+            .filter(|(key, _)| key.as_text() != Some("n"))
+            .map(|(key, value)| match key.as_text() {
+                Some("version") => (key.clone(), Value::Unsigned(1)),
+                Some("circuit") => (key.clone(), Value::text(circuit)),
+                _ => (key.clone(), value.clone()),
+            })
+            .collect();
+        ProofEnvelope::from_cbor(&cbor::encode(&Value::Map(entries)))
+    };
+
+    assert_eq!(version_1(&envelope.circuit).unwrap(), envelope);
+    assert!(matches!(
+        version_1("fib-unknown"),
+        Err(EnvelopeError::UnknownCircuit(name)) if name == "fib-unknown"
+    ));
+}
+
+#[test]
+fn instances_round_trip() {
+    let instances = vec![vec![Fp::from(1), -Fp::from(1)], vec![]];
+    let value = cbor::instances(&instances);
+    assert_eq!(cbor::to_instances(&value).unwrap(), instances);
+}
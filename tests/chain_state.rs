@@ -0,0 +1,177 @@
+use std::{fs, path::PathBuf};
+
+use fibonacci_circuit::{
+    chain_state::{self, ChainState, ChainStateError, ResumeError},
+    chunked::{self, ChunkProof},
+    fingerprint::fingerprint,
+    prover,
+    reference::fib_field,
+};
+use halo2_proofs::{
+    pasta::{EqAffine, Fp},
+    plonk::ProvingKey,
+    poly::commitment::Params,
+};
+
+const K: u32 = 4;
+const C: usize = 4;
+const CHUNKS: usize = 4;
+
+fn state_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("fibo-chain-{}-{}", name, std::process::id()));
+    let _ = fs::remove_file(&path);
+    path
+}
+
+fn seeds() -> (Fp, Fp) {
+    (Fp::one(), Fp::one())
+}
+
+fn setup() -> (Params<EqAffine>, ProvingKey<EqAffine>) {
+    let params = Params::new(K);
+    let (circuit, _) = chunked::chunk::<C>(Fp::one(), Fp::one());
+    let pk = prover::keygen(&params, &circuit).unwrap();
+    (params, pk)
+}
+
+/// The state of a job killed after proving `proofs`.
+fn interrupted(pk: &ProvingKey<EqAffine>, proofs: Vec<ChunkProof>) -> ChainState {
+    let mut state = ChainState::new(C as u32, CHUNKS as u32, fingerprint(pk.get_vk()), seeds());
+    state.proofs = proofs;
+    state
+}
+
+#[test]
+fn saves_the_state_after_every_chunk() {
+    let (params, pk) = setup();
+    let path = state_path("fresh");
+    let proofs = chain_state::resume_chain::<C>(&params, &pk, seeds(), CHUNKS, &path).unwrap();
+    assert_eq!(proofs.len(), CHUNKS);
+
+    let saved = ChainState::load(&path).unwrap();
+    assert!(saved.is_complete());
+    assert_eq!(saved.proofs, proofs);
+    let last = chunked::verify_chain(&params, pk.get_vk(), seeds(), &saved.proofs).unwrap();
+    let expected = |n| fib_field(Fp::one(), Fp::one(), n);
+    assert_eq!(last, (expected(C * CHUNKS + 1), expected(C * CHUNKS + 2)));
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn resumes_at_the_first_missing_chunk() {
+    let (params, pk) = setup();
+    let path = state_path("resume");
+    let done = chunked::prove_chain::<C>(&params, &pk, seeds(), 2).unwrap();
+    interrupted(&pk, done.clone()).save(&path).unwrap();
+
+    let proofs = chain_state::resume_chain::<C>(&params, &pk, seeds(), CHUNKS, &path).unwrap();
+    // Proofs are randomized, so equal bytes mean the first two were kept,
+    // not proven again.
+    assert_eq!(proofs[..2], done[..]);
+    assert_eq!(proofs.len(), CHUNKS);
+    chunked::verify_chain(&params, pk.get_vk(), seeds(), &proofs).unwrap();
+
+    let again = chain_state::resume_chain::<C>(&params, &pk, seeds(), CHUNKS, &path).unwrap();
+    assert_eq!(again, proofs);
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn refuses_the_state_of_another_job() {
+    let (params, pk) = setup();
+    let path = state_path("other");
+    let done = chunked::prove_chain::<C>(&params, &pk, seeds(), 1).unwrap();
+    let saved = interrupted(&pk, done);
+    saved.save(&path).unwrap();
+
+    let other = (Fp::from(2), Fp::from(3));
+    let err = chain_state::resume_chain::<C>(&params, &pk, other, CHUNKS, &path).unwrap_err();
+    assert!(matches!(err, ResumeError::OtherJob("seeds")), "{:?}", err);
+    let err = chain_state::resume_chain::<C>(&params, &pk, seeds(), 8, &path).unwrap_err();
+    assert_eq!(err.to_string(), "saved chain state has different chunks");
+    assert_eq!(ChainState::load(&path).unwrap(), saved);
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn damaged_state_is_an_error_not_a_restart() {
+    let (params, pk) = setup();
+    let path = state_path("damaged");
+    let done = chunked::prove_chain::<C>(&params, &pk, seeds(), 2).unwrap();
+    let bytes = interrupted(&pk, done).to_bytes();
+
+    fs::write(&path, &bytes[..bytes.len() - 1]).unwrap();
+    let err = chain_state::resume_chain::<C>(&params, &pk, seeds(), CHUNKS, &path).unwrap_err();
+    assert!(matches!(
+        err,
+        ResumeError::State(ChainStateError::Truncated)
+    ));
+    assert_eq!(fs::read(&path).unwrap(), bytes[..bytes.len() - 1]);
+
+    let mut trailing = bytes.clone();
+    trailing.push(0);
+    assert!(matches!(
+        ChainState::from_bytes(&trailing),
+        Err(ChainStateError::TrailingBytes(1))
+    ));
+    let mut magic = bytes.clone();
+    magic[0] = b'X';
+    assert!(matches!(
+        ChainState::from_bytes(&magic),
+        Err(ChainStateError::BadMagic(_))
+    ));
+    let mut version = bytes;
+    version[4] = 0;
+    assert!(matches!(
+        ChainState::from_bytes(&version),
+        Err(ChainStateError::UnsupportedVersion(0))
+    ));
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn more_proofs_than_chunks_is_corrupt() {
+    let (params, pk) = setup();
+    let mut state = interrupted(
+        &pk,
+        chunked::prove_chain::<C>(&params, &pk, seeds(), 3).unwrap(),
+    );
+    state.chunks = 3;
+    assert_eq!(ChainState::from_bytes(&state.to_bytes()).unwrap(), state);
+
+    state.chunks = 2;
+    assert!(matches!(
+        ChainState::from_bytes(&state.to_bytes()),
+        Err(ChainStateError::TooManyProofs {
+            proven: 3,
+            chunks: 2
+        })
+    ));
+}
+
+#[test]
+fn a_job_too_large_for_the_state_is_an_error() {
+    let (params, pk) = setup();
+    let path = state_path("too-large");
+    let chunks = u32::MAX as usize + 1;
+    let err = chain_state::resume_chain::<C>(&params, &pk, seeds(), chunks, &path).unwrap_err();
+    assert!(matches!(err, ResumeError::TooLarge("chunks")), "{:?}", err);
+    assert!(!path.exists());
+}
+
+#[test]
+fn state_round_trips() {
+    let (params, pk) = setup();
+    let state = interrupted(&pk, vec![]);
+    assert_eq!(ChainState::from_bytes(&state.to_bytes()).unwrap(), state);
+    assert_eq!(state.next_input(), seeds());
+
+    let state = interrupted(
+        &pk,
+        chunked::prove_chain::<C>(&params, &pk, seeds(), 3).unwrap(),
+    );
+    let read = ChainState::from_bytes(&state.to_bytes()).unwrap();
+    assert_eq!(read, state);
+    assert_eq!(read.next_input(), state.proofs[2].output());
+    assert!(!read.is_complete());
+}
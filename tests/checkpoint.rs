@@ -0,0 +1,62 @@
+use fibonacci_circuit::fibonacci::{
+    checkpoint::{instances, FiboCheckpointCircuit, FINAL_PAIR},
+    nth_term, NUM_TERMS,
+};
+use halo2_proofs::{circuit::Value, dev::MockProver, pasta::Fp, plonk::Error};
+
+const K: u32 = 4;
+
+fn circuit(checkpoints: &[usize]) -> FiboCheckpointCircuit<Fp> {
+    FiboCheckpointCircuit {
+        a: Value::known(Fp::from(2)),
+        b: Value::known(Fp::from(3)),
+        checkpoints: checkpoints.to_vec(),
+    }
+}
+
+#[test]
+fn exposes_checkpoints_in_the_order_given() {
+    let checkpoints = [7, 2, NUM_TERMS, 7];
+    let instances = instances(Fp::from(2), Fp::from(3), &checkpoints);
+    assert_eq!(instances[0][1], Fp::from(3));
+    assert_eq!(
+        instances[0][2],
+        nth_term(Fp::from(2), Fp::from(3), NUM_TERMS)
+    );
+
+    let prover = MockProver::run(K, &circuit(&checkpoints), instances).unwrap();
+    assert_eq!(prover.verify(), Ok(()));
+}
+
+#[test]
+fn rejects_a_wrong_interior_term() {
+    let checkpoints = [1, 5, NUM_TERMS];
+    let mut instances = instances(Fp::from(2), Fp::from(3), &checkpoints);
+    instances[0][1] += Fp::one();
+
+    let prover = MockProver::run(K, &circuit(&checkpoints), instances).unwrap();
+    assert!(prover.verify().is_err());
+}
+
+#[test]
+fn checkpoints_must_name_a_term() {
+    for checkpoints in [[0], [NUM_TERMS + 1]] {
+        let result = MockProver::run(K, &circuit(&checkpoints), vec![vec![Fp::zero()]]);
+        assert!(matches!(result, Err(Error::Synthesis)));
+    }
+}
+
+#[test]
+fn final_pair_continues_the_sequence() {
+    let (a, b) = (Fp::from(2), Fp::from(3));
+    let circuit = FiboCheckpointCircuit::final_pair(Value::known(a), Value::known(b));
+    let instances = instances(a, b, &FINAL_PAIR);
+    let [x, y] = [instances[0][0], instances[0][1]];
+    assert_eq!(x + y, nth_term(a, b, NUM_TERMS + 1));
+
+    let prover = MockProver::run(K, &circuit, instances.clone()).unwrap();
+    assert_eq!(prover.verify(), Ok(()));
+    // The pair, swapped, is not the circuit's.
+    let prover = MockProver::run(K, &circuit, vec![vec![y, x]]).unwrap();
+    assert!(prover.verify().is_err());
+}
@@ -0,0 +1,63 @@
+//! The `fibo` binary, run as a user would.
+
+use std::{
+    fs,
+    path::PathBuf,
+    process::{Command, Output},
+    time::{Duration, Instant},
+};
+
+use fibonacci_circuit::envelope::{ProofEnvelope, ENVELOPE_VERSION};
+
+fn golden_envelope() -> ProofEnvelope {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join(format!("tests/golden/envelope-v{}.proof", ENVELOPE_VERSION));
+    ProofEnvelope::from_bytes(&fs::read(path).unwrap()).unwrap()
+}
+
+fn envelope_path(name: &str, envelope: &ProofEnvelope) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("fibo-cli-{}-{}", name, std::process::id()));
+    fs::write(&path, envelope.to_bytes()).unwrap();
+    path
+}
+
+fn fibo(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_fibo"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn verify_generates_parameters_for_a_small_k() {
+    let path = envelope_path("small", &golden_envelope());
+    let output = fibo(&["verify", "--proof", path.to_str().unwrap()]);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn verify_refuses_to_generate_parameters_for_a_large_k() {
+    let envelope = ProofEnvelope {
+        k: 30,
+        ..golden_envelope()
+    };
+    let path = envelope_path("large", &envelope);
+
+    let start = Instant::now();
+    let output = fibo(&["verify", "--proof", path.to_str().unwrap()]);
+    assert!(start.elapsed() < Duration::from_secs(30));
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("the proof claims k = 30; parameters are generated only up to k = 16"),
+        "{}",
+        stderr
+    );
+    assert!(!stderr.contains("generating parameters"), "{}", stderr);
+    fs::remove_file(path).unwrap();
+}
@@ -0,0 +1,88 @@
+use fibonacci_circuit::{
+    fibonacci::{
+        batch::FiboBatchCircuit, negative_rotation, single_column, stride::FiboStrideCircuit,
+        three_column,
+    },
+    introspect::{ColumnRef, ConstraintSystemInfo},
+};
+use halo2_proofs::{
+    pasta::Fp,
+    plonk::{Any, Circuit, Column, ConstraintSystem},
+};
+
+/// The config `C` builds, with the columns its constraint system compares.
+fn configure<C: Circuit<Fp>>() -> (C::Config, Vec<ColumnRef>) {
+    let mut meta = ConstraintSystem::default();
+    let config = C::configure(&mut meta);
+    let permutation = ConstraintSystemInfo::from_cs(&meta).permutation_columns;
+    (config, permutation)
+}
+
+fn refs(columns: Vec<Column<Any>>) -> Vec<ColumnRef> {
+    columns.into_iter().map(ColumnRef::from).collect()
+}
+
+#[test]
+fn three_column_config() {
+    let (config, permutation) = configure::<three_column::FiboCircuit<Fp>>();
+    assert_eq!(
+        config.to_string(),
+        "advice A0 A1 A2, selector S0, instance I0, equality on A0 A1 A2 I0"
+    );
+    assert_eq!(
+        format!("{:?}", config),
+        "FiboConfig { advice: [A0 A1 A2], selector: S0, instance: I0 }"
+    );
+    assert_eq!(refs(config.equality_columns()), permutation);
+
+    let chip = three_column::FiboChip::<Fp>::construct(config);
+    assert!(chip
+        .to_string()
+        .starts_with("three-column chip: advice A0 A1 A2"));
+}
+
+#[test]
+fn single_column_config() {
+    let (config, permutation) = configure::<single_column::FiboCircuit<Fp>>();
+    assert_eq!(
+        config.to_string(),
+        "advice A0, selector S0, instance I0, equality on A0 I0"
+    );
+    assert_eq!(refs(config.equality_columns()), permutation);
+}
+
+#[test]
+fn negative_rotation_config() {
+    let (config, permutation) = configure::<negative_rotation::FiboCircuit<Fp>>();
+    assert_eq!(
+        config.to_string(),
+        "advice A0, selector S0, instance I0, equality on A0 I0"
+    );
+    assert_eq!(refs(config.equality_columns()), permutation);
+
+    let chip = negative_rotation::FiboChip::<Fp>::construct(config);
+    assert_eq!(
+        chip.to_string(),
+        "negative-rotation chip: advice A0, selector S0, instance I0, equality on A0 I0"
+    );
+}
+
+#[test]
+fn stride_config() {
+    let (config, permutation) = configure::<FiboStrideCircuit<Fp, 4>>();
+    assert_eq!(
+        config.to_string(),
+        "advice A0 A1, coefficients F0 F1 F2, selector S0, instance I0, equality on A1 I0"
+    );
+    assert_eq!(refs(config.equality_columns()), permutation);
+}
+
+#[test]
+fn batch_config_lists_each_group() {
+    let (config, _) = configure::<FiboBatchCircuit<Fp, 2>>();
+    assert_eq!(
+        config.to_string(),
+        "group 0: advice A0 A1 A2, selector S0, instance I0, equality on A0 A1 A2 I0\n\
+         group 1: advice A3 A4 A5, selector S1, instance I0, equality on A3 A4 A5 I0"
+    );
+}
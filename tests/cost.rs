@@ -0,0 +1,67 @@
+use fibonacci_circuit::{
+    cost::{CostReport, RegionCopies},
+    fibonacci::{single_column, three_column, NUM_TERMS},
+    reference::fib_field,
+};
+use halo2_proofs::{circuit::Value, pasta::Fp};
+
+const K: u32 = 4;
+
+fn instances() -> Vec<Vec<Fp>> {
+    vec![vec![fib_field(Fp::one(), Fp::one(), NUM_TERMS)]]
+}
+
+#[test]
+fn three_column_copies_every_step() {
+    let circuit = three_column::FiboCircuit {
+        a: Value::known(Fp::one()),
+        b: Value::known(Fp::one()),
+    };
+    let report = CostReport::try_measure("fib-three-column", K, &circuit, instances()).unwrap();
+
+    // Two terms carried into each row after the first, and the output.
+    let steps = NUM_TERMS - 3;
+    assert_eq!(report.copies.total, 2 * steps + 1);
+    assert_eq!(report.copies.outside_regions, 1);
+    assert_eq!(
+        report.copies.regions,
+        [
+            RegionCopies {
+                name: "first row".to_string(),
+                regions: 1,
+                copies: 0,
+            },
+            RegionCopies {
+                name: "next row".to_string(),
+                regions: steps,
+                copies: 2 * steps,
+            },
+        ]
+    );
+}
+
+#[test]
+fn single_column_only_copies_the_output() {
+    let circuit = single_column::FiboCircuit {
+        a: Value::known(Fp::one()),
+        b: Value::known(Fp::one()),
+    };
+    let report = CostReport::try_measure("fib-single-column", K, &circuit, instances()).unwrap();
+
+    assert_eq!(report.copies.total, 1);
+    assert_eq!(report.copies.outside_regions, 1);
+    assert!(report
+        .copies
+        .regions
+        .iter()
+        .all(|region| region.copies == 0));
+}
+
+#[test]
+fn too_small_k_is_an_error() {
+    let circuit = three_column::FiboCircuit {
+        a: Value::known(Fp::one()),
+        b: Value::known(Fp::one()),
+    };
+    assert!(CostReport::try_measure("fib-three-column", 2, &circuit, instances()).is_err());
+}
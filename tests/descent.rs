@@ -0,0 +1,71 @@
+use fibonacci_circuit::{
+    fibonacci::descent::{steps, DescentCircuit},
+    reference::{fib_field, first_wraparound},
+};
+use halo2_proofs::{circuit::Value, dev::MockProver, pasta::Fp};
+
+const K: u32 = 9;
+
+const N: usize = 12;
+
+fn verify<const S: usize>(k: u32, x: Fp, y: Fp) -> bool {
+    let circuit = DescentCircuit::<Fp, S> {
+        x: Value::known(x),
+        y: Value::known(y),
+    };
+    let prover = MockProver::run(k, &circuit, vec![vec![x, y]]).unwrap();
+    prover.verify().is_ok()
+}
+
+fn fib(n: usize) -> Fp {
+    fib_field(Fp::one(), Fp::one(), n)
+}
+
+#[test]
+fn consecutive_terms_descend() {
+    for n in 1..=N + 1 {
+        assert!(verify::<N>(K, fib(n), fib(n + 1)), "F({}), F({})", n, n + 1);
+    }
+}
+
+#[test]
+fn counts_steps_over_the_integers() {
+    assert_eq!(steps(1, 1), Some(0));
+    assert_eq!(steps(55, 89), Some(9));
+    assert_eq!(steps(89, 55), None);
+    assert_eq!(steps(4, 6), None);
+}
+
+#[test]
+fn rejects_other_pairs() {
+    for (x, y) in [(4, 6), (89, 55), (2, 2), (0, 1)] {
+        assert!(!verify::<N>(K, Fp::from(x), Fp::from(y)), "({}, {})", x, y);
+    }
+}
+
+#[test]
+fn rejects_pairs_further_than_n_steps() {
+    assert!(!verify::<N>(K, fib(N + 2), fib(N + 3)));
+}
+
+#[test]
+fn range_checks_stop_a_wrapped_descent() {
+    // Past the first wraparound the terms are only Fibonacci modulo p, and
+    // b - a goes negative in the field on the way down.
+    const STEPS: usize = 380;
+    let n = STEPS + 1;
+    assert!(n > first_wraparound::<Fp>());
+    let (x, y) = (fib(n), fib(n + 1));
+
+    let circuit = DescentCircuit::<Fp, STEPS> {
+        x: Value::known(x),
+        y: Value::known(y),
+    };
+    let failures = MockProver::run(12, &circuit, vec![vec![x, y]])
+        .unwrap()
+        .verify()
+        .unwrap_err();
+    let report = format!("{:?}", failures);
+    assert!(report.contains("byte decomposition"), "{}", report);
+    assert!(!report.contains("\"descent\""), "{}", report);
+}
@@ -0,0 +1,40 @@
+use fibonacci_circuit::{
+    differential::{compare, differential, Verdicts},
+    soak::SoakCase,
+};
+use halo2_proofs::{pasta::EqAffine, poly::commitment::Params};
+
+const K: u32 = 4;
+
+#[test]
+fn both_paths_agree_with_the_reference() {
+    let params: Params<EqAffine> = Params::new(K);
+    let mut seen = 0;
+    let report = differential(&params, K, 17, 4, |_, comparison| {
+        assert!(comparison.agrees(), "{:?}", comparison);
+        seen += 1;
+    });
+    assert_eq!(seen, 4);
+    assert!(report.passed());
+
+    let (min, median, max) = report.ratio.unwrap();
+    assert!(0.0 < min && min <= median && median <= max);
+    assert!(report
+        .to_string()
+        .starts_with("4 cases from seed 17 at k = 4: 0 disagreed\n"));
+}
+
+#[test]
+fn both_paths_reject_a_case_that_does_not_fit() {
+    let params: Params<EqAffine> = Params::new(K);
+    let mut case = SoakCase::generate(3, K);
+    case.n = 64;
+    let comparison = compare(&params, K, &case);
+
+    let rejected = Verdicts {
+        mock: false,
+        real: false,
+    };
+    assert_eq!((comparison.right, comparison.wrong), (rejected, rejected));
+    assert!(!comparison.agrees());
+}
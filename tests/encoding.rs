@@ -0,0 +1,74 @@
+use fibonacci_circuit::encoding::{Encoding, EncodingError};
+
+/// The test vectors of RFC 4648, section 10.
+const BASE64_VECTORS: [(&str, &str); 7] = [
+    ("", ""),
+    ("f", "Zg=="),
+    ("fo", "Zm8="),
+    ("foo", "Zm9v"),
+    ("foob", "Zm9vYg=="),
+    ("fooba", "Zm9vYmE="),
+    ("foobar", "Zm9vYmFy"),
+];
+
+#[test]
+fn base64_matches_rfc_4648() {
+    for (bytes, text) in BASE64_VECTORS {
+        let encoded = Encoding::Base64.encode(bytes.as_bytes());
+        assert_eq!(encoded, format!("{}\n", text).into_bytes());
+        assert_eq!(
+            Encoding::Base64.decode(text.as_bytes()).unwrap(),
+            bytes.as_bytes()
+        );
+    }
+}
+
+#[test]
+fn every_encoding_round_trips() {
+    let bytes: Vec<u8> = (0..=255).collect();
+    for encoding in Encoding::ALL {
+        assert_eq!(encoding.decode(&encoding.encode(&bytes)).unwrap(), bytes);
+    }
+}
+
+#[test]
+fn whitespace_is_ignored() {
+    assert_eq!(
+        Encoding::Hex.decode(b" de ad\nbe\tef \n").unwrap(),
+        [0xde, 0xad, 0xbe, 0xef]
+    );
+    assert_eq!(Encoding::Base64.decode(b"Zm9v\nYmFy\n").unwrap(), b"foobar");
+}
+
+#[test]
+fn malformed_text_is_rejected() {
+    assert_eq!(
+        Encoding::Hex.decode(b"abc"),
+        Err(EncodingError::InvalidLength(3))
+    );
+    assert_eq!(
+        Encoding::Hex.decode(b"zz"),
+        Err(EncodingError::InvalidCharacter(0))
+    );
+    assert_eq!(
+        Encoding::Base64.decode(b"Zm9"),
+        Err(EncodingError::InvalidLength(3))
+    );
+    assert_eq!(
+        Encoding::Base64.decode(b"Zm!v"),
+        Err(EncodingError::InvalidCharacter(2))
+    );
+    // Padding only ends the input.
+    assert_eq!(
+        Encoding::Base64.decode(b"Zg==Zm9v"),
+        Err(EncodingError::InvalidCharacter(2))
+    );
+}
+
+#[test]
+fn names_parse() {
+    for encoding in Encoding::ALL {
+        assert_eq!(encoding.name().parse::<Encoding>(), Ok(encoding));
+    }
+    assert!("utf8".parse::<Encoding>().is_err());
+}
@@ -0,0 +1,86 @@
+//! The full pipeline for every registered circuit with the real prover:
+//! parameters through their file format, keygen, proving and verifying,
+//! and a proof that must not verify against a changed public input.
+
+use std::fmt;
+
+use fibonacci_circuit::{
+    params, prover,
+    registry::{CircuitKind, CircuitVisitor},
+};
+use halo2_proofs::{dev::MockProver, pasta::Fp, plonk::Circuit};
+
+/// Larger than any demo circuit needs.
+const MAX_K: u32 = 11;
+
+struct EndToEnd;
+
+impl CircuitVisitor for EndToEnd {
+    type Output = ();
+
+    fn visit<C: Circuit<Fp> + fmt::Debug>(self, circuit: C, instances: Vec<Vec<Fp>>) {
+        let k = (1..=MAX_K)
+            .find(|&k| MockProver::run(k, &circuit, instances.clone()).is_ok())
+            .expect("the circuit fits in MAX_K");
+        let params = params::from_bytes(&params::to_bytes(&params::generate(k).unwrap())).unwrap();
+
+        let pk = prover::keygen(&params, &circuit).unwrap();
+        let vk = prover::keygen_vk(&params, &circuit).unwrap();
+        let proof = prover::create_proof(&params, &pk, circuit, &instances).unwrap();
+        prover::verify_proof(&params, &vk, &proof, &instances).unwrap();
+
+        let mut wrong = instances;
+        wrong[0][0] += Fp::one();
+        assert!(prover::verify_proof(&params, &vk, &proof, &wrong).is_err());
+    }
+}
+
+macro_rules! end_to_end {
+    ($($test:ident: $kind:ident,)*) => {
+        $(
+            #[test]
+            fn $test() {
+                CircuitKind::$kind.visit(EndToEnd);
+            }
+        )*
+
+        /// Keeps the tests above in step with the registry.
+        #[test]
+        fn every_circuit_is_covered() {
+            let covered = [$(CircuitKind::$kind),*];
+            assert_eq!(covered, CircuitKind::ALL);
+        }
+    };
+}
+
+end_to_end! {
+    fib_three_column: FibThreeColumn,
+    fib_single_column: FibSingleColumn,
+    fib_batch: FibBatch,
+    fib_chunk: FibChunk,
+    fib_stride: FibStride,
+    fib_checkpoint: FibCheckpoint,
+    fib_descent: FibDescent,
+    fib_parity: FibParity,
+    fib_last_digit: FibLastDigit,
+    fib_bounded: FibBounded,
+    fib_fixed_selector: FibFixedSelector,
+    fib_table: FibTable,
+    fib_square_test: FibSquareTest,
+    fib_non_member: FibNonMember,
+    fib_vertical: FibVertical,
+    fib_negative_rotation: FibNegativeRotation,
+    fib_versioned: FibVersioned,
+    padovan: Padovan,
+    catalan: Catalan,
+    hash_chain: HashChain,
+    histogram: Histogram,
+    horner: Horner,
+    inner_product: InnerProduct,
+    matmul: MatMul,
+    memory: Memory,
+    permutation: Permutation,
+    sorting_network: SortingNetwork,
+    sparse_merkle: SparseMerkle,
+    vm: Vm,
+}
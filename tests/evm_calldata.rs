@@ -0,0 +1,36 @@
+#![cfg(feature = "evm")]
+
+use fibonacci_circuit::evm::{decode_calldata, encode_calldata, CalldataError};
+use halo2_proofs::pasta::Fp;
+
+#[test]
+fn round_trip() {
+    let instances = vec![
+        vec![Fp::from(1), Fp::from(1), Fp::from(55)],
+        vec![-Fp::one()],
+    ];
+    let proof = vec![7u8; 100];
+
+    let calldata = encode_calldata(&instances, &proof);
+    assert_eq!(calldata.len(), 4 * 32 + 100);
+    // Big-endian words: 55 is the last byte of the third word.
+    assert_eq!(calldata[3 * 32 - 1], 55);
+    assert!(calldata[2 * 32..3 * 32 - 1].iter().all(|b| *b == 0));
+
+    assert_eq!(decode_calldata(&calldata, &[3, 1]), Ok((instances, proof)));
+}
+
+#[test]
+fn rejects_bad_calldata() {
+    assert_eq!(
+        decode_calldata(&[0; 40], &[2]),
+        Err(CalldataError::Truncated {
+            expected: 64,
+            actual: 40
+        })
+    );
+    assert_eq!(
+        decode_calldata(&[0xff; 32], &[1]),
+        Err(CalldataError::NonCanonicalInstance { column: 0, row: 0 })
+    );
+}
@@ -0,0 +1,121 @@
+use clap::Parser;
+use fibonacci_circuit::{
+    example::{ExampleArgs, ExampleError, MAX_EXAMPLE_K},
+    fibonacci::{single_column, three_column, NUM_TERMS},
+};
+use halo2_proofs::pasta::Fp;
+
+fn args(flags: &[&str]) -> ExampleArgs {
+    ExampleArgs::try_parse_from(std::iter::once("example").chain(flags.iter().copied())).unwrap()
+}
+
+fn three_column(args: &ExampleArgs) -> Result<(), ExampleError> {
+    let (a, b) = args.seeds();
+    args.check(&three_column::FiboTermsCircuit { a, b, n: args.n })
+}
+
+fn single_column(args: &ExampleArgs) -> Result<(), ExampleError> {
+    let (a, b) = args.seeds();
+    args.check(&single_column::FiboTermsCircuit { a, b, n: args.n })
+}
+
+#[test]
+fn defaults_are_the_fixed_demo() {
+    let args = args(&[]);
+    assert_eq!(
+        (args.a, args.b, args.n, args.k),
+        (Fp::one(), Fp::one(), NUM_TERMS, 4)
+    );
+    assert_eq!(args.public_input(), Fp::from(55));
+    three_column(&args).unwrap();
+    single_column(&args).unwrap();
+}
+
+#[test]
+fn flags_set_the_seeds_and_length() {
+    let args = args(&["--a", "2", "--b", "3", "--n", "20", "--k", "5"]);
+    assert_eq!(args.expected(), Fp::from(17711));
+    three_column(&args).unwrap();
+    single_column(&args).unwrap();
+    assert_eq!(
+        args.summary(),
+        "20 terms from 2, 3 at k = 5: term 20 is 17711"
+    );
+}
+
+#[test]
+fn right_public_input_is_accepted() {
+    let args = args(&["--n", "12", "--k", "5", "--public", "144"]);
+    three_column(&args).unwrap();
+    single_column(&args).unwrap();
+}
+
+#[test]
+fn hex_flags_are_accepted() {
+    let args = args(&[
+        "--a", "0x2", "--b", "0X03", "--n", "20", "--k", "5", "--public", "0x452f",
+    ]);
+    three_column(&args).unwrap();
+    assert_eq!(
+        args.summary(),
+        "20 terms from 2, 3 at k = 5: term 20 is 17711"
+    );
+}
+
+#[test]
+fn wrong_public_input_names_the_term() {
+    let args = args(&["--public", "54"]);
+    let err = three_column(&args).unwrap_err();
+    assert!(matches!(err, ExampleError::WrongPublic { n: 10, .. }));
+    assert_eq!(
+        err.to_string(),
+        "--public 54 is not term 10 of the sequence, which is 55"
+    );
+}
+
+#[test]
+fn short_table_is_rejected() {
+    let err = three_column(&args(&["--n", "2"])).unwrap_err();
+    assert!(matches!(err, ExampleError::TooFewTerms(2)));
+}
+
+#[test]
+fn large_k_is_rejected() {
+    let k = (MAX_EXAMPLE_K + 1).to_string();
+    let err = single_column(&args(&["--k", &k])).unwrap_err();
+    assert!(matches!(err, ExampleError::InvalidK(k) if k == MAX_EXAMPLE_K + 1));
+}
+
+#[test]
+fn table_too_long_for_k_suggests_one() {
+    let err = three_column(&args(&["--n", "40", "--k", "5"])).unwrap_err();
+    assert!(matches!(
+        err,
+        ExampleError::TooSmall {
+            n: 40,
+            k: 5,
+            needed: Some(6)
+        }
+    ));
+    assert_eq!(
+        err.to_string(),
+        "40 terms do not fit in 2^5 rows; try --k 6"
+    );
+}
+
+#[test]
+fn bad_flags_fail_to_parse() {
+    let modulus = "0x40000000000000000000000000000000224698fc094cf91b992d30ed00000001";
+    for flags in [
+        &["--a", "x"][..],
+        &["--n", "-1"],
+        &["--b", "-1"],
+        &["--public", "0x1g"],
+        &["--public", modulus],
+    ] {
+        assert!(ExampleArgs::try_parse_from(
+            std::iter::once("example").chain(flags.iter().copied())
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,96 @@
+use fibonacci_circuit::{
+    explain::Explanation,
+    fibonacci::{nth_term, three_column::FiboCircuit, NUM_TERMS},
+    introspect::witness::Witness,
+};
+use halo2_proofs::{
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    pasta::Fp,
+    plonk::{Advice, Circuit, Column, ConstraintSystem, Error, Selector},
+    poly::Rotation,
+};
+
+/// `a + b = c` on one row, witnessed with a wrong `c`.
+#[derive(Default)]
+struct WrongSum;
+
+impl Circuit<Fp> for WrongSum {
+    type Config = ([Column<Advice>; 3], Selector);
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        WrongSum
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+        let advice = [(); 3].map(|_| meta.advice_column());
+        let s = meta.selector();
+        meta.create_gate("sum", |meta| {
+            let [a, b, c] = advice.map(|column| meta.query_advice(column, Rotation::cur()));
+            vec![meta.query_selector(s) * (a + b - c)]
+        });
+        (advice, s)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<Fp>,
+    ) -> Result<(), Error> {
+        let (advice, s) = config;
+        layouter.assign_region(
+            || "sum",
+            |mut region| {
+                s.enable(&mut region, 0)?;
+                for (column, value) in advice.iter().zip([1, 1, 3]) {
+                    region.assign_advice(|| "x", *column, 0, || Value::known(Fp::from(value)))?;
+                }
+                Ok(())
+            },
+        )
+    }
+}
+
+#[test]
+fn substitutes_the_witness() {
+    let circuit = FiboCircuit {
+        a: Value::known(Fp::one()),
+        b: Value::known(Fp::one()),
+    };
+    let output = nth_term(Fp::one(), Fp::one(), NUM_TERMS);
+    let witness = Witness::synthesize(4, &circuit, vec![vec![output]]).unwrap();
+    let explanation = Explanation::of("fib-three-column", &witness, 2..3, None);
+
+    assert!(explanation.satisfied());
+    assert_eq!(explanation.constraints.len(), 1);
+    let constraint = &explanation.constraints[0];
+    assert_eq!(constraint.expression, "S0 * (A0@0 + A1@0 - A2@0)");
+    assert_eq!(constraint.substituted, "1 * (2 + 3 - 5)");
+    assert_eq!(constraint.value, "0");
+}
+
+#[test]
+fn inactive_rows_are_skipped() {
+    let witness = Witness::synthesize(4, &WrongSum, vec![]).unwrap();
+    let explanation = Explanation::of("wrong-sum", &witness, 1..8, None);
+
+    assert!(explanation.constraints.is_empty());
+}
+
+#[test]
+fn failing_constraint_is_reported() {
+    let witness = Witness::synthesize(4, &WrongSum, vec![]).unwrap();
+    let explanation = Explanation::of("wrong-sum", &witness, 0..1, None);
+
+    assert!(!explanation.satisfied());
+    assert_eq!(explanation.constraints[0].substituted, "1 * (1 + 1 - 3)");
+    assert_eq!(explanation.constraints[0].value, "-1");
+}
+
+#[test]
+fn too_small_k_is_an_error() {
+    assert!(matches!(
+        Witness::synthesize(2, &WrongSum, vec![]),
+        Err(Error::NotEnoughRowsAvailable { current_k: 2 })
+    ));
+}
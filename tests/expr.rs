@@ -0,0 +1,41 @@
+use fibonacci_circuit::expr;
+use halo2_proofs::{arithmetic::FieldExt, pasta::Fp, plonk::Expression};
+
+/// The value of an expression with no queries in it.
+fn value(e: &Expression<Fp>) -> Fp {
+    e.evaluate(
+        &|c| c,
+        &|_| panic!("selector"),
+        &|_, _, _| panic!("fixed query"),
+        &|_, _, _| panic!("advice query"),
+        &|_, _, _| panic!("instance query"),
+        &|a| -a,
+        &|a, b| a + b,
+        &|a, b| a * b,
+        &|a, f| a * f,
+    )
+}
+
+#[test]
+fn empty_sum_and_product_are_the_identities() {
+    assert_eq!(value(&expr::sum::<Fp>([])), Fp::zero());
+    assert_eq!(value(&expr::product::<Fp>([])), Fp::one());
+}
+
+#[test]
+fn sum_product_and_compose_evaluate_as_integers() {
+    let terms = || [2, 3, 7].map(expr::constant::<Fp>);
+    assert_eq!(value(&expr::sum(terms())), Fp::from(12));
+    assert_eq!(value(&expr::product(terms())), Fp::from(42));
+    // 2 + 3·10 + 7·100
+    assert_eq!(value(&expr::compose(terms(), Fp::from(10))), Fp::from(732));
+    assert_eq!(value(&expr::constant_u128(1 << 64)), Fp::from_u128(1 << 64));
+}
+
+#[test]
+fn bool_check_vanishes_on_bits_only() {
+    for (x, vanishes) in [(0, true), (1, true), (2, false)] {
+        let check = value(&expr::bool_check(expr::constant::<Fp>(x)));
+        assert_eq!(check == Fp::zero(), vanishes, "x = {}", x);
+    }
+}
@@ -0,0 +1,58 @@
+use fibonacci_circuit::{
+    field::{self, ParseFieldError},
+    reference::modulus,
+};
+use halo2_proofs::pasta::{Fp, Fq};
+
+#[test]
+fn decimal_and_hex_agree() {
+    for (decimal, hex) in [("0", "0x0"), ("255", "0xff"), ("65536", "0X00010000")] {
+        assert_eq!(field::parse::<Fp>(decimal), field::parse::<Fp>(hex));
+    }
+    assert_eq!(field::parse::<Fp>("0xFf"), Ok(Fp::from(255)));
+    assert_eq!(field::parse::<Fp>("007"), Ok(Fp::from(7)));
+}
+
+#[test]
+fn values_beyond_u64_are_reached() {
+    let x = field::parse::<Fp>("0x10000000000000000").unwrap();
+    assert_eq!(x, Fp::from(u64::MAX) + Fp::one());
+    let p_minus_one = (modulus::<Fp>() - 1u8).to_string();
+    assert_eq!(field::parse::<Fp>(&p_minus_one), Ok(-Fp::one()));
+}
+
+#[test]
+fn the_modulus_is_rejected_in_each_field() {
+    let p = modulus::<Fp>();
+    assert_eq!(
+        field::parse::<Fp>(&p.to_string()),
+        Err(ParseFieldError::OutOfRange {
+            value: p.clone(),
+            modulus: p.clone(),
+            hex: false
+        })
+    );
+    // Fq is larger than Fp, so p is canonical there.
+    assert!(field::parse::<Fq>(&p.to_string()).is_ok());
+    let q = modulus::<Fq>();
+    assert!(field::parse::<Fq>(&format!("{:#x}", q)).is_err());
+}
+
+#[test]
+fn errors_say_what_is_wrong() {
+    let message = |s: &str| field::parse::<Fp>(s).unwrap_err().to_string();
+    assert_eq!(message(""), "no digits");
+    assert_eq!(message("0x"), "no digits");
+    assert_eq!(
+        message("-3"),
+        "field elements have no sign; write p - x for -x"
+    );
+    assert_eq!(message("12a"), "'a' at position 2 is not a decimal digit");
+    assert_eq!(message("0x1g"), "'g' at position 3 is not a hex digit");
+    assert_eq!(message(" 1"), "' ' at position 0 is not a decimal digit");
+    assert_eq!(
+        message("0x40000000000000000000000000000000224698fc094cf91b992d30ed00000001"),
+        "0x40000000000000000000000000000000224698fc094cf91b992d30ed00000001 is not below \
+         the modulus 0x40000000000000000000000000000000224698fc094cf91b992d30ed00000001"
+    );
+}
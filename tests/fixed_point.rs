@@ -0,0 +1,300 @@
+use fibonacci_circuit::gadgets::{
+    bytes::{ByteTable, BytesChip},
+    fixed_point::{FixedPointChip, FixedPointConfig, ONE, SCALE_BITS},
+};
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    dev::MockProver,
+    pasta::Fp,
+    plonk::{Circuit, Column, ConstraintSystem, Error, Instance},
+};
+
+#[derive(Debug, Clone)]
+struct Config {
+    fixed_point: FixedPointConfig,
+    instance: Column<Instance>,
+}
+
+fn configure(meta: &mut ConstraintSystem<Fp>) -> Config {
+    let table = ByteTable::configure(meta);
+    let (z, byte) = (meta.advice_column(), meta.advice_column());
+    let bytes = BytesChip::configure(meta, z, byte, table);
+    let advice = [(); 4].map(|_| meta.advice_column());
+    let constant = meta.fixed_column();
+    let instance = meta.instance_column();
+    meta.enable_equality(instance);
+    Config {
+        fixed_point: FixedPointChip::configure(meta, advice, constant, bytes),
+        instance,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Mul,
+    Div,
+}
+
+/// Applies `op` to the raw values `x` and `y`, exposing the raw result at
+/// instance row 0.
+#[derive(Debug)]
+struct OpCircuit {
+    op: Op,
+    x: u64,
+    y: u64,
+}
+
+impl Circuit<Fp> for OpCircuit {
+    type Config = Config;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            op: self.op,
+            x: 0,
+            y: 0,
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>) -> Config {
+        configure(meta)
+    }
+
+    fn synthesize(&self, config: Config, mut layouter: impl Layouter<Fp>) -> Result<(), Error> {
+        let fixed_point = config.fixed_point;
+        fixed_point
+            .bytes
+            .table
+            .load(layouter.namespace(|| "byte table"))?;
+        let chip = FixedPointChip::construct(fixed_point);
+        let x = chip.load(layouter.namespace(|| "x"), Value::known(self.x))?;
+        let y = chip.load(layouter.namespace(|| "y"), Value::known(self.y))?;
+        let z = match self.op {
+            Op::Mul => chip.mul(layouter.namespace(|| "mul"), &x, &y)?,
+            Op::Div => chip.div(layouter.namespace(|| "div"), &x, &y)?,
+        };
+        layouter.constrain_instance(z.cell().cell(), config.instance, 0)
+    }
+}
+
+/// Lays out an operation row with an arbitrary result and remainder,
+/// range-checking them, and for division comparing the remainder with `y`,
+/// like the chip does.
+#[derive(Debug, Default, Clone, Copy)]
+struct ForgedCircuit {
+    div: bool,
+    x: Fp,
+    y: Fp,
+    z: Fp,
+    r: Fp,
+}
+
+impl ForgedCircuit {
+    /// The row the chip would lay out.
+    fn honest(op: Op, x: u64, y: u64) -> Self {
+        let (z, r) = match op {
+            Op::Mul => {
+                let product = x as u128 * y as u128 + (ONE / 2) as u128;
+                (product >> SCALE_BITS, product % ONE as u128)
+            }
+            Op::Div => {
+                let scaled = (x as u128) << SCALE_BITS;
+                (scaled / y as u128, scaled % y as u128)
+            }
+        };
+        ForgedCircuit {
+            div: op == Op::Div,
+            x: Fp::from(x),
+            y: Fp::from(y),
+            z: Fp::from_u128(z),
+            r: Fp::from_u128(r),
+        }
+    }
+}
+
+impl Circuit<Fp> for ForgedCircuit {
+    type Config = Config;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            div: self.div,
+            ..Self::default()
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>) -> Config {
+        configure(meta)
+    }
+
+    fn synthesize(&self, config: Config, mut layouter: impl Layouter<Fp>) -> Result<(), Error> {
+        let cfg = config.fixed_point;
+        cfg.bytes.table.load(layouter.namespace(|| "byte table"))?;
+        let (z, r, less) = layouter.assign_region(
+            || "forged operation",
+            |mut region| {
+                let selector = if self.div { cfg.s_div } else { cfg.s_mul };
+                selector.enable(&mut region, 0)?;
+                let mut assign = |column, row, value| {
+                    region.assign_advice(|| "forged", column, row, || Value::known(value))
+                };
+                assign(cfg.a, 0, self.x)?;
+                let y = assign(cfg.b, 0, self.y)?;
+                let z = assign(cfg.c, 0, self.z)?;
+                let r = assign(cfg.r, 0, self.r)?;
+                if !self.div {
+                    return Ok((z, r, None));
+                }
+                cfg.s_less.enable(&mut region, 1)?;
+                let r_copy = r.copy_advice(|| "r", &mut region, cfg.a, 1)?;
+                y.copy_advice(|| "y", &mut region, cfg.b, 1)?;
+                let difference = self.y - self.r - Fp::one();
+                let less =
+                    region.assign_advice(|| "y - r - 1", cfg.c, 1, || Value::known(difference))?;
+                region.constrain_equal(r.cell(), r_copy.cell())?;
+                Ok((z, r, Some(less)))
+            },
+        )?;
+        let bytes = BytesChip::construct(cfg.bytes);
+        bytes.decompose(layouter.namespace(|| "result range"), &z, 8)?;
+        let remainder_bytes = if self.div { 8 } else { 4 };
+        bytes.decompose(
+            layouter.namespace(|| "remainder range"),
+            &r,
+            remainder_bytes,
+        )?;
+        if let Some(less) = less {
+            bytes.decompose(layouter.namespace(|| "less range"), &less, 8)?;
+        }
+        Ok(())
+    }
+}
+
+const K: u32 = 9;
+
+fn run(op: Op, x: u64, y: u64, z: u64) -> MockProver<Fp> {
+    let circuit = OpCircuit { op, x, y };
+    MockProver::run(K, &circuit, vec![vec![Fp::from(z)]]).unwrap()
+}
+
+fn accepts(circuit: &ForgedCircuit) -> bool {
+    let prover = MockProver::run(K, circuit, vec![vec![]]).unwrap();
+    prover.verify().is_ok()
+}
+
+#[test]
+fn mul_rounds_to_nearest() {
+    let cases = [
+        // 1.5 · 2 = 3
+        (ONE + ONE / 2, 2 * ONE, 3 * ONE),
+        // 2^-32 · 2^-32 rounds down to 0.
+        (1, 1, 0),
+        // 2^-32 · 0.5 = 2^-33, a tie, rounds up.
+        (1, ONE / 2, 1),
+        // 2^-32 · (0.5 - 2^-32) is just below the tie.
+        (1, ONE / 2 - 1, 0),
+        // 3·2^-32 · 0.5 = 1.5·2^-32 rounds up to 2·2^-32.
+        (3, ONE / 2, 2),
+    ];
+    for (x, y, z) in cases {
+        assert_eq!(run(Op::Mul, x, y, z).verify(), Ok(()), "{} · {}", x, y);
+        assert!(
+            run(Op::Mul, x, y, z + 1).verify().is_err(),
+            "{} · {} rounded up",
+            x,
+            y
+        );
+        if z > 0 {
+            assert!(
+                run(Op::Mul, x, y, z - 1).verify().is_err(),
+                "{} · {} rounded down",
+                x,
+                y
+            );
+        }
+    }
+}
+
+#[test]
+fn div_rounds_down() {
+    let cases = [
+        // 3 / 2 = 1.5
+        (3 * ONE, 2 * ONE, ONE + ONE / 2),
+        // 1 / 3 = 0.0101…₂, truncated to 32 bits.
+        (ONE, 3 * ONE, (ONE - 1) / 3),
+        // 2 / 3 truncates where rounding to nearest would not.
+        (2 * ONE, 3 * ONE, 2 * (ONE - 1) / 3),
+        // 2^-32 / 2 = 2^-33 rounds down to 0.
+        (1, 2 * ONE, 0),
+    ];
+    for (x, y, z) in cases {
+        assert_eq!(run(Op::Div, x, y, z).verify(), Ok(()), "{} / {}", x, y);
+        assert!(
+            run(Op::Div, x, y, z + 1).verify().is_err(),
+            "{} / {} rounded up",
+            x,
+            y
+        );
+    }
+}
+
+#[test]
+fn division_by_zero_is_unsatisfiable() {
+    assert!(run(Op::Div, ONE, 0, 0).verify().is_err());
+}
+
+#[test]
+fn out_of_range_remainders_are_rejected() {
+    // 2^-32 · 1.5 = 1.5·2^-32, so z = 2, r = 0.
+    let mul = ForgedCircuit::honest(Op::Mul, 1, ONE + ONE / 2);
+    assert!(accepts(&mul));
+    // One less in the result and 2^32 more in the remainder still satisfies
+    // the gate, but the remainder no longer fits in 32 bits.
+    let forged = ForgedCircuit {
+        z: mul.z - Fp::one(),
+        r: mul.r + Fp::from(ONE),
+        ..mul
+    };
+    assert!(!accepts(&forged), "remainder of 2^32 in mul");
+
+    // 1 / 3: the remainder has to stay below y.
+    let div = ForgedCircuit::honest(Op::Div, ONE, 3 * ONE);
+    assert!(accepts(&div));
+    let forged = ForgedCircuit {
+        z: div.z - Fp::one(),
+        r: div.r + div.y,
+        ..div
+    };
+    assert!(!accepts(&forged), "remainder of y or more in div");
+}
+
+#[test]
+fn forged_results_are_rejected() {
+    for (op, x, y) in [(Op::Mul, 3 * ONE, ONE / 3), (Op::Div, 7 * ONE, 3 * ONE)] {
+        let honest = ForgedCircuit::honest(op, x, y);
+        assert!(accepts(&honest), "{:?}", op);
+
+        let forgeries = [
+            // A result that is off by one.
+            ForgedCircuit {
+                z: honest.z + Fp::one(),
+                ..honest
+            },
+            // One more in the result, balanced by a negative remainder.
+            ForgedCircuit {
+                z: honest.z + Fp::one(),
+                r: honest.r
+                    - if op == Op::Mul {
+                        Fp::from(ONE)
+                    } else {
+                        honest.y
+                    },
+                ..honest
+            },
+        ];
+        for circuit in forgeries {
+            assert!(!accepts(&circuit), "accepted {:?}", circuit);
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use fibonacci_circuit::{
+    cost::CostReport,
+    fibonacci::{fixed_selector::FiboFixedCircuit, three_column::FiboCircuit, NUM_TERMS},
+    fingerprint::pinned,
+    prover,
+    reference::fib_field,
+};
+use halo2_proofs::{circuit::Value, pasta::Fp, poly::commitment::Params};
+
+const K: u32 = 4;
+
+fn instances() -> Vec<Vec<Fp>> {
+    vec![vec![fib_field(Fp::one(), Fp::one(), NUM_TERMS)]]
+}
+
+/// Compression turns the one selector into a fixed column holding what
+/// `q` holds, so the keys differ only in the selector count they pin.
+#[test]
+fn keygen_compiles_the_selector_to_the_same_column() {
+    let params = Params::new(K);
+    let selector = pinned(&prover::keygen_vk(&params, &FiboCircuit::<Fp>::default()).unwrap());
+    let fixed = pinned(&prover::keygen_vk(&params, &FiboFixedCircuit::<Fp>::default()).unwrap());
+    assert_ne!(selector, fixed);
+    assert_eq!(
+        selector.replace("num_selectors: 1", "num_selectors: 0"),
+        fixed
+    );
+}
+
+#[test]
+fn costs_the_same_as_the_selector() {
+    let (a, b) = (Value::known(Fp::one()), Value::known(Fp::one()));
+    let selector = CostReport::try_measure("fib", K, &FiboCircuit { a, b }, instances()).unwrap();
+    let fixed = CostReport::try_measure("fib", K, &FiboFixedCircuit { a, b }, instances()).unwrap();
+    assert_eq!(selector, fixed);
+}
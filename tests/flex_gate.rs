@@ -0,0 +1,79 @@
+use fibonacci_circuit::{
+    fibonacci::vertical,
+    gadgets::flex_gate::{FlexGateChip, FlexGateConfig, GateBuilder, QuantumCell},
+};
+use halo2_proofs::{
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    dev::MockProver,
+    pasta::Fp,
+    plonk::{Circuit, Column, ConstraintSystem, Error, Instance},
+};
+
+/// `3x² + 2`, from a witnessed `x`.
+#[derive(Default)]
+struct Polynomial {
+    x: Value<Fp>,
+}
+
+impl Circuit<Fp> for Polynomial {
+    type Config = (FlexGateConfig, Column<Instance>);
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+        let advice = meta.advice_column();
+        let constants = meta.fixed_column();
+        let instance = meta.instance_column();
+        meta.enable_equality(instance);
+        (FlexGateChip::configure(meta, advice, constants), instance)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<Fp>,
+    ) -> Result<(), Error> {
+        let (gate, instance) = config;
+        let mut builder = GateBuilder::new();
+        let x = builder.push(QuantumCell::Witness(self.x));
+        let square = builder.mul(QuantumCell::Offset(x), QuantumCell::Offset(x));
+        let y = builder.mul_add(
+            QuantumCell::Constant(Fp::from(2)),
+            QuantumCell::Constant(Fp::from(3)),
+            QuantumCell::Offset(square),
+        );
+        let cells =
+            FlexGateChip::construct(gate).assign(layouter.namespace(|| "poly"), &builder)?;
+        layouter.constrain_instance(cells[y].cell(), instance, 0)
+    }
+}
+
+#[test]
+fn chained_additions_take_three_rows_each() {
+    let mut builder = GateBuilder::new();
+    let last = vertical::fibonacci(
+        &mut builder,
+        Value::known(Fp::one()),
+        Value::known(Fp::one()),
+        10,
+    );
+    assert_eq!((last, builder.len()), (24, 25));
+    builder
+        .value(last)
+        .assert_if_known(|value| *value == Fp::from(55));
+}
+
+#[test]
+fn multiplies_and_adds_constants() {
+    let circuit = Polynomial {
+        x: Value::known(Fp::from(5)),
+    };
+    let prover = MockProver::run(4, &circuit, vec![vec![Fp::from(77)]]).unwrap();
+    assert_eq!(prover.verify(), Ok(()));
+
+    let prover = MockProver::run(4, &circuit, vec![vec![Fp::from(78)]]).unwrap();
+    assert!(prover.verify().is_err());
+}
@@ -0,0 +1,93 @@
+use fibonacci_circuit::folding::{self, FoldedStepCircuit, StepInstance};
+use halo2_proofs::{circuit::Value, dev::MockProver, pasta::Fp};
+
+const K: u32 = 4;
+
+/// Whether `FoldedStepCircuit` accepts `accumulator`.
+fn proves(accumulator: StepInstance) -> bool {
+    let circuit = FoldedStepCircuit {
+        accumulator: Value::known(accumulator),
+    };
+    let instances = folding::public_inputs(&accumulator);
+    let prover = MockProver::run(K, &circuit, instances).unwrap();
+    prover.verify().is_ok()
+}
+
+/// The step from `(x0, x1)` with its second output off by one.
+fn broken_step(x0: Fp, x1: Fp) -> StepInstance {
+    let mut step = StepInstance::step(x0, x1);
+    step.z[3] += Fp::one();
+    step
+}
+
+#[test]
+fn folding_satisfied_steps_leaves_no_residual() {
+    let a = StepInstance::step(Fp::one(), Fp::one());
+    let b = StepInstance::step(Fp::one(), Fp::from(2));
+    assert!(a.is_satisfied() && b.is_satisfied());
+    assert_eq!(folding::cross_term(&a, &b), [Fp::zero(); 2]);
+
+    let folded = folding::fold(&a, &b);
+    assert_eq!(folded.residual(), [Fp::zero(); 2]);
+    assert_eq!(folded.e, [Fp::zero(); 2]);
+    assert_ne!(folded.u, Fp::one());
+    assert!(proves(folded));
+}
+
+#[test]
+fn a_broken_step_leaves_a_residual() {
+    let good = StepInstance::step(Fp::one(), Fp::one());
+    let bad = broken_step(Fp::one(), Fp::from(2));
+    assert_eq!(bad.residual(), [Fp::zero(), -Fp::one()]);
+
+    // Either side of the fold.
+    for folded in [folding::fold(&good, &bad), folding::fold(&bad, &good)] {
+        assert_ne!(folded.residual(), [Fp::zero(); 2]);
+        assert!(!proves(folded));
+    }
+
+    // And it survives folding in more satisfied steps.
+    let mut acc = folding::fold(&good, &bad);
+    for step in folding::trace(Fp::from(2), Fp::from(3), 4) {
+        acc = folding::fold(&acc, &step);
+        assert!(!acc.is_satisfied());
+    }
+    assert!(!proves(acc));
+}
+
+#[test]
+fn fold_trace_folds_a_whole_trace() {
+    let steps = folding::trace(Fp::one(), Fp::one(), 10);
+    let acc = folding::fold_trace(&steps).unwrap();
+    assert!(acc.is_satisfied());
+    assert!(proves(acc));
+
+    let mut broken = steps;
+    let (x0, x1) = broken[6].input();
+    broken[6] = broken_step(x0, x1);
+    // Keep the trace chained, so the break is caught by the fold alone.
+    for i in 7..broken.len() {
+        let (y0, y1) = broken[i - 1].output();
+        broken[i] = StepInstance::step(y0, y1);
+    }
+    let acc = folding::fold_trace(&broken).unwrap();
+    assert!(!acc.is_satisfied());
+    assert!(!proves(acc));
+}
+
+#[test]
+fn fold_trace_rejects_unchained_steps() {
+    let mut steps = folding::trace(Fp::one(), Fp::one(), 5);
+    // Every step is satisfied on its own, but the third does not start
+    // where the second ended.
+    steps[2] = StepInstance::step(Fp::from(100), Fp::from(200));
+    assert!(steps.iter().all(StepInstance::is_satisfied));
+    assert_eq!(folding::fold_trace(&steps), None);
+
+    // Nor does swapping two steps chain.
+    let mut swapped = folding::trace(Fp::one(), Fp::one(), 5);
+    swapped.swap(1, 3);
+    assert_eq!(folding::fold_trace(&swapped), None);
+
+    assert_eq!(folding::fold_trace(&[]), None);
+}
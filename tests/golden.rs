@@ -0,0 +1,160 @@
+//! Artifacts checked in under `tests/golden/` must still read and verify:
+//! the format 1 envelope and bundle written by version 0.1.0, before
+//! envelopes recorded `n`, and the format 2 ones. Files of the current
+//! format must also re-encode to the same bytes.
+//!
+//! A failure here means a serialization format or the demo circuit
+//! changed. If that is deliberate, bump [`ENVELOPE_VERSION`] or
+//! [`BUNDLE_VERSION`] when old files can no longer be read, keep the old
+//! files, and write new ones for the new version:
+//!
+//! ```sh
+//! cargo run --bin fibo -- params gen --k 4 --output tests/golden/params-k4.bin
+//! cargo run --bin fibo -- prove fib-three-column --k 4 --params tests/golden/params-k4.bin --output tests/golden/envelope-v2.proof
+//! cargo run --bin fibo -- prove fib-three-column --k 4 --params tests/golden/params-k4.bin --bundle --output tests/golden/bundle-v2.fibb
+//! ```
+//!
+//! The verifying keys of both Fibonacci layouts are pinned by fingerprint
+//! in [`VK_FINGERPRINTS`]; update the digests there when a circuit changes
+//! on purpose.
+
+use std::{fmt, fs, path::PathBuf};
+
+use fibonacci_circuit::{
+    bundle::{ProofBundle, BUNDLE_VERSION, OLDEST_BUNDLE_VERSION},
+    envelope::{ProofEnvelope, ENVELOPE_VERSION, OLDEST_ENVELOPE_VERSION},
+    fingerprint::{self, fingerprint},
+    params, prover,
+    registry::{CircuitKind, CircuitVisitor},
+};
+use halo2_proofs::{
+    pasta::{EqAffine, Fp},
+    plonk::{Circuit, VerifyingKey},
+    poly::commitment::Params,
+};
+
+const K: u32 = 4;
+
+/// Fingerprints of the verifying keys at `K`.
+const VK_FINGERPRINTS: [(CircuitKind, &str); 2] = [
+    (
+        CircuitKind::FibThreeColumn,
+        "a58b4c3dbcea0e0ffe16fd81332ab9fe9711bb18381f2337fe907186987949c2",
+    ),
+    (
+        CircuitKind::FibSingleColumn,
+        "9b6171932ea9534691fd6dfa936f0939bbb78d0554f77bb1849838e1c05c8d55",
+    ),
+];
+
+fn golden(name: &str) -> Vec<u8> {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(name);
+    fs::read(&path).unwrap_or_else(|e| panic!("{}: {}", path.display(), e))
+}
+
+fn golden_params() -> Params<EqAffine> {
+    params::from_bytes(&golden(&format!("params-k{}.bin", K))).unwrap()
+}
+
+/// The verifying key of the circuit an envelope names, from today's code.
+struct Keygen<'a>(&'a Params<EqAffine>);
+
+impl CircuitVisitor for Keygen<'_> {
+    type Output = VerifyingKey<EqAffine>;
+
+    fn visit<C: Circuit<Fp> + fmt::Debug>(self, circuit: C, _: Vec<Vec<Fp>>) -> Self::Output {
+        prover::keygen_vk(self.0, &circuit).unwrap()
+    }
+}
+
+fn verify(envelope: &ProofEnvelope, params: &Params<EqAffine>) -> VerifyingKey<EqAffine> {
+    let kind: CircuitKind = envelope.circuit.parse().unwrap();
+    let vk = kind.visit(Keygen(params));
+    assert_eq!(
+        fingerprint::to_hex(&fingerprint(&vk)),
+        fingerprint::to_hex(&envelope.fingerprint),
+        "the verifying key of {} changed",
+        envelope.circuit
+    );
+    prover::verify_proof(params, &vk, &envelope.proof, &envelope.instances).unwrap();
+    vk
+}
+
+#[test]
+fn params_are_unchanged() {
+    let bytes = golden(&format!("params-k{}.bin", K));
+    assert_eq!(params::to_bytes(&params::generate(K).unwrap()), bytes);
+    assert_eq!(
+        params::to_bytes(&params::from_bytes(&bytes).unwrap()),
+        bytes
+    );
+}
+
+#[test]
+fn envelopes_still_verify() {
+    for version in OLDEST_ENVELOPE_VERSION..=ENVELOPE_VERSION {
+        let bytes = golden(&format!("envelope-v{}.proof", version));
+        let envelope = ProofEnvelope::from_bytes(&bytes).unwrap();
+        assert_eq!(envelope.k, K);
+        assert_eq!(envelope.n as usize, CircuitKind::FibThreeColumn.n());
+
+        let current = envelope.to_bytes();
+        assert_eq!(current[4], ENVELOPE_VERSION);
+        assert_eq!(ProofEnvelope::from_bytes(&current).unwrap(), envelope);
+        if version == ENVELOPE_VERSION {
+            assert_eq!(current, bytes);
+        }
+
+        verify(&envelope, &golden_params());
+    }
+}
+
+#[test]
+fn bundles_still_verify() {
+    let params = golden_params();
+    for version in OLDEST_BUNDLE_VERSION..=BUNDLE_VERSION {
+        let bytes = golden(&format!("bundle-v{}.fibb", version));
+        let bundle = ProofBundle::from_bytes(&bytes).unwrap();
+        assert_eq!(bundle.envelope.n as usize, CircuitKind::FibThreeColumn.n());
+
+        let current = bundle.to_bytes();
+        assert_eq!(current[4], BUNDLE_VERSION);
+        assert_eq!(ProofBundle::from_bytes(&current).unwrap(), bundle);
+        if version == BUNDLE_VERSION {
+            assert_eq!(current, bytes);
+        }
+
+        assert_eq!(bundle.params.k, K);
+        assert_eq!(
+            bundle.params.digest,
+            params::digest(&params::to_bytes(&params))
+        );
+        let vk = verify(&bundle.envelope, &params);
+        assert_eq!(bundle.vk, fingerprint::pinned(&vk));
+    }
+}
+
+#[test]
+fn verifying_keys_are_deterministic_and_unchanged() {
+    let params = golden_params();
+    for (kind, expected) in VK_FINGERPRINTS {
+        let first = fingerprint::to_hex(&fingerprint(&kind.visit(Keygen(&params))));
+        let second = fingerprint::to_hex(&fingerprint(&kind.visit(Keygen(&params))));
+        assert_eq!(first, second, "keygen of {} is not deterministic", kind);
+        assert_eq!(first, expected, "the verifying key of {} changed", kind);
+    }
+}
+
+#[test]
+fn golden_files_carry_their_version() {
+    for version in OLDEST_ENVELOPE_VERSION..=ENVELOPE_VERSION {
+        let envelope = golden(&format!("envelope-v{}.proof", version));
+        assert_eq!(envelope[4], version);
+    }
+    for version in OLDEST_BUNDLE_VERSION..=BUNDLE_VERSION {
+        let bundle = golden(&format!("bundle-v{}.fibb", version));
+        assert_eq!(bundle[4], version);
+    }
+}
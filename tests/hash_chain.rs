@@ -0,0 +1,45 @@
+use fibonacci_circuit::{
+    circuits::hash_chain::{nth_state, HashChainCircuit},
+    gadgets::poseidon::{hash1, hash2},
+};
+use halo2_proofs::{circuit::Value, dev::MockProver, pasta::Fp};
+
+const K: u32 = 10;
+
+const N: usize = 8;
+
+fn run(head: Fp, instances: [Fp; 2]) -> bool {
+    let circuit = HashChainCircuit::<_, N> {
+        head: Value::known(head),
+    };
+    let prover = MockProver::run(K, &circuit, vec![instances.to_vec()]).unwrap();
+    prover.verify().is_ok()
+}
+
+#[test]
+fn hash1_is_its_own_domain() {
+    assert_ne!(hash1(Fp::from(5)), hash2(Fp::from(5), Fp::zero()));
+    assert_eq!(nth_state(Fp::from(5), 2), hash1(hash1(Fp::from(5))));
+    assert_eq!(nth_state(Fp::from(5), 0), Fp::from(5));
+}
+
+#[test]
+fn proves_the_chain() {
+    for head in [Fp::zero(), Fp::from(1), -Fp::from(1)] {
+        assert!(run(head, [head, nth_state(head, N)]));
+    }
+}
+
+#[test]
+fn rejects_wrong_tails() {
+    let head = Fp::from(1);
+    assert!(!run(head, [head, nth_state(head, N - 1)]));
+    assert!(!run(head, [head, nth_state(head, N + 1)]));
+}
+
+#[test]
+fn rejects_a_wrong_head() {
+    let head = Fp::from(1);
+    assert!(!run(Fp::from(2), [head, nth_state(head, N)]));
+    assert!(!run(head, [Fp::from(2), nth_state(head, N)]));
+}
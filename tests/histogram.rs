@@ -0,0 +1,46 @@
+use fibonacci_circuit::{
+    circuits::histogram::{histogram, Histogram, HistogramCircuit},
+    fibonacci::NUM_TERMS,
+};
+use halo2_proofs::{arithmetic::Field, circuit::Value, dev::MockProver, pasta::Fp};
+use rand_core::OsRng;
+
+const K: u32 = 6;
+
+fn run(a: u64, b: u64, histogram: Histogram) -> bool {
+    let circuit = HistogramCircuit {
+        a: Value::known(Fp::from(a)),
+        b: Value::known(Fp::from(b)),
+        histogram,
+    };
+    let prover = MockProver::run(K, &circuit, vec![vec![Fp::random(OsRng)]]).unwrap();
+    prover.verify().is_ok()
+}
+
+#[test]
+fn counts_the_last_digits() {
+    // 1 1 2 3 5 8 3 1 4 5
+    assert_eq!(histogram(1, 1, NUM_TERMS), [0, 3, 1, 2, 1, 2, 0, 0, 1, 0]);
+    for (a, b) in [(1, 1), (0, 0), (9, 9), (2, 7)] {
+        assert!(run(a, b, histogram(a, b, NUM_TERMS)), "seeds {}, {}", a, b);
+    }
+}
+
+#[test]
+fn rejects_a_wrong_count() {
+    let true_counts = histogram(1, 1, NUM_TERMS);
+    // One of the three 1s claimed as a 2, as a 0, and as a digit that
+    // does not occur.
+    for other in [2, 0, 7] {
+        let mut claimed = true_counts;
+        claimed[1] -= 1;
+        claimed[other] += 1;
+        assert!(!run(1, 1, claimed), "1 moved to {}", other);
+    }
+}
+
+#[test]
+#[should_panic(expected = "add up")]
+fn counts_must_cover_every_term() {
+    run(1, 1, [0; 10]);
+}
@@ -0,0 +1,158 @@
+use fibonacci_circuit::circuits::horner::{self, HornerCircuit, HornerCircuitConfig};
+use halo2_proofs::{
+    arithmetic::Field,
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    dev::{MockProver, VerifyFailure},
+    pasta::Fp,
+    plonk::{Circuit, ConstraintSystem, Error},
+};
+
+const K: u32 = 4;
+/// `3x³ + 5x + 7`.
+const COEFFS: [u64; 4] = [3, 0, 5, 7];
+const X: u64 = 2;
+
+fn coeffs() -> Vec<Fp> {
+    COEFFS.map(Fp::from).to_vec()
+}
+
+/// Lays out the Horner rows with an arbitrary point `xs[i]` and
+/// accumulator `accs[i]` on each row, each point constrained equal to the
+/// public one as the chip's copies are.
+#[derive(Debug, Clone)]
+struct ForgedCircuit {
+    xs: Vec<Fp>,
+    accs: Vec<Fp>,
+}
+
+impl ForgedCircuit {
+    /// The rows for the points `xs`, every step followed honestly.
+    fn stepping(xs: Vec<Fp>) -> Self {
+        let mut accs: Vec<Fp> = vec![];
+        for (row, coeff) in coeffs().into_iter().enumerate() {
+            let acc = match row {
+                0 => coeff,
+                _ => accs[row - 1] * xs[row - 1] + coeff,
+            };
+            accs.push(acc);
+        }
+        ForgedCircuit { xs, accs }
+    }
+
+    fn honest() -> Self {
+        Self::stepping(vec![Fp::from(X); COEFFS.len()])
+    }
+}
+
+impl Circuit<Fp> for ForgedCircuit {
+    type Config = HornerCircuitConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        self.clone()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>) -> HornerCircuitConfig {
+        HornerCircuit::<Fp>::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: HornerCircuitConfig,
+        mut layouter: impl Layouter<Fp>,
+    ) -> Result<(), Error> {
+        let cfg = config.horner;
+        let x = layouter.assign_region(
+            || "load x",
+            |mut region| region.assign_advice_from_instance(|| "x", config.instance, 0, cfg.x, 0),
+        )?;
+        let y = layouter.assign_region(
+            || "forged horner",
+            |mut region| {
+                let mut cell = None;
+                for (row, coeff) in coeffs().into_iter().enumerate() {
+                    if row == 0 {
+                        cfg.s_first.enable(&mut region, row)?;
+                    } else {
+                        cfg.s_step.enable(&mut region, row)?;
+                    }
+                    region.assign_fixed(|| "coeff", cfg.coeff, row, || Value::known(coeff))?;
+                    let x_row = Value::known(self.xs[row]);
+                    let x_row = region.assign_advice(|| "x", cfg.x, row, || x_row)?;
+                    region.constrain_equal(x.cell(), x_row.cell())?;
+                    let acc = Value::known(self.accs[row]);
+                    cell = Some(region.assign_advice(|| "acc", cfg.acc, row, || acc)?);
+                }
+                Ok(cell.unwrap())
+            },
+        )?;
+        layouter.constrain_instance(y.cell(), config.instance, 1)
+    }
+}
+
+/// The gates that fail, by name, with the honest `x` and `p(x)` public.
+fn failures(circuit: &impl Circuit<Fp>) -> Vec<String> {
+    let x = Fp::from(X);
+    let instances = vec![vec![x, horner::evaluate(&coeffs(), x)]];
+    let prover = MockProver::run(K, circuit, instances).unwrap();
+    let mut names: Vec<_> = prover
+        .verify()
+        .err()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|failure| match failure {
+            VerifyFailure::ConstraintNotSatisfied { constraint, .. } => {
+                // "Constraint i ('name') in gate j ('gate')"
+                let constraint = constraint.to_string();
+                constraint.rsplit('\'').nth(1).unwrap().to_string()
+            }
+            VerifyFailure::Permutation { .. } => "permutation".to_string(),
+            other => other.to_string(),
+        })
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+#[test]
+fn the_honest_evaluation_verifies() {
+    assert_eq!(horner::evaluate(&coeffs(), Fp::from(X)), Fp::from(41));
+    let circuit = HornerCircuit { coeffs: coeffs() };
+    assert_eq!(failures(&circuit), Vec::<String>::new());
+    assert_eq!(failures(&ForgedCircuit::honest()), Vec::<String>::new());
+}
+
+#[test]
+fn forged_accumulators_are_rejected() {
+    let honest = ForgedCircuit::honest();
+
+    // One accumulator off, the evaluation still the honest one.
+    let mut forged = honest.clone();
+    forged.accs[1] += Fp::one();
+    assert_eq!(failures(&forged), ["horner step"]);
+
+    // The first coefficient forged, the rest following from it.
+    let mut forged = honest.clone();
+    forged.accs[0] += Fp::one();
+    for row in 1..COEFFS.len() - 1 {
+        forged.accs[row] = forged.accs[row - 1] * forged.xs[row - 1] + coeffs()[row];
+    }
+    assert_eq!(failures(&forged), ["horner first", "horner step"]);
+}
+
+#[test]
+fn the_point_cannot_vary_between_rows() {
+    // Another point on row 0 forges the second accumulator; another on
+    // row 1 brings the third back, so every step holds.
+    let mut xs = vec![Fp::from(X); COEFFS.len()];
+    xs[0] = Fp::from(5);
+    let honest = ForgedCircuit::honest();
+    let forged_acc = Fp::from(COEFFS[0]) * xs[0] + Fp::from(COEFFS[1]);
+    xs[1] = honest.accs[1] * Fp::from(X) * forged_acc.invert().unwrap();
+
+    let forged = ForgedCircuit::stepping(xs);
+    assert_ne!(forged.accs[1], honest.accs[1]);
+    assert_eq!(forged.accs[2..], honest.accs[2..]);
+    assert_eq!(failures(&forged), ["permutation"]);
+}
@@ -0,0 +1,144 @@
+use fibonacci_circuit::circuits::inner_product::{
+    self, InnerProductCircuit, InnerProductCircuitConfig,
+};
+use halo2_proofs::{
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    dev::{MockProver, VerifyFailure},
+    pasta::Fp,
+    plonk::{Circuit, ConstraintSystem, Error},
+};
+
+const K: u32 = 4;
+const N: usize = 4;
+const A: [u64; N] = [1, 2, 3, 4];
+const B: [u64; N] = [5, 6, 7, 8];
+
+/// Lays out arbitrary rows `(a_i, b_i, acc_i)` as the chip does, exposing
+/// the last accumulator.
+#[derive(Debug, Clone)]
+struct ForgedCircuit {
+    rows: Vec<[Fp; 3]>,
+}
+
+impl ForgedCircuit {
+    /// The rows for `a` and `b`, every accumulator following from the
+    /// previous one.
+    fn stepping(a: [Fp; N], b: [Fp; N]) -> Self {
+        let mut acc = Fp::zero();
+        let rows = a
+            .into_iter()
+            .zip(b)
+            .map(|(a, b)| {
+                acc += a * b;
+                [a, b, acc]
+            })
+            .collect();
+        ForgedCircuit { rows }
+    }
+
+    fn honest() -> Self {
+        Self::stepping(A.map(Fp::from), B.map(Fp::from))
+    }
+}
+
+impl Circuit<Fp> for ForgedCircuit {
+    type Config = InnerProductCircuitConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        self.clone()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>) -> InnerProductCircuitConfig {
+        InnerProductCircuit::<Fp, N>::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: InnerProductCircuitConfig,
+        mut layouter: impl Layouter<Fp>,
+    ) -> Result<(), Error> {
+        let cfg = config.inner_product;
+        let result = layouter.assign_region(
+            || "forged inner product",
+            |mut region| {
+                let mut cell = None;
+                for (row, values) in self.rows.iter().enumerate() {
+                    if row == 0 {
+                        cfg.s_first.enable(&mut region, row)?;
+                    } else {
+                        cfg.s_step.enable(&mut region, row)?;
+                    }
+                    for (column, value) in [cfg.a, cfg.b, cfg.acc].into_iter().zip(values) {
+                        let value = Value::known(*value);
+                        cell = Some(region.assign_advice(|| "forged", column, row, || value)?);
+                    }
+                }
+                Ok(cell.unwrap())
+            },
+        )?;
+        layouter.constrain_instance(result.cell(), config.instance, 0)
+    }
+}
+
+/// The gates that fail, by name, with the honest inner product public.
+fn failures(circuit: &impl Circuit<Fp>) -> Vec<String> {
+    let c = inner_product::inner_product(&A.map(Fp::from), &B.map(Fp::from));
+    let prover = MockProver::run(K, circuit, vec![vec![c]]).unwrap();
+    let mut names: Vec<_> = prover
+        .verify()
+        .err()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|failure| match failure {
+            VerifyFailure::ConstraintNotSatisfied { constraint, .. } => {
+                // "Constraint i ('name') in gate j ('gate')"
+                let constraint = constraint.to_string();
+                constraint.rsplit('\'').nth(1).unwrap().to_string()
+            }
+            VerifyFailure::Permutation { .. } => "permutation".to_string(),
+            other => other.to_string(),
+        })
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+#[test]
+fn the_honest_product_verifies() {
+    let circuit = InnerProductCircuit::<Fp, N> {
+        a: A.map(|a| Value::known(Fp::from(a))),
+        b: B.map(|b| Value::known(Fp::from(b))),
+    };
+    assert_eq!(failures(&circuit), Vec::<String>::new());
+    assert_eq!(failures(&ForgedCircuit::honest()), Vec::<String>::new());
+    assert_eq!(ForgedCircuit::honest().rows[N - 1][2], Fp::from(70));
+}
+
+#[test]
+fn forged_accumulators_are_rejected() {
+    let honest = ForgedCircuit::honest();
+
+    // One accumulator off, the result still the honest one.
+    let mut forged = honest.clone();
+    forged.rows[1][2] += Fp::one();
+    assert_eq!(failures(&forged), ["inner product step"]);
+
+    // The first product forged.
+    let mut forged = honest.clone();
+    forged.rows[0][2] += Fp::one();
+    assert_eq!(
+        failures(&forged),
+        ["inner product first", "inner product step"]
+    );
+
+    // A term changed and the accumulators following it, except the last,
+    // which is put back; only the last row's gate is left to notice.
+    let mut a = A.map(Fp::from);
+    a[1] += Fp::one();
+    let mut forged = ForgedCircuit::stepping(a, B.map(Fp::from));
+    assert_ne!(forged.rows[N - 1][2], honest.rows[N - 1][2]);
+    forged.rows[N - 1][2] = honest.rows[N - 1][2];
+    assert_eq!(failures(&forged), ["inner product step"]);
+}
@@ -0,0 +1,169 @@
+use fibonacci_circuit::gadgets::is_equal::{IsEqualChip, IsEqualConfig};
+use halo2_proofs::{
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    dev::MockProver,
+    pasta::Fp,
+    plonk::{Advice, Circuit, Column, ConstraintSystem, Error, Instance},
+};
+
+#[derive(Debug, Clone)]
+struct Config {
+    input: Column<Advice>,
+    instance: Column<Instance>,
+    is_equal: IsEqualConfig<Fp>,
+}
+
+fn configure(meta: &mut ConstraintSystem<Fp>) -> Config {
+    let input = meta.advice_column();
+    meta.enable_equality(input);
+    let instance = meta.instance_column();
+    meta.enable_equality(instance);
+    let [a, b, diff_inv, out] = [(); 4].map(|_| meta.advice_column());
+    Config {
+        input,
+        instance,
+        is_equal: IsEqualChip::configure(meta, a, b, diff_inv, out),
+    }
+}
+
+/// Loads `a` and `b` and exposes `a == b` at instance row 0.
+#[derive(Debug, Default)]
+struct IsEqualCircuit {
+    a: Value<Fp>,
+    b: Value<Fp>,
+}
+
+impl Circuit<Fp> for IsEqualCircuit {
+    type Config = Config;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>) -> Config {
+        configure(meta)
+    }
+
+    fn synthesize(&self, config: Config, mut layouter: impl Layouter<Fp>) -> Result<(), Error> {
+        let (a, b) = layouter.assign_region(
+            || "inputs",
+            |mut region| {
+                let a = region.assign_advice(|| "a", config.input, 0, || self.a)?;
+                let b = region.assign_advice(|| "b", config.input, 1, || self.b)?;
+                Ok((a, b))
+            },
+        )?;
+        let chip = IsEqualChip::construct(config.is_equal);
+        let bit = chip.is_equal(layouter.namespace(|| "is equal"), &a, &b)?;
+        layouter.constrain_instance(bit.cell().cell(), config.instance, 0)
+    }
+}
+
+/// Lays out the gadget row by hand with an arbitrary output bit and
+/// inverse witness, the way a cheating prover could.
+#[derive(Debug, Default)]
+struct ForgedCircuit {
+    a: Fp,
+    b: Fp,
+    diff_inv: Fp,
+    out: Fp,
+}
+
+impl Circuit<Fp> for ForgedCircuit {
+    type Config = Config;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>) -> Config {
+        configure(meta)
+    }
+
+    fn synthesize(&self, config: Config, mut layouter: impl Layouter<Fp>) -> Result<(), Error> {
+        let cfg = config.is_equal;
+        let out = layouter.assign_region(
+            || "forged is equal",
+            |mut region| {
+                cfg.selector.enable(&mut region, 0)?;
+                region.assign_advice(|| "a", cfg.a, 0, || Value::known(self.a))?;
+                region.assign_advice(|| "b", cfg.b, 0, || Value::known(self.b))?;
+                region.assign_advice(
+                    || "diff inv",
+                    cfg.is_zero.value_inv,
+                    0,
+                    || Value::known(self.diff_inv),
+                )?;
+                region.assign_advice(|| "out", cfg.out, 0, || Value::known(self.out))
+            },
+        )?;
+        layouter.constrain_instance(out.cell(), config.instance, 0)
+    }
+}
+
+const K: u32 = 4;
+
+#[test]
+fn equal_and_unequal_inputs() {
+    for (a, b, expected) in [(3, 3, 1), (3, 4, 0), (0, 0, 1), (0, 7, 0)] {
+        let circuit = IsEqualCircuit {
+            a: Value::known(Fp::from(a)),
+            b: Value::known(Fp::from(b)),
+        };
+        let prover = MockProver::run(K, &circuit, vec![vec![Fp::from(expected)]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()), "{} == {}", a, b);
+
+        let wrong = Fp::from(1 - expected);
+        let prover = MockProver::run(K, &circuit, vec![vec![wrong]]).unwrap();
+        assert!(
+            prover.verify().is_err(),
+            "{} == {} claimed {:?}",
+            a,
+            b,
+            wrong
+        );
+    }
+}
+
+#[test]
+fn forged_bits_are_rejected() {
+    let (three, four) = (Fp::from(3), Fp::from(4));
+    let forgeries = [
+        // Unequal inputs claimed equal, whatever the inverse witness.
+        (three, four, Fp::zero(), Fp::one()),
+        (three, four, Fp::from(5), Fp::one()),
+        (three, four, -Fp::one(), Fp::one()),
+        // Equal inputs claimed unequal.
+        (three, three, Fp::zero(), Fp::zero()),
+        (three, three, Fp::from(5), Fp::zero()),
+        // A non-boolean output.
+        (three, four, -Fp::one(), Fp::from(2)),
+        (three, three, Fp::zero(), Fp::from(2)),
+    ];
+    for (a, b, diff_inv, out) in forgeries {
+        let circuit = ForgedCircuit {
+            a,
+            b,
+            diff_inv,
+            out,
+        };
+        let prover = MockProver::run(K, &circuit, vec![vec![out]]).unwrap();
+        assert!(
+            prover.verify().is_err(),
+            "accepted {:?}",
+            (a, b, diff_inv, out)
+        );
+    }
+
+    // The honest witness laid out the same way is accepted.
+    let circuit = ForgedCircuit {
+        a: three,
+        b: four,
+        diff_inv: -Fp::one(),
+        out: Fp::zero(),
+    };
+    let prover = MockProver::run(K, &circuit, vec![vec![Fp::zero()]]).unwrap();
+    assert_eq!(prover.verify(), Ok(()));
+}
@@ -0,0 +1,48 @@
+use fibonacci_circuit::{
+    fibonacci::{
+        last_digit::{nth_digit, LastDigitCircuit},
+        NUM_TERMS,
+    },
+    reference::fib_biguint,
+};
+use halo2_proofs::{circuit::Value, dev::MockProver, pasta::Fp};
+use num_bigint::BigUint;
+
+const K: u32 = 5;
+
+fn run(a: u64, b: u64, digit: u64) -> bool {
+    let circuit = LastDigitCircuit {
+        a: Value::known(Fp::from(a)),
+        b: Value::known(Fp::from(b)),
+    };
+    let prover = MockProver::run(K, &circuit, vec![vec![Fp::from(digit)]]).unwrap();
+    prover.verify().is_ok()
+}
+
+#[test]
+fn digits_follow_the_integers() {
+    for n in 1..=100 {
+        assert_eq!(
+            BigUint::from(nth_digit(1, 1, n)),
+            fib_biguint(n) % 10u32,
+            "F({})",
+            n
+        );
+    }
+}
+
+#[test]
+fn exposes_the_last_digit() {
+    for (a, b) in [(1, 1), (9, 9), (3, 7), (0, 0)] {
+        let digit = nth_digit(a, b, NUM_TERMS);
+        assert!(run(a, b, digit), "seeds {}, {}", a, b);
+        assert!(!run(a, b, (digit + 1) % 10), "seeds {}, {}", a, b);
+        // The same residue without the reduction is not a digit.
+        assert!(!run(a, b, digit + 10), "seeds {}, {}", a, b);
+    }
+}
+
+#[test]
+fn seeds_must_be_digits() {
+    assert!(!run(11, 1, nth_digit(11, 1, NUM_TERMS)));
+}
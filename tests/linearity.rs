@@ -0,0 +1,63 @@
+//! For random seeds `a, b`, each layout exposes `a·F(n-2) + b·F(n-1)`.
+//!
+//! The seeds are arbitrary field elements, so the output only comes out
+//! right if every copy constraint carries its term into the next row; with
+//! seeds `1, 1` alone a layout could get away with less. A failure prints
+//! the seeds to reproduce it with.
+
+use fibonacci_circuit::{
+    fibonacci::{single_column, stride::FiboStrideCircuit, three_column, NUM_TERMS},
+    reference::{fib_biguint, to_field},
+};
+use halo2_proofs::{arithmetic::Field, circuit::Value, dev::MockProver, pasta::Fp, plonk::Circuit};
+use rand_core::OsRng;
+
+const K: u32 = 4;
+const CASES: usize = 16;
+
+/// `a·F(n-2) + b·F(n-1)`, with `n = NUM_TERMS`.
+fn linear(a: Fp, b: Fp) -> Fp {
+    let f = |n| to_field::<Fp>(&fib_biguint(n));
+    a * f(NUM_TERMS - 2) + b * f(NUM_TERMS - 1)
+}
+
+fn check<C: Circuit<Fp>>(layout: &str, circuit: impl Fn(Value<Fp>, Value<Fp>) -> C) {
+    for _ in 0..CASES {
+        let (a, b) = (Fp::random(OsRng), Fp::random(OsRng));
+        let circuit = circuit(Value::known(a), Value::known(b));
+        let output = linear(a, b);
+
+        let prover = MockProver::run(K, &circuit, vec![vec![output]]).unwrap();
+        assert_eq!(
+            prover.verify(),
+            Ok(()),
+            "{} with seeds {:?}, {:?}",
+            layout,
+            a,
+            b
+        );
+        let prover = MockProver::run(K, &circuit, vec![vec![output + Fp::one()]]).unwrap();
+        assert!(
+            prover.verify().is_err(),
+            "{} with seeds {:?}, {:?}",
+            layout,
+            a,
+            b
+        );
+    }
+}
+
+#[test]
+fn three_column() {
+    check("three-column", |a, b| three_column::FiboCircuit { a, b });
+}
+
+#[test]
+fn single_column() {
+    check("single-column", |a, b| single_column::FiboCircuit { a, b });
+}
+
+#[test]
+fn stride() {
+    check("stride", |a, b| FiboStrideCircuit::<_, 4> { a, b });
+}
@@ -0,0 +1,147 @@
+//! A valid proof altered every way a transport or an attacker might: bytes
+//! flipped, cut short, with garbage after it. Each must fail to verify
+//! with an error, not a panic, and the untouched proof must keep verifying
+//! however often it is checked. Each is also checked through
+//! [`aggregation::verify_aggregate`], next to an untouched copy.
+
+use std::panic::{self, AssertUnwindSafe};
+
+use fibonacci_circuit::{
+    aggregation::{self, Proof},
+    fibonacci::{three_column::FiboCircuit, NUM_TERMS},
+    prover,
+    reference::fib_field,
+    rng::SplitMix64,
+};
+use halo2_proofs::{
+    circuit::Value,
+    pasta::{EqAffine, Fp},
+    plonk::{Error, VerifyingKey},
+    poly::commitment::Params,
+};
+use rand_core::RngCore;
+
+const K: u32 = 4;
+
+/// Byte positions flipped on top of the first and last.
+const SAMPLES: usize = 48;
+
+struct Fixture {
+    params: Params<EqAffine>,
+    vk: VerifyingKey<EqAffine>,
+    proof: Vec<u8>,
+    instances: Vec<Vec<Fp>>,
+}
+
+impl Fixture {
+    fn new() -> Self {
+        let params = Params::new(K);
+        let circuit = FiboCircuit {
+            a: Value::known(Fp::one()),
+            b: Value::known(Fp::one()),
+        };
+        let pk = prover::keygen(&params, &circuit).unwrap();
+        let instances = vec![vec![fib_field(Fp::one(), Fp::one(), NUM_TERMS)]];
+        let proof = prover::create_proof(&params, &pk, circuit, &instances).unwrap();
+        Fixture {
+            vk: pk.get_vk().clone(),
+            params,
+            proof,
+            instances,
+        }
+    }
+
+    /// Verifies `proof`, failing the test if the verifier panics.
+    fn verify(&self, proof: &[u8]) -> Result<(), Error> {
+        panic::catch_unwind(AssertUnwindSafe(|| {
+            prover::verify_proof(&self.params, &self.vk, proof, &self.instances)
+        }))
+        .unwrap_or_else(|_| panic!("verifier panicked on a {}-byte proof", proof.len()))
+    }
+
+    /// Whether `proof` and the untouched proof verify together as an
+    /// aggregate, failing the test if the verifier panics.
+    fn aggregates(&self, proof: &[u8]) -> bool {
+        let proofs = [&self.proof[..], proof].map(|bytes| Proof {
+            bytes: bytes.to_vec(),
+            instances: self.instances.clone(),
+        });
+        panic::catch_unwind(AssertUnwindSafe(|| {
+            aggregation::verify_aggregate(&self.params, &self.vk, &proofs)
+        }))
+        .unwrap_or_else(|_| panic!("aggregation panicked on a {}-byte proof", proof.len()))
+    }
+
+    /// Whether both verifiers reject `proof`.
+    fn rejects(&self, proof: &[u8]) -> bool {
+        self.verify(proof).is_err() && !self.aggregates(proof)
+    }
+}
+
+#[test]
+fn flipped_bytes_fail() {
+    let fixture = Fixture::new();
+    let len = fixture.proof.len();
+    let mut rng = SplitMix64::new(478);
+    let mut positions = vec![0, len - 1];
+    positions.extend((0..SAMPLES).map(|_| rng.next_u64() as usize % len));
+
+    for position in positions {
+        let mut proof = fixture.proof.clone();
+        proof[position] = !proof[position];
+        assert!(
+            fixture.rejects(&proof),
+            "flipping byte {} of {} still verifies",
+            position,
+            len
+        );
+    }
+}
+
+#[test]
+fn truncated_proofs_fail() {
+    let fixture = Fixture::new();
+    let len = fixture.proof.len();
+    for cut in [0, 1, 31, 32, 33, len / 2, len - 32, len - 1] {
+        assert!(
+            fixture.rejects(&fixture.proof[..cut]),
+            "the first {} of {} bytes verify",
+            cut,
+            len
+        );
+    }
+}
+
+#[test]
+fn appended_bytes_fail() {
+    let fixture = Fixture::new();
+    let mut rng = SplitMix64::new(478);
+    for extra in [1, 32, 1000] {
+        let mut garbage = vec![0; extra];
+        rng.fill_bytes(&mut garbage);
+        for tail in [vec![0; extra], garbage] {
+            let mut proof = fixture.proof.clone();
+            proof.extend(tail);
+            assert!(
+                fixture.rejects(&proof),
+                "{} bytes appended still verify",
+                extra
+            );
+        }
+    }
+}
+
+#[test]
+fn verification_is_stateless() {
+    let fixture = Fixture::new();
+    fixture.verify(&fixture.proof).unwrap();
+    fixture.verify(&fixture.proof).unwrap();
+    assert!(fixture.aggregates(&fixture.proof));
+
+    // A rejected proof in between leaves nothing behind either.
+    let mut flipped = fixture.proof.clone();
+    flipped[0] = !flipped[0];
+    assert!(fixture.rejects(&flipped));
+    fixture.verify(&fixture.proof).unwrap();
+    assert!(fixture.aggregates(&fixture.proof));
+}
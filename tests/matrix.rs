@@ -0,0 +1,522 @@
+//! Every layout seeded with `a, b` against a table of `(a, b, n, k,
+//! expected)` cases, where `expected` is the `n`th term.
+//!
+//! Each layout turns a case into its circuit and instances, or skips it if
+//! it cannot lay out `n` terms from those seeds. A case must verify with
+//! the `MockProver` at its `k` (or the layout's smallest `k`, if larger),
+//! and must stop verifying when `expected` is off by one; at `k` up to
+//! [`PROVER_K`] it also goes through the real prover. A registered circuit
+//! has to be in [`layouts!`] or in [`UNSEEDED`], so a new one gets the
+//! whole table as soon as it says how to build a case.
+
+use fibonacci_circuit::{
+    circuits::{histogram::HistogramCircuit, permutation::PERMUTATION_CHALLENGE},
+    fibonacci::{
+        batch::FiboBatchCircuit, bounded::FiboBoundedCircuit, checkpoint::FiboCheckpointCircuit,
+        chunk::FiboChunkCircuit, descent::DescentCircuit, fixed_selector::FiboFixedCircuit,
+        last_digit::LastDigitCircuit, negative_rotation, parity::FiboParityCircuit, single_column,
+        square_test::SquareTestCircuit, stride::FiboStrideCircuit, table::FibTableCircuit,
+        three_column, vertical::FiboVerticalCircuit, NUM_TERMS,
+    },
+    gadgets::version::{self, CIRCUIT_VERSION},
+    params, prover,
+    reference::fib_field,
+    registry::{CircuitKind, BATCH_SEEDS, BOUND_BITS, CHUNK_STEPS, DESCENT_STEPS, STRIDE},
+};
+use halo2_proofs::{
+    arithmetic::FieldExt, circuit::Value, dev::MockProver, pasta::Fp, plonk::Circuit,
+};
+
+/// The largest `k` the real prover runs at.
+const PROVER_K: u32 = 4;
+
+#[derive(Debug, Clone, Copy)]
+struct Case {
+    a: u64,
+    b: u64,
+    n: usize,
+    k: u32,
+    expected: u64,
+}
+
+const fn case(a: u64, b: u64, n: usize, k: u32, expected: u64) -> Case {
+    Case {
+        a,
+        b,
+        n,
+        k,
+        expected,
+    }
+}
+
+const CASES: [Case; 14] = [
+    case(1, 1, 10, 4, 55),
+    case(1, 1, 10, 6, 55),
+    case(2, 3, 10, 4, 144),
+    case(5, 8, 10, 4, 377),
+    case(0, 1, 10, 4, 34),
+    case(1, 0, 10, 4, 21),
+    case(0, 0, 10, 4, 0),
+    case(9, 9, 10, 5, 495),
+    case(1, 1, 6, 4, 8),
+    case(3, 4, 5, 4, 18),
+    case(7, 9, 2, 4, 9),
+    case(7, 9, 1, 4, 7),
+    case(1, 1, 3, 4, 2),
+    case(1, 1, 13, 4, 233),
+];
+
+fn fp(x: u64) -> Value<Fp> {
+    Value::known(Fp::from(x))
+}
+
+fn term(case: &Case, n: usize) -> Fp {
+    fib_field(Fp::from(case.a), Fp::from(case.b), n)
+}
+
+/// A layout under test, built from a case whose last term is claimed to be
+/// `expected`.
+trait Layout {
+    type Circuit: Circuit<Fp>;
+
+    /// The registered circuit this layout is, if any.
+    const KIND: Option<CircuitKind>;
+
+    /// The smallest `k` the layout fits in.
+    const MIN_K: u32 = 4;
+
+    fn build(case: &Case, expected: u64) -> Option<(Self::Circuit, Vec<Vec<Fp>>)>;
+}
+
+struct ThreeColumn;
+
+impl Layout for ThreeColumn {
+    type Circuit = three_column::FiboCircuit<Fp>;
+    const KIND: Option<CircuitKind> = Some(CircuitKind::FibThreeColumn);
+
+    fn build(case: &Case, expected: u64) -> Option<(Self::Circuit, Vec<Vec<Fp>>)> {
+        (case.n == NUM_TERMS).then(|| {
+            let circuit = three_column::FiboCircuit {
+                a: fp(case.a),
+                b: fp(case.b),
+            };
+            (circuit, vec![vec![Fp::from(expected)]])
+        })
+    }
+}
+
+struct SingleColumn;
+
+impl Layout for SingleColumn {
+    type Circuit = single_column::FiboCircuit<Fp>;
+    const KIND: Option<CircuitKind> = Some(CircuitKind::FibSingleColumn);
+
+    fn build(case: &Case, expected: u64) -> Option<(Self::Circuit, Vec<Vec<Fp>>)> {
+        (case.n == NUM_TERMS).then(|| {
+            let circuit = single_column::FiboCircuit {
+                a: fp(case.a),
+                b: fp(case.b),
+            };
+            (circuit, vec![vec![Fp::from(expected)]])
+        })
+    }
+}
+
+struct NegativeRotation;
+
+impl Layout for NegativeRotation {
+    type Circuit = negative_rotation::FiboCircuit<Fp>;
+    const KIND: Option<CircuitKind> = Some(CircuitKind::FibNegativeRotation);
+
+    fn build(case: &Case, expected: u64) -> Option<(Self::Circuit, Vec<Vec<Fp>>)> {
+        (case.n == NUM_TERMS).then(|| {
+            let circuit = negative_rotation::FiboCircuit {
+                a: fp(case.a),
+                b: fp(case.b),
+            };
+            (circuit, vec![vec![Fp::from(expected)]])
+        })
+    }
+}
+
+struct Versioned;
+
+impl Layout for Versioned {
+    type Circuit = version::Versioned<three_column::FiboCircuit<Fp>>;
+    const KIND: Option<CircuitKind> = Some(CircuitKind::FibVersioned);
+
+    fn build(case: &Case, expected: u64) -> Option<(Self::Circuit, Vec<Vec<Fp>>)> {
+        ThreeColumn::build(case, expected).map(|(circuit, instances)| {
+            (
+                version::Versioned::new(circuit),
+                version::instances(instances, CIRCUIT_VERSION),
+            )
+        })
+    }
+}
+
+struct Batch;
+
+impl Layout for Batch {
+    type Circuit = FiboBatchCircuit<Fp, { BATCH_SEEDS.len() }>;
+    const KIND: Option<CircuitKind> = Some(CircuitKind::FibBatch);
+
+    /// Every sequence of the batch from the case's seeds.
+    fn build(case: &Case, expected: u64) -> Option<(Self::Circuit, Vec<Vec<Fp>>)> {
+        (case.n == NUM_TERMS).then(|| {
+            let circuit = FiboBatchCircuit {
+                seeds: [(fp(case.a), fp(case.b)); BATCH_SEEDS.len()],
+            };
+            (circuit, vec![vec![Fp::from(expected); BATCH_SEEDS.len()]])
+        })
+    }
+}
+
+struct Chunk<const C: usize>;
+
+impl<const C: usize> Layout for Chunk<C> {
+    type Circuit = FiboChunkCircuit<Fp, C>;
+    const KIND: Option<CircuitKind> = if C == CHUNK_STEPS {
+        Some(CircuitKind::FibChunk)
+    } else {
+        None
+    };
+
+    /// `C` steps reach term `C + 2`.
+    fn build(case: &Case, expected: u64) -> Option<(Self::Circuit, Vec<Vec<Fp>>)> {
+        (case.n == C + 2).then(|| {
+            let circuit = FiboChunkCircuit {
+                x0: fp(case.a),
+                x1: fp(case.b),
+            };
+            let instances = vec![
+                Fp::from(case.a),
+                Fp::from(case.b),
+                term(case, case.n - 1),
+                Fp::from(expected),
+            ];
+            (circuit, vec![instances])
+        })
+    }
+}
+
+struct Stride<const K: usize>;
+
+impl<const K: usize> Layout for Stride<K> {
+    type Circuit = FiboStrideCircuit<Fp, K>;
+    const KIND: Option<CircuitKind> = if K == STRIDE {
+        Some(CircuitKind::FibStride)
+    } else {
+        None
+    };
+
+    fn build(case: &Case, expected: u64) -> Option<(Self::Circuit, Vec<Vec<Fp>>)> {
+        (case.n == NUM_TERMS).then(|| {
+            let circuit = FiboStrideCircuit {
+                a: fp(case.a),
+                b: fp(case.b),
+            };
+            (circuit, vec![vec![Fp::from(expected)]])
+        })
+    }
+}
+
+struct Checkpoint;
+
+impl Layout for Checkpoint {
+    type Circuit = FiboCheckpointCircuit<Fp>;
+    const KIND: Option<CircuitKind> = Some(CircuitKind::FibCheckpoint);
+
+    fn build(case: &Case, expected: u64) -> Option<(Self::Circuit, Vec<Vec<Fp>>)> {
+        (case.n <= NUM_TERMS).then(|| {
+            let circuit = FiboCheckpointCircuit {
+                a: fp(case.a),
+                b: fp(case.b),
+                checkpoints: vec![case.n],
+            };
+            (circuit, vec![vec![Fp::from(expected)]])
+        })
+    }
+}
+
+struct Descent;
+
+impl Layout for Descent {
+    type Circuit = DescentCircuit<Fp, DESCENT_STEPS>;
+    const KIND: Option<CircuitKind> = Some(CircuitKind::FibDescent);
+    const MIN_K: u32 = 9;
+
+    /// `(F(n), F(n+1))` descends to `(1, 1)` in `n - 1` steps, and only
+    /// from the seeds `1, 1`.
+    fn build(case: &Case, expected: u64) -> Option<(Self::Circuit, Vec<Vec<Fp>>)> {
+        ((case.a, case.b) == (1, 1) && case.n <= DESCENT_STEPS + 1).then(|| {
+            let (x, y) = (Fp::from(expected), term(case, case.n + 1));
+            let circuit = DescentCircuit {
+                x: Value::known(x),
+                y: Value::known(y),
+            };
+            (circuit, vec![vec![x, y]])
+        })
+    }
+}
+
+struct SquareTest;
+
+impl Layout for SquareTest {
+    type Circuit = SquareTestCircuit<Fp>;
+    const KIND: Option<CircuitKind> = Some(CircuitKind::FibSquareTest);
+    const MIN_K: u32 = 8;
+
+    /// Like [`Descent`], with no bound on `n` short of `2^64`.
+    fn build(case: &Case, expected: u64) -> Option<(Self::Circuit, Vec<Vec<Fp>>)> {
+        ((case.a, case.b) == (1, 1)).then(|| {
+            let (x, y) = (Fp::from(expected), term(case, case.n + 1));
+            let circuit = SquareTestCircuit {
+                x: Value::known(x),
+                y: Value::known(y),
+            };
+            (circuit, vec![vec![x, y]])
+        })
+    }
+}
+
+struct Parity;
+
+impl Layout for Parity {
+    type Circuit = FiboParityCircuit<Fp>;
+    const KIND: Option<CircuitKind> = Some(CircuitKind::FibParity);
+    const MIN_K: u32 = 7;
+
+    fn build(case: &Case, expected: u64) -> Option<(Self::Circuit, Vec<Vec<Fp>>)> {
+        (case.n == NUM_TERMS).then(|| {
+            let circuit = FiboParityCircuit {
+                a: fp(case.a),
+                b: fp(case.b),
+            };
+            (circuit, vec![vec![Fp::from(expected & 1)]])
+        })
+    }
+}
+
+struct LastDigit;
+
+impl Layout for LastDigit {
+    type Circuit = LastDigitCircuit<Fp>;
+    const KIND: Option<CircuitKind> = Some(CircuitKind::FibLastDigit);
+    const MIN_K: u32 = 5;
+
+    /// The seeds must be digits already.
+    fn build(case: &Case, expected: u64) -> Option<(Self::Circuit, Vec<Vec<Fp>>)> {
+        (case.n == NUM_TERMS && case.a < 10 && case.b < 10).then(|| {
+            let circuit = LastDigitCircuit {
+                a: fp(case.a),
+                b: fp(case.b),
+            };
+            (circuit, vec![vec![Fp::from(expected % 10)]])
+        })
+    }
+}
+
+struct FixedSelector;
+
+impl Layout for FixedSelector {
+    type Circuit = FiboFixedCircuit<Fp>;
+    const KIND: Option<CircuitKind> = Some(CircuitKind::FibFixedSelector);
+
+    fn build(case: &Case, expected: u64) -> Option<(Self::Circuit, Vec<Vec<Fp>>)> {
+        (case.n == NUM_TERMS).then(|| {
+            let circuit = FiboFixedCircuit {
+                a: fp(case.a),
+                b: fp(case.b),
+            };
+            (circuit, vec![vec![Fp::from(expected)]])
+        })
+    }
+}
+
+struct Vertical;
+
+impl Layout for Vertical {
+    type Circuit = FiboVerticalCircuit<Fp>;
+    const KIND: Option<CircuitKind> = Some(CircuitKind::FibVertical);
+    const MIN_K: u32 = 5;
+
+    fn build(case: &Case, expected: u64) -> Option<(Self::Circuit, Vec<Vec<Fp>>)> {
+        (case.n == NUM_TERMS).then(|| {
+            let circuit = FiboVerticalCircuit {
+                a: fp(case.a),
+                b: fp(case.b),
+            };
+            (circuit, vec![vec![Fp::from(expected)]])
+        })
+    }
+}
+
+struct Table;
+
+impl Layout for Table {
+    type Circuit = FibTableCircuit<Fp>;
+    const KIND: Option<CircuitKind> = Some(CircuitKind::FibTable);
+    const MIN_K: u32 = 5;
+
+    /// The table starts `0, 1`, so only those seeds and `1, 1` index into
+    /// it.
+    fn build(case: &Case, expected: u64) -> Option<(Self::Circuit, Vec<Vec<Fp>>)> {
+        let index = match (case.a, case.b) {
+            (0, 1) => case.n - 1,
+            (1, 1) => case.n,
+            _ => return None,
+        };
+        let circuit = FibTableCircuit::new(case.k.max(Self::MIN_K));
+        Some((
+            circuit,
+            vec![vec![Fp::from(index as u64), Fp::from(expected)]],
+        ))
+    }
+}
+
+struct Bounded;
+
+impl Layout for Bounded {
+    type Circuit = FiboBoundedCircuit<Fp, BOUND_BITS>;
+    const KIND: Option<CircuitKind> = Some(CircuitKind::FibBounded);
+    const MIN_K: u32 = 5;
+
+    fn build(case: &Case, expected: u64) -> Option<(Self::Circuit, Vec<Vec<Fp>>)> {
+        (case.n == NUM_TERMS).then(|| {
+            let circuit = FiboBoundedCircuit {
+                a: fp(case.a),
+                b: fp(case.b),
+            };
+            (circuit, vec![vec![Fp::from(expected)]])
+        })
+    }
+}
+
+struct Histogram;
+
+impl Layout for Histogram {
+    type Circuit = HistogramCircuit<Fp>;
+    const KIND: Option<CircuitKind> = Some(CircuitKind::Histogram);
+    const MIN_K: u32 = 6;
+
+    /// The counts of the first `n - 1` digits, plus the digit of
+    /// `expected`.
+    fn build(case: &Case, expected: u64) -> Option<(Self::Circuit, Vec<Vec<Fp>>)> {
+        (case.n == NUM_TERMS && case.a < 10 && case.b < 10).then(|| {
+            let mut histogram = [0; 10];
+            for i in 1..case.n {
+                histogram[term(case, i).get_lower_128() as usize % 10] += 1;
+            }
+            histogram[expected as usize % 10] += 1;
+            let circuit = HistogramCircuit {
+                a: fp(case.a),
+                b: fp(case.b),
+                histogram,
+            };
+            (circuit, vec![vec![Fp::from(PERMUTATION_CHALLENGE)]])
+        })
+    }
+}
+
+/// Registered circuits that are not seeded with `a, b`, and so have no
+/// place in the table.
+const UNSEEDED: [CircuitKind; 12] = [
+    CircuitKind::FibNonMember,
+    CircuitKind::Padovan,
+    CircuitKind::Catalan,
+    CircuitKind::HashChain,
+    CircuitKind::Horner,
+    CircuitKind::InnerProduct,
+    CircuitKind::MatMul,
+    CircuitKind::Memory,
+    CircuitKind::Permutation,
+    CircuitKind::SortingNetwork,
+    CircuitKind::SparseMerkle,
+    CircuitKind::Vm,
+];
+
+/// Runs every case through layout `L`, returning how many it laid out.
+fn run<L: Layout>(name: &str) -> usize {
+    let mut ran = 0;
+    for case in &CASES {
+        assert_eq!(
+            term(case, case.n),
+            Fp::from(case.expected),
+            "the table is wrong: {:?}",
+            case
+        );
+        let Some((circuit, instances)) = L::build(case, case.expected) else {
+            continue;
+        };
+        let k = case.k.max(L::MIN_K);
+
+        let prover = MockProver::run(k, &circuit, instances.clone()).unwrap();
+        assert_eq!(prover.verify(), Ok(()), "{} with {:?}", name, case);
+
+        let (wrong, wrong_instances) = L::build(case, case.expected + 1).unwrap();
+        let prover = MockProver::run(k, &wrong, wrong_instances).unwrap();
+        assert!(
+            prover.verify().is_err(),
+            "{} accepts {:?} off by one",
+            name,
+            case
+        );
+
+        if k <= PROVER_K {
+            let params = params::generate(k).unwrap();
+            let pk = prover::keygen(&params, &circuit).unwrap();
+            let proof = prover::create_proof(&params, &pk, circuit, &instances).unwrap();
+            prover::verify_proof(&params, pk.get_vk(), &proof, &instances)
+                .unwrap_or_else(|e| panic!("{} with {:?}: {:?}", name, case, e));
+        }
+        ran += 1;
+    }
+    ran
+}
+
+macro_rules! layouts {
+    ($($test:ident: $layout:ty,)*) => {
+        $(
+            #[test]
+            fn $test() {
+                assert!(run::<$layout>(stringify!($test)) > 0, "no case fits");
+            }
+        )*
+
+        /// Keeps the tests above and [`UNSEEDED`] in step with the
+        /// registry.
+        #[test]
+        fn every_circuit_is_covered() {
+            let mut covered: Vec<_> = [$(<$layout as Layout>::KIND),*]
+                .into_iter()
+                .flatten()
+                .chain(UNSEEDED)
+                .collect();
+            covered.sort_by_key(|kind| CircuitKind::ALL.iter().position(|k| k == kind));
+            assert_eq!(covered, CircuitKind::ALL);
+        }
+    };
+}
+
+layouts! {
+    three_column: ThreeColumn,
+    single_column: SingleColumn,
+    negative_rotation: NegativeRotation,
+    versioned: Versioned,
+    batch: Batch,
+    chunk_4: Chunk<4>,
+    chunk_8: Chunk<8>,
+    stride_2: Stride<2>,
+    stride_4: Stride<4>,
+    checkpoint: Checkpoint,
+    descent: Descent,
+    square_test: SquareTest,
+    parity: Parity,
+    last_digit: LastDigit,
+    bounded: Bounded,
+    fixed_selector: FixedSelector,
+    vertical: Vertical,
+    table: Table,
+    histogram: Histogram,
+}
@@ -0,0 +1,248 @@
+use fibonacci_circuit::{
+    circuits::memory::{self, Access, MemoryCircuit, MemoryConfig},
+    registry::{MEMORY_ACCESSES, MEMORY_WRITES},
+};
+use halo2_proofs::{
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    dev::{MockProver, VerifyFailure},
+    pasta::Fp,
+    plonk::{Circuit, ConstraintSystem, Error},
+};
+
+const K: u32 = 9;
+
+/// The demo run, with the value of every access.
+fn demo() -> (Vec<Access>, Vec<Fp>) {
+    let writes = MEMORY_WRITES.map(Fp::from);
+    let accesses = MEMORY_ACCESSES.to_vec();
+    let values = memory::run(&accesses, &writes);
+    (accesses, values)
+}
+
+/// The gates and lookups that fail, by name, with the reads of `values`
+/// as the public input.
+fn failures(circuit: &impl Circuit<Fp>, accesses: &[Access], values: &[Fp]) -> Vec<String> {
+    let instances = vec![memory::public_inputs(accesses, values)];
+    let prover = MockProver::run(K, circuit, instances).unwrap();
+    let mut names: Vec<_> = prover
+        .verify()
+        .err()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|failure| match failure {
+            VerifyFailure::ConstraintNotSatisfied { constraint, .. } => {
+                // "Constraint i ('name') in gate j ('gate')"
+                let constraint = constraint.to_string();
+                constraint.rsplit('\'').nth(1).unwrap().to_string()
+            }
+            VerifyFailure::Lookup { .. } => "lookup".to_string(),
+            VerifyFailure::Permutation { .. } => "permutation".to_string(),
+            other => other.to_string(),
+        })
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// The memory run witnessing `values`, whatever they are.
+fn run(accesses: &[Access], values: &[Fp]) -> Vec<String> {
+    let circuit = MemoryCircuit {
+        accesses: accesses.to_vec(),
+        values: values.iter().copied().map(Value::known).collect(),
+    };
+    failures(&circuit, accesses, values)
+}
+
+/// Lays out the memory as `MemoryCircuit` does, except that the sorted
+/// copy of each access's value is witnessed from `sorted` instead of being
+/// copied, and only then constrained equal to the trace's.
+#[derive(Debug, Clone)]
+struct ForgedSorted {
+    accesses: Vec<Access>,
+    /// The trace's values, in execution order.
+    values: Vec<Fp>,
+    /// The sorted copies' values, also in execution order.
+    sorted: Vec<Fp>,
+}
+
+impl Circuit<Fp> for ForgedSorted {
+    type Config = MemoryConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        self.clone()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>) -> MemoryConfig {
+        MemoryCircuit::<Fp>::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: MemoryConfig,
+        mut layouter: impl Layouter<Fp>,
+    ) -> Result<(), Error> {
+        config.table.load(layouter.namespace(|| "byte table"))?;
+        let mut order: Vec<usize> = (0..self.accesses.len()).collect();
+        order.sort_by_key(|&time| (self.accesses[time].addr(), time));
+
+        let reads = layouter.assign_region(
+            || "forged memory",
+            |mut region| {
+                let fields = |time: usize| {
+                    let access = self.accesses[time];
+                    [access.addr(), time as u64, access.is_write() as u64].map(Fp::from)
+                };
+                let mut trace = vec![];
+                for time in 0..self.accesses.len() {
+                    let mut cells = vec![];
+                    for (column, field) in config.trace.iter().zip(fields(time)) {
+                        cells.push(region.assign_fixed(
+                            || "trace",
+                            *column,
+                            time,
+                            || Value::known(field),
+                        )?);
+                    }
+                    let value = Value::known(self.values[time]);
+                    let value = region.assign_advice(|| "value", config.value, time, || value)?;
+                    trace.push((cells, value));
+                }
+
+                for (row, &time) in order.iter().enumerate() {
+                    if row == 0 {
+                        config.s_first.enable(&mut region, row)?;
+                    } else {
+                        config.q_next.enable(&mut region, row)?;
+                    }
+                    let (cells, value) = &trace[time];
+                    for ((cell, column), field) in
+                        cells.iter().zip(&config.sorted).zip(fields(time))
+                    {
+                        let copy = region.assign_advice(
+                            || "sorted",
+                            *column,
+                            row,
+                            || Value::known(field),
+                        )?;
+                        region.constrain_equal(cell.cell(), copy.cell())?;
+                    }
+                    let sorted = Value::known(self.sorted[time]);
+                    let copy = region.assign_advice(
+                        || "sorted value",
+                        config.sorted[3],
+                        row,
+                        || sorted,
+                    )?;
+                    region.constrain_equal(value.cell(), copy.cell())?;
+
+                    let addr = self.accesses[time].addr();
+                    let fresh = row == 0 || self.accesses[order[row - 1]].addr() != addr;
+                    region.assign_advice(
+                        || "fresh",
+                        config.sorted[4],
+                        row,
+                        || Value::known(Fp::from(fresh as u64)),
+                    )?;
+                }
+
+                Ok(self
+                    .accesses
+                    .iter()
+                    .zip(trace)
+                    .filter(|(access, _)| !access.is_write())
+                    .map(|(_, (_, value))| value)
+                    .collect::<Vec<_>>())
+            },
+        )?;
+
+        for (row, read) in reads.iter().enumerate() {
+            layouter.constrain_instance(read.cell(), config.instance, row)?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn the_demo_run_verifies() {
+    let (accesses, values) = demo();
+    assert_eq!(run(&accesses, &values), Vec::<String>::new());
+}
+
+#[test]
+fn a_stale_or_forged_read_is_rejected() {
+    let (accesses, values) = demo();
+    // Access 5 reads address 0 after its second write stored 9.
+    assert_eq!((accesses[5], values[5]), (Access::Read(0), Fp::from(9)));
+
+    // The value of the first write, with the public input claiming it too.
+    let mut stale = values.clone();
+    stale[5] = Fp::from(5);
+    assert_eq!(run(&accesses, &stale), ["memory consistency"]);
+
+    let mut forged = values;
+    forged[5] = Fp::from(42);
+    assert_eq!(run(&accesses, &forged), ["memory consistency"]);
+}
+
+#[test]
+fn a_first_read_of_a_fresh_address_sees_zero() {
+    let (accesses, mut values) = demo();
+    // Access 6 reads address 2, which is never written.
+    assert_eq!((accesses[6], values[6]), (Access::Read(2), Fp::zero()));
+    values[6] = Fp::from(3);
+    assert_eq!(run(&accesses, &values), ["memory consistency"]);
+
+    // The same on the first sorted row, which has its own gate.
+    let accesses = [Access::Read(0), Access::Write(0), Access::Read(0)];
+    let honest = memory::run(&accesses, &[Fp::from(7)]);
+    assert_eq!(run(&accesses, &honest), Vec::<String>::new());
+    let forged = [Fp::from(7), Fp::from(7), Fp::from(7)];
+    assert_eq!(run(&accesses, &forged), ["memory first access"]);
+}
+
+#[test]
+fn sorted_values_must_match_the_trace() {
+    let (accesses, values) = demo();
+    let honest = ForgedSorted {
+        accesses: accesses.clone(),
+        values: values.clone(),
+        sorted: values.clone(),
+    };
+    assert_eq!(failures(&honest, &accesses, &values), Vec::<String>::new());
+
+    // The trace and public input show a stale read, while the sorted copy,
+    // which the gates check, shows the right value.
+    let mut stale = values.clone();
+    stale[5] = Fp::from(5);
+    let forged = ForgedSorted {
+        accesses: accesses.clone(),
+        values: stale.clone(),
+        sorted: values,
+    };
+    assert_eq!(failures(&forged, &accesses, &stale), ["permutation"]);
+}
+
+#[test]
+fn gaps_must_fit_in_a_byte() {
+    // 256 apart is the most.
+    let near = [Access::Write(0), Access::Write(256), Access::Read(0)];
+    let values = memory::run(&near, &[Fp::from(1), Fp::from(2)]);
+    assert_eq!(run(&near, &values), Vec::<String>::new());
+
+    let far = [Access::Write(0), Access::Write(1000), Access::Read(0)];
+    let values = memory::run(&far, &[Fp::from(1), Fp::from(2)]);
+    assert_eq!(run(&far, &values), ["lookup"]);
+
+    // Address 0 left alone for 257 steps.
+    let mut idle = vec![Access::Write(0)];
+    idle.extend([Access::Write(1); 256]);
+    idle.push(Access::Read(0));
+    let writes = vec![Fp::one(); 257];
+    let values = memory::run(&idle, &writes);
+    assert_eq!(run(&idle, &values), ["lookup"]);
+    idle.remove(1);
+    let values = memory::run(&idle, &writes);
+    assert_eq!(run(&idle, &values), Vec::<String>::new());
+}
@@ -0,0 +1,85 @@
+use fibonacci_circuit::{
+    metrics::ProverMetrics,
+    progress::{Event, Phase},
+};
+
+fn phase(phase: Phase, elapsed_ms: f64) -> Event {
+    Event::Phase { phase, elapsed_ms }
+}
+
+fn run() -> Vec<Event> {
+    vec![
+        phase(Phase::Synthesis, 0.0),
+        Event::Rows { rows: 4, usable: 9 },
+        phase(Phase::Keygen, 20.0),
+        phase(Phase::Proving, 220.0),
+        phase(Phase::Done, 2220.0),
+    ]
+}
+
+#[test]
+fn counts_runs_by_circuit_and_outcome() {
+    let metrics = ProverMetrics::new();
+    metrics.record("fib-three-column", &run(), true);
+    metrics.record("fib-three-column", &run(), true);
+    metrics.record("fib-three-column", &run()[..2], false);
+    let text = metrics.render();
+
+    assert!(text.contains("# TYPE fibo_proofs_total counter\n"));
+    assert!(
+        text.contains("fibo_proofs_total{circuit=\"fib-three-column\",outcome=\"success\"} 2\n")
+    );
+    assert!(
+        text.contains("fibo_proofs_total{circuit=\"fib-three-column\",outcome=\"failure\"} 1\n")
+    );
+}
+
+#[test]
+fn phases_last_until_the_next_starts() {
+    let metrics = ProverMetrics::new();
+    metrics.record("fib-three-column", &run(), true);
+    let text = metrics.render();
+
+    assert!(
+        text.contains("fibo_phase_duration_seconds_bucket{phase=\"synthesis\",le=\"0.01\"} 0\n")
+    );
+    assert!(
+        text.contains("fibo_phase_duration_seconds_bucket{phase=\"synthesis\",le=\"0.05\"} 1\n")
+    );
+    assert!(text.contains("fibo_phase_duration_seconds_bucket{phase=\"keygen\",le=\"0.5\"} 1\n"));
+    assert!(text.contains("fibo_phase_duration_seconds_bucket{phase=\"proving\",le=\"1\"} 0\n"));
+    assert!(text.contains("fibo_phase_duration_seconds_bucket{phase=\"proving\",le=\"+Inf\"} 1\n"));
+    assert!(text.contains("fibo_phase_duration_seconds_sum{phase=\"proving\"} 2\n"));
+    assert!(text.contains("fibo_phase_duration_seconds_count{phase=\"keygen\"} 1\n"));
+    // Done ends the run rather than starting a phase of its own.
+    assert!(!text.contains("phase=\"done\""));
+}
+
+#[test]
+fn a_failed_run_has_no_duration_for_the_phase_it_stopped_in() {
+    let metrics = ProverMetrics::new();
+    metrics.record("fib-three-column", &run()[..3], false);
+    let text = metrics.render();
+
+    assert!(text.contains("fibo_phase_duration_seconds_count{phase=\"synthesis\"} 1\n"));
+    assert!(!text.contains("phase=\"keygen\""));
+}
+
+#[test]
+fn reports_the_queue_depth() {
+    let metrics = ProverMetrics::new();
+    assert!(metrics.render().contains("fibo_queue_depth 0\n"));
+    metrics.set_queue_depth(3);
+    assert!(metrics
+        .render()
+        .contains("# TYPE fibo_queue_depth gauge\nfibo_queue_depth 3\n"));
+}
+
+#[test]
+fn escapes_label_values() {
+    let metrics = ProverMetrics::new();
+    metrics.record("a\"b\\c\nd", &[], true);
+    assert!(metrics
+        .render()
+        .contains("fibo_proofs_total{circuit=\"a\\\"b\\\\c\\nd\",outcome=\"success\"} 1\n"));
+}
@@ -0,0 +1,203 @@
+use fibonacci_circuit::gadgets::{
+    mul64::{Mul64Chip, Mul64Config},
+    u64::U64Chip,
+};
+use halo2_proofs::{
+    arithmetic::FieldExt,
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    dev::MockProver,
+    pasta::Fp,
+    plonk::{Advice, Circuit, Column, ConstraintSystem, Error, Instance},
+};
+
+#[derive(Debug, Clone)]
+struct Config {
+    input: Column<Advice>,
+    instance: Column<Instance>,
+    mul: Mul64Config,
+}
+
+fn configure(meta: &mut ConstraintSystem<Fp>) -> Config {
+    let input = meta.advice_column();
+    meta.enable_equality(input);
+    let instance = meta.instance_column();
+    meta.enable_equality(instance);
+    let advice = [(); 4].map(|_| meta.advice_column());
+    let range = meta.advice_column();
+    Config {
+        input,
+        instance,
+        mul: Mul64Chip::configure(meta, advice, range),
+    }
+}
+
+/// Multiplies `a` and `b`, exposing `lo` and `hi` at instance rows 0 and 1.
+#[derive(Debug, Default)]
+struct Mul64Circuit {
+    a: u64,
+    b: u64,
+}
+
+impl Circuit<Fp> for Mul64Circuit {
+    type Config = Config;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>) -> Config {
+        configure(meta)
+    }
+
+    fn synthesize(&self, config: Config, mut layouter: impl Layouter<Fp>) -> Result<(), Error> {
+        let (a, b) = layouter.assign_region(
+            || "inputs",
+            |mut region| {
+                let a = region.assign_advice(
+                    || "a",
+                    config.input,
+                    0,
+                    || Value::known(Fp::from(self.a)),
+                )?;
+                let b = region.assign_advice(
+                    || "b",
+                    config.input,
+                    1,
+                    || Value::known(Fp::from(self.b)),
+                )?;
+                Ok((a, b))
+            },
+        )?;
+        let range = U64Chip::construct(config.mul.range.clone());
+        let a = range.range_check(layouter.namespace(|| "a range"), &a)?;
+        let b = range.range_check(layouter.namespace(|| "b range"), &b)?;
+
+        let chip = Mul64Chip::construct(config.mul);
+        let product = chip.mul(layouter.namespace(|| "mul"), &a, &b)?;
+        layouter.constrain_instance(product.lo.cell().cell(), config.instance, 0)?;
+        layouter.constrain_instance(product.hi.cell().cell(), config.instance, 1)
+    }
+}
+
+/// Lays out the multiplication row with arbitrary limbs, range-checking
+/// them like the chip does.
+#[derive(Debug, Default)]
+struct ForgedCircuit {
+    a: Fp,
+    b: Fp,
+    lo: Fp,
+    hi: Fp,
+}
+
+impl Circuit<Fp> for ForgedCircuit {
+    type Config = Config;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>) -> Config {
+        configure(meta)
+    }
+
+    fn synthesize(&self, config: Config, mut layouter: impl Layouter<Fp>) -> Result<(), Error> {
+        let cfg = config.mul;
+        let cells = layouter.assign_region(
+            || "forged mul64",
+            |mut region| {
+                cfg.selector.enable(&mut region, 0)?;
+                cfg.advice
+                    .iter()
+                    .zip([self.a, self.b, self.lo, self.hi])
+                    .map(|(column, value)| {
+                        region.assign_advice(|| "forged", *column, 0, || Value::known(value))
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            },
+        )?;
+        let range = U64Chip::construct(cfg.range);
+        for cell in &cells {
+            range.range_check(layouter.namespace(|| "range"), cell)?;
+        }
+        Ok(())
+    }
+}
+
+const K: u32 = 9;
+
+const EDGES: [u64; 7] = [0, 1, 2, 1 << 32, (1 << 32) - 1, 1 << 63, u64::MAX];
+
+#[test]
+fn edge_cases() {
+    for a in EDGES {
+        for b in EDGES {
+            let product = a as u128 * b as u128;
+            let lo = Fp::from(product as u64);
+            let hi = Fp::from((product >> 64) as u64);
+            let circuit = Mul64Circuit { a, b };
+
+            let prover = MockProver::run(K, &circuit, vec![vec![lo, hi]]).unwrap();
+            assert_eq!(prover.verify(), Ok(()), "{} * {}", a, b);
+
+            let prover = MockProver::run(K, &circuit, vec![vec![hi, lo]]).unwrap();
+            assert_eq!(prover.verify().is_err(), lo != hi, "{} * {} swapped", a, b);
+        }
+    }
+}
+
+#[test]
+fn max_times_max() {
+    // (2^64 - 1)^2 = 2^128 - 2^65 + 1
+    let circuit = Mul64Circuit {
+        a: u64::MAX,
+        b: u64::MAX,
+    };
+    let expected = vec![vec![Fp::one(), Fp::from(u64::MAX - 1)]];
+    let prover = MockProver::run(K, &circuit, expected).unwrap();
+    assert_eq!(prover.verify(), Ok(()));
+}
+
+#[test]
+fn non_canonical_splits_are_rejected() {
+    let two_pow_64 = Fp::from_u128(1 << 64);
+    let a = Fp::from(u64::MAX);
+    let b = Fp::from(3);
+    // 3·(2^64 - 1) = 2^64·2 + (2^64 - 3)
+    let (lo, hi) = (Fp::from(u64::MAX - 2), Fp::from(2));
+
+    let honest = ForgedCircuit { a, b, lo, hi };
+    assert_eq!(
+        MockProver::run(K, &honest, vec![vec![]]).unwrap().verify(),
+        Ok(())
+    );
+
+    let forgeries = [
+        // lo carries an extra 2^64 out of hi.
+        ForgedCircuit {
+            a,
+            b,
+            lo: lo + two_pow_64,
+            hi: hi - Fp::one(),
+        },
+        // hi goes negative, wrapping around the field.
+        ForgedCircuit {
+            a,
+            b,
+            lo: lo + two_pow_64 * Fp::from(3),
+            hi: hi - Fp::from(3),
+        },
+        // Limbs that do not multiply out.
+        ForgedCircuit {
+            a,
+            b,
+            lo: lo + Fp::one(),
+            hi,
+        },
+    ];
+    for circuit in forgeries {
+        let prover = MockProver::run(K, &circuit, vec![vec![]]).unwrap();
+        assert!(prover.verify().is_err(), "accepted {:?}", circuit);
+    }
+}
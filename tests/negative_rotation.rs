@@ -0,0 +1,117 @@
+use fibonacci_circuit::{
+    cost::CostReport,
+    fibonacci::{negative_rotation, single_column, NUM_TERMS},
+    reference::fib_field,
+};
+use halo2_proofs::{
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    dev::{MockProver, VerifyFailure},
+    pasta::Fp,
+    plonk::{Advice, Circuit, Column, ConstraintSystem, Error, Selector},
+    poly::Rotation,
+};
+
+const K: u32 = 4;
+
+/// The negative-rotation gate with the selector on every row, the seeds'
+/// included, which wraps round to the end of the column.
+#[derive(Default)]
+struct EnabledFromRowZero;
+
+impl Circuit<Fp> for EnabledFromRowZero {
+    type Config = (Column<Advice>, Selector);
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+        let advice = meta.advice_column();
+        let selector = meta.selector();
+        meta.create_gate("fibonacci from the rows before", |meta| {
+            let a = meta.query_advice(advice, Rotation(-2));
+            let b = meta.query_advice(advice, Rotation::prev());
+            let c = meta.query_advice(advice, Rotation::cur());
+            vec![meta.query_selector(selector) * (a + b - c)]
+        });
+        (advice, selector)
+    }
+
+    fn synthesize(
+        &self,
+        (advice, selector): Self::Config,
+        mut layouter: impl Layouter<Fp>,
+    ) -> Result<(), Error> {
+        layouter.assign_region(
+            || "terms",
+            |mut region| {
+                let (mut a, mut b) = (Fp::one(), Fp::one());
+                for row in 0..NUM_TERMS {
+                    selector.enable(&mut region, row)?;
+                    region.assign_advice(|| "term", advice, row, || Value::known(a))?;
+                    (a, b) = (b, a + b);
+                }
+                Ok(())
+            },
+        )
+    }
+}
+
+fn instances() -> Vec<Vec<Fp>> {
+    vec![vec![fib_field(Fp::one(), Fp::one(), NUM_TERMS)]]
+}
+
+#[test]
+fn gate_reads_the_two_rows_before() {
+    let circuit = negative_rotation::FiboCircuit {
+        a: Value::known(Fp::one()),
+        b: Value::known(Fp::one()),
+    };
+    let prover = MockProver::run(K, &circuit, instances()).unwrap();
+    assert_eq!(prover.verify(), Ok(()));
+
+    let wrong = vec![vec![Fp::from(54)]];
+    let prover = MockProver::run(K, &circuit, wrong).unwrap();
+    assert!(prover.verify().is_err());
+}
+
+#[test]
+fn selector_on_the_seeds_wraps_round() {
+    let prover = MockProver::run(K, &EnabledFromRowZero, vec![]).unwrap();
+    let unassigned: Vec<_> = prover
+        .verify()
+        .unwrap_err()
+        .into_iter()
+        .filter_map(|failure| match failure {
+            VerifyFailure::CellNotAssigned {
+                gate_offset,
+                offset,
+                ..
+            } => Some((gate_offset, offset)),
+            _ => None,
+        })
+        .collect();
+    // Rows 0 and 1 read the last two rows of the 16, which nothing assigns.
+    assert_eq!(unassigned, [(0, 14), (0, 15), (1, 15)]);
+}
+
+#[test]
+fn costs_the_same_as_looking_ahead() {
+    let back = CostReport::try_measure(
+        "back",
+        K,
+        &negative_rotation::FiboCircuit::<Fp>::default(),
+        instances(),
+    )
+    .unwrap();
+    let ahead = CostReport::try_measure(
+        "ahead",
+        K,
+        &single_column::FiboCircuit::<Fp>::default(),
+        instances(),
+    )
+    .unwrap();
+    assert_eq!(back.proof.bytes, ahead.proof.bytes);
+    assert_eq!(back.blinding_factors, ahead.blinding_factors);
+}
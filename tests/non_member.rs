@@ -0,0 +1,56 @@
+use fibonacci_circuit::{
+    fibonacci::non_member::{gap_bits, terms, NonMemberCircuit, MAX_TERMS},
+    reference::fib_biguint,
+    registry::NON_MEMBER_VALUE,
+};
+use halo2_proofs::{circuit::Value, dev::MockProver, pasta::Fp};
+use num_bigint::BigUint;
+
+const K: u32 = 8;
+
+fn verify<const N: usize>(v: Fp) -> bool {
+    let circuit = NonMemberCircuit::<Fp, N> { v: Value::known(v) };
+    let prover = MockProver::run(K, &circuit, vec![vec![v]]).unwrap();
+    prover.verify().is_ok()
+}
+
+#[test]
+fn terms_agree_with_the_reference() {
+    let terms = terms(MAX_TERMS);
+    assert_eq!(terms.len(), MAX_TERMS + 1);
+    for (n, &t) in terms.iter().enumerate() {
+        assert_eq!(BigUint::from(t), fib_biguint(n));
+    }
+    assert!(fib_biguint(MAX_TERMS + 1).bits() > 64);
+    assert_eq!(gap_bits(10), 6);
+    assert_eq!(gap_bits(MAX_TERMS), 64);
+}
+
+#[test]
+fn demo_value_is_not_a_term() {
+    assert!(!terms(20).contains(&NON_MEMBER_VALUE));
+}
+
+#[test]
+fn accepts_every_non_member_below_the_bound() {
+    let terms = terms(10);
+    for v in 0..terms[10] {
+        assert_eq!(verify::<10>(Fp::from(v)), !terms.contains(&v), "{}", v);
+    }
+}
+
+#[test]
+fn rejects_values_at_or_past_the_bound() {
+    for v in [55, 56, 100, u64::MAX] {
+        assert!(!verify::<10>(Fp::from(v)), "{}", v);
+    }
+    assert!(!verify::<10>(-Fp::from(4)));
+}
+
+#[test]
+fn covers_the_largest_table() {
+    let terms = terms(MAX_TERMS);
+    assert!(verify::<MAX_TERMS>(Fp::from(terms[92] + 1)));
+    assert!(!verify::<MAX_TERMS>(Fp::from(terms[92])));
+    assert!(!verify::<MAX_TERMS>(Fp::from(terms[93])));
+}
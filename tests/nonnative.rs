@@ -0,0 +1,188 @@
+use fibonacci_circuit::gadgets::{
+    bytes::{ByteTable, BytesChip},
+    nonnative::{from_limbs, ForeignModulus, NonNativeChip, NonNativeConfig, Secp256k1Base},
+};
+use halo2_proofs::{
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    dev::MockProver,
+    pasta::Fp,
+    plonk::{Circuit, Column, ConstraintSystem, Error, Instance},
+};
+use num_bigint::BigUint;
+use rand_core::{OsRng, RngCore};
+
+type Chip = NonNativeChip<Fp, Secp256k1Base>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Add,
+    Mul,
+}
+
+#[derive(Debug, Clone)]
+struct Config {
+    nonnative: NonNativeConfig,
+    instance: Column<Instance>,
+}
+
+/// Loads `a` and `b`, applies `op` and exposes the result's limbs.
+#[derive(Debug, Clone)]
+struct OpCircuit {
+    a: Value<BigUint>,
+    b: Value<BigUint>,
+    op: Op,
+}
+
+impl Circuit<Fp> for OpCircuit {
+    type Config = Config;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        OpCircuit {
+            a: Value::unknown(),
+            b: Value::unknown(),
+            op: self.op,
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>) -> Config {
+        let table = ByteTable::configure(meta);
+        let (z, byte) = (meta.advice_column(), meta.advice_column());
+        let bytes = BytesChip::configure(meta, z, byte, table);
+        let instance = meta.instance_column();
+        meta.enable_equality(instance);
+        Config {
+            nonnative: Chip::configure(meta, bytes),
+            instance,
+        }
+    }
+
+    fn synthesize(&self, config: Config, mut layouter: impl Layouter<Fp>) -> Result<(), Error> {
+        config
+            .nonnative
+            .bytes
+            .table
+            .load(layouter.namespace(|| "bytes"))?;
+        let chip = Chip::construct(config.nonnative);
+        let a = chip.load(layouter.namespace(|| "a"), self.a.clone())?;
+        let b = chip.load(layouter.namespace(|| "b"), self.b.clone())?;
+        let c = match self.op {
+            Op::Add => chip.add(layouter.namespace(|| "add"), &a, &b)?,
+            Op::Mul => chip.mul(layouter.namespace(|| "mul"), &a, &b)?,
+        };
+        for (row, limb) in c.limbs.iter().enumerate() {
+            layouter.constrain_instance(limb.cell(), config.instance, row)?;
+        }
+        Ok(())
+    }
+}
+
+const K: u32 = 10;
+
+fn p() -> BigUint {
+    Secp256k1Base::modulus()
+}
+
+fn limbs(x: &BigUint) -> Vec<Fp> {
+    let mut digits = x.to_u64_digits();
+    digits.resize(4, 0);
+    digits.into_iter().map(Fp::from).collect()
+}
+
+fn random() -> BigUint {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    BigUint::from_bytes_le(&bytes) % p()
+}
+
+fn run(a: &BigUint, b: &BigUint, op: Op, claimed: &BigUint) -> Result<(), String> {
+    let circuit = OpCircuit {
+        a: Value::known(a.clone()),
+        b: Value::known(b.clone()),
+        op,
+    };
+    MockProver::run(K, &circuit, vec![limbs(claimed)])
+        .map_err(|e| format!("{:?}", e))?
+        .verify()
+        .map_err(|e| format!("{} failures", e.len()))
+}
+
+fn expected(a: &BigUint, b: &BigUint, op: Op) -> BigUint {
+    match op {
+        Op::Add => (a + b) % p(),
+        Op::Mul => (a * b) % p(),
+    }
+}
+
+#[test]
+fn modulus_limbs() {
+    let p = (BigUint::from(1u8) << 256) - (BigUint::from(1u8) << 32) - BigUint::from(977u32);
+    assert_eq!(Secp256k1Base::modulus(), p);
+    assert_eq!(from_limbs(Secp256k1Base::LIMBS), p);
+}
+
+#[test]
+fn random_operands() {
+    for op in [Op::Add, Op::Mul] {
+        for _ in 0..4 {
+            let (a, b) = (random(), random());
+            assert_eq!(run(&a, &b, op, &expected(&a, &b, op)), Ok(()), "{:?}", op);
+        }
+    }
+}
+
+#[test]
+fn edge_operands() {
+    let one = BigUint::from(1u8);
+    let edges = [
+        BigUint::from(0u8),
+        one.clone(),
+        BigUint::from(u64::MAX),
+        one.clone() << 64,
+        p() - &one,
+        p() - BigUint::from(2u8),
+    ];
+    for op in [Op::Add, Op::Mul] {
+        for a in &edges {
+            for b in &edges {
+                assert_eq!(
+                    run(a, b, op, &expected(a, b, op)),
+                    Ok(()),
+                    "{:?} {} {}",
+                    op,
+                    a,
+                    b
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn wrong_results_are_rejected() {
+    let (a, b) = (random(), random());
+    for op in [Op::Add, Op::Mul] {
+        let c = expected(&a, &b, op);
+        let wrong = (&c + 1u8) % p();
+        assert!(run(&a, &b, op, &wrong).is_err(), "{:?}", op);
+    }
+}
+
+#[test]
+fn non_canonical_inputs_are_rejected() {
+    // p itself and p + 1 are congruent to 0 and 1 but not canonical.
+    let zero = BigUint::from(0u8);
+    assert!(run(&p(), &zero, Op::Add, &zero).is_err());
+    let one = BigUint::from(1u8);
+    assert!(run(&(p() + &one), &one, Op::Mul, &one).is_err());
+}
+
+#[test]
+fn largest_product() {
+    // The largest carries stay within the offset's range.
+    let max = p() - 1u8;
+    assert_eq!(
+        run(&max, &max, Op::Mul, &expected(&max, &max, Op::Mul)),
+        Ok(())
+    );
+}
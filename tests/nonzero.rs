@@ -0,0 +1,93 @@
+use fibonacci_circuit::gadgets::{
+    nonzero::{NonZeroChip, NonZeroConfig},
+    simple::Operand,
+};
+use halo2_proofs::{
+    arithmetic::Field,
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    dev::MockProver,
+    pasta::Fp,
+    plonk::{Circuit, ConstraintSystem, Error},
+};
+
+const K: u32 = 4;
+
+/// Asserts `x` is nonzero, through [`NonZeroChip::assert_nonzero`] or, with
+/// `inverse` set, by laying out the row with that inverse.
+#[derive(Debug, Default)]
+struct NonZeroCircuit {
+    x: Fp,
+    inverse: Option<Fp>,
+}
+
+impl Circuit<Fp> for NonZeroCircuit {
+    type Config = NonZeroConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+        let (x, x_inv) = (meta.advice_column(), meta.advice_column());
+        NonZeroChip::configure(meta, x, x_inv)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<Fp>,
+    ) -> Result<(), Error> {
+        let x_column = config.x;
+        let chip = NonZeroChip::construct(config);
+        let x = layouter.assign_region(
+            || "x",
+            |mut region| region.assign_advice(|| "x", x_column, 0, || Value::known(self.x)),
+        )?;
+        match self.inverse {
+            None => {
+                chip.assert_nonzero(layouter.namespace(|| "nonzero"), &x)?;
+            }
+            Some(x_inv) => {
+                chip.assign_row(
+                    layouter.namespace(|| "forged"),
+                    [Operand::Cell(&x), Operand::Witness(Value::known(x_inv))],
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn verify(x: Fp, inverse: Option<Fp>) -> bool {
+    let circuit = NonZeroCircuit { x, inverse };
+    MockProver::run(K, &circuit, vec![])
+        .unwrap()
+        .verify()
+        .is_ok()
+}
+
+#[test]
+fn nonzero_values_are_accepted() {
+    for x in [Fp::one(), Fp::from(7), -Fp::one()] {
+        assert!(verify(x, None));
+        assert!(verify(x, Some(x.invert().unwrap())));
+    }
+}
+
+#[test]
+fn zero_is_rejected() {
+    assert!(!verify(Fp::zero(), None));
+    // No inverse makes 0 · x_inv = 1.
+    for x_inv in [Fp::zero(), Fp::one(), Fp::from(7)] {
+        assert!(!verify(Fp::zero(), Some(x_inv)));
+    }
+}
+
+#[test]
+fn forged_inverse_is_rejected() {
+    let x = Fp::from(7);
+    for x_inv in [Fp::zero(), Fp::one(), x, x.invert().unwrap() + Fp::one()] {
+        assert!(!verify(x, Some(x_inv)), "x_inv = {:?}", x_inv);
+    }
+}
@@ -0,0 +1,95 @@
+use fibonacci_circuit::{
+    fibonacci::{negative_rotation, single_column, three_column, NUM_TERMS},
+    introspect::witness::Witness,
+    occupancy::{Occupancy, OccupancyReport},
+    reference::fib_field,
+};
+use halo2_proofs::{circuit::Value, pasta::Fp};
+
+const K: u32 = 4;
+
+fn instances() -> Vec<Vec<Fp>> {
+    vec![vec![fib_field(Fp::one(), Fp::one(), NUM_TERMS)]]
+}
+
+#[test]
+fn three_column_fills_one_row_per_region() {
+    let circuit = three_column::FiboCircuit {
+        a: Value::known(Fp::one()),
+        b: Value::known(Fp::one()),
+    };
+    let witness = Witness::synthesize(K, &circuit, instances()).unwrap();
+    let report = OccupancyReport::of("fib-three-column", &witness);
+
+    assert_eq!(report.entries.len(), 3 * (NUM_TERMS - 2));
+    for (i, entry) in report.entries.iter().enumerate() {
+        assert_eq!(entry.region, i / 3);
+        assert_eq!(entry.column, format!("A{}", i % 3));
+        assert_eq!((entry.start, entry.length, entry.cells), (i / 3, 1, 1));
+    }
+}
+
+#[test]
+fn one_term_per_row_takes_less_than_half_the_area() {
+    let (a, b) = (Value::known(Fp::one()), Value::known(Fp::one()));
+    let cells = |witness: Witness| OccupancyReport::of("", &witness).cells();
+    let three =
+        cells(Witness::synthesize(K, &three_column::FiboCircuit { a, b }, instances()).unwrap());
+    let single =
+        cells(Witness::synthesize(K, &single_column::FiboCircuit { a, b }, instances()).unwrap());
+    let back = cells(
+        Witness::synthesize(K, &negative_rotation::FiboCircuit { a, b }, instances()).unwrap(),
+    );
+
+    assert_eq!(
+        (three, single, back),
+        (3 * (NUM_TERMS - 2), NUM_TERMS, NUM_TERMS)
+    );
+    assert!(2 * single < three);
+}
+
+#[test]
+fn single_column_is_one_region() {
+    let circuit = single_column::FiboCircuit {
+        a: Value::known(Fp::one()),
+        b: Value::known(Fp::one()),
+    };
+    let witness = Witness::synthesize(K, &circuit, instances()).unwrap();
+    let csv = OccupancyReport::of("fib-single-column", &witness).to_csv();
+
+    assert_eq!(
+        csv,
+        format!(
+            "region,name,column,start,length,cells\n0,entire fibonacci table,A0,0,{0},{0}\n",
+            NUM_TERMS
+        )
+    );
+}
+
+#[test]
+fn names_are_quoted_when_needed() {
+    let entry = |name: &str| Occupancy {
+        region: 0,
+        name: name.to_string(),
+        column: "A0".to_string(),
+        start: 3,
+        length: 4,
+        cells: 2,
+    };
+    let report = OccupancyReport {
+        circuit: "test".to_string(),
+        k: K,
+        usable_rows: 10,
+        entries: vec![entry("plain"), entry("a, b"), entry("say \"hi\"")],
+    };
+
+    let lines: Vec<_> = report.to_csv().lines().skip(1).map(String::from).collect();
+    assert_eq!(
+        lines,
+        [
+            "0,plain,A0,3,4,2",
+            "0,\"a, b\",A0,3,4,2",
+            "0,\"say \"\"hi\"\"\",A0,3,4,2",
+        ]
+    );
+}
@@ -0,0 +1,131 @@
+use fibonacci_circuit::{
+    circuits::padovan::{self, PadovanCircuit, PadovanConfig},
+    fibonacci::NUM_TERMS,
+};
+use halo2_proofs::{
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    dev::{MockProver, VerifyFailure},
+    pasta::Fp,
+    plonk::{Circuit, ConstraintSystem, Error},
+};
+
+const K: u32 = 4;
+const SEEDS: [u64; 3] = [1, 1, 1];
+
+/// Lays out arbitrary `terms` as the chip does, exposing the last.
+#[derive(Debug, Default, Clone)]
+struct ForgedCircuit {
+    terms: Vec<Fp>,
+}
+
+impl ForgedCircuit {
+    /// The table the chip would lay out.
+    fn honest() -> Self {
+        let seeds = SEEDS.map(Fp::from);
+        ForgedCircuit {
+            terms: (1..=NUM_TERMS)
+                .map(|n| padovan::nth_term(seeds, n))
+                .collect(),
+        }
+    }
+}
+
+impl Circuit<Fp> for ForgedCircuit {
+    type Config = PadovanConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        self.clone()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>) -> PadovanConfig {
+        PadovanCircuit::<Fp>::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: PadovanConfig,
+        mut layouter: impl Layouter<Fp>,
+    ) -> Result<(), Error> {
+        let last = layouter.assign_region(
+            || "forged padovan table",
+            |mut region| {
+                let mut last = None;
+                for (row, term) in self.terms.iter().enumerate() {
+                    if row >= 3 {
+                        config.selector.enable(&mut region, row)?;
+                    }
+                    let cell = region.assign_advice(
+                        || "term",
+                        config.advice,
+                        row,
+                        || Value::known(*term),
+                    )?;
+                    last = Some(cell);
+                }
+                Ok(last.unwrap())
+            },
+        )?;
+        layouter.constrain_instance(last.cell(), config.instance, 0)
+    }
+}
+
+/// The gates that fail, by name, with the honest last term public.
+fn failures(circuit: &ForgedCircuit) -> Vec<String> {
+    let last = *ForgedCircuit::honest().terms.last().unwrap();
+    let prover = MockProver::run(K, circuit, vec![vec![last]]).unwrap();
+    let mut names: Vec<_> = prover
+        .verify()
+        .err()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|failure| match failure {
+            VerifyFailure::ConstraintNotSatisfied { constraint, .. } => {
+                // "Constraint i ('name') in gate j ('gate')"
+                let constraint = constraint.to_string();
+                constraint.rsplit('\'').nth(1).unwrap().to_string()
+            }
+            VerifyFailure::Permutation { .. } => "permutation".to_string(),
+            other => other.to_string(),
+        })
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+#[test]
+fn the_honest_table_verifies() {
+    assert_eq!(failures(&ForgedCircuit::honest()), Vec::<String>::new());
+    let circuit = PadovanCircuit {
+        seeds: SEEDS.map(|seed| Value::known(Fp::from(seed))),
+    };
+    let last = *ForgedCircuit::honest().terms.last().unwrap();
+    let prover = MockProver::run(K, &circuit, vec![vec![last]]).unwrap();
+    assert_eq!(prover.verify(), Ok(()));
+}
+
+#[test]
+fn forged_intermediate_terms_are_rejected() {
+    let honest = ForgedCircuit::honest();
+    let last = NUM_TERMS - 1;
+
+    // One term off, the last still the honest one.
+    let mut forged = honest.clone();
+    forged.terms[5] += Fp::one();
+    assert_eq!(failures(&forged), ["padovan"]);
+
+    // Every term after a forged one follows from it, except the last, which
+    // is put back; only the last row's gate is left to notice.
+    let mut forged = honest.clone();
+    forged.terms[5] += Fp::one();
+    for row in 6..last {
+        forged.terms[row] = forged.terms[row - 3] + forged.terms[row - 2];
+    }
+    assert_ne!(
+        forged.terms[last - 3] + forged.terms[last - 2],
+        honest.terms[last]
+    );
+    assert_eq!(forged.terms[last], honest.terms[last]);
+    assert_eq!(failures(&forged), ["padovan"]);
+}
@@ -0,0 +1,46 @@
+use fibonacci_circuit::{
+    fibonacci::{nth_term, parity::FiboParityCircuit, NUM_TERMS},
+    gadgets::u64::to_u64,
+};
+use halo2_proofs::{arithmetic::Field, circuit::Value, dev::MockProver, pasta::Fp};
+
+const K: u32 = 7;
+
+fn run(a: u64, b: u64, bit: u64) -> bool {
+    let circuit = FiboParityCircuit {
+        a: Value::known(Fp::from(a)),
+        b: Value::known(Fp::from(b)),
+    };
+    let prover = MockProver::run(K, &circuit, vec![vec![Fp::from(bit)]]).unwrap();
+    prover.verify().is_ok()
+}
+
+#[test]
+fn exposes_the_parity_of_the_last_term() {
+    for (a, b) in [(1, 1), (2, 3), (4, 6), (7, 2)] {
+        let last = to_u64(nth_term(Fp::from(a), Fp::from(b), NUM_TERMS)).unwrap();
+        assert!(run(a, b, last & 1), "seeds {}, {}", a, b);
+        assert!(!run(a, b, 1 - (last & 1)), "seeds {}, {}", a, b);
+    }
+}
+
+#[test]
+fn either_bit_splits_in_the_field() {
+    // Without the range check, `x = 2·h + bit` holds for both bits; only
+    // the true one leaves `h` small.
+    let x = nth_term(Fp::one(), Fp::one(), NUM_TERMS);
+    let half = Fp::from(2).invert().unwrap();
+    let h = |bit: u64| (x - Fp::from(bit)) * half;
+
+    assert_eq!(h(0) * Fp::from(2), x);
+    assert_eq!(h(1) * Fp::from(2) + Fp::one(), x);
+    assert_eq!(to_u64(h(1)), Some(27));
+    assert_eq!(to_u64(h(0)), None);
+}
+
+#[test]
+fn terms_above_two_to_the_65_have_no_witness() {
+    let a = 1 << 60;
+    assert!(!run(a, a, 0));
+    assert!(!run(a, a, 1));
+}
@@ -0,0 +1,56 @@
+use fibonacci_circuit::circuits::permutation::{
+    instances, PermutationCircuit, PERMUTATION_CHALLENGE,
+};
+use halo2_proofs::{arithmetic::Field, circuit::Value, dev::MockProver, pasta::Fp};
+use rand_core::OsRng;
+
+const K: u32 = 5;
+
+fn run(gamma: Fp, list: [u64; 5], permuted: [u64; 5]) -> bool {
+    let circuit = PermutationCircuit {
+        permuted: permuted.map(|x| Value::known(Fp::from(x))),
+    };
+    let instances = instances(gamma, &list.map(Fp::from));
+    let prover = MockProver::run(K, &circuit, instances).unwrap();
+    prover.verify().is_ok()
+}
+
+#[test]
+fn accepts_permutations() {
+    let gamma = Fp::from(PERMUTATION_CHALLENGE);
+    assert!(run(gamma, [1, 2, 3, 4, 5], [5, 4, 3, 2, 1]));
+    assert!(run(gamma, [7, 7, 0, 7, 1], [0, 7, 1, 7, 7]));
+    assert!(run(Fp::random(OsRng), [1, 2, 3, 4, 5], [2, 3, 1, 5, 4]));
+}
+
+#[test]
+fn rejects_other_multisets() {
+    let gamma = Fp::random(OsRng);
+    // Same sum, same set, different multiplicities.
+    assert!(!run(gamma, [1, 2, 3, 4, 5], [1, 2, 3, 3, 6]));
+    assert!(!run(gamma, [7, 7, 0, 7, 1], [7, 0, 0, 7, 1]));
+}
+
+#[test]
+fn a_known_challenge_can_be_cheated() {
+    // Knowing γ in advance, the prover picks `b_0 = 10` and solves
+    // `(1 + γ)(2 + γ) = (b_0 + γ)(b_1 + γ)` for `b_1`, so the running
+    // product closes on a list that is no permutation.
+    let gamma = Fp::from(PERMUTATION_CHALLENGE);
+    let list = [1u64, 2, 3, 4, 5];
+    let b0 = Fp::from(10);
+    let b1 = (Fp::from(1) + gamma) * (Fp::from(2) + gamma) * (b0 + gamma).invert().unwrap() - gamma;
+
+    let circuit = PermutationCircuit {
+        permuted: [
+            Value::known(b0),
+            Value::known(b1),
+            Value::known(Fp::from(3)),
+            Value::known(Fp::from(4)),
+            Value::known(Fp::from(5)),
+        ],
+    };
+    let instances = instances(gamma, &list.map(Fp::from));
+    let prover = MockProver::run(K, &circuit, instances).unwrap();
+    assert_eq!(prover.verify(), Ok(()));
+}
@@ -0,0 +1,44 @@
+use fibonacci_circuit::{
+    fibonacci::{three_column::FiboCircuit, NUM_TERMS},
+    precheck::{self, PrecheckError},
+    reference::fib_field,
+};
+use halo2_proofs::{circuit::Value, pasta::Fp, plonk::Error};
+
+const K: u32 = 4;
+
+fn circuit() -> FiboCircuit<Fp> {
+    FiboCircuit {
+        a: Value::known(Fp::one()),
+        b: Value::known(Fp::one()),
+    }
+}
+
+#[test]
+fn accepts_a_good_witness() {
+    let instances = vec![vec![fib_field(Fp::one(), Fp::one(), NUM_TERMS)]];
+    precheck::check(K, &circuit(), &instances).unwrap();
+}
+
+#[test]
+fn names_what_a_wrong_public_input_breaks() {
+    let instances = vec![vec![Fp::from(54)]];
+    match precheck::check(K, &circuit(), &instances) {
+        Err(error @ PrecheckError::Unsatisfied(_)) => {
+            let message = error.to_string();
+            assert!(message.starts_with("the MockProver rejects the witness:\n  "));
+        }
+        other => panic!("expected unsatisfied constraints, got {:?}", other),
+    }
+}
+
+#[test]
+fn reports_a_circuit_that_does_not_fit() {
+    let instances = vec![vec![fib_field(Fp::one(), Fp::one(), NUM_TERMS)]];
+    assert!(matches!(
+        precheck::check(2, &circuit(), &instances),
+        Err(PrecheckError::Synthesis(
+            Error::NotEnoughRowsAvailable { .. }
+        ))
+    ));
+}
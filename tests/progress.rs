@@ -0,0 +1,98 @@
+use fibonacci_circuit::{
+    fibonacci::{three_column::FiboCircuit, NUM_TERMS},
+    progress::{self, Event, Phase},
+    prover,
+    reference::fib_field,
+};
+use halo2_proofs::{circuit::Value, pasta::Fp, plonk::Error, poly::commitment::Params};
+
+const K: u32 = 4;
+
+fn circuit() -> FiboCircuit<Fp> {
+    FiboCircuit {
+        a: Value::known(Fp::one()),
+        b: Value::known(Fp::one()),
+    }
+}
+
+#[test]
+fn phases_come_in_order_around_the_rows() {
+    let params = Params::new(K);
+    let instances = vec![vec![fib_field(Fp::one(), Fp::one(), NUM_TERMS)]];
+    let mut events = vec![];
+    let (pk, proof) = progress::prove(&params, K, circuit(), &instances, |event| {
+        events.push(event)
+    })
+    .unwrap();
+    prover::verify_proof(&params, pk.get_vk(), &proof, &instances).unwrap();
+
+    let phases: Vec<Phase> = events
+        .iter()
+        .filter_map(|event| match event {
+            Event::Phase { phase, .. } => Some(*phase),
+            Event::Rows { .. } => None,
+        })
+        .collect();
+    assert_eq!(
+        phases,
+        [Phase::Synthesis, Phase::Keygen, Phase::Proving, Phase::Done]
+    );
+
+    // One row event per region, between synthesis and keygen.
+    let rows: Vec<usize> = events[1..events.len() - 3]
+        .iter()
+        .map(|event| match event {
+            Event::Rows { rows, .. } => *rows,
+            Event::Phase { .. } => panic!("phase during synthesis"),
+        })
+        .collect();
+    assert!(rows.windows(2).all(|pair| pair[0] <= pair[1]));
+    assert_eq!(*rows.last().unwrap(), NUM_TERMS - 2);
+
+    let elapsed: Vec<f64> = events
+        .iter()
+        .filter_map(|event| match event {
+            Event::Phase { elapsed_ms, .. } => Some(*elapsed_ms),
+            Event::Rows { .. } => None,
+        })
+        .collect();
+    assert!(elapsed.windows(2).all(|pair| pair[0] <= pair[1]));
+}
+
+#[test]
+fn witness_that_does_not_fit_fails_during_synthesis() {
+    let params = Params::new(3);
+    let mut events = vec![];
+    let err = progress::prove(&params, 3, circuit(), &[vec![Fp::one()]], |event| {
+        events.push(event)
+    })
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        Error::NotEnoughRowsAvailable { current_k: 3 }
+    ));
+    assert!(events
+        .iter()
+        .all(|event| !matches!(event, Event::Phase { phase, .. } if *phase != Phase::Synthesis)));
+}
+
+#[test]
+fn events_serialize_with_a_tag() {
+    let json = serde_json::to_value(Event::Rows {
+        rows: 8,
+        usable: 10,
+    })
+    .unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({"event": "rows", "rows": 8, "usable": 10})
+    );
+
+    let json = serde_json::to_value(Event::Phase {
+        phase: Phase::Keygen,
+        elapsed_ms: 1.5,
+    })
+    .unwrap();
+    assert_eq!(json["event"], "phase");
+    assert_eq!(json["phase"], "keygen");
+}
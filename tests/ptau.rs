@@ -0,0 +1,164 @@
+use fibonacci_circuit::ptau::{
+    self, PtauError, PtauFile, BETA_TAU_G1, CONTRIBUTIONS, LAGRANGE, MAGIC, TAU_G1,
+};
+use num_bigint::BigUint;
+
+/// The BN254 base field modulus.
+const BN254_Q: &str =
+    "21888242871839275222246405745257275088696311157297823662689037894645226208583";
+const N8: usize = 32;
+
+fn section(id: u32, data: &[u8]) -> Vec<u8> {
+    let mut bytes = id.to_le_bytes().to_vec();
+    bytes.extend((data.len() as u64).to_le_bytes());
+    bytes.extend(data);
+    bytes
+}
+
+/// A file of `power` with each section filled with its id, and a
+/// contributions section.
+fn sample(power: u32) -> Vec<u8> {
+    let mut header = (N8 as u32).to_le_bytes().to_vec();
+    let mut q = BN254_Q.parse::<BigUint>().unwrap().to_bytes_le();
+    q.resize(N8, 0);
+    header.extend(q);
+    header.extend(power.to_le_bytes());
+    header.extend(power.to_le_bytes());
+
+    let n = 1usize << power;
+    let points = [
+        (2 * n - 1) * 2 * N8,
+        n * 4 * N8,
+        n * 2 * N8,
+        n * 2 * N8,
+        4 * N8,
+    ];
+    let mut bytes = MAGIC.to_vec();
+    bytes.extend(1u32.to_le_bytes());
+    bytes.extend(7u32.to_le_bytes());
+    bytes.extend(section(1, &header));
+    for (id, len) in (2..).zip(points) {
+        bytes.extend(section(id, &vec![id as u8; len]));
+    }
+    bytes.extend(section(7, &[0; 4]));
+    bytes
+}
+
+#[test]
+fn reads_the_header_and_sections() {
+    let bytes = sample(3);
+    let info = ptau::info(&bytes).unwrap();
+
+    assert_eq!((info.version, info.n8, info.power), (1, 32, 3));
+    assert_eq!(info.prime, BN254_Q);
+    let ids: Vec<u32> = info.sections.iter().map(|section| section.id).collect();
+    assert_eq!(ids, [1, 2, 3, 4, 5, 6, 7]);
+    assert_eq!(info.sections[1].bytes, 15 * 64);
+    assert_eq!(info.bytes, bytes.len());
+    assert_eq!(info.digest.len(), 64);
+
+    let file = PtauFile::parse(&bytes).unwrap();
+    assert!(file.section(TAU_G1).unwrap().iter().all(|b| *b == 2));
+}
+
+#[test]
+fn degree_is_checked_against_k() {
+    let bytes = sample(3);
+    let file = PtauFile::parse(&bytes).unwrap();
+    file.check_degree(3).unwrap();
+    assert_eq!(
+        file.check_degree(4),
+        Err(PtauError::TooSmall { power: 3, k: 4 })
+    );
+}
+
+#[test]
+fn malformed_files_are_rejected() {
+    let mut bytes = sample(2);
+    bytes[0] = b'x';
+    assert_eq!(PtauFile::parse(&bytes), Err(PtauError::Magic));
+
+    let bytes = sample(2);
+    assert!(matches!(
+        PtauFile::parse(&bytes[..bytes.len() - 1]),
+        Err(PtauError::Truncated(_))
+    ));
+
+    // Section 5 claims one point fewer than the header's power implies.
+    let mut bytes = sample(2);
+    let at = bytes
+        .windows(12)
+        .position(|w| w[..4] == BETA_TAU_G1.to_le_bytes() && w[4..] == 256u64.to_le_bytes())
+        .unwrap();
+    bytes[at + 4..at + 12].copy_from_slice(&192u64.to_le_bytes());
+    bytes.drain(at + 12..at + 12 + 64);
+    assert_eq!(
+        PtauFile::parse(&bytes),
+        Err(PtauError::SectionLength {
+            id: BETA_TAU_G1,
+            expected: 256,
+            actual: 192
+        })
+    );
+}
+
+#[test]
+fn digest_pins_the_file() {
+    let a = ptau::info(&sample(2)).unwrap();
+    let mut bytes = sample(2);
+    let last = bytes.len() - 1;
+    bytes[last] ^= 1;
+    assert_ne!(ptau::info(&bytes).unwrap().digest, a.digest);
+}
+
+#[test]
+fn trimmed_file_keeps_the_first_powers() {
+    let bytes = sample(4);
+    let big = PtauFile::parse(&bytes).unwrap();
+    let trimmed = big.trim(2).unwrap();
+    let small = PtauFile::parse(&trimmed).unwrap();
+
+    assert_eq!((small.power, small.ceremony_power), (2, 4));
+    assert_eq!(small.prime, big.prime);
+    let fresh = sample(2);
+    let fresh = PtauFile::parse(&fresh).unwrap();
+    for id in 2..=7 {
+        assert_eq!(small.section(id), fresh.section(id), "section {}", id);
+    }
+    for id in [TAU_G1, BETA_TAU_G1] {
+        let section = small.section(id).unwrap();
+        assert_eq!(section, &big.section(id).unwrap()[..section.len()]);
+    }
+    assert_eq!(small.section(CONTRIBUTIONS), big.section(CONTRIBUTIONS));
+    assert!(trimmed.len() < bytes.len());
+}
+
+#[test]
+fn trimming_to_the_same_power_changes_nothing() {
+    let bytes = sample(3);
+    assert_eq!(PtauFile::parse(&bytes).unwrap().trim(3).unwrap(), bytes);
+}
+
+#[test]
+fn trimming_drops_the_lagrange_sections() {
+    let mut bytes = sample(2);
+    bytes[8] += 1;
+    bytes.extend(section(LAGRANGE[0], &[0; 64]));
+    let trimmed = PtauFile::parse(&bytes).unwrap().trim(1).unwrap();
+    let ids: Vec<u32> = ptau::info(&trimmed)
+        .unwrap()
+        .sections
+        .iter()
+        .map(|section| section.id)
+        .collect();
+    assert_eq!(ids, [1, 2, 3, 4, 5, 6, 7]);
+}
+
+#[test]
+fn trimming_cannot_grow_a_file() {
+    let bytes = sample(2);
+    assert_eq!(
+        PtauFile::parse(&bytes).unwrap().trim(3),
+        Err(PtauError::TooSmall { power: 2, k: 3 })
+    );
+}
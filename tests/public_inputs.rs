@@ -0,0 +1,73 @@
+use fibonacci_circuit::{
+    chunked,
+    fibonacci::{
+        checkpoint::FiboCheckpointCircuit,
+        public_inputs::{public_inputs_for, InstanceMode},
+        table::FibTableCircuit,
+        three_column::FiboTermsCircuit,
+    },
+};
+use halo2_proofs::{circuit::Value, dev::MockProver, pasta::Fp, plonk::Circuit};
+
+const K: u32 = 5;
+
+fn seeds() -> (Fp, Fp) {
+    (Fp::from(2), Fp::from(3))
+}
+
+fn accepts<C: Circuit<Fp>>(circuit: &C, instances: Vec<Vec<Fp>>) -> bool {
+    MockProver::run(K, circuit, instances)
+        .unwrap()
+        .verify()
+        .is_ok()
+}
+
+#[test]
+fn each_mode_matches_its_circuit() {
+    let (a, b) = seeds();
+    let (x, y) = (Value::known(a), Value::known(b));
+
+    let terms = FiboTermsCircuit { a: x, b: y, n: 12 };
+    assert!(accepts(
+        &terms,
+        public_inputs_for(a, b, 12, &InstanceMode::Final)
+    ));
+
+    let mode = InstanceMode::Checkpoints(vec![10, 3, 1]);
+    let checkpoint = FiboCheckpointCircuit {
+        a: x,
+        b: y,
+        checkpoints: vec![10, 3, 1],
+    };
+    assert!(accepts(&checkpoint, public_inputs_for(a, b, 10, &mode)));
+
+    let table = FibTableCircuit::new(K);
+    let one = Fp::one();
+    assert!(accepts(
+        &table,
+        public_inputs_for(one, one, 12, &InstanceMode::IndexAndTerm)
+    ));
+
+    let (_, instances) = chunked::chunk::<6>(a, b);
+    assert_eq!(
+        public_inputs_for(a, b, 8, &InstanceMode::SeedsAndFinal),
+        instances
+    );
+}
+
+#[test]
+fn rows_out_of_order_are_rejected() {
+    let (a, b) = seeds();
+    let mut instances = public_inputs_for(Fp::one(), Fp::one(), 12, &InstanceMode::IndexAndTerm);
+    instances[0].reverse();
+    assert!(!accepts(&FibTableCircuit::new(K), instances));
+
+    let mut instances = public_inputs_for(a, b, 10, &InstanceMode::Checkpoints(vec![1, 10]));
+    instances[0].swap(0, 1);
+    let circuit = FiboCheckpointCircuit {
+        a: Value::known(a),
+        b: Value::known(b),
+        checkpoints: vec![1, 10],
+    };
+    assert!(!accepts(&circuit, instances));
+}
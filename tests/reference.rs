@@ -0,0 +1,161 @@
+//! The registered circuits' public outputs against the native reference,
+//! which does not share code with their witness generation.
+
+use std::fmt;
+
+use fibonacci_circuit::{
+    fibonacci::{bigint, nth_term, NUM_TERMS},
+    gadgets::version::CIRCUIT_VERSION,
+    reference::{fib_biguint, fib_field, first_wraparound, modulus, to_field, Expected},
+    registry::{CircuitKind, CircuitVisitor, BATCH_SEEDS, CHECKPOINTS, CHUNK_STEPS},
+};
+use halo2_proofs::{dev::MockProver, pasta::Fp, plonk::Circuit};
+use num_bigint::BigUint;
+
+/// Checks the circuit accepts its demo instances and hands them back.
+struct Outputs;
+
+impl CircuitVisitor for Outputs {
+    type Output = Vec<Vec<Fp>>;
+
+    fn visit<C: Circuit<Fp> + fmt::Debug>(
+        self,
+        circuit: C,
+        instances: Vec<Vec<Fp>>,
+    ) -> Self::Output {
+        let k = (1..=10)
+            .find(|&k| MockProver::run(k, &circuit, instances.clone()).is_ok())
+            .expect("the circuit fits in k = 10");
+        MockProver::run(k, &circuit, instances.clone())
+            .unwrap()
+            .assert_satisfied();
+        instances
+    }
+}
+
+/// What the reference says a Fibonacci circuit's demo exposes, `None` for
+/// circuits of other sequences.
+fn expected(kind: CircuitKind) -> Option<Vec<Vec<Fp>>> {
+    let one = Fp::one();
+    let n = NUM_TERMS;
+    Some(match kind {
+        CircuitKind::FibThreeColumn
+        | CircuitKind::FibSingleColumn
+        | CircuitKind::FibStride
+        | CircuitKind::FibBounded
+        | CircuitKind::FibFixedSelector
+        | CircuitKind::FibVertical
+        | CircuitKind::FibNegativeRotation => {
+            vec![vec![fib_field(one, one, n)]]
+        }
+        CircuitKind::FibVersioned => {
+            vec![
+                vec![fib_field(one, one, n)],
+                vec![Fp::from(CIRCUIT_VERSION)],
+            ]
+        }
+        CircuitKind::FibBatch => vec![BATCH_SEEDS
+            .iter()
+            .map(|&(a, b)| fib_field(Fp::from(a), Fp::from(b), n))
+            .collect()],
+        CircuitKind::FibCheckpoint => vec![CHECKPOINTS
+            .iter()
+            .map(|&n| to_field(&fib_biguint(n)))
+            .collect()],
+        CircuitKind::FibDescent | CircuitKind::FibSquareTest => {
+            vec![vec![fib_field(one, one, n), fib_field(one, one, n + 1)]]
+        }
+        CircuitKind::FibParity => {
+            let bit = fib_biguint(n).bit(0);
+            vec![vec![Fp::from(u64::from(bit))]]
+        }
+        CircuitKind::FibLastDigit => vec![vec![to_field(&(fib_biguint(n) % 10u32))]],
+        CircuitKind::FibTable => vec![vec![Fp::from(n as u64), fib_field(one, one, n)]],
+        CircuitKind::FibChunk => {
+            let c = CHUNK_STEPS;
+            vec![vec![
+                one,
+                one,
+                fib_field(one, one, c + 1),
+                fib_field(one, one, c + 2),
+            ]]
+        }
+        // Four trips round the loop leave F(9) in r1.
+        CircuitKind::Vm => vec![vec![to_field(&fib_biguint(9))]],
+        CircuitKind::FibNonMember
+        | CircuitKind::Padovan
+        | CircuitKind::Catalan
+        | CircuitKind::HashChain
+        | CircuitKind::Histogram
+        | CircuitKind::Horner
+        | CircuitKind::InnerProduct
+        | CircuitKind::MatMul
+        | CircuitKind::Memory
+        | CircuitKind::Permutation
+        | CircuitKind::SortingNetwork
+        | CircuitKind::SparseMerkle => return None,
+    })
+}
+
+#[test]
+fn every_fibonacci_circuit_exposes_the_reference_value() {
+    for kind in CircuitKind::ALL {
+        if let Some(expected) = expected(kind) {
+            assert_eq!(kind.visit(Outputs), expected, "{}", kind);
+        }
+    }
+}
+
+#[test]
+fn known_fibonacci_numbers() {
+    let small: Vec<_> = (0..12).map(fib_biguint).collect();
+    let expected = [0u8, 1, 1, 2, 3, 5, 8, 13, 21, 34, 55, 89].map(BigUint::from);
+    assert_eq!(small, expected);
+    assert_eq!(
+        fib_biguint(100),
+        "354224848179261915075".parse::<BigUint>().unwrap()
+    );
+}
+
+#[test]
+fn agrees_with_the_witness_helpers() {
+    let one = BigUint::from(1u8);
+    for n in 1..400 {
+        assert_eq!(bigint::nth_term(&one, &one, n), fib_biguint(n));
+    }
+    for (a, b) in [(1, 1), (2, 3), (5, 0), (0, 1), (7, 11)] {
+        let (a, b) = (Fp::from(a), Fp::from(b));
+        for n in 1..40 {
+            assert_eq!(fib_field(a, b, n), nth_term(a, b, n));
+        }
+    }
+}
+
+#[test]
+fn reduces_large_numbers_into_the_field() {
+    // F(370) is larger than the modulus.
+    let (mut a, mut b) = (Fp::zero(), Fp::one());
+    for _ in 0..370 {
+        (a, b) = (b, a + b);
+    }
+    assert_eq!(to_field::<Fp>(&fib_biguint(370)), a);
+}
+
+#[test]
+fn reports_wraparound() {
+    let first = first_wraparound::<Fp>();
+    assert!(fib_biguint(first - 1) < modulus::<Fp>());
+    assert!(fib_biguint(first) >= modulus::<Fp>());
+
+    let small = Expected::<Fp>::of(first - 1);
+    assert!(!small.wrapped());
+    assert_eq!(to_field::<Fp>(&small.integer), small.field);
+
+    let large = Expected::<Fp>::of(370);
+    assert!(large.wrapped());
+    assert_eq!(large.wraps, BigUint::from(3u8));
+    assert_eq!(
+        large.field,
+        to_field::<Fp>(&(large.integer.clone() - modulus::<Fp>() * 3u8))
+    );
+}
@@ -0,0 +1,91 @@
+use std::cell::RefCell;
+
+use fibonacci_circuit::{expr, rows::RowCursor};
+use halo2_proofs::{
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    dev::MockProver,
+    pasta::Fp,
+    plonk::{Advice, Circuit, Column, ConstraintSystem, Error, Selector},
+};
+
+#[derive(Debug, Clone)]
+struct Config {
+    advice: Column<Advice>,
+    /// `x_next = x + 1`.
+    step: Selector,
+}
+
+/// Counts up from `start` for `steps` rows in one region, recording where
+/// the count starts and ends and each row holding more than `3`.
+#[derive(Debug, Default)]
+struct Counter {
+    start: u64,
+    steps: usize,
+    checkpoints: RefCell<Vec<(&'static str, usize)>>,
+}
+
+impl Circuit<Fp> for Counter {
+    type Config = Config;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            start: self.start,
+            steps: self.steps,
+            ..Self::default()
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>) -> Config {
+        let advice = meta.advice_column();
+        let step = meta.selector();
+        meta.create_gate("count", |meta| {
+            let x = expr::advice(meta, advice, 0);
+            let x_next = expr::advice(meta, advice, 1);
+            let s = meta.query_selector(step);
+            vec![s * (x_next - x - expr::constant(1))]
+        });
+        Config { advice, step }
+    }
+
+    fn synthesize(&self, config: Config, mut layouter: impl Layouter<Fp>) -> Result<(), Error> {
+        layouter.assign_region(
+            || "counter",
+            |mut region| {
+                let mut rows = RowCursor::new(&mut region);
+                rows.checkpoint("start");
+                let mut x = self.start;
+                for _ in 0..self.steps {
+                    rows.enable(config.step)?;
+                    rows.assign_advice("x", config.advice, Value::known(Fp::from(x)))?;
+                    if x > 3 {
+                        rows.checkpoint("above 3");
+                    }
+                    x += 1;
+                    rows.advance();
+                }
+                rows.assign_advice("x", config.advice, Value::known(Fp::from(x)))?;
+                rows.checkpoint("end");
+                *self.checkpoints.borrow_mut() = rows.checkpoints().to_vec();
+                assert_eq!(rows.checkpoint_row("above 3"), Some(self.steps - 1));
+                assert_eq!(rows.checkpoint_row("missing"), None);
+                Ok(())
+            },
+        )
+    }
+}
+
+#[test]
+fn rows_follow_the_cursor() {
+    let circuit = Counter {
+        start: 2,
+        steps: 4,
+        ..Counter::default()
+    };
+    let prover = MockProver::run(4, &circuit, vec![]).unwrap();
+    assert_eq!(prover.verify(), Ok(()));
+    assert_eq!(
+        *circuit.checkpoints.borrow(),
+        [("start", 0), ("above 3", 2), ("above 3", 3), ("end", 4)]
+    );
+}
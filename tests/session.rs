@@ -0,0 +1,96 @@
+use std::{fs, path::PathBuf, thread};
+
+use fibonacci_circuit::{
+    envelope::{ProofEnvelope, ENVELOPE_VERSION},
+    fibonacci::{three_column::FiboCircuit, NUM_TERMS},
+    inspect::Check,
+    params, prover,
+    reference::fib_field,
+    registry::CircuitKind,
+    session::VerifierSession,
+    verification::Status,
+};
+use halo2_proofs::{circuit::Value, pasta::Fp};
+
+fn golden() -> (ProofEnvelope, VerifierSession) {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/golden");
+    let envelope = fs::read(dir.join(format!("envelope-v{}.proof", ENVELOPE_VERSION))).unwrap();
+    let params = params::from_bytes(&fs::read(dir.join("params-k4.bin")).unwrap()).unwrap();
+    let session = VerifierSession::new(CircuitKind::FibThreeColumn, params).unwrap();
+    (ProofEnvelope::from_bytes(&envelope).unwrap(), session)
+}
+
+fn names(checks: &[Check]) -> Vec<(&str, bool)> {
+    checks
+        .iter()
+        .map(|check| (check.name, check.passed))
+        .collect()
+}
+
+#[test]
+fn verifies_envelopes_with_the_loaded_key() {
+    let (envelope, session) = golden();
+    assert_eq!(session.fingerprint(), &envelope.fingerprint);
+
+    let verification = session.verify_envelope(&envelope);
+    assert_eq!(verification.status, Status::Valid);
+    assert_eq!(verification.timings.keygen_ms, 0.0);
+    assert_eq!(
+        names(&verification.checks),
+        [
+            ("circuit", true),
+            ("statement", true),
+            ("fingerprint", true),
+            ("proof", true)
+        ]
+    );
+
+    session
+        .verify(&envelope.proof, &envelope.instances)
+        .unwrap();
+    assert!(session
+        .verify(&envelope.proof, &[vec![Fp::from(54)]])
+        .is_err());
+}
+
+#[test]
+fn rejects_envelopes_of_other_circuits() {
+    let (mut envelope, session) = golden();
+    envelope.circuit = CircuitKind::FibSingleColumn.name().to_string();
+    let verification = session.verify_envelope(&envelope);
+    assert_eq!(verification.status, Status::Invalid);
+    assert_eq!(names(&verification.checks), [("circuit", false)]);
+    assert_eq!(
+        verification.reason.unwrap(),
+        "circuit: envelope is for fib-single-column, the session verifies fib-three-column"
+    );
+}
+
+#[test]
+fn one_session_serves_many_threads() {
+    let (_, session) = golden();
+    let pk = prover::keygen(session.params(), &FiboCircuit::<Fp>::default()).unwrap();
+    let proofs: Vec<_> = (1..=4u64)
+        .map(|seed| {
+            let a = Fp::from(seed);
+            let circuit = FiboCircuit {
+                a: Value::known(a),
+                b: Value::known(a),
+            };
+            let instances = vec![vec![fib_field(a, a, NUM_TERMS)]];
+            let proof = prover::create_proof(session.params(), &pk, circuit, &instances).unwrap();
+            (proof, instances)
+        })
+        .collect();
+
+    thread::scope(|scope| {
+        let session = &session;
+        let handles: Vec<_> = proofs
+            .iter()
+            .map(|(proof, instances)| scope.spawn(move || session.verify(proof, instances)))
+            .collect();
+        for handle in handles {
+            handle.join().unwrap().unwrap();
+        }
+    });
+}
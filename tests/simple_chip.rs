@@ -0,0 +1,74 @@
+use fibonacci_circuit::{expr, gadgets::simple::Operand, simple_chip};
+use halo2_proofs::{
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    dev::MockProver,
+    pasta::Fp,
+    plonk::{Circuit, ConstraintSystem, Error},
+};
+
+simple_chip! {
+    /// `y = x² + 1`.
+    pub struct SquarePlusOneChip(SquarePlusOneConfig);
+    gate "square plus one"(x, y) => [x.clone() * x - y + expr::constant(1)];
+}
+
+/// Lays out `x, y` as one row, witnessing `y` directly and then copying
+/// the row's `y` into a second row as its `x`.
+#[derive(Debug, Default)]
+struct Chain {
+    x: Value<Fp>,
+    y: Value<Fp>,
+    z: Value<Fp>,
+}
+
+impl Circuit<Fp> for Chain {
+    type Config = SquarePlusOneConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+        let (x, y) = (meta.advice_column(), meta.advice_column());
+        SquarePlusOneChip::configure(meta, x, y)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<Fp>,
+    ) -> Result<(), Error> {
+        let chip = SquarePlusOneChip::construct(config);
+        let [_, y] = chip.assign_row(
+            layouter.namespace(|| "first"),
+            [Operand::Witness(self.x), Operand::Witness(self.y)],
+        )?;
+        chip.assign_row(
+            layouter.namespace(|| "second"),
+            [Operand::Cell(&y), Operand::Witness(self.z)],
+        )?;
+        Ok(())
+    }
+}
+
+fn run(x: u64, y: u64, z: u64) -> Result<(), Vec<halo2_proofs::dev::VerifyFailure>> {
+    let circuit = Chain {
+        x: Value::known(Fp::from(x)),
+        y: Value::known(Fp::from(y)),
+        z: Value::known(Fp::from(z)),
+    };
+    MockProver::run(4, &circuit, vec![]).unwrap().verify()
+}
+
+#[test]
+fn generated_chip_accepts_the_relation() {
+    // 3² + 1 = 10, 10² + 1 = 101
+    assert_eq!(run(3, 10, 101), Ok(()));
+}
+
+#[test]
+fn generated_chip_constrains_every_row() {
+    assert!(run(3, 11, 122).is_err());
+    assert!(run(3, 10, 100).is_err());
+}
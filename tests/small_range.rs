@@ -0,0 +1,67 @@
+use fibonacci_circuit::{
+    circuits::small_range::{ProductRangeCircuit, SplitRangeCircuit, DEMO_VALUES},
+    cost::CostReport,
+    prover,
+};
+use halo2_proofs::{dev::MockProver, pasta::Fp, plonk::Circuit, poly::commitment::Params};
+
+const K: u32 = 4;
+
+fn satisfied<C: Circuit<Fp>>(circuit: &C) -> bool {
+    MockProver::run(K, circuit, vec![])
+        .unwrap()
+        .verify()
+        .is_ok()
+}
+
+#[test]
+fn accepts_values_below_the_range_only() {
+    assert!(satisfied(&ProductRangeCircuit::new(&[
+        0, 1, 2, 3, 4, 5, 6, 7
+    ])));
+    assert!(satisfied(&SplitRangeCircuit::new(&[
+        0, 1, 2, 3, 4, 5, 6, 7
+    ])));
+    for value in [8, 9, 100] {
+        assert!(!satisfied(&ProductRangeCircuit::new(&[1, value])));
+        assert!(!satisfied(&SplitRangeCircuit::new(&[1, value])));
+    }
+    let minus_one = DEMO_VALUES.map(|_| u64::MAX);
+    assert!(!satisfied(&SplitRangeCircuit::<Fp>::new(&minus_one)));
+}
+
+#[test]
+fn splitting_lowers_the_degree_and_the_extended_domain() {
+    let product = ProductRangeCircuit::new(&DEMO_VALUES);
+    let split = SplitRangeCircuit::new(&DEMO_VALUES);
+    let product = CostReport::try_measure("product", K, &product, vec![]).unwrap();
+    let split = CostReport::try_measure("split", K, &split, vec![]).unwrap();
+
+    assert_eq!((product.max_degree, product.extended_k), (9, K + 3));
+    assert_eq!((split.max_degree, split.extended_k), (4, K + 2));
+    assert_eq!(product.minimum_rows, split.minimum_rows);
+    assert_eq!(split.advice_columns, product.advice_columns + 3);
+
+    let vanishing = |report: &CostReport| {
+        let part = report.proof.parts.iter().find(|p| p.name == "vanishing");
+        part.unwrap().points
+    };
+    assert_eq!((vanishing(&product), vanishing(&split)), (9, 4));
+    // Three more advice commitments and evaluations outweigh five fewer
+    // quotient pieces.
+    assert_eq!((product.proof.bytes, split.proof.bytes), (1088, 1120));
+}
+
+fn prove<C: Circuit<Fp>>(circuit: C) -> usize {
+    let params = Params::new(K);
+    let pk = prover::keygen(&params, &circuit).unwrap();
+    let proof = prover::create_proof(&params, &pk, circuit, &[]).unwrap();
+    prover::verify_proof(&params, pk.get_vk(), &proof, &[]).unwrap();
+    proof.len()
+}
+
+#[test]
+fn both_prove_at_the_estimated_size() {
+    assert_eq!(prove(ProductRangeCircuit::new(&DEMO_VALUES)), 1088);
+    assert_eq!(prove(SplitRangeCircuit::new(&DEMO_VALUES)), 1120);
+}
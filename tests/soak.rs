@@ -0,0 +1,62 @@
+use fibonacci_circuit::{
+    rng::SplitMix64,
+    soak::{run_case, soak, Mismatch, SoakCase, SoakFailure},
+    spec::Layout,
+};
+use halo2_proofs::{
+    pasta::{EqAffine, Fp},
+    poly::commitment::Params,
+};
+use rand_core::RngCore;
+
+const K: u32 = 4;
+
+#[test]
+fn split_mix_matches_its_reference_output() {
+    let mut rng = SplitMix64::new(0);
+    assert_eq!(rng.next_u64(), 0xe220_a839_7b1d_cdaf);
+    assert_eq!(rng.next_u64(), 0x6e78_9e6a_a1b9_65f4);
+}
+
+#[test]
+fn a_seed_names_one_case_that_fits_k() {
+    for seed in 0..200 {
+        let case = SoakCase::generate(seed, K);
+        assert_eq!(case, SoakCase::generate(seed, K));
+        assert!(case.n >= 3);
+        assert!(case.layout.rows(case.n) <= case.layout.usable_rows(K));
+    }
+    let layouts: Vec<Layout> = (0..20).map(|s| SoakCase::generate(s, K).layout).collect();
+    assert!(Layout::ALL.iter().all(|l| layouts.contains(l)));
+}
+
+#[test]
+fn cases_pass_and_are_reported_in_order() {
+    let params: Params<EqAffine> = Params::new(K);
+    let mut seen = vec![];
+    let report = soak(&params, K, 41, 4, |i, failure| {
+        assert!(failure.is_none());
+        seen.push(i);
+    });
+    assert!(report.passed());
+    assert_eq!(seen, [0, 1, 2, 3]);
+    assert_eq!(
+        report.to_string(),
+        "4 cases from seed 41 at k = 4: 0 failed\n"
+    );
+}
+
+#[test]
+fn cases_that_do_not_prove_are_failures() {
+    // More terms than fit `k` fail before there is a proof to check.
+    let params: Params<EqAffine> = Params::new(K);
+    let mut case = SoakCase::generate(5, K);
+    case.n = 64;
+    assert!(matches!(run_case(&params, &case), Err(Mismatch::Error(_))));
+
+    case.a = Fp::from(3);
+    case.b = Fp::from(4);
+    let failure = SoakFailure::new(&case, K, &Mismatch::AcceptedWrongTerm);
+    assert!(failure.to_string().starts_with("seed 5: "));
+    assert_eq!((failure.a.as_str(), failure.b.as_str()), ("3", "4"));
+}
@@ -0,0 +1,54 @@
+use fibonacci_circuit::circuits::sorting_network::{comparators, SortingNetworkCircuit};
+use halo2_proofs::{circuit::Value, dev::MockProver, pasta::Fp};
+
+const K: u32 = 11;
+
+fn run(input: [u64; 8], sorted: [u64; 8]) -> bool {
+    let circuit = SortingNetworkCircuit {
+        sorted: sorted.map(|x| Value::known(Fp::from(x))),
+    };
+    let prover = MockProver::run(K, &circuit, vec![input.map(Fp::from).to_vec()]).unwrap();
+    prover.verify().is_ok()
+}
+
+/// Applies the network to `values` natively.
+fn sort(values: &mut [u64]) {
+    for (i, j) in comparators(values.len()) {
+        if values[i] > values[j] {
+            values.swap(i, j);
+        }
+    }
+}
+
+#[test]
+fn network_sorts_every_zero_one_input() {
+    // By the 0-1 principle, a network sorting all 0-1 inputs sorts
+    // everything.
+    for n in [1, 2, 4, 8, 16] {
+        for bits in 0..1u32 << n {
+            let mut values: Vec<u64> = (0..n).map(|i| (bits >> i & 1) as u64).collect();
+            sort(&mut values);
+            assert!(values.windows(2).all(|w| w[0] <= w[1]), "{} wires", n);
+        }
+    }
+    assert_eq!(comparators(8).len(), 19);
+}
+
+#[test]
+fn accepts_the_sorted_array() {
+    let input = [5, 3, 8, 1, 9, 2, 7, 3];
+    assert!(run(input, [1, 2, 3, 3, 5, 7, 8, 9]));
+    assert!(run(
+        [u64::MAX, 0, 1, 2, 3, 4, 5, 6],
+        [0, 1, 2, 3, 4, 5, 6, u64::MAX]
+    ));
+}
+
+#[test]
+fn rejects_anything_else() {
+    let input = [5, 3, 8, 1, 9, 2, 7, 3];
+    // Unsorted, a different multiset, and the input itself.
+    assert!(!run(input, [1, 2, 3, 3, 5, 8, 7, 9]));
+    assert!(!run(input, [1, 2, 3, 4, 5, 7, 8, 9]));
+    assert!(!run(input, input));
+}
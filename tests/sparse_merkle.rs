@@ -0,0 +1,114 @@
+use fibonacci_circuit::{
+    circuits::sparse_merkle::SparseMerkleCircuit,
+    gadgets::{
+        poseidon::hash2,
+        sparse_merkle::{defaults, SparseMerkleTree},
+    },
+};
+use halo2_proofs::{circuit::Value, dev::MockProver, pasta::Fp};
+
+const K: u32 = 10;
+
+const DEPTH: usize = 8;
+
+fn tree() -> SparseMerkleTree<Fp> {
+    let mut tree = SparseMerkleTree::new(DEPTH);
+    tree.insert(3, Fp::from(30));
+    tree.insert(4, Fp::from(40));
+    tree.insert(200, Fp::from(2000));
+    tree
+}
+
+fn run(circuit: &SparseMerkleCircuit<Fp, DEPTH>, instances: Vec<Vec<Fp>>) -> bool {
+    let prover = MockProver::run(K, circuit, instances).unwrap();
+    prover.verify().is_ok()
+}
+
+#[test]
+fn native_root_matches_the_hashes() {
+    let mut tree = SparseMerkleTree::<Fp>::new(2);
+    assert_eq!(tree.root(), defaults::<Fp>(2)[2]);
+
+    tree.insert(2, Fp::from(7));
+    let d = defaults::<Fp>(1)[1];
+    assert_eq!(tree.root(), hash2(d, hash2(Fp::from(7), Fp::zero())));
+    assert_eq!(tree.path(2), vec![None, None]);
+    assert_eq!(
+        tree.path(0),
+        vec![None, Some(hash2(Fp::from(7), Fp::zero()))]
+    );
+
+    tree.insert(2, Fp::zero());
+    assert_eq!(tree.root(), defaults::<Fp>(2)[2]);
+}
+
+#[test]
+fn proves_membership() {
+    let tree = tree();
+    for key in [3, 4, 200] {
+        let (circuit, instances) = SparseMerkleCircuit::<_, DEPTH>::new(&tree, key);
+        assert!(run(&circuit, instances), "key {}", key);
+    }
+}
+
+#[test]
+fn proves_non_membership() {
+    let tree = tree();
+    for key in [0, 5, 201, 255] {
+        let (circuit, instances) = SparseMerkleCircuit::<_, DEPTH>::new(&tree, key);
+        assert_eq!(instances[0][2], Fp::zero());
+        assert!(run(&circuit, instances), "key {}", key);
+    }
+
+    let empty = SparseMerkleTree::new(DEPTH);
+    let (circuit, instances) = SparseMerkleCircuit::<_, DEPTH>::new(&empty, 17);
+    assert!(run(&circuit, instances));
+}
+
+#[test]
+fn rejects_wrong_values() {
+    let tree = tree();
+
+    let (circuit, mut instances) = SparseMerkleCircuit::<_, DEPTH>::new(&tree, 3);
+    instances[0][2] = Fp::from(31);
+    assert!(!run(&circuit, instances));
+
+    // Claiming a present key is absent.
+    let (circuit, mut instances) = SparseMerkleCircuit::<_, DEPTH>::new(&tree, 4);
+    instances[0][2] = Fp::zero();
+    assert!(!run(&circuit, instances));
+
+    // The sibling of 3 proving a leaf at 4.
+    let (circuit, mut instances) = SparseMerkleCircuit::<_, DEPTH>::new(&tree, 3);
+    instances[0][1] = Fp::from(4);
+    assert!(!run(&circuit, instances));
+}
+
+#[test]
+fn rejects_other_roots() {
+    let mut other = tree();
+    other.insert(9, Fp::from(90));
+    let (circuit, mut instances) = SparseMerkleCircuit::<_, DEPTH>::new(&tree(), 3);
+    instances[0][0] = other.root();
+    assert!(!run(&circuit, instances));
+}
+
+#[test]
+fn rejects_keys_out_of_range() {
+    // 256 + 3 has the bits of 3 below the depth, so the path would hash to
+    // the root if the key were truncated.
+    let (circuit, mut instances) = SparseMerkleCircuit::<_, DEPTH>::new(&tree(), 3);
+    instances[0][1] = Fp::from(256 + 3);
+    assert!(!run(&circuit, instances));
+}
+
+#[test]
+fn rejects_a_wrong_default_flag() {
+    // Marking the subtree over 4..8, the sibling of 3 two levels up, as
+    // empty.
+    let tree = tree();
+    let (mut circuit, instances) = SparseMerkleCircuit::<_, DEPTH>::new(&tree, 3);
+    assert!(tree.path(3)[2].is_some());
+    circuit.path[2] = Value::known(None);
+    assert!(!run(&circuit, instances));
+}
@@ -0,0 +1,141 @@
+use fibonacci_circuit::{
+    fibonacci::{single_column, three_column},
+    field::ParseFieldError,
+    reference::fib_field,
+    spec::{CircuitSpec, Layout, SpecProblem},
+};
+use halo2_proofs::{circuit::Value, dev::MockProver, pasta::Fp, plonk::Error};
+
+fn spec(layout: Layout, n: usize, k: u32) -> CircuitSpec {
+    CircuitSpec {
+        layout,
+        a: "1".to_string(),
+        b: "1".to_string(),
+        n,
+        k,
+        instances: vec![vec![fib_field(Fp::one(), Fp::one(), n)]],
+        params_k: Some(k),
+    }
+}
+
+fn fits(layout: Layout, n: usize, k: u32) -> bool {
+    let (a, b) = (Value::known(Fp::one()), Value::known(Fp::one()));
+    let instances = vec![vec![fib_field(Fp::one(), Fp::one(), n)]];
+    let result = match layout {
+        Layout::ThreeColumn => {
+            MockProver::run(k, &three_column::FiboTermsCircuit { a, b, n }, instances)
+        }
+        Layout::SingleColumn => {
+            MockProver::run(k, &single_column::FiboTermsCircuit { a, b, n }, instances)
+        }
+    };
+    match result {
+        Ok(prover) => {
+            prover.verify().unwrap();
+            true
+        }
+        Err(Error::NotEnoughRowsAvailable { .. }) => false,
+        Err(e) => panic!("{:?}", e),
+    }
+}
+
+#[test]
+fn a_good_spec_validates() {
+    for layout in [Layout::ThreeColumn, Layout::SingleColumn] {
+        spec(layout, 10, 4).validate().unwrap();
+    }
+}
+
+#[test]
+fn the_row_bound_matches_synthesis() {
+    for layout in [Layout::ThreeColumn, Layout::SingleColumn] {
+        for k in [4, 5] {
+            let largest = (3..200)
+                .take_while(|&n| spec(layout, n, k).validate().is_ok())
+                .last()
+                .unwrap();
+            assert!(fits(layout, largest, k), "{:?} n = {}", layout, largest);
+            assert!(
+                !fits(layout, largest + 1, k),
+                "{:?} n = {}",
+                layout,
+                largest + 1
+            );
+        }
+    }
+}
+
+#[test]
+fn reports_every_problem_at_once() {
+    let spec = CircuitSpec {
+        layout: Layout::ThreeColumn,
+        a: "one".to_string(),
+        b: "-1".to_string(),
+        n: 40,
+        k: 4,
+        instances: vec![vec![Fp::one(), Fp::one()]],
+        params_k: Some(5),
+    };
+    let problems = spec.validate().unwrap_err();
+    assert_eq!(
+        problems,
+        [
+            SpecProblem::InvalidSeed {
+                name: "a",
+                value: "one".to_string(),
+                reason: ParseFieldError::InvalidDigit {
+                    position: 0,
+                    found: 'o',
+                    hex: false
+                },
+            },
+            SpecProblem::InvalidSeed {
+                name: "b",
+                value: "-1".to_string(),
+                reason: ParseFieldError::Negative,
+            },
+            SpecProblem::TooManyRows {
+                rows: 38,
+                usable: Layout::ThreeColumn.usable_rows(4),
+                needed: Some(6),
+            },
+            SpecProblem::InstanceShape {
+                columns: 1,
+                cells: 2
+            },
+            SpecProblem::ParamsK { params: 5, k: 4 },
+        ]
+    );
+}
+
+#[test]
+fn seeds_must_be_canonical() {
+    let modulus = "28948022309329048855892746252171976963363056481941560715954676764349967630337";
+    let mut spec = spec(Layout::SingleColumn, 10, 4);
+    spec.a = modulus.to_string();
+    assert_eq!(
+        spec.validate().unwrap_err(),
+        [SpecProblem::InvalidSeed {
+            name: "a",
+            value: modulus.to_string(),
+            reason: ParseFieldError::OutOfRange {
+                value: modulus.parse().unwrap(),
+                modulus: modulus.parse().unwrap(),
+                hex: false
+            },
+        }]
+    );
+    spec.a = "28948022309329048855892746252171976963363056481941560715954676764349967630336".into();
+    assert_eq!(spec.seeds().unwrap().0, -Fp::one());
+}
+
+#[test]
+fn short_sequences_and_large_k_are_rejected() {
+    let mut short = spec(Layout::SingleColumn, 2, 4);
+    short.instances = vec![vec![Fp::one()]];
+    assert_eq!(short.validate().unwrap_err(), [SpecProblem::TooFewTerms(2)]);
+
+    let mut large = spec(Layout::SingleColumn, 10, 40);
+    large.params_k = None;
+    assert_eq!(large.validate().unwrap_err(), [SpecProblem::InvalidK(40)]);
+}
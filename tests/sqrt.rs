@@ -0,0 +1,204 @@
+use fibonacci_circuit::gadgets::sqrt::{self, SqrtChip, SqrtConfig};
+use halo2_proofs::{
+    arithmetic::Field,
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    dev::MockProver,
+    pasta::{group::ff::PrimeField, Fp},
+    plonk::{Advice, Circuit, Column, ConstraintSystem, Error, Instance},
+};
+use rand_core::OsRng;
+
+#[derive(Debug, Clone)]
+struct Config {
+    input: Column<Advice>,
+    instance: Column<Instance>,
+    sqrt: SqrtConfig,
+}
+
+fn configure(meta: &mut ConstraintSystem<Fp>) -> Config {
+    let input = meta.advice_column();
+    meta.enable_equality(input);
+    let instance = meta.instance_column();
+    meta.enable_equality(instance);
+    let advice = [(); 4].map(|_| meta.advice_column());
+    Config {
+        input,
+        instance,
+        sqrt: SqrtChip::configure(meta, advice),
+    }
+}
+
+/// Takes the square root of `x`, exposing the root and the non-residue
+/// flag at instance rows 0 and 1.
+#[derive(Debug, Default)]
+struct SqrtCircuit {
+    x: Value<Fp>,
+}
+
+impl Circuit<Fp> for SqrtCircuit {
+    type Config = Config;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>) -> Config {
+        configure(meta)
+    }
+
+    fn synthesize(&self, config: Config, mut layouter: impl Layouter<Fp>) -> Result<(), Error> {
+        let x = layouter.assign_region(
+            || "input",
+            |mut region| region.assign_advice(|| "x", config.input, 0, || self.x),
+        )?;
+        let chip = SqrtChip::construct(config.sqrt);
+        let result = chip.sqrt(layouter.namespace(|| "sqrt"), &x)?;
+        layouter.constrain_instance(result.root.cell(), config.instance, 0)?;
+        layouter.constrain_instance(result.non_residue.cell().cell(), config.instance, 1)
+    }
+}
+
+/// Lays out the gadget row with an arbitrary witness.
+#[derive(Debug, Default)]
+struct ForgedCircuit {
+    x: Fp,
+    root: Fp,
+    non_residue: Fp,
+    root_inv: Fp,
+}
+
+impl Circuit<Fp> for ForgedCircuit {
+    type Config = Config;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>) -> Config {
+        configure(meta)
+    }
+
+    fn synthesize(&self, config: Config, mut layouter: impl Layouter<Fp>) -> Result<(), Error> {
+        let cfg = config.sqrt;
+        layouter.assign_region(
+            || "forged sqrt",
+            |mut region| {
+                cfg.selector.enable(&mut region, 0)?;
+                for (column, value) in [
+                    (cfg.x, self.x),
+                    (cfg.root, self.root),
+                    (cfg.non_residue, self.non_residue),
+                    (cfg.root_inv, self.root_inv),
+                ] {
+                    region.assign_advice(|| "forged", column, 0, || Value::known(value))?;
+                }
+                Ok(())
+            },
+        )
+    }
+}
+
+const K: u32 = 4;
+
+fn check(x: Fp) {
+    let (root, non_residue) = sqrt::witness(x);
+    let circuit = SqrtCircuit { x: Value::known(x) };
+    let flag = if non_residue { Fp::one() } else { Fp::zero() };
+    let prover = MockProver::run(K, &circuit, vec![vec![root, flag]]).unwrap();
+    assert_eq!(prover.verify(), Ok(()), "x = {:?}", x);
+
+    let expected = if non_residue {
+        x * Fp::multiplicative_generator()
+    } else {
+        x
+    };
+    assert_eq!(root.square(), expected);
+}
+
+#[test]
+fn random_squares() {
+    for _ in 0..20 {
+        let x = Fp::random(OsRng).square();
+        assert!(!sqrt::witness(x).1);
+        check(x);
+    }
+    check(Fp::zero());
+    check(Fp::one());
+}
+
+#[test]
+fn random_non_squares() {
+    let g = Fp::multiplicative_generator();
+    for _ in 0..20 {
+        let x = Fp::random(OsRng).square() * g;
+        if x == Fp::zero() {
+            continue;
+        }
+        assert!(sqrt::witness(x).1);
+        check(x);
+    }
+}
+
+#[test]
+fn forged_witnesses_are_rejected() {
+    let g = Fp::multiplicative_generator();
+    let r = Fp::random(OsRng);
+    let square = r.square();
+    let non_square = square * g;
+    let forgeries = [
+        // A non-square claimed to be a square.
+        ForgedCircuit {
+            x: non_square,
+            root: r,
+            non_residue: Fp::zero(),
+            root_inv: r.invert().unwrap(),
+        },
+        // A square claimed to be a non-residue; no root of g·x exists, so
+        // try the square's own root.
+        ForgedCircuit {
+            x: square,
+            root: r,
+            non_residue: Fp::one(),
+            root_inv: r.invert().unwrap(),
+        },
+        // Zero claimed to be a non-residue.
+        ForgedCircuit {
+            x: Fp::zero(),
+            root: Fp::zero(),
+            non_residue: Fp::one(),
+            root_inv: Fp::zero(),
+        },
+        // A non-boolean flag.
+        ForgedCircuit {
+            x: square,
+            root: r,
+            non_residue: Fp::from(2),
+            root_inv: r.invert().unwrap(),
+        },
+        // A wrong root.
+        ForgedCircuit {
+            x: square,
+            root: r + Fp::one(),
+            non_residue: Fp::zero(),
+            root_inv: Fp::zero(),
+        },
+    ];
+    for circuit in forgeries {
+        let prover = MockProver::run(K, &circuit, vec![vec![]]).unwrap();
+        assert!(prover.verify().is_err(), "accepted {:?}", circuit);
+    }
+
+    // Either sign of the root is accepted.
+    for root in [r, -r] {
+        let circuit = ForgedCircuit {
+            x: square,
+            root,
+            non_residue: Fp::zero(),
+            root_inv: Fp::zero(),
+        };
+        let prover = MockProver::run(K, &circuit, vec![vec![]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+    }
+}
@@ -0,0 +1,59 @@
+use fibonacci_circuit::{
+    fibonacci::square_test::SquareTestCircuit,
+    reference::{fib_biguint, to_field},
+};
+use halo2_proofs::{arithmetic::Field, circuit::Value, dev::MockProver, pasta::Fp};
+
+const K: u32 = 8;
+
+fn verify(x: Fp, y: Fp) -> bool {
+    let circuit = SquareTestCircuit {
+        x: Value::known(x),
+        y: Value::known(y),
+    };
+    let prover = MockProver::run(K, &circuit, vec![vec![x, y]]).unwrap();
+    prover.verify().is_ok()
+}
+
+fn fib(n: usize) -> Fp {
+    to_field(&fib_biguint(n))
+}
+
+#[test]
+fn accepts_consecutive_terms_below_2_64() {
+    for n in (0..20).chain(88..93) {
+        assert!(verify(fib(n), fib(n + 1)), "F({}), F({})", n, n + 1);
+    }
+}
+
+#[test]
+fn rejects_other_pairs() {
+    // (1, 0) is F(-1), F(0): the order check is what leaves it out.
+    for (x, y) in [(4, 6), (89, 55), (2, 2), (1, 0), (5, 13), (55, 90)] {
+        assert!(!verify(Fp::from(x), Fp::from(y)), "({}, {})", x, y);
+    }
+}
+
+#[test]
+fn rejects_terms_past_2_64() {
+    assert!(fib_biguint(94).bits() > 64);
+    assert!(!verify(fib(93), fib(94)));
+}
+
+#[test]
+fn rejects_solutions_only_modulo_p() {
+    // 5·4² ± 4 is no integer square, but one of them has a root in the
+    // field, and y = (4 + r) / 2 solves y² - 4y - 16 = ±1 modulo p.
+    let x = Fp::from(4);
+    let solutions: Vec<Fp> = [Fp::from(84), Fp::from(76)]
+        .into_iter()
+        .filter_map(|t| Option::<Fp>::from(t.sqrt()))
+        .map(|r| (x + r) * Fp::from(2).invert().unwrap())
+        .collect();
+    assert!(!solutions.is_empty());
+    for y in solutions {
+        let sign = y.square() - x * y - x.square();
+        assert!(sign == Fp::one() || sign == -Fp::one());
+        assert!(!verify(x, y));
+    }
+}
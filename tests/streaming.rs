@@ -0,0 +1,91 @@
+use std::{
+    fs::File,
+    io::{self, BufReader, BufWriter, Write},
+};
+
+use fibonacci_circuit::{
+    fibonacci::{three_column::FiboCircuit, NUM_TERMS},
+    prover::{self, Blinding},
+    reference::fib_field,
+};
+use halo2_proofs::{circuit::Value, pasta::Fp, plonk::Error, poly::commitment::Params};
+
+const K: u32 = 4;
+
+fn circuit() -> FiboCircuit<Fp> {
+    FiboCircuit {
+        a: Value::known(Fp::one()),
+        b: Value::known(Fp::one()),
+    }
+}
+
+fn instances() -> Vec<Vec<Fp>> {
+    vec![vec![fib_field(Fp::one(), Fp::one(), NUM_TERMS)]]
+}
+
+/// Accepts `room` bytes, then fails every write.
+struct Full {
+    room: usize,
+}
+
+impl Write for Full {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if buf.len() > self.room {
+            return Err(io::Error::new(io::ErrorKind::WriteZero, "disk full"));
+        }
+        self.room -= buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn proof_streams_to_a_file_and_back() {
+    let params = Params::new(K);
+    let pk = prover::keygen(&params, &circuit()).unwrap();
+    let path = std::env::temp_dir().join(format!("fibo-stream-{}.proof", std::process::id()));
+
+    let file = BufWriter::new(File::create(&path).unwrap());
+    let file = prover::create_proof_to(&params, &pk, circuit(), &instances(), Blinding::Off, file)
+        .unwrap();
+    file.into_inner().unwrap().sync_all().unwrap();
+
+    let streamed = std::fs::read(&path).unwrap();
+    let buffered =
+        prover::create_proof_with(&params, &pk, circuit(), &instances(), Blinding::Off).unwrap();
+    assert_eq!(streamed, buffered);
+
+    let reader = BufReader::new(File::open(&path).unwrap());
+    prover::verify_proof_from(&params, pk.get_vk(), reader, &instances()).unwrap();
+    let reader = BufReader::new(File::open(&path).unwrap());
+    let wrong = vec![vec![Fp::from(54)]];
+    assert!(prover::verify_proof_from(&params, pk.get_vk(), reader, &wrong).is_err());
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn failed_write_fails_the_proof() {
+    let params = Params::new(K);
+    let pk = prover::keygen(&params, &circuit()).unwrap();
+    let result = prover::create_proof_to(
+        &params,
+        &pk,
+        circuit(),
+        &instances(),
+        Blinding::ZeroKnowledge,
+        Full { room: 100 },
+    );
+    assert!(matches!(result, Err(Error::Transcript(e)) if e.kind() == io::ErrorKind::WriteZero));
+}
+
+#[test]
+fn truncated_stream_is_rejected() {
+    let params = Params::new(K);
+    let pk = prover::keygen(&params, &circuit()).unwrap();
+    let proof = prover::create_proof(&params, &pk, circuit(), &instances()).unwrap();
+    let short = &proof[..proof.len() - 1];
+    assert!(prover::verify_proof_from(&params, pk.get_vk(), short, &instances()).is_err());
+}
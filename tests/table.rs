@@ -0,0 +1,37 @@
+use fibonacci_circuit::{
+    fibonacci::table::{capacity, FibTableCircuit},
+    reference::{fib_biguint, to_field},
+};
+use halo2_proofs::{dev::MockProver, pasta::Fp};
+
+const K: u32 = 5;
+
+fn run(i: usize, v: Fp) -> Result<(), Vec<halo2_proofs::dev::VerifyFailure>> {
+    let instances = vec![vec![Fp::from(i as u64), v]];
+    MockProver::run(K, &FibTableCircuit::new(K), instances)
+        .unwrap()
+        .verify()
+}
+
+#[test]
+fn every_entry_is_a_member() {
+    assert_eq!(capacity::<Fp>(K), 25);
+    for i in 0..capacity::<Fp>(K) {
+        assert_eq!(run(i, to_field(&fib_biguint(i))), Ok(()), "F({})", i);
+    }
+}
+
+#[test]
+fn rejects_pairs_outside_the_table() {
+    assert!(run(10, Fp::from(54)).is_err());
+    // F(8) is in the table, but not at 9.
+    assert!(run(9, Fp::from(21)).is_err());
+    let past = capacity::<Fp>(K);
+    assert!(run(past, to_field(&fib_biguint(past))).is_err());
+}
+
+#[test]
+fn does_not_fit_a_smaller_k() {
+    let instances = vec![vec![Fp::from(0), Fp::from(0)]];
+    assert!(MockProver::run(K - 1, &FibTableCircuit::new(K), instances).is_err());
+}
@@ -0,0 +1,120 @@
+use std::thread;
+
+use fibonacci_circuit::{
+    bundle::ProofBundle,
+    cache::ProofCache,
+    envelope::ProofEnvelope,
+    fibonacci::{
+        fixed_selector, negative_rotation, single_column,
+        three_column::{self, FiboCircuit},
+        vertical, NUM_TERMS,
+    },
+    folding::FoldedStepCircuit,
+    gadgets::{arithmetic, flex_gate, is_zero, poseidon, version},
+    introspect::witness::Witness,
+    metrics::ProverMetrics,
+    prover,
+    reference::fib_field,
+    registry::CircuitKind,
+    session::VerifierSession,
+};
+use halo2_proofs::{
+    circuit::Value,
+    pasta::{EqAffine, Fp},
+    plonk::ProvingKey,
+    poly::commitment::Params,
+};
+
+/// Fails to compile unless `T` can be shared between threads. The circuits
+/// in the registry are checked by the bound on `CircuitVisitor::visit`;
+/// these are the chips, configs and circuits outside it, and what a prover
+/// service would hold on to between requests.
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn shared_types_are_send_and_sync() {
+    assert_send_sync::<three_column::FiboChip<Fp>>();
+    assert_send_sync::<three_column::FiboConfig>();
+    assert_send_sync::<three_column::FiboTermsCircuit<Fp>>();
+    assert_send_sync::<three_column::FiboWitness<Fp>>();
+    assert_send_sync::<single_column::FiboChip<Fp>>();
+    assert_send_sync::<single_column::FiboConfig>();
+    assert_send_sync::<single_column::FiboTermsCircuit<Fp>>();
+    assert_send_sync::<fixed_selector::FiboFixedChip<Fp>>();
+    assert_send_sync::<fixed_selector::FiboFixedConfig>();
+    assert_send_sync::<vertical::FiboVerticalConfig>();
+    assert_send_sync::<negative_rotation::FiboChip<Fp>>();
+    assert_send_sync::<negative_rotation::FiboConfig>();
+    assert_send_sync::<flex_gate::FlexGateChip<Fp>>();
+    assert_send_sync::<flex_gate::GateBuilder<Fp>>();
+    assert_send_sync::<arithmetic::ArithmeticChip<Fp>>();
+    assert_send_sync::<is_zero::IsZeroChip<Fp>>();
+    assert_send_sync::<poseidon::PoseidonChip<Fp>>();
+    assert_send_sync::<version::VersionChip<Fp>>();
+    assert_send_sync::<FoldedStepCircuit>();
+
+    assert_send_sync::<CircuitKind>();
+    assert_send_sync::<Params<EqAffine>>();
+    assert_send_sync::<ProvingKey<EqAffine>>();
+    assert_send_sync::<ProofEnvelope>();
+    assert_send_sync::<ProofBundle>();
+    assert_send_sync::<ProofCache>();
+    assert_send_sync::<VerifierSession>();
+    assert_send_sync::<ProverMetrics>();
+    assert_send_sync::<Witness>();
+}
+
+#[test]
+fn pool_has_the_requested_threads() {
+    for threads in [1, 3] {
+        assert_eq!(
+            prover::with_threads(threads, rayon::current_num_threads).unwrap(),
+            threads
+        );
+    }
+}
+
+#[test]
+fn single_threaded_proof_verifies() {
+    let params = Params::new(4);
+    let circuit = FiboCircuit {
+        a: Value::known(Fp::one()),
+        b: Value::known(Fp::one()),
+    };
+    let instances = vec![vec![fib_field(Fp::one(), Fp::one(), NUM_TERMS)]];
+
+    let (pk, proof) = prover::with_threads(1, || {
+        let pk = prover::keygen(&params, &circuit).unwrap();
+        let proof = prover::create_proof(&params, &pk, circuit, &instances).unwrap();
+        (pk, proof)
+    })
+    .unwrap();
+    prover::verify_proof(&params, pk.get_vk(), &proof, &instances).unwrap();
+}
+
+#[test]
+fn threads_prove_with_one_shared_key() {
+    let params = Params::new(4);
+    let pk = prover::keygen(&params, &FiboCircuit::<Fp>::default()).unwrap();
+
+    thread::scope(|scope| {
+        let handles: Vec<_> = (1..=3u64)
+            .map(|seed| {
+                let (params, pk) = (&params, &pk);
+                scope.spawn(move || {
+                    let a = Fp::from(seed);
+                    let circuit = FiboCircuit {
+                        a: Value::known(a),
+                        b: Value::known(a),
+                    };
+                    let instances = vec![vec![fib_field(a, a, NUM_TERMS)]];
+                    let proof = prover::create_proof(params, pk, circuit, &instances).unwrap();
+                    prover::verify_proof(params, pk.get_vk(), &proof, &instances)
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap().unwrap();
+        }
+    });
+}
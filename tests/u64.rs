@@ -0,0 +1,206 @@
+use fibonacci_circuit::gadgets::u64::{to_u64, U64Chip, U64Config, BITS};
+use halo2_proofs::{
+    arithmetic::{Field, FieldExt},
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    dev::{MockProver, VerifyFailure},
+    pasta::Fp,
+    plonk::{Advice, Circuit, Column, ConstraintSystem, Error},
+};
+
+#[derive(Debug, Clone)]
+struct Config {
+    input: Column<Advice>,
+    range: U64Config,
+}
+
+fn configure(meta: &mut ConstraintSystem<Fp>) -> Config {
+    let input = meta.advice_column();
+    meta.enable_equality(input);
+    let z = meta.advice_column();
+    Config {
+        input,
+        range: U64Chip::configure(meta, z),
+    }
+}
+
+/// Range-checks `x` to `bits` bits with the chip.
+#[derive(Debug, Default)]
+struct RangeCircuit {
+    x: Fp,
+    bits: usize,
+}
+
+impl Circuit<Fp> for RangeCircuit {
+    type Config = Config;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            bits: self.bits,
+            ..Self::default()
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>) -> Config {
+        configure(meta)
+    }
+
+    fn synthesize(&self, config: Config, mut layouter: impl Layouter<Fp>) -> Result<(), Error> {
+        let x = layouter.assign_region(
+            || "input",
+            |mut region| region.assign_advice(|| "x", config.input, 0, || Value::known(self.x)),
+        )?;
+        let chip = U64Chip::construct(config.range);
+        if self.bits == BITS {
+            chip.range_check(layouter.namespace(|| "range"), &x)?;
+        } else {
+            chip.range_check_bits(layouter.namespace(|| "range"), &x, self.bits)?;
+        }
+        Ok(())
+    }
+}
+
+/// Lays out a 64-bit running sum of arbitrary `z_0..=z_64`.
+#[derive(Debug, Default)]
+struct ForgedCircuit {
+    z: Vec<Fp>,
+}
+
+impl ForgedCircuit {
+    /// The running sum the chip would lay out for `x`.
+    fn honest(x: u64) -> Self {
+        ForgedCircuit {
+            z: (0..=BITS)
+                .map(|i| Fp::from(x.checked_shr(i as u32).unwrap_or(0)))
+                .collect(),
+        }
+    }
+}
+
+impl Circuit<Fp> for ForgedCircuit {
+    type Config = Config;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            z: vec![Fp::zero(); BITS + 1],
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>) -> Config {
+        configure(meta)
+    }
+
+    fn synthesize(&self, config: Config, mut layouter: impl Layouter<Fp>) -> Result<(), Error> {
+        let cfg = config.range;
+        layouter.assign_region(
+            || "forged running sum",
+            |mut region| {
+                for (row, z) in self.z.iter().enumerate() {
+                    if row < BITS {
+                        cfg.s_bit.enable(&mut region, row)?;
+                    } else {
+                        cfg.s_end.enable(&mut region, row)?;
+                    }
+                    region.assign_advice(|| "z", cfg.z, row, || Value::known(*z))?;
+                }
+                Ok(())
+            },
+        )
+    }
+}
+
+const K: u32 = 7;
+
+fn failures(circuit: &impl Circuit<Fp>) -> Vec<String> {
+    let prover = MockProver::run(K, circuit, vec![]).unwrap();
+    let mut names: Vec<_> = prover
+        .verify()
+        .err()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|failure| match failure {
+            VerifyFailure::ConstraintNotSatisfied { constraint, .. } => {
+                // "Constraint i ('name') in gate j ('gate')"
+                let constraint = constraint.to_string();
+                constraint.rsplit('\'').nth(1).unwrap().to_string()
+            }
+            other => other.to_string(),
+        })
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+fn accepts(x: Fp, bits: usize) -> bool {
+    failures(&RangeCircuit { x, bits }).is_empty()
+}
+
+#[test]
+fn accepts_exactly_the_values_below_2_pow_64() {
+    for x in [0, 1, 2, 1 << 32, 1 << 63, u64::MAX - 1, u64::MAX] {
+        assert!(accepts(Fp::from(x), BITS), "{}", x);
+    }
+    let two_pow_64 = Fp::from_u128(1 << 64);
+    for x in [
+        two_pow_64,
+        two_pow_64 + Fp::one(),
+        Fp::from_u128(u128::MAX),
+        -Fp::one(),
+        -Fp::from(u64::MAX),
+    ] {
+        assert!(!accepts(x, BITS), "{:?}", x);
+    }
+}
+
+#[test]
+fn fewer_bits_give_a_tighter_bound() {
+    for bits in [0, 1, 8, 33, 63] {
+        let bound = 1u128 << bits;
+        assert!(accepts(Fp::from_u128(bound - 1), bits), "2^{} - 1", bits);
+        assert!(!accepts(Fp::from_u128(bound), bits), "2^{}", bits);
+    }
+}
+
+#[test]
+fn forged_running_sums_are_rejected() {
+    assert_eq!(
+        failures(&ForgedCircuit::honest(u64::MAX)),
+        Vec::<String>::new()
+    );
+    assert_eq!(failures(&ForgedCircuit::honest(5)), Vec::<String>::new());
+
+    // A "bit" of 3: 5 = 3 + 2·1.
+    let mut forged = ForgedCircuit::honest(5);
+    forged.z[1] = Fp::one();
+    assert_eq!(failures(&forged), ["u64 bit"]);
+
+    // A bit flipped in the middle of the sum.
+    let mut forged = ForgedCircuit::honest(u64::MAX);
+    forged.z[32] -= Fp::one();
+    assert_eq!(failures(&forged), ["u64 bit"]);
+
+    // 2^64 halved 64 times, every step a valid bit of 0, ends at 1.
+    let half = Fp::from(2).invert().unwrap();
+    let mut z = vec![Fp::from_u128(1 << 64)];
+    for i in 0..BITS {
+        z.push(z[i] * half);
+    }
+    assert_eq!(z[BITS], Fp::one());
+    assert_eq!(failures(&ForgedCircuit { z: z.clone() }), ["u64 end"]);
+
+    // Ending at 0 instead leaves the last step a "bit" of 2.
+    z[BITS] = Fp::zero();
+    assert_eq!(failures(&ForgedCircuit { z }), ["u64 bit"]);
+}
+
+#[test]
+fn to_u64_round_trips_the_boundaries() {
+    for x in [0, 1, u64::MAX] {
+        assert_eq!(to_u64(Fp::from(x)), Some(x));
+    }
+    assert_eq!(to_u64(Fp::from_u128(1 << 64)), None);
+    assert_eq!(to_u64(Fp::from_u128(u128::MAX)), None);
+    assert_eq!(to_u64(-Fp::one()), None);
+}
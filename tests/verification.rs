@@ -0,0 +1,137 @@
+use std::{fs, path::PathBuf};
+
+use fibonacci_circuit::{
+    bundle::{ProofBundle, BUNDLE_VERSION},
+    params,
+    verification::{verify, Status, Verification},
+};
+use halo2_proofs::{pasta::EqAffine, poly::commitment::Params};
+
+fn golden() -> (ProofBundle, Params<EqAffine>) {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/golden");
+    let bundle = ProofBundle::from_bytes(
+        &fs::read(dir.join(format!("bundle-v{}.fibb", BUNDLE_VERSION))).unwrap(),
+    )
+    .unwrap();
+    let params = params::from_bytes(&fs::read(dir.join("params-k4.bin")).unwrap()).unwrap();
+    (bundle, params)
+}
+
+fn names(verification: &Verification) -> Vec<(&str, bool)> {
+    verification
+        .checks
+        .iter()
+        .map(|check| (check.name, check.passed))
+        .collect()
+}
+
+#[test]
+fn valid_bundle_passes_every_check() {
+    let (bundle, params) = golden();
+    let verification = verify(&bundle.envelope, Some(&bundle), &params);
+
+    assert_eq!(verification.status, Status::Valid);
+    assert!(verification.valid);
+    assert_eq!(verification.reason, None);
+    assert_eq!(verification.circuit.as_deref(), Some("fib-three-column"));
+    assert_eq!(
+        names(&verification),
+        [
+            ("parameters", true),
+            ("statement", true),
+            ("bundled key", true),
+            ("fingerprint", true),
+            ("proof", true)
+        ]
+    );
+}
+
+#[test]
+fn envelope_skips_the_bundle_checks() {
+    let (bundle, params) = golden();
+    let verification = verify(&bundle.envelope, None, &params);
+    assert_eq!(
+        names(&verification),
+        [("statement", true), ("fingerprint", true), ("proof", true)]
+    );
+}
+
+#[test]
+fn tampered_proof_is_invalid() {
+    let (mut bundle, params) = golden();
+    let last = bundle.envelope.proof.len() - 5;
+    bundle.envelope.proof[last] ^= 1;
+    let verification = verify(&bundle.envelope, None, &params);
+
+    assert_eq!(verification.status, Status::Invalid);
+    assert!(!verification.valid);
+    assert_eq!(
+        names(&verification),
+        [("statement", true), ("fingerprint", true), ("proof", false)]
+    );
+    assert!(verification
+        .reason
+        .unwrap()
+        .starts_with("proof: rejected by the verifier"));
+}
+
+#[test]
+fn first_failed_check_stops_verification() {
+    let (mut bundle, params) = golden();
+    bundle.envelope.fingerprint[0] ^= 1;
+    let verification = verify(&bundle.envelope, Some(&bundle), &params);
+    assert_eq!(verification.status, Status::Invalid);
+    assert_eq!(
+        names(&verification),
+        [
+            ("parameters", true),
+            ("statement", true),
+            ("bundled key", true),
+            ("fingerprint", false)
+        ]
+    );
+    assert_eq!(verification.timings.verify_ms, 0.0);
+
+    let (bundle, _) = golden();
+    let verification = verify(
+        &bundle.envelope,
+        Some(&bundle),
+        &params::generate(5).unwrap(),
+    );
+    assert_eq!(names(&verification), [("parameters", false)]);
+}
+
+#[test]
+fn proof_for_another_n_is_invalid() {
+    let (mut bundle, params) = golden();
+    bundle.envelope.n = 100;
+    let verification = verify(&bundle.envelope, None, &params);
+    assert_eq!(verification.status, Status::Invalid);
+    assert_eq!(names(&verification), [("statement", false)]);
+    assert_eq!(
+        verification.reason.unwrap(),
+        "statement: envelope claims n = 100, fib-three-column proves n = 10"
+    );
+}
+
+#[test]
+fn unknown_circuit_is_an_error() {
+    let (mut bundle, params) = golden();
+    bundle.envelope.circuit = "fib-four-column".to_string();
+    let verification = verify(&bundle.envelope, None, &params);
+    assert_eq!(verification.status, Status::Error);
+    assert!(verification.checks.is_empty());
+}
+
+#[test]
+fn json_names_the_status() {
+    let (bundle, params) = golden();
+    let json = serde_json::to_value(verify(&bundle.envelope, None, &params)).unwrap();
+    assert_eq!(json["status"], "valid");
+    assert_eq!(json["valid"], true);
+    assert!(json["timings"]["verify_ms"].as_f64().unwrap() > 0.0);
+
+    let json = serde_json::to_value(Verification::error("no such file")).unwrap();
+    assert_eq!(json["status"], "error");
+    assert_eq!(json["reason"], "no such file");
+}
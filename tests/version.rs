@@ -0,0 +1,60 @@
+use fibonacci_circuit::{
+    fibonacci::{three_column::FiboCircuit, NUM_TERMS},
+    fingerprint::fingerprint,
+    gadgets::version::{self, Versioned, CIRCUIT_VERSION},
+    prover,
+    reference::fib_field,
+};
+use halo2_proofs::{circuit::Value, dev::MockProver, pasta::Fp, poly::commitment::Params};
+
+const K: u32 = 4;
+
+fn versioned(version: u64) -> Versioned<FiboCircuit<Fp>> {
+    Versioned {
+        circuit: FiboCircuit {
+            a: Value::known(Fp::one()),
+            b: Value::known(Fp::one()),
+        },
+        version,
+    }
+}
+
+fn instances(version: u64) -> Vec<Vec<Fp>> {
+    let last = fib_field(Fp::one(), Fp::one(), NUM_TERMS);
+    version::instances(vec![vec![last]], version)
+}
+
+#[test]
+fn version_row_must_match_the_fixed_constant() {
+    let circuit = versioned(CIRCUIT_VERSION);
+    let prover = MockProver::run(K, &circuit, instances(CIRCUIT_VERSION)).unwrap();
+    assert_eq!(prover.verify(), Ok(()));
+
+    let prover = MockProver::run(K, &circuit, instances(CIRCUIT_VERSION + 1)).unwrap();
+    assert!(prover.verify().is_err());
+}
+
+#[test]
+fn versions_have_different_keys() {
+    let params = Params::new(K);
+    let v1 = prover::keygen_vk(&params, &versioned(1)).unwrap();
+    let v2 = prover::keygen_vk(&params, &versioned(2)).unwrap();
+    let again = prover::keygen_vk(&params, &versioned(1)).unwrap();
+    assert_ne!(fingerprint(&v1), fingerprint(&v2));
+    assert_eq!(fingerprint(&v1), fingerprint(&again));
+}
+
+#[test]
+fn proof_of_an_upgrade_fails_the_old_key() {
+    let params = Params::new(K);
+    let old = prover::keygen(&params, &versioned(1)).unwrap();
+    let new = prover::keygen(&params, &versioned(2)).unwrap();
+    let proof = prover::create_proof(&params, &new, versioned(2), &instances(2)).unwrap();
+
+    prover::verify_proof(&params, new.get_vk(), &proof, &instances(2)).unwrap();
+    // Claiming either version, the old key rejects it.
+    assert!(prover::verify_proof(&params, old.get_vk(), &proof, &instances(2)).is_err());
+    assert!(prover::verify_proof(&params, old.get_vk(), &proof, &instances(1)).is_err());
+    // And the new key rejects a claim of the old version.
+    assert!(prover::verify_proof(&params, new.get_vk(), &proof, &instances(1)).is_err());
+}
@@ -0,0 +1,189 @@
+//! The VM against executions it must reject. [`Forged`] loads the real
+//! program into the ROM but lays out whatever program and trace it is
+//! given, so each test can break one thing and check the constraint meant
+//! to catch it is the one that fails.
+
+use fibonacci_circuit::circuits::vm::{
+    isa::{self, Instruction, Step},
+    VmChip, VmConfig,
+};
+use halo2_proofs::{
+    circuit::{Layouter, SimpleFloorPlanner},
+    dev::{MockProver, VerifyFailure},
+    pasta::Fp,
+    plonk::{Circuit, ConstraintSystem, Error},
+};
+
+const K: u32 = 6;
+const ROWS: usize = 25;
+const ITERATIONS: u64 = 4;
+
+#[derive(Debug, Clone)]
+struct Forged {
+    /// The program loaded into the ROM.
+    rom: Vec<Instruction>,
+    /// The program each row's instruction is taken from.
+    executed: Vec<Instruction>,
+    trace: Vec<Step<Fp>>,
+    rows: usize,
+}
+
+impl Forged {
+    /// The honest execution of the Fibonacci program.
+    fn honest() -> Self {
+        let program = isa::fibonacci_program(ITERATIONS);
+        let trace = isa::execute(&program, ROWS).unwrap();
+        Forged {
+            rom: program.clone(),
+            executed: program,
+            trace,
+            rows: ROWS,
+        }
+    }
+
+    /// Runs `executed` natively for its trace, leaving the ROM alone.
+    fn executing(executed: Vec<Instruction>) -> Self {
+        let trace = isa::execute(&executed, ROWS).unwrap();
+        Forged {
+            executed,
+            trace,
+            ..Self::honest()
+        }
+    }
+
+    /// The gates and lookups that fail, by name.
+    fn failures(&self) -> Vec<String> {
+        let r1 = self.trace.last().unwrap().registers[1];
+        let prover = MockProver::run(K, self, vec![vec![r1]]).unwrap();
+        let mut names: Vec<_> = prover
+            .verify()
+            .err()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|failure| match failure {
+                VerifyFailure::ConstraintNotSatisfied { constraint, .. } => {
+                    // "Constraint i ('name') in gate j ('gate')"
+                    let constraint = constraint.to_string();
+                    constraint.rsplit('\'').nth(1).unwrap().to_string()
+                }
+                VerifyFailure::Lookup { .. } => "lookup".to_string(),
+                other => other.to_string(),
+            })
+            .collect();
+        names.sort();
+        names.dedup();
+        names
+    }
+}
+
+impl Circuit<Fp> for Forged {
+    type Config = VmConfig<Fp>;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        self.clone()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+        let instance = meta.instance_column();
+        VmChip::configure(meta, instance)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<Fp>,
+    ) -> Result<(), Error> {
+        let chip = VmChip::construct(config);
+        chip.load_program(layouter.namespace(|| "program"), &self.rom)?;
+        let r1 = chip.assign(
+            layouter.namespace(|| "execute"),
+            &self.executed,
+            &self.trace,
+            self.rows,
+        )?;
+        chip.expose_public(layouter.namespace(|| "expose r1"), r1, 0)
+    }
+}
+
+#[test]
+fn honest_execution_verifies() {
+    assert_eq!(Forged::honest().failures(), Vec::<String>::new());
+}
+
+#[test]
+fn instructions_outside_the_rom_fail_the_fetch() {
+    let program = isa::fibonacci_program(ITERATIONS);
+    let forgeries = [
+        // Another opcode with the same effect: r0 = r0 + r0 = 0.
+        (0, Instruction::add(0, 0, 0)),
+        // Another destination register, overwritten before it is read.
+        (0, Instruction::mov(3, 0)),
+        // Another immediate.
+        (1, Instruction::mov(1, 2)),
+    ];
+    for (pc, instruction) in forgeries {
+        let mut executed = program.clone();
+        executed[pc] = instruction;
+        assert_eq!(
+            Forged::executing(executed).failures(),
+            ["lookup"],
+            "{} at pc {}",
+            instruction,
+            pc
+        );
+    }
+}
+
+/// The row of the first taken `JNZ`, the jump back to the loop.
+fn first_jump(trace: &[Step<Fp>]) -> usize {
+    trace.windows(2).position(|w| w[1].pc < w[0].pc).unwrap()
+}
+
+#[test]
+fn wrong_pc_transition_is_rejected() {
+    // pc 0 steps to 2, skipping `MOV r1, 1`.
+    let mut forged = Forged::honest();
+    forged.trace[1].pc = 2;
+    let failures = forged.failures();
+    assert!(failures.contains(&"vm pc".to_string()), "{:?}", failures);
+    assert!(!failures.contains(&"lookup".to_string()));
+}
+
+#[test]
+fn wrong_branch_target_is_rejected() {
+    let honest = Forged::honest();
+    let jump = first_jump(&honest.trace);
+    assert_eq!(honest.trace[jump + 1].pc, 4);
+
+    // Taken, but landing one instruction past the target.
+    let mut forged = honest.clone();
+    forged.trace[jump + 1].pc = 5;
+    let failures = forged.failures();
+    assert!(failures.contains(&"vm pc".to_string()), "{:?}", failures);
+
+    // Not taken on the last iteration, but jumping anyway.
+    let mut forged = honest;
+    let fall_through = forged.trace.len() - 1;
+    assert_eq!(forged.trace[fall_through].pc, 8);
+    forged.trace[fall_through].pc = 4;
+    // No rows repeat the last step, so only the JNZ row's transition is
+    // left to break the pc gate.
+    forged.rows = forged.trace.len();
+    let failures = forged.failures();
+    assert!(failures.contains(&"vm pc".to_string()), "{:?}", failures);
+}
+
+#[test]
+fn trace_without_the_final_halt_is_rejected() {
+    // Stop one step short: the last row is the JNZ falling through.
+    let mut forged = Forged::honest();
+    forged.trace.pop();
+    forged.rows = forged.trace.len();
+    assert_eq!(forged.failures(), ["vm end"]);
+
+    // The same trace padded to the full rows repeats the JNZ instead.
+    forged.rows = ROWS;
+    let failures = forged.failures();
+    assert!(failures.contains(&"vm end".to_string()), "{:?}", failures);
+}
@@ -0,0 +1,435 @@
+use std::fmt;
+
+use fibonacci_circuit::{
+    copies::CopyReport,
+    coverage::{CoverageIssue, CoverageReport},
+    fibonacci::{
+        nth_term,
+        three_column::{FiboCircuit, FiboWitness},
+        NUM_TERMS,
+    },
+    introspect::{
+        witness::{CellRef, Witness},
+        ColumnKind, ColumnRef,
+    },
+    registry::{CircuitKind, CircuitVisitor},
+    unconstrained::UnconstrainedReport,
+};
+use halo2_proofs::{
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    pasta::Fp,
+    plonk::{Advice, Circuit, Column, ConstraintSystem, Error, Selector},
+    poly::Rotation,
+};
+
+/// Larger than any demo circuit needs.
+const MAX_K: u32 = 11;
+
+/// The demo witness of a registered circuit at the smallest `k` it fits.
+struct Synthesize;
+
+impl CircuitVisitor for Synthesize {
+    type Output = Witness;
+
+    fn visit<C: Circuit<Fp> + fmt::Debug>(self, circuit: C, instances: Vec<Vec<Fp>>) -> Witness {
+        smallest(&circuit, instances)
+    }
+}
+
+fn smallest<C: Circuit<Fp>>(circuit: &C, instances: Vec<Vec<Fp>>) -> Witness {
+    (1..=MAX_K)
+        .find_map(|k| Witness::synthesize(k, circuit, instances.clone()).ok())
+        .expect("the circuit fits in MAX_K")
+}
+
+/// Two syntheses of the same demo circuit, at the smallest `k` it fits.
+struct SynthesizeTwice;
+
+impl CircuitVisitor for SynthesizeTwice {
+    type Output = (Witness, Witness);
+
+    fn visit<C: Circuit<Fp> + fmt::Debug>(
+        self,
+        circuit: C,
+        instances: Vec<Vec<Fp>>,
+    ) -> (Witness, Witness) {
+        let first = smallest(&circuit, instances.clone());
+        let second = Witness::synthesize(first.k, &circuit, instances).unwrap();
+        (first, second)
+    }
+}
+
+/// `a + b = c` on row 0, with a second row assigned but never enabled.
+#[derive(Default)]
+struct Dangling;
+
+impl Circuit<Fp> for Dangling {
+    type Config = ([Column<Advice>; 3], Selector);
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Dangling
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+        let advice = [(); 3].map(|_| meta.advice_column());
+        let s = meta.selector();
+        meta.create_gate("sum", |meta| {
+            let [a, b, c] = advice.map(|column| meta.query_advice(column, Rotation::cur()));
+            vec![meta.query_selector(s) * (a + b - c)]
+        });
+        (advice, s)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<Fp>,
+    ) -> Result<(), Error> {
+        let (advice, s) = config;
+        layouter.assign_region(
+            || "sum",
+            |mut region| {
+                s.enable(&mut region, 0)?;
+                for row in 0..2 {
+                    for (column, name) in advice.iter().zip(["a", "b", "c"]) {
+                        region.assign_advice(|| name, *column, row, || Value::known(Fp::one()))?;
+                    }
+                }
+                Ok(())
+            },
+        )
+    }
+}
+
+/// `a + b = c` on one row, with an `a * b = c` gate nothing enables.
+struct DeadGate;
+
+impl Circuit<Fp> for DeadGate {
+    type Config = ([Column<Advice>; 3], Selector);
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        DeadGate
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+        let advice = [(); 3].map(|_| meta.advice_column());
+        let [add, mul] = [(); 2].map(|_| meta.selector());
+        meta.create_gate("add", |meta| {
+            let [a, b, c] = advice.map(|column| meta.query_advice(column, Rotation::cur()));
+            vec![meta.query_selector(add) * (a + b - c)]
+        });
+        meta.create_gate("mul", |meta| {
+            let [a, b, c] = advice.map(|column| meta.query_advice(column, Rotation::cur()));
+            vec![meta.query_selector(mul) * (a * b - c)]
+        });
+        (advice, add)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<Fp>,
+    ) -> Result<(), Error> {
+        let (advice, add) = config;
+        layouter.assign_region(
+            || "add",
+            |mut region| {
+                add.enable(&mut region, 0)?;
+                for (column, value) in advice.iter().zip([1, 1, 2]) {
+                    region.assign_advice(|| "", *column, 0, || Value::known(Fp::from(value)))?;
+                }
+                Ok(())
+            },
+        )
+    }
+}
+
+fn three_column() -> (FiboCircuit<Fp>, Vec<Vec<Fp>>) {
+    let circuit = FiboCircuit {
+        a: Value::known(Fp::one()),
+        b: Value::known(Fp::one()),
+    };
+    let output = nth_term(Fp::one(), Fp::one(), NUM_TERMS);
+    (circuit, vec![vec![output]])
+}
+
+/// Four `a + b = c` steps, one region each, the last never enabled.
+#[derive(Default)]
+struct LastStepOff;
+
+impl Circuit<Fp> for LastStepOff {
+    type Config = ([Column<Advice>; 3], Selector);
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        LastStepOff
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+        Dangling::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<Fp>,
+    ) -> Result<(), Error> {
+        let (advice, s) = config;
+        for step in 0..4 {
+            layouter.assign_region(
+                || "step",
+                |mut region| {
+                    if step < 3 {
+                        s.enable(&mut region, 0)?;
+                    }
+                    for (column, value) in advice.iter().zip([1, 1, 2]) {
+                        region.assign_advice(
+                            || "x",
+                            *column,
+                            0,
+                            || Value::known(Fp::from(value)),
+                        )?;
+                    }
+                    Ok(())
+                },
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Assigns how many times it has been synthesized.
+#[derive(Default)]
+struct Counter(std::cell::Cell<u64>);
+
+impl Circuit<Fp> for Counter {
+    type Config = ([Column<Advice>; 3], Selector);
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Counter::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+        Dangling::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<Fp>,
+    ) -> Result<(), Error> {
+        let (advice, _) = config;
+        self.0.set(self.0.get() + 1);
+        layouter.assign_region(
+            || "count",
+            |mut region| {
+                let count = Value::known(Fp::from(self.0.get()));
+                region.assign_advice(|| "count", advice[0], 0, || count)?;
+                Ok(())
+            },
+        )
+    }
+}
+
+#[test]
+fn observes_each_region_as_it_is_assigned() {
+    let (circuit, instances) = three_column();
+    let mut seen = vec![];
+    let witness = Witness::synthesize_with(4, &circuit, instances, |so_far, region| {
+        // The region is the last one and nothing after it is assigned yet.
+        assert_eq!(so_far.regions.last(), Some(region));
+        let (_, last) = region.rows.unwrap();
+        assert!(so_far
+            .advice
+            .iter()
+            .all(|column| column[last + 1].is_none()));
+        seen.push(region.name.clone());
+    })
+    .unwrap();
+
+    assert_eq!(seen.len(), witness.regions.len());
+    assert_eq!(seen[0], "first row");
+    assert!(seen[1..].iter().all(|name| name == "next row"));
+}
+
+#[test]
+fn copy_cycles_chain_the_three_column_rows() {
+    let (circuit, instances) = three_column();
+    let witness = Witness::synthesize(4, &circuit, instances).unwrap();
+    let cell = |kind, index, row| CellRef {
+        column: ColumnRef { kind, index },
+        row,
+    };
+    let advice = |index, row| cell(ColumnKind::Advice, index, row);
+    let cycles = witness.copy_cycles();
+
+    // `b` of row 0 becomes `a` of row 1, and each `c` becomes the next
+    // row's `b` and the row after's `a`.
+    assert!(cycles.contains(&vec![advice(0, 1), advice(1, 0)]));
+    for row in 0..6 {
+        assert!(cycles.contains(&vec![
+            advice(0, row + 2),
+            advice(1, row + 1),
+            advice(2, row)
+        ]));
+    }
+    // The last `c` is the public output.
+    let last = NUM_TERMS - 3;
+    assert!(cycles.contains(&vec![advice(2, last), cell(ColumnKind::Instance, 0, 0)]));
+
+    let report = CopyReport::of("fib-three-column", &witness, None);
+    assert!(report.consistent());
+    assert_eq!(report.groups.len(), witness.regions.len());
+}
+
+#[test]
+fn flags_cells_no_gate_reaches() {
+    let witness = Witness::synthesize(4, &Dangling, vec![]).unwrap();
+    let report = UnconstrainedReport::of("dangling", &witness);
+
+    assert!(!report.passed());
+    let cells: Vec<_> = report.cells.iter().map(|cell| cell.cell.as_str()).collect();
+    assert_eq!(cells, ["A0@1", "A1@1", "A2@1"]);
+    assert_eq!(report.cells[0].region_name, "sum");
+    assert_eq!(report.cells[0].annotation, "a");
+}
+
+#[test]
+fn every_circuit_is_fully_constrained() {
+    for kind in CircuitKind::ALL {
+        let witness = kind.visit(Synthesize);
+        let report = UnconstrainedReport::of(kind.name(), &witness);
+        assert!(report.passed(), "{}", report);
+    }
+}
+
+#[test]
+fn flags_a_step_left_disabled() {
+    let witness = Witness::synthesize(4, &LastStepOff, vec![]).unwrap();
+    let report = CoverageReport::of("last-step-off", &witness);
+
+    assert_eq!(report.selectors[0].rows, [(0, 2)]);
+    assert_eq!(
+        report.issues,
+        [CoverageIssue::UnlikeSiblings {
+            region: 3,
+            name: "step".to_string(),
+            enabled: vec![],
+            expected: vec![(0, 0)],
+        }]
+    );
+}
+
+#[test]
+fn flags_a_row_left_disabled_in_its_region() {
+    let witness = Witness::synthesize(4, &Dangling, vec![]).unwrap();
+    let report = CoverageReport::of("dangling", &witness);
+
+    assert_eq!(
+        report.issues,
+        [CoverageIssue::UncoveredRow {
+            region: 0,
+            name: "sum".to_string(),
+            row: 1,
+        }]
+    );
+}
+
+#[test]
+fn flags_a_gate_nothing_enables() {
+    let witness = Witness::synthesize(4, &DeadGate, vec![]).unwrap();
+    let report = CoverageReport::of("dead-gate", &witness);
+
+    let rows: Vec<_> = report.gates.iter().map(|gate| gate.rows).collect();
+    assert_eq!(rows, [1, 0]);
+    assert_eq!(
+        report.issues,
+        [CoverageIssue::DeadGate {
+            gate: 1,
+            name: "mul".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn every_circuit_covers_its_rows() {
+    for kind in CircuitKind::ALL {
+        let witness = kind.visit(Synthesize);
+        let mut report = CoverageReport::of(kind.name(), &witness);
+        // Dead gates are checked below.
+        report
+            .issues
+            .retain(|issue| !matches!(issue, CoverageIssue::DeadGate { .. }));
+        assert!(report.passed(), "{}", report);
+    }
+}
+
+#[test]
+fn only_unused_chip_operations_are_dead_gates() {
+    let mut dead = vec![];
+    for kind in CircuitKind::ALL {
+        let witness = kind.visit(Synthesize);
+        for issue in CoverageReport::of(kind.name(), &witness).issues {
+            if let CoverageIssue::DeadGate { name, .. } = issue {
+                dead.push(format!("{}: {}", kind, name));
+            }
+        }
+    }
+    // Catalan multiplies and divides with `ArithmeticChip`, never adds.
+    assert_eq!(dead, ["catalan: add"]);
+}
+
+#[test]
+fn notices_a_witness_that_changes_between_syntheses() {
+    let circuit = Counter::default();
+    let first = Witness::synthesize(4, &circuit, vec![]).unwrap();
+    let second = Witness::synthesize(4, &circuit, vec![]).unwrap();
+
+    assert_eq!(first.difference(&first), None);
+    let difference = first.difference(&second).unwrap();
+    assert!(
+        difference.starts_with("region 0 `count` assigns A0@0"),
+        "{}",
+        difference
+    );
+}
+
+#[test]
+fn every_circuit_synthesizes_deterministically() {
+    for kind in CircuitKind::ALL {
+        let (first, second) = kind.visit(SynthesizeTwice);
+        if let Some(difference) = first.difference(&second) {
+            panic!("{} synthesized differently: {}", kind, difference);
+        }
+    }
+}
+
+#[test]
+fn three_column_assigns_the_precomputed_terms() {
+    let (circuit, instances) = three_column();
+    let terms: Vec<_> = FiboWitness::generate(circuit.a, circuit.b)
+        .terms
+        .into_iter()
+        .map(known)
+        .collect();
+    assert_eq!(terms.len(), NUM_TERMS);
+    assert_eq!(
+        terms[NUM_TERMS - 1],
+        nth_term(Fp::one(), Fp::one(), NUM_TERMS)
+    );
+
+    let witness = Witness::synthesize(4, &circuit, instances).unwrap();
+    let c = &witness.advice[2];
+    for (row, term) in terms[2..].iter().enumerate() {
+        assert_eq!(c[row], Some(*term));
+    }
+}
+
+fn known(value: Value<Fp>) -> Fp {
+    let mut known = None;
+    value.map(|v| known = Some(v));
+    known.expect("a known value")
+}